@@ -0,0 +1,180 @@
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+use syn::{Attribute, Lit, Meta, MetaNameValue, NestedMeta, WherePredicate};
+
+/// Options of a type, set with `#[rdc(...)]`.
+#[derive(Default)]
+pub struct TypeOptions {
+    /// Predicates of `#[rdc(bound = "...")]`, which replace the inferred bounds.
+    pub bound: Option<Punctuated<WherePredicate, Comma>>,
+    /// Names of `#[rdc(implements_shared = "...")]`, the shared Java interfaces a struct implements.
+    pub implements_shared: Vec<String>,
+}
+
+/// Options of a field, set with `#[rdc(...)]`.
+#[derive(Default)]
+pub struct FieldOptions {
+    /// `#[rdc(skip_bound)]` keeps the type of the field from adding bounds.
+    pub skip_bound: bool,
+    /// `#[rdc(format = "...")]` sets the text format of a date or time field.
+    pub format: Option<String>,
+    /// `#[rdc(timezone = "...")]` sets the time zone the format applies in.
+    pub timezone: Option<String>,
+    /// `#[rdc(example = "...")]` sets an example value of the field.
+    pub example: Option<String>,
+    /// `#[rdc(sql_type = "...")]` sets the SQL type of the column of the field.
+    pub sql_type: Option<String>,
+    /// `#[rdc(primary_key)]` makes the field a part of the primary key of its table.
+    pub primary_key: bool,
+}
+
+/// Options of an enum variant, set with `#[rdc(...)]`.
+#[derive(Default)]
+pub struct VariantOptions {
+    /// `#[rdc(display = "...")]` sets the label of the variant.
+    pub display: Option<String>,
+    /// `#[rdc(description = "...")]` sets the description of the variant.
+    pub description: Option<String>,
+    /// `#[rdc(display(de = "...", fr = "..."))]` sets the labels of the variant in the given locales.
+    pub translations: Vec<(String, String)>,
+}
+
+impl TypeOptions {
+    pub fn parse(attributes: &[Attribute]) -> Self {
+        let mut options = TypeOptions::default();
+        for item in rdc_items(attributes) {
+            match item {
+                NestedMeta::Meta(Meta::NameValue(name_value))
+                    if name_value.path.is_ident("bound") =>
+                {
+                    let predicates = string_value(&name_value, "bound")
+                        .parse_with(Punctuated::parse_terminated)
+                        .unwrap_or_else(|e| panic!("Invalid `#[rdc(bound)]`: {e}"));
+                    options.bound = Some(predicates);
+                }
+                NestedMeta::Meta(Meta::NameValue(name_value))
+                    if name_value.path.is_ident("implements_shared") =>
+                {
+                    let name = string_value(&name_value, "implements_shared").value();
+                    options.implements_shared.push(name);
+                }
+                _ => panic!(
+                    "Unsupported rdc attribute of a type, only `bound` and `implements_shared` are supported"
+                ),
+            }
+        }
+        options
+    }
+}
+
+impl FieldOptions {
+    pub fn parse(attributes: &[Attribute]) -> Self {
+        let mut options = FieldOptions::default();
+        for item in rdc_items(attributes) {
+            match item {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip_bound") => {
+                    options.skip_bound = true
+                }
+                NestedMeta::Meta(Meta::NameValue(name_value)) if name_value.path.is_ident("format") => {
+                    options.format = Some(string_value(&name_value, "format").value())
+                }
+                NestedMeta::Meta(Meta::NameValue(name_value))
+                    if name_value.path.is_ident("timezone") =>
+                {
+                    options.timezone = Some(string_value(&name_value, "timezone").value())
+                }
+                NestedMeta::Meta(Meta::NameValue(name_value))
+                    if name_value.path.is_ident("example") =>
+                {
+                    options.example = Some(string_value(&name_value, "example").value())
+                }
+                NestedMeta::Meta(Meta::NameValue(name_value))
+                    if name_value.path.is_ident("sql_type") =>
+                {
+                    options.sql_type = Some(string_value(&name_value, "sql_type").value())
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("primary_key") => {
+                    options.primary_key = true
+                }
+                _ => panic!(
+                    "Unsupported rdc attribute of a field, only `skip_bound`, `format`, `timezone`, `example`, `sql_type` and `primary_key` are supported"
+                ),
+            }
+        }
+        if options.timezone.is_some() && options.format.is_none() {
+            panic!("`#[rdc(timezone)]` needs `#[rdc(format)]`");
+        }
+        options
+    }
+
+    /// Returns whether options other than `skip_bound` are set, which only struct fields support.
+    pub fn has_struct_options(&self) -> bool {
+        self.format.is_some()
+            || self.example.is_some()
+            || self.sql_type.is_some()
+            || self.primary_key
+    }
+}
+
+impl VariantOptions {
+    pub fn parse(attributes: &[Attribute]) -> Self {
+        let mut options = VariantOptions::default();
+        for item in rdc_items(attributes) {
+            match item {
+                NestedMeta::Meta(Meta::NameValue(name_value))
+                    if name_value.path.is_ident("display") =>
+                {
+                    options.display = Some(string_value(&name_value, "display").value())
+                }
+                NestedMeta::Meta(Meta::NameValue(name_value))
+                    if name_value.path.is_ident("description") =>
+                {
+                    options.description = Some(string_value(&name_value, "description").value())
+                }
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("display") => {
+                    for nested in list.nested {
+                        match nested {
+                            NestedMeta::Meta(Meta::NameValue(name_value)) => {
+                                let locale = name_value
+                                    .path
+                                    .get_ident()
+                                    .map(|ident| ident.to_string())
+                                    .unwrap_or_else(|| panic!("Expected a locale, like `de`"));
+                                let label = string_value(&name_value, "display").value();
+                                options.translations.push((locale, label));
+                            }
+                            _ => panic!("Expected `#[rdc(display(de = \"...\"))]`"),
+                        }
+                    }
+                }
+                _ => panic!(
+                    "Unsupported rdc attribute of a variant, only `display` and `description` are supported"
+                ),
+            }
+        }
+        options
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.display.is_none() && self.description.is_none() && self.translations.is_empty()
+    }
+}
+
+/// Returns the items of all the `#[rdc(...)]` attributes.
+fn rdc_items(attributes: &[Attribute]) -> Vec<NestedMeta> {
+    attributes
+        .iter()
+        .filter(|attr| attr.path.is_ident("rdc"))
+        .flat_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => list.nested.into_iter().collect::<Vec<NestedMeta>>(),
+            _ => panic!("Expected `#[rdc(...)]`"),
+        })
+        .collect()
+}
+
+fn string_value(name_value: &MetaNameValue, key: &str) -> syn::LitStr {
+    match &name_value.lit {
+        Lit::Str(lit_str) => lit_str.clone(),
+        _ => panic!("`#[rdc({key})]` expects a string, like `#[rdc({key} = \"...\")]`"),
+    }
+}
@@ -0,0 +1,104 @@
+use crate::attributes::{FieldOptions, TypeOptions};
+use crate::utils::is_phantom_data;
+use proc_macro2::Ident;
+use syn::{
+    parse_quote, DeriveInput, Field, GenericArgument, Generics, Path, PathArguments, ReturnType,
+    Type, TypeParamBound, WherePredicate,
+};
+
+/// Returns the types of the fields which add bounds, i.e. the generated ones not marked with `#[rdc(skip_bound)]`.
+pub fn bounded_types<'a>(fields: impl Iterator<Item = &'a Field>) -> Vec<&'a Type> {
+    fields
+        .filter(|field| {
+            !FieldOptions::parse(&field.attrs).skip_bound && !is_phantom_data(&field.ty)
+        })
+        .map(|field| &field.ty)
+        .collect()
+}
+
+/// Returns the generics of the implementations with the bounds they need, and the type parameters used by the given
+/// field types in declaration order, which are a part of the type name.
+///
+/// Used type parameters get an `rdc::RDCType` bound, unless `#[rdc(bound = "...")]` replaces these bounds.
+/// Other type parameters only get `'static`, which `rdc::RDCType` requires of the implementing type.
+pub fn add_bounds(input: &DeriveInput, field_types: &[&Type]) -> (Generics, Vec<Ident>) {
+    let mut generics = input.generics.clone();
+    let (used, unused): (Vec<Ident>, Vec<Ident>) = input
+        .generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .partition(|ident| field_types.iter().any(|t| mentions(t, ident)));
+    let bounds: Vec<WherePredicate> = match TypeOptions::parse(&input.attrs).bound {
+        Some(predicates) => predicates.into_iter().collect(),
+        None => used
+            .iter()
+            .map(|ident| parse_quote!(#ident: rdc::RDCType))
+            .collect(),
+    };
+    let predicates = &mut generics.make_where_clause().predicates;
+    predicates.extend(bounds);
+    predicates.extend(
+        unused
+            .iter()
+            .map(|ident| -> WherePredicate { parse_quote!(#ident: 'static) }),
+    );
+    (generics, used)
+}
+
+/// Returns whether the type mentions the type parameter, like `Vec<T>` or `T::Item` mention `T`.
+fn mentions(t: &Type, param: &Ident) -> bool {
+    match t {
+        Type::Path(tp) => {
+            tp.qself
+                .as_ref()
+                .is_some_and(|qself| mentions(&qself.ty, param))
+                || path_mentions(&tp.path, param)
+        }
+        Type::Reference(reference) => mentions(&reference.elem, param),
+        Type::Array(array) => mentions(&array.elem, param),
+        Type::Slice(slice) => mentions(&slice.elem, param),
+        Type::Ptr(ptr) => mentions(&ptr.elem, param),
+        Type::Paren(paren) => mentions(&paren.elem, param),
+        Type::Group(group) => mentions(&group.elem, param),
+        Type::Tuple(tuple) => tuple.elems.iter().any(|t| mentions(t, param)),
+        Type::TraitObject(object) => bounds_mention(object.bounds.iter(), param),
+        Type::ImplTrait(implemented) => bounds_mention(implemented.bounds.iter(), param),
+        _ => false,
+    }
+}
+
+fn path_mentions(path: &Path, param: &Ident) -> bool {
+    let is_param = path.leading_colon.is_none()
+        && path
+            .segments
+            .first()
+            .is_some_and(|segment| segment.ident == *param);
+    is_param
+        || path
+            .segments
+            .iter()
+            .any(|segment| match &segment.arguments {
+                PathArguments::None => false,
+                PathArguments::AngleBracketed(arguments) => {
+                    arguments.args.iter().any(|argument| match argument {
+                        GenericArgument::Type(t) => mentions(t, param),
+                        GenericArgument::Binding(binding) => mentions(&binding.ty, param),
+                        GenericArgument::Constraint(constraint) => {
+                            bounds_mention(constraint.bounds.iter(), param)
+                        }
+                        _ => false,
+                    })
+                }
+                PathArguments::Parenthesized(arguments) => {
+                    arguments.inputs.iter().any(|t| mentions(t, param))
+                        || matches!(&arguments.output, ReturnType::Type(_, t) if mentions(t, param))
+                }
+            })
+}
+
+fn bounds_mention<'a>(mut bounds: impl Iterator<Item = &'a TypeParamBound>, param: &Ident) -> bool {
+    bounds.any(|bound| match bound {
+        TypeParamBound::Trait(trait_bound) => path_mentions(&trait_bound.path, param),
+        TypeParamBound::Lifetime(_) => false,
+    })
+}
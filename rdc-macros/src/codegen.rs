@@ -0,0 +1,3 @@
+pub mod data_enums;
+pub mod structs;
+pub mod unit_enums;
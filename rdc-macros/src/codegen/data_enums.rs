@@ -0,0 +1,121 @@
+use crate::attributes::{FieldOptions, VariantOptions};
+use crate::bounds::{add_bounds, bounded_types};
+use crate::targets::java::implement_java_types;
+use crate::utils::{find_serde_rename, is_phantom_data};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DataEnum, DeriveInput, Field, Fields, FieldsNamed, FieldsUnnamed, Variant};
+
+pub fn generate_data_enum_code(input: &DeriveInput, data: &DataEnum) -> TokenStream {
+    let name = &input.ident;
+    let field_types = bounded_types(data.variants.iter().flat_map(|v| v.fields.iter()));
+    let (generics, type_params) = add_bounds(input, &field_types);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let variants = data.variants.iter().collect::<Vec<_>>();
+    let variants_code = variants.iter().map(|v| variant_code(v)).collect::<Vec<_>>();
+
+    let java_implements = implement_java_types(name, &generics, &type_params);
+    quote!(
+        impl #impl_generics rdc::codegen::GenerateIR for #name #ty_generics #where_clause {
+            fn add_to_ir(ir: &mut rdc::ir::IntermediateRepresentation) {
+                let custom_type = ir.target().resolve_custom_type::<Self>();
+                let type_name = custom_type.type_name();
+                let mut enum_ir = rdc::ir::DataEnum::new(
+                    rdc::ir::Name::from_pascal_case(type_name),
+                    custom_type,
+                    rdc::ir::DataEnumStyle::External,
+                );
+                #(#variants_code)*
+
+                ir.add_data_enum(enum_ir);
+            }
+        }
+
+        impl #impl_generics rdc::RDCType for #name #ty_generics #where_clause {}
+
+        #java_implements
+    )
+}
+
+fn variant_code(variant: &Variant) -> TokenStream {
+    if !VariantOptions::parse(&variant.attrs).is_empty() {
+        panic!("`#[rdc(display)]` and `#[rdc(description)]` are only supported on variants of unit enums");
+    }
+    for field in &variant.fields {
+        if FieldOptions::parse(&field.attrs).has_struct_options() {
+            panic!("`#[rdc(format)]`, `#[rdc(example)]`, `#[rdc(sql_type)]` and `#[rdc(primary_key)]` are only supported on struct fields");
+        }
+    }
+    let variant_name = variant.ident.to_string();
+    let json_name =
+        find_serde_rename(variant.attrs.iter()).unwrap_or_else(|| variant_name.to_string());
+    match variant.fields {
+        Fields::Unnamed(ref fields) => tuple_variant_code(&variant_name, &json_name, fields),
+        Fields::Named(ref fields) => object_variant_code(&variant_name, &json_name, fields),
+        Fields::Unit => unit_variant_code(&variant_name, &json_name),
+    }
+}
+
+fn unit_variant_code(variant_name: &str, json_name: &str) -> TokenStream {
+    quote!(
+        enum_ir.add_variant(
+            rdc::ir::DataEnumVariant::unit(
+                rdc::ir::Name::from_pascal_case(#variant_name),
+                #json_name.to_string(),
+            )
+        );
+    )
+}
+
+fn tuple_variant_code(variant_name: &str, json_name: &str, fields: &FieldsUnnamed) -> TokenStream {
+    let field_types = fields
+        .unnamed
+        .iter()
+        .map(|f| &f.ty)
+        .map(|t| quote!(rdc::ir::TypeTarget::Java.resolve_type::<#t>()));
+    let dependencies = fields
+        .unnamed
+        .iter()
+        .map(|f| &f.ty)
+        .map(|t| quote!(ir.add::<#t>();));
+    quote!(
+        enum_ir.add_variant(
+            rdc::ir::DataEnumVariant::tuple(
+                rdc::ir::Name::from_pascal_case(#variant_name),
+                #json_name.to_string(),
+                vec![#(#field_types),*],
+            )
+        );
+        #(#dependencies)*
+    )
+}
+
+fn object_variant_code(variant_name: &str, json_name: &str, fields: &FieldsNamed) -> TokenStream {
+    let fields = fields
+        .named
+        .iter()
+        .filter(|field| !is_phantom_data(&field.ty))
+        .map(|field: &Field| {
+            let field_name = field.ident.as_ref().unwrap().to_string();
+            let json_name =
+                find_serde_rename(field.attrs.iter()).unwrap_or_else(|| field_name.to_string());
+            let field_type = &field.ty;
+            quote!({
+                ir.add::<#field_type>();
+                rdc::ir::DataEnumObjectField::new(
+                    rdc::ir::Name::from_pascal_case(#field_name),
+                    #json_name.to_string(),
+                    rdc::ir::TypeTarget::Java.resolve_type::<#field_type>(),
+                )
+            })
+        });
+    quote!(
+        enum_ir.add_variant(
+            rdc::ir::DataEnumVariant::object(
+                rdc::ir::Name::from_pascal_case(#variant_name),
+                #json_name.to_string(),
+                vec![#(#fields),*],
+            )
+        );
+    )
+}
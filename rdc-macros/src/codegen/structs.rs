@@ -0,0 +1,192 @@
+use crate::attributes::{FieldOptions, TypeOptions};
+use crate::bounds::{add_bounds, bounded_types};
+use crate::targets::java::implement_java_types;
+use crate::utils::{find_serde_adapter, find_serde_rename, is_phantom_data};
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+use syn::{Attribute, DataStruct, DeriveInput, Field, Fields, Type, TypePath};
+
+pub fn generate_struct_code(input: &DeriveInput, data: &DataStruct) -> TokenStream {
+    let name = &input.ident;
+    let fields = match &data.fields {
+        Fields::Named(fields) => &fields.named,
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            return generate_newtype_code(input, &fields.unnamed[0])
+        }
+        Fields::Unnamed(_) => panic!("Tuple structs with more than one field are not supported"),
+        Fields::Unit => panic!("Unit structs are not supported"),
+    };
+    let (generics, type_params) = add_bounds(input, &bounded_types(fields.iter()));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let field_code = generate_fields_code(fields);
+    let shared_interfaces = TypeOptions::parse(&input.attrs).implements_shared;
+
+    let java_implements = implement_java_types(name, &generics, &type_params);
+
+    quote!(
+        impl #impl_generics rdc::codegen::GenerateIR for #name #ty_generics #where_clause {
+            fn add_to_ir(ir: &mut rdc::ir::IntermediateRepresentation) {
+                let custom_type = ir.target().resolve_custom_type::<Self>();
+                let type_name = custom_type.type_name();
+                let mut struct_ir = rdc::ir::Struct::new(
+                    rdc::ir::Name::from_pascal_case(type_name),
+                    custom_type,
+                );
+                #field_code
+                #(struct_ir.add_shared_interface(#shared_interfaces);)*
+                ir.add_struct(struct_ir);
+            }
+        }
+
+        impl #impl_generics rdc::RDCType for #name #ty_generics #where_clause {}
+
+        #java_implements
+    )
+}
+
+/// Maps a newtype, e.g. `struct Tags(HashMap<String, String>)`, to a transparent struct, like `rdc_newtype!` does.
+fn generate_newtype_code(input: &DeriveInput, field: &Field) -> TokenStream {
+    let name = &input.ident;
+    if FieldOptions::parse(&field.attrs).has_struct_options() {
+        panic!("`#[rdc(format)]`, `#[rdc(example)]`, `#[rdc(sql_type)]` and `#[rdc(primary_key)]` are not supported on newtypes");
+    }
+    if !TypeOptions::parse(&input.attrs)
+        .implements_shared
+        .is_empty()
+    {
+        panic!("`#[rdc(implements_shared)]` is not supported on newtypes");
+    }
+    let (generics, type_params) = add_bounds(input, &bounded_types(std::iter::once(field)));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let inner = &field.ty;
+
+    let java_implements = implement_java_types(name, &generics, &type_params);
+
+    quote!(
+        impl #impl_generics rdc::codegen::GenerateIR for #name #ty_generics #where_clause {
+            fn add_to_ir(ir: &mut rdc::ir::IntermediateRepresentation) {
+                let custom_type = ir.target().resolve_custom_type::<Self>();
+                let type_name = custom_type.type_name();
+                let mut newtype = rdc::ir::Struct::new(
+                    rdc::ir::Name::from_pascal_case(type_name),
+                    custom_type,
+                );
+                newtype.add_field(rdc::ir::Field::new(
+                    rdc::ir::Name::from_snake_case("value"),
+                    "value",
+                    ir.target().resolve_type::<#inner>(),
+                ));
+                newtype.set_transparent(true);
+                ir.add_struct(newtype);
+                ir.add::<#inner>();
+            }
+        }
+
+        impl #impl_generics rdc::RDCType for #name #ty_generics #where_clause {}
+
+        #java_implements
+    )
+}
+
+fn get_json_field_names(fields: &[&Field]) -> Vec<String> {
+    fields
+        .iter()
+        .map(|field: &&Field| {
+            let field_name = field.ident.as_ref().unwrap();
+            let serde_rename = find_serde_rename(field.attrs.iter());
+            serde_rename.unwrap_or_else(|| field_name.to_string())
+        })
+        .collect()
+}
+
+fn generate_fields_code(fields: &Punctuated<Field, Comma>) -> TokenStream {
+    let fields: Vec<&Field> = fields.iter().filter(|f| !is_phantom_data(&f.ty)).collect();
+    let field_names: Vec<String> = fields
+        .iter()
+        .map(|f| f.ident.as_ref().unwrap().to_string())
+        .collect();
+    let field_types: Vec<TypePath> = fields
+        .iter()
+        .map(|f| f.ty.clone())
+        .map(|t| match t {
+            Type::Path(tp) => tp,
+            _ => panic!("Unsupported type"),
+        })
+        .collect();
+    let resolved_types: Vec<TokenStream> = field_types
+        .iter()
+        .map(|t: &TypePath| quote!(rdc::ir::TypeTarget::Java.resolve_type::<#t>()))
+        .collect();
+    let dependencies: Vec<TokenStream> = field_types
+        .iter()
+        .map(|t: &TypePath| quote!(ir.add::<#t>()))
+        .collect();
+    let json_field_names = get_json_field_names(&fields);
+    let options: Vec<TokenStream> = fields
+        .iter()
+        .map(|f| field_options_code(&FieldOptions::parse(&f.attrs)))
+        .collect();
+    let adapters: Vec<TokenStream> = fields.iter().map(|f| adapter_code(&f.attrs)).collect();
+    quote!(
+        #({
+            let resolved_type = #resolved_types;
+            #[allow(unused_mut)]
+            let mut field = rdc::ir::Field::new(
+                rdc::ir::Name::from_snake_case(#field_names),
+                #json_field_names,
+                resolved_type,
+            );
+            #adapters
+            #options
+            struct_ir.add_field(field);
+            #dependencies
+        })*
+    )
+}
+
+/// Returns the code setting the options of a field on its IR.
+fn field_options_code(options: &FieldOptions) -> TokenStream {
+    let mut code = TokenStream::new();
+    if let Some(format) = &options.format {
+        let timezone = options
+            .timezone
+            .as_ref()
+            .map(|timezone| quote!(.with_timezone(#timezone)));
+        code.extend(quote!(
+            field.set_format(rdc::ir::DateTimeFormat::new(#format)#timezone);
+        ));
+    }
+    if let Some(example) = &options.example {
+        code.extend(quote!(
+            field.set_example(#example);
+        ));
+    }
+    if let Some(sql_type) = &options.sql_type {
+        code.extend(quote!(
+            field.set_column_type(#sql_type);
+        ));
+    }
+    if options.primary_key {
+        code.extend(quote!(
+            field.set_primary_key(true);
+        ));
+    }
+    code
+}
+
+/// Returns the code setting the `serde_with` adapter of a field, which replaces the field type with the written one.
+fn adapter_code(attributes: &[Attribute]) -> TokenStream {
+    match find_serde_adapter(attributes) {
+        Some(adapter) => {
+            let adapter = Ident::new(adapter, Span::call_site());
+            quote!(
+                if let Err(error) = field.set_adapter(rdc::ir::SerdeAdapter::#adapter) {
+                    panic!("{}", error.message());
+                }
+            )
+        }
+        None => TokenStream::new(),
+    }
+}
@@ -0,0 +1,72 @@
+use crate::attributes::VariantOptions;
+use crate::bounds::add_bounds;
+use crate::targets::java::implement_java_types;
+use crate::utils::find_serde_rename;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DataEnum, DeriveInput, Variant};
+
+pub fn generate_unit_enum_code(input: &DeriveInput, data: &DataEnum) -> TokenStream {
+    let name = &input.ident;
+    let (generics, type_params) = add_bounds(input, &[]);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let variants = data.variants.iter().collect::<Vec<_>>();
+    let variants_code = variants
+        .iter()
+        .map(|v| generate_variant(v))
+        .collect::<Vec<_>>();
+
+    let java_implements = implement_java_types(name, &generics, &type_params);
+    quote!(
+        impl #impl_generics rdc::codegen::GenerateIR for #name #ty_generics #where_clause {
+            fn add_to_ir(ir: &mut rdc::ir::IntermediateRepresentation) {
+                let custom_type = ir.target().resolve_custom_type::<Self>();
+                let type_name = custom_type.type_name();
+                let mut enum_ir = rdc::ir::Enum::new(
+                    rdc::ir::Name::from_pascal_case(type_name),
+                    custom_type,
+                );
+                #(#variants_code)*
+
+                ir.add_enum(enum_ir);
+            }
+        }
+
+        impl #impl_generics rdc::RDCType for #name #ty_generics #where_clause {}
+
+        #java_implements
+    )
+}
+
+fn generate_variant(variant: &Variant) -> TokenStream {
+    let variant_name = &variant.ident.to_string();
+    if !variant.fields.is_empty() {
+        panic!("Unit enums must have no fields");
+    }
+    let json_name =
+        find_serde_rename(variant.attrs.iter()).unwrap_or_else(|| variant_name.to_string());
+
+    let options = VariantOptions::parse(&variant.attrs);
+    let display = options
+        .display
+        .map(|display| quote!(variant.set_display_name(#display);));
+    let description = options
+        .description
+        .map(|description| quote!(variant.set_description(#description);));
+    let translations = options
+        .translations
+        .iter()
+        .map(|(locale, label)| quote!(variant.set_translation(#locale, #label);));
+
+    quote!({
+        #[allow(unused_mut)]
+        let mut variant = rdc::ir::EnumVariant::new(
+            rdc::ir::Name::from_pascal_case(#variant_name),
+            #json_name,
+        );
+        #display
+        #description
+        #(#translations)*
+        enum_ir.add_variant(variant)
+    })
+}
@@ -0,0 +1,52 @@
+use crate::attributes::TypeOptions;
+use crate::codegen::data_enums::generate_data_enum_code;
+use crate::codegen::structs::generate_struct_code;
+use crate::codegen::unit_enums::generate_unit_enum_code;
+use proc_macro::TokenStream;
+use syn::{DeriveInput, Variant};
+
+mod attributes;
+mod bounds;
+mod codegen;
+mod targets;
+mod utils;
+
+fn generate_code(input: &DeriveInput) -> proc_macro2::TokenStream {
+    match &input.data {
+        syn::Data::Struct(data) => generate_struct_code(input, data),
+        syn::Data::Enum(enum_data) => {
+            if !TypeOptions::parse(&input.attrs)
+                .implements_shared
+                .is_empty()
+            {
+                panic!("`#[rdc(implements_shared)]` is only supported on structs");
+            }
+            if enum_data
+                .variants
+                .iter()
+                .all(|variant: &Variant| variant.fields.is_empty())
+            {
+                generate_unit_enum_code(input, enum_data)
+            } else {
+                generate_data_enum_code(input, enum_data)
+            }
+        }
+        syn::Data::Union(_) => panic!("Unions are not supported"),
+    }
+}
+
+/// Derive macro for generating code for the `rdc` crate.
+/// Supported types are structs, enums and primitive types.
+///
+/// Type parameters used by the fields get an `rdc::RDCType` bound, other ones only `'static`.
+/// `#[rdc(bound = "...")]` on the type replaces the inferred bounds,
+/// and `#[rdc(skip_bound)]` on a field keeps its type from adding bounds.
+/// `PhantomData` fields hold no data, so they are left out.
+/// `#[rdc(implements_shared = "...")]` on a struct names a shared Java interface its class implements.
+/// Struct fields written with the `serde_with` adapters the IR describes, e.g. `#[serde_as(as = "DisplayFromStr")]`
+/// under `#[serde_as]`, get the types the adapters write.
+#[proc_macro_derive(RDC, attributes(serde, rdc))]
+pub fn derive_rdc(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    generate_code(&input).into()
+}
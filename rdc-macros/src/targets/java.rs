@@ -0,0 +1,40 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::Generics;
+
+/// Implements the Java type traits. Only the given type parameters, the ones used by the fields,
+/// are a part of the type name, because other ones do not change the generated class.
+pub fn implement_java_types(
+    name: &Ident,
+    generics: &Generics,
+    type_params: &[Ident],
+) -> TokenStream {
+    let type_name = match type_params.len() {
+        0 => quote!(stringify!(#name).to_string()),
+        _ => {
+            let generic_types = type_params.iter().map(|param: &Ident| {
+                quote!(rdc::ir::TypeTarget::Java.resolve_type::<#param>().type_name())
+            });
+            quote!({
+                let mut type_string = stringify!(#name).to_string();
+                #(type_string.push_str(#generic_types);)*
+                type_string
+            })
+        }
+    };
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote!(
+        impl #impl_generics rdc::targets::java::type_resolver::JavaCustomType for #name #ty_generics #where_clause {
+            fn java_custom_type() -> rdc::ir::CustomType {
+                rdc::ir::CustomType::new(#type_name)
+            }
+        }
+
+        impl #impl_generics rdc::targets::java::type_resolver::JavaType for #name #ty_generics #where_clause {
+            fn java_type() -> rdc::ir::Type {
+                rdc::ir::Type::new(#type_name)
+            }
+        }
+
+    )
+}
@@ -0,0 +1,102 @@
+use syn::{Attribute, GenericArgument, Lit, Meta, NestedMeta, PathArguments, Type};
+
+pub fn find_serde_rename<'a>(
+    mut attributes: impl Iterator<Item = &'a Attribute>,
+) -> Option<String> {
+    attributes
+        .find(|attr| attr.path.is_ident("serde"))
+        .and_then(|attr| attr.parse_meta().ok())
+        .and_then(|meta| {
+            if let syn::Meta::List(list) = meta {
+                list.nested
+                    .iter()
+                    .find(|nested| {
+                        if let syn::NestedMeta::Meta(meta) = nested {
+                            meta.path().is_ident("rename")
+                        } else {
+                            false
+                        }
+                    })
+                    .and_then(|nested| {
+                        if let syn::NestedMeta::Meta(syn::Meta::NameValue(name_value)) = nested {
+                            if let syn::Lit::Str(lit_str) = &name_value.lit {
+                                Some(lit_str.value())
+                            } else {
+                                None
+                            }
+                        } else {
+                            None
+                        }
+                    })
+            } else {
+                None
+            }
+        })
+}
+
+/// Returns whether the type is `PhantomData`, which holds no data and therefore is not a part of the IR.
+pub fn is_phantom_data(t: &Type) -> bool {
+    match t {
+        Type::Path(tp) => tp
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "PhantomData"),
+        _ => false,
+    }
+}
+
+/// Returns the name of the `SerdeAdapter` of a field written with a `serde_with` adapter, if it is one the IR describes.
+/// `#[serde_as]`, which goes above the derive, turns `#[serde_as(as = "...")]` on fields
+/// into `#[serde(with = "::serde_with::As::<...>")]`, so the adapter is read from the latter.
+pub fn find_serde_adapter(attributes: &[Attribute]) -> Option<&'static str> {
+    attributes
+        .iter()
+        .filter(|attr| attr.path.is_ident("serde"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|nested| match nested {
+            NestedMeta::Meta(Meta::NameValue(name_value)) => match name_value.lit {
+                Lit::Str(lit_str) if name_value.path.is_ident("with") => {
+                    let with = lit_str.value().replace(' ', "");
+                    let start = with.find("As::<")? + "As::<".len();
+                    with.strip_suffix('>').map(|with| with[start..].to_string())
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .find_map(|adapter| adapter_name(&syn::parse_str(&adapter).ok()?))
+}
+
+/// Maps an adapter type, e.g. `Option<DisplayFromStr>` or `Vec<(_, _)>`, to the name of its `SerdeAdapter`.
+fn adapter_name(t: &Type) -> Option<&'static str> {
+    let Type::Path(tp) = t else {
+        return None;
+    };
+    let segment = tp.path.segments.last()?;
+    let arguments = match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) => arguments
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(t) => Some(t),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    match (segment.ident.to_string().as_str(), arguments.as_slice()) {
+        ("DisplayFromStr", []) => Some("DisplayFromStr"),
+        ("DurationSeconds", []) => Some("DurationSeconds"),
+        ("DurationSeconds", [Type::Path(format), ..]) if format.path.is_ident("u64") => {
+            Some("DurationSeconds")
+        }
+        ("Vec", [Type::Tuple(tuple)]) if tuple.elems.len() == 2 => Some("MapAsPairs"),
+        ("Option" | "Vec", [inner]) => adapter_name(inner),
+        _ => None,
+    }
+}
@@ -4,10 +4,21 @@ use crate::RDCType;
 /// This is a trait that adds all required structs and enums to the IR.
 /// It is also implemented for all the primitive types that are supported natively by the target language.
 /// Then `GenerateIR::add_to_ir` method is empty, because the type is already supported by the target language.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can not be added to the IR",
+    label = "`{Self}` does not implement `GenerateIR`",
+    note = "add `#[derive(RDC)]` to `{Self}`, or register trait objects with `rdc_polymorphic!`"
+)]
 pub trait GenerateIR {
     fn add_to_ir(_ir: &mut IntermediateRepresentation) {}
 }
 
+/// This is a trait implemented by service traits declared with `rdc_service!` or `rdc_rpc_service!`.
+/// It adds the service description and all the types used by its endpoints or methods to the IR.
+pub trait RDCService {
+    fn add_to_ir(ir: &mut IntermediateRepresentation);
+}
+
 macro_rules! rdc_type {
     ($type:ty) => {
         impl GenerateIR for $type {}
@@ -23,13 +34,20 @@ rdc_type!(i64);
 rdc_type!(f32);
 rdc_type!(f64);
 rdc_type!(String);
+rdc_type!(u8);
+rdc_type!(u16);
+rdc_type!(u32);
+rdc_type!(u64);
+rdc_type!(std::time::Duration);
+#[cfg(feature = "json")]
+rdc_type!(serde_json::Value);
 
 impl<T> GenerateIR for Vec<T>
 where
     T: RDCType,
 {
     fn add_to_ir(ir: &mut IntermediateRepresentation) {
-        T::add_to_ir(ir);
+        ir.add::<T>();
     }
 }
 impl<T> RDCType for Vec<T> where T: RDCType {}
@@ -39,7 +57,7 @@ where
     T: RDCType,
 {
     fn add_to_ir(ir: &mut IntermediateRepresentation) {
-        T::add_to_ir(ir);
+        ir.add::<T>();
     }
 }
 impl<T> RDCType for Option<T> where T: RDCType {}
@@ -50,8 +68,8 @@ where
     V: RDCType,
 {
     fn add_to_ir(ir: &mut IntermediateRepresentation) {
-        K::add_to_ir(ir);
-        V::add_to_ir(ir);
+        ir.add::<K>();
+        ir.add::<V>();
     }
 }
 impl<K, V> RDCType for std::collections::HashMap<K, V>
@@ -61,12 +79,496 @@ where
 {
 }
 
+impl<K, V> GenerateIR for std::collections::BTreeMap<K, V>
+where
+    K: RDCType,
+    V: RDCType,
+{
+    fn add_to_ir(ir: &mut IntermediateRepresentation) {
+        ir.add::<K>();
+        ir.add::<V>();
+    }
+}
+impl<K, V> RDCType for std::collections::BTreeMap<K, V>
+where
+    K: RDCType,
+    V: RDCType,
+{
+}
+
 impl<T> GenerateIR for Box<T>
 where
     T: RDCType,
 {
     fn add_to_ir(ir: &mut IntermediateRepresentation) {
-        T::add_to_ir(ir);
+        ir.add::<T>();
     }
 }
 impl<T> RDCType for Box<T> where T: RDCType {}
+
+/// This macro makes `Box<dyn Trait>` usable in data structures by listing all the implementations of the trait.
+/// It generates a base type with the implementations registered as its subtypes.
+///
+/// By default values are wrapped in an object with the implementation name as its only key,
+/// which matches the default representation used by the `typetag` crate.
+/// An internally tagged representation can be selected with `tag = "..."`.
+///
+/// Example:
+/// ```rust
+/// use rdc::{rdc_java, rdc_polymorphic, RDC};
+///
+/// trait Shape {}
+///
+/// #[derive(RDC)]
+/// struct Circle {
+///     radius: f64,
+/// }
+/// impl Shape for Circle {}
+///
+/// #[derive(RDC)]
+/// struct Square {
+///     side: f64,
+/// }
+/// impl Shape for Square {}
+///
+/// rdc_polymorphic!(dyn Shape, tag = "type" => Circle, Square);
+///
+/// #[derive(RDC)]
+/// struct Drawing {
+///     shapes: Vec<Box<dyn Shape>>,
+/// }
+///
+/// let classes = rdc_java!(Drawing).unwrap();
+/// assert_eq!(classes.len(), 4);
+/// ```
+#[macro_export]
+macro_rules! rdc_polymorphic {
+    (dyn $base:ident => $($variant:ident),+ $(,)?) => {
+        $crate::rdc_polymorphic!(@impl $base, None => $($variant),+);
+    };
+    (dyn $base:ident, tag = $tag:literal => $($variant:ident),+ $(,)?) => {
+        $crate::rdc_polymorphic!(@impl $base, Some($tag.to_string()) => $($variant),+);
+    };
+    (@impl $base:ident, $tag:expr => $($variant:ident),+) => {
+        $crate::__rdc_polymorphic_impl!(Box<dyn $base>, $base, $tag, false => $($variant),+);
+    };
+}
+
+/// This macro declares a tagged envelope for messages, e.g. ones sent over a WebSocket.
+/// It emits a Rust enum with a variant wrapping each message type, named after it,
+/// together with `From` conversions from the message types.
+///
+/// The envelope is generated like a type declared with `rdc_polymorphic!`, so that Java reads it with `@JsonTypeInfo`,
+/// and targets also generate a handler, e.g. `ChatMessageHandler` with an `on<Message>` method for every message.
+/// Without attributes the envelope is externally tagged, like `{"Join": {...}}`, which matches the default representation of serde.
+/// When the first attribute is `#[serde(tag = "...")]`, it is internally tagged, like `{"type": "Join", ...}`.
+///
+/// Example:
+/// ```rust
+/// use rdc::{rdc_envelope, rdc_java, RDC};
+///
+/// #[derive(RDC)]
+/// struct Join {
+///     user: String,
+/// }
+///
+/// #[derive(RDC)]
+/// struct Leave {
+///     user: String,
+/// }
+///
+/// rdc_envelope! {
+///     pub enum ChatMessage { Join, Leave }
+/// }
+///
+/// let message: ChatMessage = Join { user: "alice".to_string() }.into();
+/// assert!(matches!(message, ChatMessage::Join(_)));
+/// let classes = rdc_java!(ChatMessage).unwrap();
+/// assert!(classes.iter().any(|c| c.name() == "ChatMessageHandler"));
+/// ```
+#[macro_export]
+macro_rules! rdc_envelope {
+    (
+        #[serde(tag = $tag:literal)]
+        $(#[$attr:meta])*
+        $vis:vis enum $name:ident { $($variant:ident),+ $(,)? }
+    ) => {
+        $crate::rdc_envelope!(@impl [$(#[$attr])* #[serde(tag = $tag)]] $vis $name, Some($tag.to_string()) => $($variant),+);
+    };
+    (
+        $(#[$attr:meta])*
+        $vis:vis enum $name:ident { $($variant:ident),+ $(,)? }
+    ) => {
+        $crate::rdc_envelope!(@impl [$(#[$attr])*] $vis $name, None => $($variant),+);
+    };
+    (@impl [$($attr:tt)*] $vis:vis $name:ident, $tag:expr => $($variant:ident),+) => {
+        $($attr)*
+        $vis enum $name {
+            $($variant($variant)),+
+        }
+
+        $(
+            impl From<$variant> for $name {
+                fn from(message: $variant) -> Self {
+                    $name::$variant(message)
+                }
+            }
+        )+
+
+        $crate::__rdc_polymorphic_impl!($name, $name, $tag, true => $($variant),+);
+    };
+}
+
+/// This macro maps a set of flags, e.g. a type declared with `bitflags!`, to a flags type in the IR.
+/// It lists the flags, which are read from the associated constants of the type with `bits()`,
+/// and the JSON format of the type, either `bits` for an integer or `names` for names separated with `|`,
+/// which is the format of `bitflags` 2 with serde.
+///
+/// Java represents the flags with a value class holding the bits, with a nested `Flag` enum,
+/// bitwise helpers, conversions from and to an `EnumSet` and a matching Jackson serializer and deserializer.
+///
+/// Example:
+/// ```rust
+/// use rdc::{rdc_bitflags, rdc_java, RDC};
+///
+/// // bitflags! { pub struct Permissions: u32 { const READ = 1; const WRITE = 2; } }
+/// pub struct Permissions(u32);
+///
+/// impl Permissions {
+///     pub const READ: Self = Self(1);
+///     pub const WRITE: Self = Self(2);
+///
+///     pub fn bits(&self) -> u32 {
+///         self.0
+///     }
+/// }
+///
+/// rdc_bitflags!(Permissions, format = names => READ, WRITE);
+///
+/// #[derive(RDC)]
+/// struct User {
+///     permissions: Permissions,
+/// }
+///
+/// let classes = rdc_java!(User).unwrap();
+/// assert!(classes[1].code().contains("public boolean contains(Flag flag)"));
+/// ```
+#[macro_export]
+macro_rules! rdc_bitflags {
+    ($type:ident, format = bits => $($flag:ident),+ $(,)?) => {
+        $crate::rdc_bitflags!(@impl $type, $crate::ir::FlagsFormat::Bits => $($flag),+);
+    };
+    ($type:ident, format = names => $($flag:ident),+ $(,)?) => {
+        $crate::rdc_bitflags!(@impl $type, $crate::ir::FlagsFormat::Names => $($flag),+);
+    };
+    (@impl $type:ident, $format:expr => $($flag:ident),+) => {
+        impl $crate::targets::java::type_resolver::JavaType for $type {
+            fn java_type() -> $crate::ir::Type {
+                $crate::ir::Type::new(stringify!($type))
+            }
+        }
+
+        impl $crate::codegen::GenerateIR for $type {
+            fn add_to_ir(ir: &mut $crate::ir::IntermediateRepresentation) {
+                let mut flags = $crate::ir::Enum::new(
+                    $crate::ir::Name::from_pascal_case(stringify!($type)),
+                    $crate::ir::CustomType::new(stringify!($type)),
+                );
+                flags.set_flags($format);
+                $(
+                    let mut flag = $crate::ir::EnumVariant::new(
+                        $crate::ir::Name::from_snake_case(stringify!($flag).to_lowercase()),
+                        stringify!($flag),
+                    );
+                    flag.set_bits($type::$flag.bits() as u64);
+                    flags.add_variant(flag);
+                )+
+                ir.add_enum(flags);
+            }
+        }
+
+        impl $crate::RDCType for $type {}
+    };
+}
+
+/// This macro maps a newtype, e.g. `struct Tags(HashMap<String, String>)`, to a transparent struct in the IR.
+/// The derive does the same for tuple structs with one field, this macro is for the newtypes which do not derive `RDC`.
+/// serde writes a newtype as the value it wraps, so its JSON is the JSON of the wrapped type.
+///
+/// Java represents the newtype with a class delegating to the wrapped value, which Jackson reads with `@JsonCreator`
+/// and writes with `@JsonAnyGetter` for maps and `@JsonValue` for other types. OpenAPI documents, JSON schemas
+/// and TypeScript describe it as the wrapped type, while other targets reject it.
+///
+/// Example:
+/// ```rust
+/// use rdc::{rdc_java, rdc_newtype, RDC};
+/// use std::collections::HashMap;
+///
+/// pub struct Tags(HashMap<String, String>);
+///
+/// rdc_newtype!(Tags => HashMap<String, String>);
+///
+/// #[derive(RDC)]
+/// struct Article {
+///     tags: Tags,
+/// }
+///
+/// let classes = rdc_java!(Article).unwrap();
+/// let tags = classes.iter().find(|c| c.name() == "Tags").unwrap();
+/// assert!(tags.code().contains("@JsonAnyGetter public Map<String, String> getValue()"));
+/// ```
+#[macro_export]
+macro_rules! rdc_newtype {
+    ($type:ident => $inner:ty) => {
+        impl $crate::targets::java::type_resolver::JavaType for $type {
+            fn java_type() -> $crate::ir::Type {
+                $crate::ir::Type::new(stringify!($type))
+            }
+        }
+
+        impl $crate::codegen::GenerateIR for $type {
+            fn add_to_ir(ir: &mut $crate::ir::IntermediateRepresentation) {
+                let mut newtype = $crate::ir::Struct::new(
+                    $crate::ir::Name::from_pascal_case(stringify!($type)),
+                    $crate::ir::CustomType::new(stringify!($type)),
+                );
+                newtype.add_field($crate::ir::Field::new(
+                    $crate::ir::Name::from_snake_case("value"),
+                    "value",
+                    ir.target().resolve_type::<$inner>(),
+                ));
+                newtype.set_transparent(true);
+                ir.add_struct(newtype);
+                ir.add::<$inner>();
+            }
+        }
+
+        impl $crate::RDCType for $type {}
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __rdc_polymorphic_impl {
+    ($type:ty, $base:ident, $tag:expr, $envelope:expr => $($variant:ident),+) => {
+        impl $crate::targets::java::type_resolver::JavaType for $type {
+            fn java_type() -> $crate::ir::Type {
+                $crate::ir::Type::new(stringify!($base))
+            }
+        }
+
+        impl $crate::codegen::GenerateIR for $type {
+            fn add_to_ir(ir: &mut $crate::ir::IntermediateRepresentation) {
+                let mut polymorphic = $crate::ir::Polymorphic::new(
+                    $crate::ir::Name::from_pascal_case(stringify!($base)),
+                    $crate::ir::CustomType::new(stringify!($base)),
+                    $tag,
+                );
+                polymorphic.set_envelope($envelope);
+                $(
+                    polymorphic.add_variant($crate::ir::PolymorphicVariant::new(
+                        stringify!($variant),
+                        ir.target().resolve_type::<$variant>(),
+                    ));
+                    ir.add::<$variant>();
+                )+
+                ir.add_polymorphic(polymorphic);
+            }
+        }
+
+        impl $crate::RDCType for $type {}
+    };
+}
+
+/// This macro declares a service trait describing HTTP endpoints.
+/// It emits the trait itself and makes it possible to add the service to the IR,
+/// so that clients can be generated for it, e.g. `ir.add_service_trait::<dyn UserApi>()`.
+///
+/// Every method is annotated with `#[rdc(method, "path")]`, where method is one of
+/// `get`, `post`, `put`, `patch` or `delete`.
+/// Arguments matching `{name}` placeholders in the path are path parameters,
+/// an argument called `body` is the JSON request body and all the other arguments are query parameters.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::{rdc_service, RDC};
+///
+/// #[derive(RDC)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// rdc_service! {
+///     pub trait UserApi {
+///         #[rdc(get, "/users/{id}")]
+///         fn get_user(&self, id: String) -> User;
+///         #[rdc(post, "/users")]
+///         fn create_user(&self, body: User) -> User;
+///         #[rdc(delete, "/users/{id}")]
+///         fn delete_user(&self, id: String);
+///     }
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add_service_trait::<dyn UserApi>();
+/// assert_eq!(ir.services()[0].endpoints().len(), 3);
+/// ```
+#[macro_export]
+macro_rules! rdc_service {
+    (
+        $(#[$attr:meta])*
+        $vis:vis trait $name:ident {
+            $(
+                $(#[doc = $doc:literal])*
+                #[rdc($method:ident, $path:literal)]
+                fn $fn_name:ident(&self $(, $arg:ident : $arg_ty:ty)* $(,)?) $(-> $ret:ty)?;
+            )*
+        }
+    ) => {
+        $(#[$attr])*
+        $vis trait $name {
+            $(
+                $(#[doc = $doc])*
+                fn $fn_name(&self $(, $arg: $arg_ty)*) $(-> $ret)?;
+            )*
+        }
+
+        impl $crate::codegen::RDCService for dyn $name {
+            fn add_to_ir(ir: &mut $crate::ir::IntermediateRepresentation) {
+                let mut service = $crate::ir::Service::new(
+                    $crate::ir::Name::from_pascal_case(stringify!($name)),
+                );
+                $(
+                    let mut endpoint = $crate::ir::Endpoint::new(
+                        $crate::ir::Name::from_snake_case(stringify!($fn_name)),
+                        $crate::__rdc_http_method!($method),
+                        $path,
+                    );
+                    $(
+                        endpoint.add_param(
+                            $crate::ir::Name::from_snake_case(stringify!($arg)),
+                            ir.target().resolve_type::<$arg_ty>(),
+                        );
+                        ir.add::<$arg_ty>();
+                    )*
+                    $(
+                        endpoint.set_response(ir.target().resolve_type::<$ret>());
+                        ir.add::<$ret>();
+                    )?
+                    service.add_endpoint(endpoint);
+                )*
+                ir.add_service(service);
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __rdc_http_method {
+    (get) => {
+        $crate::ir::HttpMethod::Get
+    };
+    (post) => {
+        $crate::ir::HttpMethod::Post
+    };
+    (put) => {
+        $crate::ir::HttpMethod::Put
+    };
+    (patch) => {
+        $crate::ir::HttpMethod::Patch
+    };
+    (delete) => {
+        $crate::ir::HttpMethod::Delete
+    };
+    ($other:ident) => {
+        compile_error!(concat!(
+            "unsupported HTTP method `",
+            stringify!($other),
+            "`, expected one of: get, post, put, patch, delete"
+        ))
+    };
+}
+
+/// This macro declares a JSON-RPC service trait.
+/// Like `rdc_service!`, it emits the trait itself and makes it possible to add the service to the IR,
+/// e.g. `ir.add_service_trait::<dyn UserRpc>()`, so that clients and dispatchers can be generated for it.
+///
+/// Every method is annotated with `#[rdc("name")]`, where name is the JSON-RPC method name.
+/// The arguments are sent as named parameters and are described by a request DTO added to the IR,
+/// e.g. `GetUserParams` for `get_user`.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::{rdc_rpc_service, RDC};
+///
+/// #[derive(RDC)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// rdc_rpc_service! {
+///     pub trait UserRpc {
+///         #[rdc("user.get")]
+///         fn get_user(&self, id: String) -> User;
+///         #[rdc("user.ping")]
+///         fn ping(&self);
+///     }
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add_service_trait::<dyn UserRpc>();
+/// assert_eq!(ir.rpc_services()[0].methods().len(), 2);
+/// assert!(ir.structs().iter().any(|s| s.self_type().type_name() == "GetUserParams"));
+/// ```
+#[macro_export]
+macro_rules! rdc_rpc_service {
+    (
+        $(#[$attr:meta])*
+        $vis:vis trait $name:ident {
+            $(
+                $(#[doc = $doc:literal])*
+                #[rdc($rpc_name:literal)]
+                fn $fn_name:ident(&self $(, $arg:ident : $arg_ty:ty)* $(,)?) $(-> $ret:ty)?;
+            )*
+        }
+    ) => {
+        $(#[$attr])*
+        $vis trait $name {
+            $(
+                $(#[doc = $doc])*
+                fn $fn_name(&self $(, $arg: $arg_ty)*) $(-> $ret)?;
+            )*
+        }
+
+        impl $crate::codegen::RDCService for dyn $name {
+            fn add_to_ir(ir: &mut $crate::ir::IntermediateRepresentation) {
+                let mut service = $crate::ir::RpcService::new(
+                    $crate::ir::Name::from_pascal_case(stringify!($name)),
+                );
+                $(
+                    #[allow(unused_mut)]
+                    let mut method = $crate::ir::RpcMethod::new(
+                        $crate::ir::Name::from_snake_case(stringify!($fn_name)),
+                        $rpc_name,
+                    );
+                    $(
+                        method.add_param(
+                            $crate::ir::Name::from_snake_case(stringify!($arg)),
+                            ir.target().resolve_type::<$arg_ty>(),
+                        );
+                        ir.add::<$arg_ty>();
+                    )*
+                    $(
+                        method.set_result(ir.target().resolve_type::<$ret>());
+                        ir.add::<$ret>();
+                    )?
+                    service.add_method(method);
+                )*
+                ir.add_rpc_service(service);
+            }
+        }
+    };
+}
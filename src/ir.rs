@@ -1,4 +1,26 @@
+mod builder;
 mod data;
+mod diff;
+mod filter;
+mod fingerprint;
+mod groups;
+#[cfg(feature = "json")]
+mod json_schema;
+mod rename;
+mod rpc;
+mod service;
+mod shared;
+mod snapshot;
 mod types;
+mod validation;
+pub use builder::*;
 pub use data::*;
+pub use diff::*;
+pub use filter::*;
+pub use fingerprint::*;
+pub use groups::*;
+pub use rename::*;
+pub use rpc::*;
+pub use service::*;
+pub use shared::*;
 pub use types::*;
@@ -0,0 +1,453 @@
+use crate::errors::Error;
+use crate::ir::{
+    CustomType, DateTimeFormat, Enum, EnumVariant, Field, IntermediateRepresentation, Name,
+    RenameRule, Struct, Type, TypeTarget,
+};
+use crate::targets::java::type_resolver::{list_type, map_type, option_type};
+
+/// This enum describes the type of a field declared with `IrBuilder`, independently of the target.
+/// It is resolved to a `Type` of the target when the IR is built.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FieldType {
+    Bool,
+    I8,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    F32,
+    F64,
+    String,
+    /// Struct or enum declared with the same builder, referenced by its name.
+    Named(String),
+    List(Box<FieldType>),
+    Map(Box<FieldType>, Box<FieldType>),
+    Option(Box<FieldType>),
+}
+
+impl FieldType {
+    pub fn string() -> Self {
+        Self::String
+    }
+
+    pub fn bool() -> Self {
+        Self::Bool
+    }
+
+    pub fn i32() -> Self {
+        Self::I32
+    }
+
+    pub fn i64() -> Self {
+        Self::I64
+    }
+
+    pub fn f64() -> Self {
+        Self::F64
+    }
+
+    pub fn named<S: Into<String>>(type_name: S) -> Self {
+        Self::Named(type_name.into())
+    }
+
+    pub fn list(element: FieldType) -> Self {
+        Self::List(Box::new(element))
+    }
+
+    pub fn map(key: FieldType, value: FieldType) -> Self {
+        Self::Map(Box::new(key), Box::new(value))
+    }
+
+    pub fn option(inner: FieldType) -> Self {
+        Self::Option(Box::new(inner))
+    }
+
+    /// Returns the type the field has in the target, the same one the derive macro would resolve.
+    pub fn resolve(&self, target: &TypeTarget) -> Type {
+        match self {
+            Self::Bool => target.resolve_type::<bool>(),
+            Self::I8 => target.resolve_type::<i8>(),
+            Self::I16 => target.resolve_type::<i16>(),
+            Self::I32 => target.resolve_type::<i32>(),
+            Self::I64 => target.resolve_type::<i64>(),
+            Self::U8 => target.resolve_type::<u8>(),
+            Self::U16 => target.resolve_type::<u16>(),
+            Self::U32 => target.resolve_type::<u32>(),
+            Self::U64 => target.resolve_type::<u64>(),
+            Self::F32 => target.resolve_type::<f32>(),
+            Self::F64 => target.resolve_type::<f64>(),
+            Self::String => target.resolve_type::<String>(),
+            Self::Named(type_name) => Type::new(type_name.as_str()),
+            Self::List(element) => match target {
+                TypeTarget::Java => list_type(element.resolve(target)),
+            },
+            Self::Map(key, value) => match target {
+                TypeTarget::Java => map_type(key.resolve(target), value.resolve(target)),
+            },
+            Self::Option(inner) => match target {
+                TypeTarget::Java => option_type(inner.resolve(target)),
+            },
+        }
+    }
+
+    pub(crate) fn named_types(&self) -> Vec<&str> {
+        match self {
+            Self::Named(type_name) => vec![type_name.as_str()],
+            Self::List(inner) | Self::Option(inner) => inner.named_types(),
+            Self::Map(key, value) => {
+                let mut named = key.named_types();
+                named.extend(value.named_types());
+                named
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// This is a fluent builder of an IR, for tools that synthesize types at runtime, e.g. from a database schema,
+/// instead of deriving them from Rust types.
+///
+/// Every `field` is added to the last declared struct and every `variant` to the last declared enum.
+/// Type names are given in pascal case and field names in snake case.
+/// JSON names default to the names of fields and variants, converted with the `rename_all` rule of the type if set,
+/// like serde does without `rename`.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{FieldType, IrBuilder, TypeTarget};
+/// use rdc::targets::java::generate_java_code;
+///
+/// let ir = IrBuilder::new(TypeTarget::Java)
+///     .struct_("Invoice")
+///     .field("id", FieldType::string())
+///     .field("created_at", FieldType::i64())
+///     .json_name("createdAt")
+///     .optional_field("status", FieldType::named("Status"))
+///     .enum_("Status")
+///     .variant("Paid")
+///     .variant("Open")
+///     .build()
+///     .unwrap();
+/// let classes = generate_java_code(&ir).unwrap();
+/// assert!(classes[0].code().contains("@JsonProperty(\"createdAt\")"));
+/// ```
+pub struct IrBuilder {
+    target: TypeTarget,
+    items: Vec<Item>,
+    error: Option<String>,
+}
+
+enum Item {
+    Struct {
+        name: String,
+        rename_all: Option<RenameRule>,
+        fields: Vec<FieldDeclaration>,
+    },
+    Enum {
+        name: String,
+        rename_all: Option<RenameRule>,
+        variants: Vec<(String, Option<String>)>,
+    },
+}
+
+struct FieldDeclaration {
+    name: String,
+    json_name: Option<String>,
+    field_type: FieldType,
+    readonly: bool,
+    format: Option<DateTimeFormat>,
+    example: Option<String>,
+}
+
+impl IrBuilder {
+    pub fn new(target: TypeTarget) -> Self {
+        Self {
+            target,
+            items: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Declares a struct, e.g. `Invoice`. The following fields are added to it.
+    pub fn struct_<S: Into<String>>(mut self, name: S) -> Self {
+        self.items.push(Item::Struct {
+            name: name.into(),
+            rename_all: None,
+            fields: Vec::new(),
+        });
+        self
+    }
+
+    /// Declares an enum without data, e.g. `Status`. The following variants are added to it.
+    pub fn enum_<S: Into<String>>(mut self, name: S) -> Self {
+        self.items.push(Item::Enum {
+            name: name.into(),
+            rename_all: None,
+            variants: Vec::new(),
+        });
+        self
+    }
+
+    /// Adds a field to the last declared struct.
+    pub fn field<S: Into<String>>(mut self, name: S, field_type: FieldType) -> Self {
+        let name = name.into();
+        match self.items.last_mut() {
+            Some(Item::Struct { fields, .. }) => fields.push(FieldDeclaration {
+                json_name: None,
+                name,
+                field_type,
+                readonly: false,
+                format: None,
+                example: None,
+            }),
+            _ => self.fail(format!("Field `{name}` is not declared in a struct")),
+        }
+        self
+    }
+
+    /// Adds a field wrapped in an `Option` to the last declared struct.
+    pub fn optional_field<S: Into<String>>(self, name: S, field_type: FieldType) -> Self {
+        self.field(name, FieldType::option(field_type))
+    }
+
+    /// Adds a variant to the last declared enum.
+    pub fn variant<S: Into<String>>(mut self, name: S) -> Self {
+        let name = name.into();
+        match self.items.last_mut() {
+            Some(Item::Enum { variants, .. }) => variants.push((name, None)),
+            _ => self.fail(format!("Variant `{name}` is not declared in an enum")),
+        }
+        self
+    }
+
+    /// Sets the JSON name of the last declared field or variant.
+    pub fn json_name<S: Into<String>>(mut self, json_name: S) -> Self {
+        let json_name = json_name.into();
+        match self.items.last_mut() {
+            Some(Item::Struct { fields, .. }) if !fields.is_empty() => {
+                fields.last_mut().unwrap().json_name = Some(json_name);
+            }
+            Some(Item::Enum { variants, .. }) if !variants.is_empty() => {
+                variants.last_mut().unwrap().1 = Some(json_name);
+            }
+            _ => self.fail(format!(
+                "JSON name `{json_name}` does not follow a field or a variant"
+            )),
+        }
+        self
+    }
+
+    /// Sets the case convention of the JSON names of the fields or variants of the last declared type,
+    /// like serde `rename_all`. Names set with `json_name` are kept as they are.
+    pub fn rename_all(mut self, rule: RenameRule) -> Self {
+        match self.items.last_mut() {
+            Some(Item::Struct { rename_all, .. }) | Some(Item::Enum { rename_all, .. }) => {
+                *rename_all = Some(rule);
+            }
+            None => self.fail(format!(
+                "Rename rule `{}` does not follow a type",
+                rule.serde_name()
+            )),
+        }
+        self
+    }
+
+    /// Marks the last declared field as read-only.
+    pub fn readonly(mut self) -> Self {
+        match self.items.last_mut() {
+            Some(Item::Struct { fields, .. }) if !fields.is_empty() => {
+                fields.last_mut().unwrap().readonly = true;
+            }
+            _ => self.fail("`readonly` does not follow a field".to_string()),
+        }
+        self
+    }
+
+    /// Sets the text format of the last declared field, e.g. of a date.
+    pub fn format(mut self, format: DateTimeFormat) -> Self {
+        match self.items.last_mut() {
+            Some(Item::Struct { fields, .. }) if !fields.is_empty() => {
+                fields.last_mut().unwrap().format = Some(format);
+            }
+            _ => self.fail(format!(
+                "Format `{}` does not follow a field",
+                format.pattern()
+            )),
+        }
+        self
+    }
+
+    /// Sets an example value of the last declared field, e.g. `INV-001`.
+    pub fn example<S: Into<String>>(mut self, example: S) -> Self {
+        let example = example.into();
+        match self.items.last_mut() {
+            Some(Item::Struct { fields, .. }) if !fields.is_empty() => {
+                fields.last_mut().unwrap().example = Some(example);
+            }
+            _ => self.fail(format!("Example `{example}` does not follow a field")),
+        }
+        self
+    }
+
+    /// Builds and validates the IR.
+    /// It fails if the builder was misused, a field references an undeclared type, or the IR is invalid.
+    pub fn build(self) -> Result<IntermediateRepresentation, Error> {
+        if let Some(error) = self.error {
+            return Err(Error::new(&error));
+        }
+        let declared = self
+            .items
+            .iter()
+            .map(|item| match item {
+                Item::Struct { name, .. } | Item::Enum { name, .. } => name.as_str(),
+            })
+            .collect::<Vec<&str>>();
+        let mut structs = Vec::new();
+        let mut enums = Vec::new();
+        for item in &self.items {
+            match item {
+                Item::Struct {
+                    name,
+                    rename_all,
+                    fields,
+                } => {
+                    let mut s = Struct::new(Name::from_pascal_case(name), CustomType::new(name));
+                    for f in fields {
+                        if let Some(unknown) = f
+                            .field_type
+                            .named_types()
+                            .into_iter()
+                            .find(|t| !declared.contains(t))
+                        {
+                            return Err(Error::new(&format!(
+                                "Field `{name}.{}` references undeclared type `{unknown}`",
+                                f.name
+                            )));
+                        }
+                        let field_name = Name::from_snake_case(&f.name);
+                        let json_name = match (&f.json_name, rename_all) {
+                            (Some(json_name), _) => json_name.clone(),
+                            (None, Some(rule)) => rule.apply_to_field(&field_name),
+                            (None, None) => field_name.as_snake_case(),
+                        };
+                        let field_type = f.field_type.resolve(&self.target);
+                        let mut field = Field::new(field_name, json_name, field_type);
+                        field.set_readonly(f.readonly);
+                        if let Some(format) = &f.format {
+                            field.set_format(format.clone());
+                        }
+                        if let Some(example) = &f.example {
+                            field.set_example(example);
+                        }
+                        s.add_field(field);
+                    }
+                    structs.push(s);
+                }
+                Item::Enum {
+                    name,
+                    rename_all,
+                    variants,
+                } => {
+                    let mut e = Enum::new(Name::from_pascal_case(name), CustomType::new(name));
+                    for (variant, json_name) in variants {
+                        let json_name = match (json_name, rename_all) {
+                            (Some(json_name), _) => json_name.clone(),
+                            (None, Some(rule)) => {
+                                rule.apply_to_variant(&Name::from_pascal_case(variant))
+                            }
+                            (None, None) => variant.clone(),
+                        };
+                        e.add_variant(EnumVariant::new(Name::from_pascal_case(variant), json_name));
+                    }
+                    enums.push(e);
+                }
+            }
+        }
+        let mut ir = IntermediateRepresentation::new(self.target);
+        structs.into_iter().for_each(|s| ir.add_struct(s));
+        enums.into_iter().for_each(|e| ir.add_enum(e));
+        ir.validate()?;
+        Ok(ir)
+    }
+
+    /// Keeps the first misuse of the builder, which is reported by `build`.
+    fn fail(&mut self, error: String) {
+        self.error.get_or_insert(error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::targets::java::generate_java_code;
+
+    #[test]
+    fn build_ir() {
+        let ir = IrBuilder::new(TypeTarget::Java)
+            .struct_("Invoice")
+            .field("id", FieldType::string())
+            .readonly()
+            .example("INV-001")
+            .field("lines", FieldType::list(FieldType::named("InvoiceLine")))
+            .optional_field("status", FieldType::named("Status"))
+            .struct_("InvoiceLine")
+            .field(
+                "amounts",
+                FieldType::map(FieldType::string(), FieldType::U64),
+            )
+            .enum_("Status")
+            .rename_all(RenameRule::KebabCase)
+            .variant("Paid")
+            .variant("PartiallyPaid")
+            .variant("Overdue")
+            .json_name("late")
+            .build()
+            .unwrap();
+        let invoice = &ir.structs()[0];
+        assert_eq!(invoice.fields()[0].field_type().type_name(), "String");
+        assert!(invoice.fields()[0].is_readonly());
+        assert_eq!(invoice.fields()[0].example(), Some("INV-001"));
+        assert_eq!(
+            invoice.fields()[1].field_type().type_name(),
+            "java.util.List<InvoiceLine>"
+        );
+        assert_eq!(
+            ir.structs()[1].fields()[0].field_type().type_name(),
+            "java.util.Map<String, BigInteger>"
+        );
+        let variants = ir.enums()[0]
+            .variants()
+            .iter()
+            .map(|v| v.json_name().as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(variants, ["paid", "partially-paid", "late"]);
+        let classes = generate_java_code(&ir).unwrap();
+        let code = classes[0].code();
+        println!("{code}");
+        assert!(code.contains("private Status status;"));
+        assert!(!code.contains("setId"));
+    }
+
+    #[test]
+    fn builder_errors() {
+        let error = IrBuilder::new(TypeTarget::Java)
+            .field("id", FieldType::string())
+            .build()
+            .err()
+            .unwrap();
+        assert_eq!(error.message(), "Field `id` is not declared in a struct");
+        let error = IrBuilder::new(TypeTarget::Java)
+            .struct_("Invoice")
+            .field("customer", FieldType::option(FieldType::named("Customer")))
+            .build()
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.message(),
+            "Field `Invoice.customer` references undeclared type `Customer`"
+        );
+    }
+}
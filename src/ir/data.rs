@@ -1,50 +1,119 @@
-use crate::ir::TypeTarget;
+use crate::codegen::RDCService;
+use crate::errors::Error;
+use crate::ir::{validation, RpcService, Service, TypeTarget};
+use crate::targets::java::type_resolver::{
+    is_sorted_map, list_type, map_type, option_type, sorted_map_type, DURATION,
+};
 use crate::RDCType;
-use std::any::TypeId;
-use std::collections::HashSet;
+use std::any::{type_name, TypeId};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 /// This is intermediate representation of the data.
 /// It is used to generate code for other languages.
+#[derive(Clone)]
 pub struct IntermediateRepresentation {
     structs: Vec<Struct>,
     enums: Vec<Enum>,
     data_enums: Vec<DataEnum>,
+    polymorphics: Vec<Polymorphic>,
+    services: Vec<Service>,
+    rpc_services: Vec<RpcService>,
     target: TypeTarget,
     type_ids: HashSet<TypeId>,
+    rust_types: Vec<RustType>,
+    generated_names: HashMap<String, Option<RustType>>,
+    collisions: Vec<String>,
+}
+
+/// Rust type that is currently being added to the IR.
+#[derive(Clone, Copy)]
+struct RustType {
+    type_id: TypeId,
+    name: &'static str,
 }
 
 /// This is a struct that is used to represent a struct in the IR.
 /// It is usually translated to a class in other languages.
+#[derive(Clone)]
 pub struct Struct {
     name: Name,
     self_type: CustomType,
     fields: Vec<Field>,
+    transparent: bool,
+    shared_interfaces: Vec<String>,
 }
 
 /// This is a regular enum representation in the IR.
 /// It does not have any data associated with it.
+#[derive(Clone)]
 pub struct Enum {
     name: Name,
     self_type: CustomType,
     variants: Vec<EnumVariant>,
+    flags: Option<FlagsFormat>,
+}
+
+/// This enum describes how a set of flags, e.g. a type declared with `bitflags!`, is represented in JSON.
+/// The variants of a flags `Enum` are the flags, each with its bits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlagsFormat {
+    /// Integer holding the bits of all the flags, e.g. `3`.
+    Bits,
+    /// Names of the flags separated with `|`, e.g. `"READ | WRITE"`, which is the format of `bitflags` 2 with serde.
+    Names,
 }
 
 /// This is a struct that represents a field in `Struct`.
 /// It contains the name of the field, the type of the field and the name of the field in JSON.
+#[derive(Clone)]
 pub struct Field {
     name: Name,
     json_name: String,
     field_type: Type,
+    readonly: bool,
+    format: Option<DateTimeFormat>,
+    adapter: Option<SerdeAdapter>,
+    example: Option<String>,
+    column_type: Option<String>,
+    primary_key: bool,
+}
+
+/// This enum lists the `serde_with` adapters, used with `#[serde_as(as = "...")]`, whose JSON layout the IR can describe.
+/// The derive macro only sees the Rust type of a field, so the adapter is set on the IR,
+/// which replaces the field type with the type written by the adapter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SerdeAdapter {
+    /// `DisplayFromStr` writes values as strings, e.g. `"42"` for an `u64`. It may be wrapped in `Option` or `Vec`.
+    DisplayFromStr,
+    /// `DurationSeconds<u64>` writes a `std::time::Duration` as a number of whole seconds.
+    DurationSeconds,
+    /// `Vec<(_, _)>` writes a map as a list of key-value pairs, e.g. `[["a", 1], ["b", 2]]`.
+    /// Pairs are lists of the key type when keys and values have the same type, and of arbitrary JSON values otherwise.
+    MapAsPairs,
+}
+
+/// This struct describes the text format of a date or time field, e.g. one written by a serde `with` module.
+/// The pattern uses the syntax of `java.time.format.DateTimeFormatter`, e.g. `yyyy-MM-dd'T'HH:mm:ssX`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DateTimeFormat {
+    pattern: String,
+    timezone: Option<String>,
 }
 
 /// This is a struct that represents a variant in `Enum`.
 /// It contains the name of the variant and the name of the variant in JSON.
+#[derive(Clone)]
 pub struct EnumVariant {
     name: Name,
     json_name: String,
+    bits: Option<u64>,
+    display_name: Option<String>,
+    translations: BTreeMap<String, String>,
+    description: Option<String>,
 }
 
 /// This is a data enum representation in the IR.
+#[derive(Clone)]
 pub struct DataEnum {
     name: Name,
     self_type: CustomType,
@@ -53,6 +122,7 @@ pub struct DataEnum {
 }
 
 /// This enum represents type of data enum variant
+#[derive(Clone)]
 pub enum DataEnumVariant {
     Unit {
         name: Name,
@@ -71,14 +141,35 @@ pub enum DataEnumVariant {
 }
 
 /// This struct represents a single field in a data enum object variant.
+#[derive(Clone)]
 pub struct DataEnumObjectField {
     name: Name,
     json_name: String,
     field_type: Type,
 }
 
+/// This is a representation of a trait object, e.g. `Box<dyn Shape>`, in the IR.
+/// It is usually translated to an interface with the variants being its implementations.
+#[derive(Clone)]
+pub struct Polymorphic {
+    name: Name,
+    self_type: CustomType,
+    tag: Option<String>,
+    variants: Vec<PolymorphicVariant>,
+    envelope: bool,
+}
+
+/// This is a struct that represents a single implementation of a `Polymorphic` type.
+/// It contains the name used in JSON and the type of the implementation.
+#[derive(Clone)]
+pub struct PolymorphicVariant {
+    json_name: String,
+    variant_type: Type,
+}
+
 /// This enum represents the style of a data enum.
 /// Currently only external style is supported.
+#[derive(Clone)]
 pub enum DataEnumStyle {
     External,
 }
@@ -87,19 +178,40 @@ pub enum DataEnumStyle {
 ///
 /// Despite IR is mostly language agnostic, types are language specific.
 /// It is used for type resolution.
-pub struct Type(String);
+#[derive(Clone)]
+pub struct Type {
+    type_name: String,
+    kind: TypeKind,
+}
+
+/// This enum describes the structure of a `Type`.
+/// It allows targets to inspect and rewrite type references without parsing type names.
+#[derive(Clone)]
+pub enum TypeKind {
+    /// Type referenced only by its name, e.g. a generated class or a builtin type.
+    Named,
+    List(Box<Type>),
+    Map(Box<Type>, Box<Type>),
+    Option(Box<Type>),
+    /// Unsigned 64-bit integer, which does not fit into signed 64-bit types of most targets.
+    UnsignedLong,
+}
 
 /// This is a custom type struct that is used to represent a type in the IR.
 ///
 /// Despite IR is mostly language agnostic, types are language specific.
 /// It is used for type code generation.
+#[derive(Clone)]
 pub struct CustomType(String);
 
 /// This is a struct that is used to represent a name in the IR.
 /// It is used to generate code for other languages.
 /// It is capable of converting multiple cases between each other.
+#[derive(Clone)]
 pub struct Name {
     snake_case: String,
+    /// The raw identifier the name was created from, e.g. `r#type`, if it had the `r#` prefix.
+    raw: Option<String>,
 }
 
 impl IntermediateRepresentation {
@@ -108,27 +220,398 @@ impl IntermediateRepresentation {
             structs: Vec::new(),
             enums: Vec::new(),
             data_enums: Vec::new(),
+            polymorphics: Vec::new(),
+            services: Vec::new(),
+            rpc_services: Vec::new(),
             target,
             type_ids: HashSet::new(),
+            rust_types: Vec::new(),
+            generated_names: HashMap::new(),
+            collisions: Vec::new(),
         }
     }
 
     pub fn add_struct(&mut self, s: Struct) {
+        self.register_name(s.self_type());
         self.structs.push(s);
     }
 
     pub fn add_enum(&mut self, e: Enum) {
+        self.register_name(e.self_type());
         self.enums.push(e);
     }
 
     pub fn add_data_enum(&mut self, de: DataEnum) {
+        self.register_name(de.self_type());
         self.data_enums.push(de);
     }
 
+    pub fn add_polymorphic(&mut self, p: Polymorphic) {
+        self.register_name(p.self_type());
+        self.polymorphics.push(p);
+    }
+
+    pub fn add_service(&mut self, service: Service) {
+        self.register_name(&CustomType::new(service.name().as_pascal_case()));
+        self.services.push(service);
+    }
+
+    /// Adds a JSON-RPC service together with the request DTOs of its methods,
+    /// which are added as structs, e.g. `GetUserParams` for the method `get_user`.
+    pub fn add_rpc_service(&mut self, service: RpcService) {
+        for method in service.methods() {
+            if let Some(params_type) = method.params_type() {
+                let mut params =
+                    Struct::new(Name::from_pascal_case(params_type.type_name()), params_type);
+                for param in method.params() {
+                    params.add_field(param.clone());
+                }
+                self.add_struct(params);
+            }
+        }
+        self.register_name(&CustomType::new(service.name().as_pascal_case()));
+        self.rpc_services.push(service);
+    }
+
+    /// Adds a service trait declared with `rdc_service!` or `rdc_rpc_service!`, e.g. `ir.add_service_trait::<dyn UserApi>()`,
+    /// together with all the request and response types it uses.
+    pub fn add_service_trait<T>(&mut self)
+    where
+        T: RDCService + ?Sized,
+    {
+        T::add_to_ir(self);
+    }
+
+    /// This function removes a type, e.g. a dependency that is replaced with an existing hand-written class.
+    /// References to the type are kept, so the generated code uses a class with the same name, which has to be provided separately.
+    /// Adding the Rust type again with `add` has no effect.
+    /// It fails if there is no type with the given generated name in the IR.
+    pub fn remove_type(&mut self, name: &str) -> Result<(), Error> {
+        if !self.has_type(name) {
+            return Err(Error::new(&format!("`{name}` is not a type in the IR")));
+        }
+        self.retain_types(|n| n != name);
+        self.generated_names.remove(name);
+        Ok(())
+    }
+
+    /// Replaces the struct with the same generated name as `s`, keeping its position in the IR.
+    /// It fails if there is no such struct in the IR.
+    pub fn replace_struct(&mut self, s: Struct) -> Result<(), Error> {
+        let name = s.self_type().type_name().clone();
+        match self
+            .structs
+            .iter_mut()
+            .find(|current| current.self_type().type_name() == &name)
+        {
+            Some(current) => {
+                *current = s;
+                Ok(())
+            }
+            None => Err(Error::new(&format!("`{name}` is not a struct in the IR"))),
+        }
+    }
+
+    /// This function changes the generated name of a type and updates all the references to it
+    /// in fields, variants and services.
+    /// It fails if there is no type with the old name or if the new name is already used in the IR.
+    pub fn rename(&mut self, old: &str, new: &str) -> Result<(), Error> {
+        if !self.has_type(old) {
+            return Err(Error::new(&format!("`{old}` is not a type in the IR")));
+        }
+        if self.generated_names.contains_key(new) {
+            return Err(Error::new(&format!("`{new}` is already used in the IR")));
+        }
+        let self_types = self
+            .structs
+            .iter_mut()
+            .map(|s| (&mut s.name, &mut s.self_type))
+            .chain(
+                self.enums
+                    .iter_mut()
+                    .map(|e| (&mut e.name, &mut e.self_type)),
+            )
+            .chain(
+                self.data_enums
+                    .iter_mut()
+                    .map(|de| (&mut de.name, &mut de.self_type)),
+            )
+            .chain(
+                self.polymorphics
+                    .iter_mut()
+                    .map(|p| (&mut p.name, &mut p.self_type)),
+            );
+        for (name, self_type) in self_types.filter(|(_, t)| t.type_name() == old) {
+            *name = Name::from_pascal_case(new);
+            *self_type = CustomType::new(new);
+        }
+        let target = self.target.clone();
+        for t in self.type_references_mut() {
+            *t = renamed(t, old, new, &target);
+        }
+        if let Some(rust_type) = self.generated_names.remove(old) {
+            self.generated_names.insert(new.to_string(), rust_type);
+        }
+        Ok(())
+    }
+
+    /// This function sets the display name and the description of an enum variant, e.g. `("PaymentMethod", "CreditCard", "Credit Card", None)`,
+    /// like `#[rdc(display = ..., description = ...)]` on the variant does. The variant is given by its Rust name.
+    /// It fails if there is no such enum or variant in the IR.
+    pub fn set_variant_display(
+        &mut self,
+        type_name: &str,
+        variant: &str,
+        display_name: &str,
+        description: Option<&str>,
+    ) -> Result<(), Error> {
+        let v = self.enum_variant_mut(type_name, variant)?;
+        v.set_display_name(display_name);
+        if let Some(description) = description {
+            v.set_description(description);
+        }
+        Ok(())
+    }
+
+    /// This function sets the display name of an enum variant in a locale, e.g. `("PaymentMethod", "CreditCard", "de", "Kreditkarte")`,
+    /// where `set_variant_display` sets the one of the default locale, like `#[rdc(display(de = ...))]` on the variant does.
+    /// The translations end up in resource bundles generated with `targets::properties`.
+    /// It fails if there is no such enum or variant in the IR.
+    pub fn set_variant_translation(
+        &mut self,
+        type_name: &str,
+        variant: &str,
+        locale: &str,
+        display_name: &str,
+    ) -> Result<(), Error> {
+        self.enum_variant_mut(type_name, variant)?
+            .set_translation(locale, display_name);
+        Ok(())
+    }
+
+    fn enum_variant_mut(
+        &mut self,
+        type_name: &str,
+        variant: &str,
+    ) -> Result<&mut EnumVariant, Error> {
+        self.enums
+            .iter_mut()
+            .filter(|e| e.self_type().type_name() == type_name)
+            .flat_map(|e| e.variants.iter_mut())
+            .find(|v| v.name().as_pascal_case() == variant)
+            .ok_or_else(|| {
+                Error::new(&format!(
+                    "`{type_name}::{variant}` is not an enum variant in the IR"
+                ))
+            })
+    }
+
+    /// This function sets the text format of a date or time field, e.g. `("Event", "created_at", DateTimeFormat::new("yyyy-MM-dd"))`,
+    /// like `#[rdc(format = ..., timezone = ...)]` on the field does. The field is given by its Rust name.
+    /// It fails if there is no such struct or field in the IR.
+    pub fn set_field_format(
+        &mut self,
+        type_name: &str,
+        field: &str,
+        format: DateTimeFormat,
+    ) -> Result<(), Error> {
+        self.struct_field_mut(type_name, field)?.set_format(format);
+        Ok(())
+    }
+
+    /// This function sets an example value of a field, e.g. `("Invoice", "number", "INV-001")`, like `#[rdc(example = ...)]` does.
+    /// The example ends up in getter Javadoc and `@Schema` annotations, OpenAPI documents, JSON schemas and Java fixtures.
+    /// Examples of numeric and boolean fields are written as JSON numbers and booleans, other examples as strings.
+    /// It fails if there is no such struct or field in the IR.
+    pub fn set_field_example(
+        &mut self,
+        type_name: &str,
+        field: &str,
+        example: &str,
+    ) -> Result<(), Error> {
+        self.struct_field_mut(type_name, field)?
+            .set_example(example);
+        Ok(())
+    }
+
+    /// This function marks a field as read-only, e.g. `("Invoice", "id", true)`, so that targets generate no setter for it.
+    /// The field is given by its Rust name. Jackson still populates read-only fields when deserializing.
+    /// It fails if there is no such struct or field in the IR.
+    pub fn set_field_readonly(
+        &mut self,
+        type_name: &str,
+        field: &str,
+        readonly: bool,
+    ) -> Result<(), Error> {
+        self.struct_field_mut(type_name, field)?
+            .set_readonly(readonly);
+        Ok(())
+    }
+
+    /// This function sets the `serde_with` adapter of a field, e.g. `("Job", "timeout", SerdeAdapter::DurationSeconds)`,
+    /// and replaces the field type with the one the adapter writes, like `#[serde_as(as = ...)]` on a derived struct does.
+    /// The field is given by its Rust name.
+    /// It fails if there is no such struct or field in the IR, or if the adapter does not apply to the field type.
+    pub fn set_field_adapter(
+        &mut self,
+        type_name: &str,
+        field: &str,
+        adapter: SerdeAdapter,
+    ) -> Result<(), Error> {
+        let f = self.struct_field_mut(type_name, field)?;
+        let field_type = f.field_type.type_name().clone();
+        f.set_adapter(adapter).map_err(|_| {
+            Error::new(&format!(
+                "`{adapter:?}` does not apply to `{type_name}.{field}` of type `{field_type}`"
+            ))
+        })
+    }
+
+    fn struct_field_mut(&mut self, type_name: &str, field: &str) -> Result<&mut Field, Error> {
+        self.structs
+            .iter_mut()
+            .filter(|s| s.self_type().type_name() == type_name)
+            .flat_map(|s| s.fields.iter_mut())
+            .find(|f| f.name().as_snake_case() == field)
+            .ok_or_else(|| {
+                Error::new(&format!(
+                    "`{type_name}.{field}` is not a struct field in the IR"
+                ))
+            })
+    }
+
+    fn has_type(&self, name: &str) -> bool {
+        self.structs
+            .iter()
+            .any(|s| s.self_type().type_name() == name)
+            || self.enums.iter().any(|e| e.self_type().type_name() == name)
+            || self
+                .data_enums
+                .iter()
+                .any(|de| de.self_type().type_name() == name)
+            || self
+                .polymorphics
+                .iter()
+                .any(|p| p.self_type().type_name() == name)
+    }
+
+    /// Returns all the types referenced by fields, variants and services.
+    fn type_references_mut(&mut self) -> Vec<&mut Type> {
+        let mut references: Vec<&mut Type> = Vec::new();
+        for s in &mut self.structs {
+            references.extend(s.fields.iter_mut().map(|f| &mut f.field_type));
+        }
+        for de in &mut self.data_enums {
+            for v in &mut de.variants {
+                match v {
+                    DataEnumVariant::Object { fields, .. } => {
+                        references.extend(fields.iter_mut().map(|f| &mut f.field_type))
+                    }
+                    DataEnumVariant::Tuple { fields, .. } => references.extend(fields.iter_mut()),
+                    DataEnumVariant::Unit { .. } => {}
+                }
+            }
+        }
+        for p in &mut self.polymorphics {
+            references.extend(p.variants.iter_mut().map(|v| &mut v.variant_type));
+        }
+        for service in &mut self.services {
+            references.extend(service.types_mut());
+        }
+        for service in &mut self.rpc_services {
+            references.extend(service.types_mut());
+        }
+        references
+    }
+
+    /// Remembers which Rust type generated the name, so that different types
+    /// resolving to the same name (e.g. `Wrapper<a::Id>` and `Wrapper<b::Id>`) can be reported.
+    fn register_name(&mut self, self_type: &CustomType) {
+        let name = self_type.type_name();
+        let current = self.rust_types.last().copied();
+        if let Some(previous) = self.generated_names.get(name) {
+            let same_type = matches!(
+                (previous, current),
+                (Some(a), Some(b)) if a.type_id == b.type_id
+            );
+            if !same_type {
+                let describe = |t: &Option<RustType>| match t {
+                    Some(t) => format!("`{}`", t.name),
+                    None => "a manually added type".to_string(),
+                };
+                self.collisions.push(format!(
+                    "{} and {} both generate `{}`",
+                    describe(previous),
+                    describe(&current),
+                    name
+                ));
+            }
+        } else {
+            self.generated_names.insert(name.clone(), current);
+        }
+    }
+
+    /// Checks that the IR can be used for code generation.
+    /// It fails if multiple types resolve to the same generated name,
+    /// if an enum has no variants, if fields or variants of a single type share a JSON name,
+    /// or if a polymorphic type has variants that are not structs.
+    /// All the problems found are reported in a single error.
+    pub fn validate(&self) -> Result<(), Error> {
+        let mut problems: Vec<String> = self
+            .collisions
+            .iter()
+            .map(|c| format!("Generated name collision: {c}"))
+            .collect();
+        problems.extend(validation::empty_enums(self));
+        problems.extend(validation::duplicate_json_names(self));
+        problems.extend(validation::invalid_polymorphics(self));
+        problems.extend(validation::invalid_transparent_structs(self));
+        problems.extend(validation::unadapted_durations(self));
+        problems.extend(validation::invalid_services(self));
+        problems.extend(validation::invalid_rpc_services(self));
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::new(&problems.join("; ")))
+        }
+    }
+
+    /// Fails for targets that write transparent structs, e.g. ones declared with `rdc_newtype!`, as objects.
+    pub(crate) fn reject_transparent_structs(&self, target: &str) -> Result<(), Error> {
+        match self.structs.iter().find(|s| s.is_transparent()) {
+            Some(s) => Err(Error::new(&format!(
+                "Transparent struct `{}` is not supported by the {target} target",
+                s.self_type().type_name()
+            ))),
+            None => Ok(()),
+        }
+    }
+
     pub fn structs(&self) -> &[Struct] {
         &self.structs
     }
 
+    pub(crate) fn structs_mut(&mut self) -> &mut [Struct] {
+        &mut self.structs
+    }
+
+    /// Removes the types, except services, whose generated names are rejected by `keep`.
+    pub(crate) fn retain_types<F: Fn(&str) -> bool>(&mut self, keep: F) {
+        self.structs.retain(|s| keep(s.self_type().type_name()));
+        self.enums.retain(|e| keep(e.self_type().type_name()));
+        self.data_enums
+            .retain(|de| keep(de.self_type().type_name()));
+        self.polymorphics
+            .retain(|p| keep(p.self_type().type_name()));
+    }
+
+    /// Removes the services and JSON-RPC services whose names in pascal case are rejected by `keep`.
+    pub(crate) fn retain_services<F: Fn(&str) -> bool>(&mut self, keep: F) {
+        self.services.retain(|s| keep(&s.name().as_pascal_case()));
+        self.rpc_services
+            .retain(|s| keep(&s.name().as_pascal_case()));
+    }
+
     pub fn enums(&self) -> &[Enum] {
         &self.enums
     }
@@ -137,6 +620,35 @@ impl IntermediateRepresentation {
         &self.data_enums
     }
 
+    pub fn polymorphics(&self) -> &[Polymorphic] {
+        &self.polymorphics
+    }
+
+    pub fn services(&self) -> &[Service] {
+        &self.services
+    }
+
+    pub fn rpc_services(&self) -> &[RpcService] {
+        &self.rpc_services
+    }
+
+    /// Returns the name of the Rust type that generated the given name, as returned by `std::any::type_name`,
+    /// e.g. `my_crate::orders::Order`. It is `None` for unknown names and types added manually.
+    pub fn rust_type_name(&self, generated_name: &str) -> Option<&'static str> {
+        self.generated_names
+            .get(generated_name)
+            .and_then(|t| t.as_ref())
+            .map(|t| t.name)
+    }
+
+    /// Returns the module of the Rust type that generated the given name, e.g. `my_crate::orders`.
+    /// It is `None` for unknown names, types added manually and types declared outside of modules.
+    pub fn rust_module(&self, generated_name: &str) -> Option<&'static str> {
+        self.rust_type_name(generated_name)
+            .map(module_path)
+            .filter(|module| !module.is_empty())
+    }
+
     pub fn target(&self) -> &TypeTarget {
         &self.target
     }
@@ -152,17 +664,46 @@ impl IntermediateRepresentation {
     pub fn add<T: RDCType>(&mut self) {
         if !self.has_type_id(TypeId::of::<T>()) {
             self.add_type_id(TypeId::of::<T>());
+            self.rust_types.push(RustType {
+                type_id: TypeId::of::<T>(),
+                name: type_name::<T>(),
+            });
             T::add_to_ir(self);
+            self.rust_types.pop();
         }
     }
 }
 
+/// Returns the type with references to the `old` type replaced with the `new` one.
+fn renamed(t: &Type, old: &str, new: &str, target: &TypeTarget) -> Type {
+    match (t.kind(), target) {
+        (TypeKind::Named, _) if t.type_name() == old => Type::new(new),
+        (TypeKind::List(element), TypeTarget::Java) => {
+            list_type(renamed(element, old, new, target))
+        }
+        (TypeKind::Option(inner), TypeTarget::Java) => {
+            option_type(renamed(inner, old, new, target))
+        }
+        (TypeKind::Map(key, value), TypeTarget::Java) if is_sorted_map(t) => sorted_map_type(
+            renamed(key, old, new, target),
+            renamed(value, old, new, target),
+        ),
+        (TypeKind::Map(key, value), TypeTarget::Java) => map_type(
+            renamed(key, old, new, target),
+            renamed(value, old, new, target),
+        ),
+        _ => t.clone(),
+    }
+}
+
 impl Struct {
     pub fn new(name: Name, self_type: CustomType) -> Self {
         Self {
             name,
             self_type,
             fields: Vec::new(),
+            transparent: false,
+            shared_interfaces: Vec::new(),
         }
     }
 
@@ -170,6 +711,10 @@ impl Struct {
         self.fields.push(field);
     }
 
+    pub(crate) fn retain_fields<F: Fn(&Field) -> bool>(&mut self, keep: F) {
+        self.fields.retain(keep);
+    }
+
     pub fn name(&self) -> &Name {
         &self.name
     }
@@ -181,6 +726,26 @@ impl Struct {
     pub fn fields(&self) -> &Vec<Field> {
         &self.fields
     }
+
+    /// Marks the struct as a newtype, e.g. `struct Tags(HashMap<String, String>)`, whose JSON is the JSON of its only field,
+    /// like serde writes newtype structs and `#[serde(transparent)]` structs.
+    pub fn set_transparent(&mut self, transparent: bool) {
+        self.transparent = transparent;
+    }
+
+    pub fn is_transparent(&self) -> bool {
+        self.transparent
+    }
+
+    /// Names a shared interface the struct implements, like `#[rdc(implements_shared = ...)]` does.
+    /// Interfaces without fields in `JavaConfig::with_shared_interface` get the fields all the structs naming them share.
+    pub fn add_shared_interface<S: Into<String>>(&mut self, name: S) {
+        self.shared_interfaces.push(name.into());
+    }
+
+    pub fn shared_interfaces(&self) -> &[String] {
+        &self.shared_interfaces
+    }
 }
 
 impl Enum {
@@ -189,6 +754,7 @@ impl Enum {
             name,
             self_type,
             variants: Vec::new(),
+            flags: None,
         }
     }
 
@@ -196,6 +762,16 @@ impl Enum {
         self.variants.push(variant);
     }
 
+    /// Marks the enum as a set of flags, represented in JSON in the given format.
+    pub fn set_flags(&mut self, format: FlagsFormat) {
+        self.flags = Some(format);
+    }
+
+    /// Returns the JSON format of the enum if it is a set of flags.
+    pub fn flags(&self) -> Option<FlagsFormat> {
+        self.flags
+    }
+
     pub fn name(&self) -> &Name {
         &self.name
     }
@@ -240,15 +816,89 @@ impl DataEnum {
     }
 }
 
+impl Polymorphic {
+    /// Creates a polymorphic type.
+    /// Without a tag, values are wrapped in an object with the variant name as its only key, like `{"Circle": {...}}`.
+    /// With a tag, the variant name is stored in the tag field next to the other fields, like `{"type": "Circle", ...}`.
+    pub fn new(name: Name, self_type: CustomType, tag: Option<String>) -> Self {
+        Self {
+            name,
+            self_type,
+            tag,
+            variants: Vec::new(),
+            envelope: false,
+        }
+    }
+
+    /// Marks the type as a message envelope declared with `rdc_envelope!`,
+    /// for which targets also generate message handlers.
+    pub fn set_envelope(&mut self, envelope: bool) {
+        self.envelope = envelope;
+    }
+
+    pub fn add_variant(&mut self, variant: PolymorphicVariant) {
+        self.variants.push(variant);
+    }
+
+    pub fn name(&self) -> &Name {
+        &self.name
+    }
+
+    pub fn self_type(&self) -> &CustomType {
+        &self.self_type
+    }
+
+    pub fn tag(&self) -> Option<&str> {
+        self.tag.as_deref()
+    }
+
+    pub fn is_envelope(&self) -> bool {
+        self.envelope
+    }
+
+    pub fn variants(&self) -> &Vec<PolymorphicVariant> {
+        &self.variants
+    }
+}
+
+impl PolymorphicVariant {
+    pub fn new<S: Into<String>>(json_name: S, variant_type: Type) -> Self {
+        Self {
+            json_name: json_name.into(),
+            variant_type,
+        }
+    }
+
+    pub fn json_name(&self) -> &String {
+        &self.json_name
+    }
+
+    pub fn variant_type(&self) -> &Type {
+        &self.variant_type
+    }
+}
+
 impl Field {
     pub fn new<S: Into<String>>(name: Name, json_name: S, field_type: Type) -> Self {
         Self {
+            json_name: json_name_of(&name, json_name.into()),
             name,
-            json_name: json_name.into(),
             field_type,
+            readonly: false,
+            format: None,
+            adapter: None,
+            example: None,
+            column_type: None,
+            primary_key: false,
         }
     }
 
+    /// Marks the field as read-only.
+    /// Read-only fields can only be populated by deserialization, so targets should not generate setters for them.
+    pub fn set_readonly(&mut self, readonly: bool) {
+        self.readonly = readonly;
+    }
+
     pub fn name(&self) -> &Name {
         &self.name
     }
@@ -260,16 +910,161 @@ impl Field {
     pub fn field_type(&self) -> &Type {
         &self.field_type
     }
+
+    pub fn is_readonly(&self) -> bool {
+        self.readonly
+    }
+
+    /// Returns true if the field must be present in JSON. Fields are optional only when their type is an `Option`,
+    /// which serde accepts as missing or `null`. Targets report optionality from this flag,
+    /// e.g. as `required` in schemas or `@Nullable` in Java, so that they never disagree.
+    pub fn is_required(&self) -> bool {
+        !matches!(self.field_type.kind(), TypeKind::Option(_))
+    }
+
+    /// Sets the text format of the field, which targets reproduce, e.g. with `@JsonFormat` in Java.
+    pub fn set_format(&mut self, format: DateTimeFormat) {
+        self.format = Some(format);
+    }
+
+    pub fn format(&self) -> Option<&DateTimeFormat> {
+        self.format.as_ref()
+    }
+
+    /// Sets the `serde_with` adapter of the field and replaces the field type with the one the adapter writes,
+    /// like `#[serde_as(as = ...)]` on the field does. It fails if the adapter does not apply to the field type.
+    pub fn set_adapter(&mut self, adapter: SerdeAdapter) -> Result<(), Error> {
+        let wire_type = adapter.wire_type(&self.field_type).ok_or_else(|| {
+            Error::new(&format!(
+                "`{adapter:?}` does not apply to `{}` of type `{}`",
+                self.name.as_snake_case(),
+                self.field_type.type_name()
+            ))
+        })?;
+        self.field_type = wire_type;
+        self.adapter = Some(adapter);
+        Ok(())
+    }
+
+    /// Returns the `serde_with` adapter of the field. The field type is already the one written by the adapter.
+    pub fn adapter(&self) -> Option<SerdeAdapter> {
+        self.adapter
+    }
+
+    /// Sets an example value of the field, e.g. `INV-001` or `42`, which targets show in documentation and fixtures.
+    pub fn set_example<S: Into<String>>(&mut self, example: S) {
+        self.example = Some(example.into());
+    }
+
+    pub fn example(&self) -> Option<&str> {
+        self.example.as_deref()
+    }
+
+    /// Sets the SQL type of the column of the field, e.g. `NUMERIC(12, 2)`, which `targets::sql` uses
+    /// instead of the type mapped from the field type.
+    pub fn set_column_type<S: Into<String>>(&mut self, column_type: S) {
+        self.column_type = Some(column_type.into());
+    }
+
+    pub fn column_type(&self) -> Option<&str> {
+        self.column_type.as_deref()
+    }
+
+    /// Makes the field a part of the primary key of the table `targets::sql` generates for the struct.
+    pub fn set_primary_key(&mut self, primary_key: bool) {
+        self.primary_key = primary_key;
+    }
+
+    pub fn is_primary_key(&self) -> bool {
+        self.primary_key
+    }
+}
+
+impl SerdeAdapter {
+    /// Returns the type written by the adapter for a field of the given type, or `None` if the adapter does not apply to it.
+    fn wire_type(&self, t: &Type) -> Option<Type> {
+        match (self, t.kind()) {
+            (_, TypeKind::Option(inner)) => self.wire_type(inner).map(option_type),
+            (SerdeAdapter::DisplayFromStr, TypeKind::List(element)) => {
+                self.wire_type(element).map(list_type)
+            }
+            (SerdeAdapter::DisplayFromStr, TypeKind::Named | TypeKind::UnsignedLong) => {
+                Some(Type::new("String"))
+            }
+            (SerdeAdapter::DurationSeconds, TypeKind::Named) if t.type_name() == DURATION => {
+                Some(Type::new("Long"))
+            }
+            (SerdeAdapter::MapAsPairs, TypeKind::Map(key, value)) => {
+                let element = match key.type_name() == value.type_name() {
+                    true => key.as_ref().clone(),
+                    false => Type::new("com.fasterxml.jackson.databind.JsonNode"),
+                };
+                Some(list_type(list_type(element)))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl DateTimeFormat {
+    pub fn new<S: Into<String>>(pattern: S) -> Self {
+        Self {
+            pattern: pattern.into(),
+            timezone: None,
+        }
+    }
+
+    /// Sets the time zone the values are formatted in, e.g. `UTC` or `Europe/Warsaw`.
+    pub fn with_timezone<S: Into<String>>(mut self, timezone: S) -> Self {
+        self.timezone = Some(timezone.into());
+        self
+    }
+
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn timezone(&self) -> Option<&str> {
+        self.timezone.as_deref()
+    }
 }
 
 impl EnumVariant {
     pub fn new<S: Into<String>>(name: Name, json_name: S) -> Self {
         Self {
+            json_name: json_name_of(&name, json_name.into()),
             name,
-            json_name: json_name.into(),
+            bits: None,
+            display_name: None,
+            translations: BTreeMap::new(),
+            description: None,
         }
     }
 
+    /// Sets the bits of the variant, when it is a flag of a flags enum.
+    pub fn set_bits(&mut self, bits: u64) {
+        self.bits = Some(bits);
+    }
+
+    /// Sets the human-readable name of the variant, e.g. `Credit Card`.
+    pub fn set_display_name<S: Into<String>>(&mut self, display_name: S) {
+        self.display_name = Some(display_name.into());
+    }
+
+    /// Sets the human-readable name of the variant in the given locale, e.g. `de` and `Kreditkarte`.
+    pub fn set_translation<L: Into<String>, S: Into<String>>(
+        &mut self,
+        locale: L,
+        display_name: S,
+    ) {
+        self.translations.insert(locale.into(), display_name.into());
+    }
+
+    /// Sets the description of the variant.
+    pub fn set_description<S: Into<String>>(&mut self, description: S) {
+        self.description = Some(description.into());
+    }
+
     pub fn name(&self) -> &Name {
         &self.name
     }
@@ -277,27 +1072,47 @@ impl EnumVariant {
     pub fn json_name(&self) -> &String {
         &self.json_name
     }
+
+    pub fn bits(&self) -> Option<u64> {
+        self.bits
+    }
+
+    pub fn display_name(&self) -> Option<&str> {
+        self.display_name.as_deref()
+    }
+
+    /// Returns the display names of the variant by locale, sorted by locale.
+    pub fn translations(&self) -> &BTreeMap<String, String> {
+        &self.translations
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
 }
 
 impl DataEnumVariant {
     pub fn object(name: Name, json_name: String, fields: Vec<DataEnumObjectField>) -> Self {
         Self::Object {
+            json_name: json_name_of(&name, json_name),
             name,
-            json_name,
             fields,
         }
     }
 
     pub fn tuple(name: Name, json_name: String, fields: Vec<Type>) -> Self {
         Self::Tuple {
+            json_name: json_name_of(&name, json_name),
             name,
-            json_name,
             fields,
         }
     }
 
     pub fn unit(name: Name, json_name: String) -> Self {
-        Self::Unit { name, json_name }
+        Self::Unit {
+            json_name: json_name_of(&name, json_name),
+            name,
+        }
     }
 
     pub fn name(&self) -> &Name {
@@ -320,8 +1135,8 @@ impl DataEnumVariant {
 impl DataEnumObjectField {
     pub fn new(name: Name, json_name: String, field_type: Type) -> Self {
         Self {
+            json_name: json_name_of(&name, json_name),
             name,
-            json_name,
             field_type,
         }
     }
@@ -335,15 +1150,31 @@ impl DataEnumObjectField {
     pub fn field_type(&self) -> &Type {
         &self.field_type
     }
+
+    /// Returns true if the field must be present in JSON, like `Field::is_required`.
+    pub fn is_required(&self) -> bool {
+        !matches!(self.field_type.kind(), TypeKind::Option(_))
+    }
 }
 
 impl Type {
     pub fn new<S: Into<String>>(type_name: S) -> Self {
-        Self(type_name.into())
+        Self::with_kind(type_name, TypeKind::Named)
+    }
+
+    pub fn with_kind<S: Into<String>>(type_name: S, kind: TypeKind) -> Self {
+        Self {
+            type_name: type_name.into(),
+            kind,
+        }
     }
 
     pub fn type_name(&self) -> &String {
-        &self.0
+        &self.type_name
+    }
+
+    pub fn kind(&self) -> &TypeKind {
+        &self.kind
     }
 }
 
@@ -357,41 +1188,99 @@ impl CustomType {
     }
 }
 
+/// This function strips the `r#` prefix of raw identifiers, e.g. `r#type` becomes `type`.
+/// Serde uses identifiers without the prefix, so it is never a part of names.
+fn unraw(identifier: String) -> String {
+    match identifier.strip_prefix("r#") {
+        Some(stripped) => stripped.to_string(),
+        None => identifier,
+    }
+}
+
+/// This function returns the identifier if it is a raw identifier, e.g. `r#type`.
+fn raw_identifier(identifier: &str) -> Option<String> {
+    identifier.starts_with("r#").then(|| identifier.to_string())
+}
+
+/// This function returns the JSON name of a field or a variant. A JSON name derived from a raw identifier,
+/// i.e. equal to the identifier the name was created from, loses its `r#` prefix like in serde,
+/// while an explicit rename, e.g. `#[serde(rename = "r#kind")]`, is kept as it is.
+fn json_name_of(name: &Name, json_name: String) -> String {
+    match &name.raw {
+        Some(raw) if *raw == json_name => unraw(json_name),
+        _ => json_name,
+    }
+}
+
 impl Name {
+    /// Creates a name from a snake case identifier.
+    /// Case conversions support non-ASCII letters, so target languages allowing Unicode identifiers can use them directly.
     pub fn from_snake_case<S: Into<String>>(snake_case: S) -> Self {
+        let snake_case = snake_case.into();
         Self {
-            snake_case: snake_case.into(),
+            raw: raw_identifier(&snake_case),
+            snake_case: unraw(snake_case),
         }
     }
 
     pub fn from_camel_case<S: Into<String>>(camel_case: S) -> Self {
         let camel_case = camel_case.into();
+        let raw = raw_identifier(&camel_case);
+        let camel_case = unraw(camel_case);
         let mut snake_case = String::new();
         let mut chars = camel_case.chars();
         if let Some(first_char) = chars.next() {
-            snake_case.push(first_char.to_ascii_lowercase());
+            snake_case.extend(first_char.to_lowercase());
         }
         for char in chars {
-            if char.is_ascii_uppercase() {
+            if char.is_uppercase() {
                 snake_case.push('_');
-                snake_case.push(char.to_ascii_lowercase());
+                snake_case.extend(char.to_lowercase());
             } else {
                 snake_case.push(char);
             }
         }
-        Self { snake_case }
+        Self { snake_case, raw }
     }
 
     pub fn from_pascal_case<S: Into<String>>(pascal_case: S) -> Self {
         Self::from_camel_case(pascal_case)
     }
 
+    /// Creates a name from a kebab case identifier, e.g. `payment-pending`. Screaming kebab case is accepted as well.
+    pub fn from_kebab_case<S: Into<String>>(kebab_case: S) -> Self {
+        Self {
+            snake_case: kebab_case.into().replace('-', "_").to_lowercase(),
+            raw: None,
+        }
+    }
+
     pub fn as_snake_case(&self) -> String {
         self.snake_case.clone()
     }
 
     pub fn as_upper_snake_case(&self) -> String {
-        self.snake_case.to_ascii_uppercase()
+        self.snake_case.to_uppercase()
+    }
+
+    pub fn as_kebab_case(&self) -> String {
+        self.snake_case.replace('_', "-")
+    }
+
+    /// Returns the name in screaming kebab case, e.g. `PAYMENT-PENDING`.
+    pub fn as_upper_kebab_case(&self) -> String {
+        self.as_kebab_case().to_uppercase()
+    }
+
+    /// Returns the words of the name in lower case without separators, e.g. `paymentpending`,
+    /// like serde `rename_all = "lowercase"` renders variants.
+    pub fn as_lower_case(&self) -> String {
+        self.snake_case.replace('_', "")
+    }
+
+    /// Returns the words of the name in upper case without separators, e.g. `PAYMENTPENDING`.
+    pub fn as_upper_case(&self) -> String {
+        self.as_lower_case().to_uppercase()
     }
 
     pub fn as_camel_case(&self) -> String {
@@ -399,9 +1288,8 @@ impl Name {
         let mut chars = self.snake_case.chars().peekable();
         while let Some(char) = chars.next() {
             if char == '_' {
-                if let Some(next_char) = chars.peek() {
-                    camel_case.push(next_char.to_ascii_uppercase());
-                    chars.next();
+                if let Some(next_char) = chars.next() {
+                    camel_case.extend(next_char.to_uppercase());
                 }
             } else {
                 camel_case.push(char);
@@ -413,15 +1301,337 @@ impl Name {
     pub fn as_pascal_case(&self) -> String {
         let mut camel_case = self.as_camel_case();
         if let Some(first_char) = camel_case.chars().next() {
-            camel_case.replace_range(..1, &first_char.to_ascii_uppercase().to_string());
+            camel_case.replace_range(
+                ..first_char.len_utf8(),
+                &first_char.to_uppercase().to_string(),
+            );
         }
         camel_case
     }
 }
 
+/// Returns the module of a Rust type name, e.g. `shop::orders` for `shop::orders::Order<shop::Id>`.
+/// Polymorphic types are added as boxed trait objects, so they belong to the module of the trait.
+pub(crate) fn module_path(rust_type_name: &str) -> &str {
+    let name = rust_type_name
+        .trim_start_matches("alloc::boxed::Box<")
+        .trim_start_matches("dyn ")
+        .split('<')
+        .next()
+        .unwrap_or(rust_type_name);
+    name.rsplit_once("::")
+        .map(|(module, _)| module)
+        .unwrap_or("")
+}
+
+#[cfg(test)]
 mod tests {
     #[allow(unused_imports)]
     use super::*;
+    use crate as rdc;
+    use crate::RDC;
+
+    mod first {
+        use crate as rdc;
+        use crate::RDC;
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub struct Id {
+            value: String,
+        }
+    }
+
+    mod second {
+        use crate as rdc;
+        use crate::RDC;
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub struct Id {
+            value: i64,
+        }
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Wrapper<T>
+    where
+        T: RDCType,
+    {
+        id: T,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Container {
+        list: Vec<first::Id>,
+        single: first::Id,
+        optional: Option<Box<first::Id>>,
+    }
+
+    #[test]
+    fn test_name_collision() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Wrapper<first::Id>>();
+        ir.add::<Wrapper<second::Id>>();
+        let error = ir.validate().unwrap_err();
+        assert!(error.message().contains("both generate `WrapperId`"));
+        assert!(error.message().contains("both generate `Id`"));
+    }
+
+    #[test]
+    fn test_duplicate_json_names() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        let mut s = Struct::new(Name::from_pascal_case("User"), CustomType::new("User"));
+        s.add_field(Field::new(
+            Name::from_snake_case("id"),
+            "id",
+            Type::new("String"),
+        ));
+        s.add_field(Field::new(
+            Name::from_snake_case("user_id"),
+            "id",
+            Type::new("String"),
+        ));
+        ir.add_struct(s);
+        let mut e = Enum::new(Name::from_pascal_case("Status"), CustomType::new("Status"));
+        e.add_variant(EnumVariant::new(Name::from_pascal_case("Active"), "ACTIVE"));
+        e.add_variant(EnumVariant::new(
+            Name::from_pascal_case("Enabled"),
+            "ACTIVE",
+        ));
+        e.add_variant(EnumVariant::new(
+            Name::from_pascal_case("Disabled"),
+            "DISABLED",
+        ));
+        ir.add_enum(e);
+
+        let error = ir.validate().unwrap_err();
+        assert!(error
+            .message()
+            .contains("Struct `User` has multiple fields with JSON name `id`: `id`, `user_id`"));
+        assert!(error.message().contains(
+            "Enum `Status` has multiple variants with JSON name `ACTIVE`: `Active`, `Enabled`"
+        ));
+    }
+
+    #[derive(RDC)]
+    enum Never {}
+
+    #[test]
+    fn test_empty_enum() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Never>();
+        let error = ir.validate().unwrap_err();
+        assert_eq!(
+            error.message(),
+            "Enum `Never` has no variants, so it can not be represented in JSON"
+        );
+    }
+
+    #[test]
+    fn test_invalid_polymorphic() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        let mut s = Struct::new(Name::from_pascal_case("Circle"), CustomType::new("Circle"));
+        s.add_field(Field::new(
+            Name::from_snake_case("kind"),
+            "type",
+            Type::new("String"),
+        ));
+        ir.add_struct(s);
+        let mut p = Polymorphic::new(
+            Name::from_pascal_case("Shape"),
+            CustomType::new("Shape"),
+            Some("type".to_string()),
+        );
+        p.add_variant(PolymorphicVariant::new("Circle", Type::new("Circle")));
+        p.add_variant(PolymorphicVariant::new("Square", Type::new("Integer")));
+        ir.add_polymorphic(p);
+
+        let error = ir.validate().unwrap_err();
+        assert!(error.message().contains(
+            "Polymorphic type `Shape` uses tag `type`, which is also a field of `Circle`"
+        ));
+        assert!(error
+            .message()
+            .contains("Polymorphic type `Shape` has variant `Integer`, which is not a struct"));
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Job {
+        id: u64,
+        tags: Option<Vec<i32>>,
+        timeout: std::time::Duration,
+        labels: HashMap<String, String>,
+        limits: HashMap<String, i32>,
+    }
+
+    #[test]
+    fn test_serde_adapters() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Job>();
+        assert_eq!(
+            ir.validate().unwrap_err().message(),
+            "`Job.timeout` is a `Duration`, which is only supported in struct fields with `SerdeAdapter::DurationSeconds`"
+        );
+        ir.set_field_adapter("Job", "id", SerdeAdapter::DisplayFromStr)
+            .unwrap();
+        ir.set_field_adapter("Job", "tags", SerdeAdapter::DisplayFromStr)
+            .unwrap();
+        ir.set_field_adapter("Job", "timeout", SerdeAdapter::DurationSeconds)
+            .unwrap();
+        ir.set_field_adapter("Job", "labels", SerdeAdapter::MapAsPairs)
+            .unwrap();
+        ir.set_field_adapter("Job", "limits", SerdeAdapter::MapAsPairs)
+            .unwrap();
+        ir.validate().unwrap();
+        let fields = ir.structs()[0].fields();
+        let types = fields
+            .iter()
+            .map(|f| f.field_type().type_name().as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(
+            types,
+            [
+                "String",
+                "java.util.List<String>",
+                "Long",
+                "java.util.List<java.util.List<String>>",
+                "java.util.List<java.util.List<com.fasterxml.jackson.databind.JsonNode>>",
+            ]
+        );
+        assert!(matches!(fields[1].field_type().kind(), TypeKind::Option(_)));
+        assert_eq!(fields[2].adapter(), Some(SerdeAdapter::DurationSeconds));
+        assert_eq!(
+            ir.set_field_adapter("Job", "id", SerdeAdapter::MapAsPairs)
+                .unwrap_err()
+                .message(),
+            "`MapAsPairs` does not apply to `Job.id` of type `String`"
+        );
+    }
+
+    /// Fields as `#[serde_as]` leaves them for the derive, e.g. `#[serde_as(as = "DisplayFromStr")]` becomes
+    /// `#[serde(with = "::serde_with::As::<DisplayFromStr>")]`.
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct AdaptedJob {
+        #[serde(with = ":: serde_with :: As :: < DisplayFromStr >")]
+        id: u64,
+        #[serde(with = "::serde_with::As::<Option<Vec<serde_with::DisplayFromStr>>>")]
+        tags: Option<Vec<i32>>,
+        #[serde(with = "::serde_with::As::<DurationSeconds<u64>>")]
+        timeout: std::time::Duration,
+        #[serde(rename = "labelPairs", with = "::serde_with::As::<Vec<(_, _)>>")]
+        labels: HashMap<String, String>,
+        #[serde(with = "::serde_with::As::<Base64>")]
+        payload: Vec<u8>,
+    }
+
+    #[test]
+    fn test_derived_serde_adapters() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<AdaptedJob>();
+        ir.validate().unwrap();
+        let fields = ir.structs()[0].fields();
+        let adapters = fields
+            .iter()
+            .map(|f| f.adapter())
+            .collect::<Vec<Option<SerdeAdapter>>>();
+        assert_eq!(
+            adapters,
+            [
+                Some(SerdeAdapter::DisplayFromStr),
+                Some(SerdeAdapter::DisplayFromStr),
+                Some(SerdeAdapter::DurationSeconds),
+                Some(SerdeAdapter::MapAsPairs),
+                None,
+            ]
+        );
+        assert_eq!(fields[1].field_type().type_name(), "java.util.List<String>");
+        assert_eq!(fields[3].json_name(), "labelPairs");
+    }
+
+    #[test]
+    fn test_container_dependencies_added_once() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Container>();
+        assert!(ir.validate().is_ok());
+        assert_eq!(ir.structs().len(), 2);
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Order {
+        items: Vec<Item>,
+        gift: Option<Item>,
+        customer: Customer,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Item {
+        sku: String,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Customer {
+        name: String,
+    }
+
+    #[test]
+    fn test_mutations() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Order>();
+        ir.remove_type("Customer").unwrap();
+        assert_eq!(ir.structs().len(), 2);
+        ir.rename("Item", "LineItem").unwrap();
+        let order = ir
+            .structs()
+            .iter()
+            .find(|s| s.self_type().type_name() == "Order")
+            .unwrap();
+        let types = order
+            .fields()
+            .iter()
+            .map(|f| f.field_type().type_name().as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(types, ["java.util.List<LineItem>", "LineItem", "Customer"]);
+        assert!(ir
+            .structs()
+            .iter()
+            .any(|s| s.self_type().type_name() == "LineItem"));
+        let mut item = Struct::new(
+            Name::from_pascal_case("LineItem"),
+            CustomType::new("LineItem"),
+        );
+        item.add_field(Field::new(
+            Name::from_snake_case("code"),
+            "code",
+            Type::new("String"),
+        ));
+        ir.replace_struct(item).unwrap();
+        let item = ir
+            .structs()
+            .iter()
+            .find(|s| s.self_type().type_name() == "LineItem")
+            .unwrap();
+        assert_eq!(item.fields()[0].json_name(), "code");
+        assert!(ir.validate().is_ok());
+        let error = ir.remove_type("Customer").unwrap_err();
+        assert_eq!(error.message(), "`Customer` is not a type in the IR");
+        let error = ir.rename("Order", "LineItem").unwrap_err();
+        assert_eq!(error.message(), "`LineItem` is already used in the IR");
+        let error = ir
+            .replace_struct(Struct::new(
+                Name::from_pascal_case("Invoice"),
+                CustomType::new("Invoice"),
+            ))
+            .unwrap_err();
+        assert_eq!(error.message(), "`Invoice` is not a struct in the IR");
+    }
 
     #[test]
     fn test_name_from_snake_case() {
@@ -449,4 +1659,47 @@ mod tests {
         assert_eq!(name.as_pascal_case(), "PascalCase");
         assert_eq!(name.as_upper_snake_case(), "PASCAL_CASE");
     }
+
+    #[test]
+    fn test_name_kebab_and_flat_cases() {
+        let name = Name::from_kebab_case("PAYMENT-PENDING");
+        assert_eq!(name.as_snake_case(), "payment_pending");
+        assert_eq!(name.as_kebab_case(), "payment-pending");
+        assert_eq!(name.as_upper_kebab_case(), "PAYMENT-PENDING");
+        assert_eq!(name.as_lower_case(), "paymentpending");
+        assert_eq!(name.as_upper_case(), "PAYMENTPENDING");
+    }
+
+    #[test]
+    fn test_name_non_ascii() {
+        let name = Name::from_snake_case("état_civil");
+        assert_eq!(name.as_camel_case(), "étatCivil");
+        assert_eq!(name.as_pascal_case(), "ÉtatCivil");
+        assert_eq!(name.as_upper_snake_case(), "ÉTAT_CIVIL");
+        let name = Name::from_pascal_case("ÉtatÜbersicht");
+        assert_eq!(name.as_snake_case(), "état_übersicht");
+    }
+
+    #[test]
+    fn test_raw_identifiers() {
+        let name = Name::from_snake_case("r#type");
+        assert_eq!(name.as_snake_case(), "type");
+        assert_eq!(name.as_pascal_case(), "Type");
+        let field = Field::new(name, "r#type", Type::new("String"));
+        assert_eq!(field.json_name(), "type");
+        let field = Field::new(Name::from_snake_case("r#type"), "kind", Type::new("String"));
+        assert_eq!(field.json_name(), "kind");
+        let field = Field::new(
+            Name::from_snake_case("r#type"),
+            "r#kind",
+            Type::new("String"),
+        );
+        assert_eq!(field.json_name(), "r#kind");
+        let field = Field::new(Name::from_snake_case("kind"), "r#kind", Type::new("String"));
+        assert_eq!(field.json_name(), "r#kind");
+        let variant = EnumVariant::new(Name::from_pascal_case("r#Match"), "r#Match");
+        assert_eq!(variant.json_name(), "Match");
+        let variant = DataEnumVariant::unit(Name::from_pascal_case("Match"), "r#Match".to_string());
+        assert_eq!(variant.json_name(), "r#Match");
+    }
 }
@@ -0,0 +1,637 @@
+use crate::errors::Error;
+use crate::ir::{DataEnumVariant, Field, IntermediateRepresentation, Type, TypeKind};
+use std::collections::HashSet;
+
+/// This is a change between two versions of an IR, as found by `diff`.
+/// Types are matched by name, fields and variants by their JSON names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    TypeAdded {
+        type_name: String,
+    },
+    TypeRemoved {
+        type_name: String,
+    },
+    /// The type changed its kind, e.g. a struct became an enum, or a polymorphic type changed its tag.
+    TypeChanged {
+        type_name: String,
+    },
+    FieldAdded {
+        type_name: String,
+        json_name: String,
+        optional: bool,
+    },
+    FieldRemoved {
+        type_name: String,
+        json_name: String,
+    },
+    /// The JSON name of a field changed, while its Rust name stayed the same.
+    FieldRenamed {
+        type_name: String,
+        old_json_name: String,
+        new_json_name: String,
+    },
+    FieldTypeChanged {
+        type_name: String,
+        json_name: String,
+        old_type: String,
+        new_type: String,
+    },
+    VariantAdded {
+        type_name: String,
+        json_name: String,
+    },
+    VariantRemoved {
+        type_name: String,
+        json_name: String,
+    },
+    /// The payload of a data enum variant, or the type of a polymorphic variant changed.
+    VariantChanged {
+        type_name: String,
+        json_name: String,
+    },
+}
+
+impl Change {
+    /// Returns the name of the changed type.
+    pub fn type_name(&self) -> &str {
+        match self {
+            Change::TypeAdded { type_name }
+            | Change::TypeRemoved { type_name }
+            | Change::TypeChanged { type_name }
+            | Change::FieldAdded { type_name, .. }
+            | Change::FieldRemoved { type_name, .. }
+            | Change::FieldRenamed { type_name, .. }
+            | Change::FieldTypeChanged { type_name, .. }
+            | Change::VariantAdded { type_name, .. }
+            | Change::VariantRemoved { type_name, .. }
+            | Change::VariantChanged { type_name, .. } => type_name,
+        }
+    }
+
+    /// Returns the changed element, e.g. `Invoice`, `Invoice.total` or `Status::Paid`,
+    /// which is used to acknowledge breaking changes in `check_compatibility`.
+    pub fn subject(&self) -> String {
+        match self {
+            Change::TypeAdded { type_name }
+            | Change::TypeRemoved { type_name }
+            | Change::TypeChanged { type_name } => type_name.clone(),
+            Change::FieldAdded {
+                type_name,
+                json_name,
+                ..
+            }
+            | Change::FieldRemoved {
+                type_name,
+                json_name,
+            }
+            | Change::FieldRenamed {
+                type_name,
+                old_json_name: json_name,
+                ..
+            }
+            | Change::FieldTypeChanged {
+                type_name,
+                json_name,
+                ..
+            } => format!("{type_name}.{json_name}"),
+            Change::VariantAdded {
+                type_name,
+                json_name,
+            }
+            | Change::VariantRemoved {
+                type_name,
+                json_name,
+            }
+            | Change::VariantChanged {
+                type_name,
+                json_name,
+            } => format!("{type_name}::{json_name}"),
+        }
+    }
+
+    /// Returns true if payloads written with the old version may fail to deserialize into the new one.
+    /// Removed fields count as breaking, because Jackson fails on unknown properties by default.
+    pub fn is_breaking(&self) -> bool {
+        match self {
+            Change::TypeAdded { .. } | Change::VariantAdded { .. } => false,
+            Change::FieldAdded { optional, .. } => !optional,
+            _ => true,
+        }
+    }
+
+    /// Returns true if old payloads can not be upgraded mechanically, so a migration has to be written by hand.
+    pub fn needs_manual_migration(&self) -> bool {
+        match self {
+            Change::TypeAdded { .. }
+            | Change::VariantAdded { .. }
+            | Change::FieldRemoved { .. }
+            | Change::FieldRenamed { .. } => false,
+            Change::FieldAdded { optional, .. } => !optional,
+            _ => true,
+        }
+    }
+
+    /// Returns a human readable description of the change.
+    pub fn description(&self) -> String {
+        match self {
+            Change::TypeAdded { type_name } => format!("Type `{type_name}` was added"),
+            Change::TypeRemoved { type_name } => format!("Type `{type_name}` was removed"),
+            Change::TypeChanged { type_name } => {
+                format!("Type `{type_name}` changed its representation")
+            }
+            Change::FieldAdded {
+                type_name,
+                json_name,
+                optional,
+            } => format!(
+                "{} field `{type_name}.{json_name}` was added",
+                if *optional { "Optional" } else { "Required" }
+            ),
+            Change::FieldRemoved {
+                type_name,
+                json_name,
+            } => format!("Field `{type_name}.{json_name}` was removed"),
+            Change::FieldRenamed {
+                type_name,
+                old_json_name,
+                new_json_name,
+            } => format!("Field `{type_name}.{old_json_name}` was renamed to `{new_json_name}`"),
+            Change::FieldTypeChanged {
+                type_name,
+                json_name,
+                old_type,
+                new_type,
+            } => format!(
+                "Field `{type_name}.{json_name}` changed its type from `{old_type}` to `{new_type}`"
+            ),
+            Change::VariantAdded {
+                type_name,
+                json_name,
+            } => format!("Variant `{type_name}::{json_name}` was added"),
+            Change::VariantRemoved {
+                type_name,
+                json_name,
+            } => format!("Variant `{type_name}::{json_name}` was removed"),
+            Change::VariantChanged {
+                type_name,
+                json_name,
+            } => format!("Variant `{type_name}::{json_name}` changed its payload"),
+        }
+    }
+}
+
+/// This function compares two versions of an IR, e.g. one restored from a snapshot and the current one,
+/// and returns the changes of their JSON representation.
+///
+/// Fields are matched by their JSON names first, and then by their Rust names,
+/// so a field renamed with `#[serde(rename)]` is reported as renamed rather than removed and added.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{diff, Change, IntermediateRepresentation, TypeTarget};
+///
+/// mod v1 {
+///     #[derive(rdc::RDC)]
+///     pub struct Invoice {
+///         pub number: String,
+///     }
+/// }
+///
+/// mod v2 {
+///     #[derive(rdc::RDC)]
+///     pub struct Invoice {
+///         pub number: String,
+///         pub note: Option<String>,
+///     }
+/// }
+///
+/// let mut old = IntermediateRepresentation::new(TypeTarget::Java);
+/// old.add::<v1::Invoice>();
+/// let mut new = IntermediateRepresentation::new(TypeTarget::Java);
+/// new.add::<v2::Invoice>();
+/// let changes = diff(&old, &new);
+/// assert_eq!(changes[0].description(), "Optional field `Invoice.note` was added");
+/// assert!(!changes[0].is_breaking());
+/// ```
+pub fn diff(old: &IntermediateRepresentation, new: &IntermediateRepresentation) -> Vec<Change> {
+    let old_types = type_shapes(old);
+    let new_types = type_shapes(new);
+    let mut changes = Vec::new();
+    for (type_name, old_shape) in &old_types {
+        let type_name = type_name.to_string();
+        match new_types.iter().find(|(n, _)| **n == type_name) {
+            None => changes.push(Change::TypeRemoved { type_name }),
+            Some((_, new_shape)) => diff_shapes(type_name, old_shape, new_shape, &mut changes),
+        }
+    }
+    for (type_name, _) in &new_types {
+        if !old_types.iter().any(|(n, _)| n == type_name) {
+            changes.push(Change::TypeAdded {
+                type_name: type_name.to_string(),
+            });
+        }
+    }
+    changes
+}
+
+/// This function fails if the new version of the IR introduces breaking changes
+/// that are not acknowledged by their subjects, e.g. `Invoice.total` for a field of `Invoice`.
+/// It returns all the changes otherwise.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{check_compatibility, IntermediateRepresentation, TypeTarget};
+///
+/// mod v1 {
+///     #[derive(rdc::RDC)]
+///     pub struct Invoice {
+///         pub number: String,
+///         pub total: i32,
+///     }
+/// }
+///
+/// mod v2 {
+///     #[derive(rdc::RDC)]
+///     pub struct Invoice {
+///         pub number: String,
+///         pub total: f64,
+///     }
+/// }
+///
+/// let mut old = IntermediateRepresentation::new(TypeTarget::Java);
+/// old.add::<v1::Invoice>();
+/// let mut new = IntermediateRepresentation::new(TypeTarget::Java);
+/// new.add::<v2::Invoice>();
+/// assert!(check_compatibility(&old, &new, &[]).is_err());
+/// assert!(check_compatibility(&old, &new, &["Invoice.total".to_string()]).is_ok());
+/// ```
+pub fn check_compatibility(
+    old: &IntermediateRepresentation,
+    new: &IntermediateRepresentation,
+    acknowledged: &[String],
+) -> Result<Vec<Change>, Error> {
+    let changes = diff(old, new);
+    let unacknowledged = changes
+        .iter()
+        .filter(|c| c.is_breaking() && !acknowledged.contains(&c.subject()))
+        .map(|c| format!("{} (acknowledge `{}`)", c.description(), c.subject()))
+        .collect::<Vec<String>>();
+    if !unacknowledged.is_empty() {
+        return Err(Error::new(&format!(
+            "Breaking changes are not acknowledged:\n{}",
+            unacknowledged.join("\n")
+        )));
+    }
+    Ok(changes)
+}
+
+/// Returns a signature of the type, e.g. `List<Option<Invoice>>`, used to compare types across versions.
+pub(crate) fn type_signature(t: &Type) -> String {
+    match t.kind() {
+        TypeKind::Named => t.type_name().clone(),
+        TypeKind::List(element) => format!("List<{}>", type_signature(element)),
+        TypeKind::Map(key, value) => {
+            format!("Map<{}, {}>", type_signature(key), type_signature(value))
+        }
+        TypeKind::Option(inner) => format!("Option<{}>", type_signature(inner)),
+        TypeKind::UnsignedLong => "u64".to_string(),
+    }
+}
+
+enum Shape<'a> {
+    Struct(&'a [Field]),
+    Enum(Vec<(&'a str, String)>),
+    Polymorphic(Option<&'a str>, Vec<(&'a str, String)>),
+}
+
+fn type_shapes(ir: &IntermediateRepresentation) -> Vec<(&String, Shape<'_>)> {
+    let structs = ir
+        .structs()
+        .iter()
+        .map(|s| (s.self_type().type_name(), Shape::Struct(s.fields())));
+    let enums = ir.enums().iter().map(|e| {
+        let variants = e
+            .variants()
+            .iter()
+            .map(|v| (v.json_name().as_str(), String::new()));
+        (e.self_type().type_name(), Shape::Enum(variants.collect()))
+    });
+    let data_enums = ir.data_enums().iter().map(|de| {
+        let variants = de
+            .variants()
+            .iter()
+            .map(|v| (v.json_name().as_str(), variant_signature(v)));
+        (de.self_type().type_name(), Shape::Enum(variants.collect()))
+    });
+    let polymorphics = ir.polymorphics().iter().map(|p| {
+        let variants = p
+            .variants()
+            .iter()
+            .map(|v| (v.json_name().as_str(), type_signature(v.variant_type())));
+        (
+            p.self_type().type_name(),
+            Shape::Polymorphic(p.tag(), variants.collect()),
+        )
+    });
+    structs
+        .chain(enums)
+        .chain(data_enums)
+        .chain(polymorphics)
+        .collect()
+}
+
+fn variant_signature(variant: &DataEnumVariant) -> String {
+    match variant {
+        DataEnumVariant::Unit { .. } => String::new(),
+        DataEnumVariant::Tuple { fields, .. } => {
+            let fields = fields.iter().map(type_signature).collect::<Vec<String>>();
+            format!("({})", fields.join(", "))
+        }
+        DataEnumVariant::Object { fields, .. } => {
+            let fields = fields
+                .iter()
+                .map(|f| format!("{}: {}", f.json_name(), type_signature(f.field_type())))
+                .collect::<Vec<String>>();
+            format!("{{{}}}", fields.join(", "))
+        }
+    }
+}
+
+fn diff_shapes(type_name: String, old: &Shape, new: &Shape, changes: &mut Vec<Change>) {
+    match (old, new) {
+        (Shape::Struct(old_fields), Shape::Struct(new_fields)) => {
+            diff_fields(type_name, old_fields, new_fields, changes)
+        }
+        (Shape::Enum(old_variants), Shape::Enum(new_variants)) => {
+            diff_variants(type_name, old_variants, new_variants, changes)
+        }
+        (Shape::Polymorphic(old_tag, old_variants), Shape::Polymorphic(new_tag, new_variants))
+            if old_tag == new_tag =>
+        {
+            diff_variants(type_name, old_variants, new_variants, changes)
+        }
+        _ => changes.push(Change::TypeChanged { type_name }),
+    }
+}
+
+fn diff_fields(type_name: String, old: &[Field], new: &[Field], changes: &mut Vec<Change>) {
+    let mut matched = HashSet::new();
+    for old_field in old {
+        let new_field = new
+            .iter()
+            .find(|f| f.json_name() == old_field.json_name())
+            .or_else(|| {
+                new.iter()
+                    .find(|f| f.name().as_snake_case() == old_field.name().as_snake_case())
+            });
+        let Some(new_field) = new_field else {
+            changes.push(Change::FieldRemoved {
+                type_name: type_name.clone(),
+                json_name: old_field.json_name().clone(),
+            });
+            continue;
+        };
+        matched.insert(new_field.json_name());
+        if new_field.json_name() != old_field.json_name() {
+            changes.push(Change::FieldRenamed {
+                type_name: type_name.clone(),
+                old_json_name: old_field.json_name().clone(),
+                new_json_name: new_field.json_name().clone(),
+            });
+        }
+        let old_type = type_signature(old_field.field_type());
+        let new_type = type_signature(new_field.field_type());
+        if old_type != new_type {
+            changes.push(Change::FieldTypeChanged {
+                type_name: type_name.clone(),
+                json_name: new_field.json_name().clone(),
+                old_type,
+                new_type,
+            });
+        }
+    }
+    for new_field in new {
+        if !matched.contains(new_field.json_name()) {
+            changes.push(Change::FieldAdded {
+                type_name: type_name.clone(),
+                json_name: new_field.json_name().clone(),
+                optional: !new_field.is_required(),
+            });
+        }
+    }
+}
+
+fn diff_variants(
+    type_name: String,
+    old: &[(&str, String)],
+    new: &[(&str, String)],
+    changes: &mut Vec<Change>,
+) {
+    for (json_name, old_signature) in old {
+        let json_name = json_name.to_string();
+        match new.iter().find(|(n, _)| *n == json_name) {
+            None => changes.push(Change::VariantRemoved {
+                type_name: type_name.clone(),
+                json_name,
+            }),
+            Some((_, new_signature)) if new_signature != old_signature => {
+                changes.push(Change::VariantChanged {
+                    type_name: type_name.clone(),
+                    json_name,
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    for (json_name, _) in new {
+        if !old.iter().any(|(n, _)| n == json_name) {
+            changes.push(Change::VariantAdded {
+                type_name: type_name.clone(),
+                json_name: json_name.to_string(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ir::{check_compatibility, diff, Change, IntermediateRepresentation, TypeTarget};
+    use crate::targets::java::{generate_java_code_with_config, JavaConfig};
+
+    mod v1 {
+        use crate as rdc;
+        use crate::RDC;
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub struct Invoice {
+            pub number: String,
+            pub total: i32,
+            pub legacy_code: String,
+            pub status: Status,
+            pub discount: Discount,
+        }
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub enum Status {
+            Draft,
+            Cancelled,
+        }
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub enum Discount {
+            Percent(i32),
+            Fixed { amount: f64 },
+        }
+    }
+
+    mod v2 {
+        use crate as rdc;
+        use crate::RDC;
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub struct Invoice {
+            #[serde(rename = "invoiceNumber")]
+            pub number: String,
+            pub total: f64,
+            pub note: Option<String>,
+            pub currency: String,
+            pub status: Status,
+            pub discount: Discount,
+        }
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub enum Status {
+            Draft,
+            Paid,
+        }
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub enum Discount {
+            Percent(i32),
+            Fixed { amount: f64, currency: String },
+        }
+    }
+
+    #[test]
+    fn diff_versions() {
+        let mut old = IntermediateRepresentation::new(TypeTarget::Java);
+        old.add::<v1::Invoice>();
+        let mut new = IntermediateRepresentation::new(TypeTarget::Java);
+        new.add::<v2::Invoice>();
+        let changes = diff(&old, &new);
+        for change in &changes {
+            println!("{}", change.description());
+        }
+        assert!(changes.contains(&Change::FieldRenamed {
+            type_name: "Invoice".to_string(),
+            old_json_name: "number".to_string(),
+            new_json_name: "invoiceNumber".to_string(),
+        }));
+        assert!(changes.contains(&Change::FieldTypeChanged {
+            type_name: "Invoice".to_string(),
+            json_name: "total".to_string(),
+            old_type: "Integer".to_string(),
+            new_type: "Double".to_string(),
+        }));
+        assert!(changes.contains(&Change::FieldRemoved {
+            type_name: "Invoice".to_string(),
+            json_name: "legacy_code".to_string(),
+        }));
+        assert!(changes.contains(&Change::FieldAdded {
+            type_name: "Invoice".to_string(),
+            json_name: "note".to_string(),
+            optional: true,
+        }));
+        assert!(changes.contains(&Change::FieldAdded {
+            type_name: "Invoice".to_string(),
+            json_name: "currency".to_string(),
+            optional: false,
+        }));
+        assert!(changes.contains(&Change::VariantRemoved {
+            type_name: "Status".to_string(),
+            json_name: "Cancelled".to_string(),
+        }));
+        assert!(changes.contains(&Change::VariantAdded {
+            type_name: "Status".to_string(),
+            json_name: "Paid".to_string(),
+        }));
+        assert!(changes.contains(&Change::VariantChanged {
+            type_name: "Discount".to_string(),
+            json_name: "Fixed".to_string(),
+        }));
+        assert_eq!(changes.len(), 8);
+        let manual = changes
+            .iter()
+            .filter(|c| c.needs_manual_migration())
+            .count();
+        assert_eq!(manual, 4);
+    }
+
+    #[test]
+    fn compatibility_check() {
+        let mut old = IntermediateRepresentation::new(TypeTarget::Java);
+        old.add::<v1::Invoice>();
+        let mut new = IntermediateRepresentation::new(TypeTarget::Java);
+        new.add::<v2::Invoice>();
+        let error = check_compatibility(&old, &new, &[]).err().unwrap();
+        println!("{}", error.message());
+        assert!(error
+            .message()
+            .starts_with("Breaking changes are not acknowledged:"));
+        assert!(error
+            .message()
+            .contains("Variant `Status::Cancelled` was removed (acknowledge `Status::Cancelled`)"));
+        assert!(!error.message().contains("Invoice.note"));
+        let acknowledged = [
+            "Invoice.number",
+            "Invoice.total",
+            "Invoice.legacy_code",
+            "Invoice.currency",
+            "Status::Cancelled",
+        ]
+        .map(String::from);
+        let error = check_compatibility(&old, &new, &acknowledged)
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.message(),
+            "Breaking changes are not acknowledged:\nVariant `Discount::Fixed` changed its payload (acknowledge `Discount::Fixed`)"
+        );
+        let mut acknowledged = acknowledged.to_vec();
+        acknowledged.push("Discount::Fixed".to_string());
+        let changes = check_compatibility(&old, &new, &acknowledged).unwrap();
+        assert_eq!(changes.len(), 8);
+    }
+
+    #[test]
+    fn compatibility_snapshot_config() {
+        let path = std::env::temp_dir().join("rdc_compatibility_snapshot_config/types.json");
+        let path = path.to_str().unwrap();
+        let _ = std::fs::remove_file(path);
+        let mut old = IntermediateRepresentation::new(TypeTarget::Java);
+        old.add::<v1::Invoice>();
+        let mut new = IntermediateRepresentation::new(TypeTarget::Java);
+        new.add::<v2::Invoice>();
+        let config = JavaConfig::new().with_compatibility_snapshot(path);
+        assert!(generate_java_code_with_config(&new, &config).is_ok());
+        old.write_snapshot(path).unwrap();
+        let error = generate_java_code_with_config(&new, &config).err().unwrap();
+        assert!(error.message().contains("acknowledge `Invoice.total`"));
+        assert!(generate_java_code_with_config(&old, &config).is_ok());
+    }
+
+    #[test]
+    fn no_changes() {
+        let mut old = IntermediateRepresentation::new(TypeTarget::Java);
+        old.add::<v1::Invoice>();
+        let restored = IntermediateRepresentation::from_snapshot(&old.to_snapshot()).unwrap();
+        assert!(diff(&old, &restored).is_empty());
+    }
+}
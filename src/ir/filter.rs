@@ -0,0 +1,320 @@
+use crate::errors::Error;
+use crate::ir::{DataEnumVariant, IntermediateRepresentation, Type, TypeKind};
+
+/// This struct describes which types and fields are generated for which targets,
+/// so that one set of Rust types can produce slightly different surfaces per consumer,
+/// e.g. a public TypeScript client without internal fields.
+///
+/// Attributes other than serde ones are not available to the derive macro, so the rules are declared here.
+/// Subjects are Rust type names, like `User`, or fields in snake case, like `User.password_hash`.
+/// Targets are free-form names, like `java` or `typescript`, chosen by the caller of `apply`.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TargetFilter, TypeTarget};
+/// use rdc::RDC;
+///
+/// #[derive(RDC)]
+/// struct User {
+///     name: String,
+///     password_hash: String,
+///     audit: Audit,
+/// }
+///
+/// #[derive(RDC)]
+/// struct Audit {
+///     created_by: String,
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add::<User>();
+/// let filter = TargetFilter::new()
+///     .with_skipped_target("User.password_hash", "typescript")
+///     .with_targets("User.audit", ["java"])
+///     .with_targets("Audit", ["java"]);
+/// let public = filter.apply(&ir, "typescript").unwrap();
+/// assert_eq!(public.structs().len(), 1);
+/// assert_eq!(public.structs()[0].fields().len(), 1);
+/// let internal = filter.apply(&ir, "java").unwrap();
+/// assert_eq!(internal.structs().len(), 2);
+/// ```
+#[derive(Default)]
+pub struct TargetFilter {
+    rules: Vec<Rule>,
+}
+
+enum Rule {
+    Only {
+        subject: String,
+        targets: Vec<String>,
+    },
+    Skip {
+        subject: String,
+        target: String,
+    },
+}
+
+impl TargetFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Generates the type or field only for the given targets.
+    pub fn with_targets<S, I, T>(mut self, subject: S, targets: I) -> Self
+    where
+        S: Into<String>,
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.rules.push(Rule::Only {
+            subject: subject.into(),
+            targets: targets.into_iter().map(|t| t.into()).collect(),
+        });
+        self
+    }
+
+    /// Skips the type or field for the given target.
+    pub fn with_skipped_target<S: Into<String>, T: Into<String>>(
+        mut self,
+        subject: S,
+        target: T,
+    ) -> Self {
+        self.rules.push(Rule::Skip {
+            subject: subject.into(),
+            target: target.into(),
+        });
+        self
+    }
+
+    /// Returns true if the type or field is generated for the target.
+    pub fn includes(&self, subject: &str, target: &str) -> bool {
+        self.rules.iter().all(|rule| match rule {
+            Rule::Only {
+                subject: s,
+                targets,
+            } => s != subject || targets.iter().any(|t| t == target),
+            Rule::Skip {
+                subject: s,
+                target: t,
+            } => s != subject || t != target,
+        })
+    }
+
+    /// This function returns a copy of the IR with only the types and fields generated for the target.
+    ///
+    /// It fails if a rule names a type or a field that is not in the IR,
+    /// or if a remaining type or service still references a skipped type, since the generated code would not compile.
+    pub fn apply(
+        &self,
+        ir: &IntermediateRepresentation,
+        target: &str,
+    ) -> Result<IntermediateRepresentation, Error> {
+        for rule in &self.rules {
+            let subject = match rule {
+                Rule::Only { subject, .. } | Rule::Skip { subject, .. } => subject,
+            };
+            if !has_subject(ir, subject) {
+                return Err(Error::new(&format!(
+                    "`{subject}` is not a type or a struct field in the IR"
+                )));
+            }
+        }
+        let mut filtered = ir.clone();
+        filtered.retain_types(|name| self.includes(name, target));
+        for s in filtered.structs_mut() {
+            let type_name = s.self_type().type_name().clone();
+            s.retain_fields(|f| {
+                self.includes(&format!("{type_name}.{}", f.name().as_snake_case()), target)
+            });
+        }
+        let skipped = type_names(ir)
+            .into_iter()
+            .filter(|name| !self.includes(name, target))
+            .collect::<Vec<&str>>();
+        for (owner, t) in references(&filtered) {
+            let mut names = Vec::new();
+            named_types(t, &mut names);
+            if let Some(name) = names.into_iter().find(|n| skipped.contains(n)) {
+                return Err(Error::new(&format!(
+                    "`{name}` is not generated for `{target}`, but `{owner}` references it"
+                )));
+            }
+        }
+        Ok(filtered)
+    }
+}
+
+pub(crate) fn type_names(ir: &IntermediateRepresentation) -> Vec<&str> {
+    let structs = ir.structs().iter().map(|s| s.self_type());
+    let enums = ir.enums().iter().map(|e| e.self_type());
+    let data_enums = ir.data_enums().iter().map(|de| de.self_type());
+    let polymorphics = ir.polymorphics().iter().map(|p| p.self_type());
+    structs
+        .chain(enums)
+        .chain(data_enums)
+        .chain(polymorphics)
+        .map(|t| t.type_name().as_str())
+        .collect()
+}
+
+fn has_subject(ir: &IntermediateRepresentation, subject: &str) -> bool {
+    match subject.split_once('.') {
+        Some((type_name, field_name)) => ir.structs().iter().any(|s| {
+            s.self_type().type_name() == type_name
+                && s.fields()
+                    .iter()
+                    .any(|f| f.name().as_snake_case() == field_name)
+        }),
+        None => type_names(ir).contains(&subject),
+    }
+}
+
+/// Returns all the type references in the IR, together with the members holding them.
+fn references(ir: &IntermediateRepresentation) -> Vec<(String, &Type)> {
+    let mut references = Vec::new();
+    for s in ir.structs() {
+        for f in s.fields() {
+            let owner = format!("{}.{}", s.self_type().type_name(), f.name().as_snake_case());
+            references.push((owner, f.field_type()));
+        }
+    }
+    for de in ir.data_enums() {
+        for v in de.variants() {
+            let owner = format!(
+                "{}::{}",
+                de.self_type().type_name(),
+                v.name().as_pascal_case()
+            );
+            match v {
+                DataEnumVariant::Object { fields, .. } => {
+                    references.extend(fields.iter().map(|f| (owner.clone(), f.field_type())))
+                }
+                DataEnumVariant::Tuple { fields, .. } => {
+                    references.extend(fields.iter().map(|t| (owner.clone(), t)))
+                }
+                DataEnumVariant::Unit { .. } => {}
+            }
+        }
+    }
+    for p in ir.polymorphics() {
+        for v in p.variants() {
+            let owner = format!("{}::{}", p.self_type().type_name(), v.json_name());
+            references.push((owner, v.variant_type()));
+        }
+    }
+    for service in ir.services() {
+        for endpoint in service.endpoints() {
+            let owner = format!(
+                "{}.{}",
+                service.name().as_pascal_case(),
+                endpoint.name().as_snake_case()
+            );
+            references.extend(
+                endpoint
+                    .params()
+                    .iter()
+                    .map(|p| (owner.clone(), p.param_type())),
+            );
+            references.extend(endpoint.response().map(|r| (owner.clone(), r)));
+        }
+    }
+    for service in ir.rpc_services() {
+        for method in service.methods() {
+            let owner = format!(
+                "{}.{}",
+                service.name().as_pascal_case(),
+                method.name().as_snake_case()
+            );
+            references.extend(method.result().map(|r| (owner, r)));
+        }
+    }
+    references
+}
+
+pub(crate) fn named_types<'t>(t: &'t Type, names: &mut Vec<&'t str>) {
+    match t.kind() {
+        TypeKind::Named => names.push(t.type_name()),
+        TypeKind::List(inner) | TypeKind::Option(inner) => named_types(inner, names),
+        TypeKind::Map(key, value) => {
+            named_types(key, names);
+            named_types(value, names);
+        }
+        TypeKind::UnsignedLong => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as rdc;
+    use crate::ir::TypeTarget;
+    use crate::RDC;
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Account {
+        id: String,
+        internal_notes: Option<String>,
+        limits: Limits,
+        status: Status,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Limits {
+        daily: f64,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Status {
+        Active,
+        Closed,
+    }
+
+    #[test]
+    fn filter_targets() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Account>();
+        let filter = TargetFilter::new()
+            .with_skipped_target("Account.internal_notes", "typescript")
+            .with_targets("Account.limits", ["java", "openapi"])
+            .with_targets("Limits", ["java", "openapi"]);
+        let typescript = filter.apply(&ir, "typescript").unwrap();
+        let fields = typescript.structs()[0]
+            .fields()
+            .iter()
+            .map(|f| f.name().as_snake_case())
+            .collect::<Vec<String>>();
+        assert_eq!(fields, ["id", "status"]);
+        assert_eq!(typescript.structs().len(), 1);
+        assert_eq!(typescript.enums().len(), 1);
+        let java = filter.apply(&ir, "java").unwrap();
+        assert_eq!(java.structs().len(), 2);
+        let account = java
+            .structs()
+            .iter()
+            .find(|s| s.self_type().type_name() == "Account")
+            .unwrap();
+        assert_eq!(account.fields().len(), 4);
+    }
+
+    #[test]
+    fn filter_errors() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Account>();
+        let filter = TargetFilter::new().with_skipped_target("Limits", "typescript");
+        let error = filter.apply(&ir, "typescript").err().unwrap();
+        assert_eq!(
+            error.message(),
+            "`Limits` is not generated for `typescript`, but `Account.limits` references it"
+        );
+        assert!(filter.apply(&ir, "java").is_ok());
+        let filter = TargetFilter::new().with_skipped_target("Account.notes", "java");
+        let error = filter.apply(&ir, "java").err().unwrap();
+        assert_eq!(
+            error.message(),
+            "`Account.notes` is not a type or a struct field in the IR"
+        );
+    }
+}
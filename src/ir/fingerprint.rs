@@ -0,0 +1,281 @@
+use crate::ir::{
+    DataEnum, DataEnumVariant, Enum, IntermediateRepresentation, Polymorphic, Struct, Type,
+    TypeKind, TypeTarget,
+};
+use crate::RDCType;
+use std::collections::BTreeMap;
+
+impl IntermediateRepresentation {
+    /// Returns a fingerprint of the JSON shape of the type, as 16 hexadecimal digits.
+    /// Services can exchange fingerprints, e.g. at handshake time, to detect deployments with mismatched types.
+    ///
+    /// The fingerprint covers the JSON names and types of the fields and variants of the type
+    /// and of all the types it references, so a change of a nested type changes it too.
+    /// Referenced types are numbered in the order they are reached, so the fingerprint does not depend
+    /// on the order of fields and variants, the names of the types or the Rust version.
+    /// It is `None` if the type is not in the IR.
+    ///
+    /// Example:
+    /// ```rust
+    /// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+    /// use rdc::RDC;
+    ///
+    /// #[derive(RDC)]
+    /// struct Invoice {
+    ///     number: String,
+    /// }
+    ///
+    /// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+    /// ir.add::<Invoice>();
+    /// assert_eq!(ir.fingerprint("Invoice").unwrap().len(), 16);
+    /// assert_eq!(ir.fingerprint("Invoice"), rdc::ir::type_fingerprint::<Invoice>());
+    /// ```
+    pub fn fingerprint(&self, type_name: &str) -> Option<String> {
+        let definitions = definitions(self);
+        definitions.get(type_name)?;
+        let mut reached = vec![type_name.to_string()];
+        let mut canonical = String::new();
+        let mut index = 0;
+        while index < reached.len() {
+            let definition = &definitions[reached[index].as_str()];
+            let shape = definition.shape(&mut |name| {
+                if !definitions.contains_key(name) {
+                    return name.to_string();
+                }
+                let position = match reached.iter().position(|r| r == name) {
+                    Some(position) => position,
+                    None => {
+                        reached.push(name.to_string());
+                        reached.len() - 1
+                    }
+                };
+                format!("#{position}")
+            });
+            canonical.push_str(&format!("#{index} = {shape}\n"));
+            index += 1;
+        }
+        Some(format!("{:016x}", fnv1a(canonical.as_bytes())))
+    }
+}
+
+/// This function returns the fingerprint of a Rust type, the same one `IntermediateRepresentation::fingerprint`
+/// returns for its generated name.
+pub fn type_fingerprint<T: RDCType>() -> Option<String> {
+    let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+    ir.add::<T>();
+    ir.fingerprint(T::java_type().type_name())
+}
+
+enum Definition<'a> {
+    Struct(&'a Struct),
+    Enum(&'a Enum),
+    DataEnum(&'a DataEnum),
+    Polymorphic(&'a Polymorphic),
+}
+
+impl Definition<'_> {
+    /// Returns a canonical description of the type, with the names of the types of the IR replaced by `rename`.
+    /// Fields and variants are sorted by their JSON names before they are described,
+    /// so `rename` sees the referenced types in an order which does not depend on their names.
+    fn shape(&self, rename: &mut dyn FnMut(&str) -> String) -> String {
+        match self {
+            Definition::Struct(s) => {
+                let mut fields = s.fields().iter().collect::<Vec<_>>();
+                fields.sort_by_key(|f| f.json_name());
+                let fields = fields
+                    .into_iter()
+                    .map(|f| format!("{}: {}", f.json_name(), signature(f.field_type(), rename)))
+                    .collect::<Vec<String>>();
+                format!("struct {{{}}}", fields.join(", "))
+            }
+            Definition::Enum(e) => {
+                let mut variants = e
+                    .variants()
+                    .iter()
+                    .map(|v| v.json_name().clone())
+                    .collect::<Vec<String>>();
+                variants.sort();
+                format!("enum [{}]", variants.join(", "))
+            }
+            Definition::DataEnum(de) => {
+                let mut variants = de.variants().iter().collect::<Vec<_>>();
+                variants.sort_by_key(|v| v.json_name());
+                let variants = variants
+                    .into_iter()
+                    .map(|v| format!("{}{}", v.json_name(), variant_signature(v, rename)))
+                    .collect::<Vec<String>>();
+                format!("data enum [{}]", variants.join(", "))
+            }
+            Definition::Polymorphic(p) => {
+                let mut variants = p.variants().iter().collect::<Vec<_>>();
+                variants.sort_by_key(|v| v.json_name());
+                let variants = variants
+                    .into_iter()
+                    .map(|v| format!("{}: {}", v.json_name(), signature(v.variant_type(), rename)))
+                    .collect::<Vec<String>>();
+                format!("polymorphic {:?} [{}]", p.tag(), variants.join(", "))
+            }
+        }
+    }
+}
+
+fn definitions(ir: &IntermediateRepresentation) -> BTreeMap<&str, Definition<'_>> {
+    let structs = ir
+        .structs()
+        .iter()
+        .map(|s| (s.self_type().type_name().as_str(), Definition::Struct(s)));
+    let enums = ir
+        .enums()
+        .iter()
+        .map(|e| (e.self_type().type_name().as_str(), Definition::Enum(e)));
+    let data_enums = ir.data_enums().iter().map(|de| {
+        (
+            de.self_type().type_name().as_str(),
+            Definition::DataEnum(de),
+        )
+    });
+    let polymorphics = ir.polymorphics().iter().map(|p| {
+        (
+            p.self_type().type_name().as_str(),
+            Definition::Polymorphic(p),
+        )
+    });
+    structs
+        .chain(enums)
+        .chain(data_enums)
+        .chain(polymorphics)
+        .collect()
+}
+
+fn signature(t: &Type, rename: &mut dyn FnMut(&str) -> String) -> String {
+    match t.kind() {
+        TypeKind::Named => rename(t.type_name()),
+        TypeKind::List(element) => format!("List<{}>", signature(element, rename)),
+        TypeKind::Map(key, value) => {
+            let key = signature(key, rename);
+            format!("Map<{key}, {}>", signature(value, rename))
+        }
+        TypeKind::Option(inner) => format!("Option<{}>", signature(inner, rename)),
+        TypeKind::UnsignedLong => "u64".to_string(),
+    }
+}
+
+fn variant_signature(variant: &DataEnumVariant, rename: &mut dyn FnMut(&str) -> String) -> String {
+    match variant {
+        DataEnumVariant::Unit { .. } => String::new(),
+        DataEnumVariant::Tuple { fields, .. } => {
+            let fields = fields
+                .iter()
+                .map(|t| signature(t, rename))
+                .collect::<Vec<String>>();
+            format!("({})", fields.join(", "))
+        }
+        DataEnumVariant::Object { fields, .. } => {
+            let mut fields = fields.iter().collect::<Vec<_>>();
+            fields.sort_by_key(|f| f.json_name());
+            let fields = fields
+                .into_iter()
+                .map(|f| format!("{}: {}", f.json_name(), signature(f.field_type(), rename)))
+                .collect::<Vec<String>>();
+            format!("{{{}}}", fields.join(", "))
+        }
+    }
+}
+
+/// 64-bit FNV-1a hash, which, unlike the hashers of the standard library, is stable across Rust versions.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod v1 {
+        use crate as rdc;
+        use crate::RDC;
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub struct Order {
+            pub id: String,
+            pub customer: Customer,
+        }
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub struct Customer {
+            pub name: String,
+        }
+    }
+
+    mod v2 {
+        use crate as rdc;
+        use crate::RDC;
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub struct Order {
+            pub customer: Customer,
+            #[serde(rename = "id")]
+            pub order_id: String,
+        }
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub struct Customer {
+            pub name: String,
+        }
+    }
+
+    mod v3 {
+        use crate as rdc;
+        use crate::RDC;
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub struct Order {
+            pub id: String,
+            pub customer: Customer,
+        }
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub struct Customer {
+            pub name: Option<String>,
+        }
+    }
+
+    mod v4 {
+        use crate as rdc;
+        use crate::RDC;
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub struct Purchase {
+            pub id: String,
+            pub customer: Client,
+        }
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub struct Client {
+            pub name: String,
+        }
+    }
+
+    #[test]
+    fn stable_fingerprints() {
+        let v1 = type_fingerprint::<v1::Order>().unwrap();
+        assert_eq!(v1, type_fingerprint::<v2::Order>().unwrap());
+        assert_ne!(v1, type_fingerprint::<v3::Order>().unwrap());
+        assert_eq!(
+            type_fingerprint::<v1::Customer>(),
+            type_fingerprint::<v2::Customer>()
+        );
+        assert_eq!(v1, type_fingerprint::<v4::Purchase>().unwrap());
+        assert_eq!(format!("{:016x}", fnv1a(b"rdc")), "89ec9d1960f6bfe0");
+    }
+}
@@ -0,0 +1,338 @@
+use crate::errors::Error;
+use crate::ir::filter::named_types;
+use crate::ir::{DataEnumVariant, IntermediateRepresentation};
+use std::collections::{BTreeSet, HashMap};
+
+/// This struct splits a single IR into output groups, e.g. public DTOs for an SDK and internal DTOs for a service,
+/// so that every group can be generated with its own target, configuration and package in one run.
+///
+/// Attributes other than serde ones are not available to the derive macro, so groups are declared here.
+/// Members are generated type names, like `Invoice`, or service names, like `UserApi`.
+/// A group contains its members and every type they use, so that its code compiles on its own.
+/// It is an error for a group to use a member of another group, since it would leak into the wrong output.
+/// Types and services that are neither members nor used by a group are left out, unless a default group is set.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, OutputGroups, TypeTarget};
+/// use rdc::RDC;
+///
+/// #[derive(RDC)]
+/// struct Invoice {
+///     number: String,
+///     customer: Customer,
+/// }
+///
+/// #[derive(RDC)]
+/// struct Customer {
+///     name: String,
+/// }
+///
+/// #[derive(RDC)]
+/// struct AuditEntry {
+///     invoice: Invoice,
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add::<Invoice>();
+/// ir.add::<AuditEntry>();
+/// let groups = OutputGroups::new()
+///     .with_group("public_api", ["Invoice"])
+///     .with_default_group("internal");
+/// let split = groups.split(&ir).unwrap();
+/// assert_eq!(split[0].0, "public_api");
+/// assert_eq!(split[0].1.structs().len(), 2);
+/// assert_eq!(split[1].0, "internal");
+/// assert_eq!(split[1].1.structs().len(), 3);
+/// ```
+#[derive(Default)]
+pub struct OutputGroups {
+    groups: Vec<(String, Vec<String>)>,
+    default_group: Option<String>,
+}
+
+impl OutputGroups {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a group with the given types and services.
+    pub fn with_group<S, I, T>(mut self, name: S, members: I) -> Self
+    where
+        S: Into<String>,
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.groups
+            .push((name.into(), members.into_iter().map(|m| m.into()).collect()));
+        self
+    }
+
+    /// Adds a group with all the types and services that do not belong to other groups.
+    /// Unlike other groups, it may use members of other groups, which are then generated in it as well.
+    pub fn with_default_group<S: Into<String>>(mut self, name: S) -> Self {
+        self.default_group = Some(name.into());
+        self
+    }
+
+    /// This function returns a copy of the IR for every group, in the order of declaration, with the default group last.
+    ///
+    /// It fails if a member is not a type or a service in the IR, or if a group uses a member of another group.
+    pub fn split(
+        &self,
+        ir: &IntermediateRepresentation,
+    ) -> Result<Vec<(String, IntermediateRepresentation)>, Error> {
+        let dependencies = dependencies(ir);
+        let mut owners: HashMap<&str, &str> = HashMap::new();
+        for (group, members) in &self.groups {
+            for member in members {
+                if !dependencies.contains_key(member) {
+                    return Err(Error::new(&format!(
+                        "`{member}` is not a type or a service in the IR"
+                    )));
+                }
+                owners.insert(member, group);
+            }
+        }
+        let mut split = Vec::new();
+        let mut grouped = BTreeSet::new();
+        for (group, members) in &self.groups {
+            let contents = closure(members.iter().map(|m| m.as_str()), &dependencies);
+            for name in &contents {
+                match owners.get(name) {
+                    Some(owner) if owner != group => {
+                        return Err(Error::new(&format!(
+                            "`{name}` belongs to group `{owner}`, but group `{group}` uses it"
+                        )))
+                    }
+                    _ => {}
+                }
+            }
+            grouped.extend(contents.iter().copied());
+            split.push((group.clone(), retain(ir, &contents)));
+        }
+        if let Some(group) = &self.default_group {
+            let roots = dependencies
+                .keys()
+                .map(|name| name.as_str())
+                .filter(|name| !grouped.contains(name));
+            let contents = closure(roots, &dependencies);
+            split.push((group.clone(), retain(ir, &contents)));
+        }
+        Ok(split)
+    }
+}
+
+/// Returns the names of the types used by every type and service of the IR.
+fn dependencies(ir: &IntermediateRepresentation) -> HashMap<String, Vec<String>> {
+    let mut dependencies = HashMap::new();
+    for s in ir.structs() {
+        let mut used = Vec::new();
+        s.fields()
+            .iter()
+            .for_each(|f| named_types(f.field_type(), &mut used));
+        dependencies.insert(s.self_type().type_name().clone(), owned(used));
+    }
+    for e in ir.enums() {
+        dependencies.insert(e.self_type().type_name().clone(), Vec::new());
+    }
+    for de in ir.data_enums() {
+        let mut used = Vec::new();
+        for v in de.variants() {
+            match v {
+                DataEnumVariant::Object { fields, .. } => fields
+                    .iter()
+                    .for_each(|f| named_types(f.field_type(), &mut used)),
+                DataEnumVariant::Tuple { fields, .. } => {
+                    fields.iter().for_each(|t| named_types(t, &mut used))
+                }
+                DataEnumVariant::Unit { .. } => {}
+            }
+        }
+        dependencies.insert(de.self_type().type_name().clone(), owned(used));
+    }
+    for p in ir.polymorphics() {
+        let mut used = Vec::new();
+        p.variants()
+            .iter()
+            .for_each(|v| named_types(v.variant_type(), &mut used));
+        dependencies.insert(p.self_type().type_name().clone(), owned(used));
+    }
+    for service in ir.services() {
+        let mut used = Vec::new();
+        for endpoint in service.endpoints() {
+            for param in endpoint.params() {
+                named_types(param.param_type(), &mut used);
+            }
+            if let Some(response) = endpoint.response() {
+                named_types(response, &mut used);
+            }
+        }
+        dependencies.insert(service.name().as_pascal_case(), owned(used));
+    }
+    for service in ir.rpc_services() {
+        let mut used = Vec::new();
+        for method in service.methods() {
+            if let Some(result) = method.result() {
+                named_types(result, &mut used);
+            }
+        }
+        let mut used = owned(used);
+        used.extend(
+            service
+                .methods()
+                .iter()
+                .filter_map(|m| m.params_type())
+                .map(|t| t.type_name().clone()),
+        );
+        dependencies.insert(service.name().as_pascal_case(), used);
+    }
+    dependencies
+}
+
+fn owned(names: Vec<&str>) -> Vec<String> {
+    names.into_iter().map(|n| n.to_string()).collect()
+}
+
+/// Returns the given types and services together with all the types they use, directly or not.
+/// Builtin types, which are not in the IR, are skipped.
+fn closure<'d, I: IntoIterator<Item = &'d str>>(
+    roots: I,
+    dependencies: &'d HashMap<String, Vec<String>>,
+) -> BTreeSet<&'d str> {
+    let mut visited = BTreeSet::new();
+    let mut pending = roots.into_iter().collect::<Vec<&str>>();
+    while let Some(name) = pending.pop() {
+        if let Some((name, used)) = dependencies.get_key_value(name) {
+            if visited.insert(name.as_str()) {
+                pending.extend(used.iter().map(|u| u.as_str()));
+            }
+        }
+    }
+    visited
+}
+
+/// Returns the given types and services of the IR together with all the types they use, directly or not,
+/// e.g. to generate only the part of the IR that is being worked on.
+pub(crate) fn selection(
+    ir: &IntermediateRepresentation,
+    members: &[&str],
+) -> Result<BTreeSet<String>, Error> {
+    let dependencies = dependencies(ir);
+    if let Some(member) = members.iter().find(|m| !dependencies.contains_key(**m)) {
+        return Err(Error::new(&format!(
+            "`{member}` is not a type or a service in the IR"
+        )));
+    }
+    let contents = closure(members.iter().copied(), &dependencies);
+    Ok(contents.into_iter().map(|name| name.to_string()).collect())
+}
+
+/// Returns a copy of the IR with only the given types and services.
+fn retain(ir: &IntermediateRepresentation, names: &BTreeSet<&str>) -> IntermediateRepresentation {
+    let mut group = ir.clone();
+    group.retain_types(|name| names.contains(name));
+    group.retain_services(|name| names.contains(name));
+    group
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as rdc;
+    use crate::ir::TypeTarget;
+    use crate::targets::java::{generate_java_code_with_config, JavaConfig};
+    use crate::RDC;
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Order {
+        id: String,
+        lines: Vec<OrderLine>,
+        status: OrderStatus,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct OrderLine {
+        sku: String,
+        quantity: u32,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum OrderStatus {
+        Open,
+        Shipped,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Reservation {
+        order: Order,
+        warehouse: String,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct StockLevel {
+        sku: String,
+        available: u32,
+    }
+
+    fn type_names(ir: &IntermediateRepresentation) -> Vec<&str> {
+        let mut names = crate::ir::filter::type_names(ir);
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn split_groups() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Reservation>();
+        ir.add::<StockLevel>();
+        let groups = OutputGroups::new()
+            .with_group("public_api", ["Order"])
+            .with_group("inventory", ["StockLevel"])
+            .with_default_group("internal");
+        let split = groups.split(&ir).unwrap();
+        let names = split.iter().map(|(n, _)| n.as_str()).collect::<Vec<&str>>();
+        assert_eq!(names, ["public_api", "inventory", "internal"]);
+        assert_eq!(
+            type_names(&split[0].1),
+            ["Order", "OrderLine", "OrderStatus"]
+        );
+        assert_eq!(type_names(&split[1].1), ["StockLevel"]);
+        assert_eq!(
+            type_names(&split[2].1),
+            ["Order", "OrderLine", "OrderStatus", "Reservation"]
+        );
+        let config = JavaConfig::new().with_class_suffix("Dto");
+        let classes = generate_java_code_with_config(&split[0].1, &config).unwrap();
+        let classes = classes.iter().map(|c| c.name()).collect::<Vec<&str>>();
+        assert!(classes.contains(&"OrderDto"));
+        assert!(!classes.contains(&"ReservationDto"));
+        let without_default = OutputGroups::new().with_group("inventory", ["StockLevel"]);
+        assert_eq!(without_default.split(&ir).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn group_errors() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Reservation>();
+        let groups = OutputGroups::new()
+            .with_group("public_api", ["OrderLine"])
+            .with_group("internal", ["Reservation"]);
+        let error = groups.split(&ir).err().unwrap();
+        assert_eq!(
+            error.message(),
+            "`OrderLine` belongs to group `public_api`, but group `internal` uses it"
+        );
+        let groups = OutputGroups::new().with_group("public_api", ["Invoice"]);
+        let error = groups.split(&ir).err().unwrap();
+        assert_eq!(
+            error.message(),
+            "`Invoice` is not a type or a service in the IR"
+        );
+    }
+}
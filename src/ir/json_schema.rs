@@ -0,0 +1,641 @@
+use crate::errors::Error;
+use crate::ir::filter::type_names;
+use crate::ir::{
+    Endpoint, FieldType, HttpMethod, IntermediateRepresentation, IrBuilder, Name, Service, Type,
+    TypeTarget,
+};
+use serde_json::{Map, Value};
+
+impl IntermediateRepresentation {
+    /// This function creates an IR from JSON schemas, e.g. the ones `schemars::schema_for!` produces,
+    /// so that types deriving `JsonSchema` can be generated without deriving `RDC` as well.
+    ///
+    /// Every schema contributes its root type, named after its `title`, and its `definitions` or `$defs`.
+    /// Types defined by several schemas are added once. Object schemas become structs, whose properties
+    /// that are not `required` become optional, and string schemas with `enum` values become enums.
+    /// It fails for schemas without a counterpart in the IR, e.g. inline objects or enums with data.
+    ///
+    /// Example:
+    /// ```rust
+    /// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+    /// use serde_json::json;
+    ///
+    /// // serde_json::to_value(schemars::schema_for!(Invoice)).unwrap()
+    /// let schema = json!({
+    ///     "title": "Invoice",
+    ///     "type": "object",
+    ///     "required": ["number", "status"],
+    ///     "properties": {
+    ///         "number": { "type": "string" },
+    ///         "dueDate": { "type": ["string", "null"] },
+    ///         "status": { "$ref": "#/definitions/Status" }
+    ///     },
+    ///     "definitions": {
+    ///         "Status": { "type": "string", "enum": ["paid", "open"] }
+    ///     }
+    /// });
+    /// let ir = IntermediateRepresentation::from_json_schemas(TypeTarget::Java, [&schema]).unwrap();
+    /// assert_eq!(ir.structs()[0].fields()[0].name().as_snake_case(), "due_date");
+    /// assert_eq!(ir.enums()[0].variants()[0].name().as_pascal_case(), "Paid");
+    /// ```
+    pub fn from_json_schemas<'s, I: IntoIterator<Item = &'s Value>>(
+        target: TypeTarget,
+        schemas: I,
+    ) -> Result<Self, Error> {
+        let mut definitions: Vec<(&str, &Value)> = Vec::new();
+        for schema in schemas {
+            if let Some(title) = schema.get("title").and_then(Value::as_str) {
+                definitions.push((title, schema));
+            }
+            for key in ["definitions", "$defs"] {
+                if let Some(defs) = schema.get(key).and_then(Value::as_object) {
+                    definitions.extend(defs.iter().map(|(name, s)| (name.as_str(), s)));
+                }
+            }
+        }
+        declare_all(IrBuilder::new(target), definitions)?.build()
+    }
+
+    /// This function creates an IR from an OpenAPI 3 document, e.g. the one `utoipa` generates for an axum service,
+    /// so that a Java client with matching DTOs can be generated for an existing service.
+    ///
+    /// Component schemas are converted like in `from_json_schemas`, also with OpenAPI 3.0 `nullable` properties.
+    /// Operations become endpoints of services named after their first tag, or `Api` for untagged operations,
+    /// and are named after their `operationId`. Parameters are sent in the path or the query string,
+    /// the JSON request body becomes the `body` parameter and the JSON response of the first 2xx status is the response.
+    ///
+    /// Example:
+    /// ```rust
+    /// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+    /// use serde_json::json;
+    ///
+    /// // serde_json::to_value(ApiDoc::openapi()).unwrap()
+    /// let document = json!({
+    ///     "openapi": "3.0.3",
+    ///     "paths": {
+    ///         "/users/{id}": {
+    ///             "get": {
+    ///                 "tags": ["users"],
+    ///                 "operationId": "get_user",
+    ///                 "parameters": [
+    ///                     { "name": "id", "in": "path", "required": true, "schema": { "type": "string" } }
+    ///                 ],
+    ///                 "responses": {
+    ///                     "200": {
+    ///                         "description": "",
+    ///                         "content": {
+    ///                             "application/json": { "schema": { "$ref": "#/components/schemas/User" } }
+    ///                         }
+    ///                     }
+    ///                 }
+    ///             }
+    ///         }
+    ///     },
+    ///     "components": {
+    ///         "schemas": {
+    ///             "User": {
+    ///                 "type": "object",
+    ///                 "required": ["name"],
+    ///                 "properties": { "name": { "type": "string" } }
+    ///             }
+    ///         }
+    ///     }
+    /// });
+    /// let ir = IntermediateRepresentation::from_openapi(TypeTarget::Java, &document).unwrap();
+    /// assert_eq!(ir.services()[0].name().as_pascal_case(), "Users");
+    /// assert_eq!(ir.services()[0].endpoints()[0].path(), "/users/{id}");
+    /// ```
+    pub fn from_openapi(target: TypeTarget, document: &Value) -> Result<Self, Error> {
+        let definitions = document
+            .pointer("/components/schemas")
+            .and_then(Value::as_object)
+            .map(|schemas| schemas.iter().map(|(name, s)| (name.as_str(), s)).collect())
+            .unwrap_or_default();
+        let builder = declare_all(IrBuilder::new(target.clone()), definitions)?;
+        let mut ir = builder.build()?;
+        let mut services: Vec<Service> = Vec::new();
+        let paths = document.get("paths").and_then(Value::as_object);
+        for (path, operations) in paths.into_iter().flatten() {
+            for (method, operation) in operations.as_object().into_iter().flatten() {
+                let method = match method.as_str() {
+                    "get" => HttpMethod::Get,
+                    "post" => HttpMethod::Post,
+                    "put" => HttpMethod::Put,
+                    "patch" => HttpMethod::Patch,
+                    "delete" => HttpMethod::Delete,
+                    _ => continue,
+                };
+                let endpoint = endpoint(&ir, &target, path, method, operation)?;
+                let tag = operation
+                    .pointer("/tags/0")
+                    .and_then(Value::as_str)
+                    .unwrap_or("Api");
+                let name = rust_name(tag);
+                match services
+                    .iter_mut()
+                    .find(|s| s.name().as_pascal_case() == name.as_pascal_case())
+                {
+                    Some(service) => service.add_endpoint(endpoint),
+                    None => {
+                        let mut service = Service::new(name);
+                        service.add_endpoint(endpoint);
+                        services.push(service);
+                    }
+                }
+            }
+        }
+        services.into_iter().for_each(|s| ir.add_service(s));
+        ir.validate()?;
+        Ok(ir)
+    }
+}
+
+/// Declares the types of all the definitions with the builder. Types defined more than once are declared once.
+fn declare_all(
+    mut builder: IrBuilder,
+    definitions: Vec<(&str, &Value)>,
+) -> Result<IrBuilder, Error> {
+    let mut declared: Vec<&str> = Vec::new();
+    for (name, schema) in definitions {
+        if declared.contains(&name) {
+            continue;
+        }
+        declared.push(name);
+        builder = declare(builder, name, schema)?;
+    }
+    Ok(builder)
+}
+
+/// Returns the endpoint of an OpenAPI operation.
+fn endpoint(
+    ir: &IntermediateRepresentation,
+    target: &TypeTarget,
+    path: &str,
+    method: HttpMethod,
+    operation: &Value,
+) -> Result<Endpoint, Error> {
+    let operation_id = operation
+        .get("operationId")
+        .and_then(Value::as_str)
+        .ok_or_else(|| {
+            Error::new(&format!(
+                "Operation `{} {path}` has no `operationId`",
+                method.as_str()
+            ))
+        })?;
+    let mut endpoint = Endpoint::new(rust_name(operation_id), method, path);
+    let parameters = operation.get("parameters").and_then(Value::as_array);
+    for parameter in parameters.into_iter().flatten() {
+        if !matches!(
+            parameter.get("in").and_then(Value::as_str),
+            Some("path") | Some("query")
+        ) {
+            continue;
+        }
+        let name = parameter.get("name").and_then(Value::as_str).unwrap_or("");
+        let subject = format!("{operation_id}.{name}");
+        let schema = parameter.get("schema").unwrap_or(&Value::Null);
+        let mut param_type = field_type(schema, &subject)?;
+        if parameter.get("required") != Some(&Value::Bool(true))
+            && !matches!(param_type, FieldType::Option(_))
+        {
+            param_type = FieldType::option(param_type);
+        }
+        endpoint.add_param(rust_name(name), resolve(ir, target, param_type, &subject)?);
+    }
+    if let Some(schema) = operation.pointer("/requestBody/content/application~1json/schema") {
+        let subject = format!("{operation_id}.body");
+        let body_type = field_type(schema, &subject)?;
+        endpoint.add_param(
+            Name::from_snake_case("body"),
+            resolve(ir, target, body_type, &subject)?,
+        );
+    }
+    let responses = operation.get("responses").and_then(Value::as_object);
+    let response = responses
+        .into_iter()
+        .flatten()
+        .filter(|(status, _)| status.starts_with('2'))
+        .find_map(|(_, response)| response.pointer("/content/application~1json/schema"));
+    if let Some(schema) = response {
+        let subject = format!("{operation_id}.response");
+        let response_type = field_type(schema, &subject)?;
+        endpoint.set_response(resolve(ir, target, response_type, &subject)?);
+    }
+    Ok(endpoint)
+}
+
+/// Returns the type of a parameter or a response in the target.
+/// It fails if the type references a type that is not declared by the component schemas.
+fn resolve(
+    ir: &IntermediateRepresentation,
+    target: &TypeTarget,
+    field_type: FieldType,
+    subject: &str,
+) -> Result<Type, Error> {
+    let declared = type_names(ir);
+    match field_type
+        .named_types()
+        .into_iter()
+        .find(|t| !declared.contains(t))
+    {
+        Some(unknown) => Err(Error::new(&format!(
+            "`{subject}` references undeclared type `{unknown}`"
+        ))),
+        None => Ok(field_type.resolve(target)),
+    }
+}
+
+/// Declares the type described by the schema with the builder.
+fn declare(builder: IrBuilder, name: &str, schema: &Value) -> Result<IrBuilder, Error> {
+    if let Some(values) = enum_values(schema) {
+        let mut builder = builder.enum_(name);
+        for value in values {
+            builder = builder
+                .variant(rust_name(value).as_pascal_case())
+                .json_name(value);
+        }
+        return Ok(builder);
+    }
+    let empty = Map::new();
+    let properties = match schema.get("properties") {
+        Some(properties) => properties.as_object(),
+        None if is_type(schema, "object") => Some(&empty),
+        None => None,
+    }
+    .ok_or_else(|| {
+        Error::new(&format!(
+            "`{name}` is not an object or a string enum schema"
+        ))
+    })?;
+    let required = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|r| r.iter().filter_map(Value::as_str).collect::<Vec<&str>>())
+        .unwrap_or_default();
+    let mut builder = builder.struct_(name);
+    for (json_name, property) in properties {
+        let subject = format!("{name}.{json_name}");
+        let mut field_type = field_type(property, &subject)?;
+        if !required.contains(&json_name.as_str()) && !matches!(field_type, FieldType::Option(_)) {
+            field_type = FieldType::option(field_type);
+        }
+        builder = builder
+            .field(rust_name(json_name).as_snake_case(), field_type)
+            .json_name(json_name);
+        if property.get("readOnly") == Some(&Value::Bool(true)) {
+            builder = builder.readonly();
+        }
+    }
+    Ok(builder)
+}
+
+/// Returns the type of a property schema. Nullable schemas are optional.
+fn field_type(schema: &Value, subject: &str) -> Result<FieldType, Error> {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let name = reference
+            .strip_prefix("#/definitions/")
+            .or_else(|| reference.strip_prefix("#/$defs/"))
+            .or_else(|| reference.strip_prefix("#/components/schemas/"))
+            .ok_or_else(|| {
+                Error::new(&format!(
+                    "`{subject}` references `{reference}`, which is not a definition"
+                ))
+            })?;
+        return Ok(FieldType::named(name));
+    }
+    for key in ["anyOf", "oneOf", "allOf"] {
+        if let Some(options) = schema.get(key).and_then(Value::as_array) {
+            let (nulls, others): (Vec<&Value>, Vec<&Value>) =
+                options.iter().partition(|o| is_type(o, "null"));
+            if others.len() != 1 {
+                break;
+            }
+            let inner = field_type(others[0], subject)?;
+            return Ok(match nulls.is_empty() && !is_nullable(schema) {
+                true => inner,
+                false => FieldType::option(inner),
+            });
+        }
+    }
+    let types = match schema.get("type") {
+        Some(Value::String(t)) => vec![t.as_str()],
+        Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    let nullable = types.contains(&"null") || is_nullable(schema);
+    let inner = match types.iter().find(|t| **t != "null").copied() {
+        Some("string") if enum_values(schema).is_none() => FieldType::string(),
+        Some("boolean") => FieldType::bool(),
+        Some("integer") => integer_type(schema.get("format").and_then(Value::as_str)),
+        Some("number") => match schema.get("format").and_then(Value::as_str) {
+            Some("float") => FieldType::F32,
+            _ => FieldType::f64(),
+        },
+        Some("array") => {
+            let items = schema.get("items").unwrap_or(&Value::Null);
+            FieldType::list(field_type(items, subject)?)
+        }
+        Some("object") if schema.get("properties").is_none() => {
+            match schema.get("additionalProperties") {
+                Some(values @ Value::Object(_)) => {
+                    FieldType::map(FieldType::string(), field_type(values, subject)?)
+                }
+                _ => return Err(unsupported(subject)),
+            }
+        }
+        _ => return Err(unsupported(subject)),
+    };
+    Ok(match nullable {
+        true => FieldType::option(inner),
+        false => inner,
+    })
+}
+
+/// Returns the type of an integer schema with the given `format`, which schemars sets to the Rust type.
+fn integer_type(format: Option<&str>) -> FieldType {
+    match format {
+        Some("int8") => FieldType::I8,
+        Some("int16") => FieldType::I16,
+        Some("int32") => FieldType::i32(),
+        Some("uint8") => FieldType::U8,
+        Some("uint16") => FieldType::U16,
+        Some("uint32") => FieldType::U32,
+        Some("uint64") | Some("uint") => FieldType::U64,
+        _ => FieldType::i64(),
+    }
+}
+
+/// Returns the values of a string enum schema, described with `enum` or with a `oneOf` of single values.
+fn enum_values(schema: &Value) -> Option<Vec<&str>> {
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        return values.iter().map(Value::as_str).collect();
+    }
+    let options = schema.get("oneOf").and_then(Value::as_array)?;
+    let mut values = Vec::new();
+    for option in options {
+        match option.get("const").and_then(Value::as_str) {
+            Some(value) => values.push(value),
+            None => values.extend(enum_values(option)?),
+        }
+    }
+    Some(values)
+}
+
+/// Returns the name of a property or a variant with the given JSON name, e.g. `due_date` for `dueDate`.
+fn rust_name(json_name: &str) -> Name {
+    if json_name.contains('-') {
+        Name::from_kebab_case(json_name)
+    } else if json_name.starts_with(|c: char| c.is_ascii_uppercase()) {
+        Name::from_pascal_case(json_name)
+    } else if json_name.contains(|c: char| c.is_ascii_uppercase()) {
+        Name::from_camel_case(json_name)
+    } else {
+        Name::from_snake_case(json_name)
+    }
+}
+
+/// Returns true if the schema is marked with `nullable`, which OpenAPI 3.0 uses instead of the `null` type.
+fn is_nullable(schema: &Value) -> bool {
+    schema.get("nullable") == Some(&Value::Bool(true))
+}
+
+fn is_type(schema: &Value, type_name: &str) -> bool {
+    schema.get("type").and_then(Value::as_str) == Some(type_name)
+}
+
+fn unsupported(subject: &str) -> Error {
+    Error::new(&format!(
+        "`{subject}` has a schema that can not be converted to a type"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{ParamKind, TypeKind};
+    use crate::targets::java::generate_java_code;
+    use serde_json::json;
+
+    #[test]
+    fn import_schemas() {
+        let order = json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "title": "Order",
+            "type": "object",
+            "required": ["id", "lines", "status", "attributes"],
+            "properties": {
+                "id": { "type": "integer", "format": "uint64", "readOnly": true },
+                "lines": { "type": "array", "items": { "$ref": "#/definitions/OrderLine" } },
+                "status": { "$ref": "#/definitions/OrderStatus" },
+                "attributes": { "type": "object", "additionalProperties": { "type": "string" } },
+                "shipping": {
+                    "anyOf": [{ "$ref": "#/definitions/Address" }, { "type": "null" }]
+                }
+            },
+            "definitions": {
+                "OrderLine": {
+                    "type": "object",
+                    "required": ["sku", "quantity"],
+                    "properties": {
+                        "sku": { "type": "string" },
+                        "quantity": { "type": "integer", "format": "uint32" },
+                        "discount": { "type": ["number", "null"], "format": "float" }
+                    }
+                },
+                "OrderStatus": {
+                    "oneOf": [
+                        { "type": "string", "enum": ["open", "in-transit"] },
+                        { "description": "Delivered to the customer.", "const": "delivered" }
+                    ]
+                },
+                "Address": {
+                    "type": "object",
+                    "required": ["street"],
+                    "properties": { "street": { "type": "string" } }
+                }
+            }
+        });
+        let address = json!({
+            "title": "Address",
+            "type": "object",
+            "required": ["street"],
+            "properties": { "street": { "type": "string" } }
+        });
+        let ir =
+            IntermediateRepresentation::from_json_schemas(TypeTarget::Java, [&order, &address])
+                .unwrap();
+        let structs = ir
+            .structs()
+            .iter()
+            .map(|s| s.self_type().type_name().as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(structs, ["Order", "Address", "OrderLine"]);
+        let fields = ir.structs()[0]
+            .fields()
+            .iter()
+            .map(|f| (f.json_name().as_str(), f.field_type().type_name().as_str()))
+            .collect::<Vec<(&str, &str)>>();
+        assert_eq!(
+            fields,
+            [
+                ("attributes", "java.util.Map<String, String>"),
+                ("id", "BigInteger"),
+                ("lines", "java.util.List<OrderLine>"),
+                ("shipping", "Address"),
+                ("status", "OrderStatus"),
+            ]
+        );
+        assert!(ir.structs()[0].fields()[1].is_readonly());
+        assert!(matches!(
+            ir.structs()[0].fields()[3].field_type().kind(),
+            TypeKind::Option(_)
+        ));
+        let variants = ir.enums()[0]
+            .variants()
+            .iter()
+            .map(|v| (v.name().as_pascal_case(), v.json_name().as_str()))
+            .collect::<Vec<(String, &str)>>();
+        assert_eq!(
+            variants,
+            [
+                ("Open".to_string(), "open"),
+                ("InTransit".to_string(), "in-transit"),
+                ("Delivered".to_string(), "delivered"),
+            ]
+        );
+        let classes = generate_java_code(&ir).unwrap();
+        assert!(classes.iter().any(|c| c.name() == "OrderLine"));
+    }
+
+    #[test]
+    fn import_openapi() {
+        let document = json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Orders", "version": "1.0.0" },
+            "paths": {
+                "/orders": {
+                    "get": {
+                        "tags": ["orders"],
+                        "operationId": "listOrders",
+                        "parameters": [
+                            { "name": "pageSize", "in": "query", "schema": { "type": "integer", "format": "int32" } },
+                            { "name": "X-Request-Id", "in": "header", "schema": { "type": "string" } }
+                        ],
+                        "responses": {
+                            "200": {
+                                "description": "",
+                                "content": {
+                                    "application/json": {
+                                        "schema": { "type": "array", "items": { "$ref": "#/components/schemas/Order" } }
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    "post": {
+                        "tags": ["orders"],
+                        "operationId": "create_order",
+                        "requestBody": {
+                            "content": {
+                                "application/json": { "schema": { "$ref": "#/components/schemas/Order" } }
+                            }
+                        },
+                        "responses": { "201": { "description": "" } }
+                    }
+                },
+                "/health": {
+                    "get": {
+                        "operationId": "health",
+                        "responses": { "200": { "description": "" } }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "Order": {
+                        "type": "object",
+                        "required": ["id"],
+                        "properties": {
+                            "id": { "type": "string" },
+                            "note": { "type": "string", "nullable": true },
+                            "status": { "allOf": [{ "$ref": "#/components/schemas/Status" }], "nullable": true }
+                        }
+                    },
+                    "Status": { "type": "string", "enum": ["Open", "Closed"] }
+                }
+            }
+        });
+        let ir = IntermediateRepresentation::from_openapi(TypeTarget::Java, &document).unwrap();
+        assert_eq!(ir.structs()[0].fields().len(), 3);
+        let services = ir
+            .services()
+            .iter()
+            .map(|s| s.name().as_pascal_case())
+            .collect::<Vec<String>>();
+        assert_eq!(services, ["Api", "Orders"]);
+        let orders = &ir.services()[1];
+        let list = &orders.endpoints()[0];
+        assert_eq!(list.name().as_snake_case(), "list_orders");
+        assert_eq!(list.params().len(), 1);
+        assert_eq!(list.params()[0].name().as_snake_case(), "page_size");
+        assert_eq!(list.params()[0].kind(), ParamKind::Query);
+        assert_eq!(
+            list.response().unwrap().type_name(),
+            "java.util.List<Order>"
+        );
+        let create = &orders.endpoints()[1];
+        assert_eq!(create.method(), HttpMethod::Post);
+        assert_eq!(create.params()[0].kind(), ParamKind::Body);
+        assert!(create.response().is_none());
+        let classes = generate_java_code(&ir).unwrap();
+        assert!(classes.iter().any(|c| c.name() == "Order"));
+        let document = json!({
+            "paths": {
+                "/orders": {
+                    "get": {
+                        "operationId": "list_orders",
+                        "responses": {
+                            "200": {
+                                "content": {
+                                    "application/json": { "schema": { "$ref": "#/components/schemas/Order" } }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        });
+        let error = IntermediateRepresentation::from_openapi(TypeTarget::Java, &document)
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.message(),
+            "`list_orders.response` references undeclared type `Order`"
+        );
+    }
+
+    #[test]
+    fn import_errors() {
+        let schema = json!({
+            "title": "Order",
+            "type": "object",
+            "properties": { "customer": { "type": "object", "properties": {} } }
+        });
+        let error = IntermediateRepresentation::from_json_schemas(TypeTarget::Java, [&schema])
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.message(),
+            "`Order.customer` has a schema that can not be converted to a type"
+        );
+        let schema = json!({ "title": "Order", "type": "string" });
+        let error = IntermediateRepresentation::from_json_schemas(TypeTarget::Java, [&schema])
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.message(),
+            "`Order` is not an object or a string enum schema"
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use crate::errors::Error;
+use crate::ir::Name;
+
+/// This enum represents the case conventions of serde `rename_all`,
+/// which derive the JSON names of fields and variants from their Rust names.
+///
+/// Like in serde, conventions are applied differently to fields, which are written in snake case,
+/// and to variants, which are written in pascal case. For example `lowercase` keeps the field `due_date`,
+/// but turns the variant `PaymentPending` into `paymentpending`.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{Name, RenameRule};
+///
+/// let rule = RenameRule::from_serde("SCREAMING-KEBAB-CASE").unwrap();
+/// assert_eq!(rule.apply_to_field(&Name::from_snake_case("due_date")), "DUE-DATE");
+/// assert_eq!(rule.apply_to_variant(&Name::from_pascal_case("PaymentPending")), "PAYMENT-PENDING");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RenameRule {
+    LowerCase,
+    UpperCase,
+    PascalCase,
+    CamelCase,
+    SnakeCase,
+    ScreamingSnakeCase,
+    KebabCase,
+    ScreamingKebabCase,
+}
+
+const RULES: [(&str, RenameRule); 8] = [
+    ("lowercase", RenameRule::LowerCase),
+    ("UPPERCASE", RenameRule::UpperCase),
+    ("PascalCase", RenameRule::PascalCase),
+    ("camelCase", RenameRule::CamelCase),
+    ("snake_case", RenameRule::SnakeCase),
+    ("SCREAMING_SNAKE_CASE", RenameRule::ScreamingSnakeCase),
+    ("kebab-case", RenameRule::KebabCase),
+    ("SCREAMING-KEBAB-CASE", RenameRule::ScreamingKebabCase),
+];
+
+impl RenameRule {
+    /// Parses the value of serde `rename_all`, e.g. `kebab-case`.
+    pub fn from_serde(value: &str) -> Result<Self, Error> {
+        RULES
+            .iter()
+            .find(|(name, _)| *name == value)
+            .map(|(_, rule)| *rule)
+            .ok_or_else(|| Error::new(&format!("Unknown rename rule `{value}`")))
+    }
+
+    /// Returns the value of serde `rename_all` for the convention.
+    pub fn serde_name(&self) -> &'static str {
+        RULES.iter().find(|(_, rule)| rule == self).unwrap().0
+    }
+
+    /// Returns the JSON name of a struct field with the given name.
+    pub fn apply_to_field(&self, name: &Name) -> String {
+        match self {
+            RenameRule::LowerCase | RenameRule::SnakeCase => name.as_snake_case(),
+            RenameRule::UpperCase | RenameRule::ScreamingSnakeCase => name.as_upper_snake_case(),
+            _ => self.apply_to_variant(name),
+        }
+    }
+
+    /// Returns the JSON name of an enum variant with the given name.
+    pub fn apply_to_variant(&self, name: &Name) -> String {
+        match self {
+            RenameRule::LowerCase => name.as_lower_case(),
+            RenameRule::UpperCase => name.as_upper_case(),
+            RenameRule::PascalCase => name.as_pascal_case(),
+            RenameRule::CamelCase => name.as_camel_case(),
+            RenameRule::SnakeCase => name.as_snake_case(),
+            RenameRule::ScreamingSnakeCase => name.as_upper_snake_case(),
+            RenameRule::KebabCase => name.as_kebab_case(),
+            RenameRule::ScreamingKebabCase => name.as_upper_kebab_case(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_conventions() {
+        let field = Name::from_snake_case("due_date");
+        let variant = Name::from_pascal_case("PaymentPending");
+        let expected = [
+            ("lowercase", "due_date", "paymentpending"),
+            ("UPPERCASE", "DUE_DATE", "PAYMENTPENDING"),
+            ("PascalCase", "DueDate", "PaymentPending"),
+            ("camelCase", "dueDate", "paymentPending"),
+            ("snake_case", "due_date", "payment_pending"),
+            ("SCREAMING_SNAKE_CASE", "DUE_DATE", "PAYMENT_PENDING"),
+            ("kebab-case", "due-date", "payment-pending"),
+            ("SCREAMING-KEBAB-CASE", "DUE-DATE", "PAYMENT-PENDING"),
+        ];
+        for (convention, field_name, variant_name) in expected {
+            let rule = RenameRule::from_serde(convention).unwrap();
+            assert_eq!(rule.serde_name(), convention);
+            assert_eq!(rule.apply_to_field(&field), field_name);
+            assert_eq!(rule.apply_to_variant(&variant), variant_name);
+        }
+        let error = RenameRule::from_serde("Title Case").unwrap_err();
+        assert_eq!(error.message(), "Unknown rename rule `Title Case`");
+    }
+}
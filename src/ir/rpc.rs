@@ -0,0 +1,99 @@
+use crate::ir::{CustomType, Field, Name, Type};
+
+/// This is a representation of a JSON-RPC service trait in the IR.
+/// Its methods are called by name, with named parameters sent as a single JSON object.
+#[derive(Clone)]
+pub struct RpcService {
+    name: Name,
+    methods: Vec<RpcMethod>,
+}
+
+/// This is a single method of an `RpcService`.
+/// Its parameters form a request DTO, which is added to the IR as a struct, and its result is the response DTO.
+#[derive(Clone)]
+pub struct RpcMethod {
+    name: Name,
+    rpc_name: String,
+    params: Vec<Field>,
+    result: Option<Type>,
+}
+
+impl RpcService {
+    pub fn new(name: Name) -> Self {
+        Self {
+            name,
+            methods: Vec::new(),
+        }
+    }
+
+    pub fn add_method(&mut self, method: RpcMethod) {
+        self.methods.push(method);
+    }
+
+    pub fn name(&self) -> &Name {
+        &self.name
+    }
+
+    pub fn methods(&self) -> &Vec<RpcMethod> {
+        &self.methods
+    }
+
+    /// Returns the types of all the results, so that they can be rewritten.
+    pub(crate) fn types_mut(&mut self) -> Vec<&mut Type> {
+        self.methods
+            .iter_mut()
+            .filter_map(|m| m.result.as_mut())
+            .collect()
+    }
+}
+
+impl RpcMethod {
+    /// Creates a method, where `rpc_name` is the name used in JSON-RPC requests, e.g. `user.get`.
+    pub fn new<S: Into<String>>(name: Name, rpc_name: S) -> Self {
+        Self {
+            name,
+            rpc_name: rpc_name.into(),
+            params: Vec::new(),
+            result: None,
+        }
+    }
+
+    /// Adds a named parameter, which is sent under its snake case name.
+    pub fn add_param(&mut self, name: Name, param_type: Type) {
+        let json_name = name.as_snake_case();
+        self.params.push(Field::new(name, json_name, param_type));
+    }
+
+    pub fn set_result(&mut self, result: Type) {
+        self.result = Some(result);
+    }
+
+    pub fn name(&self) -> &Name {
+        &self.name
+    }
+
+    pub fn rpc_name(&self) -> &str {
+        &self.rpc_name
+    }
+
+    pub fn params(&self) -> &Vec<Field> {
+        &self.params
+    }
+
+    pub fn result(&self) -> Option<&Type> {
+        self.result.as_ref()
+    }
+
+    /// Returns the type of the request DTO holding the parameters, e.g. `GetUserParams` for `get_user`.
+    /// It is `None` for methods without parameters, which are called without params.
+    pub fn params_type(&self) -> Option<CustomType> {
+        if self.params.is_empty() {
+            None
+        } else {
+            Some(CustomType::new(format!(
+                "{}Params",
+                self.name.as_pascal_case()
+            )))
+        }
+    }
+}
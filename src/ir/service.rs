@@ -0,0 +1,169 @@
+use crate::ir::{Name, Type};
+
+/// This is a representation of a service trait in the IR.
+/// It describes HTTP endpoints using the data structures from the IR as requests and responses.
+#[derive(Clone)]
+pub struct Service {
+    name: Name,
+    endpoints: Vec<Endpoint>,
+}
+
+/// This is a single endpoint of a `Service`.
+#[derive(Clone)]
+pub struct Endpoint {
+    name: Name,
+    method: HttpMethod,
+    path: String,
+    params: Vec<Param>,
+    response: Option<Type>,
+}
+
+/// This enum represents HTTP methods supported by endpoints.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HttpMethod {
+    Get,
+    Post,
+    Put,
+    Patch,
+    Delete,
+}
+
+/// This is a parameter of an `Endpoint`.
+#[derive(Clone)]
+pub struct Param {
+    name: Name,
+    param_type: Type,
+    kind: ParamKind,
+}
+
+/// This enum describes where a parameter is sent in an HTTP request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParamKind {
+    /// Parameter that replaces a `{name}` placeholder in the path.
+    Path,
+    /// Parameter sent in the query string.
+    Query,
+    /// Parameter sent as the JSON request body.
+    Body,
+}
+
+impl Service {
+    pub fn new(name: Name) -> Self {
+        Self {
+            name,
+            endpoints: Vec::new(),
+        }
+    }
+
+    pub fn add_endpoint(&mut self, endpoint: Endpoint) {
+        self.endpoints.push(endpoint);
+    }
+
+    pub fn name(&self) -> &Name {
+        &self.name
+    }
+
+    pub fn endpoints(&self) -> &Vec<Endpoint> {
+        &self.endpoints
+    }
+
+    /// Returns the types of all the parameters and responses, so that they can be rewritten.
+    pub(crate) fn types_mut(&mut self) -> Vec<&mut Type> {
+        let mut types = Vec::new();
+        for endpoint in &mut self.endpoints {
+            types.extend(endpoint.params.iter_mut().map(|p| &mut p.param_type));
+            types.extend(endpoint.response.as_mut());
+        }
+        types
+    }
+}
+
+impl Endpoint {
+    pub fn new<S: Into<String>>(name: Name, method: HttpMethod, path: S) -> Self {
+        Self {
+            name,
+            method,
+            path: path.into(),
+            params: Vec::new(),
+            response: None,
+        }
+    }
+
+    /// Adds a parameter. Its kind is derived from the endpoint:
+    /// parameters with a `{name}` placeholder in the path are path parameters,
+    /// a parameter called `body` is the request body and all the other parameters are sent in the query string.
+    pub fn add_param(&mut self, name: Name, param_type: Type) {
+        let kind = if self.path_variables().contains(&name.as_snake_case()) {
+            ParamKind::Path
+        } else if name.as_snake_case() == "body" {
+            ParamKind::Body
+        } else {
+            ParamKind::Query
+        };
+        self.params.push(Param {
+            name,
+            param_type,
+            kind,
+        });
+    }
+
+    pub fn set_response(&mut self, response: Type) {
+        self.response = Some(response);
+    }
+
+    pub fn name(&self) -> &Name {
+        &self.name
+    }
+
+    pub fn method(&self) -> HttpMethod {
+        self.method
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn params(&self) -> &Vec<Param> {
+        &self.params
+    }
+
+    pub fn response(&self) -> Option<&Type> {
+        self.response.as_ref()
+    }
+
+    /// Returns names of `{name}` placeholders in the path.
+    pub fn path_variables(&self) -> Vec<String> {
+        self.path
+            .split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}'))
+            .map(|(variable, _)| variable.to_string())
+            .collect()
+    }
+}
+
+impl HttpMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HttpMethod::Get => "GET",
+            HttpMethod::Post => "POST",
+            HttpMethod::Put => "PUT",
+            HttpMethod::Patch => "PATCH",
+            HttpMethod::Delete => "DELETE",
+        }
+    }
+}
+
+impl Param {
+    pub fn name(&self) -> &Name {
+        &self.name
+    }
+
+    pub fn param_type(&self) -> &Type {
+        &self.param_type
+    }
+
+    pub fn kind(&self) -> ParamKind {
+        self.kind
+    }
+}
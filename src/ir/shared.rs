@@ -0,0 +1,208 @@
+use crate::ir::{Field, IntermediateRepresentation, Struct};
+use std::collections::BTreeSet;
+
+/// This struct describes a set of fields shared by multiple structs, with the same JSON names and types.
+/// Field names are Rust names, e.g. `created_at`, and struct names are generated type names, e.g. `Invoice`.
+pub struct SharedFields {
+    fields: Vec<String>,
+    structs: Vec<String>,
+}
+
+impl SharedFields {
+    pub fn fields(&self) -> &[String] {
+        &self.fields
+    }
+
+    pub fn structs(&self) -> &[String] {
+        &self.structs
+    }
+}
+
+impl IntermediateRepresentation {
+    /// This function finds sets of at least `min_fields` fields that two or more structs share,
+    /// e.g. as candidates for a common Java interface made with `JavaConfig::with_shared_interface`.
+    ///
+    /// Every set is the largest one shared by some pair of structs, and lists all the structs having it.
+    /// Sets shared by more structs come first, then larger sets.
+    ///
+    /// Example:
+    /// ```rust
+    /// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+    /// use rdc::RDC;
+    ///
+    /// #[derive(RDC)]
+    /// struct Invoice {
+    ///     id: String,
+    ///     created_at: i64,
+    ///     total: f64,
+    /// }
+    ///
+    /// #[derive(RDC)]
+    /// struct Customer {
+    ///     id: String,
+    ///     created_at: i64,
+    ///     name: String,
+    /// }
+    ///
+    /// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+    /// ir.add::<Invoice>();
+    /// ir.add::<Customer>();
+    /// let shared = ir.shared_fields(2);
+    /// assert_eq!(shared[0].fields(), ["id", "created_at"]);
+    /// assert_eq!(shared[0].structs(), ["Invoice", "Customer"]);
+    /// ```
+    pub fn shared_fields(&self, min_fields: usize) -> Vec<SharedFields> {
+        let structs = self.structs();
+        let mut found: Vec<(BTreeSet<FieldKey>, SharedFields)> = Vec::new();
+        for (i, a) in structs.iter().enumerate() {
+            for b in &structs[i + 1..] {
+                let fields = a
+                    .fields()
+                    .iter()
+                    .filter(|f| b.fields().iter().any(|other| key(other) == key(f)))
+                    .collect::<Vec<&Field>>();
+                if fields.len() < min_fields.max(1) {
+                    continue;
+                }
+                let keys = fields
+                    .iter()
+                    .map(|f| key(f))
+                    .collect::<BTreeSet<FieldKey>>();
+                if found.iter().any(|(existing, _)| existing == &keys) {
+                    continue;
+                }
+                let sharing = structs
+                    .iter()
+                    .filter(|s| keys.iter().all(|k| has_field(s, k)))
+                    .map(|s| s.self_type().type_name().clone())
+                    .collect();
+                let shared = SharedFields {
+                    fields: fields.iter().map(|f| f.name().as_snake_case()).collect(),
+                    structs: sharing,
+                };
+                found.push((keys, shared));
+            }
+        }
+        let mut shared = found
+            .into_iter()
+            .map(|(_, shared)| shared)
+            .collect::<Vec<SharedFields>>();
+        shared.sort_by(|a, b| {
+            b.structs
+                .len()
+                .cmp(&a.structs.len())
+                .then(b.fields.len().cmp(&a.fields.len()))
+        });
+        shared
+    }
+
+    /// This function returns the shared interfaces named with `#[rdc(implements_shared = ...)]`, in the order they are
+    /// first named. Each of them has the fields all the structs naming it share, in the order of the first struct.
+    pub fn declared_shared_fields(&self) -> Vec<(String, SharedFields)> {
+        let mut names: Vec<&String> = Vec::new();
+        for s in self.structs() {
+            for name in s.shared_interfaces() {
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        names
+            .into_iter()
+            .map(|name| {
+                let structs = self
+                    .structs()
+                    .iter()
+                    .filter(|s| s.shared_interfaces().contains(name))
+                    .collect::<Vec<&Struct>>();
+                let fields = structs[0]
+                    .fields()
+                    .iter()
+                    .filter(|f| structs.iter().all(|s| has_field(s, &key(f))))
+                    .map(|f| f.name().as_snake_case())
+                    .collect();
+                let shared = SharedFields {
+                    fields,
+                    structs: structs
+                        .iter()
+                        .map(|s| s.self_type().type_name().clone())
+                        .collect(),
+                };
+                (name.clone(), shared)
+            })
+            .collect()
+    }
+}
+
+/// Fields are the same when their Rust names, JSON names and types are the same.
+type FieldKey = (String, String, String);
+
+fn key(f: &Field) -> FieldKey {
+    (
+        f.name().as_snake_case(),
+        f.json_name().clone(),
+        f.field_type().type_name().clone(),
+    )
+}
+
+fn has_field(s: &Struct, k: &FieldKey) -> bool {
+    s.fields().iter().any(|f| &key(f) == k)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::ir::{IntermediateRepresentation, TypeTarget};
+    use crate::RDC;
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Order {
+        id: String,
+        created_at: i64,
+        owner: String,
+        total: f64,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Customer {
+        id: String,
+        created_at: i64,
+        owner: String,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Product {
+        id: String,
+        #[serde(rename = "createdAt")]
+        created_at: i64,
+        total: f64,
+    }
+
+    #[test]
+    fn find_shared_fields() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Order>();
+        ir.add::<Customer>();
+        ir.add::<Product>();
+        let shared = ir.shared_fields(1);
+        let sets = shared
+            .iter()
+            .map(|s| (s.fields().join(","), s.structs().join(",")))
+            .collect::<Vec<(String, String)>>();
+        assert_eq!(
+            sets,
+            [
+                ("id".to_string(), "Order,Customer,Product".to_string()),
+                (
+                    "id,created_at,owner".to_string(),
+                    "Order,Customer".to_string()
+                ),
+                ("id,total".to_string(), "Order,Product".to_string()),
+            ]
+        );
+        assert_eq!(ir.shared_fields(3).len(), 1);
+    }
+}
@@ -0,0 +1,401 @@
+use crate::errors::Error;
+use crate::ir::{
+    CustomType, DataEnum, DataEnumObjectField, DataEnumStyle, DataEnumVariant, DateTimeFormat,
+    Enum, EnumVariant, Field, IntermediateRepresentation, Name, Polymorphic, PolymorphicVariant,
+    Struct, Type, TypeKind, TypeTarget,
+};
+use crate::targets::json::Json;
+use std::io::Write;
+
+impl IntermediateRepresentation {
+    /// Serializes the types of the IR to JSON, so that it can be saved and compared with later versions,
+    /// e.g. to generate migrations or to check backward compatibility. Services are not included.
+    ///
+    /// Example:
+    /// ```rust
+    /// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+    /// use rdc::RDC;
+    ///
+    /// #[derive(RDC)]
+    /// struct Invoice {
+    ///     number: String,
+    /// }
+    ///
+    /// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+    /// ir.add::<Invoice>();
+    /// let snapshot = ir.to_snapshot();
+    /// let restored = IntermediateRepresentation::from_snapshot(&snapshot).unwrap();
+    /// assert_eq!(restored.structs()[0].fields()[0].json_name(), "number");
+    /// ```
+    pub fn to_snapshot(&self) -> String {
+        let structs = self.structs().iter().map(|s| {
+            let fields = s.fields().iter().map(|f| {
+                Json::Object(vec![
+                    ("name", Json::string(f.name().as_snake_case())),
+                    ("json_name", Json::string(f.json_name())),
+                    ("type", type_to_json(f.field_type())),
+                    ("readonly", Json::Bool(f.is_readonly())),
+                    (
+                        "format",
+                        f.format()
+                            .map(|f| Json::string(f.pattern()))
+                            .unwrap_or(Json::Null),
+                    ),
+                    (
+                        "timezone",
+                        f.format()
+                            .and_then(|f| f.timezone())
+                            .map(Json::string)
+                            .unwrap_or(Json::Null),
+                    ),
+                ])
+            });
+            Json::Object(vec![
+                ("name", Json::string(s.name().as_snake_case())),
+                ("type", Json::string(s.self_type().type_name())),
+                ("transparent", Json::Bool(s.is_transparent())),
+                ("fields", Json::Array(fields.collect())),
+            ])
+        });
+        let enums = self.enums().iter().map(|e| {
+            let variants = e.variants().iter().map(|v| {
+                Json::Object(vec![
+                    ("name", Json::string(v.name().as_snake_case())),
+                    ("json_name", Json::string(v.json_name())),
+                ])
+            });
+            Json::Object(vec![
+                ("name", Json::string(e.name().as_snake_case())),
+                ("type", Json::string(e.self_type().type_name())),
+                ("variants", Json::Array(variants.collect())),
+            ])
+        });
+        let data_enums = self.data_enums().iter().map(|de| {
+            let variants = de.variants().iter().map(|v| {
+                let mut entries = vec![
+                    ("name", Json::string(v.name().as_snake_case())),
+                    ("json_name", Json::string(v.json_name())),
+                ];
+                match v {
+                    DataEnumVariant::Unit { .. } => entries.push(("kind", Json::string("unit"))),
+                    DataEnumVariant::Tuple { fields, .. } => {
+                        entries.push(("kind", Json::string("tuple")));
+                        entries.push((
+                            "fields",
+                            Json::Array(fields.iter().map(type_to_json).collect()),
+                        ));
+                    }
+                    DataEnumVariant::Object { fields, .. } => {
+                        let fields = fields.iter().map(|f| {
+                            Json::Object(vec![
+                                ("name", Json::string(f.name().as_snake_case())),
+                                ("json_name", Json::string(f.json_name())),
+                                ("type", type_to_json(f.field_type())),
+                            ])
+                        });
+                        entries.push(("kind", Json::string("object")));
+                        entries.push(("fields", Json::Array(fields.collect())));
+                    }
+                }
+                Json::Object(entries)
+            });
+            Json::Object(vec![
+                ("name", Json::string(de.name().as_snake_case())),
+                ("type", Json::string(de.self_type().type_name())),
+                ("variants", Json::Array(variants.collect())),
+            ])
+        });
+        let polymorphics = self.polymorphics().iter().map(|p| {
+            let variants = p.variants().iter().map(|v| {
+                Json::Object(vec![
+                    ("json_name", Json::string(v.json_name())),
+                    ("type", type_to_json(v.variant_type())),
+                ])
+            });
+            Json::Object(vec![
+                ("name", Json::string(p.name().as_snake_case())),
+                ("type", Json::string(p.self_type().type_name())),
+                ("tag", p.tag().map(Json::string).unwrap_or(Json::Null)),
+                ("variants", Json::Array(variants.collect())),
+            ])
+        });
+        let snapshot = Json::Object(vec![
+            ("structs", Json::Array(structs.collect())),
+            ("enums", Json::Array(enums.collect())),
+            ("data_enums", Json::Array(data_enums.collect())),
+            ("polymorphics", Json::Array(polymorphics.collect())),
+        ]);
+        let mut rendered = snapshot.render();
+        rendered.push('\n');
+        rendered
+    }
+
+    /// Restores an IR from a snapshot created with `to_snapshot`.
+    pub fn from_snapshot(snapshot: &str) -> Result<Self, Error> {
+        let json = Json::parse(snapshot)?;
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        for s in array(&json, "structs")? {
+            let mut struct_ir = Struct::new(name(s)?, CustomType::new(string(s, "type")?));
+            struct_ir.set_transparent(field_bool(s, "transparent"));
+            for f in array(s, "fields")? {
+                let mut field = Field::new(
+                    name(f)?,
+                    string(f, "json_name")?,
+                    type_from_json(field(f, "type")?)?,
+                );
+                field.set_readonly(field_bool(f, "readonly"));
+                if let Some(pattern) = f.get("format").and_then(|p| p.as_str()) {
+                    let mut format = DateTimeFormat::new(pattern);
+                    if let Some(timezone) = f.get("timezone").and_then(|t| t.as_str()) {
+                        format = format.with_timezone(timezone);
+                    }
+                    field.set_format(format);
+                }
+                struct_ir.add_field(field);
+            }
+            ir.add_struct(struct_ir);
+        }
+        for e in array(&json, "enums")? {
+            let mut enum_ir = Enum::new(name(e)?, CustomType::new(string(e, "type")?));
+            for v in array(e, "variants")? {
+                enum_ir.add_variant(EnumVariant::new(name(v)?, string(v, "json_name")?));
+            }
+            ir.add_enum(enum_ir);
+        }
+        for de in array(&json, "data_enums")? {
+            let mut data_enum = DataEnum::new(
+                name(de)?,
+                CustomType::new(string(de, "type")?),
+                DataEnumStyle::External,
+            );
+            for v in array(de, "variants")? {
+                let variant_name = name(v)?;
+                let json_name = string(v, "json_name")?.to_string();
+                let variant = match string(v, "kind")? {
+                    "unit" => DataEnumVariant::unit(variant_name, json_name),
+                    "tuple" => DataEnumVariant::tuple(
+                        variant_name,
+                        json_name,
+                        array(v, "fields")?
+                            .iter()
+                            .map(type_from_json)
+                            .collect::<Result<Vec<Type>, Error>>()?,
+                    ),
+                    "object" => DataEnumVariant::object(
+                        variant_name,
+                        json_name,
+                        array(v, "fields")?
+                            .iter()
+                            .map(|f| {
+                                Ok(DataEnumObjectField::new(
+                                    name(f)?,
+                                    string(f, "json_name")?.to_string(),
+                                    type_from_json(field(f, "type")?)?,
+                                ))
+                            })
+                            .collect::<Result<Vec<DataEnumObjectField>, Error>>()?,
+                    ),
+                    kind => {
+                        return Err(Error::new(&format!(
+                            "Unknown data enum variant kind `{kind}` in snapshot"
+                        )))
+                    }
+                };
+                data_enum.add_variant(variant);
+            }
+            ir.add_data_enum(data_enum);
+        }
+        for p in array(&json, "polymorphics")? {
+            let tag = field(p, "tag")?.as_str().map(|t| t.to_string());
+            let mut polymorphic =
+                Polymorphic::new(name(p)?, CustomType::new(string(p, "type")?), tag);
+            for v in array(p, "variants")? {
+                polymorphic.add_variant(PolymorphicVariant::new(
+                    string(v, "json_name")?,
+                    type_from_json(field(v, "type")?)?,
+                ));
+            }
+            ir.add_polymorphic(polymorphic);
+        }
+        Ok(ir)
+    }
+
+    /// Writes the snapshot of the IR to the given file.
+    pub fn write_snapshot(&self, file_path: &str) -> Result<(), Error> {
+        if let Some(parent) = std::path::Path::new(file_path).parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|_| Error::new("Failed to create directory"))?;
+        }
+        let mut file =
+            std::fs::File::create(file_path).map_err(|_| Error::new("Failed to create file"))?;
+        file.write_all(self.to_snapshot().as_bytes())
+            .map_err(|_| Error::new("Failed to write to file"))
+    }
+
+    /// Reads an IR from a snapshot file written with `write_snapshot`.
+    pub fn read_snapshot(file_path: &str) -> Result<Self, Error> {
+        let snapshot = std::fs::read_to_string(file_path)
+            .map_err(|_| Error::new(&format!("Failed to read snapshot `{file_path}`")))?;
+        Self::from_snapshot(&snapshot)
+    }
+}
+
+fn type_to_json(t: &Type) -> Json {
+    let (kind, args) = match t.kind() {
+        TypeKind::Named => ("named", Vec::new()),
+        TypeKind::List(element) => ("list", vec![type_to_json(element)]),
+        TypeKind::Map(key, value) => ("map", vec![type_to_json(key), type_to_json(value)]),
+        TypeKind::Option(inner) => ("option", vec![type_to_json(inner)]),
+        TypeKind::UnsignedLong => ("unsigned_long", Vec::new()),
+    };
+    let mut entries = vec![
+        ("name", Json::string(t.type_name())),
+        ("kind", Json::string(kind)),
+    ];
+    if !args.is_empty() {
+        entries.push(("args", Json::Array(args)));
+    }
+    Json::Object(entries)
+}
+
+fn type_from_json(json: &Json) -> Result<Type, Error> {
+    let type_name = string(json, "name")?;
+    let mut args = match json.get("args") {
+        Some(_) => array(json, "args")?
+            .iter()
+            .map(type_from_json)
+            .collect::<Result<Vec<Type>, Error>>()?,
+        None => Vec::new(),
+    }
+    .into_iter();
+    let mut arg = || {
+        args.next().map(Box::new).ok_or_else(|| {
+            Error::new(&format!(
+                "Missing type argument of `{type_name}` in snapshot"
+            ))
+        })
+    };
+    let kind = match string(json, "kind")? {
+        "named" => TypeKind::Named,
+        "list" => TypeKind::List(arg()?),
+        "map" => TypeKind::Map(arg()?, arg()?),
+        "option" => TypeKind::Option(arg()?),
+        "unsigned_long" => TypeKind::UnsignedLong,
+        kind => {
+            return Err(Error::new(&format!(
+                "Unknown type kind `{kind}` in snapshot"
+            )))
+        }
+    };
+    Ok(Type::with_kind(type_name, kind))
+}
+
+fn field<'a>(json: &'a Json, key: &str) -> Result<&'a Json, Error> {
+    json.get(key)
+        .ok_or_else(|| Error::new(&format!("Missing `{key}` in snapshot")))
+}
+
+fn string<'a>(json: &'a Json, key: &str) -> Result<&'a str, Error> {
+    field(json, key)?
+        .as_str()
+        .ok_or_else(|| Error::new(&format!("`{key}` in snapshot is not a string")))
+}
+
+fn array<'a>(json: &'a Json, key: &str) -> Result<&'a [Json], Error> {
+    field(json, key)?
+        .as_array()
+        .ok_or_else(|| Error::new(&format!("`{key}` in snapshot is not an array")))
+}
+
+fn field_bool(json: &Json, key: &str) -> bool {
+    json.get(key).and_then(|b| b.as_bool()).unwrap_or(false)
+}
+
+fn name(json: &Json) -> Result<Name, Error> {
+    string(json, "name").map(Name::from_snake_case)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::ir::{DateTimeFormat, IntermediateRepresentation, TypeTarget};
+    use crate::{rdc_polymorphic, RDC};
+    use std::collections::HashMap;
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Invoice {
+        #[serde(rename = "no")]
+        number: String,
+        lines: Vec<Option<String>>,
+        totals: HashMap<String, u64>,
+        discount: Discount,
+        payment: Box<dyn Payment>,
+        status: Status,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Status {
+        Draft,
+        Paid,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Discount {
+        Percent(i32),
+        Fixed { amount: f64 },
+        None,
+    }
+
+    trait Payment {}
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Card {
+        number: String,
+    }
+    impl Payment for Card {}
+
+    rdc_polymorphic!(dyn Payment => Card);
+
+    #[test]
+    fn snapshot_round_trip() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Invoice>();
+        ir.set_field_format(
+            "Invoice",
+            "number",
+            DateTimeFormat::new("yyyyMMdd").with_timezone("UTC"),
+        )
+        .unwrap();
+        let snapshot = ir.to_snapshot();
+        let restored = IntermediateRepresentation::from_snapshot(&snapshot).unwrap();
+        assert_eq!(restored.to_snapshot(), snapshot);
+        assert_eq!(
+            restored
+                .structs()
+                .iter()
+                .find(|s| s.self_type().type_name() == "Invoice")
+                .unwrap()
+                .fields()[0]
+                .format(),
+            Some(&DateTimeFormat::new("yyyyMMdd").with_timezone("UTC"))
+        );
+        let generated = rdc::targets::java::generate_java_code(&ir).unwrap();
+        let regenerated = rdc::targets::java::generate_java_code(&restored).unwrap();
+        assert_eq!(generated.len(), regenerated.len());
+        for (a, b) in generated.iter().zip(regenerated.iter()) {
+            assert_eq!(a.code(), b.code());
+        }
+    }
+
+    #[test]
+    fn invalid_snapshot() {
+        let error = IntermediateRepresentation::from_snapshot("{\"structs\": []}")
+            .err()
+            .unwrap();
+        assert_eq!(error.message(), "Missing `enums` in snapshot");
+    }
+}
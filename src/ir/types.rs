@@ -3,6 +3,7 @@ use crate::targets::java::type_resolver::{JavaCustomType, JavaType};
 
 /// This is a type that is used to mark a target type for IR data.
 /// IR is mostly generic, but type resolution is target specific.
+#[derive(Clone)]
 pub enum TypeTarget {
     Java,
 }
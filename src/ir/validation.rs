@@ -0,0 +1,280 @@
+use crate::ir::{
+    DataEnumVariant, HttpMethod, IntermediateRepresentation, ParamKind, Type, TypeKind,
+};
+use crate::targets::java::type_resolver::DURATION;
+
+/// This function finds enums without any variants.
+/// Such enums can not be instantiated, so there is no JSON representation to generate code for.
+pub(crate) fn empty_enums(ir: &IntermediateRepresentation) -> Vec<String> {
+    let enums = ir
+        .enums()
+        .iter()
+        .filter(|e| e.variants().is_empty())
+        .map(|e| e.self_type());
+    let data_enums = ir
+        .data_enums()
+        .iter()
+        .filter(|de| de.variants().is_empty())
+        .map(|de| de.self_type());
+    enums
+        .chain(data_enums)
+        .map(|t| {
+            format!(
+                "Enum `{}` has no variants, so it can not be represented in JSON",
+                t.type_name()
+            )
+        })
+        .collect()
+}
+
+/// This function checks that every polymorphic type can be represented in JSON.
+/// Its variants have to be structs, so that they can implement the generated base type,
+/// and they must not have a field with the same JSON name as the tag.
+pub(crate) fn invalid_polymorphics(ir: &IntermediateRepresentation) -> Vec<String> {
+    let mut problems = Vec::new();
+    for p in ir.polymorphics() {
+        let owner = format!("Polymorphic type `{}`", p.self_type().type_name());
+        if p.variants().is_empty() {
+            problems.push(format!("{owner} has no variants"));
+        }
+        let variants = p
+            .variants()
+            .iter()
+            .map(|v| (v.variant_type().type_name().clone(), v.json_name().as_str()));
+        problems.extend(find_duplicates(&owner, "variants", variants));
+        for variant in p.variants() {
+            let type_name = variant.variant_type().type_name();
+            let variant_struct = ir
+                .structs()
+                .iter()
+                .find(|s| s.self_type().type_name() == type_name);
+            match (variant_struct, p.tag()) {
+                (None, _) => problems.push(format!(
+                    "{owner} has variant `{type_name}`, which is not a struct"
+                )),
+                (Some(s), Some(tag)) if s.fields().iter().any(|f| f.json_name() == tag) => problems
+                    .push(format!(
+                        "{owner} uses tag `{tag}`, which is also a field of `{type_name}`"
+                    )),
+                _ => {}
+            }
+        }
+    }
+    problems
+}
+
+/// This function checks that every transparent struct has exactly one field, whose JSON is the JSON of the struct.
+pub(crate) fn invalid_transparent_structs(ir: &IntermediateRepresentation) -> Vec<String> {
+    ir.structs()
+        .iter()
+        .filter(|s| s.is_transparent() && s.fields().len() != 1)
+        .map(|s| {
+            format!(
+                "Transparent struct `{}` has {} fields instead of one",
+                s.self_type().type_name(),
+                s.fields().len()
+            )
+        })
+        .collect()
+}
+
+/// This function finds fields of type `std::time::Duration`, which serde writes as objects of `secs` and `nanos`.
+/// There is no target type for that layout, so such struct fields have to be written with `SerdeAdapter::DurationSeconds`.
+pub(crate) fn unadapted_durations(ir: &IntermediateRepresentation) -> Vec<String> {
+    let mut fields: Vec<(String, &Type)> = Vec::new();
+    for s in ir.structs() {
+        let owner = s.self_type().type_name();
+        for f in s.fields() {
+            let name = format!("{owner}.{}", f.name().as_snake_case());
+            fields.push((name, f.field_type()));
+        }
+    }
+    for de in ir.data_enums() {
+        for v in de.variants() {
+            let owner = format!(
+                "{}::{}",
+                de.self_type().type_name(),
+                v.name().as_pascal_case()
+            );
+            match v {
+                DataEnumVariant::Object {
+                    fields: object_fields,
+                    ..
+                } => fields.extend(object_fields.iter().map(|f| {
+                    (
+                        format!("{owner}.{}", f.name().as_snake_case()),
+                        f.field_type(),
+                    )
+                })),
+                DataEnumVariant::Tuple {
+                    fields: tuple_fields,
+                    ..
+                } => fields.extend(
+                    tuple_fields
+                        .iter()
+                        .enumerate()
+                        .map(|(i, t)| (format!("{owner}.{i}"), t)),
+                ),
+                DataEnumVariant::Unit { .. } => {}
+            }
+        }
+    }
+    fields
+        .into_iter()
+        .filter(|(_, t)| contains_duration(t))
+        .map(|(field, _)| {
+            format!("`{field}` is a `Duration`, which is only supported in struct fields with `SerdeAdapter::DurationSeconds`")
+        })
+        .collect()
+}
+
+fn contains_duration(t: &Type) -> bool {
+    match t.kind() {
+        TypeKind::List(inner) | TypeKind::Option(inner) => contains_duration(inner),
+        TypeKind::Map(key, value) => contains_duration(key) || contains_duration(value),
+        TypeKind::Named => t.type_name() == DURATION,
+        TypeKind::UnsignedLong => false,
+    }
+}
+
+/// This function checks that every endpoint of a service can be turned into an HTTP request.
+/// Each `{name}` placeholder in the path needs a matching parameter and there can be at most one body,
+/// which is not allowed in GET requests.
+pub(crate) fn invalid_services(ir: &IntermediateRepresentation) -> Vec<String> {
+    let mut problems = Vec::new();
+    for service in ir.services() {
+        for endpoint in service.endpoints() {
+            let owner = format!(
+                "Endpoint `{}` of service `{}`",
+                endpoint.name().as_snake_case(),
+                service.name().as_pascal_case()
+            );
+            for variable in endpoint.path_variables() {
+                let matched = endpoint
+                    .params()
+                    .iter()
+                    .any(|p| p.kind() == ParamKind::Path && p.name().as_snake_case() == variable);
+                if !matched {
+                    problems.push(format!(
+                        "{owner} has no parameter for path variable `{variable}`"
+                    ));
+                }
+            }
+            let bodies = endpoint
+                .params()
+                .iter()
+                .filter(|p| p.kind() == ParamKind::Body)
+                .count();
+            if bodies > 0 && endpoint.method() == HttpMethod::Get {
+                problems.push(format!(
+                    "{owner} is a GET request, so it can not have a body"
+                ));
+            }
+            if bodies > 1 {
+                problems.push(format!("{owner} has more than one body"));
+            }
+        }
+    }
+    problems
+}
+
+/// This function finds methods of a JSON-RPC service that share a method name,
+/// since a dispatcher could not tell which of them is called.
+pub(crate) fn invalid_rpc_services(ir: &IntermediateRepresentation) -> Vec<String> {
+    let mut problems = Vec::new();
+    for service in ir.rpc_services() {
+        let mut names: Vec<&str> = Vec::new();
+        for method in service.methods() {
+            if names.contains(&method.rpc_name()) {
+                problems.push(format!(
+                    "RPC service `{}` has multiple methods called `{}`",
+                    service.name().as_pascal_case(),
+                    method.rpc_name()
+                ));
+            } else {
+                names.push(method.rpc_name());
+            }
+        }
+    }
+    problems
+}
+
+/// This function finds fields and variants that share a JSON name within a single type.
+/// Serde rejects such types only at runtime, so they are reported as generation errors.
+pub(crate) fn duplicate_json_names(ir: &IntermediateRepresentation) -> Vec<String> {
+    let mut problems = Vec::new();
+    for s in ir.structs() {
+        let fields = s
+            .fields()
+            .iter()
+            .map(|f| (f.name().as_snake_case(), f.json_name().as_str()));
+        problems.extend(find_duplicates(
+            &format!("Struct `{}`", s.self_type().type_name()),
+            "fields",
+            fields,
+        ));
+    }
+    for e in ir.enums() {
+        let variants = e
+            .variants()
+            .iter()
+            .map(|v| (v.name().as_pascal_case(), v.json_name().as_str()));
+        problems.extend(find_duplicates(
+            &format!("Enum `{}`", e.self_type().type_name()),
+            "variants",
+            variants,
+        ));
+    }
+    for de in ir.data_enums() {
+        let owner = format!("Enum `{}`", de.self_type().type_name());
+        let variants = de
+            .variants()
+            .iter()
+            .map(|v| (v.name().as_pascal_case(), v.json_name().as_str()));
+        problems.extend(find_duplicates(&owner, "variants", variants));
+        for variant in de.variants() {
+            if let DataEnumVariant::Object { name, fields, .. } = variant {
+                let fields = fields
+                    .iter()
+                    .map(|f| (f.name().as_snake_case(), f.json_name()));
+                problems.extend(find_duplicates(
+                    &format!(
+                        "Variant `{}::{}`",
+                        de.self_type().type_name(),
+                        name.as_pascal_case()
+                    ),
+                    "fields",
+                    fields,
+                ));
+            }
+        }
+    }
+    problems
+}
+
+fn find_duplicates<'a, I>(owner: &str, members: &str, items: I) -> Vec<String>
+where
+    I: Iterator<Item = (String, &'a str)>,
+{
+    let mut grouped: Vec<(&str, Vec<String>)> = Vec::new();
+    for (name, json_name) in items {
+        match grouped.iter_mut().find(|(j, _)| *j == json_name) {
+            Some((_, names)) => names.push(name),
+            None => grouped.push((json_name, vec![name])),
+        }
+    }
+    grouped
+        .into_iter()
+        .filter(|(_, names)| names.len() > 1)
+        .map(|(json_name, names)| {
+            format!(
+                "{owner} has multiple {members} with JSON name `{json_name}`: {}",
+                names
+                    .iter()
+                    .map(|n| format!("`{n}`"))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+        })
+        .collect()
+}
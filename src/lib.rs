@@ -8,7 +8,12 @@
 //!
 //! It relies on the `serde` crate to serialize and deserialize data.
 //!
+//! It requires Rust 1.89 or newer, which the verification runner needs to lock project directories across processes.
+//!
 //! For testing purposes it uses gradle to compile and run the generated Java code.
+//! The same runner is available as `rdc::targets::java::verify` with the `verify` feature enabled.
+//! Its `JacksonMatrix` builds the generated code with every Jackson version from 2.12 on, or a chosen list,
+//! and reports failures per version.
 //!
 //! ## Java Examples
 //!
@@ -32,6 +37,9 @@
 //!
 //! let classes: Vec<JavaClass> = rdc_java!(MyStruct, MyStruct2).unwrap();
 //! ```
+//! Classes import the JDK and library classes they use, e.g. `java.util.List`, and refer to them by their simple names.
+//! A name stays fully qualified when its simple name is already taken in the class,
+//! e.g. by a nested class, by a generated class or by a class of the same name from another package.
 //!
 //! ### Struct with dependencies
 //! You do not have to specify all the types that are used in your data structures.
@@ -69,6 +77,30 @@
 //! rdc_java!(MyEnum).unwrap();
 //! ```
 //!
+//! ### Display names
+//! Enum variants can be given human-readable labels with `#[rdc(display = "...", description = "...")]`,
+//! or on the IR with `set_variant_display`.
+//! The Java enum then gets a `displayName()` method, with constants without a label using their JSON names,
+//! and descriptions become Javadoc comments of the constants.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::java::generate_java_code;
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! enum PaymentMethod {
+//!     #[rdc(display = "Credit Card", description = "Any major card.")]
+//!     CreditCard,
+//!     Cash,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<PaymentMethod>();
+//! let classes = generate_java_code(&ir).unwrap();
+//! assert!(classes[0].code().contains("CREDIT_CARD(\"Credit Card\")"));
+//! assert!(classes[0].code().contains("public String displayName()"));
+//! ```
+//!
 //! ### Data enum
 //! Data enum is a special type of enum that can contain data.
 //! ```rust
@@ -97,59 +129,1729 @@
 //!   }
 //! ]
 //! ```
+//! Switching over `variant()` with `default: throw value.unhandled();` fails loudly on variants added on the Rust side later,
+//! and `assertExhaustive(...)`, e.g. called at startup with the handled variants, reports the missing ones.
+//! `Variant.KNOWN_VARIANTS` holds all the variants known when the class was generated.
+//! Data enums compare by variant and value, so they can be asserted on in tests and kept in sets,
+//! and print like `MyEnum.Variant3(field1=value)`.
+//! Their deserializers read each value with `DeserializationContext.readTreeAsValue`, which needs Jackson 2.13 or newer.
+//!
+//! ### Unknown variants
+//! With `with_unknown_variants(true)`, a data enum keeps variants it does not know, e.g. added by a newer version of the Rust side,
+//! instead of failing to deserialize. They become the `UNKNOWN` variant holding the raw JSON, which is written back unchanged.
+//! `Variant.KNOWN_VARIANTS` does not include `UNKNOWN`. With sealed records, they become an `Unknown` record holding the raw JSON.
+//! ```rust
+//! use rdc::{rdc_java, RDC};
+//! use rdc::targets::java::JavaConfig;
+//!
+//! #[derive(RDC)]
+//! enum Shape {
+//!     Line(f64, f64),
+//!     Empty,
+//! }
+//!
+//! let config = JavaConfig::new().with_unknown_variants(true);
+//! let classes = rdc_java!(config = config; Shape).unwrap();
+//! assert!(classes[0].code().contains("public static Shape ofUnknown(JsonNode json)"));
+//! ```
+//!
+//! ### Sealed records
+//! With `DataEnumEncoding::SealedRecords`, a data enum becomes a sealed interface with a `record` per variant instead of a single class,
+//! so Java 17 code can use pattern matching. The JSON representation stays the same.
+//! ```rust
+//! use rdc::{rdc_java, RDC};
+//! use rdc::targets::java::{DataEnumEncoding, JavaConfig};
+//!
+//! #[derive(RDC)]
+//! enum Shape {
+//!     Line(f64, f64),
+//!     Polygon { points: Vec<f64> },
+//!     Empty,
+//! }
+//!
+//! let config = JavaConfig::new().with_data_enum_encoding(DataEnumEncoding::SealedRecords);
+//! let classes = rdc_java!(config = config; Shape).unwrap();
+//! assert!(classes[0].code().contains("record Line(Double arg0, Double arg1) implements Shape {}"));
+//! ```
+//!
+//! ### Flags
+//! Sets of flags, e.g. types declared with `bitflags!`, are registered with `rdc_bitflags!`, which lists the flags and their JSON format:
+//! `bits` for an integer or `names` for names separated with `|`, the format of `bitflags` 2 with serde.
+//! Java gets a value class with a nested `Flag` enum, bitwise helpers, `EnumSet` conversions and a matching serializer and deserializer.
+//! ```rust
+//! use rdc::{rdc_bitflags, rdc_java, RDC};
+//!
+//! // bitflags! { pub struct Permissions: u32 { const READ = 1; const WRITE = 2; } }
+//! pub struct Permissions(u32);
+//!
+//! impl Permissions {
+//!     pub const READ: Self = Self(1);
+//!     pub const WRITE: Self = Self(2);
+//!
+//!     pub fn bits(&self) -> u32 {
+//!         self.0
+//!     }
+//! }
+//!
+//! rdc_bitflags!(Permissions, format = bits => READ, WRITE);
+//!
+//! #[derive(RDC)]
+//! struct User {
+//!     permissions: Permissions,
+//! }
+//!
+//! let classes = rdc_java!(User).unwrap();
+//! assert!(classes[1].code().contains("public Permissions with(Flag flag)"));
+//! ```
 //!
 //! ### Generics
 //! There is support for generics in RDC.
 //! It works by generating a Java class for each combination of generic types.
 //! Every used generic type should implement `rdc::RDCType` trait.
 //! Please note that this trait is implemented automatically by `#[derive(RDC)]`.
+//! The derive adds the `RDCType` bound to every type parameter used by the fields, so it does not have to be written.
+//! Other type parameters, e.g. ones only used by `PhantomData` fields, which hold no data and are left out, are not bound
+//! and are not a part of the class name. `PhantomData` fields should be marked with `#[serde(skip)]`, so the JSON matches.
+//! `#[rdc(bound = "...")]` on the type replaces the inferred bounds, and `#[rdc(skip_bound)]` on a field
+//! keeps its type from adding bounds, e.g. when the type implements `RDCType` whatever its parameters are.
 //! ```rust
-//! use rdc::{rdc_java, RDC, RDCType};
+//! use rdc::{rdc_java, RDC};
+//! use std::marker::PhantomData;
 //!
 //! #[derive(RDC)]
-//! struct MyStruct<T> where T: RDCType {
+//! struct MyStruct<T> {
 //!     value: T,
 //! }
 //!
-//! let classes = rdc_java!(MyStruct<i32>, MyStruct<String>).unwrap();
-//! assert_eq!(classes.len(), 2);
+//! struct Internal;
+//!
+//! #[derive(RDC)]
+//! struct Wrapper<T> {
+//!     id: String,
+//!     marker: PhantomData<T>,
+//! }
+//!
+//! let classes = rdc_java!(MyStruct<i32>, MyStruct<String>, Wrapper<Internal>).unwrap();
+//! assert_eq!(classes.len(), 3);
+//! assert!(classes.iter().any(|c| c.name() == "Wrapper"));
 //! ```
 //!
-//! ### Writing
-//! RDC can write the generated code to files.
+//! ### Trait objects
+//! Fields holding trait objects, like `Box<dyn Shape>`, are supported once all the implementations are listed with `rdc_polymorphic!`.
+//! It generates a Java interface with the implementations registered as Jackson subtypes.
+//! Values are wrapped in an object with the implementation name as its key, like `{"Circle": {"radius": 1.0}}`,
+//! or tagged with a field when `tag = "..."` is provided, like `{"type": "Circle", "radius": 1.0}`.
+//! ```rust
+//! use rdc::{rdc_java, rdc_polymorphic, RDC};
+//!
+//! trait Shape {}
+//!
+//! #[derive(RDC)]
+//! struct Circle {
+//!     radius: f64,
+//! }
+//! impl Shape for Circle {}
+//!
+//! rdc_polymorphic!(dyn Shape => Circle);
+//!
+//! #[derive(RDC)]
+//! struct Drawing {
+//!     shapes: Vec<Box<dyn Shape>>,
+//! }
+//!
+//! rdc_java!(Drawing).unwrap();
+//! ```
+//!
+//! ### HTTP clients
+//! Service traits declared with `rdc_service!` generate HTTP client interfaces for Retrofit (default) or OpenFeign.
+//! Arguments matching `{name}` placeholders are path parameters, an argument called `body` is the JSON request body
+//! and all the other arguments are sent in the query string.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::java::{generate_java_code_with_config, HttpClientStyle, JavaConfig};
+//! use rdc::{rdc_service, RDC};
+//!
+//! #[derive(RDC)]
+//! struct User {
+//!     name: String,
+//! }
+//!
+//! rdc_service! {
+//!     pub trait UserApi {
+//!         #[rdc(get, "/users/{id}")]
+//!         fn get_user(&self, id: String) -> User;
+//!         #[rdc(post, "/users")]
+//!         fn create_user(&self, body: User) -> User;
+//!     }
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add_service_trait::<dyn UserApi>();
+//! let config = JavaConfig::new().with_http_client(HttpClientStyle::Feign);
+//! generate_java_code_with_config(&ir, &config).unwrap();
+//! ```
+//!
+//! ### XML
+//! With `JavaConfig::with_xml`, classes also get jackson-dataformat-xml annotations, so they can be read from and written to XML.
+//! Fields are elements by default and `with_xml_attribute` turns scalar fields into attributes.
+//! Like the other options keyed by type or field names, it fails the generation with an error when the names match nothing in the IR.
 //! ```rust
+//! use rdc::targets::java::JavaConfig;
 //! use rdc::{rdc_java, RDC};
-//! use rdc::targets::java::{JavaClass, write_java};
 //!
 //! #[derive(RDC)]
-//! enum MyEnum {
-//!     Variant1,
-//!     Variant2
+//! struct Order {
+//!     order_id: String,
+//!     lines: Vec<String>,
 //! }
-//! let classes: Vec<JavaClass> = rdc_java!(MyEnum).unwrap();
 //!
-//! write_java(&classes, "com.example", "target/test-tmp/src/main/java").unwrap();
+//! let config = JavaConfig::new()
+//!     .with_xml(true)
+//!     .with_xml_attribute("Order", "order_id");
+//! rdc_java!(config = config; Order).unwrap();
 //! ```
 //!
-//! ### Serde compatibility
-//! You can use `#[serde(rename = "new_name")]` to rename fields and it will be reflected in the generated code.
-
-use crate::codegen::GenerateIR;
-use crate::targets::java::type_resolver::JavaType;
-pub use rdc_macros::RDC;
-
-/// This module defines GenerateIR trait and implementations for all primitive types used in IR.
-pub mod codegen;
-
-/// This module contains error types.
-pub mod errors;
-
-/// This module contains intermediate representation of the data.
-pub mod ir;
+//! ### CSV
+//! With `JavaConfig::with_csv`, structs with only scalar fields are mapped to CSV rows with OpenCSV or jackson-dataformat-csv.
+//! Columns share names and order with the Rust fields. Structs with nested types are skipped with a warning.
+//! ```rust
+//! use rdc::targets::java::{CsvMapping, JavaConfig};
+//! use rdc::{rdc_java, RDC};
+//!
+//! #[derive(RDC)]
+//! struct Payment {
+//!     payment_id: String,
+//!     amount: f64,
+//! }
+//!
+//! let config = JavaConfig::new().with_csv(CsvMapping::OpenCsv);
+//! let classes = rdc_java!(config = config; Payment).unwrap();
+//! assert!(classes[0].code().contains("CSV_COLUMNS"));
+//! ```
+//!
+//! ### Test fixtures
+//! With `JavaConfig::with_fixtures`, every class gets a `<Class>Fixtures` class for tests,
+//! whose `create()` method returns an instance with all the fields populated with deterministic placeholder data.
+//! ```rust
+//! use rdc::targets::java::JavaConfig;
+//! use rdc::{rdc_java, RDC};
+//!
+//! #[derive(RDC)]
+//! struct Invoice {
+//!     number: String,
+//!     total: f64,
+//! }
+//!
+//! let config = JavaConfig::new().with_fixtures(true);
+//! let classes = rdc_java!(config = config; Invoice).unwrap();
+//! assert!(classes.iter().any(|c| c.name() == "InvoiceFixtures"));
+//! ```
+//!
+//! ### AssertJ assertions
+//! With `JavaConfig::with_assertions`, every class gets an AssertJ `<Class>Assert` class
+//! with a `has<Field>` method for every field, e.g. `assertThat(invoice).hasNumber("1")`.
+//! ```rust
+//! use rdc::targets::java::JavaConfig;
+//! use rdc::{rdc_java, RDC};
+//!
+//! #[derive(RDC)]
+//! struct Invoice {
+//!     number: String,
+//! }
+//!
+//! let config = JavaConfig::new().with_assertions(true);
+//! let classes = rdc_java!(config = config; Invoice).unwrap();
+//! assert!(classes.iter().any(|c| c.name() == "InvoiceAssert"));
+//! ```
+//!
+//! ### Benchmarks
+//! `generate_jmh_benchmark` generates a JMH benchmark class serializing and deserializing a fixture instance
+//! of every struct and data enum, so that the cost of the wire format can be tracked. It requires fixtures.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::java::{generate_jmh_benchmark, JavaConfig};
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! struct Invoice {
+//!     number: String,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<Invoice>();
+//! let config = JavaConfig::new().with_fixtures(true);
+//! let benchmark = generate_jmh_benchmark(&ir, &config, "SerializationBenchmark").unwrap();
+//! ```
+//!
+//! ### ObjectMapper factory
+//! With `JavaConfig::with_rdc_json`, an `RdcJson` class builds an `ObjectMapper` configured the way the generated classes expect.
+//! It registers the `Jdk8Module` and `JavaTimeModule`, so `jackson-datatype-jdk8` and `jackson-datatype-jsr310` are required.
+//! Types using `#[serde(deny_unknown_fields)]` are marked with `with_deny_unknown_fields`, since the derive macro does not see the attribute.
+//! ```rust
+//! use rdc::targets::java::JavaConfig;
+//! use rdc::{rdc_java, RDC};
+//!
+//! #[derive(RDC)]
+//! struct Request {
+//!     id: String,
+//! }
+//!
+//! let config = JavaConfig::new()
+//!     .with_rdc_json(true)
+//!     .with_deny_unknown_fields("Request");
+//! let classes = rdc_java!(config = config; Request).unwrap();
+//! assert!(classes.iter().any(|c| c.name() == "RdcJson"));
+//! ```
+//!
+//! ### Case-insensitive properties
+//! Legacy producers sending keys in another case, e.g. `{"Id": "1"}`, can be accepted with `with_case_insensitive_properties`.
+//! Data classes and classes of data enum variants are annotated with `@JsonFormat(with = JsonFormat.Feature.ACCEPT_CASE_INSENSITIVE_PROPERTIES)`,
+//! which works with any `ObjectMapper`, while properties are still written with their serde names.
+//! Data enums match variant keys regardless of case, and the `RdcJson` mapper enables `MapperFeature.ACCEPT_CASE_INSENSITIVE_PROPERTIES`.
+//! ```rust
+//! use rdc::targets::java::JavaConfig;
+//! use rdc::{rdc_java, RDC};
+//!
+//! #[derive(RDC)]
+//! struct Request {
+//!     id: String,
+//! }
+//!
+//! let config = JavaConfig::new().with_case_insensitive_properties(true);
+//! let classes = rdc_java!(config = config; Request).unwrap();
+//! assert!(classes[0].code().contains("ACCEPT_CASE_INSENSITIVE_PROPERTIES"));
+//! ```
+//!
+//! ### Field name constants
+//! Data classes can hold a nested `Fields` class with the JSON names of their fields,
+//! which is handy for queries, JSON pointers or partial updates.
+//! ```rust
+//! use rdc::targets::java::JavaConfig;
+//! use rdc::{rdc_java, RDC};
+//!
+//! #[derive(RDC)]
+//! struct Invoice {
+//!     created_at: String,
+//! }
+//!
+//! let config = JavaConfig::new().with_field_constants(true);
+//! let classes = rdc_java!(config = config; Invoice).unwrap();
+//! assert!(classes[0].code().contains("public static final String CREATED_AT = \"created_at\";"));
+//! ```
+//!
+//! ### JSON views
+//! Fields can be assigned to Jackson views, so that the same class can be serialized with different exposure levels.
+//! The derive macro only reads serde attributes, so views are assigned in the configuration, using Rust names.
+//! A `Views` class with a marker class for every view is generated along with the data classes.
+//! ```rust
+//! use rdc::targets::java::JavaConfig;
+//! use rdc::{rdc_java, RDC};
+//!
+//! #[derive(RDC)]
+//! struct Account {
+//!     name: String,
+//!     email: String,
+//! }
+//!
+//! let config = JavaConfig::new()
+//!     .with_json_view("Account", "name", "Public")
+//!     .with_json_view("Account", "email", "Internal");
+//! let classes = rdc_java!(config = config; Account).unwrap();
+//! assert!(classes[0].code().contains("@JsonView(Views.Internal.class) private String email;"));
+//! ```
+//!
+//! ### JSON filters
+//! Data classes can be annotated with `@JsonFilter`, so that responses can be limited to some fields, e.g. for sparse fieldsets.
+//! The `JsonFilters` class holds the filter ids and builds `FilterProvider`s from field names, like the `Fields` constants.
+//! Jackson needs a filter provider to serialize filtered classes, so the `RdcJson` mapper sets one serializing all the properties.
+//! ```rust
+//! use rdc::targets::java::JavaConfig;
+//! use rdc::{rdc_java, RDC};
+//!
+//! #[derive(RDC)]
+//! struct Invoice {
+//!     number: String,
+//!     total: f64,
+//! }
+//!
+//! let config = JavaConfig::new().with_json_filters(true).with_field_constants(true);
+//! let classes = rdc_java!(config = config; Invoice).unwrap();
+//! assert!(classes[0].code().contains("@JsonFilter(\"Invoice\") public class Invoice"));
+//! // mapper.writer(JsonFilters.only(JsonFilters.INVOICE, Invoice.Fields.NUMBER)).writeValueAsString(invoice)
+//! assert!(classes[1].code().contains("public static SimpleFilterProvider only(String filterId, String... fields)"));
+//! ```
+//!
+//! ### Builders
+//! Data classes can have a nested `Builder`. Fields that are not an `Option` are required,
+//! so `build()` throws an `IllegalStateException` when one of them was not set.
+//! ```rust
+//! use rdc::targets::java::JavaConfig;
+//! use rdc::{rdc_java, RDC};
+//!
+//! #[derive(RDC)]
+//! struct Invoice {
+//!     number: String,
+//!     note: Option<String>,
+//! }
+//!
+//! let config = JavaConfig::new().with_builders(true);
+//! let classes = rdc_java!(config = config; Invoice).unwrap();
+//! assert!(classes[0].code().contains("throw new IllegalStateException(\"number is required\");"));
+//! ```
+//!
+//! ### All-args constructors
+//! `JavaConfig::with_all_args_constructors` adds a `@JsonCreator` constructor taking every field in declaration order,
+//! so Java code can create data classes in one expression and Jackson binds them through the constructor.
+//! ```rust
+//! use rdc::targets::java::JavaConfig;
+//! use rdc::{rdc_java, RDC};
+//!
+//! #[derive(RDC)]
+//! struct Invoice {
+//!     number: String,
+//!     note: Option<String>,
+//! }
+//!
+//! let config = JavaConfig::new().with_all_args_constructors(true);
+//! let classes = rdc_java!(config = config; Invoice).unwrap();
+//! assert!(classes[0].code().contains("public Invoice(@JsonProperty(\"number\") String number, @JsonProperty(\"note\") String note)"));
+//! ```
+//!
+//! ### Accessor styles
+//! `JavaConfig::with_accessor_style` names getters and setters of data classes and data enums in one of the styles of `AccessorStyle`:
+//! JavaBeans `getX()` and `setX(x)`, `isX()` getters of `Boolean` properties, record-style `x()` and `x(x)`,
+//! or accessors that Kotlin sees as properties named like the fields, e.g. `isActive()` and `setActive(isActive)` for `is_active`.
+//! ```rust
+//! use rdc::targets::java::{AccessorStyle, JavaConfig};
+//! use rdc::{rdc_java, RDC};
+//!
+//! #[derive(RDC)]
+//! struct Account {
+//!     total: i64,
+//!     is_active: bool,
+//! }
+//!
+//! let config = JavaConfig::new().with_accessor_style(AccessorStyle::Record);
+//! let classes = rdc_java!(config = config; Account).unwrap();
+//! assert!(classes[0].code().contains("public Long total() {"));
+//! ```
+//!
+//! ### Null objects
+//! `JavaConfig::with_empty_factories` adds a static `empty()` factory to every data class, returning a null object.
+//! Collections are empty, numbers are `0`, booleans are `false` and strings are empty,
+//! nested data classes are created with their own `empty()` factories and optional fields stay `null`.
+//! ```rust
+//! use rdc::{rdc_java, RDC};
+//! use rdc::targets::java::JavaConfig;
+//!
+//! #[derive(RDC)]
+//! struct Cart {
+//!     items: Vec<String>,
+//!     owner: Owner,
+//! }
+//!
+//! #[derive(RDC)]
+//! struct Owner {
+//!     name: String,
+//!     email: Option<String>,
+//! }
+//!
+//! let classes = rdc_java!(config = JavaConfig::new().with_empty_factories(true); Cart).unwrap();
+//! let cart = classes.iter().find(|c| c.name() == "Cart").unwrap();
+//! assert!(cart.code().contains("value.owner = Owner.empty();"));
+//! ```
+//!
+//! ### Read-only interfaces
+//! `JavaConfig::with_view_interfaces` generates a read-only interface for every data class, e.g. `InvoiceView` for `Invoice`,
+//! which the class implements. Getters of nested data classes return their interfaces,
+//! so Java APIs can expose the interfaces and keep the mutable Jackson-bound classes internal.
+//! Data enum classes get one too, e.g. `ShapeView` with `variant()`, the `isCircle()` checks and the variant getters,
+//! where struct variants are returned as their nested classes, which have no setters.
+//! Sealed records have no interfaces, because they are immutable already.
+//! ```rust
+//! use rdc::{rdc_java, RDC};
+//! use rdc::targets::java::JavaConfig;
+//!
+//! #[derive(RDC)]
+//! struct Invoice {
+//!     number: String,
+//!     lines: Vec<Line>,
+//! }
+//!
+//! #[derive(RDC)]
+//! struct Line {
+//!     amount: f64,
+//! }
+//!
+//! let classes = rdc_java!(config = JavaConfig::new().with_view_interfaces(true); Invoice).unwrap();
+//! let view = classes.iter().find(|c| c.name() == "InvoiceView").unwrap();
+//! assert!(view.code().contains("List<? extends LineView> getLines();"));
+//! ```
+//!
+//! ### Shared interfaces
+//! `JavaConfig::with_shared_interface` generates an interface with getters of the given fields,
+//! which every data class having them implements, so that generic Java utilities can handle many DTOs.
+//! `IntermediateRepresentation::shared_fields` finds sets of fields that structs share, as candidates for such interfaces.
+//! ```rust
+//! use rdc::{rdc_java, RDC};
+//! use rdc::targets::java::JavaConfig;
+//!
+//! #[derive(RDC)]
+//! struct Invoice {
+//!     id: String,
+//!     created_at: i64,
+//!     customer: Customer,
+//! }
+//!
+//! #[derive(RDC)]
+//! struct Customer {
+//!     id: String,
+//!     created_at: i64,
+//! }
+//!
+//! let config = JavaConfig::new().with_shared_interface("Auditable", ["id", "created_at"]);
+//! let classes = rdc_java!(config = config; Invoice).unwrap();
+//! let customer = classes.iter().find(|c| c.name() == "Customer").unwrap();
+//! assert!(customer.code().contains("public class Customer implements Auditable {"));
+//! ```
+//!
+//! Structs can also name the interfaces they implement with `#[rdc(implements_shared = "...")]`.
+//! An interface which is not in the config gets the fields all the structs naming it share, with the same JSON names and types,
+//! and generation fails if a struct does not have the fields of an interface it names.
+//! ```rust
+//! use rdc::{rdc_java, RDC};
+//!
+//! #[derive(RDC)]
+//! #[rdc(implements_shared = "Tracked")]
+//! struct Parcel {
+//!     tracking_code: String,
+//!     weight: f64,
+//! }
+//!
+//! #[derive(RDC)]
+//! #[rdc(implements_shared = "Tracked")]
+//! struct Letter {
+//!     tracking_code: String,
+//! }
+//!
+//! #[derive(RDC)]
+//! struct Mailbag {
+//!     parcels: Vec<Parcel>,
+//!     letters: Vec<Letter>,
+//! }
+//!
+//! let classes = rdc_java!(Mailbag).unwrap();
+//! let tracked = classes.iter().find(|c| c.name() == "Tracked").unwrap();
+//! assert!(tracked.code().contains("String getTrackingCode();"));
+//! ```
+//!
+//! ### JSON Schema constants
+//! Every generated class can carry the JSON Schema of its type in a `JSON_SCHEMA` text block, e.g. for runtime validation.
+//! The schemas come from `rdc::targets::openapi::generate_json_schema`, and text blocks require Java 15 or newer.
+//! ```rust
+//! use rdc::targets::java::JavaConfig;
+//! use rdc::{rdc_java, RDC};
+//!
+//! #[derive(RDC)]
+//! struct Invoice {
+//!     number: String,
+//! }
+//!
+//! let config = JavaConfig::new().with_json_schema_constants(true);
+//! let classes = rdc_java!(config = config; Invoice).unwrap();
+//! assert!(classes[0].code().contains("public static final String JSON_SCHEMA = \"\"\""));
+//! ```
+//!
+//! ### Packages
+//! Classes can be spread over multiple packages by mapping Rust modules to Java packages.
+//! Every class carries its package, and `write_java` lays them out in a tree of packages with imports between them.
+//! Classes of types from unmapped modules are written to the package passed to `write_java`.
+//! ```rust,no_run
+//! use rdc::targets::java::{write_java, JavaConfig};
+//! use rdc::{rdc_java, RDC};
+//!
+//! #[derive(RDC)]
+//! struct Customer {
+//!     name: String,
+//! }
+//!
+//! mod billing {
+//!     #[derive(rdc::RDC)]
+//!     pub struct Invoice {
+//!         pub number: String,
+//!     }
+//! }
+//!
+//! let config = JavaConfig::new().with_module_package("my_crate::billing", "com.example.billing");
+//! let classes = rdc_java!(config = config; billing::Invoice, Customer).unwrap();
+//! write_java(&classes, "com.example", "src/main/java").unwrap();
+//! ```
+//!
+//! ### Common fields
+//! Fields shared by all struct variants of a data enum, with the same JSON names and types, can be lifted into a nested abstract `Base` class.
+//! The variant classes extend it, and the data enum class gets getters of the shared fields, so they can be read without checking the variant.
+//! With sealed records, the interface declares accessors of the shared fields instead, which the records of struct variants implement
+//! and which fail for other variants.
+//! ```rust
+//! use rdc::targets::java::JavaConfig;
+//! use rdc::{rdc_java, RDC};
+//!
+//! #[derive(RDC)]
+//! enum Event {
+//!     Created { id: String, owner: String },
+//!     Deleted { id: String },
+//! }
+//!
+//! let config = JavaConfig::new().with_common_fields(true);
+//! let classes = rdc_java!(config = config; Event).unwrap();
+//! assert!(classes[0].code().contains("public static class Created extends Base"));
+//! assert!(classes[0].code().contains("return ((Base) value).getId();"));
+//! ```
+//!
+//! ### Schema annotations
+//! Data classes and their fields can carry swagger `@Schema` annotations, so that OpenAPI documents generated by springdoc match the Rust model.
+//! Fields that are not an `Option` are marked as required. Descriptions are configured by Rust names, and examples are taken from the IR.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::java::{generate_java_code_with_config, JavaConfig};
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! struct Invoice {
+//!     number: String,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<Invoice>();
+//! ir.set_field_example("Invoice", "number", "INV-001").unwrap();
+//! let config = JavaConfig::new()
+//!     .with_schema_annotations(true)
+//!     .with_type_description("Invoice", "An invoice sent to a customer.");
+//! let classes = generate_java_code_with_config(&ir, &config).unwrap();
+//! assert!(classes[0].code().contains("@Schema(example = \"INV-001\", requiredMode = Schema.RequiredMode.REQUIRED)"));
+//! ```
+//!
+//! ### Nullability
+//! Optional fields can be marked with a `@Nullable` annotation, and getters can get Javadoc telling whether their property is required.
+//! Both follow `Field::is_required`, like `required` in OpenAPI documents and JSON Schemas and `requiredMode` in `@Schema`,
+//! so all the outputs agree on which fields may be missing.
+//! ```rust
+//! use rdc::targets::java::{JavaConfig, NullableAnnotation};
+//! use rdc::{rdc_java, RDC};
+//!
+//! #[derive(RDC)]
+//! struct Invoice {
+//!     number: String,
+//!     note: Option<String>,
+//! }
+//!
+//! let config = JavaConfig::new()
+//!     .with_nullable_annotation(NullableAnnotation::Jakarta)
+//!     .with_getter_javadoc(true);
+//! let classes = rdc_java!(config = config; Invoice).unwrap();
+//! assert!(classes[0].code().contains("@Nullable public String getNote()"));
+//! ```
+//!
+//! ### Error types
+//! Error enums, e.g. `thiserror` enums returned by an API, can be marked with `with_error_type`, so that no mirror structs are needed.
+//! Their classes get `getCode()`, `getDetail()` and `getMessage()` methods, with messages configured in the format of `#[error(...)]`,
+//! and `with_error_exceptions` adds an exception class carrying the error.
+//! ```rust
+//! use rdc::targets::java::JavaConfig;
+//! use rdc::{rdc_java, RDC};
+//!
+//! #[derive(RDC)]
+//! enum ApiError {
+//!     NotFound(String),
+//!     Forbidden { reason: String },
+//! }
+//!
+//! let config = JavaConfig::new()
+//!     .with_error_type("ApiError")
+//!     .with_error_message("ApiError", "NotFound", "{0} was not found")
+//!     .with_error_exceptions(true);
+//! let classes = rdc_java!(config = config; ApiError).unwrap();
+//! assert!(classes[0].code().contains("case NOT_FOUND: return String.valueOf(getNotFound()) + \" was not found\";"));
+//! assert_eq!(classes[1].name(), "ApiErrorException");
+//! ```
+//!
+//! ### JSON-RPC
+//! JSON-RPC services are declared with `rdc_rpc_service!`, where every method is annotated with its JSON-RPC name.
+//! The arguments of a method form a request DTO, e.g. `GetUserParams`, which is generated like any other struct.
+//! For every service, the generator creates an interface with a default `dispatch` method for servers
+//! and a client, e.g. `UserRpcClient`, which sends calls through a `Transport`.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::java::generate_java_code;
+//! use rdc::{rdc_rpc_service, RDC};
+//!
+//! #[derive(RDC)]
+//! struct User {
+//!     name: String,
+//! }
+//!
+//! rdc_rpc_service! {
+//!     pub trait UserRpc {
+//!         #[rdc("user.get")]
+//!         fn get_user(&self, user_id: String) -> User;
+//!     }
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add_service_trait::<dyn UserRpc>();
+//! let classes = generate_java_code(&ir).unwrap();
+//! let client = classes.iter().find(|c| c.name() == "UserRpcClient").unwrap();
+//! assert!(client.code().contains("transport.call(\"user.get\", params, new TypeReference<User>() {})"));
+//! ```
+//!
+//! ### Message envelopes
+//! `rdc_envelope!` declares a Rust enum wrapping a set of message types, e.g. the messages of a WebSocket protocol.
+//! It is generated as an interface with `@JsonTypeInfo`, like a trait object, together with a handler
+//! that has an `on<Message>` method for every message and dispatches to them in `handle`.
+//! ```rust
+//! use rdc::{rdc_envelope, rdc_java, RDC};
+//!
+//! #[derive(RDC)]
+//! struct Join {
+//!     user: String,
+//! }
+//!
+//! #[derive(RDC)]
+//! struct Leave {
+//!     user: String,
+//! }
+//!
+//! rdc_envelope! {
+//!     pub enum ChatMessage { Join, Leave }
+//! }
+//!
+//! let classes = rdc_java!(ChatMessage).unwrap();
+//! let handler = classes.iter().find(|c| c.name() == "ChatMessageHandler").unwrap();
+//! assert!(handler.code().contains("void onJoin(Join message);"));
+//! ```
+//!
+//! ### Map types
+//! By default every map is a `java.util.Map`. With `with_concrete_maps`, maps with generated enum keys are `EnumMap`s,
+//! `BTreeMap`s are `TreeMap`s and other maps are `LinkedHashMap`s. Enums used as map keys then get key serializers,
+//! so that keys are written with their JSON names.
+//! ```rust
+//! use rdc::targets::java::JavaConfig;
+//! use rdc::{rdc_java, RDC};
+//! use std::collections::{BTreeMap, HashMap};
+//!
+//! #[derive(RDC, Hash, PartialEq, Eq)]
+//! enum Currency {
+//!     Eur,
+//!     Usd,
+//! }
+//!
+//! #[derive(RDC)]
+//! struct Balance {
+//!     totals: HashMap<Currency, f64>,
+//!     history: BTreeMap<String, f64>,
+//! }
+//!
+//! let classes = rdc_java!(config = JavaConfig::new().with_concrete_maps(true); Balance).unwrap();
+//! let balance = classes.iter().find(|c| c.name() == "Balance").unwrap();
+//! assert!(balance.code().contains("private EnumMap<Currency, Double> totals;"));
+//! assert!(balance.code().contains("private TreeMap<String, Double> history;"));
+//! ```
+//!
+//! ### Extra properties
+//! A `#[serde(flatten)]` map collecting unknown properties is declared with `with_extra_properties`.
+//! The field is annotated with `@JsonAnyGetter` and `@JsonAnySetter`, so unknown properties round-trip through Java unchanged.
+//! With the `json` feature, `serde_json::Value` values are generated as `JsonNode`.
+//! ```rust
+//! use rdc::targets::java::JavaConfig;
+//! use rdc::{rdc_java, RDC};
+//! use std::collections::HashMap;
+//!
+//! #[derive(RDC)]
+//! struct Profile {
+//!     name: String,
+//!     extra: HashMap<String, String>,
+//! }
+//!
+//! let config = JavaConfig::new().with_extra_properties("Profile", "extra");
+//! let classes = rdc_java!(config = config; Profile).unwrap();
+//! assert!(classes[0].code().contains("@JsonAnySetter"));
+//! ```
+//!
+//! ### Read-only fields
+//! Fields that clients must not change, e.g. identifiers assigned by the server, are marked with `set_field_readonly`.
+//! They get a getter but no setter, and Jackson still populates them when deserializing.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::java::generate_java_code;
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! struct Account {
+//!     id: String,
+//!     owner: String,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<Account>();
+//! ir.set_field_readonly("Account", "id", true).unwrap();
+//! let classes = generate_java_code(&ir).unwrap();
+//! assert!(!classes[0].code().contains("setId"));
+//! ```
+//!
+//! ### Date formats
+//! Fields written with a custom serde date format get the same format with `#[rdc(format = "...", timezone = "...")]`,
+//! in Java as `@JsonFormat`. The pattern uses the syntax of `DateTimeFormatter` and the time zone is optional.
+//! Types which can not be annotated get it with `set_field_format` on the IR, or with `format` on an `IrBuilder` field.
+//! Kotlin supports it with the Jackson flavor.
+//! With the Spring flavor, fields also get `@DateTimeFormat` with the pattern, for binding request parameters.
+//! OpenAPI documents and JSON Schemas keep the pattern as `x-date-format`, with the standard `format`, e.g. `date-time`,
+//! when the pattern produces RFC 3339 values, and generated docs show it next to the field description.
+//! Other targets generate the field with its plain type. Formats can only be set on struct fields, not on fields of data enum variants.
+//! ```rust
+//! use rdc::ir::{DateTimeFormat, IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::java::generate_java_code;
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! struct Event {
+//!     #[rdc(format = "yyyy-MM-dd'T'HH:mm:ssX", timezone = "UTC")]
+//!     created_at: String,
+//!     updated_at: String,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<Event>();
+//! ir.set_field_format("Event", "updated_at", DateTimeFormat::new("yyyy-MM-dd")).unwrap();
+//! let classes = generate_java_code(&ir).unwrap();
+//! assert!(classes[0].code().contains("timezone = \"UTC\")"));
+//! assert!(classes[0].code().contains("pattern = \"yyyy-MM-dd\")"));
+//! ```
+//!
+//! ### Versions
+//! Old and new generations of an API can be generated side by side.
+//! Classes generated with `with_version` are written to a subpackage named after the version, e.g. `com.example.v2`.
+//! ```rust
+//! use rdc::targets::java::{write_java, JavaConfig};
+//! use rdc::{rdc_java, RDC};
+//!
+//! mod v1 {
+//!     use rdc::RDC;
+//!
+//!     #[derive(RDC)]
+//!     pub struct Invoice {
+//!         pub number: String,
+//!     }
+//! }
+//!
+//! mod v2 {
+//!     use rdc::RDC;
+//!
+//!     #[derive(RDC)]
+//!     pub struct Invoice {
+//!         pub number: String,
+//!         pub total: f64,
+//!     }
+//! }
+//!
+//! let mut classes = rdc_java!(config = JavaConfig::new().with_version("v1"); v1::Invoice).unwrap();
+//! classes.extend(rdc_java!(config = JavaConfig::new().with_version("v2"); v2::Invoice).unwrap());
+//! assert_eq!(classes[1].version(), Some("v2"));
+//! ```
+//!
+//! ### Writing
+//! RDC can write the generated code to files.
+//! ```rust
+//! use rdc::{rdc_java, RDC};
+//! use rdc::targets::java::{JavaClass, write_java};
+//!
+//! #[derive(RDC)]
+//! enum MyEnum {
+//!     Variant1,
+//!     Variant2
+//! }
+//! let classes: Vec<JavaClass> = rdc_java!(MyEnum).unwrap();
+//!
+//! write_java(&classes, "com.example", "target/test-tmp/src/main/java").unwrap();
+//! ```
+//! `generate_and_write_java` writes each class as soon as it is generated instead, so large IRs are not held in memory.
+//! A class that fails to be generated or written does not stop the others, the error at the end lists all the failures.
+//! Files go to a `JavaSink`, e.g. `JavaDirectory`, which can be implemented to write them elsewhere.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::java::{generate_and_write_java, JavaConfig, JavaDirectory};
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! enum MyEnum {
+//!     Variant1,
+//!     Variant2
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<MyEnum>();
+//! let mut sink = JavaDirectory::new("com.example", "target/test-tmp/src/main/java");
+//! generate_and_write_java(&ir, &JavaConfig::new(), &mut sink).unwrap();
+//! ```
+//! `generate_java_code_for` and `generate_and_write_java_for` generate only the given types and services
+//! and the types they use, e.g. to regenerate the part of a large IR that is being worked on.
+//! The classes are the same as in a full run, since they are still generated in the context of the whole IR.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::java::{generate_and_write_java_for, JavaConfig, JavaDirectory};
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! struct Invoice {
+//!     number: String,
+//!     lines: Vec<LineItem>,
+//! }
+//!
+//! #[derive(RDC)]
+//! struct LineItem {
+//!     sku: String,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<Invoice>();
+//! let mut sink = JavaDirectory::new("com.example", "target/test-tmp/src/main/java");
+//! generate_and_write_java_for(&ir, &JavaConfig::new(), &["Invoice"], &mut sink).unwrap();
+//! ```
+//!
+//! ### Serde compatibility
+//! You can use `#[serde(rename = "new_name")]` to rename fields and it will be reflected in the generated code.
+//! `rename_all` is not read by the derive, but its conventions are available as `rdc::ir::RenameRule`,
+//! e.g. for types declared with `IrBuilder::rename_all`.
+//!
+//! ### serde_with adapters
+//! Fields written with a `serde_with` adapter, e.g. `#[serde_as(as = "DisplayFromStr")]`, have a different JSON layout
+//! than their Rust type. The derive reads the adapters of struct fields when `#[serde_as]` is placed above it,
+//! and `set_field_adapter` sets them for types which can not be annotated. The field type is replaced with the written one in all targets:
+//! `DisplayFromStr` writes strings, `DurationSeconds` writes a `Duration` as a number of seconds,
+//! and `MapAsPairs`, for `Vec<(_, _)>`, writes a map as a list of key-value pairs. Jackson reads all of them without extra annotations.
+//! Other adapters are not read, so such fields keep their Rust types.
+//! `Duration` fields without an adapter are rejected, because serde writes them as objects of `secs` and `nanos`.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, SerdeAdapter, TypeTarget};
+//! use rdc::targets::java::generate_java_code;
+//! use rdc::RDC;
+//! use std::time::Duration;
+//!
+//! #[derive(RDC)]
+//! struct Job {
+//!     id: u64,
+//!     timeout: Duration,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<Job>();
+//! ir.set_field_adapter("Job", "id", SerdeAdapter::DisplayFromStr).unwrap();
+//! ir.set_field_adapter("Job", "timeout", SerdeAdapter::DurationSeconds).unwrap();
+//! let classes = generate_java_code(&ir).unwrap();
+//! assert!(classes[0].code().contains("private String id;"));
+//! assert!(classes[0].code().contains("private Long timeout;"));
+//! ```
+//!
+//! ### Newtypes
+//! Newtypes, e.g. `struct Tags(HashMap<String, String>)`, are derived like structs, or declared with `rdc_newtype!`
+//! together with the type they wrap. serde writes a newtype as the value it wraps, and so does the generated class:
+//! it holds the value, is created from it with a delegating `@JsonCreator` and writes it with `@JsonAnyGetter` for maps
+//! or `@JsonValue` for other types. Maps and lists get delegating `get`, `size` and `isEmpty` methods.
+//! ```rust
+//! use rdc::{rdc_java, rdc_newtype, RDC};
+//! use std::collections::HashMap;
+//!
+//! #[derive(RDC)]
+//! pub struct Tags(HashMap<String, String>);
+//!
+//! pub struct Slug(String);
+//!
+//! rdc_newtype!(Slug => String);
+//!
+//! #[derive(RDC)]
+//! struct Article {
+//!     slug: Slug,
+//!     tags: Tags,
+//! }
+//!
+//! let classes = rdc_java!(Article).unwrap();
+//! let tags = classes.iter().find(|c| c.name() == "Tags").unwrap();
+//! assert!(tags.code().contains("@JsonCreator(mode = JsonCreator.Mode.DELEGATING) public Tags(Map<String, String> value)"));
+//! assert!(tags.code().contains("public String get(String key)"));
+//! ```
+//!
+//! ### Field examples
+//! Example values of fields are set with `#[rdc(example = "...")]`, or on the IR with `set_field_example`
+//! or `IrBuilder::example` for types which can not be annotated.
+//! They end up in getter Javadoc and `@Schema` annotations, in OpenAPI documents and JSON Schemas, and in fixtures classes.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::java::{generate_java_code_with_config, JavaConfig};
+//! use rdc::targets::openapi::generate_json_schema;
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! struct Invoice {
+//!     #[rdc(example = "INV-001")]
+//!     number: String,
+//!     total: f64,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<Invoice>();
+//! ir.set_field_example("Invoice", "total", "12.5").unwrap();
+//! let config = JavaConfig::new().with_fixtures(true);
+//! let classes = generate_java_code_with_config(&ir, &config).unwrap();
+//! let fixtures = classes.iter().find(|c| c.name() == "InvoiceFixtures").unwrap();
+//! assert!(fixtures.code().contains("value.setNumber(\"INV-001\");"));
+//! let schema = generate_json_schema(&ir, "Invoice").unwrap();
+//! assert!(schema.contains("\"examples\": [\n"));
+//! ```
+//!
+//! ### Kotlin extensions
+//! Kotlin code using the Java classes can get data class conveniences from `generate_kotlin_extensions`:
+//! `copy` functions with the current values as defaults, `componentN` operators for destructuring
+//! and `OrNull` properties of optional fields. Classes with read-only fields or extra properties get no `copy` function.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::java::{generate_kotlin_extensions, JavaConfig};
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! struct User {
+//!     name: String,
+//!     nickname: Option<String>,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<User>();
+//! let file = generate_kotlin_extensions(&ir, &JavaConfig::new(), "UserExtensions").unwrap();
+//! assert!(file.code().contains("fun User.copy(\n    name: String = this.getName(),"));
+//! assert!(file.code().contains("operator fun User.component2(): String? = getNickname()"));
+//! ```
+//!
+//! ## Avro schemas
+//! RDC can also generate Avro schemas for Kafka event pipelines.
+//! Schemas are written as `<subject>.avsc` files following Schema Registry subject name strategies,
+//! and `generate_java_subjects` generates a Java class with the matching subject names.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::avro::{generate_avro_schemas, write_avro_schemas, SubjectNameStrategy};
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! struct OrderPlaced {
+//!     order_id: String,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<OrderPlaced>();
+//! let schemas = generate_avro_schemas(&ir, "com.example").unwrap();
+//! let strategy = SubjectNameStrategy::TopicName { topic: "orders".to_string() };
+//! write_avro_schemas(&schemas, &strategy, "target/test-tmp/schemas").unwrap();
+//! ```
+//!
+//! ## OpenAPI documents
+//! Services declared with `rdc_service!` and the types they use can be described with an OpenAPI 3.0 document,
+//! ready for swagger-ui and client generators for other languages.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::openapi::write_openapi_document;
+//! use rdc::{rdc_service, RDC};
+//!
+//! #[derive(RDC)]
+//! struct User {
+//!     name: String,
+//! }
+//!
+//! rdc_service! {
+//!     pub trait UserApi {
+//!         #[rdc(get, "/users/{id}")]
+//!         fn get_user(&self, id: String) -> User;
+//!     }
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add_service_trait::<dyn UserApi>();
+//! write_openapi_document(&ir, "Users", "1.0.0", "target/test-tmp/openapi.json").unwrap();
+//! ```
+//!
+//! Types alone can be turned into the `components/schemas` section of an OpenAPI 3.1 document, in JSON or YAML,
+//! to be merged into a hand-written API spec. There `Option<T>` fields are not required and also accept `null`,
+//! e.g. `type: ["string", "null"]`, while OpenAPI 3.0 documents only leave them out of `required`.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::openapi::{write_openapi_components, ComponentsFormat};
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! struct User {
+//!     name: String,
+//!     nickname: Option<String>,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<User>();
+//! write_openapi_components(&ir, ComponentsFormat::Yaml, "target/test-tmp/components.yaml").unwrap();
+//! ```
+//!
+//! ## gRPC services
+//! The same services and types can be translated to a proto3 file with messages and gRPC services,
+//! so RPC contracts stay defined in Rust when moving away from JSON.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::proto::write_proto_file;
+//! use rdc::{rdc_service, RDC};
+//!
+//! #[derive(RDC)]
+//! struct User {
+//!     name: String,
+//! }
+//!
+//! rdc_service! {
+//!     pub trait UserApi {
+//!         #[rdc(get, "/users/{id}")]
+//!         fn get_user(&self, id: String) -> User;
+//!     }
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add_service_trait::<dyn UserApi>();
+//! write_proto_file(&ir, "example.users", "target/test-tmp/users.proto").unwrap();
+//! ```
+//! Fields are numbered in declaration order. Numbers of fields that must keep their wire format,
+//! e.g. after a field was added in the middle of a struct, can be pinned with `ProtoConfig`.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::proto::{write_proto_file_with_config, ProtoConfig};
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! struct User {
+//!     name: String,
+//!     display_name: String,
+//!     email: String,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<User>();
+//! let config = ProtoConfig::new().with_field_number("User", "display_name", 3);
+//! write_proto_file_with_config(&ir, "example.users", &config, "target/test-tmp/users.proto").unwrap();
+//! ```
+//!
+//! ## Kotlin
+//! Kotlin data classes can be generated with Jackson annotations (default) or for kotlinx.serialization,
+//! which works in Kotlin Multiplatform projects.
+//! With kotlinx.serialization, data enums and untagged trait objects get custom serializers matching serde's external tagging.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::kotlin::{generate_kotlin_code, write_kotlin, KotlinConfig, KotlinFlavor};
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! enum Payment {
+//!     Cash,
+//!     Card { number: String },
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<Payment>();
+//! let config = KotlinConfig::new().with_flavor(KotlinFlavor::Kotlinx);
+//! let files = generate_kotlin_code(&ir, &config).unwrap();
+//! write_kotlin(&files, "com.example", "target/test-tmp/kotlin").unwrap();
+//! ```
+//!
+//! ## TypeScript
+//! `rdc_typescript!` generates a TypeScript module per type, so web frontends can share the JSON types with the services:
+//! interfaces for structs, string literal unions for enums, and unions matching serde's external tagging for data enums.
+//! Numbers are `number`, so properties holding 64-bit integers get a doc comment warning that values above `Number.MAX_SAFE_INTEGER` lose precision.
+//! ```rust
+//! use rdc::targets::typescript::write_typescript;
+//! use rdc::{rdc_typescript, RDC};
+//!
+//! #[derive(RDC)]
+//! enum Payment {
+//!     Cash,
+//!     Card { number: String },
+//! }
+//!
+//! let files = rdc_typescript!(Payment).unwrap();
+//! assert!(files[0].code().contains("  | { Card: { number: string } };"));
+//! write_typescript(&files, "target/test-tmp/typescript").unwrap();
+//! ```
+//!
+//! ## Python
+//! `generate_python_dataclasses` generates a Python module of dataclasses with `from_dict` and `to_dict` helpers,
+//! so scripts can read and write the JSON of the services. Data enums get a dataclass per variant, e.g. `PaymentCard`,
+//! and their `to_dict` and `from_dict` follow serde's external tagging.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::python::{generate_python_dataclasses, write_python_dataclasses};
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! enum Payment {
+//!     Cash,
+//!     Card { number: String },
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<Payment>();
+//! let module = generate_python_dataclasses(&ir).unwrap();
+//! assert!(module.contains("class PaymentCard(Payment):"));
+//! write_python_dataclasses(&ir, "target/test-tmp/python/models.py").unwrap();
+//! ```
+//!
+//! ## Pydantic
+//! `generate_pydantic_models` generates Pydantic v2 models instead, for services that need validated request bodies, e.g. with FastAPI.
+//! Enums become `Literal` unions, optional fields `Optional[...]` and integers are checked against the range of their type.
+//! Models are dumped to the JSON of the Rust types with `model_dump(by_alias=True)`.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::pydantic::{generate_pydantic_models, write_pydantic_models};
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! enum Status {
+//!     Active,
+//!     Banned,
+//! }
+//!
+//! #[derive(RDC)]
+//! struct Account {
+//!     id: i64,
+//!     status: Option<Status>,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<Account>();
+//! let module = generate_pydantic_models(&ir).unwrap();
+//! assert!(module.contains("Status = Literal[\"Active\", \"Banned\"]"));
+//! assert!(module.contains("    status: Optional[Status] = None"));
+//! write_pydantic_models(&ir, "target/test-tmp/pydantic/models.py").unwrap();
+//! ```
+//!
+//! ## C#
+//! `generate_csharp_code` generates C# records for .NET consumers, serialized with System.Text.Json.
+//! Properties carry `[JsonPropertyName]` with the JSON names, fields that are not an `Option` are `required`,
+//! and enums and data enums get generated `JsonConverter`s reading and writing the same JSON as serde.
+//! The code needs C# 11 and .NET 7 or newer.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::csharp::{generate_csharp_code, write_csharp};
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! enum Status {
+//!     Active,
+//!     Banned,
+//! }
+//!
+//! #[derive(RDC)]
+//! struct Account {
+//!     id: i64,
+//!     status: Option<Status>,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<Account>();
+//! let files = generate_csharp_code(&ir, "Example.Accounts").unwrap();
+//! let account = files.iter().find(|f| f.name() == "Account").unwrap();
+//! assert!(account.code().contains("    public Status? Status { get; init; }"));
+//! write_csharp(&files, "target/test-tmp/csharp").unwrap();
+//! ```
+//!
+//! ## Go
+//! `rdc_go!` generates a Go source file with structs tagged with the JSON names, for Go services sharing the API.
+//! Optional fields are pointers, unit enums string types with constants, and data enums and polymorphic types
+//! tagged unions with `MarshalJSON` and `UnmarshalJSON` methods reading and writing serde's layout.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::go::write_go_code;
+//! use rdc::{rdc_go, RDC};
+//!
+//! #[derive(RDC)]
+//! enum Status {
+//!     Active,
+//!     Banned,
+//! }
+//!
+//! #[derive(RDC)]
+//! struct Account {
+//!     id: i64,
+//!     status: Option<Status>,
+//! }
+//!
+//! let code = rdc_go!(package = "accounts"; Account).unwrap();
+//! assert!(code.contains("\tStatus *Status `json:\"status\"`\n"));
+//! assert!(code.contains("\tStatusActive Status = \"Active\"\n"));
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<Account>();
+//! write_go_code(&ir, "accounts", "target/test-tmp/go/accounts/models.go").unwrap();
+//! ```
+//!
+//! ## Dart
+//! `generate_dart_code` generates a Dart library for Flutter clients, with immutable classes whose optional fields are nullable.
+//! With `DartFlavor::Manual` the classes get handwritten `fromJson` and `toJson`, with `DartFlavor::JsonSerializable`
+//! structs are annotated with `@JsonSerializable` and the `.g.dart` part is generated by `build_runner`.
+//! Data enums and polymorphic types become sealed classes reading and writing serde's layout in both flavors.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::dart::{generate_dart_code, write_dart_code, DartFlavor};
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! enum Payment {
+//!     Cash,
+//!     Card { number: String },
+//! }
+//!
+//! #[derive(RDC)]
+//! struct Order {
+//!     id: i64,
+//!     payment: Option<Payment>,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<Order>();
+//! let code = generate_dart_code(&ir, DartFlavor::JsonSerializable, "orders.dart").unwrap();
+//! assert!(code.contains("part 'orders.g.dart';"));
+//! assert!(code.contains("  final Payment? payment;"));
+//! assert!(code.contains("class PaymentCard extends Payment {"));
+//! write_dart_code(&ir, DartFlavor::Manual, "target/test-tmp/dart/lib/orders.dart").unwrap();
+//! ```
+//!
+//! ## Scala
+//! `generate_scala_code` generates a Scala source file with case classes, e.g. for Spark jobs parsing the same payloads.
+//! With `ScalaFlavor::Circe` companion objects hold implicit `Decoder` and `Encoder` instances following serde's layout,
+//! including data enums, which become sealed traits. With `ScalaFlavor::Jackson` the classes are annotated for `jackson-module-scala`,
+//! which can not read data enums, so an IR with data enums is rejected with an error. Polymorphic types become sealed traits extended by the case classes of their variants in both flavors.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::scala::{generate_scala_code, write_scala_code, ScalaFlavor};
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! enum Payment {
+//!     Cash,
+//!     Card { number: String },
+//! }
+//!
+//! #[derive(RDC)]
+//! struct Order {
+//!     id: i64,
+//!     payment: Option<Payment>,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<Order>();
+//! let code = generate_scala_code(&ir, ScalaFlavor::Circe, "com.example.orders").unwrap();
+//! assert!(code.contains("  payment: Option[Payment]\n"));
+//! assert!(code.contains("final case class PaymentCard(\n  number: String\n) extends Payment\n"));
+//! write_scala_code(&ir, ScalaFlavor::Circe, "com.example.orders", "target/test-tmp/scala/Orders.scala").unwrap();
+//! ```
+//!
+//! ## GraphQL
+//! A GraphQL schema can be generated from the same types, together with a graphql-java wiring class,
+//! so that the generated Java classes can serve the schema directly.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::graphql::generate_graphql_schema;
+//! use rdc::targets::java::{generate_graphql_wiring, JavaConfig};
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! struct User {
+//!     user_name: String,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<User>();
+//! let schema = generate_graphql_schema(&ir).unwrap();
+//! let wiring = generate_graphql_wiring(&ir, &JavaConfig::new(), "GraphQLWiring").unwrap();
+//! ```
+//!
+//! ## Documentation
+//! Markdown or HTML pages describing the wire format can replace hand-maintained descriptions of the types.
+//! Every type gets a page with JSON names, types and optionality of its fields, linked to the pages of related types.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::docs::{generate_docs, DocsConfig, DocsFormat};
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! struct Order {
+//!     order_id: String,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<Order>();
+//! let config = DocsConfig::new()
+//!     .with_format(DocsFormat::Html)
+//!     .with_field_description("Order", "order_id", "Unique identifier of the order.");
+//! let pages = generate_docs(&ir, &config).unwrap();
+//! ```
+//!
+//! ## Class diagrams
+//! A Mermaid or PlantUML class diagram shows the types with their fields and the references between them.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::diagram::{generate_class_diagram, DiagramFormat};
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! struct Order {
+//!     lines: Vec<OrderLine>,
+//! }
+//!
+//! #[derive(RDC)]
+//! struct OrderLine {
+//!     sku: String,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<Order>();
+//! let diagram = generate_class_diagram(&ir, DiagramFormat::PlantUml).unwrap();
+//! ```
+//!
+//! ## Dependency graphs
+//! A Graphviz DOT graph shows which types each type pulls in, clustered by the Rust modules they are declared in.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::dot::generate_dependency_graph;
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! struct Order {
+//!     lines: Vec<OrderLine>,
+//! }
+//!
+//! #[derive(RDC)]
+//! struct OrderLine {
+//!     sku: String,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<Order>();
+//! let graph = generate_dependency_graph(&ir).unwrap();
+//! ```
+//!
+//! ## Migrations
+//! The types of an IR can be saved as a JSON snapshot and compared with a later version.
+//! From the differences, a Java class can be generated that upgrades old JSON payloads to the new shape.
+//! Renamed fields, removed fields and added optional fields are migrated automatically,
+//! and the remaining changes are returned as warnings of the class, so that they can be migrated by hand.
+//! ```rust,no_run
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::java::{generate_java_migrations, JavaConfig};
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! struct Invoice {
+//!     #[serde(rename = "invoiceNumber")]
+//!     number: String,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<Invoice>();
+//! let old = IntermediateRepresentation::read_snapshot("snapshots/types.json").unwrap();
+//! let class = generate_java_migrations(&old, &ir, &JavaConfig::new(), "Migrations").unwrap();
+//! for warning in class.warnings() {
+//!     println!("{warning}");
+//! }
+//! ir.write_snapshot("snapshots/types.json").unwrap();
+//! ```
+//!
+//! ## Compatibility checks
+//! Generation can fail when the types break compatibility with a saved snapshot, e.g. when a field is removed or changes its type.
+//! Intended breaking changes are acknowledged by their subjects, like `Invoice.total` or `Status::Paid`.
+//! ```rust,no_run
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::java::{generate_java_code_with_config, write_java, JavaConfig};
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! struct Invoice {
+//!     total: f64,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<Invoice>();
+//! let config = JavaConfig::new()
+//!     .with_compatibility_snapshot("snapshots/types.json")
+//!     .with_acknowledged_change("Invoice.total");
+//! let classes = generate_java_code_with_config(&ir, &config).unwrap();
+//! write_java(&classes, "com.example", "src/main/java").unwrap();
+//! ir.write_snapshot("snapshots/types.json").unwrap();
+//! ```
+//!
+//! ## Resource bundles
+//! Display names of enum variants can be generated as `.properties` files of a resource bundle,
+//! so that Java UIs load them with `ResourceBundle.getBundle`. Variants are displayed as their names split into words,
+//! unless a display name or a translation is configured.
+//! ```rust,no_run
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::properties::{generate_properties, write_properties, PropertiesConfig};
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! enum OrderStatus {
+//!     PaymentPending,
+//!     Shipped,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<OrderStatus>();
+//! let config = PropertiesConfig::new("messages")
+//!     .with_display_name("OrderStatus", "Shipped", "On its way")
+//!     .with_translation("de", "OrderStatus", "Shipped", "Unterwegs");
+//! let files = generate_properties(&ir, &config).unwrap();
+//! write_properties(&files, "src/main/resources").unwrap();
+//! ```
+//!
+//! Labels and translations can also be set on the variants with `#[rdc(display = "...")]` and `#[rdc(display(de = "..."))]`,
+//! or on the IR with `set_variant_display` and `set_variant_translation`.
+//! With `JavaConfig::with_display_name_bundle`, every Java enum gets a `displayName(Locale)` method reading its label
+//! from the bundle, so UIs get localized labels without their own lookup code.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::java::{generate_java_code_with_config, JavaConfig};
+//! use rdc::targets::properties::{generate_properties, PropertiesConfig};
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! enum OrderStatus {
+//!     PaymentPending,
+//!     #[rdc(display = "On its way", display(de = "Unterwegs"))]
+//!     Shipped,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<OrderStatus>();
+//! let files = generate_properties(&ir, &PropertiesConfig::new("messages")).unwrap();
+//! assert_eq!(files[1].file_name(), "messages_de.properties");
+//! assert_eq!(files[1].content(), "OrderStatus.SHIPPED=Unterwegs\n");
+//! let config = JavaConfig::new().with_display_name_bundle("messages");
+//! let classes = generate_java_code_with_config(&ir, &config).unwrap();
+//! assert!(classes[0].code().contains("public String displayName(Locale locale)"));
+//! ```
+//!
+//! ## SQL tables
+//! `CREATE TABLE` statements for PostgreSQL can be generated from flat structs, e.g. for staging tables mirroring the types.
+//! Columns are `NOT NULL` unless their fields are optional. Column types and primary keys are set on the fields
+//! with `#[rdc(sql_type = "...")]` and `#[rdc(primary_key)]`, or in the config, which takes precedence.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::sql::{generate_sql_ddl, SqlConfig};
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! struct Invoice {
+//!     #[rdc(primary_key)]
+//!     invoice_number: String,
+//!     #[rdc(sql_type = "NUMERIC(12, 2)")]
+//!     total: f64,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<Invoice>();
+//! let config = SqlConfig::new().with_table_name("Invoice", "staging_invoices");
+//! let ddl = generate_sql_ddl(&ir, &config).unwrap();
+//! assert!(ddl.contains("\"total\" NUMERIC(12, 2) NOT NULL"));
+//! assert!(ddl.contains("PRIMARY KEY (\"invoice_number\")"));
+//! ```
+//!
+//! ## Building an IR
+//! Types that are only known at runtime, e.g. read from a database schema, can be declared with `IrBuilder` instead of the derive.
+//! ```rust
+//! use rdc::ir::{FieldType, IrBuilder, TypeTarget};
+//! use rdc::targets::java::generate_java_code;
+//!
+//! let ir = IrBuilder::new(TypeTarget::Java)
+//!     .struct_("Invoice")
+//!     .field("id", FieldType::string())
+//!     .optional_field("lines", FieldType::list(FieldType::named("InvoiceLine")))
+//!     .struct_("InvoiceLine")
+//!     .field("amount", FieldType::f64())
+//!     .build()
+//!     .unwrap();
+//! let classes = generate_java_code(&ir).unwrap();
+//! ```
+//!
+//! ## Target filters
+//! One set of Rust types can produce different surfaces per consumer with a `TargetFilter`.
+//! It returns a copy of the IR without the types and fields skipped for the given target,
+//! and fails when a remaining type still references a skipped one.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TargetFilter, TypeTarget};
+//! use rdc::targets::kotlin::{generate_kotlin_code, KotlinConfig};
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! struct User {
+//!     name: String,
+//!     password_hash: String,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<User>();
+//! let filter = TargetFilter::new().with_skipped_target("User.password_hash", "kotlin");
+//! let public = filter.apply(&ir, "kotlin").unwrap();
+//! let files = generate_kotlin_code(&public, &KotlinConfig::new()).unwrap();
+//! assert!(!files[0].code().contains("passwordHash"));
+//! ```
+//!
+//! ## Schema fingerprints
+//! Every type has a fingerprint of its JSON shape, including the types it references, which services can exchange to detect mismatched deployments.
+//! Rust code reads it with `rdc::ir::type_fingerprint::<T>()`, and generated Java classes can carry it in a `SCHEMA_FINGERPRINT` constant.
+//! ```rust
+//! use rdc::ir::{type_fingerprint, IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::java::{generate_java_code_with_config, JavaConfig};
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! struct Invoice {
+//!     number: String,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<Invoice>();
+//! let config = JavaConfig::new().with_schema_fingerprints(true);
+//! let classes = generate_java_code_with_config(&ir, &config).unwrap();
+//! let fingerprint = type_fingerprint::<Invoice>().unwrap();
+//! assert!(classes[0].code().contains(&format!("SCHEMA_FINGERPRINT = \"{fingerprint}\"")));
+//! ```
+//!
+//! ## Type registry
+//! Instead of listing every type in one `rdc_java!` call, modules can register their types in a process-wide registry,
+//! and `rdc_java_all!` generates code for all of them. The derive can not register types by itself,
+//! so with the `registry` feature, `rdc_register!(Invoice);` is placed next to each type and collected at link time.
+//! Without the feature, types are registered with explicit calls:
+//! ```rust
+//! use rdc::{rdc_java_all, RDC};
+//!
+//! #[derive(RDC)]
+//! struct Invoice {
+//!     number: String,
+//! }
+//!
+//! rdc::registry::register::<Invoice>();
+//! let classes = rdc_java_all!().unwrap();
+//! assert!(classes.iter().any(|c| c.name() == "Invoice"));
+//! ```
+//!
+//! ## Adjusting the IR
+//! Before generating code, the IR can be adjusted with `remove_type`, `replace_struct` and `rename`,
+//! e.g. to use an existing hand-written Java class instead of a generated dependency.
+//! References to a removed type are kept, while `rename` updates them.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::java::generate_java_code;
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! struct Order {
+//!     customer: Customer,
+//!     address: Address,
+//! }
+//!
+//! #[derive(RDC)]
+//! struct Customer {
+//!     name: String,
+//! }
+//!
+//! #[derive(RDC)]
+//! struct Address {
+//!     street: String,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<Order>();
+//! ir.remove_type("Customer").unwrap();
+//! ir.rename("Address", "ShippingAddress").unwrap();
+//! let classes = generate_java_code(&ir).unwrap();
+//! assert_eq!(classes.len(), 2);
+//! assert!(classes.iter().any(|c| c.code().contains("private ShippingAddress address;")));
+//! ```
+//!
+//! ## Output groups
+//! `OutputGroups` splits one IR into several output sets, e.g. public DTOs for an SDK and internal DTOs for a service,
+//! which can then be generated with different targets, configurations and packages in one run.
+//! Every group contains its members and the types they use. A group using a member of another group is an error.
+//! The default group contains everything else.
+//! ```rust
+//! use rdc::ir::{IntermediateRepresentation, OutputGroups, TypeTarget};
+//! use rdc::targets::java::{generate_java_code_with_config, JavaConfig};
+//! use rdc::RDC;
+//!
+//! #[derive(RDC)]
+//! struct Invoice {
+//!     number: String,
+//! }
+//!
+//! #[derive(RDC)]
+//! struct InvoiceAudit {
+//!     invoice: Invoice,
+//!     changed_by: String,
+//! }
+//!
+//! let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+//! ir.add::<InvoiceAudit>();
+//! let groups = OutputGroups::new()
+//!     .with_group("public_api", ["Invoice"])
+//!     .with_default_group("internal");
+//! for (group, ir) in groups.split(&ir).unwrap() {
+//!     let config = match group.as_str() {
+//!         "public_api" => JavaConfig::new().with_class_suffix("Dto"),
+//!         _ => JavaConfig::new(),
+//!     };
+//!     let classes = generate_java_code_with_config(&ir, &config).unwrap();
+//!     assert!(!classes.is_empty());
+//! }
+//! ```
+//!
+//! ## JSON schemas
+//! With the `json` feature, `IntermediateRepresentation::from_json_schemas` creates an IR from JSON schemas,
+//! e.g. the ones `schemars` produces, so types deriving `JsonSchema` can be generated without deriving `RDC`.
+//! Object schemas become structs and string enum schemas become enums.
+//! ```rust,ignore
+//! use rdc::ir::{IntermediateRepresentation, TypeTarget};
+//! use rdc::targets::java::generate_java_code;
+//!
+//! let invoice = serde_json::to_value(schemars::schema_for!(Invoice)).unwrap();
+//! let customer = serde_json::to_value(schemars::schema_for!(Customer)).unwrap();
+//! let ir = IntermediateRepresentation::from_json_schemas(TypeTarget::Java, [&invoice, &customer]).unwrap();
+//! let classes = generate_java_code(&ir).unwrap();
+//! ```
+//! Likewise, `IntermediateRepresentation::from_openapi` creates an IR from an OpenAPI 3 document, e.g. the one `utoipa` generates,
+//! with the component schemas as types and the operations as services, so a Java client can be generated for an existing service.
+
+use crate::codegen::GenerateIR;
+use crate::targets::java::type_resolver::JavaType;
+pub use rdc_macros::RDC;
+
+/// This module defines GenerateIR trait and implementations for all primitive types used in IR.
+pub mod codegen;
+
+/// This module contains error types.
+pub mod errors;
+
+/// This module contains intermediate representation of the data.
+pub mod ir;
+
+/// This module contains a process-wide registry of types, collected without listing them in one place.
+pub mod registry;
 
 /// This module contains all the programming language targets.
 pub mod targets;
 
 /// This is a type that is used to mark data structures that can be converted to IR.
 /// It should be also implemented for all types that are used in IR.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can not be used in RDC data structures",
+    label = "`{Self}` does not implement `RDCType`",
+    note = "add `#[derive(RDC)]` to `{Self}`, or implement `RDCType` for it if it is a foreign type",
+    note = "trait objects like `Box<dyn Trait>` have to be registered with `rdc_polymorphic!`",
+    note = "structs, newtypes and enums are supported, tuple structs with more fields, unit structs and unions are not"
+)]
 pub trait RDCType: GenerateIR + JavaType + 'static {}
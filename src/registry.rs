@@ -0,0 +1,233 @@
+use crate::ir::{IntermediateRepresentation, TypeTarget};
+use crate::RDCType;
+use std::any::TypeId;
+use std::sync::Mutex;
+
+/// Function adding a registered type to an IR.
+type AddToIr = fn(&mut IntermediateRepresentation);
+
+/// Types registered with `register`, in the order of registration.
+static REGISTRY: Mutex<Vec<(TypeId, AddToIr)>> = Mutex::new(Vec::new());
+
+#[cfg(feature = "registry")]
+#[doc(hidden)]
+pub use inventory;
+
+/// A type registered with `rdc_register!`, collected at link time.
+#[cfg(feature = "registry")]
+#[doc(hidden)]
+pub struct Registration {
+    type_id: fn() -> TypeId,
+    add: AddToIr,
+}
+
+#[cfg(feature = "registry")]
+impl Registration {
+    pub const fn of<T: RDCType>() -> Self {
+        Self {
+            type_id: TypeId::of::<T>,
+            add: add_to_ir::<T>,
+        }
+    }
+}
+
+#[cfg(feature = "registry")]
+inventory::collect!(Registration);
+
+#[cfg(feature = "registry")]
+fn add_to_ir<T: RDCType>(ir: &mut IntermediateRepresentation) {
+    ir.add::<T>();
+}
+
+/// This macro registers types next to their declarations, so that `IntermediateRepresentation::from_registry`
+/// and `rdc_java_all!` include them without any module having to be called. It needs the `registry` feature.
+///
+/// Example:
+/// ```rust,ignore
+/// use rdc::{rdc_register, RDC};
+///
+/// #[derive(RDC)]
+/// pub struct Invoice {
+///     pub number: String,
+/// }
+///
+/// rdc_register!(Invoice);
+/// ```
+#[cfg(feature = "registry")]
+#[macro_export]
+macro_rules! rdc_register {
+    ($($t:ty),+ $(,)?) => {
+        $(
+            $crate::registry::inventory::submit! {
+                $crate::registry::Registration::of::<$t>()
+            }
+        )+
+    };
+}
+
+/// This function adds a type to the process-wide registry, so that `IntermediateRepresentation::from_registry`
+/// and `rdc_java_all!` include it without listing it again. Registering a type twice has no effect.
+///
+/// With the `registry` feature, `rdc_register!` next to the type declaration registers it without a call.
+/// Without it, types are registered explicitly, e.g. from a function next to their declarations that each module exposes.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::RDC;
+///
+/// mod billing {
+///     use rdc::RDC;
+///
+///     #[derive(RDC)]
+///     pub struct Invoice {
+///         pub number: String,
+///     }
+///
+///     pub fn register_types() {
+///         rdc::registry::register::<Invoice>();
+///     }
+/// }
+///
+/// billing::register_types();
+/// let ir = IntermediateRepresentation::from_registry(TypeTarget::Java);
+/// assert!(ir.structs().iter().any(|s| s.self_type().type_name() == "Invoice"));
+/// ```
+pub fn register<T: RDCType>() {
+    let mut registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+    if !registry.iter().any(|(id, _)| *id == TypeId::of::<T>()) {
+        registry.push((TypeId::of::<T>(), |ir| ir.add::<T>()));
+    }
+}
+
+impl IntermediateRepresentation {
+    /// Creates an IR with all the types registered with `rdc_register!` or `rdc::registry::register` and the types they use.
+    pub fn from_registry(target: TypeTarget) -> Self {
+        let mut ir = IntermediateRepresentation::new(target);
+        let mut added = Vec::new();
+        #[cfg(feature = "registry")]
+        for registration in inventory::iter::<Registration> {
+            let type_id = (registration.type_id)();
+            if !added.contains(&type_id) {
+                added.push(type_id);
+                (registration.add)(&mut ir);
+            }
+        }
+        let registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+        for (type_id, add) in registry.iter() {
+            if !added.contains(type_id) {
+                added.push(*type_id);
+                add(&mut ir);
+            }
+        }
+        ir
+    }
+}
+
+/// This macro generates Java code for all the types registered with `rdc_register!` or `rdc::registry::register`.
+/// Like `rdc_java!`, it accepts a `JavaConfig`.
+///
+/// Example:
+/// ```rust
+/// use rdc::{rdc_java_all, RDC};
+/// use rdc::targets::java::JavaConfig;
+///
+/// #[derive(RDC)]
+/// struct Invoice {
+///     id: String,
+/// }
+///
+/// rdc::registry::register::<Invoice>();
+/// let classes = rdc_java_all!(config = JavaConfig::new().with_class_suffix("Dto")).unwrap();
+/// assert!(classes.iter().any(|c| c.name() == "InvoiceDto"));
+/// ```
+#[macro_export]
+macro_rules! rdc_java_all {
+    (config = $config:expr) => {
+        $crate::targets::java::generate_java_code_with_config(
+            &$crate::ir::IntermediateRepresentation::from_registry($crate::ir::TypeTarget::Java),
+            &$config,
+        )
+    };
+    () => {
+        $crate::targets::java::generate_java_code(
+            &$crate::ir::IntermediateRepresentation::from_registry($crate::ir::TypeTarget::Java),
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as rdc;
+    use crate::RDC;
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Shipment {
+        parcels: Vec<Parcel>,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Parcel {
+        weight: f64,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Carrier {
+        Post,
+        Courier,
+    }
+
+    #[test]
+    fn registered_types() {
+        register::<Shipment>();
+        register::<Carrier>();
+        register::<Carrier>();
+        let ir = IntermediateRepresentation::from_registry(TypeTarget::Java);
+        let structs = ir
+            .structs()
+            .iter()
+            .map(|s| s.self_type().type_name().as_str())
+            .collect::<Vec<&str>>();
+        assert!(structs.contains(&"Shipment"));
+        assert!(structs.contains(&"Parcel"));
+        let carriers = ir
+            .enums()
+            .iter()
+            .filter(|e| e.self_type().type_name() == "Carrier")
+            .count();
+        assert_eq!(carriers, 1);
+        let classes = rdc_java_all!().unwrap();
+        assert!(classes.iter().any(|c| c.name() == "Carrier"));
+    }
+
+    #[cfg(feature = "registry")]
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Warehouse {
+        code: String,
+    }
+
+    #[cfg(feature = "registry")]
+    rdc_register!(Warehouse, Parcel);
+
+    #[cfg(feature = "registry")]
+    #[test]
+    fn collected_types() {
+        register::<Warehouse>();
+        let ir = IntermediateRepresentation::from_registry(TypeTarget::Java);
+        let warehouses = ir
+            .structs()
+            .iter()
+            .filter(|s| s.self_type().type_name() == "Warehouse")
+            .count();
+        assert_eq!(warehouses, 1);
+        assert!(ir
+            .structs()
+            .iter()
+            .any(|s| s.self_type().type_name() == "Parcel"));
+    }
+}
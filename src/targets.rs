@@ -1 +1,65 @@
 pub mod java;
+
+/// This module generates Kotlin classes annotated for Jackson or kotlinx.serialization.
+pub mod kotlin;
+
+/// This module generates TypeScript types describing the JSON of the types, e.g. for web frontends.
+pub mod typescript;
+
+/// This module generates Python dataclasses converting to and from the JSON of the types, e.g. for scripts.
+pub mod python;
+
+/// This module generates Pydantic models validating the JSON of the types, e.g. for FastAPI services.
+pub mod pydantic;
+
+/// This module generates C# records serialized with System.Text.Json, e.g. for .NET consumers of the API.
+pub mod csharp;
+
+/// This module generates Go structs with `json` tags and tagged unions for data enums, e.g. for Go services.
+pub mod go;
+
+/// This module generates Dart classes with `fromJson` and `toJson` or `json_serializable` annotations, e.g. for Flutter clients.
+pub mod dart;
+
+/// This module generates Scala case classes and sealed traits with circe codecs or Jackson annotations, e.g. for Spark jobs.
+pub mod scala;
+
+/// This module generates Avro schemas and Schema Registry helpers for event pipelines.
+pub mod avro;
+
+/// This module generates OpenAPI documents describing services and the types they use.
+pub mod openapi;
+
+/// This module generates GraphQL schemas.
+pub mod graphql;
+
+/// This module generates Protobuf messages and gRPC services.
+pub mod proto;
+
+/// This module generates Markdown or HTML pages documenting the wire format of the types.
+pub mod docs;
+
+/// This module generates Mermaid or PlantUML class diagrams of the types.
+pub mod diagram;
+
+/// This module generates Graphviz graphs of dependencies between the types.
+pub mod dot;
+
+/// This module generates `.properties` resource bundles with display names of enum variants.
+pub mod properties;
+
+/// This module generates SQL `CREATE TABLE` statements for tables mirroring the structs.
+pub mod sql;
+
+pub(crate) mod json;
+
+/// This module contains helpers shared by targets generating code line by line.
+pub(crate) mod utils;
+
+/// This module contains the `TargetRunner` trait shared by runners of all targets.
+#[cfg(any(test, feature = "verify"))]
+pub mod runner;
+
+/// This module contains snapshot assertions for generated code.
+#[cfg(any(test, feature = "verify"))]
+pub mod snapshot;
@@ -0,0 +1,464 @@
+use crate::errors::Error;
+use crate::ir::{CustomType, IntermediateRepresentation, Name, Type, TypeKind};
+use crate::targets::java::JavaClass;
+use crate::targets::json::Json;
+use genco::prelude::*;
+use genco::quote;
+use std::collections::HashSet;
+use std::io::Write;
+
+/// This is a struct that represents a generated Avro schema of a single record.
+/// The schema is self-contained, so all the nested types are defined inline.
+pub struct AvroSchema {
+    name: String,
+    namespace: String,
+    schema: String,
+}
+
+impl AvroSchema {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// Returns the fully qualified name of the record, e.g. `com.example.Order`.
+    pub fn full_name(&self) -> String {
+        format!("{}.{}", self.namespace, self.name)
+    }
+
+    /// Returns the schema as JSON.
+    pub fn schema(&self) -> &str {
+        &self.schema
+    }
+
+    /// Returns the Schema Registry subject of the schema according to the given strategy.
+    pub fn subject(&self, strategy: &SubjectNameStrategy) -> String {
+        match strategy {
+            SubjectNameStrategy::TopicName { topic } => format!("{topic}-value"),
+            SubjectNameStrategy::RecordName => self.full_name(),
+            SubjectNameStrategy::TopicRecordName { topic } => {
+                format!("{topic}-{}", self.full_name())
+            }
+        }
+    }
+}
+
+/// This enum mirrors subject name strategies of Confluent Schema Registry.
+pub enum SubjectNameStrategy {
+    /// `<topic>-value`, the default strategy of Kafka serializers.
+    TopicName { topic: String },
+    /// The fully qualified record name.
+    RecordName,
+    /// `<topic>-<fully qualified record name>`.
+    TopicRecordName { topic: String },
+}
+
+/// This function generates Avro schemas of all the structs in the IR.
+///
+/// Structs are translated to records, unit enums to enums, `Vec` to arrays, `HashMap` with `String` keys to maps,
+/// `Option` to unions with `null` and polymorphic types to unions of their variants.
+/// Data enums have no Avro counterpart and are reported as an error.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::targets::avro::{generate_avro_schemas, SubjectNameStrategy};
+/// use rdc::RDC;
+///
+/// #[derive(RDC)]
+/// struct OrderPlaced {
+///     order_id: String,
+///     quantity: i32,
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add::<OrderPlaced>();
+/// let schemas = generate_avro_schemas(&ir, "com.example").unwrap();
+/// let strategy = SubjectNameStrategy::TopicName { topic: "orders".to_string() };
+/// assert_eq!(schemas[0].subject(&strategy), "orders-value");
+/// ```
+pub fn generate_avro_schemas(
+    ir: &IntermediateRepresentation,
+    namespace: &str,
+) -> Result<Vec<AvroSchema>, Error> {
+    ir.validate()?;
+    ir.reject_transparent_structs("Avro")?;
+    ir.structs()
+        .iter()
+        .map(|s| {
+            let mut generator = SchemaGenerator {
+                ir,
+                namespace,
+                defined: HashSet::new(),
+            };
+            let schema = generator.record(s.self_type())?;
+            Ok(AvroSchema {
+                name: s.self_type().type_name().clone(),
+                namespace: namespace.to_string(),
+                schema: schema.render(),
+            })
+        })
+        .collect()
+}
+
+/// This function writes schemas in the layout used to register them in Confluent Schema Registry.
+/// Every schema is written to `<subject>.avsc`, so that it can be registered with the file name as the subject.
+pub fn write_avro_schemas(
+    schemas: &[AvroSchema],
+    strategy: &SubjectNameStrategy,
+    directory: &str,
+) -> Result<(), Error> {
+    std::fs::create_dir_all(directory).map_err(|_| Error::new("Failed to create directory"))?;
+    for schema in schemas {
+        let mut file_path = std::path::PathBuf::from(directory);
+        file_path.push(format!("{}.avsc", schema.subject(strategy)));
+        let mut file =
+            std::fs::File::create(file_path).map_err(|_| Error::new("Failed to create file"))?;
+        file.write_all(schema.schema().as_bytes())
+            .map_err(|_| Error::new("Failed to write to file"))?;
+    }
+    Ok(())
+}
+
+/// This function generates a Java class with subject names of the schemas as constants,
+/// so that Kafka producers and consumers use the same subjects as the registered schemas.
+/// Constants are named after the records, e.g. `ORDER_PLACED`.
+pub fn generate_java_subjects(
+    schemas: &[AvroSchema],
+    strategy: &SubjectNameStrategy,
+    class_name: &str,
+) -> Result<JavaClass, Error> {
+    let constants = schemas.iter().map(|s| {
+        let constant = Name::from_pascal_case(s.name()).as_upper_snake_case();
+        let subject = s.subject(strategy);
+        quote!(public static final String $constant = $[str]($[const](subject));)
+    });
+    let tokens: java::Tokens = quote!(
+        public final class $class_name {
+            $(for c in constants => $c)
+
+            private $class_name() {}
+        }
+    );
+    JavaClass::from_tokens(class_name.to_string(), tokens)
+}
+
+struct SchemaGenerator<'a> {
+    ir: &'a IntermediateRepresentation,
+    namespace: &'a str,
+    defined: HashSet<String>,
+}
+
+impl SchemaGenerator<'_> {
+    fn record(&mut self, self_type: &CustomType) -> Result<Json, Error> {
+        let name = self_type.type_name();
+        let s = self
+            .ir
+            .structs()
+            .iter()
+            .find(|s| s.self_type().type_name() == name)
+            .ok_or_else(|| Error::new(&format!("Struct `{name}` is not in the IR")))?;
+        self.defined.insert(name.clone());
+        let mut fields = Vec::new();
+        for field in s.fields() {
+            let json_name = field.json_name();
+            check_name(json_name)?;
+            let mut entries = vec![
+                ("name", Json::string(json_name.as_str())),
+                ("type", self.field_type(field.field_type())?),
+            ];
+            if !field.is_required() {
+                entries.push(("default", Json::Null));
+            }
+            fields.push(Json::Object(entries));
+        }
+        Ok(Json::Object(vec![
+            ("type", Json::string("record")),
+            ("name", Json::string(name.as_str())),
+            ("namespace", Json::string(self.namespace)),
+            ("fields", Json::Array(fields)),
+        ]))
+    }
+
+    fn field_type(&mut self, t: &Type) -> Result<Json, Error> {
+        match t.kind() {
+            TypeKind::List(element) => Ok(Json::Object(vec![
+                ("type", Json::string("array")),
+                ("items", self.field_type(element)?),
+            ])),
+            TypeKind::Map(key, value) => {
+                if key.type_name() != "String" {
+                    return Err(Error::new(&format!(
+                        "Avro maps must have string keys, but `{}` was used",
+                        key.type_name()
+                    )));
+                }
+                Ok(Json::Object(vec![
+                    ("type", Json::string("map")),
+                    ("values", self.field_type(value)?),
+                ]))
+            }
+            TypeKind::Option(inner) => {
+                Ok(union(vec![Json::string("null"), self.field_type(inner)?]))
+            }
+            TypeKind::UnsignedLong => Ok(Json::Object(vec![
+                ("type", Json::string("bytes")),
+                ("logicalType", Json::string("decimal")),
+                ("precision", Json::Number(20)),
+                ("scale", Json::Number(0)),
+            ])),
+            TypeKind::Named => self.named_type(t.type_name()),
+        }
+    }
+
+    fn named_type(&mut self, name: &str) -> Result<Json, Error> {
+        let primitive = match name {
+            "Boolean" => Some("boolean"),
+            "Byte" | "Short" | "Integer" => Some("int"),
+            "Long" => Some("long"),
+            "Float" => Some("float"),
+            "Double" => Some("double"),
+            "String" => Some("string"),
+            _ => None,
+        };
+        if let Some(primitive) = primitive {
+            return Ok(Json::string(primitive));
+        }
+        if self.defined.contains(name) {
+            return Ok(Json::string(format!("{}.{}", self.namespace, name)));
+        }
+        let ir = self.ir;
+        if let Some(s) = ir
+            .structs()
+            .iter()
+            .find(|s| s.self_type().type_name() == name)
+        {
+            return self.record(s.self_type());
+        }
+        if let Some(e) = ir
+            .enums()
+            .iter()
+            .find(|e| e.self_type().type_name() == name)
+        {
+            self.defined.insert(name.to_string());
+            let mut symbols = Vec::new();
+            for variant in e.variants() {
+                check_name(variant.json_name())?;
+                symbols.push(Json::string(variant.json_name().as_str()));
+            }
+            return Ok(Json::Object(vec![
+                ("type", Json::string("enum")),
+                ("name", Json::string(name)),
+                ("namespace", Json::string(self.namespace)),
+                ("symbols", Json::Array(symbols)),
+            ]));
+        }
+        if let Some(p) = ir
+            .polymorphics()
+            .iter()
+            .find(|p| p.self_type().type_name() == name)
+        {
+            let variants = p
+                .variants()
+                .iter()
+                .map(|v| self.field_type(v.variant_type()))
+                .collect::<Result<Vec<Json>, Error>>()?;
+            return Ok(union(variants));
+        }
+        Err(Error::new(&format!(
+            "`{name}` can not be represented in Avro"
+        )))
+    }
+}
+
+/// Returns a union of the given types. Avro does not allow unions directly inside unions,
+/// so members that are unions themselves, e.g. polymorphic types or nested options, are flattened into it,
+/// keeping a single `"null"`.
+fn union(members: Vec<Json>) -> Json {
+    let mut flattened = Vec::new();
+    for member in members {
+        let nested = match member {
+            Json::Array(nested) => nested,
+            member => vec![member],
+        };
+        for member in nested {
+            let is_null = |m: &Json| matches!(m, Json::String(s) if s == "null");
+            if !(is_null(&member) && flattened.iter().any(is_null)) {
+                flattened.push(member);
+            }
+        }
+    }
+    Json::Array(flattened)
+}
+
+/// Avro names must start with a letter or underscore and contain only letters, digits and underscores.
+fn check_name(name: &str) -> Result<(), Error> {
+    let mut chars = name.chars();
+    let valid_start = chars
+        .next()
+        .map(|c| c.is_ascii_alphabetic() || c == '_')
+        .unwrap_or(false);
+    if valid_start && chars.all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        Ok(())
+    } else {
+        Err(Error::new(&format!("`{name}` is not a valid Avro name")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as rdc;
+    use crate::ir::TypeTarget;
+    use crate::{rdc_polymorphic, RDC};
+    use std::collections::HashMap;
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct OrderPlaced {
+        order_id: String,
+        lines: Vec<OrderLine>,
+        attributes: HashMap<String, i64>,
+        note: Option<String>,
+        status: Status,
+        previous_status: Option<Status>,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct OrderLine {
+        #[serde(rename = "sku")]
+        product: String,
+        quantity: u64,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Status {
+        New,
+        Paid,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Payment {
+        Card(String),
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Invoice {
+        payment: Payment,
+    }
+
+    trait Notification {}
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Email {
+        address: String,
+    }
+    impl Notification for Email {}
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Sms {
+        phone: String,
+    }
+    impl Notification for Sms {}
+
+    rdc_polymorphic!(dyn Notification, tag = "type" => Email, Sms);
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Customer {
+        notification: Option<Box<dyn Notification>>,
+        nickname: Option<Option<String>>,
+    }
+
+    fn order_schemas() -> Vec<AvroSchema> {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<OrderPlaced>();
+        generate_avro_schemas(&ir, "com.example.events").unwrap()
+    }
+
+    #[test]
+    fn generate_record_schema() {
+        let schemas = order_schemas();
+        let order = schemas.iter().find(|s| s.name() == "OrderPlaced").unwrap();
+        let schema = order.schema();
+        println!("{schema}");
+        assert!(schema.contains("\"type\": \"record\""));
+        assert!(schema.contains("\"namespace\": \"com.example.events\""));
+        assert!(schema.contains("\"name\": \"sku\""));
+        assert!(schema.contains("\"logicalType\": \"decimal\""));
+        assert!(schema.contains("\"type\": \"map\",\n"));
+        assert!(schema.contains("\"default\": null"));
+        assert!(schema.contains("\"symbols\": [\n"));
+        // Status is defined once and referenced by its full name afterwards.
+        assert_eq!(schema.matches("\"type\": \"enum\"").count(), 1);
+        assert!(schema.contains("\"com.example.events.Status\""));
+    }
+
+    #[test]
+    fn subjects() {
+        let schemas = order_schemas();
+        let order = schemas.iter().find(|s| s.name() == "OrderPlaced").unwrap();
+        let topic = "orders".to_string();
+        assert_eq!(
+            order.subject(&SubjectNameStrategy::TopicName {
+                topic: topic.clone()
+            }),
+            "orders-value"
+        );
+        assert_eq!(
+            order.subject(&SubjectNameStrategy::RecordName),
+            "com.example.events.OrderPlaced"
+        );
+        assert_eq!(
+            order.subject(&SubjectNameStrategy::TopicRecordName { topic }),
+            "orders-com.example.events.OrderPlaced"
+        );
+
+        let class =
+            generate_java_subjects(&schemas, &SubjectNameStrategy::RecordName, "EventSubjects")
+                .unwrap();
+        assert!(class.code().contains(
+            "public static final String ORDER_PLACED = \"com.example.events.OrderPlaced\";"
+        ));
+    }
+
+    #[test]
+    fn write_schemas() {
+        let schemas = order_schemas();
+        let directory = "target/test-tmp/avro";
+        write_avro_schemas(&schemas, &SubjectNameStrategy::RecordName, directory).unwrap();
+        assert!(std::path::Path::new(directory)
+            .join("com.example.events.OrderPlaced.avsc")
+            .exists());
+    }
+
+    #[test]
+    fn nested_unions_are_flattened() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Customer>();
+        let schemas = generate_avro_schemas(&ir, "com.example").unwrap();
+        let customer = schemas.iter().find(|s| s.name() == "Customer").unwrap();
+        let schema = customer.schema();
+        assert!(!schema.contains("[\n        [\n"));
+        assert!(schema.contains("\"type\": [\n        \"null\",\n        {\n          \"type\": \"record\",\n          \"name\": \"Email\""));
+        assert!(schema.contains("\"name\": \"Sms\""));
+        assert!(schema.contains("\"type\": [\n        \"null\",\n        \"string\"\n      ]"));
+    }
+
+    #[test]
+    fn data_enums_are_not_supported() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Invoice>();
+        let error = generate_avro_schemas(&ir, "com.example").err().unwrap();
+        assert_eq!(error.message(), "`Payment` can not be represented in Avro");
+    }
+}
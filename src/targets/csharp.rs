@@ -0,0 +1,890 @@
+use crate::errors::Error;
+use crate::ir::{
+    DataEnum, DataEnumVariant, Enum, FlagsFormat, IntermediateRepresentation, Polymorphic, Struct,
+    Type, TypeKind,
+};
+use crate::targets::json::Json;
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+
+const USINGS: &[&str] = &[
+    "using System;",
+    "using System.Collections.Generic;",
+    "using System.Text.Json;",
+    "using System.Text.Json.Serialization;",
+];
+
+/// This is a struct that represents a generated C# source file with a single type and its converter.
+pub struct CSharpFile {
+    name: String,
+    code: String,
+}
+
+impl CSharpFile {
+    pub fn new(name: String, code: String) -> Self {
+        CSharpFile { name, code }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+}
+
+/// This function generates C# types serialized with System.Text.Json to the JSON of the types in the IR,
+/// one file per type in the given namespace.
+///
+/// Structs are translated to sealed records with `init` properties named in Pascal case and annotated with
+/// `[JsonPropertyName]`. Properties that are not an `Option` are `required`, so missing ones fail deserialization.
+/// Unit enums become C# enums with a generated `JsonConverter` reading and writing their JSON names,
+/// also as dictionary keys. Data enums become abstract records with a nested record per variant and a converter
+/// matching serde's external tagging, e.g. `"Point"` or `{"Circle": {...}}`. Polymorphic types become interfaces
+/// implemented by their variants, with a converter handling the tag or the wrapping object.
+/// The code requires C# 11 and .NET 7 or newer, and enables nullable reference types.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::targets::csharp::generate_csharp_code;
+/// use rdc::RDC;
+///
+/// #[derive(RDC)]
+/// struct User {
+///     name: String,
+///     nickname: Option<String>,
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add::<User>();
+/// let files = generate_csharp_code(&ir, "Example.Api").unwrap();
+/// assert!(files[0].code().contains(
+///     "    [JsonPropertyName(\"name\")]\n    public required string Name { get; init; }\n"
+/// ));
+/// ```
+pub fn generate_csharp_code(
+    ir: &IntermediateRepresentation,
+    namespace: &str,
+) -> Result<Vec<CSharpFile>, Error> {
+    ir.validate()?;
+    ir.reject_transparent_structs("C#")?;
+    let generator = CSharpGenerator::new(ir, namespace);
+    let mut files = Vec::new();
+    for s in ir.structs() {
+        files.push(generator.generate_record(s)?);
+    }
+    for e in ir.enums().iter().filter(|e| e.flags().is_none()) {
+        files.push(generator.generate_enum(e));
+    }
+    for de in ir.data_enums() {
+        files.push(generator.generate_data_enum(de)?);
+    }
+    for p in ir.polymorphics() {
+        files.push(generator.generate_polymorphic(p)?);
+    }
+    Ok(files)
+}
+
+/// This function writes generated C# files to the provided directory, e.g. `src/Api/Models`.
+pub fn write_csharp(files: &[CSharpFile], directory: &str) -> Result<(), Error> {
+    std::fs::create_dir_all(directory).map_err(|_| Error::new("Failed to create directory"))?;
+    for cs_file in files {
+        let mut file_path = std::path::PathBuf::from(directory);
+        file_path.push(format!("{}.cs", cs_file.name()));
+        let mut file =
+            std::fs::File::create(file_path).map_err(|_| Error::new("Failed to create file"))?;
+        file.write_all(cs_file.code().as_bytes())
+            .map_err(|_| Error::new("Failed to write to file"))?;
+    }
+    Ok(())
+}
+
+struct CSharpGenerator {
+    namespace: String,
+    types: HashSet<String>,
+    flags: HashMap<String, FlagsFormat>,
+    unit_enums: HashSet<String>,
+    /// Polymorphic types implemented by every generated type.
+    interfaces: HashMap<String, Vec<String>>,
+}
+
+impl CSharpGenerator {
+    fn new(ir: &IntermediateRepresentation, namespace: &str) -> Self {
+        let types = ir
+            .structs()
+            .iter()
+            .map(|s| s.self_type())
+            .chain(ir.enums().iter().map(|e| e.self_type()))
+            .chain(ir.data_enums().iter().map(|de| de.self_type()))
+            .chain(ir.polymorphics().iter().map(|p| p.self_type()))
+            .map(|t| t.type_name().clone())
+            .collect();
+        let flags = ir
+            .enums()
+            .iter()
+            .filter_map(|e| e.flags().map(|f| (e.self_type().type_name().clone(), f)))
+            .collect();
+        let unit_enums = ir
+            .enums()
+            .iter()
+            .filter(|e| e.flags().is_none())
+            .map(|e| e.self_type().type_name().clone())
+            .collect();
+        let mut interfaces: HashMap<String, Vec<String>> = HashMap::new();
+        for p in ir.polymorphics() {
+            for v in p.variants() {
+                interfaces
+                    .entry(v.variant_type().type_name().clone())
+                    .or_default()
+                    .push(p.self_type().type_name().clone());
+            }
+        }
+        Self {
+            namespace: namespace.to_string(),
+            types,
+            flags,
+            unit_enums,
+            interfaces,
+        }
+    }
+
+    fn generate_record(&self, s: &Struct) -> Result<CSharpFile, Error> {
+        let name = s.self_type().type_name();
+        let properties = s
+            .fields()
+            .iter()
+            .map(|f| member_name(&f.name().as_pascal_case(), name))
+            .collect::<Vec<String>>();
+        let no_members = HashSet::new();
+        let mut lines = vec![
+            format!("public sealed record {name}{}", self.base_list(name)),
+            "{".to_string(),
+        ];
+        for (i, (field, property)) in s.fields().iter().zip(&properties).enumerate() {
+            if i > 0 {
+                lines.push(String::new());
+            }
+            let required = if field.is_required() { "required " } else { "" };
+            let property_type = self.type_name(field.field_type(), &no_members)?;
+            lines.push(format!(
+                "    [JsonPropertyName({})]",
+                Json::string(field.json_name()).render()
+            ));
+            lines.push(format!(
+                "    public {required}{property_type} {property} {{ get; init; }}"
+            ));
+        }
+        lines.push("}".to_string());
+        Ok(self.render_file(name, lines))
+    }
+
+    fn generate_enum(&self, e: &Enum) -> CSharpFile {
+        let name = e.self_type().type_name();
+        let members = e
+            .variants()
+            .iter()
+            .map(|v| {
+                (
+                    v.name().as_pascal_case(),
+                    Json::string(v.json_name()).render(),
+                )
+            })
+            .collect::<Vec<(String, String)>>();
+        let mut lines = vec![
+            format!("[JsonConverter(typeof({name}JsonConverter))]"),
+            format!("public enum {name}"),
+            "{".to_string(),
+        ];
+        for (member, _) in &members {
+            lines.push(format!("    {member},"));
+        }
+        lines.push("}".to_string());
+        lines.push(String::new());
+        lines.push(format!(
+            "public sealed class {name}JsonConverter : JsonConverter<{name}>"
+        ));
+        lines.push("{".to_string());
+        lines.push(format!(
+            "    public override {name} Read(ref Utf8JsonReader reader, Type typeToConvert, JsonSerializerOptions options) =>"
+        ));
+        lines.push("        Parse(reader.GetString());".to_string());
+        lines.push(String::new());
+        lines.push(format!(
+            "    public override void Write(Utf8JsonWriter writer, {name} value, JsonSerializerOptions options) =>"
+        ));
+        lines.push("        writer.WriteStringValue(Format(value));".to_string());
+        lines.push(String::new());
+        lines.push(format!(
+            "    public override {name} ReadAsPropertyName(ref Utf8JsonReader reader, Type typeToConvert, JsonSerializerOptions options) =>"
+        ));
+        lines.push("        Parse(reader.GetString());".to_string());
+        lines.push(String::new());
+        lines.push(format!(
+            "    public override void WriteAsPropertyName(Utf8JsonWriter writer, {name} value, JsonSerializerOptions options) =>"
+        ));
+        lines.push("        writer.WritePropertyName(Format(value));".to_string());
+        lines.push(String::new());
+        lines.push(format!(
+            "    private static {name} Parse(string? value) => value switch"
+        ));
+        lines.push("    {".to_string());
+        for (member, json_name) in &members {
+            lines.push(format!("        {json_name} => {name}.{member},"));
+        }
+        lines.push(format!(
+            "        _ => throw new JsonException($\"Unknown value of {name}: {{value}}\"),"
+        ));
+        lines.push("    };".to_string());
+        lines.push(String::new());
+        lines.push(format!(
+            "    private static string Format({name} value) => value switch"
+        ));
+        lines.push("    {".to_string());
+        for (member, json_name) in &members {
+            lines.push(format!("        {name}.{member} => {json_name},"));
+        }
+        lines.push(format!(
+            "        _ => throw new JsonException($\"Unknown value of {name}: {{value}}\"),"
+        ));
+        lines.push("    };".to_string());
+        lines.push("}".to_string());
+        self.render_file(name, lines)
+    }
+
+    fn generate_data_enum(&self, de: &DataEnum) -> Result<CSharpFile, Error> {
+        let name = de.self_type().type_name();
+        let variants = de
+            .variants()
+            .iter()
+            .map(|v| CSharpVariant::new(v, name))
+            .collect::<Vec<CSharpVariant>>();
+        // Nested records hide types of the same name, so these are referred to by full names.
+        let members = variants
+            .iter()
+            .map(|v| v.name.clone())
+            .collect::<HashSet<String>>();
+
+        let mut lines = vec![
+            format!("[JsonConverter(typeof({name}JsonConverter))]"),
+            format!("public abstract record {name}{}", self.base_list(name)),
+            "{".to_string(),
+            format!("    private {name}()"),
+            "    {".to_string(),
+            "    }".to_string(),
+        ];
+        for v in &variants {
+            lines.push(String::new());
+            let parameters = v
+                .parameters
+                .iter()
+                .map(|p| {
+                    Ok(format!(
+                        "{} {}",
+                        self.type_name(p.field_type, &members)?,
+                        p.name
+                    ))
+                })
+                .collect::<Result<Vec<String>, Error>>()?;
+            lines.push(match v.shape {
+                VariantShape::Unit => format!("    public sealed record {} : {name};", v.name),
+                _ => format!(
+                    "    public sealed record {}({}) : {name};",
+                    v.name,
+                    parameters.join(", ")
+                ),
+            });
+        }
+        lines.push("}".to_string());
+        lines.push(String::new());
+
+        lines.push(format!(
+            "public sealed class {name}JsonConverter : JsonConverter<{name}>"
+        ));
+        lines.push("{".to_string());
+        lines.push(format!(
+            "    public override {name} Read(ref Utf8JsonReader reader, Type typeToConvert, JsonSerializerOptions options)"
+        ));
+        lines.push("    {".to_string());
+        lines.push("        using var document = JsonDocument.ParseValue(ref reader);".to_string());
+        lines.push("        var root = document.RootElement;".to_string());
+        lines.push("        if (root.ValueKind == JsonValueKind.String)".to_string());
+        lines.push("        {".to_string());
+        lines.push("            return root.GetString() switch".to_string());
+        lines.push("            {".to_string());
+        for v in variants.iter().filter(|v| v.shape == VariantShape::Unit) {
+            lines.push(format!(
+                "                {} => new {name}.{}(),",
+                v.json_name, v.name
+            ));
+        }
+        lines.push(format!(
+            "                var other => throw new JsonException($\"Unknown variant of {name}: {{other}}\"),"
+        ));
+        lines.push("            };".to_string());
+        lines.push("        }".to_string());
+        lines.extend(single_property(name));
+        lines.push("        var value = variant.Value;".to_string());
+        lines.push("        switch (variant.Name)".to_string());
+        lines.push("        {".to_string());
+        let mut optional_fields = false;
+        for v in variants.iter().filter(|v| v.shape != VariantShape::Unit) {
+            let arguments = v
+                .parameters
+                .iter()
+                .map(|p| {
+                    let field_type = self.type_name(p.field_type, &members)?;
+                    Ok(match (&v.shape, p.json_name.as_deref()) {
+                        (VariantShape::Tuple, _) if v.parameters.len() == 1 => {
+                            self.deserialize("value", &field_type, p.field_type)
+                        }
+                        (VariantShape::Tuple, _) => self.deserialize(
+                            &format!("value[{}]", p.index),
+                            &field_type,
+                            p.field_type,
+                        ),
+                        (_, Some(json_name)) if !is_nullable(p.field_type) => self.deserialize(
+                            &format!("value.GetProperty({json_name})"),
+                            &field_type,
+                            p.field_type,
+                        ),
+                        (_, json_name) => {
+                            optional_fields = true;
+                            format!(
+                                "Optional<{field_type}>(value, {}, options)",
+                                json_name.unwrap_or_default()
+                            )
+                        }
+                    })
+                })
+                .collect::<Result<Vec<String>, Error>>()?;
+            lines.push(format!("            case {}:", v.json_name));
+            lines.push(format!(
+                "                return new {name}.{}({});",
+                v.name,
+                arguments.join(", ")
+            ));
+        }
+        lines.push("            default:".to_string());
+        lines.push(format!(
+            "                throw new JsonException($\"Unknown variant of {name}: {{variant.Name}}\");"
+        ));
+        lines.push("        }".to_string());
+        lines.push("    }".to_string());
+        lines.push(String::new());
+        lines.push(format!(
+            "    public override void Write(Utf8JsonWriter writer, {name} value, JsonSerializerOptions options)"
+        ));
+        lines.push("    {".to_string());
+        lines.push("        switch (value)".to_string());
+        lines.push("        {".to_string());
+        for v in &variants {
+            match v.shape {
+                VariantShape::Unit => {
+                    lines.push(format!("            case {name}.{}:", v.name));
+                    lines.push(format!(
+                        "                writer.WriteStringValue({});",
+                        v.json_name
+                    ));
+                }
+                _ => {
+                    lines.push(format!("            case {name}.{} variant:", v.name));
+                    lines.push("                writer.WriteStartObject();".to_string());
+                    lines.push(format!(
+                        "                writer.WritePropertyName({});",
+                        v.json_name
+                    ));
+                    let many = v.shape == VariantShape::Tuple && v.parameters.len() > 1;
+                    if many {
+                        lines.push("                writer.WriteStartArray();".to_string());
+                    } else if v.shape == VariantShape::Object {
+                        lines.push("                writer.WriteStartObject();".to_string());
+                    }
+                    for p in &v.parameters {
+                        if let Some(json_name) = &p.json_name {
+                            lines.push(format!(
+                                "                writer.WritePropertyName({json_name});"
+                            ));
+                        }
+                        lines.push(format!(
+                            "                JsonSerializer.Serialize(writer, variant.{}, options);",
+                            p.name
+                        ));
+                    }
+                    if many {
+                        lines.push("                writer.WriteEndArray();".to_string());
+                    } else if v.shape == VariantShape::Object {
+                        lines.push("                writer.WriteEndObject();".to_string());
+                    }
+                    lines.push("                writer.WriteEndObject();".to_string());
+                }
+            }
+            lines.push("                break;".to_string());
+        }
+        lines.push("            default:".to_string());
+        lines.push(format!(
+            "                throw new JsonException($\"Unknown variant of {name}: {{value.GetType()}}\");"
+        ));
+        lines.push("        }".to_string());
+        lines.push("    }".to_string());
+        if optional_fields {
+            lines.push(String::new());
+            lines.push("    private static T? Optional<T>(JsonElement value, string name, JsonSerializerOptions options) =>".to_string());
+            lines.push("        value.TryGetProperty(name, out var property) ? property.Deserialize<T>(options) : default;".to_string());
+        }
+        lines.push("}".to_string());
+        Ok(self.render_file(name, lines))
+    }
+
+    fn generate_polymorphic(&self, p: &Polymorphic) -> Result<CSharpFile, Error> {
+        let name = p.self_type().type_name();
+        let no_members = HashSet::new();
+        let mut variants = Vec::new();
+        for v in p.variants() {
+            let variant_type = v.variant_type().type_name();
+            if !self.types.contains(variant_type) || self.flags.contains_key(variant_type) {
+                return Err(Error::new(&format!(
+                    "`{variant_type}` is a variant of `{name}`, but only generated records can implement C# interfaces"
+                )));
+            }
+            variants.push((
+                Json::string(v.json_name()).render(),
+                self.type_name(v.variant_type(), &no_members)?,
+            ));
+        }
+        let mut lines = vec![
+            format!("[JsonConverter(typeof({name}JsonConverter))]"),
+            format!("public interface {name}{}", self.base_list(name)),
+            "{".to_string(),
+            "}".to_string(),
+            String::new(),
+            format!("public sealed class {name}JsonConverter : JsonConverter<{name}>"),
+            "{".to_string(),
+            format!(
+                "    public override {name} Read(ref Utf8JsonReader reader, Type typeToConvert, JsonSerializerOptions options)"
+            ),
+            "    {".to_string(),
+            "        using var document = JsonDocument.ParseValue(ref reader);".to_string(),
+            "        var root = document.RootElement;".to_string(),
+        ];
+        let (selector, value) = match p.tag() {
+            Some(tag) => {
+                let tag = Json::string(tag).render();
+                lines.push(format!(
+                    "        if (root.ValueKind != JsonValueKind.Object || !root.TryGetProperty({tag}, out var tag))"
+                ));
+                lines.push("        {".to_string());
+                lines.push(format!(
+                    "            throw new JsonException({});",
+                    Json::string(format!("{name} must be an object with a {tag} property"))
+                        .render()
+                ));
+                lines.push("        }".to_string());
+                ("tag.GetString()", "root")
+            }
+            None => {
+                lines.extend(single_property(name));
+                ("variant.Name", "variant.Value")
+            }
+        };
+        lines.push(format!("        return {selector} switch"));
+        lines.push("        {".to_string());
+        for (json_name, variant_type) in &variants {
+            lines.push(format!(
+                "            {json_name} => {value}.Deserialize<{variant_type}>(options)!,"
+            ));
+        }
+        lines.push(format!(
+            "            var other => throw new JsonException($\"Unknown variant of {name}: {{other}}\"),"
+        ));
+        lines.push("        };".to_string());
+        lines.push("    }".to_string());
+        lines.push(String::new());
+        lines.push(format!(
+            "    public override void Write(Utf8JsonWriter writer, {name} value, JsonSerializerOptions options)"
+        ));
+        lines.push("    {".to_string());
+        lines.push("        string name = value switch".to_string());
+        lines.push("        {".to_string());
+        for (json_name, variant_type) in &variants {
+            lines.push(format!("            {variant_type} => {json_name},"));
+        }
+        lines.push(format!(
+            "            _ => throw new JsonException($\"Unknown variant of {name}: {{value.GetType()}}\"),"
+        ));
+        lines.push("        };".to_string());
+        lines.push("        writer.WriteStartObject();".to_string());
+        match p.tag() {
+            Some(tag) => {
+                lines.push(format!(
+                    "        writer.WriteString({}, name);",
+                    Json::string(tag).render()
+                ));
+                lines.push("        foreach (var property in JsonSerializer.SerializeToElement(value, value.GetType(), options).EnumerateObject())".to_string());
+                lines.push("        {".to_string());
+                lines.push("            property.WriteTo(writer);".to_string());
+                lines.push("        }".to_string());
+            }
+            None => {
+                lines.push("        writer.WritePropertyName(name);".to_string());
+                lines.push(
+                    "        JsonSerializer.Serialize(writer, value, value.GetType(), options);"
+                        .to_string(),
+                );
+            }
+        }
+        lines.push("        writer.WriteEndObject();".to_string());
+        lines.push("    }".to_string());
+        lines.push("}".to_string());
+        Ok(self.render_file(name, lines))
+    }
+
+    /// Returns the base list of a type implementing polymorphic types, e.g. ` : Pet`.
+    fn base_list(&self, name: &str) -> String {
+        match self.interfaces.get(name) {
+            Some(interfaces) => format!(" : {}", interfaces.join(", ")),
+            None => String::new(),
+        }
+    }
+
+    /// Returns the C# type of a value. Generated types named like one of the given nested types
+    /// are referred to by their full names, because nested types hide them.
+    fn type_name(&self, t: &Type, members: &HashSet<String>) -> Result<String, Error> {
+        match t.kind() {
+            TypeKind::List(element) => Ok(format!("List<{}>", self.type_name(element, members)?)),
+            TypeKind::Map(key, value) => Ok(format!(
+                "Dictionary<{}, {}>",
+                self.type_name(key, members)?,
+                self.type_name(value, members)?
+            )),
+            TypeKind::Option(inner) => Ok(format!("{}?", self.type_name(inner, members)?)),
+            TypeKind::UnsignedLong => Ok("ulong".to_string()),
+            TypeKind::Named => match t.type_name().as_str() {
+                "Byte" => Ok("sbyte".to_string()),
+                "Short" => Ok("short".to_string()),
+                "Integer" => Ok("int".to_string()),
+                "Long" => Ok("long".to_string()),
+                "Float" => Ok("float".to_string()),
+                "Double" => Ok("double".to_string()),
+                "Boolean" => Ok("bool".to_string()),
+                "String" => Ok("string".to_string()),
+                "com.fasterxml.jackson.databind.JsonNode" => Ok("JsonElement".to_string()),
+                name => match self.flags.get(name) {
+                    Some(FlagsFormat::Bits) => Ok("ulong".to_string()),
+                    Some(FlagsFormat::Names) => Ok("string".to_string()),
+                    None if members.contains(name) => {
+                        Ok(format!("global::{}.{name}", self.namespace))
+                    }
+                    None if self.types.contains(name) => Ok(name.to_string()),
+                    None => Err(Error::new(&format!("`{name}` has no C# counterpart"))),
+                },
+            },
+        }
+    }
+
+    /// Returns an expression deserializing a JSON element, asserting that values of non-nullable reference types
+    /// are not `null`.
+    fn deserialize(&self, element: &str, type_name: &str, t: &Type) -> String {
+        let suffix = match is_nullable(t) || self.is_value_type(t) {
+            true => "",
+            false => "!",
+        };
+        format!("{element}.Deserialize<{type_name}>(options){suffix}")
+    }
+
+    fn is_value_type(&self, t: &Type) -> bool {
+        match t.kind() {
+            TypeKind::UnsignedLong => true,
+            TypeKind::Named => match t.type_name().as_str() {
+                "Byte"
+                | "Short"
+                | "Integer"
+                | "Long"
+                | "Float"
+                | "Double"
+                | "Boolean"
+                | "com.fasterxml.jackson.databind.JsonNode" => true,
+                name => {
+                    self.flags.get(name) == Some(&FlagsFormat::Bits)
+                        || self.unit_enums.contains(name)
+                }
+            },
+            _ => false,
+        }
+    }
+
+    /// This function renders the usings, the namespace and the declarations of a file.
+    fn render_file(&self, name: &str, declaration: Vec<String>) -> CSharpFile {
+        let mut code = String::from("#nullable enable\n\n");
+        for using in USINGS {
+            code.push_str(using);
+            code.push('\n');
+        }
+        code.push_str(&format!("\nnamespace {};\n\n", self.namespace));
+        for line in declaration {
+            code.push_str(&line);
+            code.push('\n');
+        }
+        CSharpFile::new(name.to_string(), code)
+    }
+}
+
+#[derive(PartialEq)]
+enum VariantShape {
+    Unit,
+    Tuple,
+    Object,
+}
+
+/// A variant of a data enum, generated as a nested positional record.
+struct CSharpVariant<'a> {
+    name: String,
+    json_name: String,
+    shape: VariantShape,
+    parameters: Vec<Parameter<'a>>,
+}
+
+/// A parameter of a positional record. Object fields have a JSON name, tuple fields an index.
+struct Parameter<'a> {
+    name: String,
+    json_name: Option<String>,
+    index: usize,
+    field_type: &'a Type,
+}
+
+impl<'a> CSharpVariant<'a> {
+    fn new(variant: &'a DataEnumVariant, owner: &str) -> Self {
+        let name = member_name(&variant.name().as_pascal_case(), owner);
+        let json_name = Json::string(variant.json_name()).render();
+        let (shape, parameters) = match variant {
+            DataEnumVariant::Unit { .. } => (VariantShape::Unit, Vec::new()),
+            DataEnumVariant::Tuple { fields, .. } => {
+                let parameters = fields
+                    .iter()
+                    .enumerate()
+                    .map(|(index, field_type)| Parameter {
+                        name: match fields.len() {
+                            1 => member_name("Value", &name),
+                            _ => format!("Item{}", index + 1),
+                        },
+                        json_name: None,
+                        index,
+                        field_type,
+                    })
+                    .collect();
+                (VariantShape::Tuple, parameters)
+            }
+            DataEnumVariant::Object { fields, .. } => {
+                let parameters = fields
+                    .iter()
+                    .enumerate()
+                    .map(|(index, f)| Parameter {
+                        name: member_name(&f.name().as_pascal_case(), &name),
+                        json_name: Some(Json::string(f.json_name()).render()),
+                        index,
+                        field_type: f.field_type(),
+                    })
+                    .collect();
+                (VariantShape::Object, parameters)
+            }
+        };
+        Self {
+            name,
+            json_name,
+            shape,
+            parameters,
+        }
+    }
+}
+
+/// Returns the name of a member, suffixed with `Value` when it is the name of the enclosing type, which C# forbids.
+fn member_name(name: &str, owner: &str) -> String {
+    match name == owner {
+        true => format!("{name}Value"),
+        false => name.to_string(),
+    }
+}
+
+fn is_nullable(t: &Type) -> bool {
+    matches!(t.kind(), TypeKind::Option(_))
+}
+
+/// Returns statements reading the single property of an object wrapping a variant into `variant`.
+fn single_property(name: &str) -> Vec<String> {
+    let error = format!(
+        "throw new JsonException({});",
+        Json::string(format!("{name} must be an object with a single property")).render()
+    );
+    vec![
+        "        var properties = root.ValueKind == JsonValueKind.Object".to_string(),
+        "            ? root.EnumerateObject()".to_string(),
+        format!("            : {};", error.trim_end_matches(';')),
+        "        if (!properties.MoveNext())".to_string(),
+        "        {".to_string(),
+        format!("            {error}"),
+        "        }".to_string(),
+        "        var variant = properties.Current;".to_string(),
+        "        if (properties.MoveNext())".to_string(),
+        "        {".to_string(),
+        format!("            {error}"),
+        "        }".to_string(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as rdc;
+    use crate::ir::TypeTarget;
+    use crate::{rdc_polymorphic, RDC};
+    use std::collections::HashMap;
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct User {
+        #[serde(rename = "user-name")]
+        name: String,
+        nickname: Option<String>,
+        roles: Vec<Role>,
+        limits: HashMap<Role, Option<i64>>,
+        shape: Shape,
+        pet: Box<dyn Pet>,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Role {
+        Admin,
+        #[serde(rename = "member")]
+        Member,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Circle {
+        radius: f64,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Shape {
+        Point,
+        Circle(Circle),
+        Line(f64, Option<f64>),
+        Rect { width: f64, height: Option<f64> },
+    }
+
+    trait Pet {}
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Dog {
+        name: String,
+    }
+    impl Pet for Dog {}
+
+    rdc_polymorphic!(dyn Pet, tag = "kind" => Dog);
+
+    fn generate() -> Vec<CSharpFile> {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<User>();
+        generate_csharp_code(&ir, "Example.Api").unwrap()
+    }
+
+    #[test]
+    fn generate_records_and_enums() {
+        let files = generate();
+        let file = |name: &str| files.iter().find(|f| f.name() == name).unwrap().code();
+        assert_eq!(
+            file("User"),
+            "#nullable enable\n\
+             \n\
+             using System;\n\
+             using System.Collections.Generic;\n\
+             using System.Text.Json;\n\
+             using System.Text.Json.Serialization;\n\
+             \n\
+             namespace Example.Api;\n\
+             \n\
+             public sealed record User\n\
+             {\n    \
+             [JsonPropertyName(\"user-name\")]\n    \
+             public required string Name { get; init; }\n\
+             \n    \
+             [JsonPropertyName(\"nickname\")]\n    \
+             public string? Nickname { get; init; }\n\
+             \n    \
+             [JsonPropertyName(\"roles\")]\n    \
+             public required List<Role> Roles { get; init; }\n\
+             \n    \
+             [JsonPropertyName(\"limits\")]\n    \
+             public required Dictionary<Role, long?> Limits { get; init; }\n\
+             \n    \
+             [JsonPropertyName(\"shape\")]\n    \
+             public required Shape Shape { get; init; }\n\
+             \n    \
+             [JsonPropertyName(\"pet\")]\n    \
+             public required Pet Pet { get; init; }\n\
+             }\n"
+        );
+        let role = file("Role");
+        assert!(role.contains(
+            "[JsonConverter(typeof(RoleJsonConverter))]\npublic enum Role\n{\n    Admin,\n    Member,\n}\n"
+        ));
+        assert!(role.contains("        \"member\" => Role.Member,\n"));
+        assert!(role.contains("        Role.Member => \"member\",\n"));
+        assert!(role.contains("public override Role ReadAsPropertyName("));
+        assert!(file("Dog").contains("public sealed record Dog : Pet\n"));
+    }
+
+    #[test]
+    fn generate_data_enum_converter() {
+        let files = generate();
+        let code = files.iter().find(|f| f.name() == "Shape").unwrap().code();
+        assert!(code.contains(
+            "public abstract record Shape\n\
+             {\n    \
+             private Shape()\n    \
+             {\n    \
+             }\n\
+             \n    \
+             public sealed record Point : Shape;\n\
+             \n    \
+             public sealed record Circle(global::Example.Api.Circle Value) : Shape;\n\
+             \n    \
+             public sealed record Line(double Item1, double? Item2) : Shape;\n\
+             \n    \
+             public sealed record Rect(double Width, double? Height) : Shape;\n\
+             }\n"
+        ));
+        assert!(code.contains("                \"Point\" => new Shape.Point(),\n"));
+        assert!(code.contains(
+            "                return new Shape.Circle(value.Deserialize<global::Example.Api.Circle>(options)!);\n"
+        ));
+        assert!(code.contains(
+            "                return new Shape.Line(value[0].Deserialize<double>(options), value[1].Deserialize<double?>(options));\n"
+        ));
+        assert!(code.contains(
+            "                return new Shape.Rect(value.GetProperty(\"width\").Deserialize<double>(options), Optional<double?>(value, \"height\", options));\n"
+        ));
+        assert!(code.contains(
+            "                writer.WriteStartArray();\n                \
+             JsonSerializer.Serialize(writer, variant.Item1, options);\n                \
+             JsonSerializer.Serialize(writer, variant.Item2, options);\n                \
+             writer.WriteEndArray();\n"
+        ));
+    }
+
+    #[test]
+    fn generate_polymorphic_converter() {
+        let files = generate();
+        let code = files.iter().find(|f| f.name() == "Pet").unwrap().code();
+        assert!(code
+            .contains("[JsonConverter(typeof(PetJsonConverter))]\npublic interface Pet\n{\n}\n"));
+        assert!(code.contains("!root.TryGetProperty(\"kind\", out var tag)"));
+        assert!(code.contains("            \"Dog\" => root.Deserialize<Dog>(options)!,\n"));
+        assert!(code.contains("            Dog => \"Dog\",\n"));
+        assert!(code.contains("        writer.WriteString(\"kind\", name);\n"));
+    }
+}
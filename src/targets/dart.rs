@@ -0,0 +1,873 @@
+use crate::errors::Error;
+use crate::ir::{
+    DataEnum, DataEnumVariant, Enum, FlagsFormat, IntermediateRepresentation, Name, Polymorphic,
+    Struct, Type, TypeKind,
+};
+use crate::targets::utils::{indent, tuple_names, variant_class};
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+
+/// Reserved words that have to be escaped with a trailing underscore when used as Dart identifiers.
+const DART_KEYWORDS: &[&str] = &[
+    "assert", "break", "case", "catch", "class", "const", "continue", "default", "do", "else",
+    "enum", "extends", "false", "final", "finally", "for", "if", "in", "is", "new", "null",
+    "rethrow", "return", "super", "switch", "this", "throw", "true", "try", "var", "void", "while",
+    "with",
+];
+
+/// Members of the generated enums, which can not be used as names of their values.
+const ENUM_MEMBERS: &[&str] = &["index", "jsonName", "name", "values"];
+
+/// This enum describes how the generated Dart classes are converted to and from JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DartFlavor {
+    /// Classes get handwritten `fromJson` factories and `toJson` methods, so no code generation is needed in Dart.
+    #[default]
+    Manual,
+    /// Structs are annotated with `@JsonSerializable` and delegate to the code generated by `json_serializable`
+    /// into the `.g.dart` part of the file, enums are annotated with `@JsonEnum`.
+    JsonSerializable,
+}
+
+/// This function generates a Dart library with classes for the JSON of the types in the IR, e.g. for Flutter clients.
+/// The file name, e.g. `models.dart`, names the `part` generated by `json_serializable`.
+///
+/// Structs are translated to immutable classes with final fields in camel case, where optional fields
+/// are nullable and the others `required` constructor parameters. Unit enums become enhanced enums holding
+/// their JSON names. Data enums and polymorphic types become sealed classes with a subclass per variant,
+/// e.g. `ShapeCircle`, and handwritten `fromJson` and `toJson` following serde's tagging in both flavors,
+/// since `json_serializable` does not support it. The code requires Dart 3.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::targets::dart::{generate_dart_code, DartFlavor};
+/// use rdc::RDC;
+///
+/// #[derive(RDC)]
+/// struct User {
+///     name: String,
+///     nickname: Option<String>,
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add::<User>();
+/// let code = generate_dart_code(&ir, DartFlavor::Manual, "models.dart").unwrap();
+/// assert!(code.contains("  final String name;\n  final String? nickname;\n"));
+/// assert!(code.contains("    nickname: json['nickname'] as String?,\n"));
+/// ```
+pub fn generate_dart_code(
+    ir: &IntermediateRepresentation,
+    flavor: DartFlavor,
+    file_name: &str,
+) -> Result<String, Error> {
+    ir.validate()?;
+    ir.reject_transparent_structs("Dart")?;
+    let generator = DartGenerator::new(ir, flavor);
+    let mut definitions = Vec::new();
+    for s in ir.structs() {
+        definitions.push(generator.generate_class(s)?);
+    }
+    for e in ir.enums() {
+        definitions.push(generator.generate_enum(e)?);
+    }
+    for de in ir.data_enums() {
+        definitions.push(generator.generate_data_enum(de)?);
+    }
+    for p in ir.polymorphics() {
+        definitions.push(generator.generate_polymorphic(p)?);
+    }
+
+    let mut code = "// GENERATED CODE - DO NOT MODIFY BY HAND\n".to_string();
+    if flavor == DartFlavor::JsonSerializable {
+        let part = file_name.strip_suffix(".dart").unwrap_or(file_name);
+        code.push_str("\nimport 'package:json_annotation/json_annotation.dart';\n");
+        code.push_str(&format!(
+            "\npart {};\n",
+            string_literal(&format!("{part}.g.dart"))
+        ));
+    }
+    for definition in definitions {
+        code.push('\n');
+        for line in definition {
+            code.push_str(&line);
+            code.push('\n');
+        }
+    }
+    Ok(code)
+}
+
+/// This function generates a Dart library and writes it to the given path, e.g. `lib/api/models.dart`.
+pub fn write_dart_code(
+    ir: &IntermediateRepresentation,
+    flavor: DartFlavor,
+    file_path: &str,
+) -> Result<(), Error> {
+    let path = std::path::Path::new(file_path);
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| Error::new(&format!("`{file_path}` is not a path of a file")))?;
+    let code = generate_dart_code(ir, flavor, file_name)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|_| Error::new("Failed to create directory"))?;
+    }
+    let mut file =
+        std::fs::File::create(file_path).map_err(|_| Error::new("Failed to create file"))?;
+    file.write_all(code.as_bytes())
+        .map_err(|_| Error::new("Failed to write to file"))
+}
+
+/// This enum describes how values of a named type are converted to and from their JSON form.
+enum Conversion {
+    /// The JSON value is cast to the Dart type, e.g. `int` or `String`.
+    Cast(&'static str),
+    /// JSON numbers without a fraction are decoded as `int`, so they are converted with `toDouble()`.
+    Double,
+    /// Flags are declared as typedefs of `int` for bits or `String` for names, which the JSON value is cast to.
+    Flags(&'static str),
+    /// Arbitrary JSON is kept as it is.
+    Json,
+    /// Unit enums are read from their JSON names with `fromJson`.
+    Enum,
+    /// Structs are read from JSON objects with `fromJson`.
+    Class,
+    /// Sealed classes of data enums and polymorphic types are read from any JSON value with `fromJson`.
+    Union,
+}
+
+/// A field of a class: its Dart name, its JSON name and its type.
+type DartField<'a> = (String, &'a str, &'a Type);
+
+struct DartGenerator {
+    flavor: DartFlavor,
+    enums: HashSet<String>,
+    flags: HashMap<String, &'static str>,
+    classes: HashSet<String>,
+    unions: HashSet<String>,
+}
+
+impl DartGenerator {
+    fn new(ir: &IntermediateRepresentation, flavor: DartFlavor) -> Self {
+        let type_names = |types: Vec<&String>| types.into_iter().cloned().collect();
+        Self {
+            flavor,
+            enums: type_names(
+                ir.enums()
+                    .iter()
+                    .filter(|e| e.flags().is_none())
+                    .map(|e| e.self_type().type_name())
+                    .collect(),
+            ),
+            flags: ir
+                .enums()
+                .iter()
+                .filter_map(|e| {
+                    let type_name = match e.flags()? {
+                        FlagsFormat::Bits => "int",
+                        FlagsFormat::Names => "String",
+                    };
+                    Some((e.self_type().type_name().clone(), type_name))
+                })
+                .collect(),
+            classes: type_names(
+                ir.structs()
+                    .iter()
+                    .map(|s| s.self_type().type_name())
+                    .collect(),
+            ),
+            unions: type_names(
+                ir.data_enums()
+                    .iter()
+                    .map(|de| de.self_type().type_name())
+                    .chain(ir.polymorphics().iter().map(|p| p.self_type().type_name()))
+                    .collect(),
+            ),
+        }
+    }
+
+    fn generate_class(&self, s: &Struct) -> Result<Vec<String>, Error> {
+        let name = s.self_type().type_name();
+        let fields = s
+            .fields()
+            .iter()
+            .map(|f| (field_name(f.name()), f.json_name().as_str(), f.field_type()))
+            .collect::<Vec<DartField>>();
+        let mut lines = Vec::new();
+        let annotated = self.flavor == DartFlavor::JsonSerializable;
+        if annotated {
+            lines.push("@JsonSerializable(explicitToJson: true)".to_string());
+        }
+        lines.push(format!("class {name} {{"));
+        for (field, json_name, t) in &fields {
+            if annotated && field != json_name {
+                lines.push(format!("  @JsonKey(name: {})", string_literal(json_name)));
+            }
+            lines.push(format!("  final {} {field};", self.type_name(t)?));
+        }
+        if !fields.is_empty() {
+            lines.push(String::new());
+        }
+        lines.extend(named_constructor(name, &fields));
+        lines.push(String::new());
+        if annotated {
+            lines.push(format!(
+                "  factory {name}.fromJson(Map<String, dynamic> json) => _${name}FromJson(json);"
+            ));
+            lines.push(String::new());
+            lines.push(format!(
+                "  Map<String, dynamic> toJson() => _${name}ToJson(this);"
+            ));
+        } else {
+            let call = self.constructor_call(name, "json", &fields)?;
+            lines.push(format!(
+                "  factory {name}.fromJson(Map<String, dynamic> json) => {}",
+                call[0]
+            ));
+            lines.extend(indent(call[1..].to_vec(), 1));
+            let last = lines.pop().unwrap_or_default();
+            lines.push(format!("{last};"));
+            lines.push(String::new());
+            let literal = self.map_literal(&fields)?;
+            lines.push(format!("  Map<String, dynamic> toJson() => {}", literal[0]));
+            lines.extend(indent(literal[1..].to_vec(), 1));
+            let last = lines.pop().unwrap_or_default();
+            lines.push(format!("{last};"));
+        }
+        lines.push("}".to_string());
+        Ok(lines)
+    }
+
+    fn generate_enum(&self, e: &Enum) -> Result<Vec<String>, Error> {
+        let name = e.self_type().type_name();
+        match e.flags() {
+            Some(FlagsFormat::Bits) => return Ok(vec![format!("typedef {name} = int;")]),
+            Some(FlagsFormat::Names) => return Ok(vec![format!("typedef {name} = String;")]),
+            None => {}
+        }
+        if e.variants().is_empty() {
+            return Err(Error::new(&format!(
+                "`{name}` has no variants, but Dart enums need at least one"
+            )));
+        }
+        let mut lines = Vec::new();
+        if self.flavor == DartFlavor::JsonSerializable {
+            lines.push("@JsonEnum(valueField: 'jsonName')".to_string());
+        }
+        lines.push(format!("enum {name} {{"));
+        let last = e.variants().len() - 1;
+        for (i, v) in e.variants().iter().enumerate() {
+            lines.push(format!(
+                "  {}({}){}",
+                enum_value_name(v.name()),
+                string_literal(v.json_name()),
+                if i == last { ";" } else { "," }
+            ));
+        }
+        lines.extend([
+            String::new(),
+            format!("  const {name}(this.jsonName);"),
+            String::new(),
+            "  final String jsonName;".to_string(),
+            String::new(),
+            format!("  static {name} fromJson(String json) => values.firstWhere("),
+            "    (v) => v.jsonName == json,".to_string(),
+            format!(
+                "    orElse: () => throw ArgumentError.value(json, 'json', 'Unknown variant of {name}'),"
+            ),
+            "  );".to_string(),
+            String::new(),
+            "  String toJson() => jsonName;".to_string(),
+            "}".to_string(),
+        ]);
+        Ok(lines)
+    }
+
+    fn generate_data_enum(&self, de: &DataEnum) -> Result<Vec<String>, Error> {
+        let name = de.self_type().type_name();
+        let mut body = Vec::new();
+        for v in de.variants() {
+            if let DataEnumVariant::Unit { json_name, .. } = v {
+                body.push(format!("if (json == {}) {{", string_literal(json_name)));
+                body.push(format!("  return const {}();", variant_class(de, v)));
+                body.push("}".to_string());
+            }
+        }
+        let mut cases = Vec::new();
+        for v in de.variants() {
+            let class_name = variant_class(de, v);
+            let statement = match v {
+                DataEnumVariant::Unit { .. } => continue,
+                DataEnumVariant::Tuple { fields, .. } if fields.len() == 1 => {
+                    let value = self.decode("value", &fields[0], 0, false)?;
+                    vec![format!("return {class_name}({value});")]
+                }
+                DataEnumVariant::Tuple { fields, .. } => {
+                    let values = fields
+                        .iter()
+                        .enumerate()
+                        .map(|(i, t)| self.decode(&format!("value[{i}]"), t, 0, false))
+                        .collect::<Result<Vec<String>, Error>>()?;
+                    vec![format!("return {class_name}({});", values.join(", "))]
+                }
+                DataEnumVariant::Object { fields, .. } => {
+                    let fields = fields
+                        .iter()
+                        .map(|f| (field_name(f.name()), f.json_name(), f.field_type()))
+                        .collect::<Vec<DartField>>();
+                    let mut call = self.constructor_call(&class_name, "value", &fields)?;
+                    call[0] = format!("return {}", call[0]);
+                    let last = call.pop().unwrap_or_default();
+                    call.push(format!("{last};"));
+                    call
+                }
+            };
+            cases.push((string_literal(v.json_name()), statement));
+        }
+        body.extend(wrapped_cases(cases));
+        body.push(format!(
+            "throw ArgumentError.value(json, 'json', 'Unknown variant of {name}');"
+        ));
+        let mut lines = sealed_class(name, body);
+
+        for v in de.variants() {
+            let class_name = variant_class(de, v);
+            let key = string_literal(v.json_name());
+            lines.push(String::new());
+            lines.push(format!("class {class_name} extends {name} {{"));
+            let to_json = match v {
+                DataEnumVariant::Unit { .. } => {
+                    lines.push(format!("  const {class_name}();"));
+                    vec![format!("  Object? toJson() => {key};")]
+                }
+                DataEnumVariant::Tuple { fields, .. } => {
+                    let names = tuple_names(fields.len());
+                    let mut values = Vec::new();
+                    for (field, t) in names.iter().zip(fields) {
+                        lines.push(format!("  final {} {field};", self.type_name(t)?));
+                        values.push(self.encode(field, t, 0, false)?);
+                    }
+                    if !fields.is_empty() {
+                        lines.push(String::new());
+                    }
+                    let parameters = names
+                        .iter()
+                        .map(|field| format!("this.{field}"))
+                        .collect::<Vec<String>>();
+                    lines.push(format!("  const {class_name}({});", parameters.join(", ")));
+                    let value = match values.len() {
+                        1 => values.remove(0),
+                        _ => format!("[{}]", values.join(", ")),
+                    };
+                    vec![format!("  Object? toJson() => {{{key}: {value}}};")]
+                }
+                DataEnumVariant::Object { fields, .. } => {
+                    let fields = fields
+                        .iter()
+                        .map(|f| (field_name(f.name()), f.json_name(), f.field_type()))
+                        .collect::<Vec<DartField>>();
+                    for (field, _, t) in &fields {
+                        lines.push(format!("  final {} {field};", self.type_name(t)?));
+                    }
+                    if !fields.is_empty() {
+                        lines.push(String::new());
+                    }
+                    lines.extend(named_constructor(&class_name, &fields));
+                    let literal = self.map_literal(&fields)?;
+                    let mut to_json =
+                        vec![format!("  Object? toJson() => {{{key}: {}", literal[0])];
+                    to_json.extend(indent(literal[1..].to_vec(), 1));
+                    let last = to_json.pop().unwrap_or_default();
+                    to_json.push(format!("{last}}};"));
+                    to_json
+                }
+            };
+            lines.push(String::new());
+            lines.push("  @override".to_string());
+            lines.extend(to_json);
+            lines.push("}".to_string());
+        }
+        Ok(lines)
+    }
+
+    fn generate_polymorphic(&self, p: &Polymorphic) -> Result<Vec<String>, Error> {
+        let name = p.self_type().type_name();
+        let mut variants = Vec::new();
+        for v in p.variants() {
+            let variant_type = v.variant_type();
+            let class_name = format!("{name}{}", self.type_name(variant_type)?);
+            variants.push((class_name, string_literal(v.json_name()), variant_type));
+        }
+        let mut body = Vec::new();
+        match p.tag() {
+            Some(tag) => {
+                body.push("if (json is Map<String, dynamic>) {".to_string());
+                body.push(format!("  switch (json[{}]) {{", string_literal(tag)));
+                for (class_name, json_name, variant_type) in &variants {
+                    // `json` is already known to be a map, so structs are read without a cast.
+                    let value = match self.conversion(variant_type) {
+                        Ok(Conversion::Class) => {
+                            format!("{}.fromJson(json)", variant_type.type_name())
+                        }
+                        _ => self.decode("json", variant_type, 0, false)?,
+                    };
+                    body.push(format!("    case {json_name}:"));
+                    body.push(format!("      return {class_name}({value});"));
+                }
+                body.push("  }".to_string());
+                body.push("}".to_string());
+            }
+            None => {
+                let mut cases = Vec::new();
+                for (class_name, json_name, variant_type) in &variants {
+                    let value = self.decode("value", variant_type, 0, false)?;
+                    cases.push((
+                        json_name.clone(),
+                        vec![format!("return {class_name}({value});")],
+                    ));
+                }
+                body.extend(wrapped_cases(cases));
+            }
+        }
+        body.push(format!(
+            "throw ArgumentError.value(json, 'json', 'Unknown variant of {name}');"
+        ));
+        let mut lines = sealed_class(name, body);
+
+        for (class_name, json_name, variant_type) in &variants {
+            let encoded = self.encode("value", variant_type, 0, false)?;
+            let to_json = match p.tag() {
+                Some(tag) => {
+                    // Structs are written as maps, other variants are checked when they are written.
+                    let encoded = match self.conversion(variant_type) {
+                        Ok(Conversion::Class) => encoded,
+                        _ => format!("({encoded} as Map<String, dynamic>)"),
+                    };
+                    format!("{{{}: {json_name}, ...{encoded}}}", string_literal(tag))
+                }
+                None => format!("{{{json_name}: {encoded}}}"),
+            };
+            lines.extend([
+                String::new(),
+                format!("class {class_name} extends {name} {{"),
+                format!("  final {} value;", self.type_name(variant_type)?),
+                String::new(),
+                format!("  const {class_name}(this.value);"),
+                String::new(),
+                "  @override".to_string(),
+                format!("  Object? toJson() => {to_json};"),
+                "}".to_string(),
+            ]);
+        }
+        Ok(lines)
+    }
+
+    /// Returns a constructor call creating an object from a JSON object, e.g. `User(name: json['name'] as String)`,
+    /// with the arguments on separate lines. Optional fields may be missing, because serde accepts missing options.
+    fn constructor_call(
+        &self,
+        class_name: &str,
+        source: &str,
+        fields: &[DartField],
+    ) -> Result<Vec<String>, Error> {
+        if fields.is_empty() {
+            return Ok(vec![format!("{class_name}()")]);
+        }
+        let mut lines = vec![format!("{class_name}(")];
+        for (field, json_name, t) in fields {
+            let value = format!("{source}[{}]", string_literal(json_name));
+            lines.push(format!("  {field}: {},", self.decode(&value, t, 0, false)?));
+        }
+        lines.push(")".to_string());
+        Ok(lines)
+    }
+
+    /// Returns a map literal holding the JSON form of the fields, e.g. `{'name': name}`,
+    /// with the entries on separate lines.
+    fn map_literal(&self, fields: &[DartField]) -> Result<Vec<String>, Error> {
+        if fields.is_empty() {
+            return Ok(vec!["{}".to_string()]);
+        }
+        let mut lines = vec!["{".to_string()];
+        for (field, json_name, t) in fields {
+            lines.push(format!(
+                "  {}: {},",
+                string_literal(json_name),
+                self.encode(field, t, 0, false)?
+            ));
+        }
+        lines.push("}".to_string());
+        Ok(lines)
+    }
+
+    /// Returns the Dart type of a value.
+    fn type_name(&self, t: &Type) -> Result<String, Error> {
+        match t.kind() {
+            TypeKind::List(element) => Ok(format!("List<{}>", self.type_name(element)?)),
+            TypeKind::Map(key, value) => {
+                self.decode_key("key", key)?;
+                Ok(format!(
+                    "Map<{}, {}>",
+                    self.type_name(key)?,
+                    self.type_name(value)?
+                ))
+            }
+            TypeKind::Option(inner) => {
+                let inner = self.type_name(inner)?;
+                Ok(match inner.ends_with('?') {
+                    true => inner,
+                    false => format!("{inner}?"),
+                })
+            }
+            TypeKind::UnsignedLong => Ok("int".to_string()),
+            TypeKind::Named => Ok(match self.conversion(t)? {
+                Conversion::Cast(type_name) => type_name.to_string(),
+                Conversion::Double => "double".to_string(),
+                Conversion::Json => "Object?".to_string(),
+                Conversion::Flags(_) | Conversion::Enum | Conversion::Class | Conversion::Union => {
+                    t.type_name().to_string()
+                }
+            }),
+        }
+    }
+
+    fn conversion(&self, t: &Type) -> Result<Conversion, Error> {
+        let name = t.type_name();
+        match name.as_str() {
+            "Byte" | "Short" | "Integer" | "Long" => Ok(Conversion::Cast("int")),
+            "Float" | "Double" => Ok(Conversion::Double),
+            "Boolean" => Ok(Conversion::Cast("bool")),
+            "String" => Ok(Conversion::Cast("String")),
+            "com.fasterxml.jackson.databind.JsonNode" => Ok(Conversion::Json),
+            _ if self.enums.contains(name) => Ok(Conversion::Enum),
+            _ if self.classes.contains(name) => Ok(Conversion::Class),
+            _ if self.unions.contains(name) => Ok(Conversion::Union),
+            _ => match self.flags.get(name) {
+                Some(type_name) => Ok(Conversion::Flags(type_name)),
+                None => Err(Error::new(&format!("`{name}` has no Dart counterpart"))),
+            },
+        }
+    }
+
+    /// Returns an expression converting a JSON value to a value of the given type.
+    /// When `nullable` is set, the JSON value may be `null`, which is kept.
+    fn decode(&self, expr: &str, t: &Type, depth: usize, nullable: bool) -> Result<String, Error> {
+        let (q, dot) = match nullable {
+            true => ("?", "?."),
+            false => ("", "."),
+        };
+        let or_null = |decoded: String| match nullable {
+            true => format!("{expr} == null ? null : {decoded}"),
+            false => decoded,
+        };
+        match t.kind() {
+            TypeKind::Option(inner) => self.decode(expr, inner, depth, true),
+            TypeKind::List(element) => {
+                let item = format!("e{depth}");
+                let decoded = self.decode(&item, element, depth + 1, false)?;
+                Ok(format!(
+                    "({expr} as List<dynamic>{q}){dot}map(({item}) => {decoded}).toList()"
+                ))
+            }
+            TypeKind::Map(key, value) => {
+                let (k, v) = (format!("k{depth}"), format!("v{depth}"));
+                let decoded_key = self.decode_key(&k, key)?;
+                let decoded_value = self.decode(&v, value, depth + 1, false)?;
+                Ok(format!(
+                    "({expr} as Map<String, dynamic>{q}){dot}map(({k}, {v}) => MapEntry({decoded_key}, {decoded_value}))"
+                ))
+            }
+            TypeKind::UnsignedLong => Ok(format!("{expr} as int{q}")),
+            TypeKind::Named => {
+                let name = t.type_name();
+                Ok(match self.conversion(t)? {
+                    Conversion::Cast(type_name) | Conversion::Flags(type_name) => {
+                        format!("{expr} as {type_name}{q}")
+                    }
+                    Conversion::Double => format!("({expr} as num{q}){dot}toDouble()"),
+                    Conversion::Json => expr.to_string(),
+                    Conversion::Enum => or_null(format!("{name}.fromJson({expr} as String)")),
+                    Conversion::Class => {
+                        or_null(format!("{name}.fromJson({expr} as Map<String, dynamic>)"))
+                    }
+                    Conversion::Union => or_null(format!("{name}.fromJson({expr})")),
+                })
+            }
+        }
+    }
+
+    /// Returns an expression converting a key of a JSON object to a key of the given type.
+    fn decode_key(&self, k: &str, t: &Type) -> Result<String, Error> {
+        match self.conversion(t) {
+            Ok(Conversion::Cast("String")) => Ok(k.to_string()),
+            Ok(Conversion::Cast("int")) => Ok(format!("int.parse({k})")),
+            Ok(Conversion::Enum) => Ok(format!("{}.fromJson({k})", t.type_name())),
+            _ => Err(Error::new(&format!(
+                "`{}` can not be a key of a JSON object in Dart",
+                t.type_name()
+            ))),
+        }
+    }
+
+    /// Returns an expression converting a value of the given type to its JSON form.
+    /// It returns the expression itself when no conversion is needed.
+    /// When `nullable` is set, the value may be `null`, which is kept.
+    fn encode(&self, expr: &str, t: &Type, depth: usize, nullable: bool) -> Result<String, Error> {
+        let dot = match nullable {
+            true => "?.",
+            false => ".",
+        };
+        match t.kind() {
+            TypeKind::Option(inner) => self.encode(expr, inner, depth, true),
+            TypeKind::List(element) => {
+                let item = format!("e{depth}");
+                let encoded = self.encode(&item, element, depth + 1, false)?;
+                Ok(match encoded == item {
+                    true => expr.to_string(),
+                    false => format!("{expr}{dot}map(({item}) => {encoded}).toList()"),
+                })
+            }
+            TypeKind::Map(key, value) => {
+                let (k, v) = (format!("k{depth}"), format!("v{depth}"));
+                // JSON keys are strings, so serde writes numeric keys as strings.
+                let encoded_key = match self.conversion(key) {
+                    Ok(Conversion::Cast("int")) => format!("{k}.toString()"),
+                    Ok(Conversion::Enum) => format!("{k}.toJson()"),
+                    _ => k.clone(),
+                };
+                let encoded_value = self.encode(&v, value, depth + 1, false)?;
+                Ok(match encoded_key == k && encoded_value == v {
+                    true => expr.to_string(),
+                    false => format!(
+                        "{expr}{dot}map(({k}, {v}) => MapEntry({encoded_key}, {encoded_value}))"
+                    ),
+                })
+            }
+            TypeKind::UnsignedLong => Ok(expr.to_string()),
+            TypeKind::Named => Ok(match self.conversion(t)? {
+                Conversion::Cast(_)
+                | Conversion::Double
+                | Conversion::Flags(_)
+                | Conversion::Json => expr.to_string(),
+                Conversion::Enum | Conversion::Class | Conversion::Union => {
+                    format!("{expr}{dot}toJson()")
+                }
+            }),
+        }
+    }
+}
+
+/// Returns a sealed class with a `fromJson` factory with the given body and an abstract `toJson`.
+fn sealed_class(name: &str, body: Vec<String>) -> Vec<String> {
+    let mut lines = vec![
+        format!("sealed class {name} {{"),
+        format!("  const {name}();"),
+        String::new(),
+        format!("  factory {name}.fromJson(Object? json) {{"),
+    ];
+    lines.extend(indent(body, 2));
+    lines.extend([
+        "  }".to_string(),
+        String::new(),
+        "  Object? toJson();".to_string(),
+        "}".to_string(),
+    ]);
+    lines
+}
+
+/// Returns statements reading an object with a single key naming the variant into `key` and `value`,
+/// followed by a switch over the key with the given cases, which read the variant from `value`.
+fn wrapped_cases(cases: Vec<(String, Vec<String>)>) -> Vec<String> {
+    if cases.is_empty() {
+        return Vec::new();
+    }
+    let mut lines = vec![
+        "if (json is Map<String, dynamic> && json.length == 1) {".to_string(),
+        "  final key = json.keys.single;".to_string(),
+        "  final value = json[key];".to_string(),
+        "  switch (key) {".to_string(),
+    ];
+    for (json_name, statement) in cases {
+        lines.push(format!("    case {json_name}:"));
+        lines.extend(indent(statement, 3));
+    }
+    lines.push("  }".to_string());
+    lines.push("}".to_string());
+    lines
+}
+
+/// Returns a `const` constructor with named parameters, where the fields that are not nullable are `required`.
+fn named_constructor(class_name: &str, fields: &[DartField]) -> Vec<String> {
+    if fields.is_empty() {
+        return vec![format!("  const {class_name}();")];
+    }
+    let mut lines = vec![format!("  const {class_name}({{")];
+    for (field, _, t) in fields {
+        lines.push(match t.kind() {
+            TypeKind::Option(_) => format!("    this.{field},"),
+            _ => format!("    required this.{field},"),
+        });
+    }
+    lines.push("  });".to_string());
+    lines
+}
+
+/// Returns the name of a field, with reserved words escaped with a trailing underscore, e.g. `class_`.
+fn field_name(name: &Name) -> String {
+    let camel_case = name.as_camel_case();
+    match DART_KEYWORDS.contains(&camel_case.as_str()) {
+        true => format!("{camel_case}_"),
+        false => camel_case,
+    }
+}
+
+/// Returns the name of an enum value, which can not be a member of the enum either, e.g. `values`.
+fn enum_value_name(name: &Name) -> String {
+    let camel_case = name.as_camel_case();
+    match ENUM_MEMBERS.contains(&camel_case.as_str()) {
+        true => format!("{camel_case}_"),
+        false => field_name(name),
+    }
+}
+
+/// Returns a single-quoted Dart string literal. `$` is escaped, since it starts an interpolation.
+fn string_literal(value: &str) -> String {
+    let mut literal = String::from("'");
+    for c in value.chars() {
+        match c {
+            '\\' => literal.push_str("\\\\"),
+            '\'' => literal.push_str("\\'"),
+            '$' => literal.push_str("\\$"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            c if c.is_control() => literal.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => literal.push(c),
+        }
+    }
+    literal.push('\'');
+    literal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generate_dart_code, DartFlavor};
+    use crate as rdc;
+    use crate::ir::{IntermediateRepresentation, TypeTarget};
+    use crate::{rdc_polymorphic, RDC};
+    use std::collections::HashMap;
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct User {
+        #[serde(rename = "user-name")]
+        name: String,
+        nickname: Option<String>,
+        roles: Vec<Role>,
+        limits: HashMap<Role, Option<i64>>,
+        shape: Shape,
+        pet: Box<dyn Pet>,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Role {
+        Admin,
+        #[serde(rename = "member")]
+        Member,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Circle {
+        radius: f64,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Shape {
+        Point,
+        Circle(Circle),
+        Line(f64, Option<f64>),
+        Rect { width: f64, height: Option<f64> },
+    }
+
+    trait Pet {}
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Dog {
+        name: String,
+    }
+    impl Pet for Dog {}
+
+    rdc_polymorphic!(dyn Pet, tag = "kind" => Dog);
+
+    fn generate(flavor: DartFlavor) -> String {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<User>();
+        generate_dart_code(&ir, flavor, "models.dart").unwrap()
+    }
+
+    #[test]
+    fn generate_classes_and_enums() {
+        let code = generate(DartFlavor::Manual);
+        assert!(code.starts_with("// GENERATED CODE - DO NOT MODIFY BY HAND\n\nclass "));
+        assert!(code.contains(
+            "  const User({\n    required this.name,\n    this.nickname,\n    required this.roles,\n"
+        ));
+        assert!(code.contains("  final Map<Role, int?> limits;\n"));
+        assert!(code.contains(
+            "    roles: (json['roles'] as List<dynamic>).map((e0) => Role.fromJson(e0 as String)).toList(),\n"
+        ));
+        assert!(code.contains(
+            "    limits: (json['limits'] as Map<String, dynamic>).map((k0, v0) => MapEntry(Role.fromJson(k0), v0 as int?)),\n"
+        ));
+        assert!(code.contains("    pet: Pet.fromJson(json['pet']),\n  );\n"));
+        assert!(code.contains(
+            "    'user-name': name,\n    'nickname': nickname,\n    'roles': roles.map((e0) => e0.toJson()).toList(),\n    'limits': limits.map((k0, v0) => MapEntry(k0.toJson(), v0)),\n"
+        ));
+        assert!(code.contains("enum Role {\n  admin('Admin'),\n  member('member');\n"));
+        assert!(code.contains("  static Role fromJson(String json) => values.firstWhere(\n"));
+        assert!(!code.contains("@JsonSerializable"));
+    }
+
+    #[test]
+    fn generate_sealed_classes() {
+        let code = generate(DartFlavor::Manual);
+        assert!(
+            code.contains("    if (json == 'Point') {\n      return const ShapePoint();\n    }\n")
+        );
+        assert!(code.contains(
+            "        case 'Line':\n          return ShapeLine((value[0] as num).toDouble(), (value[1] as num?)?.toDouble());\n"
+        ));
+        assert!(code.contains(
+            "        case 'Rect':\n          return ShapeRect(\n            width: (value['width'] as num).toDouble(),\n"
+        ));
+        assert!(code.contains("  Object? toJson() => {'Line': [value0, value1]};\n"));
+        assert!(code.contains("  Object? toJson() => {'Circle': value.toJson()};\n"));
+        assert!(code.contains(
+            "  Object? toJson() => {'Rect': {\n    'width': width,\n    'height': height,\n  }};\n"
+        ));
+        assert!(code.contains(
+            "      switch (json['kind']) {\n        case 'Dog':\n          return PetDog(Dog.fromJson(json));\n"
+        ));
+        assert!(code.contains("  Object? toJson() => {'kind': 'Dog', ...value.toJson()};\n"));
+    }
+
+    #[test]
+    fn json_serializable() {
+        let code = generate(DartFlavor::JsonSerializable);
+        assert!(code.starts_with(
+            "// GENERATED CODE - DO NOT MODIFY BY HAND\n\nimport 'package:json_annotation/json_annotation.dart';\n\npart 'models.g.dart';\n"
+        ));
+        assert!(code.contains(
+            "@JsonSerializable(explicitToJson: true)\nclass User {\n  @JsonKey(name: 'user-name')\n  final String name;\n  final String? nickname;\n"
+        ));
+        assert!(code.contains(
+            "  factory User.fromJson(Map<String, dynamic> json) => _$UserFromJson(json);\n\n  Map<String, dynamic> toJson() => _$UserToJson(this);\n"
+        ));
+        assert!(code.contains("@JsonEnum(valueField: 'jsonName')\nenum Role {\n"));
+        assert!(code.contains("sealed class Shape {\n"));
+    }
+}
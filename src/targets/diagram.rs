@@ -0,0 +1,337 @@
+use crate::errors::Error;
+use crate::ir::{DataEnumVariant, IntermediateRepresentation, Type, TypeKind};
+use std::collections::HashSet;
+use std::io::Write;
+
+/// This enum describes the syntax of the generated class diagram.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiagramFormat {
+    /// Mermaid `classDiagram`, which is rendered by most code hosting platforms.
+    #[default]
+    Mermaid,
+    /// PlantUML class diagram between `@startuml` and `@enduml`.
+    PlantUml,
+}
+
+/// This function generates a class diagram of the types in the IR.
+///
+/// Structs are classes with their fields, named after their JSON names,
+/// unit enums are enumerations, data enums are classes with the `<<union>>` stereotype listing their variants,
+/// and polymorphic types are interfaces implemented by their variants.
+/// Every reference to another type is an edge labeled with the field name.
+/// Edges of optional fields have `0..1` multiplicity and edges of collections have `*` multiplicity.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::targets::diagram::{generate_class_diagram, DiagramFormat};
+/// use rdc::RDC;
+///
+/// #[derive(RDC)]
+/// struct Order {
+///     lines: Vec<OrderLine>,
+/// }
+///
+/// #[derive(RDC)]
+/// struct OrderLine {
+///     sku: String,
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add::<Order>();
+/// let diagram = generate_class_diagram(&ir, DiagramFormat::Mermaid).unwrap();
+/// assert!(diagram.contains("Order --> \"*\" OrderLine : lines"));
+/// ```
+pub fn generate_class_diagram(
+    ir: &IntermediateRepresentation,
+    format: DiagramFormat,
+) -> Result<String, Error> {
+    ir.validate()?;
+    let generator = DiagramGenerator::new(ir, format);
+    let mut lines = Vec::new();
+    let mut edges = Vec::new();
+    for s in ir.structs() {
+        let name = s.self_type().type_name();
+        let members = s
+            .fields()
+            .iter()
+            .map(|f| generator.member(f.json_name(), f.field_type()))
+            .collect();
+        lines.extend(generator.class("class", name, None, members));
+        for f in s.fields() {
+            edges.extend(generator.edges(name, f.json_name(), f.field_type()));
+        }
+    }
+    for e in ir.enums() {
+        let name = e.self_type().type_name();
+        let members = e.variants().iter().map(|v| v.json_name().clone()).collect();
+        lines.extend(generator.class("enum", name, Some("enumeration"), members));
+    }
+    for de in ir.data_enums() {
+        let name = de.self_type().type_name();
+        let members = de
+            .variants()
+            .iter()
+            .map(|v| v.json_name().clone())
+            .collect();
+        lines.extend(generator.class("class", name, Some("union"), members));
+        for v in de.variants() {
+            match v {
+                DataEnumVariant::Unit { .. } => {}
+                DataEnumVariant::Tuple { fields, .. } => {
+                    for t in fields {
+                        edges.extend(generator.edges(name, v.json_name(), t));
+                    }
+                }
+                DataEnumVariant::Object { fields, .. } => {
+                    for f in fields {
+                        let label = format!("{}.{}", v.json_name(), f.json_name());
+                        edges.extend(generator.edges(name, &label, f.field_type()));
+                    }
+                }
+            }
+        }
+    }
+    for p in ir.polymorphics() {
+        let name = p.self_type().type_name();
+        lines.extend(generator.class("interface", name, Some("interface"), Vec::new()));
+        for v in p.variants() {
+            edges.push(format!("{name} <|.. {}", v.variant_type().type_name()));
+        }
+    }
+    let mut diagram = match format {
+        DiagramFormat::Mermaid => vec!["classDiagram".to_string()],
+        DiagramFormat::PlantUml => vec!["@startuml".to_string()],
+    };
+    let indent = match format {
+        DiagramFormat::Mermaid => "  ",
+        DiagramFormat::PlantUml => "",
+    };
+    diagram.extend(lines.into_iter().map(|l| format!("{indent}{l}")));
+    diagram.extend(edges.into_iter().map(|e| format!("{indent}{e}")));
+    if format == DiagramFormat::PlantUml {
+        diagram.push("@enduml".to_string());
+    }
+    let mut diagram = diagram.join("\n");
+    diagram.push('\n');
+    Ok(diagram)
+}
+
+/// This function generates a class diagram and writes it to the given file.
+pub fn write_class_diagram(
+    ir: &IntermediateRepresentation,
+    format: DiagramFormat,
+    file_path: &str,
+) -> Result<(), Error> {
+    let diagram = generate_class_diagram(ir, format)?;
+    if let Some(parent) = std::path::Path::new(file_path).parent() {
+        std::fs::create_dir_all(parent).map_err(|_| Error::new("Failed to create directory"))?;
+    }
+    let mut file =
+        std::fs::File::create(file_path).map_err(|_| Error::new("Failed to create file"))?;
+    file.write_all(diagram.as_bytes())
+        .map_err(|_| Error::new("Failed to write to file"))
+}
+
+struct DiagramGenerator<'a> {
+    format: DiagramFormat,
+    /// Names of the types that are nodes of the diagram.
+    nodes: HashSet<&'a str>,
+}
+
+impl<'a> DiagramGenerator<'a> {
+    fn new(ir: &'a IntermediateRepresentation, format: DiagramFormat) -> Self {
+        let nodes = ir
+            .structs()
+            .iter()
+            .map(|s| s.self_type())
+            .chain(ir.enums().iter().map(|e| e.self_type()))
+            .chain(ir.data_enums().iter().map(|de| de.self_type()))
+            .chain(ir.polymorphics().iter().map(|p| p.self_type()))
+            .map(|t| t.type_name().as_str())
+            .collect();
+        DiagramGenerator { format, nodes }
+    }
+
+    /// Returns lines declaring a node. `keyword` is the PlantUML keyword, Mermaid uses stereotypes instead.
+    /// PlantUML shows stereotypes only on classes, since enums and interfaces have their own keywords.
+    fn class(
+        &self,
+        keyword: &str,
+        name: &str,
+        stereotype: Option<&str>,
+        members: Vec<String>,
+    ) -> Vec<String> {
+        let (header, mut body) = match self.format {
+            DiagramFormat::Mermaid => (
+                format!("class {name}"),
+                stereotype.map(|s| format!("<<{s}>>")).into_iter().collect(),
+            ),
+            DiagramFormat::PlantUml => match stereotype.filter(|_| keyword == "class") {
+                Some(s) => (format!("class {name} <<{s}>>"), Vec::new()),
+                None => (format!("{keyword} {name}"), Vec::new()),
+            },
+        };
+        body.extend(members);
+        if body.is_empty() {
+            return vec![header];
+        }
+        let mut lines = vec![format!("{header} {{")];
+        lines.extend(body.into_iter().map(|m| format!("  {m}")));
+        lines.push("}".to_string());
+        lines
+    }
+
+    fn member(&self, name: &str, t: &Type) -> String {
+        let type_label = self.type_label(t);
+        match self.format {
+            DiagramFormat::Mermaid => format!("+{type_label} {name}"),
+            DiagramFormat::PlantUml => format!("+{name} : {type_label}"),
+        }
+    }
+
+    fn type_label(&self, t: &Type) -> String {
+        let (open, close) = match self.format {
+            DiagramFormat::Mermaid => ("~", "~"),
+            DiagramFormat::PlantUml => ("<", ">"),
+        };
+        match t.kind() {
+            TypeKind::Named => match t.type_name().as_str() {
+                "Boolean" => "boolean",
+                "Byte" => "int8",
+                "Short" => "int16",
+                "Integer" => "int32",
+                "Long" => "int64",
+                "Float" => "float",
+                "Double" => "double",
+                "String" => "string",
+                name => name,
+            }
+            .to_string(),
+            TypeKind::UnsignedLong => "uint64".to_string(),
+            TypeKind::Option(inner) => format!("{}?", self.type_label(inner)),
+            TypeKind::List(element) => format!("List{open}{}{close}", self.type_label(element)),
+            TypeKind::Map(key, value) => format!(
+                "Map{open}{}, {}{close}",
+                self.type_label(key),
+                self.type_label(value)
+            ),
+        }
+    }
+
+    /// Returns edges from the owner to the nodes referenced by the type.
+    fn edges(&self, owner: &str, label: &str, t: &Type) -> Vec<String> {
+        let mut targets = Vec::new();
+        self.references(t, None, &mut targets);
+        targets
+            .into_iter()
+            .map(|(target, multiplicity)| match multiplicity {
+                Some(m) => format!("{owner} --> \"{m}\" {target} : {label}"),
+                None => format!("{owner} --> {target} : {label}"),
+            })
+            .collect()
+    }
+
+    fn references(
+        &self,
+        t: &'a Type,
+        multiplicity: Option<&'static str>,
+        targets: &mut Vec<(&'a str, Option<&'static str>)>,
+    ) {
+        match t.kind() {
+            TypeKind::Named if self.nodes.contains(t.type_name().as_str()) => {
+                targets.push((t.type_name(), multiplicity))
+            }
+            TypeKind::Named | TypeKind::UnsignedLong => {}
+            TypeKind::Option(inner) => {
+                self.references(inner, multiplicity.or(Some("0..1")), targets)
+            }
+            TypeKind::List(inner) => self.references(inner, Some("*"), targets),
+            TypeKind::Map(key, value) => {
+                self.references(key, Some("*"), targets);
+                self.references(value, Some("*"), targets);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as rdc;
+    use crate::ir::TypeTarget;
+    use crate::{rdc_polymorphic, RDC};
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Order {
+        order_id: String,
+        #[serde(rename = "items")]
+        lines: Vec<OrderLine>,
+        status: Option<Status>,
+        payment: Box<dyn Payment>,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct OrderLine {
+        sku: String,
+        quantity: u64,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Status {
+        Open,
+        Closed,
+    }
+
+    trait Payment {}
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Card {
+        number: String,
+    }
+    impl Payment for Card {}
+
+    rdc_polymorphic!(dyn Payment => Card);
+
+    #[test]
+    fn mermaid_diagram() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Order>();
+        let diagram = generate_class_diagram(&ir, DiagramFormat::Mermaid).unwrap();
+        println!("{diagram}");
+        assert!(diagram.starts_with("classDiagram\n"));
+        assert!(diagram.contains(
+            "  class Order {\n    +string order_id\n    +List~OrderLine~ items\n    +Status? status\n    +Payment payment\n  }"
+        ));
+        assert!(diagram.contains("    +uint64 quantity\n"));
+        assert!(
+            diagram.contains("  class Status {\n    <<enumeration>>\n    Open\n    Closed\n  }")
+        );
+        assert!(diagram.contains("  class Payment {\n    <<interface>>\n  }"));
+        assert!(diagram.contains("  Order --> \"*\" OrderLine : items\n"));
+        assert!(diagram.contains("  Order --> \"0..1\" Status : status\n"));
+        assert!(diagram.contains("  Order --> Payment : payment\n"));
+        assert!(diagram.contains("  Payment <|.. Card\n"));
+    }
+
+    #[test]
+    fn plant_uml_diagram() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Order>();
+        let diagram = generate_class_diagram(&ir, DiagramFormat::PlantUml).unwrap();
+        println!("{diagram}");
+        assert!(diagram.starts_with("@startuml\n"));
+        assert!(diagram.ends_with("@enduml\n"));
+        assert!(
+            diagram.contains("class Order {\n  +order_id : string\n  +items : List<OrderLine>\n")
+        );
+        assert!(diagram.contains("enum Status {\n  Open\n  Closed\n}"));
+        assert!(diagram.contains("\ninterface Payment\n"));
+        assert!(diagram.contains("\nOrder --> \"*\" OrderLine : items\n"));
+        assert!(diagram.contains("\nPayment <|.. Card\n"));
+    }
+}
@@ -0,0 +1,740 @@
+use crate::errors::Error;
+use crate::ir::{DataEnumVariant, DateTimeFormat, IntermediateRepresentation, Type, TypeKind};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::Write;
+
+/// This is a struct that represents a generated documentation page.
+pub struct DocPage {
+    name: String,
+    file_name: String,
+    content: String,
+}
+
+impl DocPage {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the name of the file the page is written to, which is also the target of links to the page.
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+}
+
+/// This enum describes the format of the generated documentation.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DocsFormat {
+    /// Markdown pages, which are rendered by most code hosting platforms and wikis.
+    #[default]
+    Markdown,
+    /// Standalone HTML pages without any styles or scripts.
+    Html,
+}
+
+impl DocsFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            DocsFormat::Markdown => "md",
+            DocsFormat::Html => "html",
+        }
+    }
+}
+
+/// This struct contains options that control documentation generation.
+///
+/// Doc comments are not available to the derive macro, so descriptions are provided here.
+/// Type and field names are Rust names, e.g. `("Order", "order_id")`.
+///
+/// Example:
+/// ```rust
+/// use rdc::targets::docs::{DocsConfig, DocsFormat};
+///
+/// let config = DocsConfig::new()
+///     .with_format(DocsFormat::Html)
+///     .with_type_description("Order", "An order placed by a customer.")
+///     .with_field_description("Order", "order_id", "Unique identifier of the order.");
+/// assert_eq!(config.format(), DocsFormat::Html);
+/// ```
+pub struct DocsConfig {
+    format: DocsFormat,
+    title: String,
+    type_descriptions: HashMap<String, String>,
+    field_descriptions: HashMap<(String, String), String>,
+}
+
+impl Default for DocsConfig {
+    fn default() -> Self {
+        DocsConfig {
+            format: DocsFormat::default(),
+            title: "Data types".to_string(),
+            type_descriptions: HashMap::new(),
+            field_descriptions: HashMap::new(),
+        }
+    }
+}
+
+impl DocsConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_format(mut self, format: DocsFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Sets the title of the index page.
+    pub fn with_title<S: Into<String>>(mut self, title: S) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    pub fn with_type_description<S: Into<String>, D: Into<String>>(
+        mut self,
+        type_name: S,
+        description: D,
+    ) -> Self {
+        self.type_descriptions
+            .insert(type_name.into(), description.into());
+        self
+    }
+
+    /// Sets the description of a field of a struct or a variant of an enum.
+    pub fn with_field_description<S: Into<String>, F: Into<String>, D: Into<String>>(
+        mut self,
+        type_name: S,
+        field_name: F,
+        description: D,
+    ) -> Self {
+        self.field_descriptions
+            .insert((type_name.into(), field_name.into()), description.into());
+        self
+    }
+
+    pub fn format(&self) -> DocsFormat {
+        self.format
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn type_description(&self, type_name: &str) -> Option<&str> {
+        self.type_descriptions.get(type_name).map(|d| d.as_str())
+    }
+
+    pub fn field_description(&self, type_name: &str, field_name: &str) -> Option<&str> {
+        self.field_descriptions
+            .get(&(type_name.to_string(), field_name.to_string()))
+            .map(|d| d.as_str())
+    }
+}
+
+/// This function generates documentation of the wire format of the types in the IR.
+///
+/// Every type gets a page listing its fields or variants with JSON names, types and optionality,
+/// with links to the pages of the types it references and of the types referencing it.
+/// An `index` page lists all the types.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::targets::docs::{generate_docs, DocsConfig};
+/// use rdc::RDC;
+///
+/// #[derive(RDC)]
+/// struct Order {
+///     order_id: String,
+///     customer: Customer,
+/// }
+///
+/// #[derive(RDC)]
+/// struct Customer {
+///     name: String,
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add::<Order>();
+/// let pages = generate_docs(&ir, &DocsConfig::new()).unwrap();
+/// let order = pages.iter().find(|p| p.name() == "Order").unwrap();
+/// assert!(order.content().contains("| `customer` | [Customer](Customer.md) | yes |  |"));
+/// ```
+pub fn generate_docs(
+    ir: &IntermediateRepresentation,
+    config: &DocsConfig,
+) -> Result<Vec<DocPage>, Error> {
+    ir.validate()?;
+    let generator = PageGenerator::new(ir, config);
+    let mut pages = vec![generator.index()];
+    for s in ir.structs() {
+        let type_name = s.self_type().type_name();
+        let rows = s
+            .fields()
+            .iter()
+            .map(|f| {
+                let optional = !f.is_required();
+                vec![
+                    vec![Inline::Code(f.json_name().clone())],
+                    generator.type_inlines(f.field_type()),
+                    vec![Inline::Text(
+                        if optional { "no" } else { "yes" }.to_string(),
+                    )],
+                    generator.field_description(type_name, &f.name().as_snake_case(), f.format()),
+                ]
+            })
+            .collect();
+        let table = Block::Table(vec!["Field", "Type", "Required", "Description"], rows);
+        pages.push(generator.page(type_name, "Object", vec![table]));
+    }
+    for e in ir.enums() {
+        let type_name = e.self_type().type_name();
+        let rows = e
+            .variants()
+            .iter()
+            .map(|v| {
+                vec![
+                    vec![Inline::Code(format!("\"{}\"", v.json_name()))],
+                    generator.field_description(type_name, &v.name().as_snake_case(), None),
+                ]
+            })
+            .collect();
+        let blocks = vec![
+            Block::Paragraph(vec![Inline::Text(
+                "Values are written as one of the following strings.".to_string(),
+            )]),
+            Block::Table(vec!["Value", "Description"], rows),
+        ];
+        pages.push(generator.page(type_name, "Enum", blocks));
+    }
+    for de in ir.data_enums() {
+        let type_name = de.self_type().type_name();
+        let rows = de
+            .variants()
+            .iter()
+            .map(|v| {
+                vec![
+                    vec![Inline::Code(v.json_name().clone())],
+                    generator.variant_content(v),
+                    generator.field_description(type_name, &v.name().as_snake_case(), None),
+                ]
+            })
+            .collect();
+        let blocks = vec![
+            Block::Paragraph(vec![Inline::Text(
+                "Unit variants are written as strings, other variants as objects with the variant name as the only key."
+                    .to_string(),
+            )]),
+            Block::Table(vec!["Variant", "Content", "Description"], rows),
+        ];
+        pages.push(generator.page(type_name, "Tagged union", blocks));
+    }
+    for p in ir.polymorphics() {
+        let type_name = p.self_type().type_name();
+        let encoding = match p.tag() {
+            Some(tag) => vec![
+                Inline::Text("The variant is stored in the ".to_string()),
+                Inline::Code(tag.to_string()),
+                Inline::Text(" property, next to the fields of the variant.".to_string()),
+            ],
+            None => vec![Inline::Text(
+                "Values are objects with the variant name as the only key.".to_string(),
+            )],
+        };
+        let rows = p
+            .variants()
+            .iter()
+            .map(|v| {
+                vec![
+                    vec![Inline::Code(v.json_name().clone())],
+                    generator.type_inlines(v.variant_type()),
+                ]
+            })
+            .collect();
+        let blocks = vec![
+            Block::Paragraph(encoding),
+            Block::Table(vec!["Variant", "Type"], rows),
+        ];
+        pages.push(generator.page(type_name, "Union", blocks));
+    }
+    Ok(pages)
+}
+
+/// This function writes generated documentation pages to the provided directory.
+pub fn write_docs(pages: &[DocPage], directory: &str) -> Result<(), Error> {
+    std::fs::create_dir_all(directory).map_err(|_| Error::new("Failed to create directory"))?;
+    for page in pages {
+        let mut file_path = std::path::PathBuf::from(directory);
+        file_path.push(page.file_name());
+        let mut file =
+            std::fs::File::create(file_path).map_err(|_| Error::new("Failed to create file"))?;
+        file.write_all(page.content().as_bytes())
+            .map_err(|_| Error::new("Failed to write to file"))?;
+    }
+    Ok(())
+}
+
+/// Inline content of a paragraph or a table cell.
+enum Inline {
+    Text(String),
+    Code(String),
+    /// Link to the page of a documented type.
+    Link(String),
+}
+
+enum Block {
+    Paragraph(Vec<Inline>),
+    Table(Vec<&'static str>, Vec<Vec<Vec<Inline>>>),
+}
+
+struct PageGenerator<'a> {
+    config: &'a DocsConfig,
+    /// Documented types with their kinds.
+    kinds: BTreeMap<&'a str, &'static str>,
+    /// Documented types referencing each documented type.
+    referenced_by: HashMap<&'a str, BTreeSet<&'a str>>,
+}
+
+impl<'a> PageGenerator<'a> {
+    fn new(ir: &'a IntermediateRepresentation, config: &'a DocsConfig) -> Self {
+        let kinds = ir
+            .structs()
+            .iter()
+            .map(|s| (s.self_type().type_name().as_str(), "Object"))
+            .chain(
+                ir.enums()
+                    .iter()
+                    .map(|e| (e.self_type().type_name().as_str(), "Enum")),
+            )
+            .chain(
+                ir.data_enums()
+                    .iter()
+                    .map(|de| (de.self_type().type_name().as_str(), "Tagged union")),
+            )
+            .chain(
+                ir.polymorphics()
+                    .iter()
+                    .map(|p| (p.self_type().type_name().as_str(), "Union")),
+            )
+            .collect();
+        let mut references: Vec<(&str, &Type)> = Vec::new();
+        for s in ir.structs() {
+            let name = s.self_type().type_name().as_str();
+            references.extend(s.fields().iter().map(|f| (name, f.field_type())));
+        }
+        for de in ir.data_enums() {
+            let name = de.self_type().type_name().as_str();
+            for v in de.variants() {
+                match v {
+                    DataEnumVariant::Object { fields, .. } => {
+                        references.extend(fields.iter().map(|f| (name, f.field_type())))
+                    }
+                    DataEnumVariant::Tuple { fields, .. } => {
+                        references.extend(fields.iter().map(|t| (name, t)))
+                    }
+                    DataEnumVariant::Unit { .. } => {}
+                }
+            }
+        }
+        for p in ir.polymorphics() {
+            let name = p.self_type().type_name().as_str();
+            references.extend(p.variants().iter().map(|v| (name, v.variant_type())));
+        }
+        let mut referenced_by: HashMap<&str, BTreeSet<&str>> = HashMap::new();
+        for (name, t) in references {
+            let mut named = Vec::new();
+            named_types(t, &mut named);
+            for referenced in named {
+                if referenced != name {
+                    referenced_by.entry(referenced).or_default().insert(name);
+                }
+            }
+        }
+        PageGenerator {
+            config,
+            kinds,
+            referenced_by,
+        }
+    }
+
+    fn index(&self) -> DocPage {
+        let rows = self
+            .kinds
+            .iter()
+            .map(|(name, kind)| {
+                vec![
+                    vec![Inline::Link(name.to_string())],
+                    vec![Inline::Text(kind.to_string())],
+                    self.config
+                        .type_description(name)
+                        .map(|d| vec![Inline::Text(d.to_string())])
+                        .unwrap_or_default(),
+                ]
+            })
+            .collect();
+        self.render(
+            "index",
+            self.config.title(),
+            vec![Block::Table(vec!["Type", "Kind", "Description"], rows)],
+        )
+    }
+
+    fn page(&self, type_name: &str, kind: &str, blocks: Vec<Block>) -> DocPage {
+        let mut all_blocks = vec![Block::Paragraph(vec![Inline::Text(kind.to_string())])];
+        if let Some(description) = self.config.type_description(type_name) {
+            all_blocks.push(Block::Paragraph(vec![Inline::Text(
+                description.to_string(),
+            )]));
+        }
+        all_blocks.extend(blocks);
+        if let Some(referencing) = self.referenced_by.get(type_name) {
+            let mut inlines = vec![Inline::Text("Referenced by ".to_string())];
+            for (i, name) in referencing.iter().enumerate() {
+                if i > 0 {
+                    inlines.push(Inline::Text(", ".to_string()));
+                }
+                inlines.push(Inline::Link(name.to_string()));
+            }
+            inlines.push(Inline::Text(".".to_string()));
+            all_blocks.push(Block::Paragraph(inlines));
+        }
+        self.render(type_name, type_name, all_blocks)
+    }
+
+    /// Returns the description of a field, followed by its date format set with `set_field_format`, if any.
+    fn field_description(
+        &self,
+        type_name: &str,
+        field_name: &str,
+        format: Option<&DateTimeFormat>,
+    ) -> Vec<Inline> {
+        let mut inlines = self
+            .config
+            .field_description(type_name, field_name)
+            .map(|d| vec![Inline::Text(d.to_string())])
+            .unwrap_or_default();
+        if let Some(format) = format {
+            let separator = if inlines.is_empty() { "" } else { " " };
+            inlines.push(Inline::Text(format!("{separator}Format: ")));
+            inlines.push(Inline::Code(format.pattern().to_string()));
+            if let Some(timezone) = format.timezone() {
+                inlines.push(Inline::Text(format!(", in {timezone}")));
+            }
+            inlines.push(Inline::Text(".".to_string()));
+        }
+        inlines
+    }
+
+    fn variant_content(&self, variant: &DataEnumVariant) -> Vec<Inline> {
+        let mut inlines = Vec::new();
+        match variant {
+            DataEnumVariant::Unit { .. } => inlines.push(Inline::Text("none".to_string())),
+            DataEnumVariant::Tuple { fields, .. } if fields.len() == 1 => {
+                inlines.extend(self.type_inlines(&fields[0]))
+            }
+            DataEnumVariant::Tuple { fields, .. } => {
+                inlines.push(Inline::Text("array of ".to_string()));
+                for (i, t) in fields.iter().enumerate() {
+                    if i > 0 {
+                        inlines.push(Inline::Text(", ".to_string()));
+                    }
+                    inlines.extend(self.type_inlines(t));
+                }
+            }
+            DataEnumVariant::Object { fields, .. } => {
+                inlines.push(Inline::Text("object with ".to_string()));
+                for (i, f) in fields.iter().enumerate() {
+                    if i > 0 {
+                        inlines.push(Inline::Text(", ".to_string()));
+                    }
+                    inlines.push(Inline::Code(f.json_name().to_string()));
+                    inlines.push(Inline::Text(": ".to_string()));
+                    inlines.extend(self.type_inlines(f.field_type()));
+                }
+            }
+        }
+        inlines
+    }
+
+    /// Describes a type in terms of the wire format, with links to documented types.
+    fn type_inlines(&self, t: &Type) -> Vec<Inline> {
+        match t.kind() {
+            TypeKind::Named if self.kinds.contains_key(t.type_name().as_str()) => {
+                vec![Inline::Link(t.type_name().clone())]
+            }
+            TypeKind::Named => {
+                let name = match t.type_name().as_str() {
+                    "Boolean" => "boolean",
+                    "Byte" => "int8",
+                    "Short" => "int16",
+                    "Integer" => "int32",
+                    "Long" => "int64",
+                    "Float" => "float",
+                    "Double" => "double",
+                    "String" => "string",
+                    name => name,
+                };
+                vec![Inline::Code(name.to_string())]
+            }
+            TypeKind::UnsignedLong => vec![Inline::Code("uint64".to_string())],
+            TypeKind::Option(inner) => self.type_inlines(inner),
+            TypeKind::List(element) => {
+                let mut inlines = vec![Inline::Text("list of ".to_string())];
+                inlines.extend(self.type_inlines(element));
+                inlines
+            }
+            TypeKind::Map(key, value) => {
+                let mut inlines = vec![Inline::Text("map from ".to_string())];
+                inlines.extend(self.type_inlines(key));
+                inlines.push(Inline::Text(" to ".to_string()));
+                inlines.extend(self.type_inlines(value));
+                inlines
+            }
+        }
+    }
+
+    fn render(&self, name: &str, title: &str, blocks: Vec<Block>) -> DocPage {
+        let format = self.config.format();
+        let content = match format {
+            DocsFormat::Markdown => render_markdown(title, &blocks),
+            DocsFormat::Html => render_html(title, &blocks),
+        };
+        DocPage {
+            name: name.to_string(),
+            file_name: format!("{name}.{}", format.extension()),
+            content,
+        }
+    }
+}
+
+fn named_types<'t>(t: &'t Type, names: &mut Vec<&'t str>) {
+    match t.kind() {
+        TypeKind::Named => names.push(t.type_name()),
+        TypeKind::List(inner) | TypeKind::Option(inner) => named_types(inner, names),
+        TypeKind::Map(key, value) => {
+            named_types(key, names);
+            named_types(value, names);
+        }
+        TypeKind::UnsignedLong => {}
+    }
+}
+
+fn render_markdown(title: &str, blocks: &[Block]) -> String {
+    let inlines = |inlines: &[Inline]| {
+        inlines
+            .iter()
+            .map(|i| match i {
+                Inline::Text(text) => text.replace('|', "\\|"),
+                Inline::Code(code) => format!("`{code}`"),
+                Inline::Link(name) => format!("[{name}]({name}.md)"),
+            })
+            .collect::<String>()
+    };
+    let mut sections = vec![format!("# {title}")];
+    for block in blocks {
+        match block {
+            Block::Paragraph(paragraph) => sections.push(inlines(paragraph)),
+            Block::Table(header, rows) => {
+                let mut lines = vec![
+                    format!("| {} |", header.join(" | ")),
+                    format!("|{}", " --- |".repeat(header.len())),
+                ];
+                for row in rows {
+                    let cells = row.iter().map(|c| inlines(c)).collect::<Vec<String>>();
+                    lines.push(format!("| {} |", cells.join(" | ")));
+                }
+                sections.push(lines.join("\n"));
+            }
+        }
+    }
+    let mut markdown = sections.join("\n\n");
+    markdown.push('\n');
+    markdown
+}
+
+fn render_html(title: &str, blocks: &[Block]) -> String {
+    let inlines = |inlines: &[Inline]| {
+        inlines
+            .iter()
+            .map(|i| match i {
+                Inline::Text(text) => escape_html(text),
+                Inline::Code(code) => format!("<code>{}</code>", escape_html(code)),
+                Inline::Link(name) => {
+                    let name = escape_html(name);
+                    format!("<a href=\"{name}.html\">{name}</a>")
+                }
+            })
+            .collect::<String>()
+    };
+    let title = escape_html(title);
+    let mut lines = vec![
+        "<!DOCTYPE html>".to_string(),
+        "<html>".to_string(),
+        "<head>".to_string(),
+        "<meta charset=\"utf-8\">".to_string(),
+        format!("<title>{title}</title>"),
+        "</head>".to_string(),
+        "<body>".to_string(),
+        format!("<h1>{title}</h1>"),
+    ];
+    for block in blocks {
+        match block {
+            Block::Paragraph(paragraph) => lines.push(format!("<p>{}</p>", inlines(paragraph))),
+            Block::Table(header, rows) => {
+                lines.push("<table>".to_string());
+                let header = header
+                    .iter()
+                    .map(|h| format!("<th>{h}</th>"))
+                    .collect::<String>();
+                lines.push(format!("<thead><tr>{header}</tr></thead>"));
+                lines.push("<tbody>".to_string());
+                for row in rows {
+                    let cells = row
+                        .iter()
+                        .map(|c| format!("<td>{}</td>", inlines(c)))
+                        .collect::<String>();
+                    lines.push(format!("<tr>{cells}</tr>"));
+                }
+                lines.push("</tbody>".to_string());
+                lines.push("</table>".to_string());
+            }
+        }
+    }
+    lines.push("</body>".to_string());
+    lines.push("</html>".to_string());
+    let mut html = lines.join("\n");
+    html.push('\n');
+    html
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as rdc;
+    use crate::ir::TypeTarget;
+    use crate::{rdc_polymorphic, RDC};
+    use std::collections::HashMap;
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Order {
+        order_id: String,
+        #[serde(rename = "items")]
+        lines: Vec<OrderLine>,
+        note: Option<String>,
+        status: Status,
+        attributes: HashMap<String, u64>,
+        payment: Box<dyn Payment>,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct OrderLine {
+        sku: String,
+        quantity: i32,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Status {
+        Open,
+        #[serde(rename = "done")]
+        Closed,
+    }
+
+    trait Payment {}
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Card {
+        number: String,
+    }
+    impl Payment for Card {}
+
+    rdc_polymorphic!(dyn Payment => Card);
+
+    fn config() -> DocsConfig {
+        DocsConfig::new()
+            .with_title("Orders API")
+            .with_type_description("Order", "An order placed by a customer.")
+            .with_field_description("Order", "order_id", "Unique | stable id.")
+            .with_field_description("Status", "closed", "The order was delivered.")
+    }
+
+    #[test]
+    fn markdown_pages() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Order>();
+        let format = DateTimeFormat::new("yyyy-MM-dd").with_timezone("UTC");
+        ir.set_field_format("Order", "order_id", format).unwrap();
+        ir.set_field_format("Order", "note", DateTimeFormat::new("HH:mm"))
+            .unwrap();
+        let pages = generate_docs(&ir, &config()).unwrap();
+        let page = |name: &str| pages.iter().find(|p| p.name() == name).unwrap();
+        let index = page("index");
+        assert_eq!(index.file_name(), "index.md");
+        println!("{}", index.content());
+        assert!(index
+            .content()
+            .starts_with("# Orders API\n\n| Type | Kind | Description |"));
+        assert!(index
+            .content()
+            .contains("| [Order](Order.md) | Object | An order placed by a customer. |"));
+        assert!(index
+            .content()
+            .contains("| [Status](Status.md) | Enum |  |"));
+        assert!(index
+            .content()
+            .contains("| [Payment](Payment.md) | Union |  |"));
+
+        let order = page("Order").content();
+        println!("{order}");
+        assert!(order.starts_with("# Order\n\nObject\n\nAn order placed by a customer.\n\n"));
+        assert!(order.contains(
+            "| `order_id` | `string` | yes | Unique \\| stable id. Format: `yyyy-MM-dd`, in UTC. |"
+        ));
+        assert!(order.contains("| `items` | list of [OrderLine](OrderLine.md) | yes |  |"));
+        assert!(order.contains("| `note` | `string` | no | Format: `HH:mm`. |"));
+        assert!(order.contains("| `attributes` | map from `string` to `uint64` | yes |  |"));
+        assert!(!order.contains("Referenced by"));
+
+        let status = page("Status").content();
+        assert!(status.contains("| `\"done\"` | The order was delivered. |"));
+        assert!(status.ends_with("Referenced by [Order](Order.md).\n"));
+
+        let payment = page("Payment").content();
+        println!("{payment}");
+        assert!(payment.contains("Values are objects with the variant name as the only key."));
+        assert!(payment.contains("| `Card` | [Card](Card.md) |"));
+        assert!(page("Card")
+            .content()
+            .contains("Referenced by [Payment](Payment.md)."));
+    }
+
+    #[test]
+    fn html_pages() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Order>();
+        let pages = generate_docs(&ir, &config().with_format(DocsFormat::Html)).unwrap();
+        let order = pages.iter().find(|p| p.name() == "Order").unwrap();
+        assert_eq!(order.file_name(), "Order.html");
+        let content = order.content();
+        println!("{content}");
+        assert!(content.starts_with("<!DOCTYPE html>"));
+        assert!(content.contains("<title>Order</title>"));
+        assert!(content.contains(
+            "<tr><td><code>items</code></td><td>list of <a href=\"OrderLine.html\">OrderLine</a></td><td>yes</td><td></td></tr>"
+        ));
+        assert!(content.contains("<td>Unique | stable id.</td>"));
+    }
+}
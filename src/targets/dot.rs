@@ -0,0 +1,247 @@
+use crate::errors::Error;
+use crate::ir::{DataEnumVariant, IntermediateRepresentation, Type, TypeKind};
+use std::collections::{BTreeMap, HashSet};
+use std::io::Write;
+
+/// This function generates a Graphviz DOT graph of the dependencies between the types in the IR.
+///
+/// Nodes are the types, shaped by their kind: boxes for structs, ellipses for enums,
+/// hexagons for data enums and octagons for polymorphic types.
+/// Edges point from a type to the types it references and are labeled with the fields referencing them.
+/// Variants of polymorphic types are connected with dashed edges.
+/// Types are clustered by the Rust modules they are declared in, types added manually are not clustered.
+///
+/// Since the IR contains the types added with `ir.add` and everything they pull in,
+/// the graph of a single type shows its whole transitive dependency tree.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::targets::dot::generate_dependency_graph;
+/// use rdc::RDC;
+///
+/// #[derive(RDC)]
+/// struct Order {
+///     lines: Vec<OrderLine>,
+/// }
+///
+/// #[derive(RDC)]
+/// struct OrderLine {
+///     sku: String,
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add::<Order>();
+/// let graph = generate_dependency_graph(&ir).unwrap();
+/// assert!(graph.contains("\"Order\" -> \"OrderLine\" [label=\"lines\"];"));
+/// ```
+pub fn generate_dependency_graph(ir: &IntermediateRepresentation) -> Result<String, Error> {
+    ir.validate()?;
+    let mut nodes: Vec<(&str, &str)> = Vec::new();
+    nodes.extend(
+        ir.structs()
+            .iter()
+            .map(|s| (s.self_type().type_name().as_str(), "box")),
+    );
+    nodes.extend(
+        ir.enums()
+            .iter()
+            .map(|e| (e.self_type().type_name().as_str(), "ellipse")),
+    );
+    nodes.extend(
+        ir.data_enums()
+            .iter()
+            .map(|de| (de.self_type().type_name().as_str(), "hexagon")),
+    );
+    nodes.extend(
+        ir.polymorphics()
+            .iter()
+            .map(|p| (p.self_type().type_name().as_str(), "octagon")),
+    );
+    let names = nodes
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<HashSet<&str>>();
+
+    // Labels of edges, grouped by source and target, so that multiple fields referencing the same type share an edge.
+    let mut edges: BTreeMap<(String, String), Vec<String>> = BTreeMap::new();
+    let mut add_edges = |source: &str, label: &str, t: &Type| {
+        let mut targets = Vec::new();
+        named_types(t, &mut targets);
+        for target in targets.into_iter().filter(|t| names.contains(t)) {
+            let key = (source.to_string(), target.to_string());
+            let labels = edges.entry(key).or_default();
+            if !labels.iter().any(|l| l == label) {
+                labels.push(label.to_string());
+            }
+        }
+    };
+    for s in ir.structs() {
+        for f in s.fields() {
+            add_edges(s.self_type().type_name(), f.json_name(), f.field_type());
+        }
+    }
+    for de in ir.data_enums() {
+        let name = de.self_type().type_name();
+        for v in de.variants() {
+            match v {
+                DataEnumVariant::Unit { .. } => {}
+                DataEnumVariant::Tuple { fields, .. } => {
+                    for t in fields {
+                        add_edges(name, v.json_name(), t);
+                    }
+                }
+                DataEnumVariant::Object { fields, .. } => {
+                    for f in fields {
+                        add_edges(name, v.json_name(), f.field_type());
+                    }
+                }
+            }
+        }
+    }
+
+    let mut clusters: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for (name, shape) in &nodes {
+        let module = ir.rust_module(name).unwrap_or("");
+        clusters
+            .entry(module)
+            .or_default()
+            .push(format!("\"{name}\" [shape={shape}];"));
+    }
+    let mut lines = vec!["digraph types {".to_string(), "  rankdir=LR;".to_string()];
+    for (module, declarations) in clusters {
+        if module.is_empty() {
+            lines.extend(declarations.into_iter().map(|d| format!("  {d}")));
+        } else {
+            lines.push(format!("  subgraph \"cluster_{module}\" {{"));
+            lines.push(format!("    label=\"{module}\";"));
+            lines.extend(declarations.into_iter().map(|d| format!("    {d}")));
+            lines.push("  }".to_string());
+        }
+    }
+    for ((source, target), labels) in edges {
+        lines.push(format!(
+            "  \"{source}\" -> \"{target}\" [label=\"{}\"];",
+            labels.join(", ")
+        ));
+    }
+    for p in ir.polymorphics() {
+        for v in p.variants() {
+            lines.push(format!(
+                "  \"{}\" -> \"{}\" [style=dashed];",
+                p.self_type().type_name(),
+                v.variant_type().type_name()
+            ));
+        }
+    }
+    lines.push("}".to_string());
+    let mut graph = lines.join("\n");
+    graph.push('\n');
+    Ok(graph)
+}
+
+/// This function generates a dependency graph and writes it to the given file.
+pub fn write_dependency_graph(
+    ir: &IntermediateRepresentation,
+    file_path: &str,
+) -> Result<(), Error> {
+    let graph = generate_dependency_graph(ir)?;
+    if let Some(parent) = std::path::Path::new(file_path).parent() {
+        std::fs::create_dir_all(parent).map_err(|_| Error::new("Failed to create directory"))?;
+    }
+    let mut file =
+        std::fs::File::create(file_path).map_err(|_| Error::new("Failed to create file"))?;
+    file.write_all(graph.as_bytes())
+        .map_err(|_| Error::new("Failed to write to file"))
+}
+
+fn named_types<'t>(t: &'t Type, names: &mut Vec<&'t str>) {
+    match t.kind() {
+        TypeKind::Named => names.push(t.type_name()),
+        TypeKind::List(inner) | TypeKind::Option(inner) => named_types(inner, names),
+        TypeKind::Map(key, value) => {
+            named_types(key, names);
+            named_types(value, names);
+        }
+        TypeKind::UnsignedLong => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{module_path, TypeTarget};
+
+    mod orders {
+        use super::payments::Payment;
+        use crate as rdc;
+        use crate::RDC;
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub struct Order {
+            #[serde(rename = "items")]
+            pub lines: Vec<OrderLine>,
+            pub gifts: Option<Vec<OrderLine>>,
+            pub status: Status,
+            pub payment: Box<dyn Payment>,
+        }
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub struct OrderLine {
+            pub sku: String,
+        }
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub enum Status {
+            Open,
+            Closed,
+        }
+    }
+
+    mod payments {
+        use crate as rdc;
+        use crate::{rdc_polymorphic, RDC};
+
+        pub trait Payment {}
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub struct Card {
+            pub number: String,
+        }
+        impl Payment for Card {}
+
+        rdc_polymorphic!(dyn Payment => Card);
+    }
+
+    #[test]
+    fn dependency_graph() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<orders::Order>();
+        let graph = generate_dependency_graph(&ir).unwrap();
+        println!("{graph}");
+        assert!(graph.starts_with("digraph types {\n  rankdir=LR;\n"));
+        assert!(graph.contains(
+            "  subgraph \"cluster_rdc::targets::dot::tests::orders\" {\n    label=\"rdc::targets::dot::tests::orders\";\n"
+        ));
+        assert!(graph.contains("    \"Order\" [shape=box];\n"));
+        assert!(graph.contains("    \"Status\" [shape=ellipse];\n"));
+        assert!(graph.contains("  subgraph \"cluster_rdc::targets::dot::tests::payments\" {"));
+        assert!(graph.contains("    \"Card\" [shape=box];\n    \"Payment\" [shape=octagon];\n"));
+        assert!(graph.contains("\"Order\" -> \"OrderLine\" [label=\"items, gifts\"];"));
+        assert!(graph.contains("\"Order\" -> \"Status\" [label=\"status\"];"));
+        assert!(graph.contains("\"Payment\" -> \"Card\" [style=dashed];"));
+        assert!(graph.ends_with("}\n"));
+    }
+
+    #[test]
+    fn module_paths() {
+        assert_eq!(module_path("shop::orders::Order"), "shop::orders");
+        assert_eq!(module_path("shop::Wrapper<shop::ids::Id>"), "shop");
+        assert_eq!(module_path("alloc::boxed::Box<dyn shop::Payment>"), "shop");
+        assert_eq!(module_path("Order"), "");
+    }
+}
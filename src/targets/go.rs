@@ -0,0 +1,692 @@
+use crate::errors::Error;
+use crate::ir::{
+    DataEnum, DataEnumVariant, Enum, FlagsFormat, IntermediateRepresentation, Polymorphic, Struct,
+    Type, TypeKind,
+};
+use crate::targets::json::Json;
+use std::cell::Cell;
+use std::collections::{BTreeSet, HashSet};
+use std::io::Write;
+
+/// This function generates a Go source file of the given package with types for the JSON of the types in the IR,
+/// marshaled with `encoding/json`.
+///
+/// Structs are translated to structs with exported fields in Pascal case and `json:"..."` tags holding the JSON names.
+/// Optional fields are pointers, which are `nil` when the field is missing and are written as `null`, like serde
+/// writes `None`. Unit enums become string types with a constant per variant, e.g. `RoleAdmin`.
+/// Data enums and polymorphic types become tagged unions: a struct with a `Variant` field holding one of
+/// the variant types, e.g. `ShapeCircle`, and `MarshalJSON` and `UnmarshalJSON` methods following serde's tagging.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::targets::go::generate_go_code;
+/// use rdc::RDC;
+///
+/// #[derive(RDC)]
+/// struct User {
+///     name: String,
+///     nickname: Option<String>,
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add::<User>();
+/// let code = generate_go_code(&ir, "api").unwrap();
+/// assert!(code.contains("package api\n"));
+/// assert!(code.contains(
+///     "type User struct {\n\tName     string  `json:\"name\"`\n\tNickname *string `json:\"nickname\"`\n}\n"
+/// ));
+/// ```
+pub fn generate_go_code(ir: &IntermediateRepresentation, package: &str) -> Result<String, Error> {
+    ir.validate()?;
+    ir.reject_transparent_structs("Go")?;
+    let generator = GoGenerator::new(ir);
+    let mut definitions = Vec::new();
+    for s in ir.structs() {
+        definitions.push(generator.generate_struct(s)?);
+    }
+    for e in ir.enums() {
+        definitions.push(generator.generate_enum(e));
+    }
+    for de in ir.data_enums() {
+        definitions.push(generator.generate_data_enum(de)?);
+    }
+    for p in ir.polymorphics() {
+        definitions.push(generator.generate_polymorphic(p)?);
+    }
+
+    let mut imports = BTreeSet::new();
+    if generator.raw_json.get() || !ir.data_enums().is_empty() || !ir.polymorphics().is_empty() {
+        imports.insert("encoding/json");
+    }
+    if !ir.data_enums().is_empty() || !ir.polymorphics().is_empty() {
+        imports.insert("fmt");
+    }
+    let mut code = format!("// Code generated by rdc. DO NOT EDIT.\n\npackage {package}\n");
+    if !imports.is_empty() {
+        code.push_str("\nimport (\n");
+        for import in imports {
+            code.push_str(&format!("\t{}\n", Json::string(import).render()));
+        }
+        code.push_str(")\n");
+    }
+    for definition in definitions {
+        code.push('\n');
+        for line in definition {
+            code.push_str(&line);
+            code.push('\n');
+        }
+    }
+    Ok(code)
+}
+
+/// This function generates a Go source file and writes it to the given path, e.g. `internal/api/models.go`.
+pub fn write_go_code(
+    ir: &IntermediateRepresentation,
+    package: &str,
+    file_path: &str,
+) -> Result<(), Error> {
+    let code = generate_go_code(ir, package)?;
+    if let Some(parent) = std::path::Path::new(file_path).parent() {
+        std::fs::create_dir_all(parent).map_err(|_| Error::new("Failed to create directory"))?;
+    }
+    let mut file =
+        std::fs::File::create(file_path).map_err(|_| Error::new("Failed to create file"))?;
+    file.write_all(code.as_bytes())
+        .map_err(|_| Error::new("Failed to write to file"))
+}
+
+struct GoGenerator {
+    types: HashSet<String>,
+    /// Set when a field holds arbitrary JSON, which is a `json.RawMessage`.
+    raw_json: Cell<bool>,
+}
+
+impl GoGenerator {
+    fn new(ir: &IntermediateRepresentation) -> Self {
+        let types = ir
+            .structs()
+            .iter()
+            .map(|s| s.self_type())
+            .chain(ir.enums().iter().map(|e| e.self_type()))
+            .chain(ir.data_enums().iter().map(|de| de.self_type()))
+            .chain(ir.polymorphics().iter().map(|p| p.self_type()))
+            .map(|t| t.type_name().clone())
+            .collect();
+        Self {
+            types,
+            raw_json: Cell::new(false),
+        }
+    }
+
+    fn generate_struct(&self, s: &Struct) -> Result<Vec<String>, Error> {
+        let name = s.self_type().type_name();
+        let fields = s
+            .fields()
+            .iter()
+            .map(|f| {
+                Ok(vec![
+                    f.name().as_pascal_case(),
+                    self.type_name(f.field_type())?,
+                    json_tag(f.json_name()),
+                ])
+            })
+            .collect::<Result<Vec<Vec<String>>, Error>>()?;
+        Ok(struct_declaration(name, fields))
+    }
+
+    fn generate_enum(&self, e: &Enum) -> Vec<String> {
+        let name = e.self_type().type_name();
+        match e.flags() {
+            Some(FlagsFormat::Bits) => vec![format!("type {name} uint64")],
+            Some(FlagsFormat::Names) => vec![format!("type {name} string")],
+            None => {
+                let mut lines = vec![format!("type {name} string")];
+                if !e.variants().is_empty() {
+                    let constants = e
+                        .variants()
+                        .iter()
+                        .map(|v| {
+                            vec![
+                                format!("{name}{}", v.name().as_pascal_case()),
+                                name.clone(),
+                                format!("= {}", Json::string(v.json_name()).render()),
+                            ]
+                        })
+                        .collect();
+                    lines.push(String::new());
+                    lines.push("const (".to_string());
+                    lines.extend(align(constants, "\t"));
+                    lines.push(")".to_string());
+                }
+                lines
+            }
+        }
+    }
+
+    fn generate_data_enum(&self, de: &DataEnum) -> Result<Vec<String>, Error> {
+        let name = de.self_type().type_name();
+        let mut lines = union_declaration(name);
+        for v in de.variants() {
+            let variant_type = format!("{name}{}", v.name().as_pascal_case());
+            lines.push(String::new());
+            match v {
+                DataEnumVariant::Unit { .. } => {
+                    lines.push(format!("type {variant_type} struct{{}}"));
+                }
+                DataEnumVariant::Tuple { fields, .. } => {
+                    let fields = fields
+                        .iter()
+                        .enumerate()
+                        .map(|(i, t)| Ok(vec![tuple_field(i, fields.len()), self.type_name(t)?]))
+                        .collect::<Result<Vec<Vec<String>>, Error>>()?;
+                    lines.extend(struct_declaration(&variant_type, fields));
+                }
+                DataEnumVariant::Object { fields, .. } => {
+                    let fields = fields
+                        .iter()
+                        .map(|f| {
+                            Ok(vec![
+                                f.name().as_pascal_case(),
+                                self.type_name(f.field_type())?,
+                                json_tag(f.json_name()),
+                            ])
+                        })
+                        .collect::<Result<Vec<Vec<String>>, Error>>()?;
+                    lines.extend(struct_declaration(&variant_type, fields));
+                }
+            }
+            lines.push(String::new());
+            lines.push(format!("func ({variant_type}) is{name}() {{}}"));
+        }
+
+        lines.push(String::new());
+        lines.push(format!("func (v {name}) MarshalJSON() ([]byte, error) {{"));
+        // The variable is only declared when a variant has a payload, because Go rejects unused variables.
+        let payloads = de
+            .variants()
+            .iter()
+            .any(|v| !matches!(v, DataEnumVariant::Unit { .. }));
+        lines.push(match payloads {
+            true => "\tswitch variant := v.Variant.(type) {".to_string(),
+            false => "\tswitch v.Variant.(type) {".to_string(),
+        });
+        for v in de.variants() {
+            let variant_type = format!("{name}{}", v.name().as_pascal_case());
+            let json_name = Json::string(v.json_name()).render();
+            lines.push(format!("\tcase {variant_type}:"));
+            lines.push(match v {
+                DataEnumVariant::Unit { .. } => format!("\t\treturn json.Marshal({json_name})"),
+                DataEnumVariant::Tuple { fields, .. } if fields.len() == 1 => {
+                    format!("\t\treturn json.Marshal(map[string]any{{{json_name}: variant.Value}})")
+                }
+                DataEnumVariant::Tuple { fields, .. } => {
+                    let values = (0..fields.len())
+                        .map(|i| format!("variant.{}", tuple_field(i, fields.len())))
+                        .collect::<Vec<String>>();
+                    format!(
+                        "\t\treturn json.Marshal(map[string]any{{{json_name}: []any{{{}}}}})",
+                        values.join(", ")
+                    )
+                }
+                DataEnumVariant::Object { .. } => {
+                    format!("\t\treturn json.Marshal(map[string]any{{{json_name}: variant}})")
+                }
+            });
+        }
+        lines.push("\tdefault:".to_string());
+        lines.push(format!(
+            "\t\treturn nil, fmt.Errorf(\"unknown variant of {name}: %T\", v.Variant)"
+        ));
+        lines.push("\t}".to_string());
+        lines.push("}".to_string());
+
+        lines.push(String::new());
+        lines.push(format!(
+            "func (v *{name}) UnmarshalJSON(data []byte) error {{"
+        ));
+        lines.push("\tvar name string".to_string());
+        lines.push("\tif err := json.Unmarshal(data, &name); err == nil {".to_string());
+        lines.push("\t\tswitch name {".to_string());
+        for v in de.variants() {
+            if let DataEnumVariant::Unit { json_name, .. } = v {
+                lines.push(format!("\t\tcase {}:", Json::string(json_name).render()));
+                lines.push(format!(
+                    "\t\t\tv.Variant = {name}{}{{}}",
+                    v.name().as_pascal_case()
+                ));
+                lines.push("\t\t\treturn nil".to_string());
+            }
+        }
+        lines.push("\t\t}".to_string());
+        lines.push(format!(
+            "\t\treturn fmt.Errorf(\"unknown variant of {name}: %q\", name)"
+        ));
+        lines.push("\t}".to_string());
+        let mut cases = Vec::new();
+        for v in de.variants() {
+            let variant_type = format!("{name}{}", v.name().as_pascal_case());
+            let mut body = Vec::new();
+            match v {
+                DataEnumVariant::Unit { .. } => continue,
+                DataEnumVariant::Tuple { fields, .. } if fields.len() > 1 => {
+                    body.push("var values []json.RawMessage".to_string());
+                    body.extend(unmarshal("value", "&values"));
+                    body.push(format!("if len(values) != {} {{", fields.len()));
+                    body.push(format!(
+                        "\treturn fmt.Errorf(\"expected {} values of {name}.{}, got %d\", len(values))",
+                        fields.len(),
+                        v.name().as_pascal_case()
+                    ));
+                    body.push("}".to_string());
+                    body.push(format!("var variant {variant_type}"));
+                    for i in 0..fields.len() {
+                        body.extend(unmarshal(
+                            &format!("values[{i}]"),
+                            &format!("&variant.{}", tuple_field(i, fields.len())),
+                        ));
+                    }
+                }
+                DataEnumVariant::Tuple { .. } => {
+                    body.push(format!("var variant {variant_type}"));
+                    body.extend(unmarshal("value", "&variant.Value"));
+                }
+                DataEnumVariant::Object { .. } => {
+                    body.push(format!("var variant {variant_type}"));
+                    body.extend(unmarshal("value", "&variant"));
+                }
+            }
+            body.push("v.Variant = variant".to_string());
+            body.push("return nil".to_string());
+            cases.push((Json::string(v.json_name()).render(), body));
+        }
+        lines.extend(unmarshal_wrapped(name, "a string or an object", cases));
+        lines.push("}".to_string());
+        Ok(lines)
+    }
+
+    fn generate_polymorphic(&self, p: &Polymorphic) -> Result<Vec<String>, Error> {
+        let name = p.self_type().type_name();
+        let mut variants = Vec::new();
+        for v in p.variants() {
+            let variant_type = self.type_name(v.variant_type())?;
+            if !self.types.contains(&variant_type) {
+                return Err(Error::new(&format!(
+                    "`{variant_type}` is a variant of `{name}`, but only generated types can have methods in Go"
+                )));
+            }
+            variants.push((Json::string(v.json_name()).render(), variant_type));
+        }
+        let mut lines = union_declaration(name);
+        for (_, variant_type) in &variants {
+            lines.push(String::new());
+            lines.push(format!("func ({variant_type}) is{name}() {{}}"));
+        }
+
+        lines.push(String::new());
+        lines.push(format!("func (v {name}) MarshalJSON() ([]byte, error) {{"));
+        lines.push("\tvar tag string".to_string());
+        lines.push("\tswitch v.Variant.(type) {".to_string());
+        for (json_name, variant_type) in &variants {
+            lines.push(format!("\tcase {variant_type}:"));
+            lines.push(format!("\t\ttag = {json_name}"));
+        }
+        lines.push("\tdefault:".to_string());
+        lines.push(format!(
+            "\t\treturn nil, fmt.Errorf(\"unknown variant of {name}: %T\", v.Variant)"
+        ));
+        lines.push("\t}".to_string());
+        match p.tag() {
+            Some(tag) => {
+                lines.push("\tdata, err := json.Marshal(v.Variant)".to_string());
+                lines.push("\tif err != nil {".to_string());
+                lines.push("\t\treturn nil, err".to_string());
+                lines.push("\t}".to_string());
+                lines.push("\tvar object map[string]json.RawMessage".to_string());
+                lines.push("\tif err := json.Unmarshal(data, &object); err != nil {".to_string());
+                lines.push("\t\treturn nil, err".to_string());
+                lines.push("\t}".to_string());
+                lines.push(format!(
+                    "\tobject[{}], _ = json.Marshal(tag)",
+                    Json::string(tag).render()
+                ));
+                lines.push("\treturn json.Marshal(object)".to_string());
+            }
+            None => {
+                lines.push("\treturn json.Marshal(map[string]any{tag: v.Variant})".to_string());
+            }
+        }
+        lines.push("}".to_string());
+
+        lines.push(String::new());
+        lines.push(format!(
+            "func (v *{name}) UnmarshalJSON(data []byte) error {{"
+        ));
+        let cases = |source: &str| {
+            variants
+                .iter()
+                .map(|(json_name, variant_type)| {
+                    let mut body = vec![format!("var variant {variant_type}")];
+                    body.extend(unmarshal(source, "&variant"));
+                    body.push("v.Variant = variant".to_string());
+                    body.push("return nil".to_string());
+                    (json_name.clone(), body)
+                })
+                .collect::<Vec<(String, Vec<String>)>>()
+        };
+        match p.tag() {
+            Some(tag) => {
+                lines.push("\tvar header struct {".to_string());
+                lines.push(format!("\t\tTag string {}", json_tag(tag)));
+                lines.push("\t}".to_string());
+                lines.extend(indent(unmarshal("data", "&header"), 1));
+                lines.push("\tswitch header.Tag {".to_string());
+                for (json_name, body) in cases("data") {
+                    lines.push(format!("\tcase {json_name}:"));
+                    lines.extend(indent(body, 2));
+                }
+                lines.push("\t}".to_string());
+                lines.push(format!(
+                    "\treturn fmt.Errorf(\"unknown variant of {name}: %q\", header.Tag)"
+                ));
+            }
+            None => lines.extend(unmarshal_wrapped(name, "an object", cases("value"))),
+        }
+        lines.push("}".to_string());
+        Ok(lines)
+    }
+
+    /// Returns the Go type of a value.
+    fn type_name(&self, t: &Type) -> Result<String, Error> {
+        match t.kind() {
+            TypeKind::List(element) => Ok(format!("[]{}", self.type_name(element)?)),
+            TypeKind::Map(key, value) => Ok(format!(
+                "map[{}]{}",
+                self.type_name(key)?,
+                self.type_name(value)?
+            )),
+            TypeKind::Option(inner) => Ok(format!("*{}", self.type_name(inner)?)),
+            TypeKind::UnsignedLong => Ok("uint64".to_string()),
+            TypeKind::Named => match t.type_name().as_str() {
+                "Byte" => Ok("int8".to_string()),
+                "Short" => Ok("int16".to_string()),
+                "Integer" => Ok("int32".to_string()),
+                "Long" => Ok("int64".to_string()),
+                "Float" => Ok("float32".to_string()),
+                "Double" => Ok("float64".to_string()),
+                "Boolean" => Ok("bool".to_string()),
+                "String" => Ok("string".to_string()),
+                "com.fasterxml.jackson.databind.JsonNode" => {
+                    self.raw_json.set(true);
+                    Ok("json.RawMessage".to_string())
+                }
+                name if self.types.contains(name) => Ok(name.to_string()),
+                name => Err(Error::new(&format!("`{name}` has no Go counterpart"))),
+            },
+        }
+    }
+}
+
+/// Returns the name of a field of a tuple variant, `Value` for a single one and `Value0`, `Value1`, ... otherwise.
+fn tuple_field(index: usize, count: usize) -> String {
+    match count {
+        1 => "Value".to_string(),
+        _ => format!("Value{index}"),
+    }
+}
+
+fn json_tag(json_name: &str) -> String {
+    format!("`json:{}`", Json::string(json_name).render())
+}
+
+/// Returns a struct declaration with aligned fields, like `gofmt` formats them.
+fn struct_declaration(name: &str, fields: Vec<Vec<String>>) -> Vec<String> {
+    if fields.is_empty() {
+        return vec![format!("type {name} struct{{}}")];
+    }
+    let mut lines = vec![format!("type {name} struct {{")];
+    lines.extend(align(fields, "\t"));
+    lines.push("}".to_string());
+    lines
+}
+
+/// Returns the wrapper struct of a tagged union and the interface implemented by its variants.
+fn union_declaration(name: &str) -> Vec<String> {
+    vec![
+        format!("// {name}Variant is implemented by the variants of {name}."),
+        format!("type {name}Variant interface {{"),
+        format!("\tis{name}()"),
+        "}".to_string(),
+        String::new(),
+        format!("type {name} struct {{"),
+        format!("\tVariant {name}Variant"),
+        "}".to_string(),
+    ]
+}
+
+/// Returns statements unmarshaling `source` into `target`, returning the error if it fails.
+fn unmarshal(source: &str, target: &str) -> Vec<String> {
+    vec![
+        format!("if err := json.Unmarshal({source}, {target}); err != nil {{"),
+        "\treturn err".to_string(),
+        "}".to_string(),
+    ]
+}
+
+/// Returns statements unmarshaling an object with a single key naming the variant into `key` and `value`,
+/// followed by a switch over the key with the given cases, which read the variant from `value`.
+fn unmarshal_wrapped(name: &str, expected: &str, cases: Vec<(String, Vec<String>)>) -> Vec<String> {
+    let mut lines = vec![
+        "\tvar object map[string]json.RawMessage".to_string(),
+        "\tif err := json.Unmarshal(data, &object); err != nil {".to_string(),
+        "\t\treturn err".to_string(),
+        "\t}".to_string(),
+        "\tif len(object) != 1 {".to_string(),
+        format!("\t\treturn fmt.Errorf(\"expected {name} to be {expected} with a single key\")"),
+        "\t}".to_string(),
+    ];
+    lines.push(match cases.is_empty() {
+        true => "\tfor key := range object {".to_string(),
+        false => "\tfor key, value := range object {".to_string(),
+    });
+    lines.push("\t\tswitch key {".to_string());
+    for (json_name, body) in cases {
+        lines.push(format!("\t\tcase {json_name}:"));
+        lines.extend(indent(body, 3));
+    }
+    lines.push("\t\t}".to_string());
+    lines.push(format!(
+        "\t\treturn fmt.Errorf(\"unknown variant of {name}: %q\", key)"
+    ));
+    lines.push("\t}".to_string());
+    lines.push("\treturn nil".to_string());
+    lines
+}
+
+fn indent(lines: Vec<String>, depth: usize) -> Vec<String> {
+    let prefix = "\t".repeat(depth);
+    lines.into_iter().map(|l| format!("{prefix}{l}")).collect()
+}
+
+/// Returns rows with all the columns but the last padded to the same width, separated with a space.
+fn align(rows: Vec<Vec<String>>, prefix: &str) -> Vec<String> {
+    let columns = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    let widths = (0..columns)
+        .map(|i| {
+            rows.iter()
+                .filter(|r| i + 1 < r.len())
+                .map(|r| r[i].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<usize>>();
+    rows.into_iter()
+        .map(|row| {
+            let last = row.len() - 1;
+            let cells = row
+                .into_iter()
+                .enumerate()
+                .map(|(i, cell)| match i == last {
+                    true => cell,
+                    false => format!("{cell:width$}", width = widths[i]),
+                })
+                .collect::<Vec<String>>();
+            format!("{prefix}{}", cells.join(" "))
+        })
+        .collect()
+}
+
+/// This macro generates a Go source file with types for all the provided types and their dependencies.
+/// The package is `models` unless it is given.
+///
+/// Example:
+/// ```rust
+/// use rdc::{rdc_go, RDC};
+///
+/// #[derive(RDC)]
+/// enum Role {
+///     Admin,
+///     Member,
+/// }
+///
+/// let code = rdc_go!(package = "accounts"; Role).unwrap();
+/// assert!(code.contains("package accounts\n"));
+/// assert!(code.contains("const (\n\tRoleAdmin  Role = \"Admin\"\n\tRoleMember Role = \"Member\"\n)\n"));
+/// ```
+#[macro_export]
+macro_rules! rdc_go {
+    (package = $package:expr; $($type:ty),*) => {
+        {
+            let mut ir = $crate::ir::IntermediateRepresentation::new($crate::ir::TypeTarget::Java);
+            $(
+                ir.add::<$type>();
+            )*
+            $crate::targets::go::generate_go_code(&ir, $package)
+        }
+    };
+    ($($type:ty),*) => {
+        $crate::rdc_go!(package = "models"; $($type),*)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::{rdc_polymorphic, RDC};
+    use std::collections::HashMap;
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct User {
+        #[serde(rename = "user-name")]
+        name: String,
+        nickname: Option<String>,
+        roles: Vec<Role>,
+        limits: HashMap<Role, Option<i64>>,
+        shape: Shape,
+        pet: Box<dyn Pet>,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Role {
+        Admin,
+        #[serde(rename = "member")]
+        Member,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Circle {
+        radius: f64,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Shape {
+        Point,
+        Circle(Circle),
+        Line(f64, Option<f64>),
+        Rect { width: f64, height: Option<f64> },
+    }
+
+    trait Pet {}
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Dog {
+        name: String,
+    }
+    impl Pet for Dog {}
+
+    rdc_polymorphic!(dyn Pet, tag = "kind" => Dog);
+
+    #[test]
+    fn generate_structs_and_enums() {
+        let code = rdc_go!(User).unwrap();
+        assert!(code.starts_with(
+            "// Code generated by rdc. DO NOT EDIT.\n\npackage models\n\nimport (\n\t\"encoding/json\"\n\t\"fmt\"\n)\n"
+        ));
+        assert!(code.contains(
+            "type User struct {\n\
+             \tName     string          `json:\"user-name\"`\n\
+             \tNickname *string         `json:\"nickname\"`\n\
+             \tRoles    []Role          `json:\"roles\"`\n\
+             \tLimits   map[Role]*int64 `json:\"limits\"`\n\
+             \tShape    Shape           `json:\"shape\"`\n\
+             \tPet      Pet             `json:\"pet\"`\n\
+             }\n"
+        ));
+        assert!(code.contains(
+            "type Role string\n\nconst (\n\tRoleAdmin  Role = \"Admin\"\n\tRoleMember Role = \"member\"\n)\n"
+        ));
+    }
+
+    #[test]
+    fn generate_tagged_unions() {
+        let code = rdc_go!(User).unwrap();
+        assert!(code.contains(
+            "type Shape struct {\n\tVariant ShapeVariant\n}\n\ntype ShapePoint struct{}\n\nfunc (ShapePoint) isShape() {}\n"
+        ));
+        assert!(code.contains("type ShapeLine struct {\n\tValue0 float64\n\tValue1 *float64\n}\n"));
+        assert!(code.contains(
+            "type ShapeRect struct {\n\tWidth  float64  `json:\"width\"`\n\tHeight *float64 `json:\"height\"`\n}\n"
+        ));
+        assert!(code.contains(
+            "\tcase ShapeLine:\n\t\treturn json.Marshal(map[string]any{\"Line\": []any{variant.Value0, variant.Value1}})\n"
+        ));
+        assert!(code.contains(
+            "\t\tcase \"Circle\":\n\
+             \t\t\tvar variant ShapeCircle\n\
+             \t\t\tif err := json.Unmarshal(value, &variant.Value); err != nil {\n\
+             \t\t\t\treturn err\n\
+             \t\t\t}\n\
+             \t\t\tv.Variant = variant\n\
+             \t\t\treturn nil\n"
+        ));
+        assert!(code.contains("func (Dog) isPet() {}\n"));
+        assert!(code.contains("\tvar header struct {\n\t\tTag string `json:\"kind\"`\n\t}\n"));
+        assert!(code.contains("\tobject[\"kind\"], _ = json.Marshal(tag)\n"));
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Plain {
+        id: u64,
+    }
+
+    #[test]
+    fn imports_only_used_packages() {
+        let code = rdc_go!(package = "plain"; Plain).unwrap();
+        assert_eq!(
+            code,
+            "// Code generated by rdc. DO NOT EDIT.\n\
+             \n\
+             package plain\n\
+             \n\
+             type Plain struct {\n\
+             \tId uint64 `json:\"id\"`\n\
+             }\n"
+        );
+    }
+}
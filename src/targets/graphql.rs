@@ -0,0 +1,212 @@
+use crate::errors::Error;
+use crate::ir::{IntermediateRepresentation, Type, TypeKind};
+use std::collections::BTreeSet;
+
+/// This function generates a GraphQL schema (SDL) with object types, enums and unions for the types in the IR.
+///
+/// Structs are translated to object types with fields named after their JSON names,
+/// unit enums to enums with values named like the constants of generated Java enums,
+/// and polymorphic types to unions of their variants.
+/// `i64` and `u64` do not fit into GraphQL `Int`, so they use `Long` and `BigInteger` scalars,
+/// which are declared when used. Maps and data enums have no GraphQL counterpart and are reported as errors.
+///
+/// The generated Java classes can serve the schema directly with the wiring from
+/// `rdc::targets::java::generate_graphql_wiring`.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::targets::graphql::generate_graphql_schema;
+/// use rdc::RDC;
+///
+/// #[derive(RDC)]
+/// struct User {
+///     name: String,
+///     nickname: Option<String>,
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add::<User>();
+/// let schema = generate_graphql_schema(&ir).unwrap();
+/// assert!(schema.contains("type User {\n  name: String!\n  nickname: String\n}"));
+/// ```
+pub fn generate_graphql_schema(ir: &IntermediateRepresentation) -> Result<String, Error> {
+    ir.validate()?;
+    ir.reject_transparent_structs("GraphQL")?;
+    if let Some(de) = ir.data_enums().first() {
+        return Err(Error::new(&format!(
+            "Data enum `{}` can not be represented in GraphQL",
+            de.self_type().type_name()
+        )));
+    }
+    let mut scalars = BTreeSet::new();
+    let mut definitions = Vec::new();
+    for s in ir.structs() {
+        let mut lines = vec![format!("type {} {{", s.self_type().type_name())];
+        for field in s.fields() {
+            check_name(field.json_name())?;
+            lines.push(format!(
+                "  {}: {}",
+                field.json_name(),
+                field_type(field.field_type(), &mut scalars)?
+            ));
+        }
+        lines.push("}".to_string());
+        definitions.push(lines.join("\n"));
+    }
+    for e in ir.enums() {
+        let mut lines = vec![format!("enum {} {{", e.self_type().type_name())];
+        for variant in e.variants() {
+            lines.push(format!("  {}", variant.name().as_upper_snake_case()));
+        }
+        lines.push("}".to_string());
+        definitions.push(lines.join("\n"));
+    }
+    for p in ir.polymorphics() {
+        let variants = p
+            .variants()
+            .iter()
+            .map(|v| v.variant_type().type_name().as_str())
+            .collect::<Vec<&str>>();
+        definitions.push(format!(
+            "union {} = {}",
+            p.self_type().type_name(),
+            variants.join(" | ")
+        ));
+    }
+    let mut schema = scalars
+        .into_iter()
+        .map(|s| format!("scalar {s}\n\n"))
+        .collect::<String>();
+    schema.push_str(&definitions.join("\n\n"));
+    schema.push('\n');
+    Ok(schema)
+}
+
+/// Returns the GraphQL type of a field. Fields are non-null unless they are optional.
+fn field_type(t: &Type, scalars: &mut BTreeSet<&'static str>) -> Result<String, Error> {
+    match t.kind() {
+        TypeKind::Option(inner) => {
+            let inner = field_type(inner, scalars)?;
+            Ok(inner.strip_suffix('!').unwrap_or(&inner).to_string())
+        }
+        _ => Ok(format!("{}!", nullable_type(t, scalars)?)),
+    }
+}
+
+fn nullable_type(t: &Type, scalars: &mut BTreeSet<&'static str>) -> Result<String, Error> {
+    match t.kind() {
+        TypeKind::List(element) => Ok(format!("[{}]", field_type(element, scalars)?)),
+        TypeKind::Option(_) => field_type(t, scalars),
+        TypeKind::UnsignedLong => {
+            scalars.insert("BigInteger");
+            Ok("BigInteger".to_string())
+        }
+        TypeKind::Map(_, _) => Err(Error::new(&format!(
+            "`{}` can not be represented in GraphQL",
+            t.type_name()
+        ))),
+        TypeKind::Named => Ok(match t.type_name().as_str() {
+            "Byte" | "Short" | "Integer" => "Int".to_string(),
+            "Long" => {
+                scalars.insert("Long");
+                "Long".to_string()
+            }
+            "Double" => "Float".to_string(),
+            name => name.to_string(),
+        }),
+    }
+}
+
+/// GraphQL names must start with a letter or underscore and contain only letters, digits and underscores.
+fn check_name(name: &str) -> Result<(), Error> {
+    let mut chars = name.chars();
+    let valid_start = chars
+        .next()
+        .map(|c| c.is_ascii_alphabetic() || c == '_')
+        .unwrap_or(false);
+    if valid_start && chars.all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        Ok(())
+    } else {
+        Err(Error::new(&format!("`{name}` is not a valid GraphQL name")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as rdc;
+    use crate::ir::TypeTarget;
+    use crate::{rdc_polymorphic, RDC};
+    use std::collections::HashMap;
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct User {
+        user_name: String,
+        age: Option<i32>,
+        tags: Vec<String>,
+        aliases: Option<Vec<Option<String>>>,
+        visits: u64,
+        role: Role,
+        avatar: Box<dyn Avatar>,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Role {
+        Admin,
+        SuperUser,
+    }
+
+    trait Avatar {}
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Image {
+        url: String,
+    }
+    impl Avatar for Image {}
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Initials {
+        letters: String,
+    }
+    impl Avatar for Initials {}
+
+    rdc_polymorphic!(dyn Avatar => Image, Initials);
+
+    #[test]
+    fn generate_schema() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<User>();
+        let schema = generate_graphql_schema(&ir).unwrap();
+        println!("{schema}");
+        assert!(schema.starts_with("scalar BigInteger\n\ntype "));
+        assert!(schema.contains("  user_name: String!\n"));
+        assert!(schema.contains("  age: Int\n"));
+        assert!(schema.contains("  tags: [String!]!\n"));
+        assert!(schema.contains("  aliases: [String]\n"));
+        assert!(schema.contains("  visits: BigInteger!\n"));
+        assert!(schema.contains("enum Role {\n  ADMIN\n  SUPER_USER\n}"));
+        assert!(schema.contains("union Avatar = Image | Initials"));
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Settings {
+        values: HashMap<String, String>,
+    }
+
+    #[test]
+    fn maps_are_not_supported() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Settings>();
+        let error = generate_graphql_schema(&ir).err().unwrap();
+        assert_eq!(
+            error.message(),
+            "`java.util.Map<String, String>` can not be represented in GraphQL"
+        );
+    }
+}
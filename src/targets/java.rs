@@ -1,25 +1,73 @@
 use crate::errors::Error;
-use crate::ir::IntermediateRepresentation;
+use crate::ir::{check_compatibility, selection, IntermediateRepresentation};
+use crate::targets::kotlin::KotlinFile;
 use std::io::Write;
 
+mod cg_assertions;
+mod cg_benchmark;
+mod cg_builder;
+mod cg_csv;
 mod cg_data_enum;
+mod cg_empty;
 mod cg_enum;
+mod cg_error;
+mod cg_filters;
+mod cg_fingerprint;
+mod cg_fixtures;
+mod cg_flags;
+mod cg_graphql;
+mod cg_interface;
+mod cg_json_schema;
+mod cg_kotlin_extensions;
+mod cg_migration;
+mod cg_newtype;
+mod cg_nullability;
+mod cg_parcelable;
+mod cg_polymorphic;
+mod cg_rdc_json;
+mod cg_rpc;
+mod cg_schema;
+mod cg_service;
+mod cg_spring;
 mod cg_struct;
 mod cg_utils;
+mod cg_views;
+mod cg_xml;
+mod config;
+mod context;
+mod imports;
+mod keywords;
 pub mod type_resolver;
 
-#[cfg(test)]
-mod tests;
+pub use config::{
+    AccessorStyle, CsvMapping, DataEnumEncoding, HttpClientStyle, JavaConfig, JavaFlavor,
+    NullableAnnotation, UnsignedLongMapping,
+};
+use context::JavaContext;
+
+/// This module contains runners that build and run generated Java code with Gradle, Maven or Docker.
+/// It is available with the `verify` feature and can be used to write round-trip tests for generated classes.
+#[cfg(any(test, feature = "verify"))]
+pub mod verify;
 
 /// This is a struct that represents a generated Java class.
 pub struct JavaClass {
     name: String,
     code: String,
+    package: Option<String>,
+    version: Option<String>,
+    warnings: Vec<String>,
 }
 
 impl JavaClass {
     pub fn new(name: String, code: String) -> Self {
-        JavaClass { name, code }
+        JavaClass {
+            name,
+            code,
+            package: None,
+            version: None,
+            warnings: Vec::new(),
+        }
     }
 
     pub fn from_tokens(name: String, tokens: genco::prelude::java::Tokens) -> Result<Self, Error> {
@@ -36,22 +84,434 @@ impl JavaClass {
     pub fn code(&self) -> &str {
         &self.code
     }
+
+    /// Returns the package of the class, if it does not belong to the package passed to `write_java`.
+    pub fn package(&self) -> Option<&str> {
+        self.package.as_deref()
+    }
+
+    /// Places the class in the given package instead of the one passed to `write_java`.
+    pub fn with_package<S: Into<String>>(mut self, package: S) -> Self {
+        self.package = Some(package.into());
+        self
+    }
+
+    /// Returns the API version of the class, which `write_java` appends to its package, e.g. `com.example.v2`.
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Places the class in a versioned subpackage, e.g. `v2`, so that multiple versions of a class can coexist.
+    pub fn with_version<S: Into<String>>(mut self, version: S) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Returns warnings reported while generating the class, e.g. about renamed identifiers.
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    fn with_warnings(mut self, warnings: Vec<String>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
+    /// Imports the classes the code refers to by their fully qualified names, unless their simple names are taken.
+    /// The names of the generated classes are never imported, since `write_java` may import them from other packages.
+    fn with_short_names(mut self, ctx: &JavaContext) -> Self {
+        self.code = imports::shorten_names(&self.code, &ctx.class_names());
+        self
+    }
+
+    fn in_package(mut self, package: Option<String>) -> Self {
+        self.package = package;
+        self
+    }
 }
 
 /// This function generates Java code from an IntermediateRepresentation.
 /// Result can be saved to files and compiled.
+/// It fails if the IR is not valid, e.g. when multiple types resolve to the same class name.
 pub fn generate_java_code(ir: &IntermediateRepresentation) -> Result<Vec<JavaClass>, Error> {
+    generate_java_code_with_config(ir, &JavaConfig::default())
+}
+
+/// This function generates Java code from an IntermediateRepresentation using the provided configuration.
+/// With `JavaConfig::with_compatibility_snapshot`, it also fails on breaking changes that are not acknowledged.
+pub fn generate_java_code_with_config(
+    ir: &IntermediateRepresentation,
+    config: &JavaConfig,
+) -> Result<Vec<JavaClass>, Error> {
     let mut classes = Vec::new();
-    for struct_ir in ir.structs() {
-        classes.push(cg_struct::generate_data_class(struct_ir)?);
+    generate_each_class(ir, config, &|_| true, &mut |_, class| {
+        classes.push(class?);
+        Ok(())
+    })?;
+    Ok(classes)
+}
+
+/// This function generates Java code only for the given types and services and the types they use, directly or not,
+/// e.g. to regenerate the part of a large IR that is being worked on.
+/// Helper classes enabled in the configuration, like `RdcJson`, are generated as well, since the selected classes may use them.
+///
+/// The classes are generated in the context of the whole IR, so they are the same as the ones `generate_java_code` returns,
+/// e.g. a variant of a polymorphic type still implements its interface.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::targets::java::generate_java_code_for;
+/// use rdc::RDC;
+///
+/// #[derive(RDC)]
+/// struct Invoice {
+///     number: String,
+///     lines: Vec<LineItem>,
+/// }
+///
+/// #[derive(RDC)]
+/// struct LineItem {
+///     sku: String,
+/// }
+///
+/// #[derive(RDC)]
+/// struct Customer {
+///     name: String,
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add::<Invoice>();
+/// ir.add::<Customer>();
+/// let classes = generate_java_code_for(&ir, &["Invoice"]).unwrap();
+/// assert_eq!(classes.len(), 2);
+/// assert!(classes.iter().all(|c| c.name() != "Customer"));
+/// ```
+pub fn generate_java_code_for(
+    ir: &IntermediateRepresentation,
+    type_names: &[&str],
+) -> Result<Vec<JavaClass>, Error> {
+    generate_java_code_for_with_config(ir, &JavaConfig::default(), type_names)
+}
+
+/// This function generates Java code only for the given types and services and the types they use,
+/// using the provided configuration. It fails if a name is not a type or a service in the IR.
+pub fn generate_java_code_for_with_config(
+    ir: &IntermediateRepresentation,
+    config: &JavaConfig,
+    type_names: &[&str],
+) -> Result<Vec<JavaClass>, Error> {
+    let selection = selection(ir, type_names)?;
+    let mut classes = Vec::new();
+    let include = |name: &str| selection.contains(name);
+    generate_each_class(ir, config, &include, &mut |_, class| {
+        classes.push(class?);
+        Ok(())
+    })?;
+    Ok(classes)
+}
+
+/// Generates the classes one by one and passes each of them to `emit` with the name of the type,
+/// service or helper it was generated for, so that it can be written before the next one is generated.
+/// Only the types and services accepted by `include` are generated, while helper classes are generated whenever enabled.
+/// It fails right away if the IR is not valid, while failures of single classes are left to `emit`.
+fn generate_each_class<F>(
+    ir: &IntermediateRepresentation,
+    config: &JavaConfig,
+    include: &dyn Fn(&str) -> bool,
+    emit: &mut F,
+) -> Result<(), Error>
+where
+    F: FnMut(&str, Result<JavaClass, Error>) -> Result<(), Error>,
+{
+    ir.validate()?;
+    config.check_names(ir)?;
+    if let Some(snapshot) = config.compatibility_snapshot() {
+        if std::path::Path::new(snapshot).exists() {
+            let old = IntermediateRepresentation::read_snapshot(snapshot)?;
+            check_compatibility(&old, ir, config.acknowledged_changes())?;
+        }
+    }
+    let ctx = JavaContext::new(ir, config);
+    ctx.check_shared_interfaces(ir)?;
+    let mut emit = |source: &str, class: Result<JavaClass, Error>, package: Option<String>| {
+        let warnings = ctx.take_warnings();
+        let class = class.map(|class| {
+            let class = class
+                .with_short_names(&ctx)
+                .with_warnings(warnings)
+                .in_package(package);
+            match config.version() {
+                Some(version) => class.with_version(version),
+                None => class,
+            }
+        });
+        emit(source, class)
+    };
+    let structs = ir
+        .structs()
+        .iter()
+        .filter(|s| include(s.self_type().type_name()))
+        .collect::<Vec<_>>();
+    for struct_ir in &structs {
+        let class = match struct_ir.is_transparent() {
+            true => cg_newtype::generate_newtype_class(struct_ir, &ctx),
+            false => cg_struct::generate_data_class(struct_ir, &ctx),
+        };
+        let package = ctx.package(struct_ir.self_type());
+        emit(struct_ir.self_type().type_name(), class, package)?;
+    }
+    if config.view_interfaces() {
+        for struct_ir in structs.iter().filter(|s| !s.is_transparent()) {
+            let class = cg_interface::generate_view_interface(struct_ir, &ctx);
+            let package = ctx.package(struct_ir.self_type());
+            emit(struct_ir.self_type().type_name(), class, package)?;
+        }
+    }
+    for name in ctx.shared_interface_names() {
+        emit(
+            name,
+            cg_interface::generate_shared_interface(name, &ctx),
+            None,
+        )?;
+    }
+    if config.fixtures() {
+        for struct_ir in &structs {
+            let class = cg_fixtures::generate_fixtures_class(struct_ir, ir, &ctx);
+            let package = ctx.package(struct_ir.self_type());
+            emit(struct_ir.self_type().type_name(), class, package)?;
+        }
+    }
+    if config.assertions() {
+        for struct_ir in &structs {
+            let class = cg_assertions::generate_assert_class(struct_ir, &ctx);
+            let package = ctx.package(struct_ir.self_type());
+            emit(struct_ir.self_type().type_name(), class, package)?;
+        }
     }
     for enum_ir in ir.enums() {
-        classes.push(cg_enum::generate_enum_class(enum_ir)?);
+        let type_name = enum_ir.self_type().type_name();
+        if !include(type_name) {
+            continue;
+        }
+        let class = cg_enum::generate_enum_class(enum_ir, &ctx);
+        emit(type_name, class, ctx.package(enum_ir.self_type()))?;
+        if config.error_exceptions() && config.is_error_type(type_name) {
+            let class = cg_error::generate_exception_class(enum_ir.self_type(), &ctx);
+            emit(type_name, class, ctx.package(enum_ir.self_type()))?;
+        }
     }
     for data_enum_ir in ir.data_enums() {
-        classes.push(cg_data_enum::generate_enum_data_class(data_enum_ir)?);
+        let type_name = data_enum_ir.self_type().type_name();
+        if !include(type_name) {
+            continue;
+        }
+        let class = cg_data_enum::generate_enum_data_class(data_enum_ir, &ctx);
+        emit(type_name, class, ctx.package(data_enum_ir.self_type()))?;
+        if cg_interface::has_data_enum_views(&ctx) {
+            let class = cg_interface::generate_data_enum_view_interface(data_enum_ir, &ctx);
+            emit(type_name, class, ctx.package(data_enum_ir.self_type()))?;
+        }
+        if config.error_exceptions() && config.is_error_type(type_name) {
+            let class = cg_error::generate_exception_class(data_enum_ir.self_type(), &ctx);
+            emit(type_name, class, ctx.package(data_enum_ir.self_type()))?;
+        }
     }
-    Ok(classes)
+    for polymorphic_ir in ir.polymorphics() {
+        let type_name = polymorphic_ir.self_type().type_name();
+        if !include(type_name) {
+            continue;
+        }
+        let class = cg_polymorphic::generate_polymorphic_interface(polymorphic_ir, &ctx);
+        emit(type_name, class, ctx.package(polymorphic_ir.self_type()))?;
+        if polymorphic_ir.is_envelope() {
+            let class = cg_polymorphic::generate_message_handler(polymorphic_ir, &ctx);
+            emit(type_name, class, ctx.package(polymorphic_ir.self_type()))?;
+        }
+    }
+    for service_ir in ir.services() {
+        let name = service_ir.name().as_pascal_case();
+        if include(&name) {
+            let class = cg_service::generate_service_interface(service_ir, &ctx);
+            emit(&name, class, None)?;
+        }
+    }
+    for rpc_ir in ir.rpc_services() {
+        let name = rpc_ir.name().as_pascal_case();
+        if !include(&name) {
+            continue;
+        }
+        emit(&name, cg_rpc::generate_rpc_interface(rpc_ir, &ctx), None)?;
+        emit(&name, cg_rpc::generate_rpc_client(rpc_ir, &ctx), None)?;
+    }
+    if config.rdc_json() {
+        emit("RdcJson", cg_rdc_json::generate_rdc_json_class(&ctx), None)?;
+    }
+    if !config.json_views().is_empty() {
+        emit("Views", cg_views::generate_views_class(&ctx), None)?;
+    }
+    if config.json_filters() {
+        emit(
+            "JsonFilters",
+            cg_filters::generate_filters_class(ir, &ctx),
+            None,
+        )?;
+    }
+    Ok(())
+}
+
+/// This function generates a class that wires the generated classes into a graphql-java `RuntimeWiring`,
+/// so that they can serve the schema from `rdc::targets::graphql::generate_graphql_schema` directly.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::targets::java::{generate_graphql_wiring, JavaConfig};
+/// use rdc::RDC;
+///
+/// #[derive(RDC)]
+/// struct User {
+///     user_name: String,
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add::<User>();
+/// let wiring = generate_graphql_wiring(&ir, &JavaConfig::new(), "GraphQLWiring").unwrap();
+/// assert!(wiring.code().contains("PropertyDataFetcher.fetching(\"userName\")"));
+/// ```
+pub fn generate_graphql_wiring(
+    ir: &IntermediateRepresentation,
+    config: &JavaConfig,
+    class_name: &str,
+) -> Result<JavaClass, Error> {
+    ir.validate()?;
+    let ctx = JavaContext::new(ir, config);
+    let class = cg_graphql::generate_wiring_class(ir, &ctx, class_name)?;
+    Ok(class
+        .with_short_names(&ctx)
+        .with_warnings(ctx.take_warnings()))
+}
+
+/// This function generates a JMH benchmark class measuring Jackson serialization and deserialization
+/// of every struct and data enum, so that the cost of the wire format can be tracked.
+/// Benchmarked instances come from fixtures classes, so `JavaConfig::with_fixtures` has to be enabled.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::targets::java::{generate_jmh_benchmark, JavaConfig};
+/// use rdc::RDC;
+///
+/// #[derive(RDC)]
+/// struct Invoice {
+///     number: String,
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add::<Invoice>();
+/// let config = JavaConfig::new().with_fixtures(true);
+/// let benchmark = generate_jmh_benchmark(&ir, &config, "SerializationBenchmark").unwrap();
+/// assert!(benchmark.code().contains("public Invoice deserializeInvoice()"));
+/// ```
+pub fn generate_jmh_benchmark(
+    ir: &IntermediateRepresentation,
+    config: &JavaConfig,
+    class_name: &str,
+) -> Result<JavaClass, Error> {
+    ir.validate()?;
+    let ctx = JavaContext::new(ir, config);
+    let class = cg_benchmark::generate_benchmark_class(ir, &ctx, class_name)?;
+    Ok(class
+        .with_short_names(&ctx)
+        .with_warnings(ctx.take_warnings()))
+}
+
+/// This function generates a Kotlin file with extension functions over the generated Java classes of structs:
+/// `copy` functions with the current values as defaults, `componentN` operators for destructuring
+/// and `OrNull` properties of optional fields. It eases using the Java classes from Kotlin without a separate Kotlin model.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::targets::java::{generate_kotlin_extensions, JavaConfig};
+/// use rdc::targets::kotlin::write_kotlin;
+/// use rdc::RDC;
+///
+/// #[derive(RDC)]
+/// struct User {
+///     name: String,
+///     nickname: Option<String>,
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add::<User>();
+/// let file = generate_kotlin_extensions(&ir, &JavaConfig::new(), "UserExtensions").unwrap();
+/// assert!(file.code().contains("operator fun User.component1(): String = getName()"));
+/// assert!(file.code().contains("val User.nicknameOrNull: String?"));
+/// write_kotlin(&[file], "com.example", "target/test-tmp/src/main/kotlin").unwrap();
+/// ```
+pub fn generate_kotlin_extensions(
+    ir: &IntermediateRepresentation,
+    config: &JavaConfig,
+    file_name: &str,
+) -> Result<KotlinFile, Error> {
+    ir.validate()?;
+    let ctx = JavaContext::new(ir, config);
+    cg_kotlin_extensions::generate_extensions_file(ir, &ctx, file_name)
+}
+
+/// This function generates a class that upgrades JSON payloads written with an old version of the types,
+/// e.g. one restored with `IntermediateRepresentation::read_snapshot`, to the shape of the new version.
+/// Every migrated struct gets a static `migrate<Class>(ObjectNode)` method.
+///
+/// Only mechanical changes are migrated: renamed fields, removed fields and added optional fields.
+/// Other changes, like added required fields or changed field types, are returned as warnings of the class,
+/// so that the missing migrations can be written by hand.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::targets::java::{generate_java_migrations, JavaConfig};
+///
+/// mod v1 {
+///     #[derive(rdc::RDC)]
+///     pub struct Invoice {
+///         pub number: String,
+///     }
+/// }
+///
+/// mod v2 {
+///     #[derive(rdc::RDC)]
+///     pub struct Invoice {
+///         #[serde(rename = "invoiceNumber")]
+///         pub number: String,
+///         pub currency: String,
+///     }
+/// }
+///
+/// let mut old = IntermediateRepresentation::new(TypeTarget::Java);
+/// old.add::<v1::Invoice>();
+/// let mut new = IntermediateRepresentation::new(TypeTarget::Java);
+/// new.add::<v2::Invoice>();
+/// let class = generate_java_migrations(&old, &new, &JavaConfig::new(), "Migrations").unwrap();
+/// assert!(class.code().contains("node.set(\"invoiceNumber\", node.remove(\"number\"));"));
+/// assert_eq!(
+///     class.warnings(),
+///     ["Required field `Invoice.currency` was added, so it needs a manual migration"]
+/// );
+/// ```
+pub fn generate_java_migrations(
+    old: &IntermediateRepresentation,
+    new: &IntermediateRepresentation,
+    config: &JavaConfig,
+    class_name: &str,
+) -> Result<JavaClass, Error> {
+    new.validate()?;
+    let ctx = JavaContext::new(new, config);
+    let class = cg_migration::generate_migration_class(old, new, &ctx, class_name)?;
+    Ok(class
+        .with_short_names(&ctx)
+        .with_warnings(ctx.take_warnings()))
 }
 
 /// This macro generates code for all the provided types and their dependencies.
@@ -81,8 +541,31 @@ pub fn generate_java_code(ir: &IntermediateRepresentation) -> Result<Vec<JavaCla
 /// assert!(classes.is_ok());
 /// assert_eq!(classes.unwrap().len(), 3);
 /// ```
+///
+/// A `JavaConfig` can be passed before the list of types:
+/// ```rust
+/// use rdc::{rdc_java, RDC};
+/// use rdc::targets::java::JavaConfig;
+///
+/// #[derive(RDC)]
+/// struct Invoice {
+///     id: String,
+/// }
+///
+/// let classes = rdc_java!(config = JavaConfig::new().with_class_suffix("Dto"); Invoice).unwrap();
+/// assert_eq!(classes[0].name(), "InvoiceDto");
+/// ```
 #[macro_export]
 macro_rules! rdc_java {
+    (config = $config:expr; $($type:ty),*) => {
+        {
+            let mut ir = $crate::ir::IntermediateRepresentation::new($crate::ir::TypeTarget::Java);
+            $(
+                ir.add::<$type>();
+            )*
+            $crate::targets::java::generate_java_code_with_config(&ir, &$config)
+        }
+    };
     ($($type:ty),*) => {
         {
             let mut ir = $crate::ir::IntermediateRepresentation::new($crate::ir::TypeTarget::Java);
@@ -95,6 +578,9 @@ macro_rules! rdc_java {
 }
 
 /// This function writes generated Java code to files.
+/// Classes are written to the given package, unless they carry their own, e.g. from `JavaConfig::with_module_package`.
+/// Versioned classes, e.g. from `JavaConfig::with_version`, are written to a subpackage named after their version.
+/// Classes referencing classes from other packages get imports for them, preferring classes of the same version.
 /// ```rust
 /// use rdc::targets::java::{JavaClass, write_java};
 /// use rdc::errors::Error;
@@ -112,28 +598,247 @@ macro_rules! rdc_java {
 /// │       └── MyStruct.java
 /// ```
 pub fn write_java(classes: &[JavaClass], package: &str, directory: &str) -> Result<(), Error> {
-    let mut path = std::path::PathBuf::from(directory);
-    path.push(package.replace('.', "/"));
-    std::fs::create_dir_all(&path).map_err(|_| Error::new("Failed to create directory"))?;
+    let locations = classes.iter().map(ClassLocation::of).collect::<Vec<_>>();
+    let mut sink = JavaDirectory::new(package, directory);
     for class in classes {
-        let mut file_path = path.clone();
-        file_path.push(format!("{}.java", class.name()));
-        let mut file =
-            std::fs::File::create(file_path).map_err(|_| Error::new("Failed to create file"))?;
-        let code = format!("package {};\n\n{}", package, class.code());
-        file.write_all(code.as_bytes())
-            .map_err(|_| Error::new("Failed to write to file"))?;
+        let (class_package, source) = java_source(class, &locations, package);
+        sink.write_file(&class_package, class.name(), &source)?;
     }
     Ok(())
 }
 
+/// This trait is implemented by destinations of Java source files written by `generate_and_write_java`.
+/// Implement it to write the files somewhere else than to a directory, e.g. to an archive.
+pub trait JavaSink {
+    /// Returns the package of classes that do not carry their own.
+    fn package(&self) -> &str;
+
+    /// Writes the source file of a class, which starts with the package declaration and the imports.
+    fn write_file(&mut self, package: &str, class_name: &str, source: &str) -> Result<(), Error>;
+}
+
+/// This is a sink writing Java source files to a directory, in subdirectories of their packages.
+pub struct JavaDirectory {
+    package: String,
+    directory: String,
+}
+
+impl JavaDirectory {
+    pub fn new<P: Into<String>, D: Into<String>>(package: P, directory: D) -> Self {
+        JavaDirectory {
+            package: package.into(),
+            directory: directory.into(),
+        }
+    }
+}
+
+impl JavaSink for JavaDirectory {
+    fn package(&self) -> &str {
+        &self.package
+    }
+
+    fn write_file(&mut self, package: &str, class_name: &str, source: &str) -> Result<(), Error> {
+        let mut path = std::path::PathBuf::from(&self.directory);
+        path.push(package.replace('.', "/"));
+        std::fs::create_dir_all(&path).map_err(|_| Error::new("Failed to create directory"))?;
+        path.push(format!("{class_name}.java"));
+        let mut file =
+            std::fs::File::create(path).map_err(|_| Error::new("Failed to create file"))?;
+        file.write_all(source.as_bytes())
+            .map_err(|_| Error::new("Failed to write to file"))
+    }
+}
+
+/// This function generates Java code and writes each class to the sink before generating the next one,
+/// so that large IRs are not held in memory at once.
+/// A class that fails to be generated or written does not stop the others. The function fails at the end
+/// with a summary of all the failures, while an invalid IR fails before anything is written.
+///
+/// Imports are resolved against the classes of the types in the IR and the classes written before,
+/// the same way `write_java` resolves them.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::targets::java::{generate_and_write_java, JavaConfig, JavaDirectory};
+/// use rdc::RDC;
+///
+/// #[derive(RDC)]
+/// struct Invoice {
+///     number: String,
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add::<Invoice>();
+/// let mut sink = JavaDirectory::new("com.example", "target/test-tmp/streaming/src/main/java");
+/// generate_and_write_java(&ir, &JavaConfig::new(), &mut sink).unwrap();
+/// ```
+pub fn generate_and_write_java(
+    ir: &IntermediateRepresentation,
+    config: &JavaConfig,
+    sink: &mut dyn JavaSink,
+) -> Result<(), Error> {
+    write_each_class(ir, config, &|_| true, sink)
+}
+
+/// This function writes only the given types and services and the types they use to the sink,
+/// like `generate_java_code_for` generates them, so that a part of a large IR can be regenerated in place.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::targets::java::{generate_and_write_java_for, JavaConfig, JavaDirectory};
+/// use rdc::RDC;
+///
+/// #[derive(RDC)]
+/// struct Invoice {
+///     number: String,
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add::<Invoice>();
+/// let mut sink = JavaDirectory::new("com.example", "target/test-tmp/selected/src/main/java");
+/// generate_and_write_java_for(&ir, &JavaConfig::new(), &["Invoice"], &mut sink).unwrap();
+/// ```
+pub fn generate_and_write_java_for(
+    ir: &IntermediateRepresentation,
+    config: &JavaConfig,
+    type_names: &[&str],
+    sink: &mut dyn JavaSink,
+) -> Result<(), Error> {
+    let selection = selection(ir, type_names)?;
+    write_each_class(ir, config, &|name| selection.contains(name), sink)
+}
+
+fn write_each_class(
+    ir: &IntermediateRepresentation,
+    config: &JavaConfig,
+    include: &dyn Fn(&str) -> bool,
+    sink: &mut dyn JavaSink,
+) -> Result<(), Error> {
+    let ctx = JavaContext::new(ir, config);
+    let mut locations = ir
+        .structs()
+        .iter()
+        .map(|s| s.self_type())
+        .chain(ir.enums().iter().map(|e| e.self_type()))
+        .chain(ir.data_enums().iter().map(|de| de.self_type()))
+        .chain(ir.polymorphics().iter().map(|p| p.self_type()))
+        .map(|t| ClassLocation {
+            name: ctx.class_name(t),
+            package: ctx.package(t),
+            version: config.version().map(|v| v.to_string()),
+        })
+        .collect::<Vec<ClassLocation>>();
+    let mut written = 0;
+    let mut failures = Vec::new();
+    generate_each_class(ir, config, include, &mut |source, class| {
+        let result = class.and_then(|class| {
+            let location = ClassLocation::of(&class);
+            if !locations.contains(&location) {
+                locations.push(location);
+            }
+            let package = sink.package().to_string();
+            let (class_package, code) = java_source(&class, &locations, &package);
+            sink.write_file(&class_package, class.name(), &code)
+        });
+        match result {
+            Ok(()) => written += 1,
+            Err(e) => failures.push(format!("`{source}`: {}", e.message())),
+        }
+        Ok(())
+    })?;
+    match failures.is_empty() {
+        true => Ok(()),
+        false => Err(Error::new(&format!(
+            "{} of {} classes failed: {}",
+            failures.len(),
+            written + failures.len(),
+            failures.join("; ")
+        ))),
+    }
+}
+
+/// The name and the package of a class, which other classes import it with.
+#[derive(PartialEq)]
+struct ClassLocation {
+    name: String,
+    package: Option<String>,
+    version: Option<String>,
+}
+
+impl ClassLocation {
+    fn of(class: &JavaClass) -> Self {
+        ClassLocation {
+            name: class.name().to_string(),
+            package: class.package().map(|p| p.to_string()),
+            version: class.version().map(|v| v.to_string()),
+        }
+    }
+
+    /// Returns the package the class is written to, with its version appended.
+    fn package_in(&self, package: &str) -> String {
+        let base = self.package.as_deref().unwrap_or(package);
+        match &self.version {
+            Some(version) => format!("{base}.{version}"),
+            None => base.to_string(),
+        }
+    }
+}
+
+/// Returns the package of a class and its source file, with imports of the classes it references from other packages.
+fn java_source(class: &JavaClass, classes: &[ClassLocation], package: &str) -> (String, String) {
+    let location = ClassLocation::of(class);
+    let class_package = location.package_in(package);
+    let mut imported: Vec<&str> = Vec::new();
+    let mut imports = String::new();
+    for other in classes {
+        let other_package = other.package_in(package);
+        if other_package == class_package
+            || imported.contains(&other.name.as_str())
+            || !references_class(class.code(), &other.name)
+        {
+            continue;
+        }
+        // Classes from the same package, e.g. of the same version, take precedence.
+        let shadowed = classes
+            .iter()
+            .any(|c| c.name == other.name && c.package_in(package) == class_package);
+        let preferred = classes
+            .iter()
+            .filter(|c| c.name == other.name && c.package_in(package) != class_package)
+            .find(|c| c.version == location.version)
+            .unwrap_or(other);
+        if !shadowed {
+            imported.push(&other.name);
+            imports.push_str(&format!(
+                "import {}.{};\n",
+                preferred.package_in(package),
+                other.name
+            ));
+        }
+    }
+    let code = format!("package {};\n\n{}{}", class_package, imports, class.code());
+    (class_package, code)
+}
+
+/// Returns true if the code contains the class name as a whole identifier.
+fn references_class(code: &str, class_name: &str) -> bool {
+    let is_identifier = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    code.match_indices(class_name).any(|(start, _)| {
+        let before = code[..start].chars().next_back();
+        let after = code[start + class_name.len()..].chars().next();
+        !before.is_some_and(is_identifier) && !after.is_some_and(is_identifier)
+    })
+}
+
 #[cfg(test)]
 mod writer_tests {
     #[allow(unused_imports)]
     use super::*;
     use crate as rdc;
     use crate::targets::java::write_java;
-    use crate::{rdc_java, RDC};
+    use crate::RDC;
 
     #[derive(RDC)]
     #[allow(unused)]
@@ -162,6 +867,35 @@ mod writer_tests {
         assert_eq!(results.unwrap().len(), 3);
     }
 
+    #[test]
+    fn test_class_name_prefix_and_suffix() {
+        let config = JavaConfig::new()
+            .with_class_prefix("Api")
+            .with_class_suffix("Dto");
+        let classes = rdc_java!(config = config; MyStruct, MyEnum).unwrap();
+        let class = |name: &str| classes.iter().find(|c| c.name() == name).unwrap().code();
+        let my_struct = class("ApiMyStructDto");
+        assert!(my_struct.contains("public class ApiMyStructDto"));
+        assert!(my_struct.contains("private ApiDependencyDto dependency;"));
+        assert!(my_struct.contains("private String field1;"));
+        assert!(class("ApiDependencyDto").contains("public class ApiDependencyDto"));
+        assert!(class("ApiMyEnumDto").contains("public enum ApiMyEnumDto"));
+    }
+
+    #[test]
+    fn test_unknown_config_names() {
+        let config = JavaConfig::new()
+            .with_xml_attribute("MyStruct", "field1")
+            .with_xml_attribute("MyStruct", "field2")
+            .with_type_description("Missing", "Not generated");
+        let error = rdc_java!(config = config; MyStruct).err().unwrap();
+        assert_eq!(
+            error.message(),
+            "`with_type_description` names `Missing`, which is not a type in the IR; \
+            `with_xml_attribute` names `MyStruct.field2`, which is not a struct field in the IR"
+        );
+    }
+
     #[test]
     fn test_write_java() {
         let results = rdc_java!(MyStruct, MyEnum);
@@ -184,4 +918,150 @@ mod writer_tests {
                 .exists()
         );
     }
+
+    #[test]
+    fn test_generate_and_write_java() {
+        let mut ir = crate::ir::IntermediateRepresentation::new(crate::ir::TypeTarget::Java);
+        ir.add::<MyStruct>();
+        ir.add::<MyEnum>();
+        let directory = "target/test-tmp/streaming-writer/src/main/java";
+        let mut sink = JavaDirectory::new("com.example", directory);
+        let config = JavaConfig::new().with_shared_interface("Named", ["name"]);
+        let error = generate_and_write_java(&ir, &config, &mut sink).unwrap_err();
+        assert_eq!(
+            error.message(),
+            "1 of 4 classes failed: `Named`: Shared interface `Named` is not generated, because no struct has all of its fields"
+        );
+        for name in ["MyStruct", "Dependency", "MyEnum"] {
+            let path = format!("{directory}/com/example/{name}.java");
+            assert!(std::fs::read_to_string(path)
+                .unwrap()
+                .starts_with("package com.example;\n\n"));
+        }
+    }
+
+    #[test]
+    fn test_generate_java_code_for() {
+        let mut ir = crate::ir::IntermediateRepresentation::new(crate::ir::TypeTarget::Java);
+        ir.add::<MyStruct>();
+        ir.add::<MyEnum>();
+        let config = JavaConfig::new().with_fixtures(true);
+        let classes = generate_java_code_for_with_config(&ir, &config, &["MyStruct"]).unwrap();
+        let names = classes.iter().map(|c| c.name()).collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "Dependency",
+                "MyStruct",
+                "DependencyFixtures",
+                "MyStructFixtures"
+            ]
+        );
+        let all = generate_java_code_with_config(&ir, &config).unwrap();
+        for class in &classes {
+            let full = all.iter().find(|c| c.name() == class.name()).unwrap();
+            assert_eq!(class.code(), full.code());
+        }
+        let error = generate_java_code_for(&ir, &["Missing"]).err().unwrap();
+        assert_eq!(
+            error.message(),
+            "`Missing` is not a type or a service in the IR"
+        );
+
+        let directory = "target/test-tmp/selected-writer/src/main/java";
+        let mut sink = JavaDirectory::new("com.example", directory);
+        generate_and_write_java_for(&ir, &JavaConfig::new(), &["MyEnum"], &mut sink).unwrap();
+        let exists = |name: &str| {
+            std::path::Path::new(&format!("{directory}/com/example/{name}.java")).exists()
+        };
+        assert!(exists("MyEnum"));
+        assert!(!exists("MyStruct"));
+    }
+
+    mod billing {
+        use crate as rdc;
+        use crate::RDC;
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub struct Invoice {
+            pub number: String,
+            pub customer: super::customers::Customer,
+        }
+    }
+
+    mod customers {
+        use crate as rdc;
+        use crate::RDC;
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub struct Customer {
+            pub name: String,
+        }
+    }
+
+    #[test]
+    fn test_write_java_packages() {
+        let module = module_path!();
+        let config = JavaConfig::new()
+            .with_module_package(module, "com.example")
+            .with_module_package(format!("{module}::customers"), "com.example.customers");
+        let classes = rdc_java!(config = config; billing::Invoice).unwrap();
+        let class = |name: &str| classes.iter().find(|c| c.name() == name).unwrap();
+        assert_eq!(class("Invoice").package(), Some("com.example"));
+        assert_eq!(class("Customer").package(), Some("com.example.customers"));
+        let directory = "target/test-tmp/packages/src/main/java";
+        write_java(&classes, "com.other", directory).unwrap();
+        let invoice =
+            std::fs::read_to_string(format!("{directory}/com/example/Invoice.java")).unwrap();
+        assert!(
+            invoice.starts_with("package com.example;\n\nimport com.example.customers.Customer;\n")
+        );
+        let customer =
+            std::fs::read_to_string(format!("{directory}/com/example/customers/Customer.java"))
+                .unwrap();
+        assert!(customer.starts_with("package com.example.customers;\n\nimport com.fasterxml"));
+        assert!(!references_class("CustomerDto customer;", "Customer"));
+        assert!(references_class("private Customer customer;", "Customer"));
+    }
+
+    mod v2 {
+        use super::customers::Customer;
+        use crate as rdc;
+        use crate::RDC;
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub struct Invoice {
+            pub number: String,
+            pub total: f64,
+            pub customer: Customer,
+        }
+    }
+
+    #[test]
+    fn test_write_java_versions() {
+        let module = module_path!();
+        let config = |version: &str| {
+            JavaConfig::new()
+                .with_module_package(format!("{module}::customers"), "com.example.customers")
+                .with_version(version)
+        };
+        let mut classes = rdc_java!(config = config("v1"); billing::Invoice).unwrap();
+        classes.extend(rdc_java!(config = config("v2"); v2::Invoice).unwrap());
+        assert!(classes.iter().all(|c| c.version().is_some()));
+        let directory = "target/test-tmp/versions/src/main/java";
+        write_java(&classes, "com.example", directory).unwrap();
+        let read = |path: &str| std::fs::read_to_string(format!("{directory}/{path}")).unwrap();
+        let v1 = read("com/example/v1/Invoice.java");
+        assert!(v1
+            .starts_with("package com.example.v1;\n\nimport com.example.customers.v1.Customer;\n"));
+        let v2 = read("com/example/v2/Invoice.java");
+        assert!(v2
+            .starts_with("package com.example.v2;\n\nimport com.example.customers.v2.Customer;\n"));
+        assert!(v2.contains("private Double total;"));
+        assert!(read("com/example/customers/v2/Customer.java")
+            .starts_with("package com.example.customers.v2;"));
+    }
 }
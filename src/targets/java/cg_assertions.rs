@@ -0,0 +1,101 @@
+use crate::errors::Error;
+use crate::ir::Struct;
+use crate::targets::java::context::JavaContext;
+use crate::targets::java::JavaClass;
+use genco::prelude::*;
+use genco::quote;
+
+/// This function generates an AssertJ assertion class `<Class>Assert` with a `has<Field>` method for every field,
+/// e.g. `assertThat(invoice).hasStatus(StatusDto.PAID)`.
+pub fn generate_assert_class(dc: &Struct, ctx: &JavaContext) -> Result<JavaClass, Error> {
+    let class_name = ctx.class_name(dc.self_type());
+    let assert_name = format!("{class_name}Assert");
+    let methods = dc
+        .fields()
+        .iter()
+        .map(|f| {
+            let accessor = ctx.accessor_name(f.name());
+            let property = ctx.field_name(f.name());
+            let method = format!("has{accessor}");
+            let getter = ctx.getter_name(f.name(), f.field_type());
+            let type_name = ctx.type_name(f.field_type());
+            let message = format!("Expected {property} to be <%s> but was <%s>");
+            quote!(
+                public $(&assert_name) $method($type_name expected) {
+                    isNotNull();
+                    if (!Objects.equals(actual.$(&getter)(), expected)) {
+                        failWithMessage($[str]($[const](message)), expected, actual.$(&getter)());
+                    }
+                    return this;
+                }
+            )
+        })
+        .collect::<Vec<java::Tokens>>();
+    let tokens: java::Tokens = quote!(
+        import java.util.Objects;
+        import org.assertj.core.api.AbstractAssert;
+
+        public class $(&assert_name) extends AbstractAssert<$(&assert_name), $(&class_name)> {
+            public $(&assert_name)($(&class_name) actual) {
+                super(actual, $(&assert_name).class);
+            }
+
+            public static $(&assert_name) assertThat($(&class_name) actual) {
+                return new $(&assert_name)(actual);
+            }
+
+            $(for m in methods => $m)
+        }
+    );
+    JavaClass::from_tokens(assert_name, tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::targets::java::JavaConfig;
+    use crate::{rdc_java, RDC};
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Invoice {
+        invoice_number: String,
+        lines: Vec<String>,
+        status: Status,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Status {
+        Draft,
+        Paid,
+    }
+
+    #[test]
+    fn assert_classes() {
+        let config = JavaConfig::new()
+            .with_assertions(true)
+            .with_class_suffix("Dto");
+        let classes = rdc_java!(config = config; Invoice).unwrap();
+        let code = classes
+            .iter()
+            .find(|c| c.name() == "InvoiceDtoAssert")
+            .unwrap()
+            .code();
+        println!("{code}");
+        assert!(code.contains("import org.assertj.core.api.AbstractAssert;"));
+        assert!(code.contains(
+            "public class InvoiceDtoAssert extends AbstractAssert<InvoiceDtoAssert, InvoiceDto>"
+        ));
+        assert!(code.contains("super(actual, InvoiceDtoAssert.class);"));
+        assert!(code.contains("public static InvoiceDtoAssert assertThat(InvoiceDto actual)"));
+        assert!(code.contains("public InvoiceDtoAssert hasInvoiceNumber(String expected)"));
+        assert!(code.contains("if (!Objects.equals(actual.getInvoiceNumber(), expected))"));
+        assert!(code.contains(
+            "failWithMessage(\"Expected invoiceNumber to be <%s> but was <%s>\", expected, actual.getInvoiceNumber());"
+        ));
+        assert!(code.contains("public InvoiceDtoAssert hasLines(List<String> expected)"));
+        assert!(code.contains("public InvoiceDtoAssert hasStatus(StatusDto expected)"));
+        assert!(classes.iter().all(|c| c.name() != "StatusDtoAssert"));
+    }
+}
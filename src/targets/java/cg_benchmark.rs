@@ -0,0 +1,138 @@
+use crate::errors::Error;
+use crate::ir::{IntermediateRepresentation, Name, Type};
+use crate::targets::java::cg_fixtures::fixture_value;
+use crate::targets::java::context::JavaContext;
+use crate::targets::java::JavaClass;
+use genco::prelude::*;
+use genco::quote;
+
+/// This function generates a JMH benchmark class measuring serialization and deserialization
+/// of a fixture instance of every struct and data enum.
+/// Instances come from the fixtures classes, so they have to be generated as well.
+pub fn generate_benchmark_class(
+    ir: &IntermediateRepresentation,
+    ctx: &JavaContext,
+    class_name: &str,
+) -> Result<JavaClass, Error> {
+    if !ctx.config().fixtures() {
+        return Err(Error::new(
+            "Benchmarks use fixtures classes, enable them with `JavaConfig::with_fixtures`",
+        ));
+    }
+    let benchmarked = ir
+        .structs()
+        .iter()
+        .map(|s| s.self_type())
+        .chain(ir.data_enums().iter().map(|de| de.self_type()))
+        .map(|t| Type::new(t.type_name().clone()))
+        .collect::<Vec<Type>>();
+    let mapper = if ctx.config().rdc_json() {
+        quote!(RdcJson.objectMapper())
+    } else {
+        quote!(new ObjectMapper())
+    };
+    let mut fields = Vec::new();
+    let mut setup = Vec::new();
+    let mut benchmarks = Vec::new();
+    for t in &benchmarked {
+        let type_name = ctx.type_name(t);
+        let name = t.type_name();
+        let value = Name::from_pascal_case(t.type_name()).as_camel_case();
+        let json = format!("{value}Json");
+        let fixture = fixture_value(t, ir, ctx);
+        fields.push(quote!(
+            private $(&type_name) $(&value);
+            private String $(&json);
+        ));
+        setup.push(quote!(
+            $(&value) = $fixture;
+            $(&json) = mapper.writeValueAsString($(&value));
+        ));
+        benchmarks.push(quote!(
+            @Benchmark
+            public String serialize$name() throws Exception {
+                return mapper.writeValueAsString($(&value));
+            }
+
+            @Benchmark
+            public $(&type_name) deserialize$name() throws Exception {
+                return mapper.readValue($(&json), $(&type_name).class);
+            }
+        ));
+    }
+    let tokens: java::Tokens = quote!(
+        import com.fasterxml.jackson.databind.ObjectMapper;
+        import java.util.concurrent.TimeUnit;
+        import org.openjdk.jmh.annotations.Benchmark;
+        import org.openjdk.jmh.annotations.BenchmarkMode;
+        import org.openjdk.jmh.annotations.Mode;
+        import org.openjdk.jmh.annotations.OutputTimeUnit;
+        import org.openjdk.jmh.annotations.Scope;
+        import org.openjdk.jmh.annotations.Setup;
+        import org.openjdk.jmh.annotations.State;
+
+        @State(Scope.Benchmark)
+        @BenchmarkMode(Mode.AverageTime)
+        @OutputTimeUnit(TimeUnit.MICROSECONDS)
+        public class $class_name {
+            private final ObjectMapper mapper = $mapper;
+            $(for f in fields => $f)
+
+            @Setup
+            public void setup() throws Exception {
+                $(for s in setup => $s)
+            }
+
+            $(for b in benchmarks => $b)
+        }
+    );
+    JavaClass::from_tokens(class_name.to_string(), tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::ir::{IntermediateRepresentation, TypeTarget};
+    use crate::targets::java::{generate_jmh_benchmark, JavaConfig};
+    use crate::RDC;
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Invoice {
+        number: String,
+        discount: Discount,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Discount {
+        Percent(i32),
+        None,
+    }
+
+    #[test]
+    fn benchmark_class() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Invoice>();
+        let config = JavaConfig::new().with_fixtures(true);
+        let class = generate_jmh_benchmark(&ir, &config, "SerializationBenchmark").unwrap();
+        let code = class.code();
+        println!("{code}");
+        assert!(code.contains("@State(Scope.Benchmark)"));
+        assert!(code.contains("public class SerializationBenchmark"));
+        assert!(code.contains("private final ObjectMapper mapper = new ObjectMapper();"));
+        assert!(code.contains("invoice = InvoiceFixtures.create();"));
+        assert!(code.contains("invoiceJson = mapper.writeValueAsString(invoice);"));
+        assert!(code.contains("discount = Discount.ofPercent(1);"));
+        assert!(code.contains("public String serializeInvoice() throws Exception"));
+        assert!(code.contains("return mapper.readValue(discountJson, Discount.class);"));
+    }
+
+    #[test]
+    fn benchmark_requires_fixtures() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Invoice>();
+        let result = generate_jmh_benchmark(&ir, &JavaConfig::new(), "SerializationBenchmark");
+        assert!(result.err().unwrap().message().contains("with_fixtures"));
+    }
+}
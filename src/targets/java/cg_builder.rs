@@ -0,0 +1,90 @@
+use crate::ir::Struct;
+use crate::targets::java::context::JavaContext;
+use genco::prelude::*;
+use genco::quote;
+
+/// This function generates a static `builder()` method and a nested `Builder` class.
+/// Fields that are not `Option` are required, so `build()` throws an `IllegalStateException` when they are missing.
+pub fn generate_builder(dc: &Struct, ctx: &JavaContext) -> java::Tokens {
+    let class_name = ctx.class_name(dc.self_type());
+    let fields = dc
+        .fields()
+        .iter()
+        .map(|f| (ctx.field_name(f.name()), ctx.type_name(f.field_type())))
+        .collect::<Vec<(String, String)>>();
+    let checks = dc
+        .fields()
+        .iter()
+        .filter(|f| f.is_required())
+        .map(|f| {
+            let name = ctx.field_name(f.name());
+            let message = format!("{name} is required");
+            quote! {
+                if ($(&name) == null) {
+                    throw new IllegalStateException($[str]($[const](message)));
+                }
+            }
+        })
+        .collect::<Vec<java::Tokens>>();
+    quote! {
+        public static Builder builder() {
+            return new Builder();
+        }
+
+        public static final class Builder {
+            $(for (name, type_name) in &fields => private $type_name $name;)
+
+            private Builder() {}
+
+            $(for (name, type_name) in &fields join ($['\n']) =>
+                public Builder $name($type_name $name) {
+                    this.$name = $name;
+                    return this;
+                }
+            )
+
+            public $(&class_name) build() {
+                $(for c in checks => $c)
+                $(&class_name) value = new $(&class_name)();
+                $(for (name, _) in &fields => value.$name = $name;)
+                return value;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::targets::java::JavaConfig;
+    use crate::{rdc_java, RDC};
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Invoice {
+        number: String,
+        note: Option<String>,
+        lines: Vec<String>,
+    }
+
+    #[test]
+    fn builder() {
+        let config = JavaConfig::new().with_builders(true);
+        let classes = rdc_java!(config = config; Invoice).unwrap();
+        let code = classes[0].code();
+        println!("{code}");
+        assert!(code.contains("public static Builder builder() { return new Builder(); }"));
+        assert!(code.contains("public static final class Builder"));
+        assert!(
+            code.contains("public Builder note(String note) { this.note = note; return this; }")
+        );
+        assert!(code.contains(
+            "if (number == null) { throw new IllegalStateException(\"number is required\"); }"
+        ));
+        assert!(code.contains("if (lines == null)"));
+        assert!(!code.contains("if (note == null)"));
+        assert!(code.contains("Invoice value = new Invoice();"));
+        assert!(code.contains("value.number = number;"));
+        assert!(!rdc_java!(Invoice).unwrap()[0].code().contains("Builder"));
+    }
+}
@@ -0,0 +1,164 @@
+use crate::ir::{Field, Struct, Type, TypeKind};
+use crate::targets::java::cg_utils::Annotation;
+use crate::targets::java::config::CsvMapping;
+use crate::targets::java::context::{ClassKind, JavaContext};
+use genco::prelude::*;
+use genco::quote;
+use std::collections::BTreeSet;
+
+/// Returns OpenCSV annotations of a field when OpenCSV mapping is enabled and the struct is flat.
+/// Columns of optional fields may be empty, all other columns are required.
+pub fn field_annotations(field: &Field, owner: &Struct, ctx: &JavaContext) -> Vec<Annotation> {
+    if ctx.config().csv() != CsvMapping::OpenCsv || !is_flat(owner, ctx) {
+        return Vec::new();
+    }
+    let json_name = field.json_name();
+    let annotation = match field.field_type().kind() {
+        TypeKind::Option(_) => quote!(@CsvBindByName(column = $[str]($[const](json_name)))),
+        _ => quote!(@CsvBindByName(column = $[str]($[const](json_name)), required = true)),
+    };
+    vec![("com.opencsv.bean.CsvBindByName", annotation)]
+}
+
+/// This function generates members describing the CSV columns of a class:
+/// a `CSV_COLUMNS` constant for OpenCSV, which does not keep the declaration order on its own,
+/// or a `csvSchema()` method for jackson-dataformat-csv.
+/// Structs with fields that do not fit into a single column are reported with a warning and are not mapped.
+pub fn generate_members(
+    dc: &Struct,
+    ctx: &JavaContext,
+    imports: &mut BTreeSet<&'static str>,
+) -> Option<java::Tokens> {
+    let mapping = ctx.config().csv();
+    if mapping == CsvMapping::None {
+        return None;
+    }
+    if !is_flat(dc, ctx) {
+        let type_name = dc.self_type().type_name();
+        for field in dc
+            .fields()
+            .iter()
+            .filter(|f| !is_column(f.field_type(), ctx))
+        {
+            ctx.warn(format!(
+                "`{type_name}.{}` is not a scalar, so `{type_name}` is not mapped to CSV",
+                field.name().as_snake_case()
+            ));
+        }
+        return None;
+    }
+    let columns = dc.fields().iter().map(|f| f.json_name());
+    match mapping {
+        CsvMapping::None => None,
+        CsvMapping::OpenCsv => Some(quote! {
+            public static final String[] CSV_COLUMNS = {$(for c in columns join (, ) => $[str]($[const](c)))};
+        }),
+        CsvMapping::JacksonCsv => {
+            imports.insert("com.fasterxml.jackson.dataformat.csv.CsvSchema");
+            Some(quote! {
+                public static CsvSchema csvSchema() {
+                    return CsvSchema.builder()
+                        $(for c in columns => .addColumn($[str]($[const](c))))
+                        .setUseHeader(true)
+                        .build();
+                }
+            })
+        }
+    }
+}
+
+fn is_flat(dc: &Struct, ctx: &JavaContext) -> bool {
+    dc.fields().iter().all(|f| is_column(f.field_type(), ctx))
+}
+
+/// Scalars and unit enums fit into a single column, other generated classes and collections do not.
+fn is_column(t: &Type, ctx: &JavaContext) -> bool {
+    match t.kind() {
+        TypeKind::Named => matches!(ctx.class_kind(t), None | Some(ClassKind::Enum)),
+        TypeKind::UnsignedLong => true,
+        TypeKind::Option(inner) => is_column(inner, ctx),
+        TypeKind::List(_) | TypeKind::Map(_, _) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::targets::java::{CsvMapping, JavaConfig};
+    use crate::{rdc_java, RDC};
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Payment {
+        payment_id: String,
+        #[serde(rename = "sum")]
+        amount: f64,
+        note: Option<String>,
+        status: Status,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Status {
+        Pending,
+        Settled,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Statement {
+        owner: String,
+        payments: Vec<Payment>,
+    }
+
+    #[test]
+    fn open_csv_annotations() {
+        let config = JavaConfig::new().with_csv(CsvMapping::OpenCsv);
+        let classes = rdc_java!(config = config; Payment).unwrap();
+        let code = classes
+            .iter()
+            .find(|c| c.name() == "Payment")
+            .unwrap()
+            .code();
+        println!("{code}");
+        assert!(code.contains("import com.opencsv.bean.CsvBindByName;"));
+        assert!(code.contains("@CsvBindByName(column = \"payment_id\", required = true)"));
+        assert!(code.contains("@CsvBindByName(column = \"sum\", required = true)"));
+        assert!(code.contains("@CsvBindByName(column = \"note\") private String note;"));
+        assert!(code.contains("@CsvBindByName(column = \"status\", required = true)"));
+        assert!(code.contains(
+            "public static final String[] CSV_COLUMNS = {\"payment_id\", \"sum\", \"note\", \"status\"};"
+        ));
+    }
+
+    #[test]
+    fn jackson_csv_schema() {
+        let config = JavaConfig::new().with_csv(CsvMapping::JacksonCsv);
+        let classes = rdc_java!(config = config; Payment).unwrap();
+        let code = classes
+            .iter()
+            .find(|c| c.name() == "Payment")
+            .unwrap()
+            .code();
+        println!("{code}");
+        assert!(code.contains("import com.fasterxml.jackson.dataformat.csv.CsvSchema;"));
+        assert!(code.contains("public static CsvSchema csvSchema()"));
+        assert!(code.contains(".addColumn(\"payment_id\")"));
+        assert!(code.contains(".addColumn(\"sum\")"));
+        assert!(code.contains(".setUseHeader(true)"));
+        assert!(!code.contains("CsvBindByName"));
+    }
+
+    #[test]
+    fn nested_types_are_not_mapped() {
+        let config = JavaConfig::new().with_csv(CsvMapping::OpenCsv);
+        let classes = rdc_java!(config = config; Statement).unwrap();
+        let statement = classes.iter().find(|c| c.name() == "Statement").unwrap();
+        assert!(!statement.code().contains("Csv"));
+        assert_eq!(statement.warnings().len(), 1);
+        assert!(statement.warnings()[0]
+            .contains("`Statement.payments` is not a scalar, so `Statement` is not mapped to CSV"));
+        let payment = classes.iter().find(|c| c.name() == "Payment").unwrap();
+        assert!(payment.code().contains("CSV_COLUMNS"));
+    }
+}
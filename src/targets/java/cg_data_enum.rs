@@ -1,32 +1,67 @@
 use crate::errors::Error;
-use crate::ir::{DataEnum, DataEnumObjectField, DataEnumStyle, DataEnumVariant, Type};
+use crate::ir::{DataEnum, DataEnumObjectField, DataEnumStyle, DataEnumVariant, Name};
 use crate::quote_iter;
 use crate::targets::java::cg_data_enum::external::{
     generate_external_deserializer, generate_external_serializer,
 };
-use crate::targets::java::cg_utils::Compact;
+use crate::targets::java::cg_utils::{
+    property_order, qualified_case_insensitive_annotation, Compact,
+};
+use crate::targets::java::config::DataEnumEncoding;
+use crate::targets::java::context::JavaContext;
 use crate::targets::java::JavaClass;
+use crate::targets::java::{cg_error, cg_fingerprint, cg_interface, cg_json_schema};
 use genco::prelude::*;
 use genco::quote;
 
 mod external;
+mod records;
+
+pub use records::record_name;
 
-pub fn generate_enum_data_class(de: &DataEnum) -> Result<JavaClass, Error> {
-    let class_name = de.name().as_pascal_case();
+pub fn generate_enum_data_class(de: &DataEnum, ctx: &JavaContext) -> Result<JavaClass, Error> {
+    if ctx.config().data_enum_encoding() == DataEnumEncoding::SealedRecords {
+        return records::generate_sealed_interface(de, ctx);
+    }
+    let class_name = ctx.class_name(de.self_type());
     let class_name_str = class_name.as_str();
 
+    let unknown = preserves_unknown_variants(de, ctx);
     let serializer_code = match de.style() {
-        DataEnumStyle::External => generate_external_serializer(de),
+        DataEnumStyle::External => generate_external_serializer(de, unknown, ctx),
     };
     let deserializer_code = match de.style() {
-        DataEnumStyle::External => generate_external_deserializer(de),
+        DataEnumStyle::External => generate_external_deserializer(de, unknown, ctx),
     };
-    let fields_code = generate_fields_code(de);
-    let variants_enum = generate_variants_enum(de);
+    if ctx.config().common_fields()
+        && has_base_variant(de)
+        && !shared_object_fields(de, ctx).is_empty()
+    {
+        ctx.warn(format!(
+            "Common fields of `{}` are not lifted, because it has a variant named `Base`",
+            de.self_type().type_name()
+        ));
+    }
+    let common_fields = common_fields(de, ctx);
+    let base_class = generate_base_class(&common_fields, ctx);
+    let common_getters = generate_common_getters(&common_fields, ctx);
+    let fields_code = generate_fields_code(de, &common_fields, ctx);
+    let unknown_code = unknown.then(|| generate_unknown_variant_code(class_name_str, ctx));
+    let variants_enum = generate_variants_enum(de, unknown);
+    let json_schema = cg_json_schema::generate_constant(de.self_type(), ctx, false);
+    let fingerprint = cg_fingerprint::generate_constant(de.self_type(), ctx, false);
+    let exhaustiveness_helpers = generate_exhaustiveness_helpers(class_name_str);
+    let value_methods = generate_wrapper_value_methods(de, class_name_str);
+    let error_methods = cg_error::generate_data_enum_methods(de, ctx);
+    let implements = cg_interface::has_data_enum_views(ctx).then(|| {
+        let view_name = cg_interface::data_enum_view_name(de, ctx);
+        quote!(implements $view_name)
+    });
     let tokens: java::Tokens = quote!(
         import com.fasterxml.jackson.annotation.JsonIgnore;
         import com.fasterxml.jackson.annotation.JsonInclude;
         import com.fasterxml.jackson.annotation.JsonProperty;
+        import com.fasterxml.jackson.annotation.JsonPropertyOrder;
         import com.fasterxml.jackson.core.*;
         import com.fasterxml.jackson.databind.DeserializationContext;
         import com.fasterxml.jackson.databind.JsonNode;
@@ -36,14 +71,18 @@ pub fn generate_enum_data_class(de: &DataEnum) -> Result<JavaClass, Error> {
         import com.fasterxml.jackson.databind.deser.std.StdDeserializer;
         import com.fasterxml.jackson.databind.node.ObjectNode;
         import com.fasterxml.jackson.databind.ser.std.StdSerializer;
-        import com.fasterxml.jackson.core.type.TypeReference;
+        import com.fasterxml.jackson.databind.type.TypeFactory;
 
         import java.io.IOException;
 
         @JsonInclude(JsonInclude.Include.NON_NULL)
         @JsonSerialize(using = $class_name_str.Serializer.class)
         @JsonDeserialize(using = $class_name_str.Deserializer.class)
-        public class $class_name_str {
+        public class $class_name_str $implements {
+            $json_schema
+
+            $fingerprint
+
             @JsonIgnore
             private final Variant variant;
 
@@ -55,8 +94,14 @@ pub fn generate_enum_data_class(de: &DataEnum) -> Result<JavaClass, Error> {
                 this.value = value;
             }
 
+            $common_getters
+
+            $base_class
+
             $fields_code
 
+            $unknown_code
+
             $serializer_code
 
             $deserializer_code
@@ -66,13 +111,19 @@ pub fn generate_enum_data_class(de: &DataEnum) -> Result<JavaClass, Error> {
             Variant getVariant() {
                 return variant;
             }
+
+            $exhaustiveness_helpers
+
+            $value_methods
+
+            $error_methods
         }
     );
 
     JavaClass::from_tokens(class_name, tokens)
 }
 
-fn generate_variants_enum(de: &DataEnum) -> java::Tokens {
+fn generate_variants_enum(de: &DataEnum, unknown: bool) -> java::Tokens {
     let variants = de
         .variants()
         .iter()
@@ -80,20 +131,333 @@ fn generate_variants_enum(de: &DataEnum) -> java::Tokens {
             let name = v.name().as_upper_snake_case();
             quote!($name)
         })
+        .chain(unknown.then(|| quote!(UNKNOWN)))
         .collect::<Vec<java::Tokens>>();
+    let known_variants = match unknown {
+        true => {
+            let unknown_set = quote!(java.util.EnumSet.of(UNKNOWN));
+            quote!(java.util.EnumSet.complementOf($unknown_set))
+        }
+        false => quote!(java.util.EnumSet.allOf(Variant.class)),
+    };
     let variants_enum = quote!(
         public enum Variant {
-            $(for v in variants join (,) => $v)
+            $(for v in variants join (,) => $v);
+
+            public static final java.util.Set<Variant> KNOWN_VARIANTS =
+                java.util.Collections.unmodifiableSet($known_variants);
         }
     );
     variants_enum
 }
 
-fn generate_fields_code(de: &DataEnum) -> java::Tokens {
+/// Returns whether unknown variants of the data enum are kept in an `UNKNOWN` variant.
+/// They are not when the data enum has a variant with the same name, which is reported with a warning.
+fn preserves_unknown_variants(de: &DataEnum, ctx: &JavaContext) -> bool {
+    let preserved = has_unknown_variant(de, ctx);
+    if ctx.config().unknown_variants() && !preserved {
+        ctx.warn(format!(
+            "Unknown variants of `{}` are not preserved, because it has a variant named `Unknown`",
+            de.self_type().type_name()
+        ));
+    }
+    preserved
+}
+
+/// Returns whether the data enum keeps unknown variants, which it does unless a variant is named `Unknown`.
+pub(crate) fn has_unknown_variant(de: &DataEnum, ctx: &JavaContext) -> bool {
+    ctx.config().unknown_variants()
+        && !de
+            .variants()
+            .iter()
+            .any(|v| v.name().as_upper_snake_case() == "UNKNOWN")
+}
+
+/// Returns the factory and the accessors of the `UNKNOWN` variant, which holds the JSON of a variant
+/// that was not known when the class was generated.
+fn generate_unknown_variant_code(class_name: &str, ctx: &JavaContext) -> java::Tokens {
+    let name = Name::from_snake_case("unknown");
+    let getter_name = &ctx.variant_getter_name(&name, None);
+    quote!(
+        public static $class_name ofUnknown(JsonNode json) {
+            return new $class_name(Variant.UNKNOWN, json);
+        }
+
+        public JsonNode $getter_name() {
+            if (variant != Variant.UNKNOWN) {
+                throw new IllegalStateException("Invalid variant: " + variant);
+            }
+            return (JsonNode) value;
+        }
+
+        public boolean isUnknown() {
+            return variant == Variant.UNKNOWN;
+        }
+    )
+}
+
+/// Returns helpers that detect variants the Java code does not handle,
+/// e.g. ones added on the Rust side after the handling code was written.
+fn generate_exhaustiveness_helpers(class_name: &str) -> java::Tokens {
+    let message = format!("Unhandled variants of {class_name}: ");
+    quote!(
+        public Variant variant() {
+            return variant;
+        }
+
+        public IllegalStateException unhandled() {
+            return new IllegalStateException("Unhandled variant: " + variant);
+        }
+
+        public static void assertExhaustive(Variant... handled) {
+            java.util.Set<Variant> missing = java.util.EnumSet.allOf(Variant.class);
+            missing.removeAll(java.util.Arrays.asList(handled));
+            if (!missing.isEmpty()) {
+                throw new IllegalStateException($[str]($[const](message)) + missing);
+            }
+        }
+    )
+}
+
+/// Returns `equals` and `hashCode` comparing the variant and its value, and `toString` like `Shape.Line(1.0, 2.0)`.
+fn generate_wrapper_value_methods(de: &DataEnum, class_name: &str) -> java::Tokens {
+    let unit_cases = de
+        .variants()
+        .iter()
+        .filter(|v| matches!(v, DataEnumVariant::Unit { .. }))
+        .map(|v| {
+            let case = v.name().as_upper_snake_case();
+            let text = format!("{class_name}.{}", v.name().as_pascal_case());
+            quote!(case $case: return $[str]($[const](text));)
+        })
+        .collect::<Vec<java::Tokens>>();
+    let unit_switch = (!unit_cases.is_empty()).then(|| {
+        quote!(
+            switch (variant) {
+                $(for c in unit_cases => $c)
+                default: break;
+            }
+        )
+    });
+    let prefix = format!("{class_name}.");
+    quote!(
+        @Override
+        public boolean equals(Object o) {
+            if (this == o) {
+                return true;
+            }
+            if (!(o instanceof $class_name)) {
+                return false;
+            }
+            $class_name other = ($class_name) o;
+            return variant == other.variant && java.util.Objects.equals(value, other.value);
+        }
+
+        @Override
+        public int hashCode() {
+            return java.util.Objects.hash(variant, value);
+        }
+
+        @Override
+        public String toString() {
+            $unit_switch
+            return $[str]($[const](prefix)) + value;
+        }
+    )
+}
+
+/// Returns `equals`, `hashCode` and `toString` of a class holding the value of a variant,
+/// e.g. the class of an object variant or the holder of a tuple variant.
+/// Members are pairs of an optional label and an expression reading the member, like `getName()` or `arg0`.
+fn generate_variant_value_methods(
+    class_name: &str,
+    variant_name: &str,
+    members: &[(Option<String>, String)],
+) -> java::Tokens {
+    let comparisons = members
+        .iter()
+        .map(|(_, member)| format!("java.util.Objects.equals({member}, other.{member})"))
+        .collect::<Vec<String>>();
+    let comparison = match comparisons.is_empty() {
+        true => "true".to_string(),
+        false => comparisons.join(" && "),
+    };
+    let hashed = members
+        .iter()
+        .map(|(_, member)| member.as_str())
+        .collect::<Vec<&str>>()
+        .join(", ");
+    let parts = members
+        .iter()
+        .enumerate()
+        .map(|(i, (label, member))| {
+            let separator = if i == 0 { "(" } else { ", " };
+            let text = match label {
+                Some(label) => format!("{separator}{label}="),
+                None => separator.to_string(),
+            };
+            quote!($[str]($[const](text)) + $member)
+        })
+        .collect::<Vec<java::Tokens>>();
+    let text = match parts.is_empty() {
+        true => quote!($[str]($[const](variant_name))),
+        false => quote!($[str]($[const](variant_name)) + $(for p in parts join ( + ) => $p) + ")"),
+    };
+    quote!(
+        @Override
+        public boolean equals(Object o) {
+            if (this == o) {
+                return true;
+            }
+            if (o == null || getClass() != o.getClass()) {
+                return false;
+            }
+            $class_name other = ($class_name) o;
+            return $comparison;
+        }
+
+        @Override
+        public int hashCode() {
+            return java.util.Objects.hash($hashed);
+        }
+
+        @Override
+        public String toString() {
+            return $text;
+        }
+    )
+}
+
+/// Returns the fields that all struct variants share, with the same JSON names and types, which are lifted into `Base`,
+/// or nothing if common fields are disabled, there are less than two struct variants or a variant is named `Base`.
+pub(crate) fn common_fields<'a>(
+    de: &'a DataEnum,
+    ctx: &JavaContext,
+) -> Vec<&'a DataEnumObjectField> {
+    if !ctx.config().common_fields() || has_base_variant(de) {
+        return Vec::new();
+    }
+    shared_object_fields(de, ctx)
+}
+
+fn has_base_variant(de: &DataEnum) -> bool {
+    de.variants()
+        .iter()
+        .any(|v| v.name().as_pascal_case() == "Base")
+}
+
+/// Returns the fields of the first struct variant that all the struct variants have, with the same JSON names and types,
+/// or none if there are less than two struct variants.
+fn shared_object_fields<'a>(de: &'a DataEnum, ctx: &JavaContext) -> Vec<&'a DataEnumObjectField> {
+    let variants = de
+        .variants()
+        .iter()
+        .filter_map(|v| match v {
+            DataEnumVariant::Object { fields, .. } => Some(fields),
+            _ => None,
+        })
+        .collect::<Vec<&Vec<DataEnumObjectField>>>();
+    if variants.len() < 2 {
+        return Vec::new();
+    }
+    variants[0]
+        .iter()
+        .filter(|f| {
+            variants[1..].iter().all(|fields| {
+                fields.iter().any(|other| {
+                    other.name().as_snake_case() == f.name().as_snake_case()
+                        && other.json_name() == f.json_name()
+                        && ctx.type_name(other.field_type()) == ctx.type_name(f.field_type())
+                })
+            })
+        })
+        .collect()
+}
+
+fn is_common(field: &DataEnumObjectField, common_fields: &[&DataEnumObjectField]) -> bool {
+    common_fields
+        .iter()
+        .any(|c| c.name().as_snake_case() == field.name().as_snake_case())
+}
+
+fn generate_base_class(common_fields: &[&DataEnumObjectField], ctx: &JavaContext) -> java::Tokens {
+    if common_fields.is_empty() {
+        return quote!();
+    }
+    let field_names = &common_fields
+        .iter()
+        .map(|f| ctx.field_name(f.name()))
+        .collect::<Vec<String>>();
+    let field_declarations = &common_fields
+        .iter()
+        .map(|f| {
+            let field_name = ctx.field_name(f.name());
+            let field_type = ctx.type_name(f.field_type());
+            format!("{field_type} {field_name}")
+        })
+        .collect::<Vec<String>>();
+    let getters = common_fields
+        .iter()
+        .map(|f| {
+            let field_name = ctx.field_name(f.name());
+            let getter_name = &ctx.getter_name(f.name(), f.field_type());
+            let field_type = ctx.type_name(f.field_type());
+            let json_name = f.json_name();
+            quote!(
+                @JsonProperty($[str]($[const](json_name)))
+                public $field_type $getter_name() {
+                    return $field_name;
+                }
+            )
+        })
+        .collect::<Vec<java::Tokens>>();
+    quote!(
+        public abstract static class Base {
+            $(for fd in field_declarations => private final $fd;)
+
+            protected Base($(for fd in field_declarations join (,) => $fd)) {
+                $(for f in field_names => this.$f = $f;)
+            }
+
+            $(for g in getters => $g)
+        }
+    )
+}
+
+/// Generates getters of the common fields in the data enum class, which delegate to the value of any struct variant.
+fn generate_common_getters(
+    common_fields: &[&DataEnumObjectField],
+    ctx: &JavaContext,
+) -> java::Tokens {
+    let getters = common_fields
+        .iter()
+        .map(|f| {
+            let getter_name = &ctx.getter_name(f.name(), f.field_type());
+            let field_type = ctx.type_name(f.field_type());
+            quote!(
+                public $field_type $getter_name() {
+                    if (!(value instanceof Base)) {
+                        throw new IllegalStateException("Invalid variant: " + variant);
+                    }
+                    return ((Base) value).$getter_name();
+                }
+            )
+        })
+        .collect::<Vec<java::Tokens>>();
+    quote!(
+        $(for g in getters => $g)
+    )
+}
+
+fn generate_fields_code(
+    de: &DataEnum,
+    common_fields: &[&DataEnumObjectField],
+    ctx: &JavaContext,
+) -> java::Tokens {
     let contents: Vec<java::Tokens> = de
         .variants()
         .iter()
-        .map(|v| generate_field_code(de, v))
+        .map(|v| generate_field_code(de, v, common_fields, ctx))
         .collect();
 
     quote!(
@@ -101,8 +465,13 @@ fn generate_fields_code(de: &DataEnum) -> java::Tokens {
     )
 }
 
-fn generate_field_code(de: &DataEnum, v: &DataEnumVariant) -> java::Tokens {
-    let class_name = &de.name().as_pascal_case();
+fn generate_field_code(
+    de: &DataEnum,
+    v: &DataEnumVariant,
+    common_fields: &[&DataEnumObjectField],
+    ctx: &JavaContext,
+) -> java::Tokens {
+    let class_name = &ctx.class_name(de.self_type());
     let name = v.name();
     let variant_enum_name = &name.as_upper_snake_case();
     let of_method_name = &format!("of{}", name.as_pascal_case());
@@ -117,35 +486,47 @@ fn generate_field_code(de: &DataEnum, v: &DataEnumVariant) -> java::Tokens {
         }
         DataEnumVariant::Object { fields, .. } => {
             let sub_class_name = &name.as_pascal_case();
-            let field_names = &fields
+            let own_fields = fields
+                .iter()
+                .filter(|f| !is_common(f, common_fields))
+                .collect::<Vec<&DataEnumObjectField>>();
+            let extends = (!common_fields.is_empty()).then(|| quote!(extends Base));
+            let super_call = (!common_fields.is_empty()).then(|| {
+                let args = common_fields
+                    .iter()
+                    .map(|f| ctx.field_name(f.name()))
+                    .collect::<Vec<String>>();
+                quote!(super($(for a in args join (, ) => $a));)
+            });
+            let field_names = &own_fields
                 .iter()
-                .map(|f: &DataEnumObjectField| f.name().as_camel_case())
+                .map(|f| ctx.field_name(f.name()))
                 .collect::<Vec<String>>();
-            let field_declarations = &fields
+            let field_declarations = &own_fields
                 .iter()
-                .map(|f: &DataEnumObjectField| {
-                    let field_name = f.name().as_camel_case();
-                    let field_type = f.field_type().type_name();
+                .map(|f| {
+                    let field_name = ctx.field_name(f.name());
+                    let field_type = ctx.type_name(f.field_type());
                     format!("{field_type} {field_name}")
                 })
                 .collect::<Vec<String>>();
             let field_args = &fields
                 .iter()
                 .map(|f: &DataEnumObjectField| {
-                    let field_name = f.name().as_camel_case();
-                    let field_type = f.field_type().type_name();
+                    let field_name = ctx.field_name(f.name());
+                    let field_type = ctx.type_name(f.field_type());
                     let json_name = f.json_name();
                     quote!(
                         @JsonProperty($[str]($[const](json_name))) $field_type $field_name
                     )
                 })
                 .collect::<Vec<java::Tokens>>();
-            let getters = &fields
+            let getters = &own_fields
                 .iter()
-                .map(|f: &DataEnumObjectField| {
-                    let field_name = f.name().as_camel_case();
-                    let getter_name = &format!("get{}", f.name().as_pascal_case());
-                    let field_type = f.field_type().type_name();
+                .map(|f| {
+                    let field_name = ctx.field_name(f.name());
+                    let getter_name = &ctx.getter_name(f.name(), f.field_type());
+                    let field_type = ctx.type_name(f.field_type());
                     let json_name = f.json_name();
                     quote!(
                         @JsonProperty($[str]($[const](json_name)))
@@ -155,8 +536,19 @@ fn generate_field_code(de: &DataEnum, v: &DataEnumVariant) -> java::Tokens {
                     )
                 })
                 .collect::<Vec<java::Tokens>>();
-            let main_getter_name = &format!("get{}", name.as_pascal_case());
+            let members = fields
+                .iter()
+                .map(|f| {
+                    let getter_name = format!("{}()", ctx.getter_name(f.name(), f.field_type()));
+                    (Some(ctx.field_name(f.name())), getter_name)
+                })
+                .collect::<Vec<(Option<String>, String)>>();
+            let value_methods =
+                generate_variant_value_methods(sub_class_name, sub_class_name, &members);
+            let order = property_order(fields.iter().map(|f| f.json_name()));
+            let main_getter_name = &ctx.variant_getter_name(name, None);
             let enum_field_name = &name.as_upper_snake_case();
+            let case_insensitive = qualified_case_insensitive_annotation(ctx);
             quote!(
                 public static $class_name $of_method_name($sub_class_name value) {
                     return new $class_name(Variant.$variant_enum_name, value);
@@ -169,70 +561,70 @@ fn generate_field_code(de: &DataEnum, v: &DataEnumVariant) -> java::Tokens {
                     return ($sub_class_name) value;
                 }
 
-                public static class $sub_class_name {
+                $order
+                $case_insensitive
+                public static class $sub_class_name $extends {
                     $(for fd in field_declarations => private final $fd;)
 
                     public $sub_class_name($(for fd in field_args join (,) => $fd)) {
+                        $super_call
                         $(for f in field_names => this.$f = $f;)
                     }
 
                     $(for g in getters => $g)
+
+                    $value_methods
                 }
             )
         }
         DataEnumVariant::Tuple { fields, .. } => {
-            let mut counter = 0;
-            let args = fields
-                .iter()
-                .map(|t: &Type| {
-                    let arg_name = format!("arg{counter}");
-                    let type_name = t.type_name();
-                    counter += 1;
-                    quote!($type_name $arg_name)
-                })
-                .collect::<Vec<java::Tokens>>();
-
-            let mut counter = 0;
-            let objects = fields
+            let holder_name = &tuple_holder_name(v);
+            let arg_names = &(0..fields.len())
+                .map(|i| format!("arg{i}"))
+                .collect::<Vec<String>>();
+            let args = &fields
                 .iter()
-                .map(|_t: &Type| {
-                    let arg_name = format!("arg{counter}");
-                    counter += 1;
-                    quote!($arg_name)
-                })
-                .collect::<Vec<java::Tokens>>();
+                .zip(arg_names)
+                .map(|(t, arg_name)| format!("{} {arg_name}", ctx.type_name(t)))
+                .collect::<Vec<String>>();
 
             let getter_numbering = fields.len() != 1;
-            let mut counter = 0;
-            let variant_enum_name = &name.as_upper_snake_case();
-            let getters = quote_iter!(fields.iter() => |f: &Type| {
-                let type_name = f.type_name();
-                let getter_name = if getter_numbering {
-                    format!("get{}{}", name.as_pascal_case(), counter)
-                } else {
-                    format!("get{}", name.as_pascal_case())
-                };
-                let getter = quote!(
-                    @SuppressWarnings("unchecked")
+            let getters = quote_iter!(arg_names.iter().zip(fields).enumerate() => |(i, (arg_name, f))| {
+                let type_name = &ctx.type_name(f);
+                let getter_name = ctx.variant_getter_name(name, getter_numbering.then_some(i));
+                quote!(
                     public $type_name $getter_name() {
                         if (variant != Variant.$variant_enum_name) {
                             throw new IllegalStateException("Invalid variant: " + variant);
                         }
-                        return ($type_name) ((Object[]) value)[$counter];
+                        return (($holder_name) value).$arg_name;
                     }
-                );
-                counter += 1;
-                getter
+                )
             });
 
+            let members = arg_names
+                .iter()
+                .map(|a| (None, a.clone()))
+                .collect::<Vec<(Option<String>, String)>>();
+            let value_methods =
+                generate_variant_value_methods(holder_name, &name.as_pascal_case(), &members);
+
             quote!(
                 public static $class_name $of_method_name($(for a in args join (,) => $a)) {
-                    return new $class_name(Variant.$variant_enum_name, new Object[] {
-                        $(for o in objects join (,) => $o)
-                    });
+                    return new $class_name(Variant.$variant_enum_name, new $holder_name($(for a in arg_names join (, ) => $a)));
                 }
 
                 $getters
+
+                private static final class $holder_name {
+                    $(for a in args => private final $a;)
+
+                    private $holder_name($(for a in args join (,) => $a)) {
+                        $(for a in arg_names => this.$a = $a;)
+                    }
+
+                    $value_methods
+                }
             )
         }
     };
@@ -245,11 +637,17 @@ fn generate_field_code(de: &DataEnum, v: &DataEnumVariant) -> java::Tokens {
     )
 }
 
+/// Returns the name of the class holding the values of a tuple variant, e.g. `CircleArgs` for `Circle(Circle)`.
+/// It is not named after the variant, which would shadow a type of the same name inside the data enum class.
+fn tuple_holder_name(variant: &DataEnumVariant) -> String {
+    format!("{}Args", variant.name().as_pascal_case())
+}
+
 #[cfg(test)]
 mod tests {
     use crate as rdc;
-    use crate::targets::java::tests::run_java;
-    use crate::targets::java::JavaClass;
+    use crate::targets::java::verify::run_java;
+    use crate::targets::java::{AccessorStyle, JavaClass, JavaConfig};
     use crate::{rdc_java, RDCType, RDC};
     use genco::quote;
     use serde::{Deserialize, Serialize};
@@ -276,6 +674,180 @@ mod tests {
         Nested(Box<TestEnum<T>>),
     }
 
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Nested {
+        Groups(Vec<std::collections::HashMap<String, Option<u64>>>),
+        Pair(String, Box<Nested>),
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Event {
+        Created {
+            id: String,
+            timestamp: i64,
+            owner: String,
+        },
+        Deleted {
+            timestamp: i64,
+            id: String,
+        },
+        Unknown,
+    }
+
+    #[test]
+    fn common_fields() {
+        let config = JavaConfig::new().with_common_fields(true);
+        let classes = rdc_java!(config = config; Event).unwrap();
+        let code = classes[0].code();
+        println!("{code}");
+        assert!(code.contains("public abstract static class Base { private final String id;private final Long timestamp; protected Base(String id,Long timestamp)"));
+        assert!(code.contains("public static class Created extends Base { private final String owner; public Created(@JsonProperty(\"id\") String id,@JsonProperty(\"timestamp\") Long timestamp,@JsonProperty(\"owner\") String owner) { super(id, timestamp); this.owner = owner; }"));
+        assert!(code.contains("public static class Deleted extends Base { public Deleted(@JsonProperty(\"timestamp\") Long timestamp,@JsonProperty(\"id\") String id) { super(id, timestamp); } @Override"));
+        assert!(code.contains("public Long getTimestamp() { if (!(value instanceof Base)) { throw new IllegalStateException(\"Invalid variant: \" + variant); } return ((Base) value).getTimestamp(); }"));
+        let plain = rdc_java!(Event).unwrap();
+        assert!(!plain[0].code().contains("Base"));
+    }
+
+    #[test]
+    fn exhaustiveness_helpers() {
+        let classes = rdc_java!(Event).unwrap();
+        let code = classes[0].code();
+        assert!(code.contains("public Variant variant() { return variant; }"));
+        assert!(code.contains("public IllegalStateException unhandled() { return new IllegalStateException(\"Unhandled variant: \" + variant); }"));
+        assert!(code.contains("missing.removeAll(Arrays.asList(handled)); if (!missing.isEmpty()) { throw new IllegalStateException(\"Unhandled variants of Event: \" + missing); }"));
+        assert!(code.contains("public enum Variant { CREATED,DELETED,UNKNOWN; public static final Set<Variant> KNOWN_VARIANTS = Collections.unmodifiableSet(EnumSet.allOf(Variant.class)); }"));
+    }
+
+    #[test]
+    fn tuple_variant_java_types() {
+        let classes = rdc_java!(Nested).unwrap();
+        let code = classes[0].code();
+        assert!(code.contains(
+            "tf.constructCollectionType(List.class, tf.constructMapType(Map.class, tf.constructType(String.class), tf.constructType(BigInteger.class)))"
+        ));
+        assert!(code.contains("new PairArgs(ctxt.readTreeAsValue(values.get(0), tf.constructType(String.class)), ctxt.readTreeAsValue(values.get(1), tf.constructType(Nested.class)))"));
+        assert!(!code.contains("TypeReference"));
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Circle {
+        radius: f64,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Shape {
+        Circle(Circle),
+        Line(f64, Option<String>),
+    }
+
+    #[test]
+    fn tuple_variant_holders() {
+        let classes = rdc_java!(Shape).unwrap();
+        let code = classes.iter().find(|c| c.name() == "Shape").unwrap().code();
+        println!("{code}");
+        assert!(code.contains("private static final class CircleArgs { private final Circle arg0; private CircleArgs(Circle arg0) { this.arg0 = arg0; } @Override"));
+        assert!(code.contains("public static Shape ofLine(Double arg0,String arg1) { return new Shape(Variant.LINE, new LineArgs(arg0, arg1)); }"));
+        assert!(code.contains("public String getLine1() { if (variant != Variant.LINE) { throw new IllegalStateException(\"Invalid variant: \" + variant); } return ((LineArgs) value).arg1; }"));
+        assert!(code.contains("gen.writeObjectField(\"Circle\", ((CircleArgs) value.value).arg0);"));
+        assert!(code.contains("LineArgs holder = (LineArgs) value.value; gen.writeStartObject(); gen.writeArrayFieldStart(\"Line\"); gen.writeObject(holder.arg0);gen.writeObject(holder.arg1); gen.writeEndArray();"));
+        assert!(code.contains("JsonNode values = node.get(\"Circle\"); return new Shape(Variant.CIRCLE, new CircleArgs(ctxt.readTreeAsValue(values, tf.constructType(Circle.class))));"));
+        assert!(code.contains("JsonNode values = tupleValues(ctxt, node, \"Line\", 2); return new Shape(Variant.LINE, new LineArgs(ctxt.readTreeAsValue(values.get(0), tf.constructType(Double.class)), values.get(1).isNull() ? null : ctxt.readTreeAsValue(values.get(1), tf.constructType(String.class))));"));
+        assert!(!code.contains("Object[]"));
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Payment {
+        Card { number: String, is_virtual: bool },
+        Cash(f64),
+        Split(f64, f64),
+    }
+
+    #[test]
+    fn record_accessors() {
+        let config = JavaConfig::new().with_accessor_style(AccessorStyle::Record);
+        let classes = rdc_java!(config = config; Payment).unwrap();
+        let code = classes[0].code();
+        assert!(code.contains("public Card card() {"));
+        assert!(code.contains("public Double cash() {"));
+        assert!(code.contains("public Double split1() {"));
+        assert!(code.contains(
+            "@JsonProperty(\"is_virtual\") public Boolean isVirtual() { return isVirtual; }"
+        ));
+        assert!(code.contains("\"(number=\" + number() + \", isVirtual=\" + isVirtual()"));
+    }
+
+    #[test]
+    fn value_methods() {
+        let classes = rdc_java!(Event).unwrap();
+        let code = classes[0].code();
+        println!("{code}");
+        assert!(code.contains("Event other = (Event) o; return variant == other.variant && Objects.equals(value, other.value); }"));
+        assert!(code.contains("public int hashCode() { return Objects.hash(variant, value); }"));
+        assert!(code.contains("public String toString() { switch (variant) { case UNKNOWN: return \"Event.Unknown\"; default: break; } return \"Event.\" + value; }"));
+        assert!(code.contains("if (o == null || getClass() != o.getClass()) { return false; } Deleted other = (Deleted) o; return Objects.equals(getTimestamp(), other.getTimestamp()) && Objects.equals(getId(), other.getId()); }"));
+        assert!(code.contains(
+            "return \"Deleted\" + \"(timestamp=\" + getTimestamp() + \", id=\" + getId() + \")\";"
+        ));
+
+        let classes = rdc_java!(Shape).unwrap();
+        let code = classes.iter().find(|c| c.name() == "Shape").unwrap().code();
+        assert!(code.contains("LineArgs other = (LineArgs) o; return Objects.equals(arg0, other.arg0) && Objects.equals(arg1, other.arg1); }"));
+        assert!(code.contains("public int hashCode() { return Objects.hash(arg0, arg1); }"));
+        assert!(code.contains("return \"Line\" + \"(\" + arg0 + \", \" + arg1 + \")\";"));
+        assert!(code.contains("public String toString() { return \"Shape.\" + value; }"));
+    }
+
+    #[test]
+    fn unknown_variants() {
+        let config = JavaConfig::new().with_unknown_variants(true);
+        let classes = rdc_java!(config = config; Shape).unwrap();
+        let code = classes.iter().find(|c| c.name() == "Shape").unwrap().code();
+        assert!(code.contains("public enum Variant { CIRCLE,LINE,UNKNOWN; public static final Set<Variant> KNOWN_VARIANTS = Collections.unmodifiableSet(EnumSet.complementOf(EnumSet.of(UNKNOWN))); }"));
+        assert!(code.contains("public static Shape ofUnknown(JsonNode json) { return new Shape(Variant.UNKNOWN, json); }"));
+        assert!(code.contains("public JsonNode getUnknown() { if (variant != Variant.UNKNOWN) {"));
+        assert!(code.contains("case UNKNOWN: gen.writeTree((JsonNode) value.value); break;"));
+        assert!(code.contains("return new Shape(Variant.UNKNOWN, ctxt.readTree(p)); } else if"));
+        assert!(code.contains("return new Shape(Variant.UNKNOWN, node); }"));
+
+        let config = JavaConfig::new().with_unknown_variants(true);
+        let classes = rdc_java!(config = config; Event).unwrap();
+        assert!(!classes[0].code().contains("ofUnknown(JsonNode json)"));
+        assert_eq!(
+            classes[0].warnings(),
+            ["Unknown variants of `Event` are not preserved, because it has a variant named `Unknown`"]
+        );
+    }
+
+    #[test]
+    fn case_insensitive_properties() {
+        let config = JavaConfig::new().with_case_insensitive_properties(true);
+        let classes = rdc_java!(config = config; TestEnum<i32>).unwrap();
+        let code = classes[0].code();
+        println!("{code}");
+        assert!(code.contains("import com.fasterxml.jackson.annotation.JsonFormat;"));
+        assert!(code.contains("@JsonFormat(with = JsonFormat.Feature.ACCEPT_CASE_INSENSITIVE_PROPERTIES) public static class Other"));
+        assert!(code.contains("private static String variantKey(ObjectNode node, String name)"));
+        assert!(code.contains("if (variantKey(node, \"JSON\") != null) { JsonNode values = node.get(variantKey(node, \"JSON\")); "));
+        assert!(code.contains("if (variantKey(node, \"Other\") != null) {"));
+
+        let classes = rdc_java!(TestEnum<i32>).unwrap();
+        assert!(!classes[0].code().contains("variantKey"));
+        assert!(!classes[0].code().contains("JsonFormat"));
+    }
+
+    #[test]
+    fn object_variant_property_order() {
+        let classes = rdc_java!(TestEnum<i32>).unwrap();
+        assert!(classes[0]
+            .code()
+            .contains("@JsonPropertyOrder({\"other\"})"));
+    }
+
     #[test]
     fn enum_derive_test() {
         let mut classes = rdc_java!(TestEnum<i32>).unwrap();
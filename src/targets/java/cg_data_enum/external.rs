@@ -1,11 +1,17 @@
-use crate::ir::{DataEnum, DataEnumVariant, Type};
+use crate::ir::{DataEnum, DataEnumVariant, Type, TypeKind};
 use crate::quote_iter;
+use crate::targets::java::cg_data_enum::tuple_holder_name;
 use crate::targets::java::cg_utils::Compact;
+use crate::targets::java::context::JavaContext;
 use genco::prelude::*;
 use genco::quote;
 
-pub fn generate_external_serializer(de: &DataEnum) -> java::Tokens {
-    let class_name_str = &de.name().as_pascal_case();
+pub fn generate_external_serializer(
+    de: &DataEnum,
+    unknown: bool,
+    ctx: &JavaContext,
+) -> java::Tokens {
+    let class_name_str = &ctx.class_name(de.self_type());
     let cases = quote_iter!(de.variants().iter() => |v: &DataEnumVariant| {
         let case = v.name().as_upper_snake_case();
         let json_name = v.json_name();
@@ -19,16 +25,21 @@ pub fn generate_external_serializer(de: &DataEnum) -> java::Tokens {
                 gen.writeEndObject();
             ),
             DataEnumVariant::Tuple { fields, ..} => {
+                let holder_name = &tuple_holder_name(v);
+                let arg_names = (0..fields.len()).map(|i| format!("arg{i}")).collect::<Vec<String>>();
                 if fields.len() == 1 {
                     quote!(
                         gen.writeStartObject();
-                        gen.writeObjectField($[str]($[const](json_name)), ((Object[]) value.value)[0]);
+                        gen.writeObjectField($[str]($[const](json_name)), (($holder_name) value.value).arg0);
                         gen.writeEndObject();
                     )
                 } else {
                     quote!(
+                        $holder_name holder = ($holder_name) value.value;
                         gen.writeStartObject();
-                        gen.writeObjectField($[str]($[const](json_name)), value.value);
+                        gen.writeArrayFieldStart($[str]($[const](json_name)));
+                        $(for a in arg_names => gen.writeObject(holder.$a);)
+                        gen.writeEndArray();
                         gen.writeEndObject();
                     )
                 }
@@ -42,6 +53,13 @@ pub fn generate_external_serializer(de: &DataEnum) -> java::Tokens {
         )
     });
 
+    let unknown_case = unknown.then(|| {
+        quote!(
+            case UNKNOWN:
+                gen.writeTree((JsonNode) value.value);
+                break;
+        )
+    });
     quote!(
         public static class Serializer extends StdSerializer<$class_name_str> {
             public Serializer() {
@@ -52,29 +70,46 @@ pub fn generate_external_serializer(de: &DataEnum) -> java::Tokens {
             public void serialize($class_name_str value, JsonGenerator gen, SerializerProvider provider) throws IOException {
                 switch (value.getVariant()) {
                     $cases
+                    $unknown_case
                 }
             }
         }
     )
 }
 
-pub fn generate_external_deserializer(de: &DataEnum) -> java::Tokens {
-    let class_name_str = &de.name().as_pascal_case();
+pub fn generate_external_deserializer(
+    de: &DataEnum,
+    unknown: bool,
+    ctx: &JavaContext,
+) -> java::Tokens {
+    let class_name_str = &ctx.class_name(de.self_type());
     let mut unit_cases: Vec<java::Tokens> = vec![];
     let mut object_cases: Vec<java::Tokens> = vec![];
 
     for variant in de.variants() {
         match variant {
-            DataEnumVariant::Unit { .. } => unit_cases.push(deserialize_unit_variant(de, variant)),
+            DataEnumVariant::Unit { .. } => {
+                unit_cases.push(deserialize_unit_variant(de, variant, ctx))
+            }
             DataEnumVariant::Object { .. } => {
-                object_cases.push(deserialize_object_variant(de, variant))
+                object_cases.push(deserialize_object_variant(de, variant, ctx))
             }
             DataEnumVariant::Tuple { fields, .. } => {
-                object_cases.push(deserialize_tuple_variant(de, variant, fields))
+                object_cases.push(deserialize_tuple_variant(de, variant, fields, ctx))
             }
         }
     }
 
+    if unknown {
+        unit_cases.push(quote!(
+            return new $class_name_str(Variant.UNKNOWN, ctxt.readTree(p));
+        ));
+        object_cases.push(quote!(
+            return new $class_name_str(Variant.UNKNOWN, node);
+        ));
+    }
+
+    let tuple_values = generate_tuple_values(ctx);
     let unit_cases_code = unit_cases.compact();
     let object_cases_code = object_cases.compact();
     quote!(
@@ -83,34 +118,11 @@ pub fn generate_external_deserializer(de: &DataEnum) -> java::Tokens {
                 super($class_name_str.class);
             }
 
-            private Object[] parseField(DeserializationContext cxtx, ObjectNode node, String key, TypeReference<?>...types) throws IOException {
-                JsonNode field = node.get(key);
-                if (field == null) {
-                    return new Object[types.length];
-                }
-                if (field.isArray()) {
-                    if (field.size() != types.length) {
-                        throw new JsonParseException(cxtx.getParser(), "Expected array of size " + types.length + " for field " + key);
-                    }
-                    Object[] result = new Object[types.length];
-                    for (int i = 0; i < types.length; i++) {
-                        try (JsonParser parser = field.get(i).traverse(cxtx.getParser().getCodec())) {
-                            result[i] = parser.readValueAs(types[i]);
-                        }
-                    }
-                    return result;
-                } else {
-                    if (types.length != 1) {
-                        throw new JsonParseException(cxtx.getParser(), "Expected array for field " + key);
-                    }
-                    try (JsonParser parser = field.traverse(cxtx.getParser().getCodec())) {
-                        return new Object[]{parser.readValueAs(types[0])};
-                    }
-                }
-            }
+            $tuple_values
 
             @Override
             public $class_name_str deserialize(JsonParser p, DeserializationContext ctxt) throws IOException, JacksonException {
+                TypeFactory tf = ctxt.getTypeFactory();
 
                 if (p.currentToken() == JsonToken.VALUE_STRING) {
                     $unit_cases_code
@@ -124,8 +136,93 @@ pub fn generate_external_deserializer(de: &DataEnum) -> java::Tokens {
     )
 }
 
-fn deserialize_unit_variant(de: &DataEnum, variant: &DataEnumVariant) -> java::Tokens {
-    let base_name = &de.name().as_pascal_case();
+/// Returns the `tupleValues` method of a deserializer, returning the array holding the values of a tuple variant
+/// after checking its size.
+/// With case-insensitive properties, it is followed by a `variantKey` method finding the key of a variant in any case.
+pub(super) fn generate_tuple_values(ctx: &JavaContext) -> java::Tokens {
+    let variant_key = ctx.config().case_insensitive_properties().then(|| {
+        quote!(
+            private static String variantKey(ObjectNode node, String name) {
+                for (var names = node.fieldNames(); names.hasNext();) {
+                    String key = names.next();
+                    if (key.equalsIgnoreCase(name)) {
+                        return key;
+                    }
+                }
+                return null;
+            }
+        )
+    });
+    quote!(
+        $variant_key
+
+        private static JsonNode tupleValues(DeserializationContext ctxt, ObjectNode node, String key, int size) throws IOException {
+            JsonNode values = node.get(key);
+            if (!values.isArray() || values.size() != size) {
+                throw new JsonParseException(ctxt.getParser(), "Expected array of size " + size + " for field " + key);
+            }
+            return values;
+        }
+    )
+}
+
+/// Returns the statement finding the values of a tuple variant held by `node` under `key`, followed by expressions
+/// reading each value with `ctxt.readTreeAsValue` as its own type, so they can be passed straight to a constructor.
+/// serde writes the value of a newtype variant as it is, and the values of other variants as an array.
+pub(super) fn read_tuple_values(
+    fields: &[Type],
+    key: &java::Tokens,
+    ctx: &JavaContext,
+) -> (java::Tokens, Vec<String>) {
+    let statement = match fields.len() {
+        1 => quote!(JsonNode values = node.get($key);),
+        size => quote!(JsonNode values = tupleValues(ctxt, node, $key, $size);),
+    };
+    let values = fields
+        .iter()
+        .enumerate()
+        .map(|(i, t)| {
+            let value = match fields.len() {
+                1 => "values".to_string(),
+                _ => format!("values.get({i})"),
+            };
+            let read = format!(
+                "ctxt.readTreeAsValue({value}, {})",
+                java_type_expression(t, ctx)
+            );
+            match t.kind() {
+                TypeKind::Option(_) => format!("{value}.isNull() ? null : {read}"),
+                _ => read,
+            }
+        })
+        .collect();
+    (statement, values)
+}
+
+/// Returns a condition checking that an object holds the given variant, in any case with case-insensitive properties.
+pub(super) fn has_variant(json_name: &str, ctx: &JavaContext) -> java::Tokens {
+    if ctx.config().case_insensitive_properties() {
+        quote!(variantKey(node, $[str]($[const](json_name))) != null)
+    } else {
+        quote!(node.has($[str]($[const](json_name))))
+    }
+}
+
+/// Returns an expression with the key of the given variant in an object holding it.
+pub(super) fn variant_key(json_name: &str, ctx: &JavaContext) -> java::Tokens {
+    if ctx.config().case_insensitive_properties() {
+        quote!(variantKey(node, $[str]($[const](json_name))))
+    } else {
+        quote!($[str]($[const](json_name)))
+    }
+}
+
+fn deserialize_unit_variant(
+    de: &DataEnum,
+    variant: &DataEnumVariant,
+    ctx: &JavaContext,
+) -> java::Tokens {
+    let base_name = &ctx.class_name(de.self_type());
     let case = variant.name().as_upper_snake_case();
     let json_name = variant.json_name();
     quote!(
@@ -135,14 +232,19 @@ fn deserialize_unit_variant(de: &DataEnum, variant: &DataEnumVariant) -> java::T
     )
 }
 
-fn deserialize_object_variant(de: &DataEnum, variant: &DataEnumVariant) -> java::Tokens {
-    let base_name = &de.name().as_pascal_case();
+fn deserialize_object_variant(
+    de: &DataEnum,
+    variant: &DataEnumVariant,
+    ctx: &JavaContext,
+) -> java::Tokens {
+    let base_name = &ctx.class_name(de.self_type());
     let case = variant.name().as_upper_snake_case();
     let class_name = variant.name().as_pascal_case();
-    let json_name = variant.json_name();
+    let has_variant = has_variant(variant.json_name(), ctx);
+    let key = variant_key(variant.json_name(), ctx);
     quote!(
-        if (node.has($[str]($[const](json_name)))) {
-            return new $base_name(Variant.$case, parseField(ctxt, node, $[str]($[const](json_name)), new TypeReference<$class_name>(){})[0]);
+        if ($has_variant) {
+            return new $base_name(Variant.$case, ctxt.readTreeAsValue(node.get($key), $class_name.class));
         }
     )
 }
@@ -150,18 +252,41 @@ fn deserialize_object_variant(de: &DataEnum, variant: &DataEnumVariant) -> java:
 fn deserialize_tuple_variant(
     de: &DataEnum,
     variant: &DataEnumVariant,
-    fields: &Vec<Type>,
+    fields: &[Type],
+    ctx: &JavaContext,
 ) -> java::Tokens {
-    let base_name = &de.name().as_pascal_case();
+    let base_name = &ctx.class_name(de.self_type());
     let case = variant.name().as_upper_snake_case();
     let json_name = variant.json_name();
-    let class_names = fields
-        .iter()
-        .map(|f: &Type| f.type_name())
-        .collect::<Vec<&String>>();
+    let holder_name = tuple_holder_name(variant);
+    let has_variant = has_variant(json_name, ctx);
+    let key = variant_key(json_name, ctx);
+    let (statement, values) = read_tuple_values(fields, &key, ctx);
     quote!(
-        if (node.has($[str]($[const](json_name)))) {
-            return new $base_name(Variant.$case, parseField(ctxt, node, $[str]($[const](json_name)), $(for c in class_names join (, ) => new TypeReference<$c>(){})));
+        if ($has_variant) {
+            $statement
+            return new $base_name(Variant.$case, new $holder_name($(for v in values join (, ) => $v)));
         }
     )
 }
+
+/// Returns Java expression that constructs Jackson `JavaType` of the given type with `TypeFactory tf`.
+/// It is built from the structured IR type, so nested collections keep their element types.
+pub(super) fn java_type_expression(t: &Type, ctx: &JavaContext) -> String {
+    match t.kind() {
+        TypeKind::List(element) => format!(
+            "tf.constructCollectionType(java.util.List.class, {})",
+            java_type_expression(element, ctx)
+        ),
+        TypeKind::Map(key, value) => format!(
+            "tf.constructMapType({}.class, {}, {})",
+            ctx.map_class(t),
+            java_type_expression(key, ctx),
+            java_type_expression(value, ctx)
+        ),
+        TypeKind::Option(inner) => java_type_expression(inner, ctx),
+        TypeKind::Named | TypeKind::UnsignedLong => {
+            format!("tf.constructType({}.class)", ctx.type_name(t))
+        }
+    }
+}
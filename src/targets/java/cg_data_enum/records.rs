@@ -0,0 +1,492 @@
+use crate::errors::Error;
+use crate::ir::{DataEnum, DataEnumObjectField, DataEnumVariant, Type, TypeKind};
+use crate::targets::java::cg_data_enum::external::{
+    generate_tuple_values, has_variant, read_tuple_values, variant_key,
+};
+use crate::targets::java::cg_data_enum::{preserves_unknown_variants, shared_object_fields};
+use crate::targets::java::cg_utils::{property_order, qualified_case_insensitive_annotation};
+use crate::targets::java::context::JavaContext;
+use crate::targets::java::JavaClass;
+use crate::targets::java::{cg_error, cg_fingerprint, cg_json_schema};
+use genco::prelude::*;
+use genco::quote;
+
+/// Names of the classes used by the generated interface, which records must not shadow.
+const RESERVED_NAMES: [&str; 13] = [
+    "Object",
+    "String",
+    "IOException",
+    "IllegalStateException",
+    "JsonGenerator",
+    "JsonParser",
+    "JsonNode",
+    "ObjectNode",
+    "TypeFactory",
+    "SerializerProvider",
+    "DeserializationContext",
+    "Serializer",
+    "Deserializer",
+];
+
+/// This function generates a sealed interface of a data enum with a nested `record` per variant.
+/// The interface is bound to a serializer and a deserializer using the serde external tagging,
+/// while object variant records are bound by Jackson as they are.
+pub fn generate_sealed_interface(de: &DataEnum, ctx: &JavaContext) -> Result<JavaClass, Error> {
+    let class_name = ctx.class_name(de.self_type());
+    let class_name_str = class_name.as_str();
+    let unknown_record = preserves_unknown_variants(de, ctx)
+        .then(|| unshadowed_name(de, "Unknown".to_string(), ctx));
+    let common_accessors = generate_common_accessors(de, unknown_record.is_some(), ctx);
+    let mut records = de
+        .variants()
+        .iter()
+        .map(|v| generate_record(de, v, ctx))
+        .collect::<Vec<java::Tokens>>();
+    if let Some(unknown_record) = &unknown_record {
+        records.push(quote!(
+            record $unknown_record(JsonNode json) implements $class_name_str {}
+        ));
+    }
+    let serializer = generate_serializer(de, unknown_record.as_deref(), ctx);
+    let deserializer = generate_deserializer(de, unknown_record.as_deref(), ctx);
+    let json_schema = cg_json_schema::generate_constant(de.self_type(), ctx, true);
+    let fingerprint = cg_fingerprint::generate_constant(de.self_type(), ctx, true);
+    let error_methods = cg_error::generate_data_enum_record_methods(de, ctx);
+    let tokens: java::Tokens = quote!(
+        import com.fasterxml.jackson.annotation.JsonProperty;
+        import com.fasterxml.jackson.annotation.JsonPropertyOrder;
+        import com.fasterxml.jackson.core.*;
+        import com.fasterxml.jackson.databind.DeserializationContext;
+        import com.fasterxml.jackson.databind.JsonDeserializer;
+        import com.fasterxml.jackson.databind.JsonNode;
+        import com.fasterxml.jackson.databind.JsonSerializer;
+        import com.fasterxml.jackson.databind.SerializerProvider;
+        import com.fasterxml.jackson.databind.annotation.JsonDeserialize;
+        import com.fasterxml.jackson.databind.annotation.JsonSerialize;
+        import com.fasterxml.jackson.databind.deser.std.StdDeserializer;
+        import com.fasterxml.jackson.databind.node.ObjectNode;
+        import com.fasterxml.jackson.databind.ser.std.StdSerializer;
+        import com.fasterxml.jackson.databind.type.TypeFactory;
+
+        import java.io.IOException;
+
+        @JsonSerialize(using = $class_name_str.Serializer.class)
+        @JsonDeserialize(using = $class_name_str.Deserializer.class)
+        public sealed interface $class_name_str {
+            $json_schema
+
+            $fingerprint
+
+            $common_accessors
+
+            $(for r in records join ($['\n']) => $r)
+
+            $error_methods
+
+            $serializer
+
+            $deserializer
+        }
+    );
+
+    JavaClass::from_tokens(class_name, tokens)
+}
+
+/// Returns the name of the record of a variant, which is the name of the variant,
+/// unless it would shadow a class used by the interface, e.g. `CircleVariant` for `Circle(Circle)`.
+pub fn record_name(de: &DataEnum, variant: &DataEnumVariant, ctx: &JavaContext) -> String {
+    unshadowed_name(de, variant.name().as_pascal_case(), ctx)
+}
+
+/// Returns the name of a nested record, with a `Variant` suffix if it would shadow a class used by the interface.
+fn unshadowed_name(de: &DataEnum, name: String, ctx: &JavaContext) -> String {
+    let mut used = RESERVED_NAMES
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<String>>();
+    used.push(ctx.class_name(de.self_type()));
+    for v in de.variants() {
+        match v {
+            DataEnumVariant::Unit { .. } => {}
+            DataEnumVariant::Tuple { fields, .. } => {
+                fields.iter().for_each(|t| class_names(t, ctx, &mut used))
+            }
+            DataEnumVariant::Object { fields, .. } => fields
+                .iter()
+                .for_each(|f| class_names(f.field_type(), ctx, &mut used)),
+        }
+    }
+    if used.contains(&name) {
+        format!("{name}Variant")
+    } else {
+        name
+    }
+}
+
+/// Collects simple names of the classes making up the Java type.
+fn class_names(t: &Type, ctx: &JavaContext, names: &mut Vec<String>) {
+    match t.kind() {
+        TypeKind::Option(inner) | TypeKind::List(inner) => class_names(inner, ctx, names),
+        TypeKind::Map(key, value) => {
+            class_names(key, ctx, names);
+            class_names(value, ctx, names);
+        }
+        TypeKind::Named | TypeKind::UnsignedLong => names.push(ctx.type_name(t)),
+    }
+}
+
+fn generate_record(de: &DataEnum, v: &DataEnumVariant, ctx: &JavaContext) -> java::Tokens {
+    let class_name = &ctx.class_name(de.self_type());
+    let record_name = &record_name(de, v, ctx);
+    if record_name != &v.name().as_pascal_case() {
+        ctx.warn(format!(
+            "Record of `{}::{}` was named `{record_name}`, because the variant name is used by another class",
+            de.self_type().type_name(),
+            v.name().as_pascal_case()
+        ));
+    }
+    match v {
+        DataEnumVariant::Unit { .. } => quote!(
+            record $record_name() implements $class_name {}
+        ),
+        DataEnumVariant::Tuple { fields, .. } => {
+            let components = fields
+                .iter()
+                .enumerate()
+                .map(|(i, t)| format!("{} arg{i}", ctx.type_name(t)))
+                .collect::<Vec<String>>();
+            quote!(
+                record $record_name($(for c in components join (, ) => $c)) implements $class_name {}
+            )
+        }
+        DataEnumVariant::Object { fields, .. } => {
+            let components = fields
+                .iter()
+                .map(|f: &DataEnumObjectField| {
+                    let field_name = ctx.field_name(f.name());
+                    let field_type = ctx.type_name(f.field_type());
+                    let json_name = f.json_name();
+                    quote!(@JsonProperty($[str]($[const](json_name))) $field_type $field_name)
+                })
+                .collect::<Vec<java::Tokens>>();
+            let order = property_order(fields.iter().map(|f| f.json_name()));
+            let case_insensitive = qualified_case_insensitive_annotation(ctx);
+            // The serializer of the interface would be inherited and call itself for the content of the variant.
+            quote!(
+                $order
+                $case_insensitive
+                @JsonSerialize(using = JsonSerializer.None.class)
+                @JsonDeserialize(using = JsonDeserializer.None.class)
+                record $record_name($(for c in components join (, ) => $c)) implements $class_name {}
+            )
+        }
+    }
+}
+
+/// Returns accessors of the fields all struct variants have, which their records implement with their components.
+/// They are abstract when every variant is a struct variant, and fail for other variants otherwise.
+fn generate_common_accessors(de: &DataEnum, unknown: bool, ctx: &JavaContext) -> java::Tokens {
+    if !ctx.config().common_fields() {
+        return quote!();
+    }
+    let only_objects = !unknown
+        && de
+            .variants()
+            .iter()
+            .all(|v| matches!(v, DataEnumVariant::Object { .. }));
+    let accessors = shared_object_fields(de, ctx)
+        .into_iter()
+        .map(|f| {
+            let field_name = &ctx.field_name(f.name());
+            let field_type = &ctx.type_name(f.field_type());
+            if only_objects {
+                quote!($field_type $field_name();)
+            } else {
+                quote!(
+                    default $field_type $field_name() {
+                        throw new IllegalStateException("Invalid variant: " + this);
+                    }
+                )
+            }
+        })
+        .collect::<Vec<java::Tokens>>();
+    quote!($(for a in accessors => $a))
+}
+
+fn generate_serializer(
+    de: &DataEnum,
+    unknown_record: Option<&str>,
+    ctx: &JavaContext,
+) -> java::Tokens {
+    let class_name = &ctx.class_name(de.self_type());
+    let unknown_case = unknown_record.map(|unknown_record| {
+        quote!(
+            if (value instanceof $unknown_record) {
+                gen.writeTree((($unknown_record) value).json());
+                return;
+            }
+        )
+    });
+    let cases = de
+        .variants()
+        .iter()
+        .map(|v| {
+            let record_name = &record_name(de, v, ctx);
+            let json_name = v.json_name();
+            let serializer: java::Tokens = match v {
+                DataEnumVariant::Unit { .. } => quote!(
+                    gen.writeString($[str]($[const](json_name)));
+                ),
+                DataEnumVariant::Object { .. } => quote!(
+                    gen.writeStartObject();
+                    gen.writeObjectField($[str]($[const](json_name)), value);
+                    gen.writeEndObject();
+                ),
+                DataEnumVariant::Tuple { fields, .. } if fields.len() == 1 => quote!(
+                    gen.writeStartObject();
+                    gen.writeObjectField($[str]($[const](json_name)), (($record_name) value).arg0());
+                    gen.writeEndObject();
+                ),
+                DataEnumVariant::Tuple { fields, .. } => {
+                    let args = (0..fields.len())
+                        .map(|i| format!("arg{i}"))
+                        .collect::<Vec<String>>();
+                    quote!(
+                        $record_name tuple = ($record_name) value;
+                        gen.writeStartObject();
+                        gen.writeArrayFieldStart($[str]($[const](json_name)));
+                        $(for a in args => gen.writeObject(tuple.$a());)
+                        gen.writeEndArray();
+                        gen.writeEndObject();
+                    )
+                }
+            };
+            quote!(
+                if (value instanceof $record_name) {
+                    $serializer
+                    return;
+                }
+            )
+        })
+        .collect::<Vec<java::Tokens>>();
+    quote!(
+        class Serializer extends StdSerializer<$class_name> {
+            public Serializer() {
+                super($class_name.class);
+            }
+
+            @Override
+            public void serialize($class_name value, JsonGenerator gen, SerializerProvider provider) throws IOException {
+                $(for c in cases => $c)
+                $unknown_case
+                throw new IllegalStateException("Unknown variant: " + value);
+            }
+        }
+    )
+}
+
+fn generate_deserializer(
+    de: &DataEnum,
+    unknown_record: Option<&str>,
+    ctx: &JavaContext,
+) -> java::Tokens {
+    let class_name = &ctx.class_name(de.self_type());
+    let mut unit_cases: Vec<java::Tokens> = Vec::new();
+    let mut object_cases: Vec<java::Tokens> = Vec::new();
+    for v in de.variants() {
+        let record_name = &record_name(de, v, ctx);
+        let json_name = v.json_name();
+        match v {
+            DataEnumVariant::Unit { .. } => unit_cases.push(quote!(
+                if (p.getText().equals($[str]($[const](json_name)))) {
+                    return new $record_name();
+                }
+            )),
+            DataEnumVariant::Object { .. } => {
+                let has_variant = has_variant(json_name, ctx);
+                let key = variant_key(json_name, ctx);
+                object_cases.push(quote!(
+                    if ($has_variant) {
+                        return ctxt.readTreeAsValue(node.get($key), $record_name.class);
+                    }
+                ))
+            }
+            DataEnumVariant::Tuple { fields, .. } => {
+                let has_variant = has_variant(json_name, ctx);
+                let key = variant_key(json_name, ctx);
+                let (statement, values) = read_tuple_values(fields, &key, ctx);
+                object_cases.push(quote!(
+                    if ($has_variant) {
+                        $statement
+                        return new $record_name($(for v in values join (, ) => $v));
+                    }
+                ))
+            }
+        }
+    }
+    if let Some(unknown_record) = unknown_record {
+        unit_cases.push(quote!(
+            return new $unknown_record(ctxt.readTree(p));
+        ));
+        object_cases.push(quote!(
+            return new $unknown_record(node);
+        ));
+    }
+    let tuple_values = generate_tuple_values(ctx);
+    quote!(
+        class Deserializer extends StdDeserializer<$class_name> {
+            public Deserializer() {
+                super($class_name.class);
+            }
+
+            $tuple_values
+
+            @Override
+            public $class_name deserialize(JsonParser p, DeserializationContext ctxt) throws IOException, JacksonException {
+                TypeFactory tf = ctxt.getTypeFactory();
+
+                if (p.currentToken() == JsonToken.VALUE_STRING) {
+                    $(for c in unit_cases => $c)
+                } else if (p.currentToken() == JsonToken.START_OBJECT) {
+                    var node = (ObjectNode) p.getCodec().readTree(p);
+                    $(for c in object_cases => $c)
+                }
+                throw ctxt.instantiationException($class_name.class, "Cannot deserialize " + $[str]($[const](class_name)));
+            }
+        }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::ir::{IntermediateRepresentation, TypeTarget};
+    use crate::targets::java::{generate_java_code_with_config, DataEnumEncoding, JavaConfig};
+    use crate::{rdc_java, RDC};
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Circle {
+        radius: f64,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Shape {
+        Circle(Circle),
+        Line(f64, Option<String>),
+        Polygon {
+            #[serde(rename = "pointCount")]
+            point_count: i32,
+        },
+        Empty,
+    }
+
+    #[test]
+    fn sealed_records() {
+        let config = JavaConfig::new()
+            .with_data_enum_encoding(DataEnumEncoding::SealedRecords)
+            .with_fixtures(true);
+        let classes = rdc_java!(config = config; Shape).unwrap();
+        let class = |name: &str| classes.iter().find(|c| c.name() == name).unwrap();
+        let shape = class("Shape");
+        let code = shape.code();
+        println!("{code}");
+        assert!(code.contains("@JsonSerialize(using = Shape.Serializer.class) @JsonDeserialize(using = Shape.Deserializer.class) public sealed interface Shape {"));
+        assert!(code.contains("record CircleVariant(Circle arg0) implements Shape {}"));
+        assert!(code.contains("record Line(Double arg0, String arg1) implements Shape {}"));
+        assert!(code.contains("@JsonPropertyOrder({\"pointCount\"}) @JsonSerialize(using = JsonSerializer.None.class) @JsonDeserialize(using = JsonDeserializer.None.class) record Polygon(@JsonProperty(\"pointCount\") Integer pointCount) implements Shape {}"));
+        assert!(code.contains("record Empty() implements Shape {}"));
+        assert!(code.contains("if (value instanceof Line) { Line tuple = (Line) value; gen.writeStartObject(); gen.writeArrayFieldStart(\"Line\"); gen.writeObject(tuple.arg0());gen.writeObject(tuple.arg1()); gen.writeEndArray(); gen.writeEndObject(); return; }"));
+        assert!(
+            code.contains("if (value instanceof Empty) { gen.writeString(\"Empty\"); return; }")
+        );
+        assert!(code.contains("return ctxt.readTreeAsValue(node.get(\"Polygon\"), Polygon.class);"));
+        assert!(code.contains("JsonNode values = tupleValues(ctxt, node, \"Line\", 2); return new Line(ctxt.readTreeAsValue(values.get(0), tf.constructType(Double.class)), values.get(1).isNull() ? null : ctxt.readTreeAsValue(values.get(1), tf.constructType(String.class)));"));
+        assert!(!code.contains("Variant."));
+        assert_eq!(
+            shape.warnings(),
+            ["Record of `Shape::Circle` was named `CircleVariant`, because the variant name is used by another class"]
+        );
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Drawing {
+        shape: Shape,
+    }
+
+    #[test]
+    fn case_insensitive_records() {
+        let config = JavaConfig::new()
+            .with_data_enum_encoding(DataEnumEncoding::SealedRecords)
+            .with_case_insensitive_properties(true);
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Shape>();
+        let classes = generate_java_code_with_config(&ir, &config).unwrap();
+        let code = classes.iter().find(|c| c.name() == "Shape").unwrap().code();
+        assert!(code.contains("@JsonFormat(with = JsonFormat.Feature.ACCEPT_CASE_INSENSITIVE_PROPERTIES) @JsonSerialize(using = JsonSerializer.None.class) @JsonDeserialize(using = JsonDeserializer.None.class) record Polygon("));
+        assert!(code.contains("if (variantKey(node, \"Polygon\") != null) { return ctxt.readTreeAsValue(node.get(variantKey(node, \"Polygon\")), Polygon.class); }"));
+    }
+
+    #[test]
+    fn record_fixtures_and_errors() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Drawing>();
+        let config = JavaConfig::new()
+            .with_data_enum_encoding(DataEnumEncoding::SealedRecords)
+            .with_fixtures(true)
+            .with_error_type("Shape")
+            .with_error_message("Shape", "Line", "Line of length {0}")
+            .with_error_message("Shape", "Polygon", "{point_count} points");
+        let classes = generate_java_code_with_config(&ir, &config).unwrap();
+        let class = |name: &str| classes.iter().find(|c| c.name() == name).unwrap().code();
+        assert!(class("DrawingFixtures")
+            .contains("value.setShape(new Shape.CircleVariant(CircleFixtures.create()));"));
+        let shape = class("Shape");
+        assert!(shape.contains("default String getMessage() {"));
+        assert!(shape.contains("if (this instanceof Line value) { return \"Line of length \" + String.valueOf(value.arg0()); }"));
+        assert!(shape.contains("if (this instanceof Polygon value) { return String.valueOf(value.pointCount()) + \" points\"; }"));
+        assert!(!shape.contains("getCode()"));
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Event {
+        Created { id: String, at: i64 },
+        Deleted { id: String, reason: String },
+    }
+
+    #[test]
+    fn common_fields_and_unknown_variants() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Event>();
+        ir.add::<Shape>();
+        let config = JavaConfig::new()
+            .with_data_enum_encoding(DataEnumEncoding::SealedRecords)
+            .with_common_fields(true)
+            .with_unknown_variants(true);
+        let classes = generate_java_code_with_config(&ir, &config).unwrap();
+        let class = |name: &str| classes.iter().find(|c| c.name() == name).unwrap();
+        let event = class("Event");
+        assert!(event.warnings().is_empty());
+        let code = event.code();
+        assert!(code.contains("default String id() { throw new IllegalStateException(\"Invalid variant: \" + this); }"));
+        assert!(code.contains("record Unknown(JsonNode json) implements Event {}"));
+        assert!(code.contains(
+            "if (value instanceof Unknown) { gen.writeTree(((Unknown) value).json()); return; }"
+        ));
+        assert!(code.contains("if (p.currentToken() == JsonToken.VALUE_STRING) { return new Unknown(ctxt.readTree(p)); }"));
+        assert!(code.contains("return new Unknown(node); }"));
+
+        let config = JavaConfig::new()
+            .with_data_enum_encoding(DataEnumEncoding::SealedRecords)
+            .with_common_fields(true);
+        let classes = generate_java_code_with_config(&ir, &config).unwrap();
+        let code = classes.iter().find(|c| c.name() == "Event").unwrap().code();
+        assert!(code.contains("String id(); "));
+        assert!(!code.contains("record Unknown"));
+        let shape = classes.iter().find(|c| c.name() == "Shape").unwrap().code();
+        assert!(!shape.contains("pointCount();"));
+    }
+}
@@ -0,0 +1,122 @@
+use crate::ir::{Struct, Type, TypeKind};
+use crate::targets::java::config::UnsignedLongMapping;
+use crate::targets::java::context::{ClassKind, JavaContext};
+use genco::prelude::*;
+use genco::quote;
+
+/// Returns a static `empty()` factory of a data class, creating a null object.
+///
+/// Collections are empty and mutable, numbers are `0`, booleans are `false` and strings are empty.
+/// Nested data classes and flags come from their own `empty()` factories,
+/// while optional fields, enums and other types are left `null`.
+pub fn generate_empty_factory(dc: &Struct, ctx: &JavaContext) -> java::Tokens {
+    let class_name = ctx.class_name(dc.self_type());
+    let assignments = dc
+        .fields()
+        .iter()
+        .filter(|f| !ctx.collects_extra_properties(dc, f))
+        .filter_map(|f| {
+            let value = empty_value(f.field_type(), ctx)?;
+            let name = ctx.field_name(f.name());
+            Some(quote!(value.$name = $value;))
+        })
+        .collect::<Vec<java::Tokens>>();
+    quote!(
+        public static $(&class_name) empty() {
+            $(&class_name) value = new $(&class_name)();
+            $(for a in assignments => $a)
+            return value;
+        }
+    )
+}
+
+/// Returns a Java expression creating an empty value of the type, or `None` if the field is left `null`.
+fn empty_value(t: &Type, ctx: &JavaContext) -> Option<String> {
+    let value = match t.kind() {
+        TypeKind::Option(_) => return None,
+        TypeKind::List(_) => "new java.util.ArrayList<>()".to_string(),
+        TypeKind::Map(key, _) => match ctx.map_class(t) {
+            "java.util.Map" => "new java.util.LinkedHashMap<>()".to_string(),
+            "java.util.EnumMap" => format!("new java.util.EnumMap<>({}.class)", ctx.type_name(key)),
+            map_class => format!("new {map_class}<>()"),
+        },
+        TypeKind::UnsignedLong => match ctx.config().unsigned_long_mapping() {
+            UnsignedLongMapping::BigInteger => "java.math.BigInteger.ZERO".to_string(),
+            UnsignedLongMapping::Long => "0L".to_string(),
+        },
+        TypeKind::Named => match ctx.class_kind(t) {
+            Some(ClassKind::Struct | ClassKind::Flags) => format!("{}.empty()", ctx.type_name(t)),
+            Some(_) => return None,
+            None => match t.type_name().as_str() {
+                "Boolean" => "false".to_string(),
+                "Byte" => "(byte) 0".to_string(),
+                "Short" => "(short) 0".to_string(),
+                "Integer" => "0".to_string(),
+                "Long" => "0L".to_string(),
+                "Float" => "0.0f".to_string(),
+                "Double" => "0.0".to_string(),
+                "String" => "\"\"".to_string(),
+                _ => return None,
+            },
+        },
+    };
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::ir::{IntermediateRepresentation, TypeTarget};
+    use crate::targets::java::{generate_java_code, generate_java_code_with_config, JavaConfig};
+    use crate::RDC;
+    use std::collections::{BTreeMap, HashMap};
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Order {
+        id: String,
+        quantity: i32,
+        paid: bool,
+        total: u64,
+        items: Vec<String>,
+        totals: BTreeMap<String, f64>,
+        by_status: HashMap<Status, i64>,
+        customer: Customer,
+        note: Option<String>,
+        status: Status,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Customer {
+        name: String,
+        referrer: Option<Box<Customer>>,
+    }
+
+    #[derive(RDC, PartialEq, Eq, Hash)]
+    #[allow(unused)]
+    enum Status {
+        Open,
+        Closed,
+    }
+
+    #[test]
+    fn empty_factories() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Order>();
+        let config = JavaConfig::new()
+            .with_empty_factories(true)
+            .with_concrete_maps(true);
+        let classes = generate_java_code_with_config(&ir, &config).unwrap();
+        let class = |name: &str| classes.iter().find(|c| c.name() == name).unwrap().code();
+        let order = class("Order");
+        println!("{order}");
+        assert!(order.contains("public static Order empty() { Order value = new Order(); value.id = \"\";value.quantity = 0;value.paid = false;value.total = BigInteger.ZERO;value.items = new ArrayList<>();value.totals = new TreeMap<>();value.byStatus = new EnumMap<>(Status.class);value.customer = Customer.empty(); return value; }"));
+        let customer = class("Customer");
+        assert!(customer.contains(
+            "public static Customer empty() { Customer value = new Customer(); value.name = \"\"; return value; }"
+        ));
+        let plain = generate_java_code(&ir).unwrap();
+        assert!(plain.iter().all(|c| !c.code().contains("empty()")));
+    }
+}
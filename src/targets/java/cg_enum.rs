@@ -1,39 +1,152 @@
 use crate::errors::Error;
 use crate::ir::{Enum, EnumVariant};
+use crate::targets::java::context::JavaContext;
 use crate::targets::java::JavaClass;
+use crate::targets::java::{cg_error, cg_fingerprint, cg_flags, cg_json_schema};
+use genco::prelude::*;
 use genco::quote;
 
-pub fn generate_enum_class(enum_ir: &Enum) -> Result<JavaClass, Error> {
-    let class_name = enum_ir.name().as_pascal_case();
+pub fn generate_enum_class(enum_ir: &Enum, ctx: &JavaContext) -> Result<JavaClass, Error> {
+    if let Some(format) = enum_ir.flags() {
+        return cg_flags::generate_flags_class(enum_ir, format, ctx);
+    }
+    let class_name = ctx.class_name(enum_ir.self_type());
     let class_name_str = class_name.as_str();
+    let display_names = enum_ir
+        .variants()
+        .iter()
+        .any(|v| v.display_name().is_some());
     let variants = enum_ir.variants().iter().map(|variant: &EnumVariant| {
         let name = variant.name().as_upper_snake_case();
         let json_name = variant.json_name();
+        let doc = variant
+            .description()
+            .map(|d| java::block_comment(d.lines()));
+        let display_name = display_names.then(|| {
+            let display_name = variant.display_name().unwrap_or(json_name);
+            quote!(($[str]($[const](display_name))))
+        });
         quote!(
+            $doc
             @JsonProperty($[str]($[const](json_name)))
-            $name
+            $name$display_name
         )
     });
 
+    let key_serializers =
+        ctx.config().concrete_maps() && ctx.is_map_key(enum_ir.self_type().type_name());
+    let constants = [
+        cg_json_schema::generate_constant(enum_ir.self_type(), ctx, false),
+        cg_fingerprint::generate_constant(enum_ir.self_type(), ctx, false),
+        key_serializers.then(|| generate_key_serializers(enum_ir, class_name_str)),
+        cg_error::generate_enum_methods(enum_ir, ctx),
+        display_names.then(|| generate_display_name(class_name_str)),
+        ctx.config()
+            .display_name_bundle()
+            .map(|bundle| generate_localized_display_name(enum_ir, bundle)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<java::Tokens>>();
+    let constants = (!constants.is_empty()).then(|| quote!(; $(for c in constants => $c)));
+    let key_annotations = key_serializers.then(|| {
+        quote!(
+            @JsonSerialize(keyUsing = $class_name_str.KeySerializer.class)
+            @JsonDeserialize(keyUsing = $class_name_str.KeyDeserializer.class)
+        )
+    });
+    let key_imports = key_serializers.then(|| {
+        quote!(
+            import com.fasterxml.jackson.core.JsonGenerator;
+            import com.fasterxml.jackson.databind.DeserializationContext;
+            import com.fasterxml.jackson.databind.JsonSerializer;
+            import com.fasterxml.jackson.databind.SerializerProvider;
+            import com.fasterxml.jackson.databind.annotation.JsonDeserialize;
+            import com.fasterxml.jackson.databind.annotation.JsonSerialize;
+            import java.io.IOException;
+        )
+    });
     let tokens = quote!(
         import com.fasterxml.jackson.annotation.JsonProperty;
+        $key_imports
 
+        $key_annotations
         public enum $class_name_str {
-            $(for v in variants join (,) => $v)
+            $(for v in variants join (,) => $v)$constants
         }
     );
 
     JavaClass::from_tokens(class_name, tokens)
 }
 
+/// Returns the field, the constructor and the getter of the display names of the enum constants.
+/// Constants without a display name use their JSON name.
+fn generate_display_name(class_name: &str) -> java::Tokens {
+    quote!(
+        private final String displayName;
+
+        $class_name(String displayName) {
+            this.displayName = displayName;
+        }
+
+        public String displayName() {
+            return displayName;
+        }
+    )
+}
+
+/// Returns the method reading the display name of a constant from a resource bundle in the given locale.
+/// Keys are the ones of bundles generated with `targets::properties`, e.g. `OrderStatus.PAYMENT_PENDING`.
+fn generate_localized_display_name(enum_ir: &Enum, bundle: &str) -> java::Tokens {
+    let prefix = format!("{}.", enum_ir.self_type().type_name());
+    quote!(
+        public String displayName(java.util.Locale locale) {
+            return java.util.ResourceBundle.getBundle($[str]($[const](bundle)), locale).getString($[str]($[const](prefix)) + name());
+        }
+    )
+}
+
+/// This function generates classes writing and reading enum values used as map keys with their JSON names.
+fn generate_key_serializers(enum_ir: &Enum, class_name: &str) -> java::Tokens {
+    let variants = enum_ir
+        .variants()
+        .iter()
+        .map(|v| (v.name().as_upper_snake_case(), v.json_name().as_str()))
+        .collect::<Vec<(String, &str)>>();
+    let message = format!("not a variant of {class_name}");
+    quote!(
+        public static class KeySerializer extends JsonSerializer<$class_name> {
+            @Override
+            public void serialize($class_name value, JsonGenerator gen, SerializerProvider serializers) throws IOException {
+                switch (value) {
+                    $(for (name, json_name) in &variants join ($['\r']) => case $name: gen.writeFieldName($[str]($[const](*json_name))); break;)
+                }
+            }
+        }
+
+        public static class KeyDeserializer extends com.fasterxml.jackson.databind.KeyDeserializer {
+            @Override
+            public Object deserializeKey(String key, DeserializationContext ctxt) throws IOException {
+                switch (key) {
+                    $(for (name, json_name) in &variants join ($['\r']) => case $[str]($[const](*json_name)): return $name;)
+                    default:
+                        return ctxt.handleWeirdKey($class_name.class, key, $[str]($[const](message)));
+                }
+            }
+        }
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use crate as rdc;
     use crate::ir::TypeTarget::Java;
     use crate::ir::{CustomType, Enum, EnumVariant, IntermediateRepresentation, Name};
-    use crate::targets::java::tests::run_java;
-    use crate::targets::java::{generate_java_code, JavaClass};
-    use crate::RDC;
+    use crate::targets::java::verify::run_java;
+    use crate::targets::java::{
+        generate_java_code, generate_java_code_with_config, JavaClass, JavaConfig,
+    };
+    use crate::{rdc_java, RDC};
     use genco::quote;
     use serde::{Deserialize, Serialize};
     use std::fmt::Debug;
@@ -117,4 +230,128 @@ mod tests {
             assert_eq!(e, deserialized);
         }
     }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Report {
+        totals: std::collections::HashMap<ExportType, i64>,
+        ordered: std::collections::BTreeMap<String, i64>,
+        labels: std::collections::HashMap<String, String>,
+    }
+
+    #[test]
+    fn concrete_maps() {
+        let mut ir = IntermediateRepresentation::new(Java);
+        ir.add::<Report>();
+        let plain = generate_java_code(&ir).unwrap();
+        let report = plain.iter().find(|c| c.name() == "Report").unwrap();
+        assert!(report.code().contains("private Map<String, Long> ordered;"));
+        let config = JavaConfig::new().with_concrete_maps(true);
+        let classes = generate_java_code_with_config(&ir, &config).unwrap();
+        let class = |name: &str| classes.iter().find(|c| c.name() == name).unwrap().code();
+        let report = class("Report");
+        assert!(report.contains("private EnumMap<ExportType, Long> totals;"));
+        assert!(report.contains("private TreeMap<String, Long> ordered;"));
+        assert!(report.contains("private LinkedHashMap<String, String> labels;"));
+        let export_type = class("ExportType");
+        println!("{export_type}");
+        assert!(export_type.contains(
+            "@JsonSerialize(keyUsing = ExportType.KeySerializer.class) @JsonDeserialize(keyUsing = ExportType.KeyDeserializer.class) public enum ExportType"
+        ));
+        assert!(export_type.contains("case CSV: gen.writeFieldName(\"CSV\"); break;"));
+        assert!(export_type.contains("case \"Json\": return JSON;"));
+        assert!(export_type.contains(
+            "return ctxt.handleWeirdKey(ExportType.class, key, \"not a variant of ExportType\");"
+        ));
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum PaymentMethod {
+        CreditCard,
+        #[serde(rename = "bank_transfer")]
+        BankTransfer,
+        Cash,
+    }
+
+    #[test]
+    fn display_names() {
+        let mut ir = IntermediateRepresentation::new(Java);
+        ir.add::<PaymentMethod>();
+        ir.set_variant_display(
+            "PaymentMethod",
+            "CreditCard",
+            "Credit Card",
+            Some("Visa, Mastercard or American Express."),
+        )
+        .unwrap();
+        ir.set_variant_display("PaymentMethod", "BankTransfer", "Bank transfer", None)
+            .unwrap();
+        let classes = generate_java_code(&ir).unwrap();
+        let code = classes[0].code();
+        println!("{code}");
+        assert!(code.contains(
+            "/**\n * Visa, Mastercard or American Express.\n */ @JsonProperty(\"CreditCard\")"
+        ));
+        assert!(code.contains("@JsonProperty(\"CreditCard\") CREDIT_CARD(\"Credit Card\")"));
+        assert!(code.contains("@JsonProperty(\"bank_transfer\") BANK_TRANSFER(\"Bank transfer\")"));
+        assert!(code.contains("@JsonProperty(\"Cash\") CASH(\"Cash\");"));
+        assert!(
+            code.contains("PaymentMethod(String displayName) { this.displayName = displayName; }")
+        );
+        assert!(code.contains("public String displayName() { return displayName; }"));
+        let error = ir
+            .set_variant_display("PaymentMethod", "Cheque", "Cheque", None)
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.message(),
+            "`PaymentMethod::Cheque` is not an enum variant in the IR"
+        );
+        let mut plain = IntermediateRepresentation::new(Java);
+        plain.add::<PaymentMethod>();
+        let classes = generate_java_code(&plain).unwrap();
+        assert!(!classes[0].code().contains("displayName"));
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Shipping {
+        #[rdc(display = "Express delivery", description = "Delivered the next day.")]
+        Express,
+        Standard,
+    }
+
+    #[test]
+    fn derived_display_names() {
+        let classes = rdc_java!(Shipping).unwrap();
+        let code = classes[0].code();
+        assert!(code.contains(
+            "/**\n * Delivered the next day.\n */ @JsonProperty(\"Express\") EXPRESS(\"Express delivery\")"
+        ));
+        assert!(code.contains("@JsonProperty(\"Standard\") STANDARD(\"Standard\");"));
+    }
+
+    #[test]
+    fn localized_display_names() {
+        let mut ir = IntermediateRepresentation::new(Java);
+        ir.add::<PaymentMethod>();
+        ir.set_variant_translation("PaymentMethod", "CreditCard", "de", "Kreditkarte")
+            .unwrap();
+        let config = JavaConfig::new().with_display_name_bundle("labels");
+        let classes = generate_java_code_with_config(&ir, &config).unwrap();
+        let code = classes[0].code();
+        assert!(code.contains(
+            "public String displayName(Locale locale) { return ResourceBundle.getBundle(\"labels\", locale).getString(\"PaymentMethod.\" + name()); }"
+        ));
+        assert!(!code.contains("private final String displayName;"));
+        let error = ir
+            .set_variant_translation("PaymentMethod", "Cheque", "de", "Scheck")
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.message(),
+            "`PaymentMethod::Cheque` is not an enum variant in the IR"
+        );
+    }
 }
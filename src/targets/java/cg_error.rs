@@ -0,0 +1,315 @@
+use crate::errors::Error;
+use crate::ir::{CustomType, DataEnum, DataEnumVariant, Enum, Name};
+use crate::targets::java::cg_data_enum;
+use crate::targets::java::context::JavaContext;
+use crate::targets::java::JavaClass;
+use genco::prelude::*;
+use genco::quote;
+
+/// Returns the `getCode()`, `getDetail()` and `getMessage()` methods of a data enum marked as an error type.
+pub fn generate_data_enum_methods(de: &DataEnum, ctx: &JavaContext) -> java::Tokens {
+    if !ctx.config().is_error_type(de.self_type().type_name()) {
+        return quote!();
+    }
+    let cases = de
+        .variants()
+        .iter()
+        .map(|v| {
+            let name = v.name().as_upper_snake_case();
+            let message = message(de.self_type(), v.name(), v.json_name(), ctx, |p| {
+                placeholder(v, p, ctx)
+            });
+            quote!(case $name: return $message;)
+        })
+        .collect::<Vec<java::Tokens>>();
+    quote!(
+        public Variant getCode() {
+            return variant;
+        }
+
+        public Object getDetail() {
+            return value;
+        }
+
+        public String getMessage() {
+            switch (variant) {
+                $(for c in cases join ($['\r']) => $c)
+                default:
+                    throw new IllegalStateException("Invalid variant: " + variant);
+            }
+        }
+    )
+}
+
+/// Returns the `getMessage()` method of a data enum marked as an error type and generated as sealed records.
+/// The record itself tells the variant and carries its values, so there are no `getCode()` and `getDetail()` methods.
+pub fn generate_data_enum_record_methods(de: &DataEnum, ctx: &JavaContext) -> java::Tokens {
+    if !ctx.config().is_error_type(de.self_type().type_name()) {
+        return quote!();
+    }
+    let cases = de
+        .variants()
+        .iter()
+        .map(|v| {
+            let record_name = cg_data_enum::record_name(de, v, ctx);
+            let message = message(de.self_type(), v.name(), v.json_name(), ctx, |p| {
+                record_placeholder(v, p, ctx)
+            });
+            quote!(
+                if (this instanceof $record_name value) {
+                    return $message;
+                }
+            )
+        })
+        .collect::<Vec<java::Tokens>>();
+    quote!(
+        default String getMessage() {
+            $(for c in cases => $c)
+            throw new IllegalStateException("Invalid variant: " + this);
+        }
+    )
+}
+
+/// Returns the `getMessage()` method of an enum marked as an error type.
+pub fn generate_enum_methods(e: &Enum, ctx: &JavaContext) -> Option<java::Tokens> {
+    if !ctx.config().is_error_type(e.self_type().type_name()) {
+        return None;
+    }
+    let cases = e
+        .variants()
+        .iter()
+        .map(|v| {
+            let name = v.name().as_upper_snake_case();
+            let message = message(e.self_type(), v.name(), v.json_name(), ctx, |_| None);
+            quote!(case $name: return $message;)
+        })
+        .collect::<Vec<java::Tokens>>();
+    Some(quote!(
+        public String getMessage() {
+            switch (this) {
+                $(for c in cases join ($['\r']) => $c)
+                default:
+                    throw new IllegalStateException("Invalid variant: " + this);
+            }
+        }
+    ))
+}
+
+/// This function generates an exception class carrying an error type, e.g. `ApiErrorException` for `ApiError`.
+pub fn generate_exception_class(
+    self_type: &CustomType,
+    ctx: &JavaContext,
+) -> Result<JavaClass, Error> {
+    let error_class = &ctx.class_name(self_type);
+    let class_name = format!("{error_class}Exception");
+    let class_name_str = class_name.as_str();
+    let tokens = quote!(
+        public class $class_name_str extends RuntimeException {
+            private final $error_class error;
+
+            public $class_name_str($error_class error) {
+                super(error.getMessage());
+                this.error = error;
+            }
+
+            public $error_class getError() {
+                return error;
+            }
+        }
+    );
+    JavaClass::from_tokens(class_name, tokens)
+}
+
+/// Returns a Java expression building the message of a variant from its `#[error(...)]` format.
+/// Placeholders are resolved with `placeholder`, unknown ones are reported with a warning and kept as they are.
+fn message<F>(
+    self_type: &CustomType,
+    variant: &Name,
+    json_name: &str,
+    ctx: &JavaContext,
+    placeholder: F,
+) -> java::Tokens
+where
+    F: Fn(&str) -> Option<java::Tokens>,
+{
+    let type_name = self_type.type_name();
+    let variant_name = variant.as_pascal_case();
+    let format = match ctx.config().error_message(type_name, &variant_name) {
+        Some(format) => format,
+        None => return quote!($[str]($[const](json_name))),
+    };
+    let mut parts: Vec<java::Tokens> = Vec::new();
+    let mut literal = String::new();
+    let mut chars = format.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut spec = String::new();
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        break;
+                    }
+                    spec.push(c);
+                }
+                let argument = spec.split(':').next().unwrap_or("").trim();
+                match placeholder(argument) {
+                    Some(value) => {
+                        if !literal.is_empty() {
+                            parts.push(quote!($[str]($[const](literal.clone()))));
+                            literal.clear();
+                        }
+                        parts.push(quote!(String.valueOf($value)));
+                    }
+                    None => {
+                        ctx.warn(format!(
+                            "Message of `{type_name}::{variant_name}` refers to `{argument}`, which is not a field of the variant"
+                        ));
+                        literal.push_str(&format!("{{{spec}}}"));
+                    }
+                }
+            }
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() || parts.is_empty() {
+        parts.push(quote!($[str]($[const](literal))));
+    }
+    quote!($(for p in parts join ( + ) => $p))
+}
+
+/// Returns the getter call of the field of a data enum variant referred to by a placeholder.
+fn placeholder(
+    variant: &DataEnumVariant,
+    argument: &str,
+    ctx: &JavaContext,
+) -> Option<java::Tokens> {
+    match variant {
+        DataEnumVariant::Unit { .. } => None,
+        DataEnumVariant::Tuple { fields, .. } => {
+            let index = argument
+                .parse::<usize>()
+                .ok()
+                .filter(|i| *i < fields.len())?;
+            let getter =
+                ctx.variant_getter_name(variant.name(), (fields.len() != 1).then_some(index));
+            Some(quote!($getter()))
+        }
+        DataEnumVariant::Object { fields, .. } => {
+            let field = fields
+                .iter()
+                .find(|f| f.name().as_snake_case() == argument)?;
+            let getter = ctx.getter_name(field.name(), field.field_type());
+            let variant_getter = ctx.variant_getter_name(variant.name(), None);
+            Some(quote!($variant_getter().$getter()))
+        }
+    }
+}
+
+/// Returns the accessor call of the component of a variant record referred to by a placeholder.
+fn record_placeholder(
+    variant: &DataEnumVariant,
+    argument: &str,
+    ctx: &JavaContext,
+) -> Option<java::Tokens> {
+    let accessor = match variant {
+        DataEnumVariant::Unit { .. } => return None,
+        DataEnumVariant::Tuple { fields, .. } => {
+            let index = argument
+                .parse::<usize>()
+                .ok()
+                .filter(|i| *i < fields.len())?;
+            format!("arg{index}")
+        }
+        DataEnumVariant::Object { fields, .. } => {
+            let field = fields
+                .iter()
+                .find(|f| f.name().as_snake_case() == argument)?;
+            ctx.field_name(field.name())
+        }
+    };
+    Some(quote!(value.$accessor()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::ir::{IntermediateRepresentation, TypeTarget};
+    use crate::targets::java::{generate_java_code_with_config, JavaConfig};
+    use crate::RDC;
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum ApiError {
+        NotFound(String),
+        Conflict(String, i64),
+        #[serde(rename = "invalid_field")]
+        InvalidField {
+            field: String,
+            reason: String,
+        },
+        Unauthorized,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum AuthError {
+        Expired,
+        Revoked,
+    }
+
+    #[test]
+    fn error_types() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<ApiError>();
+        ir.add::<AuthError>();
+        let config = JavaConfig::new()
+            .with_error_type("ApiError")
+            .with_error_type("AuthError")
+            .with_error_message("ApiError", "NotFound", "{0} was not found")
+            .with_error_message("ApiError", "Conflict", "{0} conflicts with version {1}")
+            .with_error_message(
+                "ApiError",
+                "InvalidField",
+                "invalid {field}: {reason:?} {{{user}}}",
+            )
+            .with_error_message("AuthError", "Expired", "session expired")
+            .with_error_exceptions(true);
+        let classes = generate_java_code_with_config(&ir, &config).unwrap();
+        let api_error = classes.iter().find(|c| c.name() == "ApiError").unwrap();
+        let code = api_error.code();
+        println!("{code}");
+        assert!(code.contains("public Variant getCode() { return variant; }"));
+        assert!(code.contains("public Object getDetail() { return value; }"));
+        assert!(code.contains(
+            "case NOT_FOUND: return String.valueOf(getNotFound()) + \" was not found\";"
+        ));
+        assert!(code.contains("case CONFLICT: return String.valueOf(getConflict0()) + \" conflicts with version \" + String.valueOf(getConflict1());"));
+        assert!(code.contains("case INVALID_FIELD: return \"invalid \" + String.valueOf(getInvalidField().getField()) + \": \" + String.valueOf(getInvalidField().getReason()) + \" {{user}}\";"));
+        assert!(code.contains("case UNAUTHORIZED: return \"Unauthorized\";"));
+        assert_eq!(
+            api_error.warnings(),
+            ["Message of `ApiError::InvalidField` refers to `user`, which is not a field of the variant"]
+        );
+        let auth_error = classes.iter().find(|c| c.name() == "AuthError").unwrap();
+        assert!(auth_error.code().contains(
+            "public String getMessage() { switch (this) { case EXPIRED: return \"session expired\";"
+        ));
+        let exception = classes
+            .iter()
+            .find(|c| c.name() == "ApiErrorException")
+            .unwrap();
+        assert!(exception.code().contains("public class ApiErrorException extends RuntimeException { private final ApiError error; public ApiErrorException(ApiError error) { super(error.getMessage()); this.error = error; }"));
+        assert!(classes.iter().any(|c| c.name() == "AuthErrorException"));
+        let plain = generate_java_code_with_config(&ir, &JavaConfig::new()).unwrap();
+        assert!(!plain[1].code().contains("getMessage"));
+        assert_eq!(plain.len(), 2);
+    }
+}
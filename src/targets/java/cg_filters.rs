@@ -0,0 +1,106 @@
+use crate::errors::Error;
+use crate::ir::{IntermediateRepresentation, Name, Struct};
+use crate::targets::java::cg_utils::Annotation;
+use crate::targets::java::context::JavaContext;
+use crate::targets::java::JavaClass;
+use genco::prelude::*;
+use genco::quote;
+
+/// Returns the `@JsonFilter` annotation of a data class, with its class name as the filter id, when filters are enabled.
+pub fn class_annotations(dc: &Struct, ctx: &JavaContext) -> Vec<Annotation> {
+    if !ctx.config().json_filters() {
+        return Vec::new();
+    }
+    let filter_id = ctx.class_name(dc.self_type());
+    vec![(
+        "com.fasterxml.jackson.annotation.JsonFilter",
+        quote!(@JsonFilter($[str]($[const](filter_id)))),
+    )]
+}
+
+/// This function generates the `JsonFilters` class with the filter ids of all the data classes
+/// and helpers building `FilterProvider`s from field names, e.g. `Invoice.Fields.NUMBER`.
+///
+/// Jackson fails to serialize a filtered class without a filter provider,
+/// so every provider built by the helpers serializes all the properties of classes without a filter of their own.
+pub fn generate_filters_class(
+    ir: &IntermediateRepresentation,
+    ctx: &JavaContext,
+) -> Result<JavaClass, Error> {
+    let filter_ids = ir
+        .structs()
+        .iter()
+        .map(|s| {
+            let class_name = ctx.class_name(s.self_type());
+            (
+                Name::from_pascal_case(&class_name).as_upper_snake_case(),
+                class_name,
+            )
+        })
+        .collect::<Vec<(String, String)>>();
+    let tokens: java::Tokens = quote!(
+        import com.fasterxml.jackson.databind.ser.impl.SimpleBeanPropertyFilter;
+        import com.fasterxml.jackson.databind.ser.impl.SimpleFilterProvider;
+
+        public final class JsonFilters {
+            $(for (name, id) in &filter_ids => public static final String $name = $[str]($[const](id));)
+
+            public static SimpleFilterProvider serializeAll() {
+                return new SimpleFilterProvider().setDefaultFilter(SimpleBeanPropertyFilter.serializeAll());
+            }
+
+            public static SimpleFilterProvider only(String filterId, String... fields) {
+                return serializeAll().addFilter(filterId, SimpleBeanPropertyFilter.filterOutAllExcept(fields));
+            }
+
+            public static SimpleFilterProvider except(String filterId, String... fields) {
+                return serializeAll().addFilter(filterId, SimpleBeanPropertyFilter.serializeAllExcept(fields));
+            }
+
+            private JsonFilters() {}
+        }
+    );
+    JavaClass::from_tokens("JsonFilters".to_string(), tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::targets::java::JavaConfig;
+    use crate::{rdc_java, RDC};
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct UserProfile {
+        name: String,
+        email: String,
+        address: Address,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Address {
+        city: String,
+    }
+
+    #[test]
+    fn json_filters() {
+        let config = JavaConfig::new()
+            .with_json_filters(true)
+            .with_rdc_json(true);
+        let classes = rdc_java!(config = config; UserProfile).unwrap();
+        let class = |name: &str| classes.iter().find(|c| c.name() == name).unwrap().code();
+        let profile = class("UserProfile");
+        println!("{profile}");
+        assert!(profile.contains("import com.fasterxml.jackson.annotation.JsonFilter;"));
+        assert!(profile.contains("@JsonFilter(\"UserProfile\") public class UserProfile"));
+        let filters = class("JsonFilters");
+        println!("{filters}");
+        assert!(filters.contains("public static final String USER_PROFILE = \"UserProfile\";"));
+        assert!(filters.contains("public static final String ADDRESS = \"Address\";"));
+        assert!(filters.contains("return serializeAll().addFilter(filterId, SimpleBeanPropertyFilter.filterOutAllExcept(fields));"));
+        assert!(class("RdcJson").contains("mapper.setFilterProvider(JsonFilters.serializeAll());"));
+        let plain = rdc_java!(UserProfile).unwrap();
+        assert!(plain.iter().all(|c| !c.code().contains("JsonFilter")));
+    }
+}
@@ -0,0 +1,83 @@
+use crate::ir::CustomType;
+use crate::targets::java::context::JavaContext;
+use genco::prelude::*;
+use genco::quote;
+
+/// Returns a `SCHEMA_FINGERPRINT` constant holding the fingerprint of the JSON shape of the type, when it is enabled.
+/// It equals `IntermediateRepresentation::fingerprint` of the type, so Java and Rust services can compare them.
+pub fn generate_constant(
+    custom_type: &CustomType,
+    ctx: &JavaContext,
+    interface: bool,
+) -> Option<java::Tokens> {
+    if !ctx.config().schema_fingerprints() {
+        return None;
+    }
+    let fingerprint = ctx.fingerprint(custom_type.type_name())?;
+    let modifiers = (!interface).then(|| quote!(public static final));
+    Some(quote!($modifiers String SCHEMA_FINGERPRINT = $[str]($[const](fingerprint));))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::ir::{IntermediateRepresentation, TypeTarget};
+    use crate::targets::java::{generate_java_code_with_config, JavaConfig};
+    use crate::{rdc_polymorphic, RDC};
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Order {
+        id: String,
+        status: Status,
+        payment: Payment,
+        item: Box<dyn Item>,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Status {
+        Open,
+        Closed,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Payment {
+        Cash,
+        Card(String),
+    }
+
+    trait Item {}
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Book {
+        title: String,
+    }
+
+    impl Item for Book {}
+
+    rdc_polymorphic!(dyn Item => Book);
+
+    #[test]
+    fn schema_fingerprints() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Order>();
+        let config = JavaConfig::new()
+            .with_schema_fingerprints(true)
+            .with_json_schema_constants(true);
+        let classes = generate_java_code_with_config(&ir, &config).unwrap();
+        let class = |name: &str| classes.iter().find(|c| c.name() == name).unwrap().code();
+        let constant = |name: &str| {
+            let fingerprint = ir.fingerprint(name).unwrap();
+            format!("String SCHEMA_FINGERPRINT = \"{fingerprint}\";")
+        };
+        assert!(class("Order").contains(&format!("public static final {}", constant("Order"))));
+        assert!(class("Status").contains("CLOSED; public static final String JSON_SCHEMA"));
+        assert!(class("Status").contains(&format!("public static final {}", constant("Status"))));
+        assert!(class("Payment").contains(&format!("public static final {}", constant("Payment"))));
+        assert!(class("Item").contains(&constant("Item")));
+        assert!(!class("Item").contains("static final String SCHEMA_FINGERPRINT"));
+    }
+}
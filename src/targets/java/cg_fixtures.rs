@@ -0,0 +1,449 @@
+use crate::errors::Error;
+use crate::ir::{DataEnumVariant, IntermediateRepresentation, Struct, Type, TypeKind};
+use crate::targets::java::cg_data_enum;
+use crate::targets::java::config::{DataEnumEncoding, UnsignedLongMapping};
+use crate::targets::java::context::{ClassKind, JavaContext};
+use crate::targets::java::JavaClass;
+use genco::prelude::*;
+use genco::quote;
+use std::collections::HashSet;
+
+/// This function generates a `<Class>Fixtures` class with a `create()` method
+/// returning an instance with every field populated with deterministic placeholder data.
+///
+/// Numbers are `1`, booleans are `true`, strings are the JSON names of their fields,
+/// collections hold a single element, and nested classes come from their own fixtures.
+/// Scalar and enum fields with an example value are set to that example instead.
+/// Transparent structs are created from a placeholder of the value they wrap.
+/// Fields through which the class references itself are left empty, so that fixtures of recursive types terminate.
+pub fn generate_fixtures_class(
+    dc: &Struct,
+    ir: &IntermediateRepresentation,
+    ctx: &JavaContext,
+) -> Result<JavaClass, Error> {
+    let generator = FixtureGenerator { ir, ctx };
+    let class_name = ctx.class_name(dc.self_type());
+    let fixtures_name = format!("{class_name}Fixtures");
+    let type_name = dc.self_type().type_name();
+    if dc.is_transparent() {
+        let values = dc
+            .fields()
+            .iter()
+            .map(|f| generator.value(f.field_type(), f.json_name()))
+            .collect::<Vec<String>>();
+        let tokens: java::Tokens = quote!(
+            public final class $(&fixtures_name) {
+                public static $(&class_name) create() {
+                    return new $(&class_name)($(values.join(", ")));
+                }
+
+                private $(&fixtures_name)() {}
+            }
+        );
+        return JavaClass::from_tokens(fixtures_name, tokens);
+    }
+    let setters = dc
+        .fields()
+        .iter()
+        .filter(|f| {
+            if f.is_readonly() {
+                ctx.warn(format!(
+                    "`{type_name}.{}` is read-only, so fixtures leave it unset",
+                    f.name().as_snake_case()
+                ));
+            }
+            !f.is_readonly()
+        })
+        .filter(|f| !ctx.collects_extra_properties(dc, f))
+        .map(|f| {
+            let setter = ctx.setter_name(f.name(), f.field_type());
+            let example = f.example().and_then(|example| {
+                let value = generator.example_value(f.field_type(), example);
+                if value.is_none() {
+                    ctx.warn(format!(
+                        "Example `{example}` of `{type_name}.{}` does not fit its type, so fixtures use a placeholder",
+                        f.name().as_snake_case()
+                    ));
+                }
+                value
+            });
+            let value = if generator.reaches(f.field_type(), type_name, &mut HashSet::new()) {
+                generator.empty_value(f.field_type())
+            } else if let Some(example) = example {
+                example
+            } else {
+                generator.value(f.field_type(), f.json_name())
+            };
+            quote!(value.$setter($value);)
+        })
+        .collect::<Vec<java::Tokens>>();
+    let tokens: java::Tokens = quote!(
+        public final class $(&fixtures_name) {
+            public static $(&class_name) create() {
+                $(&class_name) value = new $(&class_name)();
+                $(for s in setters => $s)
+                return value;
+            }
+
+            private $(&fixtures_name)() {}
+        }
+    );
+    JavaClass::from_tokens(fixtures_name, tokens)
+}
+
+/// Returns a Java expression creating a placeholder value of the type, built the same way as in fixtures classes.
+pub fn fixture_value(t: &Type, ir: &IntermediateRepresentation, ctx: &JavaContext) -> String {
+    FixtureGenerator { ir, ctx }.value(t, "value")
+}
+
+struct FixtureGenerator<'a> {
+    ir: &'a IntermediateRepresentation,
+    ctx: &'a JavaContext<'a>,
+}
+
+impl FixtureGenerator<'_> {
+    /// Returns a Java expression creating a placeholder value of the type.
+    fn value(&self, t: &Type, json_name: &str) -> String {
+        match t.kind() {
+            TypeKind::Option(inner) => self.value(inner, json_name),
+            TypeKind::List(element) => {
+                format!("java.util.List.of({})", self.value(element, json_name))
+            }
+            TypeKind::Map(key, value) => {
+                let entries = format!(
+                    "java.util.Map.of({}, {})",
+                    self.value(key, "key"),
+                    self.value(value, json_name)
+                );
+                match self.ctx.map_class(t) {
+                    "java.util.Map" => entries,
+                    map_class => format!("new {map_class}<>({entries})"),
+                }
+            }
+            TypeKind::UnsignedLong => match self.ctx.config().unsigned_long_mapping() {
+                UnsignedLongMapping::BigInteger => "java.math.BigInteger.ONE".to_string(),
+                UnsignedLongMapping::Long => "1L".to_string(),
+            },
+            TypeKind::Named => self.named_value(t, json_name),
+        }
+    }
+
+    fn named_value(&self, t: &Type, json_name: &str) -> String {
+        let name = t.type_name();
+        let class_name = self.ctx.type_name(t);
+        match self.ctx.class_kind(t) {
+            Some(ClassKind::Struct) => format!("{class_name}Fixtures.create()"),
+            Some(ClassKind::Enum) => format!("{class_name}.values()[0]"),
+            Some(ClassKind::Flags) => format!("{class_name}.empty()"),
+            Some(ClassKind::DataEnum) => {
+                let Some(de) = self
+                    .ir
+                    .data_enums()
+                    .iter()
+                    .find(|de| de.self_type().type_name() == name)
+                else {
+                    return "null".to_string();
+                };
+                let variant = &de.variants()[0];
+                let variant_name = variant.name().as_pascal_case();
+                let values = match variant {
+                    DataEnumVariant::Unit { .. } => Vec::new(),
+                    DataEnumVariant::Tuple { fields, .. } => fields
+                        .iter()
+                        .map(|f| self.value(f, variant.json_name()))
+                        .collect(),
+                    DataEnumVariant::Object { fields, .. } => fields
+                        .iter()
+                        .map(|f| self.value(f.field_type(), f.json_name()))
+                        .collect::<Vec<String>>(),
+                };
+                match self.ctx.config().data_enum_encoding() {
+                    DataEnumEncoding::Class => {
+                        let args = match variant {
+                            DataEnumVariant::Object { .. } => vec![format!(
+                                "new {class_name}.{variant_name}({})",
+                                values.join(", ")
+                            )],
+                            _ => values,
+                        };
+                        format!("{class_name}.of{variant_name}({})", args.join(", "))
+                    }
+                    DataEnumEncoding::SealedRecords => {
+                        let record_name = cg_data_enum::record_name(de, variant, self.ctx);
+                        format!("new {class_name}.{record_name}({})", values.join(", "))
+                    }
+                }
+            }
+            Some(ClassKind::Polymorphic) => {
+                let Some(p) = self
+                    .ir
+                    .polymorphics()
+                    .iter()
+                    .find(|p| p.self_type().type_name() == name)
+                else {
+                    return "null".to_string();
+                };
+                let variant = self.ctx.type_name(p.variants()[0].variant_type());
+                format!("{variant}Fixtures.create()")
+            }
+            None => match name.as_str() {
+                "Boolean" => "true".to_string(),
+                "Byte" => "(byte) 1".to_string(),
+                "Short" => "(short) 1".to_string(),
+                "Integer" => "1".to_string(),
+                "Long" => "1L".to_string(),
+                "Float" => "1.0f".to_string(),
+                "Double" => "1.0".to_string(),
+                "String" => format!("{json_name:?}"),
+                _ => "null".to_string(),
+            },
+        }
+    }
+
+    /// Returns a Java expression creating the example value of a scalar or enum type,
+    /// or `None` if the example is not a valid value of the type.
+    fn example_value(&self, t: &Type, example: &str) -> Option<String> {
+        match t.kind() {
+            TypeKind::Option(inner) => self.example_value(inner, example),
+            TypeKind::UnsignedLong => {
+                let n = example.parse::<u64>().ok()?;
+                Some(match self.ctx.config().unsigned_long_mapping() {
+                    UnsignedLongMapping::BigInteger => format!("new java.math.BigInteger(\"{n}\")"),
+                    UnsignedLongMapping::Long => format!("Long.parseUnsignedLong(\"{n}\")"),
+                })
+            }
+            TypeKind::Named => match self.ctx.class_kind(t) {
+                Some(ClassKind::Enum) => {
+                    let e = self
+                        .ir
+                        .enums()
+                        .iter()
+                        .find(|e| e.self_type().type_name() == t.type_name())?;
+                    let variant = e.variants().iter().find(|v| v.json_name() == example)?;
+                    Some(format!(
+                        "{}.{}",
+                        self.ctx.type_name(t),
+                        variant.name().as_upper_snake_case()
+                    ))
+                }
+                Some(_) => None,
+                None => match t.type_name().as_str() {
+                    "Boolean" => example.parse::<bool>().ok().map(|b| b.to_string()),
+                    "Byte" => example.parse::<i8>().ok().map(|n| format!("(byte) {n}")),
+                    "Short" => example.parse::<i16>().ok().map(|n| format!("(short) {n}")),
+                    "Integer" => example.parse::<i32>().ok().map(|n| n.to_string()),
+                    "Long" => example.parse::<i64>().ok().map(|n| format!("{n}L")),
+                    "Float" => example
+                        .parse::<f32>()
+                        .ok()
+                        .filter(|n| n.is_finite())
+                        .map(|n| format!("{n:?}f")),
+                    "Double" => example
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|n| n.is_finite())
+                        .map(|n| format!("{n:?}")),
+                    "java.math.BigInteger" => example
+                        .parse::<i128>()
+                        .ok()
+                        .map(|n| format!("new java.math.BigInteger(\"{n}\")")),
+                    "String" => Some(format!("{example:?}")),
+                    _ => None,
+                },
+            },
+            TypeKind::List(_) | TypeKind::Map(..) => None,
+        }
+    }
+
+    /// Returns an empty value of a type that references the class being created.
+    fn empty_value(&self, t: &Type) -> String {
+        match t.kind() {
+            TypeKind::List(_) => "java.util.List.of()".to_string(),
+            TypeKind::Map(key, _) => match self.ctx.map_class(t) {
+                "java.util.Map" => "java.util.Map.of()".to_string(),
+                "java.util.EnumMap" => {
+                    format!("new java.util.EnumMap<>({}.class)", self.ctx.type_name(key))
+                }
+                map_class => format!("new {map_class}<>()"),
+            },
+            _ => "null".to_string(),
+        }
+    }
+
+    /// Returns true if the type references the target type, directly or through other types.
+    fn reaches<'t>(&'t self, t: &'t Type, target: &str, visited: &mut HashSet<&'t str>) -> bool {
+        match t.kind() {
+            TypeKind::Option(inner) | TypeKind::List(inner) => self.reaches(inner, target, visited),
+            TypeKind::Map(key, value) => {
+                self.reaches(key, target, visited) || self.reaches(value, target, visited)
+            }
+            TypeKind::UnsignedLong => false,
+            TypeKind::Named => {
+                let name = t.type_name().as_str();
+                if name == target {
+                    return true;
+                }
+                if !visited.insert(name) {
+                    return false;
+                }
+                self.referenced_types(name)
+                    .into_iter()
+                    .any(|r| self.reaches(r, target, visited))
+            }
+        }
+    }
+
+    fn referenced_types(&self, name: &str) -> Vec<&Type> {
+        let mut types = Vec::new();
+        for s in self.ir.structs() {
+            if s.self_type().type_name() == name {
+                types.extend(s.fields().iter().map(|f| f.field_type()));
+            }
+        }
+        for de in self.ir.data_enums() {
+            if de.self_type().type_name() == name {
+                for v in de.variants() {
+                    match v {
+                        DataEnumVariant::Unit { .. } => {}
+                        DataEnumVariant::Tuple { fields, .. } => types.extend(fields.iter()),
+                        DataEnumVariant::Object { fields, .. } => {
+                            types.extend(fields.iter().map(|f| f.field_type()))
+                        }
+                    }
+                }
+            }
+        }
+        for p in self.ir.polymorphics() {
+            if p.self_type().type_name() == name {
+                types.extend(p.variants().iter().map(|v| v.variant_type()));
+            }
+        }
+        types
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::ir::{IntermediateRepresentation, TypeTarget};
+    use crate::targets::java::{generate_java_code_with_config, JavaConfig};
+    use crate::{rdc_java, RDC};
+    use std::collections::HashMap;
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Invoice {
+        number: String,
+        total: f64,
+        paid: bool,
+        lines: Vec<InvoiceLine>,
+        status: Status,
+        discount: Discount,
+        tags: HashMap<String, i64>,
+        sequence: u64,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct InvoiceLine {
+        sku: String,
+        #[serde(rename = "children")]
+        sub_lines: Vec<InvoiceLine>,
+        parent: Option<Box<InvoiceLine>>,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Status {
+        Draft,
+        Sent,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Discount {
+        Percent { value: i32 },
+        None,
+    }
+
+    #[test]
+    fn fixtures_classes() {
+        let config = JavaConfig::new()
+            .with_fixtures(true)
+            .with_class_suffix("Dto");
+        let classes = rdc_java!(config = config; Invoice).unwrap();
+        let class = |name: &str| classes.iter().find(|c| c.name() == name).unwrap().code();
+        let invoice = class("InvoiceDtoFixtures");
+        println!("{invoice}");
+        assert!(invoice.contains("public final class InvoiceDtoFixtures"));
+        assert!(invoice.contains("public static InvoiceDto create()"));
+        assert!(invoice.contains("value.setNumber(\"number\");"));
+        assert!(invoice.contains("value.setTotal(1.0);"));
+        assert!(invoice.contains("value.setPaid(true);"));
+        assert!(invoice.contains("value.setLines(List.of(InvoiceLineDtoFixtures.create()));"));
+        assert!(invoice.contains("value.setStatus(StatusDto.values()[0]);"));
+        assert!(invoice
+            .contains("value.setDiscount(DiscountDto.ofPercent(new DiscountDto.Percent(1)));"));
+        assert!(invoice.contains("value.setTags(Map.of(\"key\", 1L));"));
+        assert!(invoice.contains("value.setSequence(BigInteger.ONE);"));
+
+        let line = class("InvoiceLineDtoFixtures");
+        println!("{line}");
+        assert!(line.contains("value.setSubLines(List.of());"));
+        assert!(line.contains("value.setParent(null);"));
+    }
+
+    #[test]
+    fn fixtures_use_examples() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Invoice>();
+        ir.set_field_example("Invoice", "number", "INV-001")
+            .unwrap();
+        ir.set_field_example("Invoice", "total", "12.5").unwrap();
+        ir.set_field_example("Invoice", "status", "Sent").unwrap();
+        ir.set_field_example("Invoice", "sequence", "many").unwrap();
+        let config = JavaConfig::new().with_fixtures(true);
+        let classes = generate_java_code_with_config(&ir, &config).unwrap();
+        let invoice = classes
+            .iter()
+            .find(|c| c.name() == "InvoiceFixtures")
+            .unwrap();
+        let code = invoice.code();
+        println!("{code}");
+        assert!(code.contains("value.setNumber(\"INV-001\");"));
+        assert!(code.contains("value.setTotal(12.5);"));
+        assert!(code.contains("value.setStatus(Status.SENT);"));
+        assert!(code.contains("value.setSequence(BigInteger.ONE);"));
+        assert_eq!(
+            invoice.warnings(),
+            ["Example `many` of `Invoice.sequence` does not fit its type, so fixtures use a placeholder"]
+        );
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Receipt {
+        #[rdc(example = "R-17")]
+        number: String,
+        #[rdc(example = "3")]
+        copies: i32,
+    }
+
+    #[test]
+    fn fixtures_use_derived_examples() {
+        let config = JavaConfig::new().with_fixtures(true);
+        let classes = rdc_java!(config = config; Receipt).unwrap();
+        let receipt = classes
+            .iter()
+            .find(|c| c.name() == "ReceiptFixtures")
+            .unwrap();
+        assert!(receipt.code().contains("value.setNumber(\"R-17\");"));
+        assert!(receipt.code().contains("value.setCopies(3);"));
+    }
+
+    #[test]
+    fn fixtures_disabled_by_default() {
+        let classes = rdc_java!(Invoice).unwrap();
+        assert!(classes.iter().all(|c| !c.name().ends_with("Fixtures")));
+    }
+}
@@ -0,0 +1,301 @@
+use crate::errors::Error;
+use crate::ir::{Enum, FlagsFormat};
+use crate::targets::java::context::JavaContext;
+use crate::targets::java::JavaClass;
+use crate::targets::java::{cg_fingerprint, cg_json_schema};
+use genco::prelude::*;
+use genco::quote;
+
+/// This function generates a value class for a set of flags, e.g. a type declared with `bitflags!`.
+/// It holds the bits of the flags, which are listed in a nested `Flag` enum, and has bitwise helpers,
+/// conversions from and to an `EnumSet`, and a serializer and a deserializer for the JSON format of the flags.
+pub fn generate_flags_class(
+    enum_ir: &Enum,
+    format: FlagsFormat,
+    ctx: &JavaContext,
+) -> Result<JavaClass, Error> {
+    let class_name = ctx.class_name(enum_ir.self_type());
+    let class_name_str = class_name.as_str();
+    let flags = enum_ir
+        .variants()
+        .iter()
+        .map(|v| {
+            let name = v.name().as_upper_snake_case();
+            let bits = format!("{}L", v.bits().unwrap_or_default() as i64);
+            let json_name = v.json_name();
+            quote!($name($bits, $[str]($[const](json_name))))
+        })
+        .collect::<Vec<java::Tokens>>();
+    let (serialize, deserialize) = match format {
+        FlagsFormat::Bits => (
+            quote!(gen.writeNumber(value.bits);),
+            quote!(return new $class_name_str(p.getLongValue());),
+        ),
+        FlagsFormat::Names => (
+            quote!(gen.writeString(value.toString());),
+            quote!(return parse(p.getValueAsString(), p);),
+        ),
+    };
+    let parse = matches!(format, FlagsFormat::Names).then(|| {
+        let message = format!("Unknown flag of {class_name}: ");
+        quote!(
+            private static $class_name_str parse(String text, JsonParser p) throws IOException {
+                long bits = 0L;
+                for (String part : text.split("\\|")) {
+                    String name = part.trim();
+                    if (name.isEmpty()) {
+                        continue;
+                    }
+                    if (name.startsWith("0x")) {
+                        bits |= Long.parseUnsignedLong(name.substring(2), 16);
+                        continue;
+                    }
+                    Flag flag = null;
+                    for (Flag f : Flag.values()) {
+                        if (f.name.equals(name)) {
+                            flag = f;
+                        }
+                    }
+                    if (flag == null) {
+                        throw new JsonParseException(p, $[str]($[const](message)) + name);
+                    }
+                    bits |= flag.bits;
+                }
+                return new $class_name_str(bits);
+            }
+        )
+    });
+    let json_schema = cg_json_schema::generate_constant(enum_ir.self_type(), ctx, false);
+    let fingerprint = cg_fingerprint::generate_constant(enum_ir.self_type(), ctx, false);
+    let tokens: java::Tokens = quote!(
+        import com.fasterxml.jackson.core.JsonGenerator;
+        import com.fasterxml.jackson.core.JsonParseException;
+        import com.fasterxml.jackson.core.JsonParser;
+        import com.fasterxml.jackson.databind.DeserializationContext;
+        import com.fasterxml.jackson.databind.SerializerProvider;
+        import com.fasterxml.jackson.databind.annotation.JsonDeserialize;
+        import com.fasterxml.jackson.databind.annotation.JsonSerialize;
+        import com.fasterxml.jackson.databind.deser.std.StdDeserializer;
+        import com.fasterxml.jackson.databind.ser.std.StdSerializer;
+
+        import java.io.IOException;
+        import java.util.EnumSet;
+        import java.util.Set;
+
+        @JsonSerialize(using = $class_name_str.Serializer.class)
+        @JsonDeserialize(using = $class_name_str.Deserializer.class)
+        public final class $class_name_str implements java.io.Serializable {
+            $json_schema
+
+            $fingerprint
+
+            public enum Flag {
+                $(for f in flags join (,) => $f);
+
+                private final long bits;
+                private final String name;
+
+                Flag(long bits, String name) {
+                    this.bits = bits;
+                    this.name = name;
+                }
+
+                public long bits() {
+                    return bits;
+                }
+            }
+
+            private final long bits;
+
+            private $class_name_str(long bits) {
+                this.bits = bits;
+            }
+
+            public static $class_name_str empty() {
+                return new $class_name_str(0L);
+            }
+
+            public static $class_name_str all() {
+                return of(Flag.values());
+            }
+
+            public static $class_name_str fromBits(long bits) {
+                return new $class_name_str(bits);
+            }
+
+            public static $class_name_str of(Flag... flags) {
+                long bits = 0L;
+                for (Flag flag : flags) {
+                    bits |= flag.bits;
+                }
+                return new $class_name_str(bits);
+            }
+
+            public static $class_name_str of(Set<Flag> flags) {
+                return of(flags.toArray(new Flag[0]));
+            }
+
+            public long bits() {
+                return bits;
+            }
+
+            public boolean isEmpty() {
+                return bits == 0L;
+            }
+
+            public boolean contains(Flag flag) {
+                return (bits & flag.bits) == flag.bits;
+            }
+
+            public $class_name_str with(Flag flag) {
+                return new $class_name_str(bits | flag.bits);
+            }
+
+            public $class_name_str without(Flag flag) {
+                return new $class_name_str(bits & ~flag.bits);
+            }
+
+            public $class_name_str union($class_name_str other) {
+                return new $class_name_str(bits | other.bits);
+            }
+
+            public $class_name_str intersection($class_name_str other) {
+                return new $class_name_str(bits & other.bits);
+            }
+
+            public $class_name_str difference($class_name_str other) {
+                return new $class_name_str(bits & ~other.bits);
+            }
+
+            public EnumSet<Flag> toEnumSet() {
+                EnumSet<Flag> flags = EnumSet.noneOf(Flag.class);
+                for (Flag flag : Flag.values()) {
+                    if (contains(flag)) {
+                        flags.add(flag);
+                    }
+                }
+                return flags;
+            }
+
+            @Override
+            public boolean equals(Object o) {
+                return o instanceof $class_name_str && (($class_name_str) o).bits == bits;
+            }
+
+            @Override
+            public int hashCode() {
+                return Long.hashCode(bits);
+            }
+
+            @Override
+            public String toString() {
+                StringBuilder text = new StringBuilder();
+                long remaining = bits;
+                for (Flag flag : Flag.values()) {
+                    if (flag.bits != 0L && contains(flag) && (remaining & flag.bits) != 0L) {
+                        text.append(text.length() == 0 ? "" : " | ").append(flag.name);
+                        remaining &= ~flag.bits;
+                    }
+                }
+                if (remaining != 0L) {
+                    text.append(text.length() == 0 ? "" : " | ").append("0x").append(Long.toHexString(remaining));
+                }
+                return text.toString();
+            }
+
+            $parse
+
+            public static class Serializer extends StdSerializer<$class_name_str> {
+                public Serializer() {
+                    super($class_name_str.class);
+                }
+
+                @Override
+                public void serialize($class_name_str value, JsonGenerator gen, SerializerProvider provider) throws IOException {
+                    $serialize
+                }
+            }
+
+            public static class Deserializer extends StdDeserializer<$class_name_str> {
+                public Deserializer() {
+                    super($class_name_str.class);
+                }
+
+                @Override
+                public $class_name_str deserialize(JsonParser p, DeserializationContext ctxt) throws IOException {
+                    $deserialize
+                }
+            }
+        }
+    );
+    JavaClass::from_tokens(class_name, tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::{rdc_bitflags, rdc_java, RDC};
+
+    /// Mirrors the API of a type declared with `bitflags!`.
+    pub struct Permissions(u32);
+
+    impl Permissions {
+        pub const READ: Self = Self(0b001);
+        pub const WRITE: Self = Self(0b010);
+        pub const READ_WRITE: Self = Self(0b011);
+
+        pub fn bits(&self) -> u32 {
+            self.0
+        }
+    }
+
+    rdc_bitflags!(Permissions, format = names => READ, WRITE, READ_WRITE);
+
+    pub struct Features(u8);
+
+    impl Features {
+        pub const BETA: Self = Self(1);
+
+        pub fn bits(&self) -> u8 {
+            self.0
+        }
+    }
+
+    rdc_bitflags!(Features, format = bits => BETA);
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Member {
+        permissions: Permissions,
+        features: Option<Features>,
+    }
+
+    #[test]
+    fn flags_classes() {
+        let classes = rdc_java!(Member).unwrap();
+        let class = |name: &str| classes.iter().find(|c| c.name() == name).unwrap().code();
+        let member = class("Member");
+        assert!(member.contains("private Permissions permissions;"));
+        let permissions = class("Permissions");
+        println!("{permissions}");
+        assert!(permissions.contains("@JsonSerialize(using = Permissions.Serializer.class) @JsonDeserialize(using = Permissions.Deserializer.class) public final class Permissions implements Serializable"));
+        assert!(permissions.contains(
+            "public enum Flag { READ(1L, \"READ\"),WRITE(2L, \"WRITE\"),READ_WRITE(3L, \"READ_WRITE\");"
+        ));
+        assert!(permissions.contains(
+            "public boolean contains(Flag flag) { return (bits & flag.bits) == flag.bits; }"
+        ));
+        assert!(permissions.contains("gen.writeString(value.toString());"));
+        assert!(permissions.contains(
+            "throw new JsonParseException(p, \"Unknown flag of Permissions: \" + name);"
+        ));
+        let features = class("Features");
+        assert!(features.contains("gen.writeNumber(value.bits);"));
+        assert!(features.contains("return new Features(p.getLongValue());"));
+        assert!(!features.contains("parse("));
+        let mut ir = rdc::ir::IntermediateRepresentation::new(rdc::ir::TypeTarget::Java);
+        ir.add::<Member>();
+        let schema = rdc::targets::openapi::generate_json_schema(&ir, "Features").unwrap();
+        assert!(schema.contains("\"type\": \"integer\""));
+    }
+}
@@ -0,0 +1,154 @@
+use crate::errors::Error;
+use crate::ir::{IntermediateRepresentation, Type, TypeKind};
+use crate::targets::java::context::JavaContext;
+use crate::targets::java::JavaClass;
+use genco::prelude::*;
+use genco::quote;
+
+/// This function generates a class registering the generated classes in a graphql-java `RuntimeWiring`.
+/// Fields whose GraphQL names differ from Java property names get a `PropertyDataFetcher`,
+/// unions get a type resolver based on the Java class of the value,
+/// and the extended scalars used by the schema are registered.
+pub fn generate_wiring_class(
+    ir: &IntermediateRepresentation,
+    ctx: &JavaContext,
+    class_name: &str,
+) -> Result<JavaClass, Error> {
+    if let Some(de) = ir.data_enums().first() {
+        return Err(Error::new(&format!(
+            "Data enum `{}` can not be represented in GraphQL",
+            de.self_type().type_name()
+        )));
+    }
+    let mut imports = vec![
+        "graphql.schema.idl.RuntimeWiring",
+        "graphql.schema.idl.TypeRuntimeWiring",
+    ];
+    let mut wirings: Vec<java::Tokens> = Vec::new();
+    let field_types = ir
+        .structs()
+        .iter()
+        .flat_map(|s| s.fields().iter().map(|f| f.field_type()))
+        .collect::<Vec<&Type>>();
+    if field_types.iter().any(|t| uses_scalar(t, "Long")) {
+        wirings.push(quote!(builder.scalar(ExtendedScalars.GraphQLLong);));
+    }
+    if field_types.iter().any(|t| uses_scalar(t, "BigInteger")) {
+        wirings.push(quote!(builder.scalar(ExtendedScalars.GraphQLBigInteger);));
+    }
+    if !wirings.is_empty() {
+        imports.push("graphql.scalars.ExtendedScalars");
+    }
+    for s in ir.structs() {
+        let type_name = s.self_type().type_name();
+        let fetchers = s
+            .fields()
+            .iter()
+            .filter_map(|f| {
+                let property = ctx.field_name(f.name());
+                let json_name = f.json_name();
+                (property != *json_name).then(|| {
+                    quote!(.dataFetcher($[str]($[const](json_name)), PropertyDataFetcher.fetching($[str]($[const](property)))))
+                })
+            })
+            .collect::<Vec<java::Tokens>>();
+        if !fetchers.is_empty() {
+            imports.push("graphql.schema.PropertyDataFetcher");
+            wirings.push(quote!(
+                builder.type(TypeRuntimeWiring.newTypeWiring($[str]($[const](type_name)))
+                    $(for f in fetchers => $f));
+            ));
+        }
+    }
+    for p in ir.polymorphics() {
+        let type_name = p.self_type().type_name();
+        let resolvers = p.variants().iter().map(|v| {
+            let variant_class = ctx.type_name(v.variant_type());
+            let variant_type = v.variant_type().type_name();
+            quote!(
+                if (value instanceof $variant_class) {
+                    return env.getSchema().getObjectType($[str]($[const](variant_type)));
+                }
+            )
+        });
+        wirings.push(quote!(
+            builder.type(TypeRuntimeWiring.newTypeWiring($[str]($[const](type_name))).typeResolver(env -> {
+                Object value = env.getObject();
+                $(for r in resolvers => $r)
+                return null;
+            }));
+        ));
+    }
+    imports.sort_unstable();
+    imports.dedup();
+    let tokens: java::Tokens = quote!(
+        $(for i in imports => import $i;)
+
+        public final class $class_name {
+            public static RuntimeWiring.Builder configure(RuntimeWiring.Builder builder) {
+                $(for w in wirings => $w)
+                return builder;
+            }
+
+            private $class_name() {}
+        }
+    );
+    JavaClass::from_tokens(class_name.to_string(), tokens)
+}
+
+/// Returns true if the type uses the `Long` or `BigInteger` GraphQL scalar, directly or through a list.
+fn uses_scalar(t: &Type, scalar: &str) -> bool {
+    match t.kind() {
+        TypeKind::List(inner) | TypeKind::Option(inner) => uses_scalar(inner, scalar),
+        TypeKind::Map(_, _) => false,
+        TypeKind::UnsignedLong => scalar == "BigInteger",
+        TypeKind::Named => scalar == "Long" && t.type_name() == "Long",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::ir::{IntermediateRepresentation, TypeTarget};
+    use crate::targets::java::{generate_graphql_wiring, JavaConfig};
+    use crate::{rdc_polymorphic, RDC};
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct User {
+        user_name: String,
+        id: i64,
+        class: String,
+        avatar: Box<dyn Avatar>,
+    }
+
+    trait Avatar {}
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Image {
+        url: String,
+    }
+    impl Avatar for Image {}
+
+    rdc_polymorphic!(dyn Avatar => Image);
+
+    #[test]
+    fn generate_wiring() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<User>();
+        let config = JavaConfig::new().with_class_suffix("Dto");
+        let class = generate_graphql_wiring(&ir, &config, "GraphQLWiring").unwrap();
+        let code = class.code();
+        println!("{code}");
+        assert!(code.contains("builder.scalar(ExtendedScalars.GraphQLLong);"));
+        assert!(!code.contains("GraphQLBigInteger"));
+        assert!(code.contains("TypeRuntimeWiring.newTypeWiring(\"User\")"));
+        assert!(code
+            .contains(".dataFetcher(\"user_name\", PropertyDataFetcher.fetching(\"userName\"))"));
+        assert!(code.contains(".dataFetcher(\"class\", PropertyDataFetcher.fetching(\"class_\"))"));
+        assert!(!code.contains("newTypeWiring(\"Image\")"));
+        assert!(code.contains("if (value instanceof ImageDto)"));
+        assert!(code.contains("return env.getSchema().getObjectType(\"Image\");"));
+    }
+}
@@ -0,0 +1,347 @@
+use crate::errors::Error;
+use crate::ir::{DataEnum, DataEnumVariant, Name, Struct, Type, TypeKind};
+use crate::targets::java::cg_data_enum::{common_fields, has_unknown_variant};
+use crate::targets::java::config::DataEnumEncoding;
+use crate::targets::java::context::{ClassKind, JavaContext};
+use crate::targets::java::JavaClass;
+use genco::prelude::*;
+use genco::quote;
+
+/// Returns the name of the read-only interface of a data class, e.g. `InvoiceView` for `Invoice`.
+pub fn view_name(dc: &Struct, ctx: &JavaContext) -> String {
+    format!("{}View", ctx.class_name(dc.self_type()))
+}
+
+/// This function generates a read-only interface with the getters of a data class, which the class implements.
+/// Getters of nested data classes return their interfaces, so that the whole object graph is exposed read-only.
+pub fn generate_view_interface(dc: &Struct, ctx: &JavaContext) -> Result<JavaClass, Error> {
+    let interface_name = view_name(dc, ctx);
+    let getters = dc
+        .fields()
+        .iter()
+        .map(|f| {
+            let getter = ctx.getter_name(f.name(), f.field_type());
+            let type_name = view_type(f.field_type(), ctx);
+            quote!($type_name $getter();)
+        })
+        .collect::<Vec<java::Tokens>>();
+    let tokens: java::Tokens = quote!(
+        public interface $(&interface_name) {
+            $(for g in getters join ($['\r']) => $g)
+        }
+    );
+    JavaClass::from_tokens(interface_name, tokens)
+}
+
+/// Returns the name of the read-only interface of a data enum, e.g. `ShapeView` for `Shape`.
+pub fn data_enum_view_name(de: &DataEnum, ctx: &JavaContext) -> String {
+    format!("{}View", ctx.class_name(de.self_type()))
+}
+
+/// Returns whether data enums get read-only interfaces, which only the class encoding has,
+/// because sealed records are immutable already.
+pub fn has_data_enum_views(ctx: &JavaContext) -> bool {
+    ctx.config().view_interfaces() && ctx.config().data_enum_encoding() == DataEnumEncoding::Class
+}
+
+/// This function generates a read-only interface of a data enum class, with the variant checks and getters.
+/// Struct variants are returned as their nested classes, which have no setters.
+pub fn generate_data_enum_view_interface(
+    de: &DataEnum,
+    ctx: &JavaContext,
+) -> Result<JavaClass, Error> {
+    let interface_name = data_enum_view_name(de, ctx);
+    let class_name = &ctx.class_name(de.self_type());
+    let mut methods: Vec<java::Tokens> = vec![quote!($class_name.Variant variant();)];
+    for f in common_fields(de, ctx) {
+        let getter = ctx.getter_name(f.name(), f.field_type());
+        let type_name = view_type(f.field_type(), ctx);
+        methods.push(quote!($type_name $getter();));
+    }
+    for v in de.variants() {
+        let name = v.name();
+        match v {
+            DataEnumVariant::Unit { .. } => {}
+            DataEnumVariant::Object { .. } => {
+                let getter = ctx.variant_getter_name(name, None);
+                let sub_class_name = name.as_pascal_case();
+                methods.push(quote!($class_name.$sub_class_name $getter();));
+            }
+            DataEnumVariant::Tuple { fields, .. } => {
+                let numbering = fields.len() != 1;
+                for (i, t) in fields.iter().enumerate() {
+                    let getter = ctx.variant_getter_name(name, numbering.then_some(i));
+                    let type_name = view_type(t, ctx);
+                    methods.push(quote!($type_name $getter();));
+                }
+            }
+        }
+        let is_method_name = format!("is{}", name.as_pascal_case());
+        methods.push(quote!(boolean $is_method_name();));
+    }
+    if has_unknown_variant(de, ctx) {
+        let getter = ctx.variant_getter_name(&Name::from_snake_case("unknown"), None);
+        methods.push(quote!(com.fasterxml.jackson.databind.JsonNode $getter();));
+        methods.push(quote!(boolean isUnknown();));
+    }
+    let tokens: java::Tokens = quote!(
+        public interface $(&interface_name) {
+            $(for m in methods join ($['\r']) => $m)
+        }
+    );
+    JavaClass::from_tokens(interface_name, tokens)
+}
+
+/// This function generates a shared interface with getters of fields that multiple data classes have,
+/// e.g. `Auditable` with `getId()` and `getCreatedAt()`. The classes having the fields implement it.
+/// It fails if no struct has all the fields of the interface.
+pub fn generate_shared_interface(name: &str, ctx: &JavaContext) -> Result<JavaClass, Error> {
+    let fields = ctx.shared_interface_fields(name).ok_or_else(|| {
+        Error::new(&format!(
+            "Shared interface `{name}` is not generated, because no struct has all of its fields"
+        ))
+    })?;
+    let getters = fields
+        .iter()
+        .map(|f| {
+            let getter = ctx.getter_name(f.name(), f.field_type());
+            let type_name = ctx.type_name(f.field_type());
+            quote!($type_name $getter();)
+        })
+        .collect::<Vec<java::Tokens>>();
+    let tokens: java::Tokens = quote!(
+        public interface $name {
+            $(for g in getters join ($['\r']) => $g)
+        }
+    );
+    JavaClass::from_tokens(name.to_string(), tokens)
+}
+
+/// Returns the Java type of a getter in a read-only interface.
+/// Data classes and data enums are replaced with their interfaces, and collections of them with wildcard types,
+/// which the concrete collection types of the class getters are compatible with.
+fn view_type(t: &Type, ctx: &JavaContext) -> String {
+    if !references_struct(t, ctx) {
+        return ctx.type_name(t);
+    }
+    match t.kind() {
+        TypeKind::Option(inner) => view_type(inner, ctx),
+        TypeKind::List(element) => format!("java.util.List<? extends {}>", view_type(element, ctx)),
+        TypeKind::Map(key, value) => format!(
+            "java.util.Map<{}, ? extends {}>",
+            ctx.type_name(key),
+            view_type(value, ctx)
+        ),
+        _ => format!("{}View", ctx.type_name(t)),
+    }
+}
+
+fn references_struct(t: &Type, ctx: &JavaContext) -> bool {
+    match t.kind() {
+        TypeKind::Option(inner) | TypeKind::List(inner) => references_struct(inner, ctx),
+        TypeKind::Map(_, value) => references_struct(value, ctx),
+        TypeKind::Named => match ctx.class_kind(t) {
+            Some(ClassKind::Struct) => true,
+            Some(ClassKind::DataEnum) => has_data_enum_views(ctx),
+            _ => false,
+        },
+        TypeKind::UnsignedLong => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::targets::java::{DataEnumEncoding, JavaConfig};
+    use crate::{rdc_java, RDC};
+    use std::collections::BTreeMap;
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Invoice {
+        number: String,
+        customer: Customer,
+        lines: Vec<Line>,
+        lines_by_sku: BTreeMap<String, Line>,
+        notes: Option<Vec<String>>,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Customer {
+        name: String,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Line {
+        amount: f64,
+    }
+
+    #[test]
+    fn view_interfaces() {
+        let config = JavaConfig::new()
+            .with_view_interfaces(true)
+            .with_concrete_maps(true);
+        let classes = rdc_java!(config = config; Invoice).unwrap();
+        let class = |name: &str| classes.iter().find(|c| c.name() == name).unwrap().code();
+        let view = class("InvoiceView");
+        println!("{view}");
+        assert!(view.contains("public interface InvoiceView { String getNumber();\nCustomerView getCustomer();\nList<? extends LineView> getLines();\nMap<String, ? extends LineView> getLinesBySku();\nList<String> getNotes(); }"));
+        assert!(class("Invoice").contains("public class Invoice implements InvoiceView {"));
+        assert!(class("Invoice").contains("public TreeMap<String, Line> getLinesBySku() {"));
+        assert!(class("LineView").contains("public interface LineView { Double getAmount(); }"));
+        let plain = rdc_java!(Invoice).unwrap();
+        assert!(plain.iter().all(|c| !c.name().ends_with("View")));
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Drawing {
+        shapes: Vec<Shape>,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Shape {
+        Circle(Customer),
+        Rectangle { width: f64, height: f64 },
+        Empty,
+    }
+
+    #[test]
+    fn data_enum_view_interfaces() {
+        let config = JavaConfig::new()
+            .with_view_interfaces(true)
+            .with_unknown_variants(true);
+        let classes = rdc_java!(config = config; Drawing).unwrap();
+        let class = |name: &str| classes.iter().find(|c| c.name() == name).unwrap().code();
+        let view = class("ShapeView");
+        println!("{view}");
+        assert!(view.contains("public interface ShapeView { Shape.Variant variant();\nCustomerView getCircle();\nboolean isCircle();\nShape.Rectangle getRectangle();\nboolean isRectangle();\nboolean isEmpty();\nJsonNode getUnknown();\nboolean isUnknown(); }"));
+        assert!(class("Shape").contains("public class Shape implements ShapeView {"));
+        assert!(class("DrawingView").contains("List<? extends ShapeView> getShapes();"));
+
+        let config = JavaConfig::new()
+            .with_view_interfaces(true)
+            .with_data_enum_encoding(DataEnumEncoding::SealedRecords);
+        let classes = rdc_java!(config = config; Drawing).unwrap();
+        assert!(classes.iter().all(|c| c.name() != "ShapeView"));
+        let view = classes.iter().find(|c| c.name() == "DrawingView").unwrap();
+        assert!(view.code().contains("List<Shape> getShapes();"));
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Order {
+        id: String,
+        created_at: i64,
+        total: f64,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Product {
+        id: String,
+        created_at: i64,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct LegacyProduct {
+        id: i64,
+        created_at: i64,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Catalog {
+        orders: Vec<Order>,
+        products: Vec<Product>,
+        legacy: Vec<LegacyProduct>,
+    }
+
+    #[test]
+    fn shared_interfaces() {
+        let config = JavaConfig::new().with_shared_interface("Auditable", ["id", "created_at"]);
+        let classes = rdc_java!(config = config; Catalog).unwrap();
+        let class = |name: &str| classes.iter().find(|c| c.name() == name).unwrap();
+        assert!(class("Auditable")
+            .code()
+            .contains("public interface Auditable { String getId();\nLong getCreatedAt(); }"));
+        assert!(class("Order")
+            .code()
+            .contains("public class Order implements Auditable {"));
+        assert!(class("Product")
+            .code()
+            .contains("public class Product implements Auditable {"));
+        let legacy = class("LegacyProduct");
+        assert!(legacy.code().contains("public class LegacyProduct {"));
+        assert_eq!(
+            legacy.warnings(),
+            ["`LegacyProduct` does not implement `Auditable`, because the type of `id` differs"]
+        );
+        assert!(class("Catalog").code().contains("public class Catalog {"));
+
+        let config = JavaConfig::new().with_shared_interface("Named", ["name"]);
+        let error = rdc_java!(config = config; Catalog).err().unwrap();
+        assert_eq!(
+            error.message(),
+            "Shared interface `Named` is not generated, because no struct has all of its fields"
+        );
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    #[rdc(implements_shared = "Tracked")]
+    struct Parcel {
+        tracking_code: String,
+        weight: f64,
+        carrier: String,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    #[rdc(implements_shared = "Tracked")]
+    struct Letter {
+        carrier: String,
+        tracking_code: String,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    #[rdc(implements_shared = "Auditable")]
+    struct Draft {
+        id: String,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Mailbag {
+        parcels: Vec<Parcel>,
+        letters: Vec<Letter>,
+        drafts: Vec<Draft>,
+    }
+
+    #[test]
+    fn declared_shared_interfaces() {
+        let classes = rdc_java!(Mailbag).unwrap();
+        let class = |name: &str| classes.iter().find(|c| c.name() == name).unwrap();
+        assert!(class("Tracked").code().contains(
+            "public interface Tracked { String getTrackingCode();\nString getCarrier(); }"
+        ));
+        assert!(class("Parcel")
+            .code()
+            .contains("public class Parcel implements Tracked {"));
+        assert!(class("Letter")
+            .code()
+            .contains("public class Letter implements Tracked {"));
+
+        let config = JavaConfig::new().with_shared_interface("Auditable", ["id", "created_at"]);
+        let error = rdc_java!(config = config; Mailbag, Catalog).err().unwrap();
+        assert_eq!(
+            error.message(),
+            "`Draft` is marked with `#[rdc(implements_shared = \"Auditable\")]`, but does not have the fields of `Auditable` with the same types"
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use crate::ir::CustomType;
+use crate::targets::java::context::JavaContext;
+use genco::prelude::*;
+use genco::quote;
+
+/// Returns a `JSON_SCHEMA` constant holding the JSON Schema of the type in a text block, when it is enabled.
+/// Interfaces get the constant without modifiers, as their fields are implicitly public, static and final.
+pub fn generate_constant(
+    custom_type: &CustomType,
+    ctx: &JavaContext,
+    interface: bool,
+) -> Option<java::Tokens> {
+    if !ctx.config().json_schema_constants() {
+        return None;
+    }
+    let type_name = custom_type.type_name();
+    let Some(schema) = ctx.json_schema(type_name) else {
+        ctx.warn(format!(
+            "JSON Schema of `{type_name}` can not be generated, so it has no `JSON_SCHEMA` constant"
+        ));
+        return None;
+    };
+    // Backslashes are escapes in text blocks too, so the escapes of the JSON have to be escaped again.
+    let lines = schema
+        .replace('\\', "\\\\")
+        .lines()
+        .map(|l| l.to_string())
+        .collect::<Vec<String>>();
+    let modifiers = (!interface).then(|| quote!(public static final));
+    let delimiter = "\"\"\"";
+    Some(quote! {
+        $modifiers String JSON_SCHEMA = $delimiter$['\r']
+        $(for l in lines => $l$['\r'])
+        $delimiter;
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::targets::java::JavaConfig;
+    use crate::{rdc_java, rdc_polymorphic, RDC};
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Invoice {
+        #[serde(rename = "say \"hi\"")]
+        number: String,
+        status: Status,
+        shape: Box<dyn Shape>,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Status {
+        Draft,
+        Paid,
+    }
+
+    trait Shape {}
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Circle {
+        radius: f64,
+    }
+    impl Shape for Circle {}
+
+    rdc_polymorphic!(dyn Shape => Circle);
+
+    #[test]
+    fn json_schema_constants() {
+        let config = JavaConfig::new().with_json_schema_constants(true);
+        let classes = rdc_java!(config = config; Invoice).unwrap();
+        let class = |name: &str| classes.iter().find(|c| c.name() == name).unwrap().code();
+        let invoice = class("Invoice");
+        println!("{invoice}");
+        assert!(invoice.contains("public static final String JSON_SCHEMA = \"\"\"\n"));
+        assert!(invoice.contains("\"$ref\": \"#/$defs/Invoice\""));
+        assert!(invoice.contains("\"Status\": {"));
+        assert!(invoice.contains("\"say \\\\\"hi\\\\\"\": {"));
+        assert!(invoice.contains("\n \"\"\";"));
+        assert!(class("Status").contains("PAID; public static final String JSON_SCHEMA = \"\"\""));
+        assert!(class("Shape").contains("public interface Shape { String JSON_SCHEMA = \"\"\""));
+        assert!(!class("Circle").contains("\"Invoice\""));
+        let classes = rdc_java!(Invoice).unwrap();
+        assert!(classes.iter().all(|c| !c.code().contains("JSON_SCHEMA")));
+    }
+}
@@ -0,0 +1,217 @@
+use crate::errors::Error;
+use crate::ir::{IntermediateRepresentation, Struct, Type, TypeKind};
+use crate::targets::java::context::JavaContext;
+use crate::targets::kotlin::{escape_kotlin_keyword, KotlinFile};
+use std::collections::BTreeSet;
+
+/// This function generates a Kotlin file with extension functions over the Java classes generated for structs,
+/// so that Kotlin code using them reads like code using data classes.
+///
+/// Every class gets `componentN` operators for destructuring and `OrNull` properties of its optional fields,
+/// which Kotlin otherwise sees with platform types. Classes whose fields can all be set get a `copy` function
+/// with the current values as defaults, while classes with read-only fields or extra properties do not.
+/// Classes from other packages are imported, so the file belongs in the package the other classes are written to.
+pub fn generate_extensions_file(
+    ir: &IntermediateRepresentation,
+    ctx: &JavaContext,
+    file_name: &str,
+) -> Result<KotlinFile, Error> {
+    let mut imports = BTreeSet::new();
+    let mut blocks = Vec::new();
+    for s in ir.structs() {
+        if s.is_transparent() || s.fields().is_empty() {
+            continue;
+        }
+        imports.insert(s.self_type().type_name().as_str());
+        for f in s.fields() {
+            collect_named_types(f.field_type(), &mut imports);
+        }
+        blocks.extend(generate_extensions(s, ctx));
+    }
+    if blocks.is_empty() {
+        return Err(Error::new(
+            "No struct has fields, so there are no Kotlin extensions to generate",
+        ));
+    }
+    let imports = ir
+        .structs()
+        .iter()
+        .map(|s| s.self_type())
+        .chain(ir.enums().iter().map(|e| e.self_type()))
+        .chain(ir.data_enums().iter().map(|de| de.self_type()))
+        .chain(ir.polymorphics().iter().map(|p| p.self_type()))
+        .filter(|t| imports.contains(t.type_name().as_str()))
+        .filter_map(|t| {
+            let package = ctx.package(t)?;
+            let package = match ctx.config().version() {
+                Some(version) => format!("{package}.{version}"),
+                None => package,
+            };
+            Some(format!("import {package}.{}", ctx.class_name(t)))
+        })
+        .collect::<BTreeSet<String>>();
+    let mut code = String::new();
+    for import in &imports {
+        code.push_str(import);
+        code.push('\n');
+    }
+    for block in blocks {
+        if !code.is_empty() {
+            code.push('\n');
+        }
+        for line in block {
+            code.push_str(&line);
+            code.push('\n');
+        }
+    }
+    Ok(KotlinFile::new(file_name.to_string(), code))
+}
+
+fn generate_extensions(s: &Struct, ctx: &JavaContext) -> Vec<Vec<String>> {
+    let class_name = ctx.class_name(s.self_type());
+    let mut blocks = Vec::new();
+    let settable = s
+        .fields()
+        .iter()
+        .all(|f| !f.is_readonly() && !ctx.collects_extra_properties(s, f));
+    if settable {
+        let mut copy = vec![format!("fun {class_name}.copy(")];
+        let mut assignments = Vec::new();
+        for f in s.fields() {
+            let name = escape_kotlin_keyword(&ctx.field_name(f.name()));
+            let getter = ctx.getter_name(f.name(), f.field_type());
+            let setter = ctx.setter_name(f.name(), f.field_type());
+            copy.push(format!(
+                "    {name}: {} = this.{getter}(),",
+                kotlin_type(f.field_type(), ctx)
+            ));
+            assignments.push(format!("    it.{setter}({name})"));
+        }
+        copy.push(format!("): {class_name} = {class_name}().also {{"));
+        copy.extend(assignments);
+        copy.push("}".to_string());
+        blocks.push(copy);
+    }
+    let components = s
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(i, f)| {
+            format!(
+                "operator fun {class_name}.component{}(): {} = {}()",
+                i + 1,
+                kotlin_type(f.field_type(), ctx),
+                ctx.getter_name(f.name(), f.field_type())
+            )
+        })
+        .collect();
+    blocks.push(components);
+    for f in s.fields() {
+        if f.is_required() {
+            continue;
+        }
+        blocks.push(vec![
+            format!(
+                "val {class_name}.{}OrNull: {}",
+                ctx.field_name(f.name()),
+                kotlin_type(f.field_type(), ctx)
+            ),
+            format!(
+                "    get() = {}()",
+                ctx.getter_name(f.name(), f.field_type())
+            ),
+        ]);
+    }
+    blocks
+}
+
+/// Returns the Kotlin type of a Java property. Optional types are nullable, boxed types are Kotlin types
+/// and Java collections are Kotlin collections, except for concrete maps, e.g. `java.util.TreeMap`, which the setters require.
+fn kotlin_type(t: &Type, ctx: &JavaContext) -> String {
+    match t.kind() {
+        TypeKind::Option(inner) => format!("{}?", kotlin_type(inner, ctx)),
+        TypeKind::List(element) => format!("List<{}>", kotlin_type(element, ctx)),
+        TypeKind::Map(key, value) => {
+            let java_type = ctx.type_name(t);
+            let map = match java_type.split('<').next() {
+                Some("java.util.Map") | None => "Map",
+                Some(concrete) => concrete,
+            };
+            format!(
+                "{map}<{}, {}>",
+                kotlin_type(key, ctx),
+                kotlin_type(value, ctx)
+            )
+        }
+        TypeKind::Named | TypeKind::UnsignedLong => match ctx.type_name(t).as_str() {
+            "Integer" => "Int".to_string(),
+            "Character" => "Char".to_string(),
+            "Object" => "Any".to_string(),
+            name => name.to_string(),
+        },
+    }
+}
+
+fn collect_named_types<'t>(t: &'t Type, names: &mut BTreeSet<&'t str>) {
+    match t.kind() {
+        TypeKind::Option(inner) | TypeKind::List(inner) => collect_named_types(inner, names),
+        TypeKind::Map(key, value) => {
+            collect_named_types(key, names);
+            collect_named_types(value, names);
+        }
+        TypeKind::Named => {
+            names.insert(t.type_name());
+        }
+        TypeKind::UnsignedLong => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::ir::{IntermediateRepresentation, TypeTarget};
+    use crate::targets::java::{generate_kotlin_extensions, AccessorStyle, JavaConfig};
+    use crate::RDC;
+    use std::collections::BTreeMap;
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Invoice {
+        number: String,
+        note: Option<String>,
+        lines: Vec<Line>,
+        totals: BTreeMap<String, i32>,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Line {
+        sku: String,
+        quantity: u32,
+    }
+
+    #[test]
+    fn kotlin_extensions() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Invoice>();
+        let config = JavaConfig::new().with_concrete_maps(true);
+        let file = generate_kotlin_extensions(&ir, &config, "DtoExtensions").unwrap();
+        let code = file.code();
+        println!("{code}");
+        assert_eq!(file.name(), "DtoExtensions");
+        assert!(code.starts_with("fun Line.copy(\n"));
+        assert!(code.contains(
+            "fun Invoice.copy(\n    number: String = this.getNumber(),\n    note: String? = this.getNote(),\n    lines: List<Line> = this.getLines(),\n    totals: java.util.TreeMap<String, Int> = this.getTotals(),\n): Invoice = Invoice().also {\n    it.setNumber(number)\n"
+        ));
+        assert!(code.contains("operator fun Invoice.component2(): String? = getNote()\n"));
+        assert!(code.contains("val Invoice.noteOrNull: String?\n    get() = getNote()\n"));
+        assert!(!code.contains("numberOrNull"));
+
+        let config = JavaConfig::new().with_accessor_style(AccessorStyle::Record);
+        let file = generate_kotlin_extensions(&ir, &config, "DtoExtensions").unwrap();
+        assert!(file.code().contains("    it.sku(sku)\n"));
+        assert!(file
+            .code()
+            .contains("operator fun Line.component1(): String = sku()\n"));
+    }
+}
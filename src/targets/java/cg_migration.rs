@@ -0,0 +1,301 @@
+use crate::errors::Error;
+use crate::ir::{diff, Change, IntermediateRepresentation, Struct, Type, TypeKind};
+use crate::targets::java::context::{ClassKind, JavaContext};
+use crate::targets::java::JavaClass;
+use genco::prelude::*;
+use genco::quote;
+use std::collections::HashSet;
+
+/// This function generates a class with a `migrate<Class>(ObjectNode)` method for every struct
+/// whose JSON representation changed mechanically between the versions of the IR.
+///
+/// Renamed fields are moved to their new names, removed fields are dropped, and added optional fields are set to null.
+/// Nested structs are migrated recursively, also in lists and map values.
+/// Changes that can not be migrated mechanically, e.g. added required fields or changed field types,
+/// are reported as warnings of the class, so that they can be handled by hand.
+pub fn generate_migration_class(
+    old: &IntermediateRepresentation,
+    new: &IntermediateRepresentation,
+    ctx: &JavaContext,
+    class_name: &str,
+) -> Result<JavaClass, Error> {
+    let changes = diff(old, new);
+    for change in changes.iter().filter(|c| c.needs_manual_migration()) {
+        ctx.warn(format!(
+            "{}, so it needs a manual migration",
+            change.description()
+        ));
+    }
+    let migrated = migrated_structs(new, &changes);
+    let methods = new
+        .structs()
+        .iter()
+        .filter(|s| migrated.contains(s.self_type().type_name().as_str()))
+        .map(|s| migration_method(s, &changes, &migrated, ctx))
+        .collect::<Vec<java::Tokens>>();
+    let tokens: java::Tokens = quote!(
+        import com.fasterxml.jackson.databind.JsonNode;
+        import com.fasterxml.jackson.databind.node.ObjectNode;
+
+        public final class $class_name {
+            $(for m in methods join ($['\n']) => $m)
+
+            private $class_name() {}
+        }
+    );
+    JavaClass::from_tokens(class_name.to_string(), tokens)
+}
+
+fn migration_method(
+    dc: &Struct,
+    changes: &[Change],
+    migrated: &HashSet<&str>,
+    ctx: &JavaContext,
+) -> java::Tokens {
+    let type_name = dc.self_type().type_name();
+    let method = migration_method_name(dc.self_type().type_name(), ctx);
+    let mut statements: Vec<java::Tokens> = Vec::new();
+    for change in changes.iter().filter(|c| c.type_name() == type_name) {
+        match change {
+            Change::FieldRenamed {
+                old_json_name,
+                new_json_name,
+                ..
+            } => statements.push(quote!(
+                if (node.has($(quoted(old_json_name))) && !node.has($(quoted(new_json_name)))) {
+                    node.set($(quoted(new_json_name)), node.remove($(quoted(old_json_name))));
+                }
+            )),
+            Change::FieldRemoved { json_name, .. } => {
+                statements.push(quote!(node.remove($(quoted(json_name)));))
+            }
+            Change::FieldAdded {
+                json_name,
+                optional: true,
+                ..
+            } => statements.push(quote!(
+                if (!node.has($(quoted(json_name)))) {
+                    node.putNull($(quoted(json_name)));
+                }
+            )),
+            _ => {}
+        }
+    }
+    for field in dc.fields() {
+        if let Some(nested) = nested_struct(field.field_type(), migrated, ctx) {
+            let nested_method = migration_method_name(nested.type_name(), ctx);
+            let json_name = quoted(field.json_name());
+            let statement = match element_kind(field.field_type()) {
+                ElementKind::Single => quote!(
+                    if (node.get($(&json_name)) instanceof ObjectNode) {
+                        $(&nested_method)((ObjectNode) node.get($(&json_name)));
+                    }
+                ),
+                ElementKind::Values => quote!(
+                    if (node.get($(&json_name)) != null) {
+                        for (JsonNode element : node.get($(&json_name))) {
+                            if (element instanceof ObjectNode) {
+                                $(&nested_method)((ObjectNode) element);
+                            }
+                        }
+                    }
+                ),
+            };
+            statements.push(statement);
+        }
+    }
+    quote!(
+        public static ObjectNode $method(ObjectNode node) {
+            $(for s in statements => $s)
+            return node;
+        }
+    )
+}
+
+enum ElementKind {
+    Single,
+    Values,
+}
+
+/// Returns whether the struct is stored directly in the field, or as elements of a list or values of a map.
+fn element_kind(t: &Type) -> ElementKind {
+    match t.kind() {
+        TypeKind::Option(inner) => element_kind(inner),
+        TypeKind::List(_) | TypeKind::Map(_, _) => ElementKind::Values,
+        _ => ElementKind::Single,
+    }
+}
+
+/// Returns the migrated struct stored in the field, directly or as elements of a list or values of a map.
+fn nested_struct<'t>(t: &'t Type, migrated: &HashSet<&str>, ctx: &JavaContext) -> Option<&'t Type> {
+    let element = match t.kind() {
+        TypeKind::Option(inner) => match inner.kind() {
+            TypeKind::List(element) | TypeKind::Map(_, element) => element,
+            _ => inner,
+        },
+        TypeKind::List(element) | TypeKind::Map(_, element) => element,
+        _ => t,
+    };
+    let element = match element.kind() {
+        TypeKind::Option(inner) => inner,
+        _ => element,
+    };
+    let is_struct = matches!(ctx.class_kind(element), Some(ClassKind::Struct));
+    (is_struct && migrated.contains(element.type_name().as_str())).then_some(element)
+}
+
+/// Returns the structs that changed mechanically, or contain such structs.
+fn migrated_structs<'a>(
+    ir: &'a IntermediateRepresentation,
+    changes: &[Change],
+) -> HashSet<&'a str> {
+    let mut migrated = ir
+        .structs()
+        .iter()
+        .map(|s| s.self_type().type_name().as_str())
+        .filter(|name| {
+            changes.iter().any(|c| {
+                c.type_name() == *name
+                    && matches!(
+                        c,
+                        Change::FieldRenamed { .. }
+                            | Change::FieldRemoved { .. }
+                            | Change::FieldAdded { optional: true, .. }
+                    )
+            })
+        })
+        .collect::<HashSet<&str>>();
+    loop {
+        let containing = ir
+            .structs()
+            .iter()
+            .filter(|s| !migrated.contains(s.self_type().type_name().as_str()))
+            .filter(|s| {
+                s.fields()
+                    .iter()
+                    .any(|f| references(f.field_type(), &migrated))
+            })
+            .map(|s| s.self_type().type_name().as_str())
+            .collect::<Vec<&str>>();
+        if containing.is_empty() {
+            return migrated;
+        }
+        migrated.extend(containing);
+    }
+}
+
+fn references(t: &Type, types: &HashSet<&str>) -> bool {
+    match t.kind() {
+        TypeKind::Option(inner) | TypeKind::List(inner) | TypeKind::Map(_, inner) => {
+            references(inner, types)
+        }
+        TypeKind::Named => types.contains(t.type_name().as_str()),
+        TypeKind::UnsignedLong => false,
+    }
+}
+
+fn migration_method_name(type_name: &str, ctx: &JavaContext) -> String {
+    let class_name = ctx.type_name(&Type::new(type_name));
+    format!("migrate{class_name}")
+}
+
+fn quoted(value: &str) -> String {
+    format!("{value:?}")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ir::{IntermediateRepresentation, TypeTarget};
+    use crate::targets::java::{generate_java_migrations, JavaConfig};
+
+    mod v1 {
+        use crate as rdc;
+        use crate::RDC;
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub struct Order {
+            pub id: String,
+            pub lines: Vec<OrderLine>,
+            pub customer: Customer,
+        }
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub struct OrderLine {
+            pub sku: String,
+            pub qty: i32,
+            pub legacy_price: f64,
+        }
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub struct Customer {
+            pub name: String,
+            pub age: i32,
+        }
+    }
+
+    mod v2 {
+        use crate as rdc;
+        use crate::RDC;
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub struct Order {
+            pub id: String,
+            pub lines: Vec<OrderLine>,
+            pub customer: Customer,
+            pub currency: String,
+        }
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub struct OrderLine {
+            pub sku: String,
+            #[serde(rename = "quantity")]
+            pub qty: i32,
+            pub note: Option<String>,
+        }
+
+        #[derive(RDC)]
+        #[allow(unused)]
+        pub struct Customer {
+            pub name: String,
+            pub age: i64,
+        }
+    }
+
+    #[test]
+    fn migration_class() {
+        let mut old = IntermediateRepresentation::new(TypeTarget::Java);
+        old.add::<v1::Order>();
+        let mut new = IntermediateRepresentation::new(TypeTarget::Java);
+        new.add::<v2::Order>();
+        let config = JavaConfig::new().with_class_suffix("Dto");
+        let class = generate_java_migrations(&old, &new, &config, "OrderMigrations").unwrap();
+        let code = class.code();
+        println!("{code}");
+        assert!(code.contains("public final class OrderMigrations"));
+        assert!(code.contains("public static ObjectNode migrateOrderLineDto(ObjectNode node)"));
+        assert!(code.contains(
+            "if (node.has(\"qty\") && !node.has(\"quantity\")) { node.set(\"quantity\", node.remove(\"qty\")); }"
+        ));
+        assert!(code.contains("node.remove(\"legacy_price\");"));
+        assert!(code.contains("if (!node.has(\"note\")) { node.putNull(\"note\"); }"));
+        assert!(code.contains("public static ObjectNode migrateOrderDto(ObjectNode node)"));
+        assert!(code.contains("for (JsonNode element : node.get(\"lines\"))"));
+        assert!(code.contains("migrateOrderLineDto((ObjectNode) element);"));
+        assert!(!code.contains("migrateCustomerDto"));
+        let warnings = class.warnings();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings.contains(
+            &"Required field `Order.currency` was added, so it needs a manual migration"
+                .to_string()
+        ));
+        assert!(warnings.contains(
+            &"Field `Customer.age` changed its type from `Integer` to `Long`, so it needs a manual migration"
+                .to_string()
+        ));
+    }
+}
@@ -0,0 +1,193 @@
+use crate::errors::Error;
+use crate::ir::{Struct, TypeKind};
+use crate::targets::java::context::JavaContext;
+use crate::targets::java::JavaClass;
+use crate::targets::java::{cg_fingerprint, cg_json_schema};
+use genco::prelude::*;
+use genco::quote;
+
+/// This function generates a class for a transparent struct, e.g. `struct Tags(HashMap<String, String>)`,
+/// which holds the wrapped value and reads and writes it as its own JSON, like serde does.
+///
+/// Jackson creates the class from the wrapped value with a delegating `@JsonCreator`.
+/// Maps are written with `@JsonAnyGetter` and get delegating `get`, `containsKey`, `size` and `isEmpty` methods,
+/// lists get delegating `get`, `size` and `isEmpty` methods, and other values are written with `@JsonValue`.
+pub fn generate_newtype_class(dc: &Struct, ctx: &JavaContext) -> Result<JavaClass, Error> {
+    let class_name = ctx.class_name(dc.self_type());
+    let class_name_str = class_name.as_str();
+    let field = dc.fields().first().ok_or_else(|| {
+        Error::new(&format!(
+            "Transparent struct `{}` has no field",
+            dc.self_type().type_name()
+        ))
+    })?;
+    let type_string = &ctx.type_name(field.field_type());
+    let getter_name = &ctx.getter_name(field.name(), field.field_type());
+    let mut imports = vec!["com.fasterxml.jackson.annotation.JsonCreator"];
+    let (value_annotation, delegates) = match field.field_type().kind() {
+        TypeKind::Map(key, value) => {
+            imports.push("com.fasterxml.jackson.annotation.JsonAnyGetter");
+            imports.push("com.fasterxml.jackson.annotation.JsonIgnore");
+            let key = &ctx.type_name(key);
+            let value = &ctx.type_name(value);
+            let delegates = quote! {
+                public $value get($key key) {
+                    return value.get(key);
+                }
+
+                public boolean containsKey($key key) {
+                    return value.containsKey(key);
+                }
+
+                public int size() {
+                    return value.size();
+                }
+
+                @JsonIgnore
+                public boolean isEmpty() {
+                    return value.isEmpty();
+                }
+            };
+            (quote!(@JsonAnyGetter), Some(delegates))
+        }
+        TypeKind::List(element) => {
+            imports.push("com.fasterxml.jackson.annotation.JsonValue");
+            let element = &ctx.type_name(element);
+            let delegates = quote! {
+                public $element get(int index) {
+                    return value.get(index);
+                }
+
+                public int size() {
+                    return value.size();
+                }
+
+                public boolean isEmpty() {
+                    return value.isEmpty();
+                }
+            };
+            (quote!(@JsonValue), Some(delegates))
+        }
+        _ => {
+            imports.push("com.fasterxml.jackson.annotation.JsonValue");
+            (quote!(@JsonValue), None)
+        }
+    };
+    imports.sort_unstable();
+    let json_schema = cg_json_schema::generate_constant(dc.self_type(), ctx, false);
+    let fingerprint = cg_fingerprint::generate_constant(dc.self_type(), ctx, false);
+    let tokens: java::Tokens = quote!(
+        $(for i in imports => import $i;)
+
+        public class $class_name_str {
+            $json_schema
+
+            $fingerprint
+
+            private final $type_string value;
+
+            @JsonCreator(mode = JsonCreator.Mode.DELEGATING)
+            public $class_name_str($type_string value) {
+                this.value = value;
+            }
+
+            $value_annotation
+            public $type_string $getter_name() {
+                return value;
+            }
+
+            $delegates
+        }
+    );
+    JavaClass::from_tokens(class_name, tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::targets::java::JavaConfig;
+    use crate::{rdc_java, rdc_newtype, RDC};
+    use std::collections::HashMap;
+
+    #[allow(unused)]
+    struct Tags(HashMap<String, String>);
+
+    rdc_newtype!(Tags => HashMap<String, String>);
+
+    #[allow(unused)]
+    struct Lines(Vec<String>);
+
+    rdc_newtype!(Lines => Vec<String>);
+
+    #[allow(unused)]
+    struct InvoiceNumber(String);
+
+    rdc_newtype!(InvoiceNumber => String);
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Invoice {
+        number: InvoiceNumber,
+        tags: Tags,
+        lines: Option<Lines>,
+    }
+
+    #[test]
+    fn newtype_classes() {
+        let config = JavaConfig::new().with_fixtures(true);
+        let classes = rdc_java!(config = config; Invoice).unwrap();
+        let class = |name: &str| classes.iter().find(|c| c.name() == name).unwrap().code();
+        let tags = class("Tags");
+        println!("{tags}");
+        assert!(tags.contains("import com.fasterxml.jackson.annotation.JsonAnyGetter;"));
+        assert!(tags.contains("private final Map<String, String> value;"));
+        assert!(tags.contains(
+            "@JsonCreator(mode = JsonCreator.Mode.DELEGATING) public Tags(Map<String, String> value) {"
+        ));
+        assert!(tags.contains("@JsonAnyGetter public Map<String, String> getValue()"));
+        assert!(tags.contains("public String get(String key) {"));
+        assert!(tags.contains("@JsonIgnore public boolean isEmpty()"));
+        assert!(!tags.contains("setValue"));
+        let lines = class("Lines");
+        assert!(lines.contains("@JsonValue public List<String> getValue()"));
+        assert!(lines.contains("public String get(int index) {"));
+        let number = class("InvoiceNumber");
+        assert!(number.contains("@JsonValue public String getValue()"));
+        assert!(!number.contains(" get("));
+        assert!(class("Invoice").contains("private Tags tags;"));
+        let fixtures = class("TagsFixtures");
+        assert!(fixtures.contains("return new Tags(Map.of(\"key\", \"value\"));"));
+        let fixtures = class("InvoiceFixtures");
+        assert!(fixtures.contains("value.setTags(TagsFixtures.create());"));
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Sku(String);
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Labels<T>(Vec<T>);
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Product {
+        sku: Sku,
+        labels: Labels<Sku>,
+    }
+
+    #[test]
+    fn derived_newtypes() {
+        let classes = rdc_java!(Product).unwrap();
+        let class = |name: &str| classes.iter().find(|c| c.name() == name).unwrap().code();
+        let sku = class("Sku");
+        assert!(sku.contains(
+            "@JsonCreator(mode = JsonCreator.Mode.DELEGATING) public Sku(String value) {"
+        ));
+        assert!(sku.contains("@JsonValue public String getValue()"));
+        let labels = class("LabelsSku");
+        assert!(labels.contains("private final List<Sku> value;"));
+        assert!(labels.contains("public Sku get(int index) {"));
+        assert!(class("Product").contains("private LabelsSku labels;"));
+    }
+}
@@ -0,0 +1,90 @@
+use crate::ir::{Field, Struct};
+use crate::targets::java::cg_utils::Annotation;
+use crate::targets::java::context::JavaContext;
+use genco::prelude::*;
+use genco::quote;
+
+/// Returns the `@Nullable` annotation of an optional field, also put on its getter, when it is enabled.
+pub fn field_annotations(field: &Field, ctx: &JavaContext) -> Vec<Annotation> {
+    match ctx.config().nullable_annotation() {
+        Some(annotation) if !field.is_required() => {
+            vec![(annotation.qualified_name(), quote!(@Nullable))]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the Javadoc of a getter, when it is enabled. It starts with the field description and example, if any,
+/// and its `@return` tag tells whether the property is required, the same way schemas do.
+pub fn getter_javadoc(field: &Field, owner: &Struct, ctx: &JavaContext) -> Option<java::Tokens> {
+    let config = ctx.config();
+    if !config.getter_javadoc() {
+        return None;
+    }
+    let type_name = owner.self_type().type_name();
+    let description = config.field_description(type_name, &field.name().as_snake_case());
+    let json_name = field.json_name();
+    let returns = match field.is_required() {
+        true => format!("@return the value of the required {{@code {json_name}}} property"),
+        false => format!(
+            "@return the value of the optional {{@code {json_name}}} property, or {{@code null}} when it is missing"
+        ),
+    };
+    let mut lines = description
+        .map(|d| d.lines().map(|l| l.to_string()).collect::<Vec<String>>())
+        .unwrap_or_default();
+    if let Some(example) = field.example() {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.push(format!("Example: {{@code {example}}}"));
+    }
+    if !lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines.push(returns);
+    Some(quote!($(java::block_comment(lines))))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::ir::{IntermediateRepresentation, TypeTarget};
+    use crate::targets::java::{generate_java_code_with_config, JavaConfig, NullableAnnotation};
+    use crate::{rdc_java, RDC};
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Invoice {
+        number: String,
+        note: Option<String>,
+        lines: Option<Vec<String>>,
+    }
+
+    #[test]
+    fn nullability() {
+        let config = JavaConfig::new()
+            .with_nullable_annotation(NullableAnnotation::Jakarta)
+            .with_getter_javadoc(true)
+            .with_field_description("Invoice", "note", "Note printed on the invoice.");
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Invoice>();
+        ir.set_field_example("Invoice", "note", "Paid in advance")
+            .unwrap();
+        let classes = generate_java_code_with_config(&ir, &config).unwrap();
+        let code = classes[0].code();
+        assert!(code.contains("import jakarta.annotation.Nullable;"));
+        assert!(code.contains("@JsonProperty(\"number\") private String number;"));
+        assert!(code.contains("@JsonProperty(\"note\") @Nullable private String note;"));
+        assert!(code.contains(
+            "/**\n * @return the value of the required {@code number} property\n */ public String getNumber()"
+        ));
+        assert!(code.contains(
+            "/**\n * Note printed on the invoice.\n *\n * Example: {@code Paid in advance}\n *\n * @return the value of the optional {@code note} property, or {@code null} when it is missing\n */ @Nullable public String getNote()"
+        ));
+        assert!(code.contains("@Nullable public List<String> getLines()"));
+        let plain = rdc_java!(Invoice).unwrap();
+        assert!(!plain[0].code().contains("Nullable"));
+        assert!(!plain[0].code().contains("@return"));
+    }
+}
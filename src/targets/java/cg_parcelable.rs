@@ -0,0 +1,145 @@
+use crate::ir::{Field, Struct, Type, TypeKind};
+use crate::targets::java::context::{ClassKind, JavaContext};
+use genco::prelude::*;
+use genco::quote;
+
+/// Returns imports required by the `Parcelable` implementation.
+pub fn imports() -> [&'static str; 2] {
+    ["android.os.Parcel", "android.os.Parcelable"]
+}
+
+/// Generates the `Parcelable` implementation of a class: the constructor reading from a `Parcel`,
+/// the `CREATOR` field and `writeToParcel`.
+/// Fields are written with `Parcel.writeValue`, which supports boxed primitives, strings, lists, maps,
+/// enums and other generated `Parcelable` classes. Fields that can not be written are reported as warnings.
+pub fn generate_parcelable_methods(dc: &Struct, ctx: &JavaContext) -> java::Tokens {
+    let class_name = &ctx.class_name(dc.self_type());
+    for field in dc.fields() {
+        if let Some(type_name) = find_unsupported_type(field.field_type(), ctx) {
+            ctx.warn(format!(
+                "`{}` can not be written to a Parcel, because `{type_name}` is not Parcelable",
+                ctx.field_name(field.name())
+            ));
+        }
+    }
+    let reads = dc.fields().iter().map(|f: &Field| {
+        let name = ctx.field_name(f.name());
+        let type_name = ctx.type_name(f.field_type());
+        quote!(this.$(&name) = ($type_name) in.readValue($class_name.class.getClassLoader());)
+    });
+    let writes = dc.fields().iter().map(|f: &Field| {
+        let name = ctx.field_name(f.name());
+        quote!(dest.writeValue($name);)
+    });
+    quote!(
+        @SuppressWarnings("unchecked")
+        protected $class_name(Parcel in) {
+            $(for r in reads => $r)
+        }
+
+        public static final Parcelable.Creator<$class_name> CREATOR = new Parcelable.Creator<$class_name>() {
+            @Override
+            public $class_name createFromParcel(Parcel in) {
+                return new $class_name(in);
+            }
+
+            @Override
+            public $class_name[] newArray(int size) {
+                return new $class_name[size];
+            }
+        };
+
+        @Override
+        public int describeContents() {
+            return 0;
+        }
+
+        @Override
+        public void writeToParcel(Parcel dest, int flags) {
+            $(for w in writes => $w)
+        }
+    )
+}
+
+/// Returns the name of a generated class used by the type that does not implement `Parcelable`.
+/// Structs implement `Parcelable` and unit enums are `Serializable`, but data enums are neither.
+fn find_unsupported_type(t: &Type, ctx: &JavaContext) -> Option<String> {
+    match t.kind() {
+        TypeKind::Named => match ctx.class_kind(t) {
+            Some(ClassKind::DataEnum) => Some(ctx.type_name(t)),
+            _ => None,
+        },
+        TypeKind::List(inner) | TypeKind::Option(inner) => find_unsupported_type(inner, ctx),
+        TypeKind::Map(key, value) => {
+            find_unsupported_type(key, ctx).or_else(|| find_unsupported_type(value, ctx))
+        }
+        TypeKind::UnsignedLong => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::targets::java::JavaConfig;
+    use crate::{rdc_java, RDC};
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Order {
+        id: String,
+        items: Vec<Item>,
+        status: Status,
+        payment: Payment,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Item {
+        quantity: i32,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Status {
+        Open,
+        Closed,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Payment {
+        Card(String),
+        Cash,
+    }
+
+    #[test]
+    fn generate_parcelable() {
+        let config = JavaConfig::new().with_parcelable(true);
+        let classes = rdc_java!(config = config; Order).unwrap();
+        let order = classes.iter().find(|c| c.name() == "Order").unwrap();
+        let code = order.code();
+        assert!(code.contains("import android.os.Parcelable;"));
+        assert!(code.contains("public class Order implements Parcelable"));
+        assert!(code.contains("protected Order(Parcel in)"));
+        assert!(
+            code.contains("this.items = (List<Item>) in.readValue(Order.class.getClassLoader());")
+        );
+        assert!(code.contains("public static final Parcelable.Creator<Order> CREATOR"));
+        assert!(code.contains("dest.writeValue(status);"));
+        assert_eq!(
+            order.warnings(),
+            ["`payment` can not be written to a Parcel, because `Payment` is not Parcelable"]
+        );
+
+        let item = classes.iter().find(|c| c.name() == "Item").unwrap();
+        assert!(item
+            .code()
+            .contains("public class Item implements Parcelable"));
+    }
+
+    #[test]
+    fn parcelable_disabled_by_default() {
+        let classes = rdc_java!(Order).unwrap();
+        assert!(classes.iter().all(|c| !c.code().contains("Parcel")));
+    }
+}
@@ -0,0 +1,189 @@
+use crate::errors::Error;
+use crate::ir::{Polymorphic, PolymorphicVariant};
+use crate::targets::java::context::JavaContext;
+use crate::targets::java::JavaClass;
+use crate::targets::java::{cg_fingerprint, cg_json_schema};
+use genco::prelude::*;
+use genco::quote;
+
+pub fn generate_polymorphic_interface(
+    p: &Polymorphic,
+    ctx: &JavaContext,
+) -> Result<JavaClass, Error> {
+    let class_name = ctx.class_name(p.self_type());
+    let class_name_str = class_name.as_str();
+    let type_info: java::Tokens = match p.tag() {
+        Some(tag) => quote!(
+            @JsonTypeInfo(use = JsonTypeInfo.Id.NAME, include = JsonTypeInfo.As.PROPERTY, property = $[str]($[const](tag)))
+        ),
+        None => quote!(
+            @JsonTypeInfo(use = JsonTypeInfo.Id.NAME, include = JsonTypeInfo.As.WRAPPER_OBJECT)
+        ),
+    };
+    let sub_types = p.variants().iter().map(|v: &PolymorphicVariant| {
+        let variant_class = ctx.type_name(v.variant_type());
+        let json_name = v.json_name();
+        quote!(@JsonSubTypes.Type(value = $variant_class.class, name = $[str]($[const](json_name))))
+    });
+    let json_schema = cg_json_schema::generate_constant(p.self_type(), ctx, true);
+    let fingerprint = cg_fingerprint::generate_constant(p.self_type(), ctx, true);
+    let tokens: java::Tokens = quote!(
+        import com.fasterxml.jackson.annotation.JsonSubTypes;
+        import com.fasterxml.jackson.annotation.JsonTypeInfo;
+
+        $type_info
+        @JsonSubTypes({
+            $(for st in sub_types join (,) => $st)
+        })
+        public interface $class_name_str {$json_schema$fingerprint}
+    );
+
+    JavaClass::from_tokens(class_name, tokens)
+}
+
+/// This function generates a handler for a message envelope, e.g. `ChatMessageHandler` for `ChatMessage`.
+/// It has an `on<Message>` method for every message type and a default `handle` method dispatching to them.
+pub fn generate_message_handler(p: &Polymorphic, ctx: &JavaContext) -> Result<JavaClass, Error> {
+    let envelope_name = ctx.class_name(p.self_type());
+    let class_name = format!("{envelope_name}Handler");
+    let class_name_str = class_name.as_str();
+    let handlers = p
+        .variants()
+        .iter()
+        .map(|v| {
+            let variant_class = ctx.type_name(v.variant_type());
+            (format!("on{}", v.json_name()), variant_class)
+        })
+        .collect::<Vec<(String, String)>>();
+    let tokens: java::Tokens = quote!(
+        public interface $class_name_str {
+            $(for (method, variant_class) in &handlers join ($['\n']) => void $method($variant_class message);)
+
+            default void handle($envelope_name message) {
+                $(for (method, variant_class) in &handlers join ( else ) => if (message instanceof $variant_class) {
+                    $method(($variant_class) message);
+                }) else {
+                    throw new IllegalArgumentException("Unknown message: " + message);
+                }
+            }
+        }
+    );
+
+    JavaClass::from_tokens(class_name, tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::{rdc_envelope, rdc_java, rdc_polymorphic, RDC};
+    use serde::{Deserialize, Serialize};
+
+    trait Shape {}
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Circle {
+        radius: f64,
+    }
+    impl Shape for Circle {}
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Square {
+        side: f64,
+    }
+    impl Shape for Square {}
+
+    rdc_polymorphic!(dyn Shape => Circle, Square);
+
+    trait Event {}
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Created {
+        id: String,
+    }
+    impl Event for Created {}
+
+    rdc_polymorphic!(dyn Event, tag = "kind" => Created);
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Drawing {
+        shapes: Vec<Box<dyn Shape>>,
+        last_event: Option<Box<dyn Event>>,
+    }
+
+    #[test]
+    fn generate_polymorphic_interfaces() {
+        let classes = rdc_java!(Drawing).unwrap();
+        let class = |name: &str| classes.iter().find(|c| c.name() == name).unwrap().code();
+        for class in &classes {
+            println!("{}", class.code());
+        }
+        assert_eq!(classes.len(), 6);
+
+        let shape = class("Shape");
+        assert!(shape.contains("include = JsonTypeInfo.As.WRAPPER_OBJECT"));
+        assert!(shape.contains("@JsonSubTypes.Type(value = Circle.class, name = \"Circle\")"));
+        assert!(shape.contains("@JsonSubTypes.Type(value = Square.class, name = \"Square\")"));
+        assert!(shape.contains("public interface Shape {}"));
+        assert!(class("Circle").contains("public class Circle implements Shape"));
+
+        let event = class("Event");
+        assert!(event.contains("include = JsonTypeInfo.As.PROPERTY, property = \"kind\""));
+        assert!(class("Created").contains("public class Created implements Event"));
+
+        let drawing = class("Drawing");
+        assert!(drawing.contains("private List<Shape> shapes;"));
+        assert!(drawing.contains("private Event lastEvent;"));
+    }
+
+    #[derive(RDC, Serialize, Deserialize)]
+    struct Join {
+        user: String,
+    }
+
+    #[derive(RDC, Serialize, Deserialize)]
+    struct Leave {
+        user: String,
+        reason: Option<String>,
+    }
+
+    rdc_envelope! {
+        #[serde(tag = "type")]
+        #[derive(Serialize, Deserialize)]
+        enum ChatMessage { Join, Leave }
+    }
+
+    #[test]
+    fn generate_envelope() {
+        let message: ChatMessage = Join {
+            user: "alice".to_string(),
+        }
+        .into();
+        assert_eq!(
+            serde_json::to_string(&message).unwrap(),
+            r#"{"type":"Join","user":"alice"}"#
+        );
+        let classes = rdc_java!(ChatMessage).unwrap();
+        let class = |name: &str| classes.iter().find(|c| c.name() == name).unwrap().code();
+        assert_eq!(classes.len(), 4);
+        let envelope = class("ChatMessage");
+        assert!(envelope.contains("include = JsonTypeInfo.As.PROPERTY, property = \"type\""));
+        assert!(envelope.contains("@JsonSubTypes.Type(value = Join.class, name = \"Join\")"));
+        let handler = class("ChatMessageHandler");
+        println!("{handler}");
+        assert!(handler.contains("public interface ChatMessageHandler"));
+        assert!(handler.contains("void onJoin(Join message);"));
+        assert!(handler.contains("void onLeave(Leave message);"));
+        assert!(handler.contains("default void handle(ChatMessage message)"));
+        assert!(handler.contains(
+            "if (message instanceof Join) { onJoin((Join) message); } else if (message instanceof Leave) { onLeave((Leave) message); } else { throw new IllegalArgumentException"
+        ));
+        assert!(!rdc_java!(Drawing)
+            .unwrap()
+            .iter()
+            .any(|c| c.name().ends_with("Handler")));
+    }
+}
@@ -0,0 +1,123 @@
+use crate::errors::Error;
+use crate::targets::java::context::JavaContext;
+use crate::targets::java::JavaClass;
+use genco::prelude::*;
+use genco::quote;
+
+/// This function generates the `RdcJson` class, which builds or configures an `ObjectMapper`
+/// the way the generated classes expect.
+///
+/// It registers the `Jdk8Module` and the `JavaTimeModule`,
+/// and fails on unknown properties only when some types deny unknown fields.
+/// With `@JsonFilter` annotations, it sets a filter provider serializing all the properties.
+/// With case-insensitive properties, it enables `MapperFeature.ACCEPT_CASE_INSENSITIVE_PROPERTIES`.
+/// Data enums carry their serializers in annotations, so there is no module of generated serializers to register.
+pub fn generate_rdc_json_class(ctx: &JavaContext) -> Result<JavaClass, Error> {
+    let fail_on_unknown = if ctx.config().fails_on_unknown_properties() {
+        "true"
+    } else {
+        "false"
+    };
+    let filter_provider = ctx
+        .config()
+        .json_filters()
+        .then(|| quote!(mapper.setFilterProvider(JsonFilters.serializeAll());));
+    // Also covers classes the generated ones reference, e.g. hand-written ones replacing removed types.
+    let case_insensitive = ctx.config().case_insensitive_properties().then(|| {
+        quote!(mapper.configure(com.fasterxml.jackson.databind.MapperFeature.ACCEPT_CASE_INSENSITIVE_PROPERTIES, true);)
+    });
+    let tokens: java::Tokens = quote!(
+        import com.fasterxml.jackson.databind.DeserializationFeature;
+        import com.fasterxml.jackson.databind.ObjectMapper;
+        import com.fasterxml.jackson.datatype.jdk8.Jdk8Module;
+        import com.fasterxml.jackson.datatype.jsr310.JavaTimeModule;
+
+        public final class RdcJson {
+            public static ObjectMapper objectMapper() {
+                return configure(new ObjectMapper());
+            }
+
+            public static ObjectMapper configure(ObjectMapper mapper) {
+                mapper.registerModule(new Jdk8Module());
+                mapper.registerModule(new JavaTimeModule());
+                mapper.configure(DeserializationFeature.FAIL_ON_UNKNOWN_PROPERTIES, $fail_on_unknown);
+                $case_insensitive
+                $filter_provider
+                return mapper;
+            }
+
+            private RdcJson() {}
+        }
+    );
+    JavaClass::from_tokens("RdcJson".to_string(), tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::targets::java::JavaConfig;
+    use crate::{rdc_java, RDC};
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Request {
+        id: String,
+        payload: Payload,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Payload {
+        data: String,
+    }
+
+    #[test]
+    fn rdc_json_class() {
+        let config = JavaConfig::new().with_rdc_json(true);
+        let classes = rdc_java!(config = config; Request).unwrap();
+        let code = classes
+            .iter()
+            .find(|c| c.name() == "RdcJson")
+            .unwrap()
+            .code();
+        println!("{code}");
+        assert!(code.contains("public static ObjectMapper objectMapper()"));
+        assert!(code.contains("mapper.registerModule(new Jdk8Module());"));
+        assert!(code.contains("mapper.registerModule(new JavaTimeModule());"));
+        assert!(code.contains(
+            "mapper.configure(DeserializationFeature.FAIL_ON_UNKNOWN_PROPERTIES, false);"
+        ));
+        assert!(classes
+            .iter()
+            .all(|c| !c.code().contains("JsonIgnoreProperties")));
+    }
+
+    #[test]
+    fn case_insensitive_properties() {
+        let config = JavaConfig::new()
+            .with_rdc_json(true)
+            .with_case_insensitive_properties(true);
+        let classes = rdc_java!(config = config; Request).unwrap();
+        let code = classes
+            .iter()
+            .find(|c| c.name() == "RdcJson")
+            .unwrap()
+            .code();
+        assert!(code
+            .contains("mapper.configure(MapperFeature.ACCEPT_CASE_INSENSITIVE_PROPERTIES, true);"));
+    }
+
+    #[test]
+    fn deny_unknown_fields() {
+        let config = JavaConfig::new()
+            .with_rdc_json(true)
+            .with_deny_unknown_fields("Request");
+        let classes = rdc_java!(config = config; Request).unwrap();
+        let class = |name: &str| classes.iter().find(|c| c.name() == name).unwrap().code();
+        assert!(class("RdcJson").contains(
+            "mapper.configure(DeserializationFeature.FAIL_ON_UNKNOWN_PROPERTIES, true);"
+        ));
+        assert!(!class("Request").contains("JsonIgnoreProperties"));
+        assert!(class("Payload").contains("@JsonIgnoreProperties(ignoreUnknown = true)"));
+    }
+}
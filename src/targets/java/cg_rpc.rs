@@ -0,0 +1,221 @@
+use crate::errors::Error;
+use crate::ir::{CustomType, RpcMethod, RpcService};
+use crate::targets::java::context::JavaContext;
+use crate::targets::java::JavaClass;
+use genco::prelude::*;
+use genco::quote;
+
+/// This function generates the interface of a JSON-RPC service, which servers implement.
+/// Its default `dispatch` method reads the parameters of a request and calls the method with the requested name.
+pub fn generate_rpc_interface(service: &RpcService, ctx: &JavaContext) -> Result<JavaClass, Error> {
+    let class_name = ctx.class_name(&CustomType::new(service.name().as_pascal_case()));
+    let class_name_str = class_name.as_str();
+    let methods = service
+        .methods()
+        .iter()
+        .map(|m| {
+            let signature = generate_signature(m, ctx);
+            quote!($signature;)
+        })
+        .collect::<Vec<java::Tokens>>();
+    let cases = service
+        .methods()
+        .iter()
+        .map(|m| generate_case(m, ctx))
+        .collect::<Vec<java::Tokens>>();
+    let tokens: java::Tokens = quote!(
+        import com.fasterxml.jackson.core.JsonProcessingException;
+        import com.fasterxml.jackson.databind.JsonNode;
+        import com.fasterxml.jackson.databind.ObjectMapper;
+
+        public interface $class_name_str {
+            $(for m in methods join ($['\n']) => $m)
+
+            default Object dispatch(String method, JsonNode params, ObjectMapper mapper) throws JsonProcessingException {
+                switch (method) {
+                    $(for c in cases join ($['\n']) => $c)
+                    default:
+                        throw new IllegalArgumentException("Unknown method: " + method);
+                }
+            }
+        }
+    );
+
+    JavaClass::from_tokens(class_name, tokens)
+}
+
+/// This function generates a client of a JSON-RPC service, which implements its interface
+/// and sends every call through a `Transport`, e.g. one based on an HTTP client or a WebSocket.
+pub fn generate_rpc_client(service: &RpcService, ctx: &JavaContext) -> Result<JavaClass, Error> {
+    let interface_name = ctx.class_name(&CustomType::new(service.name().as_pascal_case()));
+    let class_name = format!("{interface_name}Client");
+    let class_name_str = class_name.as_str();
+    let methods = service
+        .methods()
+        .iter()
+        .map(|m| generate_client_method(m, ctx))
+        .collect::<Vec<java::Tokens>>();
+    let tokens: java::Tokens = quote!(
+        import com.fasterxml.jackson.core.type.TypeReference;
+
+        public class $class_name_str implements $interface_name {
+            public interface Transport {
+                <T> T call(String method, Object params, TypeReference<T> resultType);
+            }
+
+            private final Transport transport;
+
+            public $class_name_str(Transport transport) {
+                this.transport = transport;
+            }
+
+            $(for m in methods join ($['\n']) => $m)
+        }
+    );
+
+    JavaClass::from_tokens(class_name, tokens)
+}
+
+fn generate_signature(method: &RpcMethod, ctx: &JavaContext) -> java::Tokens {
+    let name = ctx.field_name(method.name());
+    let result = method
+        .result()
+        .map(|r| ctx.type_name(r))
+        .unwrap_or_else(|| "void".to_string());
+    match method.params_type() {
+        Some(params_type) => {
+            let params_class = ctx.class_name(&params_type);
+            quote!($result $name($params_class params))
+        }
+        None => quote!($result $name()),
+    }
+}
+
+fn generate_case(method: &RpcMethod, ctx: &JavaContext) -> java::Tokens {
+    let rpc_name = method.rpc_name();
+    let name = ctx.field_name(method.name());
+    let call: java::Tokens = match method.params_type() {
+        Some(params_type) => {
+            let params_class = ctx.class_name(&params_type);
+            quote!($name(mapper.treeToValue(params, $params_class.class)))
+        }
+        None => quote!($name()),
+    };
+    match method.result() {
+        Some(_) => quote!(
+            case $[str]($[const](rpc_name)):
+                return $call;
+        ),
+        None => quote!(
+            case $[str]($[const](rpc_name)):
+                $call;
+                return null;
+        ),
+    }
+}
+
+fn generate_client_method(method: &RpcMethod, ctx: &JavaContext) -> java::Tokens {
+    let signature = generate_signature(method, ctx);
+    let rpc_name = method.rpc_name();
+    let params = match method.params_type() {
+        Some(_) => "params",
+        None => "null",
+    };
+    let body: java::Tokens = match method.result() {
+        Some(result) => {
+            let result = ctx.type_name(result);
+            quote!(return transport.call($[str]($[const](rpc_name)), $params, new TypeReference<$result>() {});)
+        }
+        None => {
+            quote!(transport.call($[str]($[const](rpc_name)), $params, new TypeReference<Void>() {});)
+        }
+    };
+    quote!(
+        @Override
+        public $signature {
+            $body
+        }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::ir::{IntermediateRepresentation, TypeTarget};
+    use crate::targets::java::generate_java_code;
+    use crate::{rdc_rpc_service, RDC};
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct User {
+        name: String,
+    }
+
+    rdc_rpc_service! {
+        #[allow(unused)]
+        trait UserRpc {
+            #[rdc("user.get")]
+            fn get_user(&self, user_id: String, verbose: bool) -> User;
+            #[rdc("user.list")]
+            fn list_users(&self) -> Vec<User>;
+            #[rdc("user.ping")]
+            fn ping(&self);
+        }
+    }
+
+    #[test]
+    fn generate_rpc_stubs() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add_service_trait::<dyn UserRpc>();
+        let classes = generate_java_code(&ir).unwrap();
+        let params = classes
+            .iter()
+            .find(|c| c.name() == "GetUserParams")
+            .unwrap();
+        assert!(params
+            .code()
+            .contains("@JsonProperty(\"user_id\") private String userId;"));
+        let interface = classes.iter().find(|c| c.name() == "UserRpc").unwrap();
+        let code = interface.code();
+        println!("{code}");
+        assert!(code.contains("User getUser(GetUserParams params);"));
+        assert!(code.contains("List<User> listUsers();"));
+        assert!(code.contains("void ping();"));
+        assert!(code.contains(
+            "case \"user.get\": return getUser(mapper.treeToValue(params, GetUserParams.class));"
+        ));
+        assert!(code.contains("case \"user.ping\": ping(); return null;"));
+        let client = classes
+            .iter()
+            .find(|c| c.name() == "UserRpcClient")
+            .unwrap();
+        let code = client.code();
+        println!("{code}");
+        assert!(code.contains("public class UserRpcClient implements UserRpc"));
+        assert!(code.contains(
+            "return transport.call(\"user.get\", params, new TypeReference<User>() {});"
+        ));
+        assert!(code.contains("transport.call(\"user.ping\", null, new TypeReference<Void>() {});"));
+    }
+
+    rdc_rpc_service! {
+        #[allow(unused)]
+        trait DuplicateRpc {
+            #[rdc("user.get")]
+            fn get_user(&self) -> User;
+            #[rdc("user.get")]
+            fn find_user(&self) -> User;
+        }
+    }
+
+    #[test]
+    fn duplicate_rpc_methods() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add_service_trait::<dyn DuplicateRpc>();
+        let error = ir.validate().unwrap_err();
+        assert_eq!(
+            error.message(),
+            "RPC service `DuplicateRpc` has multiple methods called `user.get`"
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use crate::ir::{Field, Struct};
+use crate::targets::java::cg_utils::Annotation;
+use crate::targets::java::context::JavaContext;
+use genco::prelude::*;
+use genco::quote;
+
+const SCHEMA: &str = "io.swagger.v3.oas.annotations.media.Schema";
+
+/// Returns the `@Schema` annotation of a data class with a description, when schema annotations are enabled.
+pub fn class_annotations(dc: &Struct, ctx: &JavaContext) -> Vec<Annotation> {
+    let config = ctx.config();
+    if !config.schema_annotations() {
+        return Vec::new();
+    }
+    match config.type_description(dc.self_type().type_name()) {
+        Some(description) => vec![(
+            SCHEMA,
+            quote!(@Schema(description = $[str]($[const](description)))),
+        )],
+        None => Vec::new(),
+    }
+}
+
+/// Returns the `@Schema` annotation of a field, when schema annotations are enabled.
+/// It holds the description and the example of the field, and marks fields that are not an `Option` as required.
+pub fn field_annotations(field: &Field, owner: &Struct, ctx: &JavaContext) -> Vec<Annotation> {
+    let config = ctx.config();
+    if !config.schema_annotations() {
+        return Vec::new();
+    }
+    let type_name = owner.self_type().type_name();
+    let field_name = field.name().as_snake_case();
+    let mut elements: Vec<java::Tokens> = Vec::new();
+    if let Some(description) = config.field_description(type_name, &field_name) {
+        elements.push(quote!(description = $[str]($[const](description))));
+    }
+    if let Some(example) = field.example() {
+        elements.push(quote!(example = $[str]($[const](example))));
+    }
+    if field.is_required() {
+        elements.push(quote!(requiredMode = Schema.RequiredMode.REQUIRED));
+    }
+    if elements.is_empty() {
+        return Vec::new();
+    }
+    vec![(
+        SCHEMA,
+        quote!(@Schema($(for e in elements join (, ) => $e))),
+    )]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::ir::{IntermediateRepresentation, TypeTarget};
+    use crate::targets::java::{generate_java_code_with_config, JavaConfig};
+    use crate::{rdc_java, RDC};
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Invoice {
+        number: String,
+        note: Option<String>,
+        comment: Option<String>,
+    }
+
+    #[test]
+    fn schema_annotations() {
+        let config = JavaConfig::new()
+            .with_schema_annotations(true)
+            .with_type_description("Invoice", "An invoice sent to a customer.")
+            .with_field_description("Invoice", "number", "Number of the \"invoice\".")
+            .with_field_description("Invoice", "note", "Note printed on the invoice.");
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Invoice>();
+        ir.set_field_example("Invoice", "number", "INV-001")
+            .unwrap();
+        let classes = generate_java_code_with_config(&ir, &config).unwrap();
+        let code = classes[0].code();
+        println!("{code}");
+        assert!(code.contains("import io.swagger.v3.oas.annotations.media.Schema;"));
+        assert!(code.contains(
+            "@Schema(description = \"An invoice sent to a customer.\") public class Invoice"
+        ));
+        assert!(code.contains(
+            "@Schema(description = \"Number of the \\\"invoice\\\".\", example = \"INV-001\", requiredMode = Schema.RequiredMode.REQUIRED) private String number;"
+        ));
+        assert!(code.contains(
+            "@Schema(description = \"Note printed on the invoice.\") private String note;"
+        ));
+        assert!(code.contains("@JsonProperty(\"comment\") private String comment;"));
+        let plain = rdc_java!(Invoice).unwrap();
+        assert!(!plain[0].code().contains("@Schema"));
+    }
+}
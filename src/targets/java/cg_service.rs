@@ -0,0 +1,243 @@
+use crate::errors::Error;
+use crate::ir::{CustomType, Endpoint, HttpMethod, Param, ParamKind, Service};
+use crate::targets::java::config::HttpClientStyle;
+use crate::targets::java::context::JavaContext;
+use crate::targets::java::JavaClass;
+use genco::prelude::*;
+use genco::quote;
+use std::collections::BTreeSet;
+
+/// This function generates an HTTP client interface for a service.
+/// Implementations are created by the configured client library, e.g. `retrofit.create(UserApi.class)`.
+pub fn generate_service_interface(
+    service: &Service,
+    ctx: &JavaContext,
+) -> Result<JavaClass, Error> {
+    let class_name = ctx.class_name(&CustomType::new(service.name().as_pascal_case()));
+    let class_name_str = class_name.as_str();
+    let mut imports = BTreeSet::new();
+    let methods = service
+        .endpoints()
+        .iter()
+        .map(|e| match ctx.config().http_client() {
+            HttpClientStyle::Retrofit => generate_retrofit_method(e, ctx, &mut imports),
+            HttpClientStyle::Feign => generate_feign_method(e, ctx, &mut imports),
+        })
+        .collect::<Vec<java::Tokens>>();
+    let tokens: java::Tokens = quote!(
+        $(for i in imports => import $i;)
+
+        public interface $class_name_str {
+            $(for m in methods join ($['\n']) => $m)
+        }
+    );
+
+    JavaClass::from_tokens(class_name, tokens)
+}
+
+fn generate_retrofit_method(
+    endpoint: &Endpoint,
+    ctx: &JavaContext,
+    imports: &mut BTreeSet<&'static str>,
+) -> java::Tokens {
+    let (import, annotation) = match endpoint.method() {
+        HttpMethod::Get => ("retrofit2.http.GET", "GET"),
+        HttpMethod::Post => ("retrofit2.http.POST", "POST"),
+        HttpMethod::Put => ("retrofit2.http.PUT", "PUT"),
+        HttpMethod::Patch => ("retrofit2.http.PATCH", "PATCH"),
+        HttpMethod::Delete => ("retrofit2.http.DELETE", "DELETE"),
+    };
+    imports.insert("retrofit2.Call");
+    imports.insert(import);
+    let path = endpoint.path();
+    let name = ctx.field_name(endpoint.name());
+    let response = endpoint
+        .response()
+        .map(|r| ctx.type_name(r))
+        .unwrap_or_else(|| "Void".to_string());
+    let params = endpoint
+        .params()
+        .iter()
+        .map(|p| {
+            let (import, annotation): (&'static str, java::Tokens) = match p.kind() {
+                ParamKind::Path => {
+                    let variable = p.name().as_snake_case();
+                    (
+                        "retrofit2.http.Path",
+                        quote!(@Path($[str]($[const](variable)))),
+                    )
+                }
+                ParamKind::Query => {
+                    let variable = p.name().as_snake_case();
+                    (
+                        "retrofit2.http.Query",
+                        quote!(@Query($[str]($[const](variable)))),
+                    )
+                }
+                ParamKind::Body => ("retrofit2.http.Body", quote!(@Body)),
+            };
+            imports.insert(import);
+            generate_param(p, Some(annotation), ctx)
+        })
+        .collect::<Vec<java::Tokens>>();
+    quote!(
+        @$annotation($[str]($[const](path)))
+        Call<$response> $name($(for p in params join (, ) => $p));
+    )
+}
+
+fn generate_feign_method(
+    endpoint: &Endpoint,
+    ctx: &JavaContext,
+    imports: &mut BTreeSet<&'static str>,
+) -> java::Tokens {
+    imports.insert("feign.RequestLine");
+    let query = endpoint
+        .params()
+        .iter()
+        .filter(|p| p.kind() == ParamKind::Query)
+        .map(|p| format!("{0}={{{0}}}", p.name().as_snake_case()))
+        .collect::<Vec<String>>();
+    let request_line = if query.is_empty() {
+        format!("{} {}", endpoint.method().as_str(), endpoint.path())
+    } else {
+        format!(
+            "{} {}?{}",
+            endpoint.method().as_str(),
+            endpoint.path(),
+            query.join("&")
+        )
+    };
+    let has_body = endpoint
+        .params()
+        .iter()
+        .any(|p| p.kind() == ParamKind::Body);
+    let headers = has_body.then(|| {
+        imports.insert("feign.Headers");
+        quote!(@Headers("Content-Type: application/json"))
+    });
+    let name = ctx.field_name(endpoint.name());
+    let response = endpoint
+        .response()
+        .map(|r| ctx.type_name(r))
+        .unwrap_or_else(|| "void".to_string());
+    let params = endpoint
+        .params()
+        .iter()
+        .map(|p| {
+            let annotation = match p.kind() {
+                ParamKind::Path | ParamKind::Query => {
+                    imports.insert("feign.Param");
+                    let variable = p.name().as_snake_case();
+                    Some(quote!(@Param($[str]($[const](variable)))))
+                }
+                ParamKind::Body => None,
+            };
+            generate_param(p, annotation, ctx)
+        })
+        .collect::<Vec<java::Tokens>>();
+    quote!(
+        @RequestLine($[str]($[const](request_line)))
+        $headers
+        $response $name($(for p in params join (, ) => $p));
+    )
+}
+
+fn generate_param(
+    param: &Param,
+    annotation: Option<java::Tokens>,
+    ctx: &JavaContext,
+) -> java::Tokens {
+    let type_string = ctx.type_name(param.param_type());
+    let name = ctx.field_name(param.name());
+    match annotation {
+        Some(annotation) => quote!($annotation $type_string $name),
+        None => quote!($type_string $name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::ir::{IntermediateRepresentation, TypeTarget};
+    use crate::targets::java::{generate_java_code_with_config, HttpClientStyle, JavaConfig};
+    use crate::{rdc_service, RDC};
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct User {
+        name: String,
+    }
+
+    rdc_service! {
+        #[allow(unused)]
+        trait UserApi {
+            #[rdc(get, "/users/{user_id}")]
+            fn get_user(&self, user_id: String) -> User;
+            #[rdc(get, "/users")]
+            fn list_users(&self, limit: Option<i32>) -> Vec<User>;
+            #[rdc(post, "/users")]
+            fn create_user(&self, body: User) -> User;
+            #[rdc(delete, "/users/{user_id}")]
+            fn delete_user(&self, user_id: String);
+        }
+    }
+
+    fn generate(config: JavaConfig) -> String {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add_service_trait::<dyn UserApi>();
+        let classes = generate_java_code_with_config(&ir, &config).unwrap();
+        assert_eq!(classes.len(), 2);
+        let class = classes.iter().find(|c| c.name() == "UserApi").unwrap();
+        println!("{}", class.code());
+        class.code().to_string()
+    }
+
+    #[test]
+    fn generate_retrofit_interface() {
+        let code = generate(JavaConfig::new());
+        assert!(code.contains("public interface UserApi"));
+        assert!(code.contains("import retrofit2.Call;"));
+        assert!(code.contains("@GET(\"/users/{user_id}\")"));
+        assert!(code.contains("Call<User> getUser(@Path(\"user_id\") String userId);"));
+        assert!(code.contains("Call<List<User>> listUsers(@Query(\"limit\") Integer limit);"));
+        assert!(code.contains("Call<User> createUser(@Body User body);"));
+        assert!(code.contains("@DELETE(\"/users/{user_id}\")"));
+        assert!(code.contains("Call<Void> deleteUser("));
+        assert!(!code.contains("PUT"));
+    }
+
+    #[test]
+    fn generate_feign_interface() {
+        let code = generate(JavaConfig::new().with_http_client(HttpClientStyle::Feign));
+        assert!(code.contains("import feign.RequestLine;"));
+        assert!(code.contains("@RequestLine(\"GET /users/{user_id}\")"));
+        assert!(code.contains("User getUser(@Param(\"user_id\") String userId);"));
+        assert!(code.contains("@RequestLine(\"GET /users?limit={limit}\")"));
+        assert!(code.contains("@Headers(\"Content-Type: application/json\")"));
+        assert!(code.contains("User createUser(User body);"));
+        assert!(code.contains("void deleteUser("));
+        assert!(!code.contains("retrofit2"));
+    }
+
+    rdc_service! {
+        #[allow(unused)]
+        trait BrokenApi {
+            #[rdc(get, "/items/{id}")]
+            fn get_item(&self, body: User);
+        }
+    }
+
+    #[test]
+    fn reject_invalid_endpoints() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add_service_trait::<dyn BrokenApi>();
+        let error = ir.validate().unwrap_err();
+        assert!(error.message().contains(
+            "Endpoint `get_item` of service `BrokenApi` has no parameter for path variable `id`"
+        ));
+        assert!(error
+            .message()
+            .contains("Endpoint `get_item` of service `BrokenApi` is a GET request, so it can not have a body"));
+    }
+}
@@ -0,0 +1,127 @@
+use crate::ir::{Field, Struct};
+use crate::targets::java::cg_utils::Annotation;
+use crate::targets::java::context::JavaContext;
+use crate::targets::java::JavaFlavor;
+use genco::quote;
+
+/// Returns Bean Validation annotations of a field when the Spring flavor is enabled.
+/// Fields that are not optional must not be null and nested generated classes are validated recursively.
+/// Fields with a format set with `set_field_format` get `@DateTimeFormat`, so that request parameters bind with it.
+pub fn field_annotations(field: &Field, ctx: &JavaContext) -> Vec<Annotation> {
+    let mut annotations = Vec::new();
+    if ctx.config().flavor() != JavaFlavor::Spring {
+        return annotations;
+    }
+    let field_type = field.field_type();
+    if field.is_required() {
+        annotations.push(("jakarta.validation.constraints.NotNull", quote!(@NotNull)));
+    }
+    if ctx.references_generated_class(field_type) {
+        annotations.push(("jakarta.validation.Valid", quote!(@Valid)));
+    }
+    if let Some(format) = field.format() {
+        annotations.push((
+            "org.springframework.format.annotation.DateTimeFormat",
+            quote!(@DateTimeFormat(pattern = $[str]($[const](format.pattern())))),
+        ));
+    }
+    annotations
+}
+
+/// Returns class annotations when the Spring flavor is enabled.
+/// Classes bound to configuration properties get `@ConfigurationProperties` and `@Validated`.
+pub fn class_annotations(dc: &Struct, ctx: &JavaContext) -> Vec<Annotation> {
+    let config = ctx.config();
+    if config.flavor() != JavaFlavor::Spring {
+        return Vec::new();
+    }
+    match config.configuration_properties(dc.self_type().type_name()) {
+        Some(prefix) => vec![
+            (
+                "org.springframework.boot.context.properties.ConfigurationProperties",
+                quote!(@ConfigurationProperties(prefix = $[str]($[const](prefix)))),
+            ),
+            (
+                "org.springframework.validation.annotation.Validated",
+                quote!(@Validated),
+            ),
+        ],
+        None => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::ir::{DateTimeFormat, IntermediateRepresentation, TypeTarget};
+    use crate::targets::java::{generate_java_code_with_config, JavaConfig, JavaFlavor};
+    use crate::{rdc_java, RDC};
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct ServerConfig {
+        host: String,
+        port: Option<i32>,
+        tls: Tls,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Tls {
+        certificates: Vec<String>,
+    }
+
+    #[test]
+    fn spring_flavor() {
+        let config = JavaConfig::new()
+            .with_flavor(JavaFlavor::Spring)
+            .with_configuration_properties("ServerConfig", "server");
+        let classes = rdc_java!(config = config; ServerConfig).unwrap();
+        let class = |name: &str| classes.iter().find(|c| c.name() == name).unwrap().code();
+        let server_config = class("ServerConfig");
+        assert!(server_config.contains("import jakarta.validation.Valid;"));
+        assert!(server_config.contains("@ConfigurationProperties(prefix = \"server\")"));
+        assert!(server_config.contains("@Validated"));
+        assert!(server_config.contains("@NotNull private String host;"));
+        assert!(server_config.contains("@JsonProperty(\"port\") private Integer port;"));
+        assert!(server_config.contains("@Valid private Tls tls;"));
+
+        let tls = class("Tls");
+        assert!(!tls.contains("ConfigurationProperties"));
+        assert!(!tls.contains("import jakarta.validation.Valid;"));
+        assert!(tls.contains("@NotNull private List<String> certificates;"));
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Booking {
+        starts_at: String,
+    }
+
+    #[test]
+    fn date_time_format() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Booking>();
+        let format = DateTimeFormat::new("yyyy-MM-dd'T'HH:mm").with_timezone("UTC");
+        ir.set_field_format("Booking", "starts_at", format).unwrap();
+
+        let config = JavaConfig::new().with_flavor(JavaFlavor::Spring);
+        let classes = generate_java_code_with_config(&ir, &config).unwrap();
+        let code = classes[0].code();
+        assert!(code.contains("import org.springframework.format.annotation.DateTimeFormat;"));
+        assert!(code.contains("@DateTimeFormat(pattern = \"yyyy-MM-dd\\'T\\'HH:mm\")"));
+        assert!(code.contains("@JsonFormat(shape = JsonFormat.Shape.STRING"));
+
+        let classes = generate_java_code_with_config(&ir, &JavaConfig::new()).unwrap();
+        assert!(!classes[0].code().contains("DateTimeFormat"));
+    }
+
+    #[test]
+    fn plain_flavor() {
+        let classes = rdc_java!(ServerConfig).unwrap();
+        for class in &classes {
+            assert!(!class.code().contains("jakarta"));
+            assert!(!class.code().contains("@NotNull"));
+        }
+    }
+}
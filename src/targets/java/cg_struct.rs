@@ -1,49 +1,316 @@
 use crate::errors::Error;
-use crate::ir::{Field, Struct};
+use crate::ir::{Field, Struct, TypeKind};
+use crate::targets::java::cg_utils::{case_insensitive_annotation, property_order, Annotation};
+use crate::targets::java::context::JavaContext;
 use crate::targets::java::JavaClass;
+use crate::targets::java::{
+    cg_builder, cg_csv, cg_empty, cg_filters, cg_fingerprint, cg_interface, cg_json_schema,
+    cg_nullability, cg_parcelable, cg_schema, cg_spring, cg_views, cg_xml,
+};
 use genco::prelude::*;
 use genco::quote;
+use std::collections::BTreeSet;
 
-fn generate_field_declaration(field: &Field) -> java::Tokens {
-    let name = field.name().as_camel_case();
+fn generate_field_declaration(
+    field: &Field,
+    dc: &Struct,
+    ctx: &JavaContext,
+    imports: &mut BTreeSet<&'static str>,
+) -> java::Tokens {
+    let name = ctx.field_name(field.name());
     let json_name = field.json_name();
-    let type_string = field.field_type().type_name();
+    let type_string = ctx.type_name(field.field_type());
+    if ctx.collects_extra_properties(dc, field) {
+        return quote!(private $type_string $name = new java.util.LinkedHashMap<>(););
+    }
+    let annotations = cg_spring::field_annotations(field, ctx)
+        .into_iter()
+        .chain(cg_xml::field_annotations(field, dc, ctx))
+        .chain(cg_csv::field_annotations(field, dc, ctx))
+        .chain(cg_views::field_annotations(field, dc, ctx))
+        .chain(cg_schema::field_annotations(field, dc, ctx))
+        .chain(cg_nullability::field_annotations(field, ctx))
+        .chain(format_annotation(field))
+        .map(|(import, annotation)| {
+            imports.insert(import);
+            annotation
+        })
+        .collect::<Vec<java::Tokens>>();
     quote! {
         @JsonProperty($[str]($[const](json_name)))
+        $(for a in annotations => $a)
         private $type_string $name;
     }
 }
 
-fn generate_access_methods(field: &Field) -> java::Tokens {
-    let pascal_name = &field.name().as_pascal_case();
-    let camel_name = &field.name().as_camel_case();
-    let type_string = field.field_type().type_name();
-    quote! {
-        public $type_string get$pascal_name() {
+/// Returns `@JsonFormat` reproducing the text format of a date or time field set with `set_field_format`.
+fn format_annotation(field: &Field) -> Option<Annotation> {
+    let format = field.format()?;
+    let pattern = format.pattern();
+    let timezone = format
+        .timezone()
+        .map(|timezone| quote!(, timezone = $[str]($[const](timezone))));
+    Some((
+        "com.fasterxml.jackson.annotation.JsonFormat",
+        quote!(@JsonFormat(shape = JsonFormat.Shape.STRING, pattern = $[str]($[const](pattern))$timezone)),
+    ))
+}
+
+fn generate_access_methods(field: &Field, dc: &Struct, ctx: &JavaContext) -> java::Tokens {
+    let camel_name = &ctx.field_name(field.name());
+    let type_string = &ctx.type_name(field.field_type());
+    if ctx.collects_extra_properties(dc, field) {
+        return generate_extra_properties_methods(field, ctx);
+    }
+    let getter_name = &ctx.getter_name(field.name(), field.field_type());
+    let javadoc = cg_nullability::getter_javadoc(field, dc, ctx);
+    let nullable = cg_nullability::field_annotations(field, ctx)
+        .into_iter()
+        .map(|(_, annotation)| annotation);
+    let getter = quote! {
+        $javadoc
+        $(for a in nullable => $a)
+        public $type_string $getter_name() {
             return $camel_name;
         }
+    };
+    if field.is_readonly() {
+        return getter;
+    }
+    let check = generate_unsigned_check(field, ctx);
+    let setter_name = &ctx.setter_name(field.name(), field.field_type());
+    // Jackson links setters to fields by JavaBeans names, so other setters are annotated with the JSON name.
+    let setter_annotation = (setter_name != &format!("set{}", ctx.accessor_name(field.name())))
+        .then(|| quote!(@JsonProperty($[str]($[const](field.json_name())))));
+    quote! {
+        $getter
 
-        public void set$pascal_name($type_string $camel_name) {
+        $setter_annotation
+        public void $setter_name($type_string $camel_name) {
+            $check
             this.$camel_name = $camel_name;
         }
     }
 }
 
-pub fn generate_data_class(dc: &Struct) -> Result<JavaClass, Error> {
-    let class_name = dc.name().as_pascal_case();
+fn generate_unsigned_check(field: &Field, ctx: &JavaContext) -> Option<java::Tokens> {
+    let camel_name = &ctx.field_name(field.name());
+    ctx.requires_unsigned_check(field.field_type()).then(|| {
+        let message = format!("{camel_name} must not be negative");
+        quote! {
+            if ($camel_name != null && $camel_name < 0) {
+                throw new IllegalArgumentException($[str]($[const](message)));
+            }
+        }
+    })
+}
+
+/// Generates a `@JsonCreator` constructor taking the fields in declaration order,
+/// besides a map collecting extra properties, which Jackson fills through its `@JsonAnySetter` method.
+/// Returns `None` when there are no parameters, because the no-arg constructor already exists.
+fn generate_all_args_constructor(dc: &Struct, ctx: &JavaContext) -> Option<java::Tokens> {
+    let fields = dc
+        .fields()
+        .iter()
+        .filter(|f| !ctx.collects_extra_properties(dc, f))
+        .collect::<Vec<&Field>>();
+    if fields.is_empty() {
+        return None;
+    }
+    let class_name = ctx.class_name(dc.self_type());
+    let parameters = fields.iter().map(|f| {
+        let json_name = f.json_name();
+        let type_string = ctx.type_name(f.field_type());
+        let name = ctx.field_name(f.name());
+        quote!(@JsonProperty($[str]($[const](json_name))) $type_string $name)
+    });
+    let checks = fields
+        .iter()
+        .filter_map(|f| generate_unsigned_check(f, ctx));
+    let assignments = fields.iter().map(|f| {
+        let name = ctx.field_name(f.name());
+        quote!(this.$(&name) = $(&name);)
+    });
+    Some(quote! {
+        @JsonCreator
+        public $class_name($(for p in parameters join (, ) => $p)) {
+            $(for c in checks => $c)
+            $(for a in assignments => $a)
+        }
+    })
+}
+
+/// Generates the methods of a map field collecting unknown properties.
+/// Jackson passes every property without a matching field to the `@JsonAnySetter` method
+/// and writes the entries returned by the `@JsonAnyGetter` method next to the other properties.
+fn generate_extra_properties_methods(field: &Field, ctx: &JavaContext) -> java::Tokens {
+    let getter_name = &ctx.getter_name(field.name(), field.field_type());
+    let setter_name = &ctx.setter_name(field.name(), field.field_type());
+    let camel_name = &ctx.field_name(field.name());
+    let type_string = &ctx.type_name(field.field_type());
+    let value_type = match field.field_type().kind() {
+        TypeKind::Map(_, value) => ctx.type_name(value),
+        _ => "Object".to_string(),
+    };
+    quote! {
+        @JsonAnyGetter
+        public $type_string $getter_name() {
+            return $camel_name;
+        }
+
+        @JsonAnySetter
+        public void $setter_name(String key, $value_type value) {
+            this.$camel_name.put(key, value);
+        }
+    }
+}
+
+/// Generates a nested class with constants holding the JSON names of the fields,
+/// so that Java code can refer to them without string literals, e.g. in queries or JSON pointers.
+fn generate_field_constants(dc: &Struct) -> java::Tokens {
+    let constants = dc.fields().iter().map(|f| {
+        let name = f.name().as_upper_snake_case();
+        let json_name = f.json_name();
+        quote!(public static final String $name = $[str]($[const](json_name));)
+    });
+    quote! {
+        public static final class Fields {
+            $(for c in constants => $c)
+
+            private Fields() {}
+        }
+    }
+}
+
+pub fn generate_data_class(dc: &Struct, ctx: &JavaContext) -> Result<JavaClass, Error> {
+    let class_name = ctx.class_name(dc.self_type());
     let class_name_str = class_name.as_str();
-    let field_declarations = dc.fields().iter().map(generate_field_declaration);
-    let access_methods = dc.fields().iter().map(generate_access_methods);
-    let class_custom_type = dc.self_type().type_name();
+    let mut imports = BTreeSet::from(["com.fasterxml.jackson.annotation.JsonProperty"]);
+    let field_declarations = dc
+        .fields()
+        .iter()
+        .map(|f| generate_field_declaration(f, dc, ctx, &mut imports))
+        .collect::<Vec<java::Tokens>>();
+    let type_name = dc.self_type().type_name();
+    let mut extra_properties = Vec::new();
+    for f in dc.fields() {
+        if ctx.collects_extra_properties(dc, f) {
+            extra_properties.push(f.json_name());
+        } else if ctx
+            .config()
+            .extra_properties(type_name, &f.name().as_snake_case())
+        {
+            ctx.warn(format!(
+                "`{type_name}.{}` is not a map with `String` keys, so it can not collect extra properties",
+                f.name().as_snake_case()
+            ));
+        }
+    }
+    if !extra_properties.is_empty() {
+        imports.insert("com.fasterxml.jackson.annotation.JsonAnyGetter");
+        imports.insert("com.fasterxml.jackson.annotation.JsonAnySetter");
+    }
+    let access_methods = dc
+        .fields()
+        .iter()
+        .map(|f| generate_access_methods(f, dc, ctx));
+    // Jackson refuses to serialize classes without properties, unless they are annotated.
+    let mut class_annotations = if dc.fields().is_empty() {
+        vec![(
+            "com.fasterxml.jackson.databind.annotation.JsonSerialize",
+            quote!(@JsonSerialize),
+        )]
+    } else {
+        vec![(
+            "com.fasterxml.jackson.annotation.JsonPropertyOrder",
+            property_order(
+                dc.fields()
+                    .iter()
+                    .map(|f| f.json_name())
+                    .filter(|json_name| !extra_properties.contains(json_name)),
+            ),
+        )]
+    };
+    if ctx.config().fails_on_unknown_properties() && !ctx.config().denies_unknown_fields(type_name)
+    {
+        class_annotations.push((
+            "com.fasterxml.jackson.annotation.JsonIgnoreProperties",
+            quote!(@JsonIgnoreProperties(ignoreUnknown = true)),
+        ));
+    }
+    class_annotations.extend(case_insensitive_annotation(ctx));
+    class_annotations.extend(cg_spring::class_annotations(dc, ctx));
+    class_annotations.extend(cg_xml::class_annotations(dc, ctx));
+    class_annotations.extend(cg_schema::class_annotations(dc, ctx));
+    class_annotations.extend(cg_filters::class_annotations(dc, ctx));
+    let class_annotations = class_annotations
+        .into_iter()
+        .map(|(import, annotation)| {
+            imports.insert(import);
+            annotation
+        })
+        .collect::<Vec<java::Tokens>>();
+    let csv_members = cg_csv::generate_members(dc, ctx, &mut imports);
+    let json_schema = cg_json_schema::generate_constant(dc.self_type(), ctx, false);
+    let fingerprint = cg_fingerprint::generate_constant(dc.self_type(), ctx, false);
+    let builder = ctx
+        .config()
+        .builders()
+        .then(|| cg_builder::generate_builder(dc, ctx));
+    let field_constants = ctx
+        .config()
+        .field_constants()
+        .then(|| generate_field_constants(dc));
+    let empty_factory = ctx
+        .config()
+        .empty_factories()
+        .then(|| cg_empty::generate_empty_factory(dc, ctx));
+    let all_args_constructor = ctx
+        .config()
+        .all_args_constructors()
+        .then(|| generate_all_args_constructor(dc, ctx))
+        .flatten();
+    if all_args_constructor.is_some() {
+        imports.insert("com.fasterxml.jackson.annotation.JsonCreator");
+    }
+    let mut interfaces = ctx.interfaces(dc.self_type());
+    if ctx.config().view_interfaces() {
+        interfaces.push(cg_interface::view_name(dc, ctx));
+    }
+    interfaces.extend(ctx.shared_interfaces(dc));
+    let parcelable = ctx.config().parcelable().then(|| {
+        imports.extend(cg_parcelable::imports());
+        interfaces.push("Parcelable".to_string());
+        cg_parcelable::generate_parcelable_methods(dc, ctx)
+    });
+    let implements = (!interfaces.is_empty())
+        .then(|| quote!(implements $(for i in &interfaces join (, ) => $i)));
     let tokens: java::Tokens = quote!(
-        import com.fasterxml.jackson.annotation.JsonProperty;
+        $(for i in imports => import $i;)
+
+        $(for a in class_annotations => $a)
+        public class $class_name_str $implements {
+            $csv_members
+
+            $json_schema
+
+            $fingerprint
+
+            $field_constants
 
-        public class $class_custom_type {
             $(for fd in field_declarations => $fd)
 
             public $class_name_str() {}
 
+            $all_args_constructor
+
+            $empty_factory
+
             $(for am in access_methods => $am)
+
+            $builder
+
+            $parcelable
         }
     );
 
@@ -54,10 +321,15 @@ pub fn generate_data_class(dc: &Struct) -> Result<JavaClass, Error> {
 mod tests {
     use crate as rdc;
     use crate::ir::TypeTarget::Java;
-    use crate::ir::{CustomType, Field, IntermediateRepresentation, Name, Struct, Type};
-    use crate::targets::java::tests::run_java;
-    use crate::targets::java::{generate_java_code, JavaClass};
-    use crate::RDCType;
+    use crate::ir::{
+        CustomType, DateTimeFormat, Field, IntermediateRepresentation, Name, Struct, Type,
+    };
+    use crate::targets::java::verify::run_java;
+    use crate::targets::java::{
+        generate_java_code, generate_java_code_with_config, AccessorStyle, JavaClass, JavaConfig,
+        UnsignedLongMapping,
+    };
+    use crate::{rdc_java, RDCType};
     use genco::quote;
     use rdc_macros::RDC;
     use serde::{Deserialize, Serialize};
@@ -86,6 +358,327 @@ mod tests {
         println!("{}", classes[0].code());
     }
 
+    #[test]
+    fn generate_readonly_field() {
+        let mut ir = IntermediateRepresentation::new(Java);
+        let mut struct_ir = Struct::new(
+            Name::from_pascal_case("TestStruct"),
+            CustomType::new("TestStruct"),
+        );
+
+        let mut id = Field::new(Name::from_snake_case("id"), "id", Type::new("String"));
+        id.set_readonly(true);
+        struct_ir.add_field(id);
+        struct_ir.add_field(Field::new(
+            Name::from_snake_case("value"),
+            "value",
+            Type::new("String"),
+        ));
+
+        ir.add_struct(struct_ir);
+
+        let classes = generate_java_code(&ir).unwrap();
+        let code = classes[0].code();
+        assert!(code.contains("public String getId()"));
+        assert!(!code.contains("setId"));
+        assert!(code.contains("public String getValue()"));
+        assert!(code.contains("public void setValue(String value)"));
+    }
+
+    /// A type which does not implement `RDCType`.
+    #[allow(unused)]
+    struct Opaque;
+
+    /// An identifier of a `T`, written as a string whatever `T` is.
+    #[allow(unused)]
+    struct Id<T>(String, std::marker::PhantomData<T>);
+
+    impl<T: 'static> crate::codegen::GenerateIR for Id<T> {}
+    impl<T: 'static> RDCType for Id<T> {}
+    impl<T> crate::targets::java::type_resolver::JavaType for Id<T> {
+        fn java_type() -> Type {
+            Type::new("String")
+        }
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Tagged<T, M> {
+        #[rdc(skip_bound)]
+        id: Id<M>,
+        value: T,
+        marker: std::marker::PhantomData<M>,
+    }
+
+    #[derive(RDC)]
+    #[rdc(bound = "T: RDCType + Default")]
+    #[allow(unused)]
+    struct Defaulted<T> {
+        value: T,
+    }
+
+    #[test]
+    fn inferred_bounds() {
+        let classes = rdc_java!(Tagged<i32, Opaque>, Defaulted<String>).unwrap();
+        let class = |name: &str| classes.iter().find(|c| c.name() == name).unwrap().code();
+        let tagged = class("TaggedInteger");
+        assert!(tagged.contains("private String id;"));
+        assert!(tagged.contains("private Integer value;"));
+        assert!(!tagged.contains("marker"));
+        assert!(class("DefaultedString").contains("private String value;"));
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Payee {
+        id: String,
+        owner: String,
+    }
+
+    #[test]
+    fn readonly_derived_field() {
+        let mut ir = IntermediateRepresentation::new(Java);
+        ir.add::<Payee>();
+        ir.set_field_readonly("Payee", "id", true).unwrap();
+        let classes = generate_java_code(&ir).unwrap();
+        let code = classes[0].code();
+        assert!(code.contains("public String getId()"));
+        assert!(!code.contains("setId"));
+        assert!(code.contains("public void setOwner(String owner)"));
+        let error = ir
+            .set_field_readonly("Payee", "balance", true)
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.message(),
+            "`Payee.balance` is not a struct field in the IR"
+        );
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Keywords {
+        class: String,
+        new: i32,
+        value: String,
+        r#type: String,
+    }
+
+    #[test]
+    fn date_time_format() {
+        let mut ir = IntermediateRepresentation::new(Java);
+        let mut struct_ir = Struct::new(Name::from_pascal_case("Event"), CustomType::new("Event"));
+        for name in ["created_at", "day"] {
+            struct_ir.add_field(Field::new(
+                Name::from_snake_case(name),
+                name,
+                Type::new("java.time.OffsetDateTime"),
+            ));
+        }
+        ir.add_struct(struct_ir);
+        ir.set_field_format(
+            "Event",
+            "created_at",
+            DateTimeFormat::new("yyyy-MM-dd'T'HH:mm:ssX").with_timezone("UTC"),
+        )
+        .unwrap();
+        ir.set_field_format("Event", "day", DateTimeFormat::new("yyyy-MM-dd"))
+            .unwrap();
+
+        let classes = generate_java_code(&ir).unwrap();
+        let code = classes[0].code();
+        assert!(code.contains("import com.fasterxml.jackson.annotation.JsonFormat;"));
+        assert!(code.contains("@JsonProperty(\"created_at\") @JsonFormat(shape = JsonFormat.Shape.STRING, pattern = \"yyyy-MM-dd\\'T\\'HH:mm:ssX\", timezone = \"UTC\") private OffsetDateTime createdAt;"));
+        assert!(code.contains(
+            "@JsonFormat(shape = JsonFormat.Shape.STRING, pattern = \"yyyy-MM-dd\") private OffsetDateTime day;"
+        ));
+        let error = ir
+            .set_field_format("Event", "updated_at", DateTimeFormat::new("yyyy"))
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.message(),
+            "`Event.updated_at` is not a struct field in the IR"
+        );
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Meeting {
+        #[rdc(format = "yyyy-MM-dd'T'HH:mm:ssX", timezone = "UTC")]
+        starts_at: String,
+        #[rdc(format = "yyyy-MM-dd")]
+        day: String,
+    }
+
+    #[test]
+    fn derived_date_time_format() {
+        let classes = rdc_java!(Meeting).unwrap();
+        let code = classes[0].code();
+        assert!(code.contains("@JsonFormat(shape = JsonFormat.Shape.STRING, pattern = \"yyyy-MM-dd\\'T\\'HH:mm:ssX\", timezone = \"UTC\") private String startsAt;"));
+        assert!(code.contains(
+            "@JsonFormat(shape = JsonFormat.Shape.STRING, pattern = \"yyyy-MM-dd\") private String day;"
+        ));
+    }
+
+    #[test]
+    fn escape_keyword_fields() {
+        let classes = rdc_java!(Keywords).unwrap();
+        let class = &classes[0];
+        let code = class.code();
+        assert!(code.contains("@JsonProperty(\"class\")"));
+        assert!(code.contains("private String class_;"));
+        assert!(code.contains("public String getClass_()"));
+        assert!(code.contains("public void setNew_(Integer new_)"));
+        assert!(code.contains("private String value;"));
+        assert!(code.contains("@JsonProperty(\"type\") private String type;"));
+        assert!(code.contains("public String getType()"));
+        assert_eq!(class.warnings().len(), 2);
+        assert!(class.warnings()[0].contains("`class_`"));
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Counters {
+        small: u8,
+        total: u64,
+        limit: Option<u64>,
+    }
+
+    #[test]
+    fn unsigned_long_as_big_integer() {
+        let classes = rdc_java!(Counters).unwrap();
+        let code = classes[0].code();
+        assert!(code.contains("private Short small;"));
+        assert!(code.contains("private BigInteger total;"));
+        assert!(code.contains("private BigInteger limit;"));
+        assert!(!code.contains("IllegalArgumentException"));
+    }
+
+    #[test]
+    fn unsigned_long_as_checked_long() {
+        let config = JavaConfig::new().with_unsigned_long_mapping(UnsignedLongMapping::Long);
+        let classes = rdc_java!(config = config; Counters).unwrap();
+        let code = classes[0].code();
+        assert!(code.contains("private Long total;"));
+        assert!(code.contains("if (total != null && total < 0)"));
+        assert!(code.contains("if (limit != null && limit < 0)"));
+        assert!(code.contains("\"total must not be negative\""));
+        assert!(!code.contains("small < 0"));
+    }
+
+    #[derive(RDC, Serialize)]
+    struct Empty {}
+
+    #[test]
+    fn generate_empty_class() {
+        let classes = rdc_java!(Empty).unwrap();
+        let code = classes[0].code();
+        assert!(code.contains("@JsonSerialize public class Empty"));
+        assert!(code.contains("public Empty() {}"));
+        assert!(!code.contains("JsonPropertyOrder"));
+        assert_eq!(serde_json::to_string(&Empty {}).unwrap(), "{}");
+    }
+
+    #[derive(RDC, Serialize)]
+    #[allow(unused)]
+    struct Ordered {
+        zeta: i32,
+        #[serde(rename = "alpha")]
+        middle: String,
+        beta: bool,
+    }
+
+    #[test]
+    fn property_order_matches_declaration() {
+        let classes = rdc_java!(Ordered).unwrap();
+        let code = classes[0].code();
+        assert!(code.contains("@JsonPropertyOrder({\"zeta\", \"alpha\", \"beta\"})"));
+    }
+
+    #[test]
+    fn field_constants() {
+        let config = JavaConfig::new().with_field_constants(true);
+        let classes = rdc_java!(config = config; Ordered).unwrap();
+        let code = classes[0].code();
+        println!("{code}");
+        assert!(code.contains("public static final class Fields {"));
+        assert!(code.contains("public static final String ZETA = \"zeta\";"));
+        assert!(code.contains("public static final String MIDDLE = \"alpha\";"));
+        assert!(code.contains("private Fields() {}"));
+        assert!(!rdc_java!(Ordered).unwrap()[0]
+            .code()
+            .contains("class Fields"));
+    }
+
+    #[test]
+    fn all_args_constructor() {
+        let config = JavaConfig::new()
+            .with_all_args_constructors(true)
+            .with_unsigned_long_mapping(UnsignedLongMapping::Long);
+        let classes = rdc_java!(config = config; Ordered, Counters, Empty).unwrap();
+        let code = classes[0].code();
+        assert!(code.contains("import com.fasterxml.jackson.annotation.JsonCreator;"));
+        assert!(code.contains(
+            "@JsonCreator public Ordered(@JsonProperty(\"zeta\") Integer zeta, @JsonProperty(\"alpha\") String middle, @JsonProperty(\"beta\") Boolean beta) { this.zeta = zeta;this.middle = middle;this.beta = beta; }"
+        ));
+        let code = classes[1].code();
+        assert_eq!(code.matches("if (total != null && total < 0)").count(), 2);
+        let code = classes[2].code();
+        assert!(!code.contains("JsonCreator"));
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Account {
+        total: i32,
+        paid: Option<bool>,
+        is_active: bool,
+        hash_code: i32,
+    }
+
+    fn accessors(style: AccessorStyle) -> (String, Vec<String>) {
+        let config = JavaConfig::new().with_accessor_style(style);
+        let classes = rdc_java!(config = config; Account).unwrap();
+        (
+            classes[0].code().to_string(),
+            classes[0].warnings().to_vec(),
+        )
+    }
+
+    #[test]
+    fn accessor_styles() {
+        let (code, _) = accessors(AccessorStyle::Bean);
+        assert!(code.contains("public Boolean getPaid()"));
+        assert!(code.contains("public Boolean getIsActive()"));
+        assert!(code.contains("public void setIsActive(Boolean isActive)"));
+
+        let (code, _) = accessors(AccessorStyle::BooleanIs);
+        assert!(code.contains("public Integer getTotal()"));
+        assert!(code.contains("public Boolean isPaid()"));
+        assert!(code.contains("public Boolean isIsActive()"));
+        assert!(code.contains("public void setPaid(Boolean paid)"));
+
+        let (code, warnings) = accessors(AccessorStyle::Record);
+        assert!(code.contains("public Integer total() { return total; }"));
+        assert!(code.contains(
+            "@JsonProperty(\"total\") public void total(Integer total) { this.total = total; }"
+        ));
+        assert!(code.contains("public Integer getHashCode()"));
+        assert!(code.contains("public void setHashCode(Integer hashCode)"));
+        assert_eq!(
+            warnings,
+            ["`hashCode()` would clash with another method, so the accessors of `hashCode` have JavaBeans names"]
+        );
+
+        let (code, _) = accessors(AccessorStyle::Kotlin);
+        assert!(code.contains("public Boolean getPaid()"));
+        assert!(code.contains("public Boolean isActive() { return isActive; }"));
+        assert!(
+            code.contains("@JsonProperty(\"is_active\") public void setActive(Boolean isActive)")
+        );
+    }
+
     #[derive(RDC, Serialize, Deserialize)]
     struct A {
         a: i32,
@@ -105,6 +698,7 @@ mod tests {
     }
 
     #[derive(RDC, Serialize, Deserialize)]
+    #[allow(unused)]
     struct C<X>
     where
         X: RDCType,
@@ -155,4 +749,59 @@ mod tests {
             Err(e) => panic!("{}", e.message()),
         }
     }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Profile {
+        name: String,
+        extra: std::collections::HashMap<String, String>,
+        tags: Vec<String>,
+    }
+
+    #[test]
+    fn extra_properties() {
+        let mut ir = IntermediateRepresentation::new(Java);
+        ir.add::<Profile>();
+        let config = JavaConfig::new()
+            .with_fixtures(true)
+            .with_extra_properties("Profile", "extra")
+            .with_extra_properties("Profile", "tags");
+        let classes = generate_java_code_with_config(&ir, &config).unwrap();
+        let profile = classes.iter().find(|c| c.name() == "Profile").unwrap();
+        let code = profile.code();
+        println!("{code}");
+        assert!(code.contains("import com.fasterxml.jackson.annotation.JsonAnyGetter;"));
+        assert!(code.contains("@JsonPropertyOrder({\"name\", \"tags\"})"));
+        assert!(code.contains("private Map<String, String> extra = new LinkedHashMap<>();"));
+        assert!(
+            code.contains("@JsonAnyGetter public Map<String, String> getExtra() { return extra; }")
+        );
+        assert!(code.contains(
+            "@JsonAnySetter public void setExtra(String key, String value) { this.extra.put(key, value); }"
+        ));
+        assert_eq!(
+            profile.warnings(),
+            ["`Profile.tags` is not a map with `String` keys, so it can not collect extra properties"]
+        );
+        let fixtures = classes
+            .iter()
+            .find(|c| c.name() == "ProfileFixtures")
+            .unwrap();
+        assert!(!fixtures.code().contains("setExtra"));
+    }
+
+    #[test]
+    fn case_insensitive_properties() {
+        let mut ir = IntermediateRepresentation::new(Java);
+        ir.add::<Profile>();
+        let config = JavaConfig::new().with_case_insensitive_properties(true);
+        let classes = generate_java_code_with_config(&ir, &config).unwrap();
+        let code = classes[0].code();
+        println!("{code}");
+        assert!(code.contains("import com.fasterxml.jackson.annotation.JsonFormat;"));
+        assert!(code.contains(
+            "@JsonFormat(with = JsonFormat.Feature.ACCEPT_CASE_INSENSITIVE_PROPERTIES) public class Profile"
+        ));
+        assert!(code.contains("@JsonProperty(\"name\") private String name;"));
+    }
 }
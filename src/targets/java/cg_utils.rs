@@ -1,8 +1,13 @@
+use crate::targets::java::context::JavaContext;
 use genco::prelude::*;
 use genco::quote;
 
+/// This is an annotation together with the import it requires.
+pub type Annotation = (&'static str, java::Tokens);
+
 pub trait Compact<T> {
     fn compact(&self) -> T;
+    #[allow(dead_code)]
     fn join(&self, sep: &str) -> T;
 }
 
@@ -18,6 +23,33 @@ impl Compact<java::Tokens> for Vec<java::Tokens> {
     }
 }
 
+/// Generates `@JsonPropertyOrder` annotation, so that Jackson writes properties in the same order as serde.
+pub fn property_order<I, S>(json_names: I) -> java::Tokens
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let names = json_names.into_iter().map(|n| n.as_ref().to_string());
+    quote!(@JsonPropertyOrder({$(for n in names join (, ) => $[str]($[const](n)))}))
+}
+
+/// Returns `@JsonFormat` matching properties regardless of case when `with_case_insensitive_properties` is enabled.
+pub fn case_insensitive_annotation(ctx: &JavaContext) -> Option<Annotation> {
+    ctx.config().case_insensitive_properties().then(|| {
+        (
+            "com.fasterxml.jackson.annotation.JsonFormat",
+            quote!(@JsonFormat(with = JsonFormat.Feature.ACCEPT_CASE_INSENSITIVE_PROPERTIES)),
+        )
+    })
+}
+
+/// Returns the case-insensitive `@JsonFormat` with a qualified name, for classes whose imports are fixed.
+pub fn qualified_case_insensitive_annotation(ctx: &JavaContext) -> Option<java::Tokens> {
+    ctx.config().case_insensitive_properties().then(|| {
+        quote!(@com.fasterxml.jackson.annotation.JsonFormat(with = com.fasterxml.jackson.annotation.JsonFormat.Feature.ACCEPT_CASE_INSENSITIVE_PROPERTIES))
+    })
+}
+
 #[macro_export]
 macro_rules! quote_iter {
     ($val:expr => $t:expr) => {{
@@ -0,0 +1,72 @@
+use crate::errors::Error;
+use crate::ir::{Field, Struct};
+use crate::targets::java::cg_utils::Annotation;
+use crate::targets::java::context::JavaContext;
+use crate::targets::java::JavaClass;
+use genco::prelude::*;
+use genco::quote;
+
+/// Returns the `@JsonView` annotation of a field assigned to a view.
+pub fn field_annotations(field: &Field, owner: &Struct, ctx: &JavaContext) -> Vec<Annotation> {
+    let type_name = owner.self_type().type_name();
+    let field_name = field.name().as_snake_case();
+    match ctx.config().json_view(type_name, &field_name) {
+        Some(view) => vec![(
+            "com.fasterxml.jackson.annotation.JsonView",
+            quote!(@JsonView(Views.$view.class)),
+        )],
+        None => Vec::new(),
+    }
+}
+
+/// This function generates the `Views` class with a marker class for every view used by the fields.
+/// Fields without a view are serialized in every view, as Jackson includes them by default.
+pub fn generate_views_class(ctx: &JavaContext) -> Result<JavaClass, Error> {
+    let views = ctx.config().json_views();
+    let tokens: java::Tokens = quote!(
+        public final class Views {
+            $(for v in views join ($['\n']) => public static class $v {})
+
+            private Views() {}
+        }
+    );
+    JavaClass::from_tokens("Views".to_string(), tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::targets::java::JavaConfig;
+    use crate::{rdc_java, RDC};
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Account {
+        name: String,
+        email: String,
+        password_hash: String,
+    }
+
+    #[test]
+    fn json_views() {
+        let config = JavaConfig::new()
+            .with_json_view("Account", "name", "Public")
+            .with_json_view("Account", "email", "Internal")
+            .with_json_view("Account", "password_hash", "Internal");
+        let classes = rdc_java!(config = config; Account).unwrap();
+        let class = |name: &str| classes.iter().find(|c| c.name() == name).unwrap().code();
+        let views = class("Views");
+        println!("{views}");
+        assert!(views.contains("public static class Public {}"));
+        assert!(views.contains("public static class Internal {}"));
+        let account = class("Account");
+        println!("{account}");
+        assert!(account.contains("import com.fasterxml.jackson.annotation.JsonView;"));
+        assert!(account.contains("@JsonView(Views.Public.class) private String name;"));
+        assert!(account.contains("@JsonView(Views.Internal.class) private String passwordHash;"));
+        assert!(rdc_java!(Account)
+            .unwrap()
+            .iter()
+            .all(|c| c.name() != "Views"));
+    }
+}
@@ -0,0 +1,127 @@
+use crate::ir::{Field, Struct, Type, TypeKind};
+use crate::targets::java::cg_utils::Annotation;
+use crate::targets::java::context::JavaContext;
+use genco::quote;
+
+/// Returns jackson-dataformat-xml annotations of a field when XML is enabled.
+/// Lists are written as repeated elements without a wrapper, like serde does.
+pub fn field_annotations(field: &Field, owner: &Struct, ctx: &JavaContext) -> Vec<Annotation> {
+    let mut annotations = Vec::new();
+    if !ctx.config().xml() {
+        return annotations;
+    }
+    let json_name = field.json_name();
+    let field_type = field.field_type();
+    let type_name = owner.self_type().type_name();
+    let field_name = field.name().as_snake_case();
+    let mut attribute = ctx.config().is_xml_attribute(type_name, &field_name);
+    if attribute && !is_scalar(field_type, ctx) {
+        ctx.warn(format!(
+            "`{type_name}.{field_name}` is not a scalar, so it is written as an XML element instead of an attribute"
+        ));
+        attribute = false;
+    }
+    let property = if attribute {
+        quote!(@JacksonXmlProperty(localName = $[str]($[const](json_name)), isAttribute = true))
+    } else {
+        quote!(@JacksonXmlProperty(localName = $[str]($[const](json_name))))
+    };
+    annotations.push((
+        "com.fasterxml.jackson.dataformat.xml.annotation.JacksonXmlProperty",
+        property,
+    ));
+    if is_list(field_type) {
+        annotations.push((
+            "com.fasterxml.jackson.dataformat.xml.annotation.JacksonXmlElementWrapper",
+            quote!(@JacksonXmlElementWrapper(useWrapping = false)),
+        ));
+    }
+    annotations
+}
+
+/// Returns the root element annotation of a class when XML is enabled.
+/// The element is named after the Rust type, so it does not change with class prefixes and suffixes.
+pub fn class_annotations(dc: &Struct, ctx: &JavaContext) -> Vec<Annotation> {
+    if !ctx.config().xml() {
+        return Vec::new();
+    }
+    let name = dc.self_type().type_name();
+    vec![(
+        "com.fasterxml.jackson.dataformat.xml.annotation.JacksonXmlRootElement",
+        quote!(@JacksonXmlRootElement(localName = $[str]($[const](name)))),
+    )]
+}
+
+fn is_list(t: &Type) -> bool {
+    match t.kind() {
+        TypeKind::List(_) => true,
+        TypeKind::Option(inner) => is_list(inner),
+        _ => false,
+    }
+}
+
+fn is_scalar(t: &Type, ctx: &JavaContext) -> bool {
+    match t.kind() {
+        TypeKind::Named => ctx.class_kind(t).is_none(),
+        TypeKind::UnsignedLong => true,
+        TypeKind::Option(inner) => is_scalar(inner, ctx),
+        TypeKind::List(_) | TypeKind::Map(_, _) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::targets::java::JavaConfig;
+    use crate::{rdc_java, RDC};
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Order {
+        order_id: String,
+        #[serde(rename = "line")]
+        lines: Vec<OrderLine>,
+        customer: Customer,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct OrderLine {
+        sku: String,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Customer {
+        name: String,
+    }
+
+    #[test]
+    fn xml_annotations() {
+        let config = JavaConfig::new()
+            .with_xml(true)
+            .with_class_suffix("Dto")
+            .with_xml_attribute("Order", "order_id")
+            .with_xml_attribute("Order", "customer");
+        let classes = rdc_java!(config = config; Order).unwrap();
+        let order = classes.iter().find(|c| c.name() == "OrderDto").unwrap();
+        let code = order.code();
+        println!("{code}");
+        assert!(code.contains(
+            "import com.fasterxml.jackson.dataformat.xml.annotation.JacksonXmlRootElement;"
+        ));
+        assert!(code.contains("@JacksonXmlRootElement(localName = \"Order\")"));
+        assert!(code.contains("@JacksonXmlProperty(localName = \"order_id\", isAttribute = true)"));
+        assert!(code.contains("@JacksonXmlProperty(localName = \"line\")"));
+        assert!(code.contains("@JacksonXmlElementWrapper(useWrapping = false)"));
+        assert!(code.contains("@JacksonXmlProperty(localName = \"customer\")"));
+        assert_eq!(order.warnings().len(), 1);
+        assert!(order.warnings()[0].contains("`Order.customer` is not a scalar"));
+    }
+
+    #[test]
+    fn xml_disabled_by_default() {
+        let classes = rdc_java!(Order).unwrap();
+        assert!(classes.iter().all(|c| !c.code().contains("JacksonXml")));
+    }
+}
@@ -0,0 +1,839 @@
+use crate::errors::Error;
+use crate::ir::IntermediateRepresentation;
+use std::collections::{HashMap, HashSet};
+
+/// This struct contains options that control Java code generation.
+///
+/// Example:
+/// ```rust
+/// use rdc::targets::java::JavaConfig;
+///
+/// let config = JavaConfig::new().with_class_suffix("Dto");
+/// assert_eq!(config.class_suffix(), "Dto");
+/// ```
+#[derive(Default)]
+pub struct JavaConfig {
+    class_prefix: String,
+    class_suffix: String,
+    unsigned_long_mapping: UnsignedLongMapping,
+    flavor: JavaFlavor,
+    parcelable: bool,
+    configuration_properties: HashMap<String, String>,
+    http_client: HttpClientStyle,
+    xml: bool,
+    xml_attributes: HashSet<(String, String)>,
+    csv: CsvMapping,
+    fixtures: bool,
+    assertions: bool,
+    rdc_json: bool,
+    deny_unknown_fields: HashSet<String>,
+    case_insensitive_properties: bool,
+    compatibility_snapshot: Option<String>,
+    field_constants: bool,
+    acknowledged_changes: Vec<String>,
+    json_views: Vec<(String, String, String)>,
+    builders: bool,
+    json_schema_constants: bool,
+    module_packages: Vec<(String, String)>,
+    common_fields: bool,
+    schema_annotations: bool,
+    schema_fingerprints: bool,
+    type_descriptions: HashMap<String, String>,
+    field_descriptions: HashMap<(String, String), String>,
+    concrete_maps: bool,
+    extra_properties: HashSet<(String, String)>,
+    version: Option<String>,
+    error_types: HashSet<String>,
+    error_messages: HashMap<(String, String), String>,
+    error_exceptions: bool,
+    json_filters: bool,
+    empty_factories: bool,
+    view_interfaces: bool,
+    data_enum_encoding: DataEnumEncoding,
+    shared_interfaces: Vec<(String, Vec<String>)>,
+    all_args_constructors: bool,
+    accessor_style: AccessorStyle,
+    nullable_annotation: Option<NullableAnnotation>,
+    getter_javadoc: bool,
+    display_name_bundle: Option<String>,
+    unknown_variants: bool,
+}
+
+/// This enum describes which CSV library the generated classes are mapped for.
+/// Only structs with scalar fields can be mapped, other structs are reported with a warning.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CsvMapping {
+    /// No CSV mapping.
+    #[default]
+    None,
+    /// OpenCSV `@CsvBindByName` annotations with a `CSV_COLUMNS` constant holding the column order.
+    OpenCsv,
+    /// A `csvSchema()` method building a jackson-dataformat-csv `CsvSchema` with the columns in declaration order.
+    JacksonCsv,
+}
+
+/// This enum describes which HTTP client library the interfaces generated for services use.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HttpClientStyle {
+    /// Retrofit interfaces returning `retrofit2.Call`.
+    #[default]
+    Retrofit,
+    /// OpenFeign interfaces annotated with `@RequestLine`.
+    Feign,
+}
+
+/// This enum describes which framework the generated classes are tailored for.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JavaFlavor {
+    /// Plain Jackson DTOs without any framework specific annotations.
+    #[default]
+    Plain,
+    /// Jackson DTOs with Bean Validation annotations, ready to be used with `@Validated` in Spring Boot.
+    /// Required fields are annotated with `@NotNull` and nested generated classes with `@Valid`.
+    Spring,
+}
+
+/// This enum describes how data enums are represented in Java.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataEnumEncoding {
+    /// A single class holding the variant and its value, with `of<Variant>` factories and getters of the values.
+    #[default]
+    Class,
+    /// A sealed interface with a `record` per variant, to be used with pattern matching. It requires Java 17 or newer.
+    /// Tuple variants become records with `arg0..argN` components, and unit variants records without components.
+    SealedRecords,
+}
+
+/// This enum describes how getters and setters of the generated classes are named.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccessorStyle {
+    /// JavaBeans accessors, e.g. `getTotal()` and `setTotal(total)`.
+    #[default]
+    Bean,
+    /// JavaBeans accessors, except that getters of `Boolean` properties start with `is`, e.g. `isPaid()`.
+    BooleanIs,
+    /// Accessors named like the properties, e.g. `total()` and `total(total)`, like the accessors of records.
+    Record,
+    /// JavaBeans accessors that Kotlin sees as properties named like the fields. `Boolean` fields starting with `is`,
+    /// e.g. `is_active`, get `isActive()` and `setActive(isActive)`, like Kotlin compiles such properties.
+    Kotlin,
+}
+
+/// This enum lists the `@Nullable` annotations that can mark optional properties.
+/// Only declaration annotations are supported, because type-use ones, like JSpecify's,
+/// can not annotate the qualified names used for collections, e.g. `java.util.List`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NullableAnnotation {
+    /// `jakarta.annotation.Nullable` from Jakarta Annotations.
+    Jakarta,
+    /// `javax.annotation.Nullable` from JSR-305, e.g. as provided by `com.google.code.findbugs:jsr305`.
+    Jsr305,
+    /// `org.jetbrains.annotations.Nullable` from JetBrains annotations.
+    JetBrains,
+}
+
+impl NullableAnnotation {
+    pub fn qualified_name(&self) -> &'static str {
+        match self {
+            NullableAnnotation::Jakarta => "jakarta.annotation.Nullable",
+            NullableAnnotation::Jsr305 => "javax.annotation.Nullable",
+            NullableAnnotation::JetBrains => "org.jetbrains.annotations.Nullable",
+        }
+    }
+}
+
+/// This enum describes how `u64` values are represented in Java.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnsignedLongMapping {
+    /// `java.math.BigInteger`, which can hold every `u64` value.
+    #[default]
+    BigInteger,
+    /// `Long`, with setters rejecting negative values.
+    /// Values above `i64::MAX` can not be represented and fail to deserialize.
+    Long,
+}
+
+impl JavaConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a prefix that is added to names of all generated classes.
+    pub fn with_class_prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.class_prefix = prefix.into();
+        self
+    }
+
+    /// Sets a suffix that is added to names of all generated classes.
+    pub fn with_class_suffix<S: Into<String>>(mut self, suffix: S) -> Self {
+        self.class_suffix = suffix.into();
+        self
+    }
+
+    /// Sets the Java type used for `u64` values.
+    pub fn with_unsigned_long_mapping(mut self, mapping: UnsignedLongMapping) -> Self {
+        self.unsigned_long_mapping = mapping;
+        self
+    }
+
+    /// Sets the framework flavor of the generated classes.
+    pub fn with_flavor(mut self, flavor: JavaFlavor) -> Self {
+        self.flavor = flavor;
+        self
+    }
+
+    /// Binds the class generated for the given type to Spring configuration properties with the given prefix.
+    /// The type name is the name before applying class prefix and suffix, e.g. `ServerConfig`.
+    /// It is only used with `JavaFlavor::Spring`.
+    pub fn with_configuration_properties<S: Into<String>, P: Into<String>>(
+        mut self,
+        type_name: S,
+        prefix: P,
+    ) -> Self {
+        self.configuration_properties
+            .insert(type_name.into(), prefix.into());
+        self
+    }
+
+    /// Makes generated classes implement Android `Parcelable` in addition to Jackson annotations.
+    pub fn with_parcelable(mut self, parcelable: bool) -> Self {
+        self.parcelable = parcelable;
+        self
+    }
+
+    /// Sets the HTTP client library used by the interfaces generated for services.
+    pub fn with_http_client(mut self, style: HttpClientStyle) -> Self {
+        self.http_client = style;
+        self
+    }
+
+    /// Adds jackson-dataformat-xml annotations, so that the generated classes can be read from and written to XML.
+    /// Fields are written as elements, unless they are marked with `with_xml_attribute`.
+    pub fn with_xml(mut self, xml: bool) -> Self {
+        self.xml = xml;
+        self
+    }
+
+    /// Writes the given field of the given type as an XML attribute instead of an element.
+    /// Both names are Rust names, e.g. `("Order", "order_id")`.
+    pub fn with_xml_attribute<S: Into<String>, F: Into<String>>(
+        mut self,
+        type_name: S,
+        field_name: F,
+    ) -> Self {
+        self.xml_attributes
+            .insert((type_name.into(), field_name.into()));
+        self
+    }
+
+    /// Assigns the given field of the given type to a Jackson view, annotating it with `@JsonView(Views.<view>.class)`.
+    /// Both names are Rust names, e.g. `("Account", "email", "Internal")`, and a `Views` class is generated with all the views.
+    pub fn with_json_view<S: Into<String>, F: Into<String>, V: Into<String>>(
+        mut self,
+        type_name: S,
+        field_name: F,
+        view: V,
+    ) -> Self {
+        self.json_views
+            .push((type_name.into(), field_name.into(), view.into()));
+        self
+    }
+
+    /// Maps structs with only scalar fields to CSV rows with the given library.
+    /// Columns are named after the JSON names of the fields and ordered like the Rust fields.
+    pub fn with_csv(mut self, csv: CsvMapping) -> Self {
+        self.csv = csv;
+        self
+    }
+
+    /// Generates a `<Class>Fixtures` class for every struct, creating instances populated with placeholder data for tests.
+    pub fn with_fixtures(mut self, fixtures: bool) -> Self {
+        self.fixtures = fixtures;
+        self
+    }
+
+    /// Generates an AssertJ `<Class>Assert` class for every struct, with a `has<Field>` assertion for every field.
+    pub fn with_assertions(mut self, assertions: bool) -> Self {
+        self.assertions = assertions;
+        self
+    }
+
+    /// Generates an `RdcJson` class building an `ObjectMapper` configured the way the generated classes expect.
+    pub fn with_rdc_json(mut self, rdc_json: bool) -> Self {
+        self.rdc_json = rdc_json;
+        self
+    }
+
+    /// Marks a type as using `#[serde(deny_unknown_fields)]`, which the derive macro does not see.
+    /// When any type is marked, the `RdcJson` mapper fails on unknown properties
+    /// and all the other classes are annotated to ignore them, like serde does by default.
+    pub fn with_deny_unknown_fields<S: Into<String>>(mut self, type_name: S) -> Self {
+        self.deny_unknown_fields.insert(type_name.into());
+        self
+    }
+
+    /// Annotates data classes and classes of data enum variants to match JSON property names regardless of case when reading,
+    /// e.g. to accept `PascalCase` keys from legacy Java producers, and data enums match variant keys the same way.
+    /// Properties are still written with their serde names.
+    /// The `@JsonFormat` annotation works with any `ObjectMapper`, while the `RdcJson` mapper also enables
+    /// `MapperFeature.ACCEPT_CASE_INSENSITIVE_PROPERTIES` for classes that are not generated.
+    pub fn with_case_insensitive_properties(mut self, case_insensitive_properties: bool) -> Self {
+        self.case_insensitive_properties = case_insensitive_properties;
+        self
+    }
+
+    /// Marks a map field as a `#[serde(flatten)]` catch-all for unknown properties, which the derive macro does not see,
+    /// e.g. `extra: HashMap<String, serde_json::Value>`. The field name is in snake case, like in Rust.
+    /// The field is annotated with `@JsonAnyGetter` and `@JsonAnySetter`, so unknown properties round-trip through Java.
+    /// Only maps with `String` keys can collect properties, other fields are reported with a warning.
+    pub fn with_extra_properties<S: Into<String>, F: Into<String>>(
+        mut self,
+        type_name: S,
+        field_name: F,
+    ) -> Self {
+        self.extra_properties
+            .insert((type_name.into(), field_name.into()));
+        self
+    }
+
+    /// Marks an enum as an error type, e.g. a `thiserror` enum returned by an API, which the derive macro does not see.
+    /// Its class gets a `getMessage()` method, and data enums also `getCode()` and `getDetail()` methods
+    /// returning the variant and its data.
+    pub fn with_error_type<S: Into<String>>(mut self, type_name: S) -> Self {
+        self.error_types.insert(type_name.into());
+        self
+    }
+
+    /// Sets the message of a variant of an error type, in the format of `#[error(...)]`, e.g. `("ApiError", "NotFound", "user {0} not found")`.
+    /// Placeholders refer to tuple fields by index and to struct fields by name. Variants without a message use their JSON name.
+    pub fn with_error_message<S: Into<String>, V: Into<String>, M: Into<String>>(
+        mut self,
+        type_name: S,
+        variant_name: V,
+        message: M,
+    ) -> Self {
+        self.error_messages
+            .insert((type_name.into(), variant_name.into()), message.into());
+        self
+    }
+
+    /// Generates a `{Class}Exception` extending `RuntimeException` for every error type, carrying the error as its DTO.
+    pub fn with_error_exceptions(mut self, error_exceptions: bool) -> Self {
+        self.error_exceptions = error_exceptions;
+        self
+    }
+
+    /// Places classes of types declared in the given Rust module, or its submodules, in the given Java package,
+    /// e.g. `("my_crate::billing", "com.example.billing")`. The most specific module wins,
+    /// and classes of other types are written to the package passed to `write_java`.
+    pub fn with_module_package<M: Into<String>, P: Into<String>>(
+        mut self,
+        module: M,
+        package: P,
+    ) -> Self {
+        self.module_packages.push((module.into(), package.into()));
+        self
+    }
+
+    /// Marks all the generated classes with an API version, e.g. `v2`, which `write_java` appends to their packages,
+    /// e.g. `com.example.v2`. Generating each version of the types with its own version lets old and new versions
+    /// of the classes coexist, e.g. during migrations.
+    pub fn with_version<S: Into<String>>(mut self, version: S) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Adds a `JSON_SCHEMA` constant to every generated class, holding the JSON Schema of its type in a text block,
+    /// e.g. for runtime validation. Text blocks require Java 15 or newer.
+    pub fn with_json_schema_constants(mut self, json_schema_constants: bool) -> Self {
+        self.json_schema_constants = json_schema_constants;
+        self
+    }
+
+    /// Generates a nested `Builder` class in every data class.
+    /// Its `build()` method throws an `IllegalStateException` when a field that is not an `Option` was not set.
+    pub fn with_builders(mut self, builders: bool) -> Self {
+        self.builders = builders;
+        self
+    }
+
+    /// Adds a `SCHEMA_FINGERPRINT` constant to every generated class, holding the fingerprint of the JSON shape of its type
+    /// as returned by `IntermediateRepresentation::fingerprint`, e.g. to detect mismatched deployments at handshake time.
+    pub fn with_schema_fingerprints(mut self, schema_fingerprints: bool) -> Self {
+        self.schema_fingerprints = schema_fingerprints;
+        self
+    }
+
+    /// Annotates data classes and their fields with swagger `@Schema` annotations,
+    /// so that OpenAPI documents generated by springdoc describe the types like the Rust model.
+    /// Fields that are not an `Option` are marked as required.
+    pub fn with_schema_annotations(mut self, schema_annotations: bool) -> Self {
+        self.schema_annotations = schema_annotations;
+        self
+    }
+
+    /// Sets the description of a type in `@Schema` annotations.
+    /// Doc comments are not available to the derive macro, so descriptions are provided here.
+    pub fn with_type_description<S: Into<String>, D: Into<String>>(
+        mut self,
+        type_name: S,
+        description: D,
+    ) -> Self {
+        self.type_descriptions
+            .insert(type_name.into(), description.into());
+        self
+    }
+
+    /// Sets the description of a struct field in `@Schema` annotations, e.g. `("Invoice", "due_date", "...")`.
+    pub fn with_field_description<S: Into<String>, F: Into<String>, D: Into<String>>(
+        mut self,
+        type_name: S,
+        field_name: F,
+        description: D,
+    ) -> Self {
+        self.field_descriptions
+            .insert((type_name.into(), field_name.into()), description.into());
+        self
+    }
+
+    /// Lifts fields shared by all struct variants of a data enum into a nested abstract `Base` class,
+    /// which the variant classes extend. The data enum class gets getters of the shared fields,
+    /// so that they can be read without checking the variant. With `DataEnumEncoding::SealedRecords`,
+    /// the sealed interface declares accessors of the shared fields, which the records of the struct variants implement.
+    pub fn with_common_fields(mut self, common_fields: bool) -> Self {
+        self.common_fields = common_fields;
+        self
+    }
+
+    /// Uses concrete map classes instead of `java.util.Map`: `java.util.EnumMap` for maps with generated enum keys,
+    /// `java.util.TreeMap` for `BTreeMap`s and `java.util.LinkedHashMap` for other maps.
+    /// Enums used as map keys get nested `KeySerializer` and `KeyDeserializer` classes,
+    /// so that their keys are written with their JSON names.
+    pub fn with_concrete_maps(mut self, concrete_maps: bool) -> Self {
+        self.concrete_maps = concrete_maps;
+        self
+    }
+
+    /// Annotates data classes with `@JsonFilter`, using their class names as filter ids, and generates a `JsonFilters` class
+    /// building `FilterProvider`s, so that responses can be limited to some fields, e.g. for sparse fieldsets.
+    /// Every `ObjectMapper` serializing the classes needs a filter provider, which `RdcJson` sets.
+    pub fn with_json_filters(mut self, json_filters: bool) -> Self {
+        self.json_filters = json_filters;
+        self
+    }
+
+    /// Generates a static `empty()` factory in every data class, returning a null object with empty collections,
+    /// zeros, empty strings and nested data classes created with their own `empty()` factories.
+    pub fn with_empty_factories(mut self, empty_factories: bool) -> Self {
+        self.empty_factories = empty_factories;
+        self
+    }
+
+    /// Generates a read-only interface with the getters of every data class, e.g. `InvoiceView` for `Invoice`,
+    /// which the class implements. Java APIs can then expose the interfaces and keep the mutable classes internal.
+    /// Data enums get an interface too when they are generated as classes, e.g. `ShapeView` for `Shape`.
+    pub fn with_view_interfaces(mut self, view_interfaces: bool) -> Self {
+        self.view_interfaces = view_interfaces;
+        self
+    }
+
+    /// Generates an interface with getters of the given fields, e.g. `Auditable` with `id` and `created_at`,
+    /// which every data class having all the fields implements, so that generic Java code can handle them.
+    /// Field names are Rust names, and `IntermediateRepresentation::shared_fields` finds candidate sets of fields.
+    /// Types of the getters come from the first struct having all the fields,
+    /// and structs with fields of other types do not implement the interface.
+    /// Structs can also name the interfaces they implement with `#[rdc(implements_shared = "...")]`.
+    pub fn with_shared_interface<S, I, T>(mut self, name: S, fields: I) -> Self
+    where
+        S: Into<String>,
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.shared_interfaces
+            .push((name.into(), fields.into_iter().map(|f| f.into()).collect()));
+        self
+    }
+
+    /// Generates a `@JsonCreator` constructor taking every field in declaration order besides the no-arg one,
+    /// so that Java code can construct data classes in one expression and Jackson binds them through the constructor.
+    /// A map collecting extra properties is not a parameter, and classes without fields get no such constructor.
+    pub fn with_all_args_constructors(mut self, all_args_constructors: bool) -> Self {
+        self.all_args_constructors = all_args_constructors;
+        self
+    }
+
+    /// Sets how getters and setters are named, both in data classes and in data enums.
+    /// Accessors that would clash with methods of `Object` or generated static methods fall back to JavaBeans names.
+    /// Records of `DataEnumEncoding::SealedRecords` always have record accessors.
+    pub fn with_accessor_style(mut self, style: AccessorStyle) -> Self {
+        self.accessor_style = style;
+        self
+    }
+
+    /// Marks optional fields of data classes and their getters with the given `@Nullable` annotation.
+    /// Fields are optional when `Field::is_required` is false, the same flag that drives `required` in schemas.
+    pub fn with_nullable_annotation(mut self, annotation: NullableAnnotation) -> Self {
+        self.nullable_annotation = Some(annotation);
+        self
+    }
+
+    /// Generates Javadoc for getters of data classes, with the field description, if any,
+    /// and a `@return` tag saying whether the property is required or may be `null`.
+    pub fn with_getter_javadoc(mut self, getter_javadoc: bool) -> Self {
+        self.getter_javadoc = getter_javadoc;
+        self
+    }
+
+    /// Generates a `displayName(Locale)` method in every enum, reading the label of the constant from the resource bundle
+    /// with the given base name, e.g. `messages`, as generated with `targets::properties`.
+    pub fn with_display_name_bundle<S: Into<String>>(mut self, bundle_name: S) -> Self {
+        self.display_name_bundle = Some(bundle_name.into());
+        self
+    }
+
+    /// Makes data enum deserializers keep variants they do not know in an `UNKNOWN` variant holding the raw `JsonNode`,
+    /// which is serialized back unchanged, instead of failing. Services forwarding messages then pass on variants
+    /// added on the Rust side before they were upgraded. With `DataEnumEncoding::SealedRecords`,
+    /// unknown variants become an `Unknown` record holding the `JsonNode`.
+    pub fn with_unknown_variants(mut self, unknown_variants: bool) -> Self {
+        self.unknown_variants = unknown_variants;
+        self
+    }
+
+    /// Sets how data enums are represented in Java.
+    pub fn with_data_enum_encoding(mut self, encoding: DataEnumEncoding) -> Self {
+        self.data_enum_encoding = encoding;
+        self
+    }
+
+    /// Generates a nested `Fields` class in every data class, with a constant holding the JSON name of every field,
+    /// e.g. `Invoice.Fields.CREATED_AT`.
+    pub fn with_field_constants(mut self, field_constants: bool) -> Self {
+        self.field_constants = field_constants;
+        self
+    }
+
+    /// Makes generation fail if the IR introduces breaking changes compared to the snapshot in the file,
+    /// written earlier with `IntermediateRepresentation::write_snapshot`.
+    /// The check is skipped when the file does not exist yet.
+    pub fn with_compatibility_snapshot<S: Into<String>>(mut self, file_path: S) -> Self {
+        self.compatibility_snapshot = Some(file_path.into());
+        self
+    }
+
+    /// Acknowledges a breaking change by its subject, e.g. `Invoice.total` or `Status::Paid`,
+    /// so that it does not fail the compatibility check.
+    pub fn with_acknowledged_change<S: Into<String>>(mut self, subject: S) -> Self {
+        self.acknowledged_changes.push(subject.into());
+        self
+    }
+
+    pub fn class_prefix(&self) -> &str {
+        &self.class_prefix
+    }
+
+    pub fn class_suffix(&self) -> &str {
+        &self.class_suffix
+    }
+
+    pub fn unsigned_long_mapping(&self) -> UnsignedLongMapping {
+        self.unsigned_long_mapping
+    }
+
+    pub fn flavor(&self) -> JavaFlavor {
+        self.flavor
+    }
+
+    pub fn parcelable(&self) -> bool {
+        self.parcelable
+    }
+
+    /// Returns an error listing the options that name a type, field or variant which is not in the IR,
+    /// e.g. because of a typo, since such options would otherwise be silently ignored.
+    /// Names are compared like the options are looked up, i.e. type names before applying class prefix and suffix,
+    /// field names in snake case and variant names in pascal case.
+    pub(crate) fn check_names(&self, ir: &IntermediateRepresentation) -> Result<(), Error> {
+        let has_type = |type_name: &str| {
+            ir.structs()
+                .iter()
+                .map(|s| s.self_type())
+                .chain(ir.enums().iter().map(|e| e.self_type()))
+                .chain(ir.data_enums().iter().map(|de| de.self_type()))
+                .chain(ir.polymorphics().iter().map(|p| p.self_type()))
+                .any(|t| t.type_name() == type_name)
+        };
+        let has_field = |type_name: &str, field_name: &str| {
+            ir.structs()
+                .iter()
+                .filter(|s| s.self_type().type_name() == type_name)
+                .flat_map(|s| s.fields())
+                .any(|f| f.name().as_snake_case() == field_name)
+        };
+        let has_variant = |type_name: &str, variant_name: &str| {
+            let enum_variants = ir
+                .enums()
+                .iter()
+                .filter(|e| e.self_type().type_name() == type_name)
+                .flat_map(|e| e.variants().iter().map(|v| v.name()));
+            let data_enum_variants = ir
+                .data_enums()
+                .iter()
+                .filter(|de| de.self_type().type_name() == type_name)
+                .flat_map(|de| de.variants().iter().map(|v| v.name()));
+            enum_variants
+                .chain(data_enum_variants)
+                .any(|name| name.as_pascal_case() == variant_name)
+        };
+        let types = self
+            .configuration_properties
+            .keys()
+            .map(|t| ("with_configuration_properties", t))
+            .chain(
+                self.deny_unknown_fields
+                    .iter()
+                    .map(|t| ("with_deny_unknown_fields", t)),
+            )
+            .chain(
+                self.type_descriptions
+                    .keys()
+                    .map(|t| ("with_type_description", t)),
+            )
+            .chain(self.error_types.iter().map(|t| ("with_error_type", t)));
+        let fields = self
+            .xml_attributes
+            .iter()
+            .map(|(t, f)| ("with_xml_attribute", t, f))
+            .chain(
+                self.json_views
+                    .iter()
+                    .map(|(t, f, _)| ("with_json_view", t, f)),
+            )
+            .chain(
+                self.field_descriptions
+                    .keys()
+                    .map(|(t, f)| ("with_field_description", t, f)),
+            )
+            .chain(
+                self.extra_properties
+                    .iter()
+                    .map(|(t, f)| ("with_extra_properties", t, f)),
+            );
+        let mut unknown = types
+            .filter(|(_, t)| !has_type(t))
+            .map(|(option, t)| format!("`{option}` names `{t}`, which is not a type in the IR"))
+            .chain(
+                fields
+                    .filter(|(_, t, f)| !has_field(t, f))
+                    .map(|(option, t, f)| {
+                        format!("`{option}` names `{t}.{f}`, which is not a struct field in the IR")
+                    }),
+            )
+            .chain(
+                self.error_messages
+                    .keys()
+                    .filter(|(t, v)| !has_variant(t, v))
+                    .map(|(t, v)| {
+                        format!("`with_error_message` names `{t}::{v}`, which is not an enum variant in the IR")
+                    }),
+            )
+            .collect::<Vec<String>>();
+        if unknown.is_empty() {
+            return Ok(());
+        }
+        unknown.sort();
+        Err(Error::new(&unknown.join("; ")))
+    }
+
+    pub fn configuration_properties(&self, type_name: &str) -> Option<&str> {
+        self.configuration_properties
+            .get(type_name)
+            .map(|p| p.as_str())
+    }
+
+    pub fn http_client(&self) -> HttpClientStyle {
+        self.http_client
+    }
+
+    pub fn xml(&self) -> bool {
+        self.xml
+    }
+
+    pub fn csv(&self) -> CsvMapping {
+        self.csv
+    }
+
+    pub fn fixtures(&self) -> bool {
+        self.fixtures
+    }
+
+    pub fn assertions(&self) -> bool {
+        self.assertions
+    }
+
+    pub fn rdc_json(&self) -> bool {
+        self.rdc_json
+    }
+
+    /// Returns true if any type denies unknown fields.
+    pub fn fails_on_unknown_properties(&self) -> bool {
+        !self.deny_unknown_fields.is_empty()
+    }
+
+    pub fn case_insensitive_properties(&self) -> bool {
+        self.case_insensitive_properties
+    }
+
+    /// Returns the Java package of the given Rust module, mapped with `with_module_package`.
+    pub fn module_package(&self, module: &str) -> Option<&str> {
+        self.module_packages
+            .iter()
+            .filter(|(m, _)| {
+                module == m
+                    || module
+                        .strip_prefix(m.as_str())
+                        .is_some_and(|rest| rest.starts_with("::"))
+            })
+            .max_by_key(|(m, _)| m.len())
+            .map(|(_, package)| package.as_str())
+    }
+
+    pub fn json_schema_constants(&self) -> bool {
+        self.json_schema_constants
+    }
+
+    pub fn builders(&self) -> bool {
+        self.builders
+    }
+
+    pub fn schema_fingerprints(&self) -> bool {
+        self.schema_fingerprints
+    }
+
+    pub fn schema_annotations(&self) -> bool {
+        self.schema_annotations
+    }
+
+    pub fn type_description(&self, type_name: &str) -> Option<&str> {
+        self.type_descriptions.get(type_name).map(|d| d.as_str())
+    }
+
+    pub fn field_description(&self, type_name: &str, field_name: &str) -> Option<&str> {
+        self.field_descriptions
+            .get(&(type_name.to_string(), field_name.to_string()))
+            .map(|d| d.as_str())
+    }
+
+    pub fn common_fields(&self) -> bool {
+        self.common_fields
+    }
+
+    pub fn concrete_maps(&self) -> bool {
+        self.concrete_maps
+    }
+
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    pub fn json_filters(&self) -> bool {
+        self.json_filters
+    }
+
+    pub fn field_constants(&self) -> bool {
+        self.field_constants
+    }
+
+    pub fn empty_factories(&self) -> bool {
+        self.empty_factories
+    }
+
+    pub fn view_interfaces(&self) -> bool {
+        self.view_interfaces
+    }
+
+    pub fn data_enum_encoding(&self) -> DataEnumEncoding {
+        self.data_enum_encoding
+    }
+
+    pub fn shared_interfaces(&self) -> &[(String, Vec<String>)] {
+        &self.shared_interfaces
+    }
+
+    pub fn all_args_constructors(&self) -> bool {
+        self.all_args_constructors
+    }
+
+    pub fn accessor_style(&self) -> AccessorStyle {
+        self.accessor_style
+    }
+
+    pub fn nullable_annotation(&self) -> Option<NullableAnnotation> {
+        self.nullable_annotation
+    }
+
+    pub fn getter_javadoc(&self) -> bool {
+        self.getter_javadoc
+    }
+
+    pub fn unknown_variants(&self) -> bool {
+        self.unknown_variants
+    }
+
+    pub fn display_name_bundle(&self) -> Option<&str> {
+        self.display_name_bundle.as_deref()
+    }
+
+    pub fn compatibility_snapshot(&self) -> Option<&str> {
+        self.compatibility_snapshot.as_deref()
+    }
+
+    pub fn acknowledged_changes(&self) -> &[String] {
+        &self.acknowledged_changes
+    }
+
+    pub fn denies_unknown_fields(&self, type_name: &str) -> bool {
+        self.deny_unknown_fields.contains(type_name)
+    }
+
+    pub fn extra_properties(&self, type_name: &str, field_name: &str) -> bool {
+        self.extra_properties
+            .contains(&(type_name.to_string(), field_name.to_string()))
+    }
+
+    pub fn is_error_type(&self, type_name: &str) -> bool {
+        self.error_types.contains(type_name)
+    }
+
+    pub fn error_message(&self, type_name: &str, variant_name: &str) -> Option<&str> {
+        self.error_messages
+            .get(&(type_name.to_string(), variant_name.to_string()))
+            .map(|m| m.as_str())
+    }
+
+    pub fn error_exceptions(&self) -> bool {
+        self.error_exceptions
+    }
+
+    pub fn json_view(&self, type_name: &str, field_name: &str) -> Option<&str> {
+        self.json_views
+            .iter()
+            .find(|(t, f, _)| t == type_name && f == field_name)
+            .map(|(_, _, view)| view.as_str())
+    }
+
+    /// Returns the distinct views fields are assigned to, in the order they were first used.
+    pub fn json_views(&self) -> Vec<&str> {
+        let mut views = Vec::new();
+        for (_, _, view) in &self.json_views {
+            if !views.contains(&view.as_str()) {
+                views.push(view.as_str());
+            }
+        }
+        views
+    }
+
+    pub fn is_xml_attribute(&self, type_name: &str, field_name: &str) -> bool {
+        self.xml_attributes
+            .contains(&(type_name.to_string(), field_name.to_string()))
+    }
+}
@@ -0,0 +1,529 @@
+use crate::errors::Error;
+use crate::ir::{
+    CustomType, DataEnumVariant, Field, IntermediateRepresentation, Name, Struct, Type, TypeKind,
+};
+use crate::targets::java::keywords::escape_java_keyword;
+use crate::targets::java::type_resolver::is_sorted_map;
+use crate::targets::java::{AccessorStyle, JavaConfig, UnsignedLongMapping};
+use crate::targets::openapi::generate_json_schema;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+
+/// This struct holds the state shared by all Java code generators during a single generation.
+/// It is responsible for naming generated classes and resolving references to them.
+pub struct JavaContext<'a> {
+    config: &'a JavaConfig,
+    classes: HashMap<String, ClassKind>,
+    interfaces: HashMap<String, Vec<String>>,
+    json_schemas: HashMap<String, String>,
+    fingerprints: HashMap<String, String>,
+    packages: HashMap<String, String>,
+    map_keys: HashSet<String>,
+    shared_interfaces: Vec<(String, Vec<Field>)>,
+    warnings: RefCell<Vec<String>>,
+}
+
+/// This enum describes what kind of IR element a generated class comes from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClassKind {
+    Struct,
+    Enum,
+    /// Enum that is a set of flags, generated as a value class.
+    Flags,
+    DataEnum,
+    Polymorphic,
+}
+
+impl<'a> JavaContext<'a> {
+    pub fn new(ir: &IntermediateRepresentation, config: &'a JavaConfig) -> Self {
+        let classes: HashMap<String, ClassKind> = ir
+            .structs()
+            .iter()
+            .map(|s| (s.self_type(), ClassKind::Struct))
+            .chain(ir.enums().iter().map(|e| match e.flags() {
+                Some(_) => (e.self_type(), ClassKind::Flags),
+                None => (e.self_type(), ClassKind::Enum),
+            }))
+            .chain(
+                ir.data_enums()
+                    .iter()
+                    .map(|de| (de.self_type(), ClassKind::DataEnum)),
+            )
+            .chain(
+                ir.polymorphics()
+                    .iter()
+                    .map(|p| (p.self_type(), ClassKind::Polymorphic)),
+            )
+            .map(|(t, kind)| (t.type_name().clone(), kind))
+            .collect();
+        let mut interfaces: HashMap<String, Vec<String>> = HashMap::new();
+        for p in ir.polymorphics() {
+            for variant in p.variants() {
+                interfaces
+                    .entry(variant.variant_type().type_name().clone())
+                    .or_default()
+                    .push(p.self_type().type_name().clone());
+            }
+        }
+        let mut json_schemas = HashMap::new();
+        if config.json_schema_constants() {
+            for type_name in classes.keys() {
+                if let Ok(schema) = generate_json_schema(ir, type_name) {
+                    json_schemas.insert(type_name.clone(), schema);
+                }
+            }
+        }
+        let mut fingerprints = HashMap::new();
+        if config.schema_fingerprints() {
+            for type_name in classes.keys() {
+                if let Some(fingerprint) = ir.fingerprint(type_name) {
+                    fingerprints.insert(type_name.clone(), fingerprint);
+                }
+            }
+        }
+        let packages = classes
+            .keys()
+            .filter_map(|type_name| {
+                let module = ir.rust_module(type_name)?;
+                let package = config.module_package(module)?;
+                Some((type_name.clone(), package.to_string()))
+            })
+            .collect();
+        let mut map_keys = HashSet::new();
+        for s in ir.structs() {
+            for f in s.fields() {
+                collect_map_keys(f.field_type(), &mut map_keys);
+            }
+        }
+        for de in ir.data_enums() {
+            for v in de.variants() {
+                match v {
+                    DataEnumVariant::Object { fields, .. } => fields
+                        .iter()
+                        .for_each(|f| collect_map_keys(f.field_type(), &mut map_keys)),
+                    DataEnumVariant::Tuple { fields, .. } => fields
+                        .iter()
+                        .for_each(|t| collect_map_keys(t, &mut map_keys)),
+                    DataEnumVariant::Unit { .. } => {}
+                }
+            }
+        }
+        let mut shared_interfaces = config
+            .shared_interfaces()
+            .iter()
+            .filter_map(|(name, field_names)| {
+                let fields = ir.structs().iter().find_map(|s| {
+                    field_names
+                        .iter()
+                        .map(|n| {
+                            s.fields()
+                                .iter()
+                                .find(|f| &f.name().as_snake_case() == n)
+                                .cloned()
+                        })
+                        .collect::<Option<Vec<Field>>>()
+                })?;
+                Some((name.clone(), fields))
+            })
+            .collect::<Vec<(String, Vec<Field>)>>();
+        for (name, shared) in ir.declared_shared_fields() {
+            if shared_interfaces.iter().any(|(n, _)| n == &name) || shared.fields().is_empty() {
+                continue;
+            }
+            let Some(first) = ir
+                .structs()
+                .iter()
+                .find(|s| s.self_type().type_name() == &shared.structs()[0])
+            else {
+                continue;
+            };
+            let fields = first
+                .fields()
+                .iter()
+                .filter(|f| shared.fields().contains(&f.name().as_snake_case()))
+                .cloned()
+                .collect();
+            shared_interfaces.push((name, fields));
+        }
+        Self {
+            config,
+            classes,
+            interfaces,
+            json_schemas,
+            fingerprints,
+            packages,
+            map_keys,
+            shared_interfaces,
+            warnings: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Returns the name of a Java field or parameter.
+    /// Reserved keywords are escaped with a trailing underscore, e.g. `class` becomes `class_`.
+    pub fn field_name(&self, name: &Name) -> String {
+        let camel_case = name.as_camel_case();
+        match escape_java_keyword(&camel_case) {
+            Some(escaped) => {
+                self.warn(format!(
+                    "`{camel_case}` is a reserved Java keyword, it was renamed to `{escaped}`"
+                ));
+                escaped
+            }
+            None => camel_case,
+        }
+    }
+
+    /// Returns the name used in accessor methods of a field, e.g. `Name` in `getName`.
+    /// It matches the field name, so escaped fields get accessors like `getClass_`.
+    pub fn accessor_name(&self, name: &Name) -> String {
+        let field_name = self.field_name(name);
+        let mut chars = field_name.chars();
+        match chars.next() {
+            Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+            None => field_name,
+        }
+    }
+
+    /// Returns the name of the getter of a field, e.g. `getPaid`, `isPaid` or `paid`, following the accessor style.
+    pub fn getter_name(&self, name: &Name, field_type: &Type) -> String {
+        let accessor = self.accessor_name(name);
+        match self.config.accessor_style() {
+            AccessorStyle::BooleanIs if is_boolean(field_type) => format!("is{accessor}"),
+            AccessorStyle::Record => self
+                .record_accessor(self.field_name(name))
+                .unwrap_or_else(|| format!("get{accessor}")),
+            AccessorStyle::Kotlin if kotlin_is_property(name, field_type).is_some() => {
+                self.field_name(name)
+            }
+            _ => format!("get{accessor}"),
+        }
+    }
+
+    /// Returns the name of the setter of a field, e.g. `setPaid` or `paid`, following the accessor style.
+    pub fn setter_name(&self, name: &Name, field_type: &Type) -> String {
+        let accessor = self.accessor_name(name);
+        match self.config.accessor_style() {
+            AccessorStyle::Record => self
+                .record_accessor(self.field_name(name))
+                .unwrap_or_else(|| format!("set{accessor}")),
+            AccessorStyle::Kotlin => match kotlin_is_property(name, field_type) {
+                Some(property) => format!("set{}", self.accessor_name(&property)),
+                None => format!("set{accessor}"),
+            },
+            _ => format!("set{accessor}"),
+        }
+    }
+
+    /// Returns the name of the getter of the value of a data enum variant, e.g. `getCircle` or `circle`,
+    /// followed by the index of the value in tuple variants with several values, e.g. `getLine0`.
+    pub fn variant_getter_name(&self, name: &Name, index: Option<usize>) -> String {
+        let index = index.map(|i| i.to_string()).unwrap_or_default();
+        let bean_name = format!("get{}{index}", name.as_pascal_case());
+        match self.config.accessor_style() {
+            AccessorStyle::Record => self
+                .record_accessor(format!("{}{index}", self.field_name(name)))
+                .unwrap_or(bean_name),
+            _ => bean_name,
+        }
+    }
+
+    /// Returns the record accessor of a property, or `None` when it would clash with a method of `Object`
+    /// or a static method of the generated classes, so that the accessors keep JavaBeans names.
+    fn record_accessor(&self, property: String) -> Option<String> {
+        if CLASHING_ACCESSORS.contains(&property.as_str()) {
+            self.warn(format!(
+                "`{property}()` would clash with another method, so the accessors of `{property}` have JavaBeans names"
+            ));
+            return None;
+        }
+        Some(property)
+    }
+
+    /// Returns the fields of a shared interface, taken from the first struct having all of them,
+    /// or `None` if there is no such struct.
+    pub fn shared_interface_fields(&self, name: &str) -> Option<&[Field]> {
+        self.shared_interfaces
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, fields)| fields.as_slice())
+    }
+
+    /// Returns names of the shared interfaces, configured ones first, then ones named with `#[rdc(implements_shared)]`.
+    pub fn shared_interface_names(&self) -> Vec<&String> {
+        let configured = self.config.shared_interfaces().iter().map(|(name, _)| name);
+        let declared = self
+            .shared_interfaces
+            .iter()
+            .map(|(name, _)| name)
+            .filter(|name| {
+                !self
+                    .config
+                    .shared_interfaces()
+                    .iter()
+                    .any(|(n, _)| n == *name)
+            });
+        configured.chain(declared).collect()
+    }
+
+    /// Returns names of the shared interfaces implemented by the class of the struct.
+    /// Structs having all the fields of an interface, but with other types, are reported with a warning.
+    pub fn shared_interfaces(&self, dc: &Struct) -> Vec<String> {
+        let mut names = Vec::new();
+        for (name, fields) in &self.shared_interfaces {
+            match self.shared_field_mismatch(dc, fields) {
+                None => {}
+                Some(Some(f)) => self.warn(format!(
+                    "`{}` does not implement `{name}`, because the type of `{}` differs",
+                    dc.self_type().type_name(),
+                    f.name().as_snake_case()
+                )),
+                Some(None) => names.push(name.clone()),
+            }
+        }
+        names
+    }
+
+    /// Checks that every struct marked with `#[rdc(implements_shared = ...)]` implements the interfaces it names.
+    pub fn check_shared_interfaces(&self, ir: &IntermediateRepresentation) -> Result<(), Error> {
+        for dc in ir.structs() {
+            for name in dc.shared_interfaces() {
+                let implemented = self.shared_interface_fields(name).is_some_and(|fields| {
+                    matches!(self.shared_field_mismatch(dc, fields), Some(None))
+                });
+                if implemented {
+                    continue;
+                }
+                let configured = self
+                    .config
+                    .shared_interfaces()
+                    .iter()
+                    .any(|(n, _)| n == name);
+                return Err(Error::new(&if configured {
+                    format!(
+                        "`{}` is marked with `#[rdc(implements_shared = \"{name}\")]`, but does not have the fields of `{name}` with the same types",
+                        dc.self_type().type_name()
+                    )
+                } else {
+                    format!(
+                        "The structs marked with `#[rdc(implements_shared = \"{name}\")]` share no fields"
+                    )
+                }));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `None` if the struct lacks some of the fields, the first field of another type if there is one,
+    /// and `Some(None)` if the struct has all the fields with the same types.
+    fn shared_field_mismatch<'f>(
+        &self,
+        dc: &Struct,
+        fields: &'f [Field],
+    ) -> Option<Option<&'f Field>> {
+        let own_fields = fields
+            .iter()
+            .map(|f| {
+                dc.fields()
+                    .iter()
+                    .find(|own| own.name().as_snake_case() == f.name().as_snake_case())
+            })
+            .collect::<Option<Vec<&Field>>>()?;
+        Some(fields.iter().zip(own_fields).find_map(|(f, own)| {
+            (self.type_name(f.field_type()) != self.type_name(own.field_type())).then_some(f)
+        }))
+    }
+
+    /// Records a warning about the class that is currently generated.
+    pub fn warn(&self, warning: String) {
+        let mut warnings = self.warnings.borrow_mut();
+        if !warnings.contains(&warning) {
+            warnings.push(warning);
+        }
+    }
+
+    /// Returns and clears all the warnings recorded so far.
+    pub fn take_warnings(&self) -> Vec<String> {
+        self.warnings.take()
+    }
+
+    /// Returns the name of the Java class generated for the given type.
+    pub fn class_name(&self, custom_type: &CustomType) -> String {
+        self.generated_name(custom_type.type_name())
+    }
+
+    /// Returns the names of the classes generated for all the types of the IR.
+    pub fn class_names(&self) -> HashSet<String> {
+        self.classes
+            .keys()
+            .map(|name| self.generated_name(name))
+            .collect()
+    }
+
+    /// Returns names of the interfaces implemented by the class generated for the given type.
+    /// A class implements an interface when it is a variant of a polymorphic type.
+    pub fn interfaces(&self, custom_type: &CustomType) -> Vec<String> {
+        self.interfaces
+            .get(custom_type.type_name())
+            .map(|names| names.iter().map(|n| self.generated_name(n)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the Java type name, with references to generated classes renamed accordingly.
+    pub fn type_name(&self, t: &Type) -> String {
+        match t.kind() {
+            TypeKind::Named if self.classes.contains_key(t.type_name()) => {
+                self.generated_name(t.type_name())
+            }
+            TypeKind::Named => t.type_name().clone(),
+            TypeKind::List(element) => format!("java.util.List<{}>", self.type_name(element)),
+            TypeKind::Map(key, value) => format!(
+                "{}<{}, {}>",
+                self.map_class(t),
+                self.type_name(key),
+                self.type_name(value)
+            ),
+            TypeKind::Option(inner) => self.type_name(inner),
+            TypeKind::UnsignedLong => match self.config.unsigned_long_mapping() {
+                UnsignedLongMapping::BigInteger => "java.math.BigInteger".to_string(),
+                UnsignedLongMapping::Long => "Long".to_string(),
+            },
+        }
+    }
+
+    /// Returns the Java class of a map, which is `java.util.Map` unless `JavaConfig::with_concrete_maps` is enabled.
+    pub fn map_class(&self, map: &Type) -> &'static str {
+        match map.kind() {
+            TypeKind::Map(key, _) if self.config.concrete_maps() => {
+                if self.class_kind(key) == Some(ClassKind::Enum) {
+                    "java.util.EnumMap"
+                } else if is_sorted_map(map) {
+                    "java.util.TreeMap"
+                } else {
+                    "java.util.LinkedHashMap"
+                }
+            }
+            _ => "java.util.Map",
+        }
+    }
+
+    /// Returns true if the field collects unknown properties, see `JavaConfig::with_extra_properties`.
+    /// Fields that are not maps with `String` keys never do.
+    pub fn collects_extra_properties(&self, owner: &Struct, field: &Field) -> bool {
+        let configured = self
+            .config
+            .extra_properties(owner.self_type().type_name(), &field.name().as_snake_case());
+        configured
+            && matches!(field.field_type().kind(), TypeKind::Map(key, _) if key.type_name() == "String")
+    }
+
+    /// Returns true if the type is used as a key of a map in a struct or a data enum.
+    pub fn is_map_key(&self, type_name: &str) -> bool {
+        self.map_keys.contains(type_name)
+    }
+
+    /// Returns the Java package of the class generated for a type, if its Rust module is mapped to one.
+    pub fn package(&self, custom_type: &CustomType) -> Option<String> {
+        self.packages.get(custom_type.type_name()).cloned()
+    }
+
+    /// Returns the JSON Schema of a type, when `JSON_SCHEMA` constants are enabled and the schema can be generated.
+    pub fn json_schema(&self, type_name: &str) -> Option<&str> {
+        self.json_schemas.get(type_name).map(|s| s.as_str())
+    }
+
+    /// Returns the fingerprint of a type, when `SCHEMA_FINGERPRINT` constants are enabled.
+    pub fn fingerprint(&self, type_name: &str) -> Option<&str> {
+        self.fingerprints.get(type_name).map(|f| f.as_str())
+    }
+
+    pub fn config(&self) -> &JavaConfig {
+        self.config
+    }
+
+    /// Returns the kind of the generated class referenced by the type, or `None` if it is not a generated class.
+    pub fn class_kind(&self, t: &Type) -> Option<ClassKind> {
+        match t.kind() {
+            TypeKind::Named => self.classes.get(t.type_name()).copied(),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the type refers to a generated class, directly or through a collection.
+    pub fn references_generated_class(&self, t: &Type) -> bool {
+        match t.kind() {
+            TypeKind::Named => self.classes.contains_key(t.type_name()),
+            TypeKind::List(inner) | TypeKind::Option(inner) => {
+                self.references_generated_class(inner)
+            }
+            TypeKind::Map(key, value) => {
+                self.references_generated_class(key) || self.references_generated_class(value)
+            }
+            TypeKind::UnsignedLong => false,
+        }
+    }
+
+    /// Returns true if values of the given type have to be checked for being non-negative.
+    /// It is the case for `u64` values mapped to `Long`.
+    pub fn requires_unsigned_check(&self, t: &Type) -> bool {
+        match t.kind() {
+            TypeKind::UnsignedLong => {
+                self.config.unsigned_long_mapping() == UnsignedLongMapping::Long
+            }
+            TypeKind::Option(inner) => self.requires_unsigned_check(inner),
+            _ => false,
+        }
+    }
+
+    fn generated_name(&self, name: &str) -> String {
+        format!(
+            "{}{}{}",
+            self.config.class_prefix(),
+            name,
+            self.config.class_suffix()
+        )
+    }
+}
+
+/// Names of methods that every class has, or that generated classes have as static methods,
+/// which record-style accessors must not take.
+const CLASHING_ACCESSORS: [&str; 11] = [
+    "hashCode",
+    "toString",
+    "equals",
+    "getClass",
+    "clone",
+    "finalize",
+    "notify",
+    "notifyAll",
+    "wait",
+    "builder",
+    "empty",
+];
+
+fn is_boolean(t: &Type) -> bool {
+    match t.kind() {
+        TypeKind::Option(inner) => is_boolean(inner),
+        TypeKind::Named => t.type_name() == "Boolean",
+        _ => false,
+    }
+}
+
+/// Returns the name of the Kotlin property without the `is` prefix, e.g. `active` for `is_active`,
+/// when the field is a `Boolean` starting with `is`.
+fn kotlin_is_property(name: &Name, t: &Type) -> Option<Name> {
+    let snake_case = name.as_snake_case();
+    let property = snake_case.strip_prefix("is_").filter(|p| !p.is_empty())?;
+    is_boolean(t).then(|| Name::from_snake_case(property))
+}
+
+/// Collects the names of the types used as map keys in the type.
+fn collect_map_keys(t: &Type, keys: &mut HashSet<String>) {
+    match t.kind() {
+        TypeKind::Map(key, value) => {
+            keys.insert(key.type_name().clone());
+            collect_map_keys(value, keys);
+        }
+        TypeKind::List(inner) | TypeKind::Option(inner) => collect_map_keys(inner, keys),
+        TypeKind::Named | TypeKind::UnsignedLong => {}
+    }
+}
@@ -0,0 +1,264 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+/// This function replaces fully qualified class names in generated code, e.g. `java.util.List<String>`,
+/// with their simple names and adds the imports they need, like a human would write the class.
+///
+/// A name is left qualified if its simple name is already taken in the class: by another class of the same name
+/// from a different package, by a class imported or referenced by its simple name, e.g. a nested `Builder`,
+/// or by one of the `reserved` names, which are the generated classes that may be imported when the class is written.
+/// Names in string literals and comments are never changed.
+pub fn shorten_names(code: &str, reserved: &HashSet<String>) -> String {
+    let (imports, body) = split_imports(code);
+    let imported = imports
+        .iter()
+        .filter_map(|i| Some((i.rsplit_once('.')?.1, *i)))
+        .collect::<BTreeMap<&str, &str>>();
+
+    let mut unqualified = HashSet::new();
+    let mut qualified: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for span in spans(body) {
+        if let Span::Code(text) = span {
+            for name in names(text) {
+                match name.split() {
+                    Some((package, simple)) => {
+                        qualified.entry(simple).or_default().insert(package);
+                    }
+                    None => {
+                        unqualified.insert(name.first());
+                    }
+                }
+            }
+        }
+    }
+
+    let mut shortened = HashSet::new();
+    let mut new_imports = Vec::new();
+    for (simple, packages) in &qualified {
+        let [package] = packages.iter().copied().collect::<Vec<_>>()[..] else {
+            continue;
+        };
+        let full_name = format!("{package}.{simple}");
+        let already_imported = imported.get(simple) == Some(&full_name.as_str());
+        let taken = reserved.contains(*simple)
+            || (unqualified.contains(simple) && !already_imported)
+            || (imported.contains_key(simple) && !already_imported);
+        if taken {
+            continue;
+        }
+        if package != "java.lang" && !already_imported {
+            new_imports.push(full_name.clone());
+        }
+        shortened.insert(full_name);
+    }
+    if shortened.is_empty() {
+        return code.to_string();
+    }
+
+    let mut output = String::new();
+    let mut all_imports = imports
+        .iter()
+        .map(|i| i.to_string())
+        .chain(new_imports)
+        .collect::<Vec<String>>();
+    all_imports.sort_unstable();
+    for i in &all_imports {
+        output.push_str(&format!("import {i};"));
+    }
+    if imports.is_empty() {
+        output.push(' ');
+    }
+    let mut rest = body;
+    for span in spans(body) {
+        let Span::Code(text) = span else {
+            continue;
+        };
+        // Spans are slices of the body, so the text before them can be copied as it is.
+        let offset = text.as_ptr() as usize - rest.as_ptr() as usize;
+        output.push_str(&rest[..offset]);
+        let mut position = 0;
+        for name in names(text) {
+            let Some((package, simple)) = name.split() else {
+                continue;
+            };
+            if shortened.contains(&format!("{package}.{simple}")) {
+                output.push_str(&text[position..name.start]);
+                position = name.start + package.len() + 1;
+            }
+        }
+        output.push_str(&text[position..]);
+        rest = &rest[offset + text.len()..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Returns the imported class names and the code following the imports.
+fn split_imports(code: &str) -> (Vec<&str>, &str) {
+    let mut imports = Vec::new();
+    let mut rest = code;
+    loop {
+        let trimmed = rest.trim_start();
+        let Some(import) = trimmed.strip_prefix("import ") else {
+            break;
+        };
+        let Some((name, after)) = import.split_once(';') else {
+            break;
+        };
+        if name.starts_with("static ") {
+            break;
+        }
+        imports.push(name.trim());
+        rest = after;
+    }
+    if imports.is_empty() {
+        (imports, code)
+    } else {
+        (imports, rest)
+    }
+}
+
+enum Span<'a> {
+    Code(&'a str),
+    /// A string or character literal, or a comment.
+    Text,
+}
+
+/// Splits code into code and spans of literals and comments, which are not parsed for names.
+fn spans(code: &str) -> Vec<Span<'_>> {
+    let bytes = code.as_bytes();
+    let mut spans = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let end = match (bytes[i], bytes.get(i + 1)) {
+            (b'"', _) if code[i..].starts_with("\"\"\"") => code[i + 3..]
+                .find("\"\"\"")
+                .map_or(bytes.len(), |e| i + 3 + e + 3),
+            (quote @ (b'"' | b'\''), _) => {
+                let mut j = i + 1;
+                while j < bytes.len() && bytes[j] != quote {
+                    j += if bytes[j] == b'\\' { 2 } else { 1 };
+                }
+                (j + 1).min(bytes.len())
+            }
+            (b'/', Some(b'/')) => code[i..].find('\n').map_or(bytes.len(), |e| i + e),
+            (b'/', Some(b'*')) => code[i + 2..]
+                .find("*/")
+                .map_or(bytes.len(), |e| i + 2 + e + 2),
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+        if start < i {
+            spans.push(Span::Code(&code[start..i]));
+        }
+        spans.push(Span::Text);
+        start = end;
+        i = end;
+    }
+    if start < bytes.len() {
+        spans.push(Span::Code(&code[start..]));
+    }
+    spans
+}
+
+/// A dotted chain of identifiers, e.g. `java.util.Map.of`, with its position in the code.
+struct Name<'a> {
+    start: usize,
+    text: &'a str,
+}
+
+impl<'a> Name<'a> {
+    fn first(&self) -> &'a str {
+        self.text.split('.').next().unwrap_or(self.text)
+    }
+
+    /// Returns the package and the simple name of a qualified class name at the start of the chain,
+    /// e.g. `java.util` and `Map` for `java.util.Map.of`.
+    /// Packages have at least two lowercase segments, so that fields of variables, e.g. `value.items`, are not mistaken for them.
+    fn split(&self) -> Option<(&'a str, &'a str)> {
+        let mut package_len = 0;
+        for (i, segment) in self.text.split('.').enumerate() {
+            if segment.starts_with(|c: char| c.is_ascii_uppercase()) {
+                return match i {
+                    0 | 1 => None,
+                    _ => Some((&self.text[..package_len - 1], segment)),
+                };
+            }
+            if !segment.starts_with(|c: char| c.is_ascii_lowercase()) {
+                return None;
+            }
+            package_len += segment.len() + 1;
+        }
+        None
+    }
+}
+
+/// Returns the dotted chains of identifiers in the code, with positions relative to it.
+/// Chains following a dot, e.g. `getItems().stream`, are skipped, since they can not start with a package.
+fn names(code: &str) -> Vec<Name<'_>> {
+    let is_identifier = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    let mut names = Vec::new();
+    let mut previous = None;
+    let mut chars = code.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if !is_identifier(c) || previous.is_some_and(is_identifier) {
+            previous = Some(c);
+            continue;
+        }
+        let mut end = start + c.len_utf8();
+        let mut last = c;
+        while let Some(&(i, c)) = chars.peek() {
+            let continues = is_identifier(c)
+                || (c == '.' && is_identifier(last) && code[i + 1..].starts_with(is_identifier));
+            if !continues {
+                break;
+            }
+            end = i + c.len_utf8();
+            last = c;
+            chars.next();
+        }
+        if previous != Some('.') && !c.is_ascii_digit() {
+            names.push(Name {
+                start,
+                text: &code[start..end],
+            });
+        }
+        previous = Some(last);
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shorten_qualified_names() {
+        let code = "import com.fasterxml.jackson.annotation.JsonProperty;import java.util.Map; public class Order { \
+            @JsonProperty(\"lines\") private java.util.List<java.util.Map<String, java.math.BigInteger>> lines = new java.util.ArrayList<>(); \
+            private java.lang.Object any = java.util.Map.of(); \
+            private java.time.Instant at; private com.example.time.Instant localAt; \
+            private com.example.Status status; private com.example.Builder builder; \
+            public static final String SCHEMA = \"java.util.List\"; /* java.util.Set */ \
+            public static class Builder {} }";
+        let reserved = HashSet::from(["Status".to_string()]);
+        let code = shorten_names(code, &reserved);
+        assert_eq!(
+            code,
+            "import com.fasterxml.jackson.annotation.JsonProperty;import java.math.BigInteger;import java.util.ArrayList;import java.util.List;import java.util.Map; public class Order { \
+            @JsonProperty(\"lines\") private List<Map<String, BigInteger>> lines = new ArrayList<>(); \
+            private Object any = Map.of(); \
+            private java.time.Instant at; private com.example.time.Instant localAt; \
+            private com.example.Status status; private com.example.Builder builder; \
+            public static final String SCHEMA = \"java.util.List\"; /* java.util.Set */ \
+            public static class Builder {} }"
+        );
+        let code = "public interface Api { java.util.List<String> list(); }";
+        assert_eq!(
+            shorten_names(code, &HashSet::new()),
+            "import java.util.List; public interface Api { List<String> list(); }"
+        );
+    }
+}
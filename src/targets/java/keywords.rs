@@ -0,0 +1,86 @@
+/// Reserved keywords and literals that can not be used as Java identifiers.
+const JAVA_KEYWORDS: &[&str] = &[
+    "abstract",
+    "assert",
+    "boolean",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extends",
+    "false",
+    "final",
+    "finally",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "implements",
+    "import",
+    "instanceof",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "short",
+    "static",
+    "strictfp",
+    "super",
+    "switch",
+    "synchronized",
+    "this",
+    "throw",
+    "throws",
+    "transient",
+    "true",
+    "try",
+    "void",
+    "volatile",
+    "while",
+    "_",
+];
+
+pub fn is_java_keyword(identifier: &str) -> bool {
+    JAVA_KEYWORDS.contains(&identifier)
+}
+
+/// Escapes a Java identifier by appending an underscore if it is a reserved keyword.
+/// Returns `None` if the identifier does not need escaping.
+///
+/// JSON names are never escaped, so `@JsonProperty` keeps pointing at the original name.
+pub fn escape_java_keyword(identifier: &str) -> Option<String> {
+    if is_java_keyword(identifier) {
+        Some(format!("{identifier}_"))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::escape_java_keyword;
+
+    #[test]
+    fn escape_keywords_test() {
+        assert_eq!(escape_java_keyword("class"), Some("class_".to_string()));
+        assert_eq!(escape_java_keyword("new"), Some("new_".to_string()));
+        assert_eq!(escape_java_keyword("className"), None);
+        assert_eq!(escape_java_keyword("Class"), None);
+    }
+}
@@ -1,2 +0,0 @@
-mod project_builder;
-pub use project_builder::run_java;
@@ -1,8 +1,13 @@
 use crate::ir::CustomType;
-use crate::ir::Type;
+use crate::ir::{Type, TypeKind};
 
 /// This is a trait that is implemented by all types that can be converted to a Java type.
 /// It is used to resolve the Java type of a given type.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no Java representation",
+    label = "`{Self}` does not implement `JavaType`",
+    note = "add `#[derive(RDC)]` to `{Self}`, or register trait objects with `rdc_polymorphic!`"
+)]
 pub trait JavaType {
     fn java_type() -> Type;
 }
@@ -31,13 +36,33 @@ bind_java_type!(i64, "Long");
 bind_java_type!(f32, "Float");
 bind_java_type!(f64, "Double");
 bind_java_type!(String, "String");
+bind_java_type!(u8, "Short");
+bind_java_type!(u16, "Integer");
+bind_java_type!(u32, "Long");
+
+// Arbitrary JSON values, e.g. in a catch-all map, are read as Jackson trees.
+#[cfg(feature = "json")]
+bind_java_type!(serde_json::Value, "com.fasterxml.jackson.databind.JsonNode");
+
+/// The Java type of `std::time::Duration`. Serde writes durations as objects of `secs` and `nanos`,
+/// which is not what `java.time.Duration` reads, so fields of this type need `SerdeAdapter::DurationSeconds`.
+pub(crate) const DURATION: &str = "java.time.Duration";
+
+bind_java_type!(std::time::Duration, DURATION);
+
+/// Java has no unsigned 64-bit type, so the actual Java type depends on `JavaConfig::unsigned_long_mapping`.
+impl JavaType for u64 {
+    fn java_type() -> Type {
+        Type::with_kind("BigInteger", TypeKind::UnsignedLong)
+    }
+}
 
 impl<T> JavaType for Vec<T>
 where
     T: JavaType,
 {
     fn java_type() -> Type {
-        Type::new(format!("java.util.List<{}>", T::java_type().type_name()))
+        list_type(T::java_type())
     }
 }
 
@@ -46,7 +71,7 @@ where
     T: JavaType,
 {
     fn java_type() -> Type {
-        T::java_type()
+        option_type(T::java_type())
     }
 }
 
@@ -56,11 +81,17 @@ where
     V: JavaType,
 {
     fn java_type() -> Type {
-        Type::new(format!(
-            "java.util.Map<{}, {}>",
-            K::java_type().type_name(),
-            V::java_type().type_name()
-        ))
+        map_type(K::java_type(), V::java_type())
+    }
+}
+
+impl<K, V> JavaType for std::collections::BTreeMap<K, V>
+where
+    K: JavaType,
+    V: JavaType,
+{
+    fn java_type() -> Type {
+        sorted_map_type(K::java_type(), V::java_type())
     }
 }
 
@@ -72,3 +103,38 @@ where
         T::java_type()
     }
 }
+
+pub(crate) fn list_type(element: Type) -> Type {
+    Type::with_kind(
+        format!("java.util.List<{}>", element.type_name()),
+        TypeKind::List(Box::new(element)),
+    )
+}
+
+pub(crate) fn option_type(inner: Type) -> Type {
+    Type::with_kind(inner.type_name().clone(), TypeKind::Option(Box::new(inner)))
+}
+
+pub(crate) fn map_type(key: Type, value: Type) -> Type {
+    Type::with_kind(
+        format!("java.util.Map<{}, {}>", key.type_name(), value.type_name()),
+        TypeKind::Map(Box::new(key), Box::new(value)),
+    )
+}
+
+/// Returns the type of a map with sorted keys, like `BTreeMap`.
+pub(crate) fn sorted_map_type(key: Type, value: Type) -> Type {
+    Type::with_kind(
+        format!(
+            "java.util.SortedMap<{}, {}>",
+            key.type_name(),
+            value.type_name()
+        ),
+        TypeKind::Map(Box::new(key), Box::new(value)),
+    )
+}
+
+/// Returns true if the type is a map with sorted keys.
+pub(crate) fn is_sorted_map(t: &Type) -> bool {
+    matches!(t.kind(), TypeKind::Map(_, _)) && t.type_name().starts_with("java.util.SortedMap<")
+}
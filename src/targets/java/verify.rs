@@ -0,0 +1,14 @@
+mod compiler;
+mod docker;
+mod jackson_matrix;
+mod project_builder;
+mod project_config;
+mod round_trip;
+pub use compiler::{compile_java, compile_java_with_build_tool, compile_java_with_config};
+pub use docker::JavaDockerRunner;
+pub use jackson_matrix::{JacksonMatrix, JacksonReport, JACKSON_VERSIONS};
+pub use project_builder::{
+    run_java, run_java_with_build_tool, run_java_with_config, JavaBuildTool,
+};
+pub use project_config::{JavaDependency, JavaProjectConfig};
+pub use round_trip::{assert_java_round_trip, assert_java_round_trip_with, java_round_trip};
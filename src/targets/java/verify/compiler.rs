@@ -0,0 +1,168 @@
+use crate::errors::Error;
+use crate::targets::java::verify::project_builder::{
+    lock_project, run_command, write_build_files, write_sources,
+};
+use crate::targets::java::verify::{JavaBuildTool, JavaProjectConfig};
+use crate::targets::java::JavaClass;
+use std::path::Path;
+
+/// This function compiles the provided classes with `javac` without running them.
+/// It is much faster than `run_java`, because the build tool is only used once to resolve Jackson jars,
+/// which are then reused by all subsequent compilations.
+/// Nothing is printed, the diagnostics of `javac` are a part of the returned error.
+///
+/// Example:
+/// ```no_run
+/// use rdc::{rdc_java, RDC};
+/// use rdc::targets::java::verify::compile_java;
+///
+/// #[derive(RDC)]
+/// struct MyStruct {
+///     field1: String,
+/// }
+///
+/// let classes = rdc_java!(MyStruct).unwrap();
+/// compile_java(&classes).unwrap();
+/// ```
+pub fn compile_java(classes: &[JavaClass]) -> Result<(), Error> {
+    compile_java_with_config(classes, &JavaProjectConfig::default())
+}
+
+/// This function works like `compile_java`, but allows choosing the build tool used to resolve dependencies.
+pub fn compile_java_with_build_tool(
+    classes: &[JavaClass],
+    build_tool: JavaBuildTool,
+) -> Result<(), Error> {
+    let config = JavaProjectConfig::default().with_build_tool(build_tool);
+    compile_java_with_config(classes, &config)
+}
+
+/// This function works like `compile_java`, but resolves dependencies of the project described by the configuration.
+pub fn compile_java_with_config(
+    classes: &[JavaClass],
+    config: &JavaProjectConfig,
+) -> Result<(), Error> {
+    let project_dir = config.build_tool().project_dir();
+    let lock = lock_project(&project_dir)?;
+    let result = compile(&project_dir, classes, config);
+    drop(lock);
+    result
+}
+
+fn compile(
+    project_dir: &Path,
+    classes: &[JavaClass],
+    config: &JavaProjectConfig,
+) -> Result<(), Error> {
+    let libs_dir = project_dir.join("libs");
+    resolve_dependencies(project_dir, &libs_dir, config)?;
+
+    let javac_dir = project_dir.join("javac");
+    if javac_dir.exists() {
+        std::fs::remove_dir_all(&javac_dir)
+            .map_err(|_| Error::new("Failed to remove previous sources"))?;
+    }
+    let src_dir = javac_dir.join("src/com/rdc");
+    std::fs::create_dir_all(&src_dir)
+        .map_err(|_| Error::new("Failed to create source directory"))?;
+    write_sources(&src_dir, classes)?;
+
+    let mut args = vec![
+        "-encoding".to_string(),
+        "UTF-8".to_string(),
+        "-d".to_string(),
+        "out".to_string(),
+        "-cp".to_string(),
+        format!("{}/*", libs_dir.display()),
+    ];
+    let sources =
+        std::fs::read_dir(&src_dir).map_err(|_| Error::new("Failed to list source files"))?;
+    for source in sources {
+        let source = source.map_err(|_| Error::new("Failed to list source files"))?;
+        args.push(source.path().display().to_string());
+    }
+    let args = args.iter().map(|a| a.as_str()).collect::<Vec<&str>>();
+    run_command(
+        "javac",
+        &args,
+        javac_dir.to_str().unwrap(),
+        "",
+        config.timeout(),
+    )?;
+    Ok(())
+}
+
+/// Dependencies are copied to the `libs` directory only once and reused afterwards.
+/// They are resolved again when the build tool, the Jackson or Java version or the dependencies change.
+fn resolve_dependencies(
+    project_dir: &Path,
+    libs_dir: &Path,
+    config: &JavaProjectConfig,
+) -> Result<(), Error> {
+    let marker_path = project_dir.join("libs.resolved");
+    // The timeout does not change the resolved jars, so it is not a part of the marker.
+    let marker = format!(
+        "{:?} {} {:?} {:?}",
+        config.build_tool(),
+        config.jackson_version(),
+        config.java_version(),
+        config.dependencies()
+    );
+    let resolved = std::fs::read_to_string(&marker_path)
+        .map(|current| current == marker)
+        .unwrap_or(false);
+    if resolved && libs_dir.exists() {
+        return Ok(());
+    }
+    if libs_dir.exists() {
+        std::fs::remove_dir_all(libs_dir)
+            .map_err(|_| Error::new("Failed to remove previous dependencies"))?;
+    }
+    std::fs::create_dir_all(project_dir)
+        .map_err(|_| Error::new("Failed to create project directory"))?;
+    write_build_files(project_dir, config)?;
+    let (cmd, args) = config.build_tool().copy_dependencies_command();
+    run_command(
+        cmd,
+        args,
+        project_dir.to_str().unwrap(),
+        "",
+        config.timeout(),
+    )?;
+    std::fs::write(marker_path, marker)
+        .map_err(|_| Error::new("Failed to write dependency marker"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::targets::java::JavaClass;
+
+    #[test]
+    fn compile_java_test() {
+        let classes = vec![JavaClass::new(
+            "Main".to_string(),
+            r#"
+                            public class Main {
+                                public static void main(String[] args) throws Exception {
+                                    var mapper = new com.fasterxml.jackson.databind.ObjectMapper();
+                                    System.out.print(mapper.writeValueAsString(Utils.input()));
+                                }
+                            }"#
+            .to_string(),
+        )];
+        super::compile_java(&classes).unwrap();
+    }
+
+    #[test]
+    fn compile_error_test() {
+        let classes = vec![JavaClass::new(
+            "Main".to_string(),
+            "public class Main { invalid }".to_string(),
+        )];
+        // A missing build tool or failed dependency resolution must not pass for a compilation error.
+        let error = super::compile_java(&classes).err().unwrap();
+        assert!(error.message().starts_with("Command javac failed"));
+        assert!(error.message().contains("Main.java"));
+    }
+}
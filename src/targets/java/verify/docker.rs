@@ -0,0 +1,175 @@
+use crate::errors::Error;
+use crate::targets::java::verify::project_builder::{
+    lock_project, prepare_project, run_command_with_cleanup,
+};
+use crate::targets::java::verify::{JavaBuildTool, JavaProjectConfig};
+use crate::targets::java::JavaClass;
+use crate::targets::runner::TargetRunner;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Number of containers started by this process, used to give each of them a unique name.
+static CONTAINERS: AtomicUsize = AtomicUsize::new(0);
+
+/// This struct runs generated Java code inside a Docker container.
+/// It allows running round-trip tests without a local JDK or build tool and pins the Java toolchain.
+///
+/// The project directory is mounted into the container and build tool caches are kept in named volumes,
+/// so subsequent runs do not have to download dependencies again.
+///
+/// Example:
+/// ```no_run
+/// use rdc::targets::java::JavaClass;
+/// use rdc::targets::java::verify::JavaDockerRunner;
+///
+/// let main = JavaClass::new(
+///     "Main".to_string(),
+///     r#"
+///         public class Main {
+///             public static void main(String[] args) {
+///                 System.out.print(Utils.input());
+///             }
+///         }
+///     "#.to_string(),
+/// );
+/// let runner = JavaDockerRunner::new("gradle:8-jdk17");
+/// assert_eq!(runner.run(&[main], "hello").unwrap(), "hello");
+/// ```
+pub struct JavaDockerRunner {
+    image: String,
+    project_config: JavaProjectConfig,
+}
+
+impl JavaDockerRunner {
+    pub fn new<S: Into<String>>(image: S) -> Self {
+        Self {
+            image: image.into(),
+            project_config: JavaProjectConfig::default(),
+        }
+    }
+
+    /// Sets the build tool that is used inside the container.
+    /// The image has to provide it, e.g. `maven:3-eclipse-temurin-17` for Maven.
+    pub fn with_build_tool(mut self, build_tool: JavaBuildTool) -> Self {
+        self.project_config = self.project_config.with_build_tool(build_tool);
+        self
+    }
+
+    /// Sets the configuration of the project built in the container.
+    pub fn with_project_config(mut self, project_config: JavaProjectConfig) -> Self {
+        self.project_config = project_config;
+        self
+    }
+
+    pub fn image(&self) -> &str {
+        &self.image
+    }
+
+    pub fn project_config(&self) -> &JavaProjectConfig {
+        &self.project_config
+    }
+
+    /// Builds and runs the provided classes in the container.
+    /// It works the same way as `run_java`.
+    pub fn run(&self, classes: &[JavaClass], input: &str) -> Result<String, Error> {
+        let project_dir = self.project_dir();
+        let lock = lock_project(&project_dir)?;
+        let result = self.run_project(&project_dir, classes, input);
+        drop(lock);
+        result
+    }
+
+    fn project_dir(&self) -> PathBuf {
+        std::env::temp_dir()
+            .join("rdc")
+            .join(match self.project_config.build_tool() {
+                JavaBuildTool::Gradle => "docker-gradle-project",
+                JavaBuildTool::Maven => "docker-maven-project",
+            })
+    }
+
+    fn run_project(
+        &self,
+        project_dir: &Path,
+        classes: &[JavaClass],
+        input: &str,
+    ) -> Result<String, Error> {
+        let build_tool = self.project_config.build_tool();
+        prepare_project(project_dir, classes, &self.project_config)?;
+
+        let (cache_volume, cache_dir) = match build_tool {
+            JavaBuildTool::Gradle => ("rdc-gradle-cache", "/home/gradle/.gradle"),
+            JavaBuildTool::Maven => ("rdc-maven-cache", "/root/.m2"),
+        };
+        let project_mount = format!("{}:/project", project_dir.display());
+        let cache_mount = format!("{cache_volume}:{cache_dir}");
+        let (cmd, cmd_args) = build_tool.command();
+        // Killing the client on timeout does not stop the container, so it is named to be killed explicitly.
+        let container = format!(
+            "rdc-{}-{}",
+            std::process::id(),
+            CONTAINERS.fetch_add(1, Ordering::Relaxed)
+        );
+        let mut args = vec![
+            "run",
+            "--rm",
+            "--name",
+            container.as_str(),
+            "-i",
+            "-v",
+            project_mount.as_str(),
+            "-v",
+            cache_mount.as_str(),
+            "-w",
+            "/project",
+            self.image.as_str(),
+            cmd,
+        ];
+        args.extend_from_slice(cmd_args);
+        run_command_with_cleanup(
+            "docker",
+            &args,
+            project_dir.to_str().unwrap(),
+            input,
+            self.project_config.timeout(),
+            || {
+                let _ = Command::new("docker")
+                    .args(["kill", container.as_str()])
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .status();
+            },
+        )
+    }
+}
+
+impl TargetRunner for JavaDockerRunner {
+    type File = JavaClass;
+
+    fn run(&self, files: &[JavaClass], stdin: &str) -> Result<String, Error> {
+        JavaDockerRunner::run(self, files, stdin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::targets::java::JavaClass;
+
+    #[test]
+    fn docker_uppercase_stdin_test() {
+        let classes = vec![JavaClass::new(
+            "Main".to_string(),
+            r#"
+                            public class Main {
+                                public static void main(String[] args) throws Exception {
+                                    System.out.print(Utils.input().toUpperCase());
+                                }
+                            }"#
+            .to_string(),
+        )];
+        let runner = super::JavaDockerRunner::new("gradle:8-jdk17");
+        let result = runner.run(&classes, "hello, world!");
+        assert_eq!(result.unwrap(), "HELLO, WORLD!");
+    }
+}
@@ -0,0 +1,168 @@
+use crate::errors::Error;
+use crate::targets::java::verify::{
+    compile_java_with_config, run_java_with_config, JavaProjectConfig,
+};
+use crate::targets::java::JavaClass;
+
+/// Jackson versions checked by default, the latest patch release of every minor version since 2.12.
+pub const JACKSON_VERSIONS: &[&str] = &[
+    "2.12.7", "2.13.5", "2.14.3", "2.15.4", "2.16.2", "2.17.2", "2.18.2",
+];
+
+/// This struct checks generated code against a list of Jackson versions.
+/// Support for annotations and their attributes differs between versions, so code that works with the version
+/// used by `run_java` may still fail for consumers of the generated code that are stuck on an older one.
+///
+/// Every version is built with the same project configuration, with Jackson modules added as dependencies
+/// moved to the same version. Versions are checked one after another and all of them are checked even if some fail.
+/// Nothing is printed, the results of all versions are returned in a `JacksonReport`.
+///
+/// Example:
+/// ```no_run
+/// use rdc::{rdc_java, RDC};
+/// use rdc::targets::java::verify::{JacksonMatrix, JavaProjectConfig};
+///
+/// #[derive(RDC)]
+/// struct MyStruct {
+///     field1: String,
+/// }
+///
+/// let classes = rdc_java!(MyStruct).unwrap();
+/// let report = JacksonMatrix::new(JavaProjectConfig::new())
+///     .with_versions(["2.12.7", "2.17.2"])
+///     .compile(&classes);
+/// for (version, error) in report.failures() {
+///     println!("Jackson {version}: {}", error.message());
+/// }
+/// ```
+pub struct JacksonMatrix {
+    config: JavaProjectConfig,
+    versions: Vec<String>,
+}
+
+/// This struct holds the results of checking generated code against every version of a `JacksonMatrix`.
+pub struct JacksonReport<T> {
+    results: Vec<(String, Result<T, Error>)>,
+}
+
+impl JacksonMatrix {
+    /// Creates a matrix of `JACKSON_VERSIONS` built with the given project configuration.
+    pub fn new(config: JavaProjectConfig) -> Self {
+        Self {
+            config,
+            versions: JACKSON_VERSIONS.iter().map(|v| v.to_string()).collect(),
+        }
+    }
+
+    /// Replaces the checked Jackson versions, e.g. with the versions used by consumers of the generated code.
+    pub fn with_versions<I, S>(mut self, versions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.versions = versions.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn versions(&self) -> &[String] {
+        &self.versions
+    }
+
+    /// Compiles the classes with every Jackson version.
+    pub fn compile(&self, classes: &[JavaClass]) -> JacksonReport<()> {
+        self.check(|config| compile_java_with_config(classes, config))
+    }
+
+    /// Builds and runs the classes with every Jackson version, like `run_java`, collecting the printed output.
+    pub fn run(&self, classes: &[JavaClass], input: &str) -> JacksonReport<String> {
+        self.check(|config| run_java_with_config(classes, input, config))
+    }
+
+    /// Calls `check` with the project configuration of every Jackson version,
+    /// e.g. to run a round trip with `java_round_trip`, which accepts the configuration as a runner.
+    pub fn check<T, F>(&self, check: F) -> JacksonReport<T>
+    where
+        F: Fn(&JavaProjectConfig) -> Result<T, Error>,
+    {
+        let results = self
+            .versions
+            .iter()
+            .map(|version| {
+                let result = check(&self.config.for_jackson_version(version));
+                (version.clone(), result)
+            })
+            .collect();
+        JacksonReport { results }
+    }
+}
+
+impl<T> JacksonReport<T> {
+    /// Returns the result of every version, in the order of the matrix.
+    pub fn results(&self) -> &[(String, Result<T, Error>)] {
+        &self.results
+    }
+
+    /// Returns the versions that failed, with their errors.
+    pub fn failures(&self) -> Vec<(&str, &Error)> {
+        self.results
+            .iter()
+            .filter_map(|(version, result)| result.as_ref().err().map(|e| (version.as_str(), e)))
+            .collect()
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.results.iter().all(|(_, result)| result.is_ok())
+    }
+
+    /// Returns the results of all the versions, or an error listing every failed version with its error.
+    pub fn into_result(self) -> Result<Vec<T>, Error> {
+        let failures = self
+            .failures()
+            .iter()
+            .map(|(version, e)| format!("Jackson {version}: {}", e.message()))
+            .collect::<Vec<String>>();
+        if !failures.is_empty() {
+            return Err(Error::new(&failures.join("; ")));
+        }
+        Ok(self
+            .results
+            .into_iter()
+            .filter_map(|(_, result)| result.ok())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::targets::java::verify::JavaDependency;
+
+    #[test]
+    fn report_failed_versions() {
+        let config = JavaProjectConfig::new().with_dependency(JavaDependency::new(
+            "com.fasterxml.jackson.datatype",
+            "jackson-datatype-jsr310",
+            "2.14.1",
+        ));
+        let matrix = JacksonMatrix::new(config).with_versions(["2.12.7", "2.13.5", "2.17.2"]);
+        let report = matrix.check(|config| {
+            assert_eq!(config.dependencies()[0].version(), config.jackson_version());
+            match config.jackson_version() < "2.14" {
+                true => Err(Error::new("cannot find symbol")),
+                false => Ok(config.jackson_version().to_string()),
+            }
+        });
+        assert!(!report.is_ok());
+        let failed = report
+            .failures()
+            .iter()
+            .map(|(v, _)| *v)
+            .collect::<Vec<&str>>();
+        assert_eq!(failed, ["2.12.7", "2.13.5"]);
+        assert_eq!(
+            report.into_result().unwrap_err().message(),
+            "Jackson 2.12.7: cannot find symbol; \
+             Jackson 2.13.5: cannot find symbol"
+        );
+    }
+}
@@ -0,0 +1,475 @@
+use crate::errors::Error;
+use crate::targets::java::verify::JavaProjectConfig;
+use crate::targets::java::JavaClass;
+use crate::targets::runner::TargetRunner;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const UTILS_JAVA: &str = r#"
+package com.rdc;
+
+import java.io.IOException;
+
+public class Utils {
+    public static String input() {
+        try {
+            var stream = System.in;
+            var bytes = stream.readAllBytes();
+            stream.close();
+            return new String(bytes, java.nio.charset.StandardCharsets.UTF_8);
+        } catch (java.io.IOException ex) {
+            throw new java.lang.RuntimeException(ex);
+        }
+    }
+}
+"#;
+
+/// This enum represents a build tool that is used to build and run the generated Java project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JavaBuildTool {
+    #[default]
+    Gradle,
+    Maven,
+}
+
+impl JavaBuildTool {
+    pub(super) fn command(&self) -> (&'static str, &'static [&'static str]) {
+        match self {
+            JavaBuildTool::Gradle => ("gradle", &["-q", "run"]),
+            JavaBuildTool::Maven => ("mvn", &["-q", "compile", "exec:java"]),
+        }
+    }
+
+    /// Returns a command that copies all the runtime dependencies of the project to the `libs` directory.
+    pub(super) fn copy_dependencies_command(&self) -> (&'static str, &'static [&'static str]) {
+        match self {
+            JavaBuildTool::Gradle => ("gradle", &["-q", "copyDependencies"]),
+            JavaBuildTool::Maven => (
+                "mvn",
+                &[
+                    "-q",
+                    "dependency:copy-dependencies",
+                    "-DoutputDirectory=libs",
+                ],
+            ),
+        }
+    }
+
+    /// The project directory is kept between runs, so that only the sources have to be rebuilt.
+    pub(super) fn project_dir(&self) -> PathBuf {
+        let name = match self {
+            JavaBuildTool::Gradle => "gradle-project",
+            JavaBuildTool::Maven => "maven-project",
+        };
+        std::env::temp_dir().join("rdc").join(name)
+    }
+}
+
+/// Build tools run projects locally, so they require the tool and a JDK to be installed.
+impl TargetRunner for JavaBuildTool {
+    type File = JavaClass;
+
+    fn run(&self, files: &[JavaClass], stdin: &str) -> Result<String, Error> {
+        run_java_with_build_tool(files, stdin, *self)
+    }
+}
+
+impl TargetRunner for JavaProjectConfig {
+    type File = JavaClass;
+
+    fn run(&self, files: &[JavaClass], stdin: &str) -> Result<String, Error> {
+        run_java_with_config(files, stdin, self)
+    }
+}
+
+struct Project;
+static PROJECT_MUTEX: Mutex<Project> = Mutex::new(Project);
+
+/// Exclusive access to a project directory, released when dropped.
+pub(super) struct ProjectLock {
+    _guard: MutexGuard<'static, Project>,
+    _file: File,
+}
+
+/// Locks a project directory, so that its sources and dependencies are not replaced while they are used.
+/// Threads of this process wait for each other on a mutex, while other processes, e.g. other `cargo test` runs
+/// or other crates using `verify`, wait for a lock on the `.lock` file in the directory.
+pub(super) fn lock_project(project_dir: &Path) -> Result<ProjectLock, Error> {
+    let guard = PROJECT_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+    std::fs::create_dir_all(project_dir)
+        .map_err(|_| Error::new("Failed to create project directory"))?;
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(project_dir.join(".lock"))
+        .map_err(|_| Error::new("Failed to open project lock file"))?;
+    file.lock()
+        .map_err(|_| Error::new("Failed to lock project directory"))?;
+    Ok(ProjectLock {
+        _guard: guard,
+        _file: file,
+    })
+}
+
+/// This function builds a Gradle project from the provided classes and runs it.
+/// Classes are placed in the `com.rdc` package and `com.rdc.Main` is used as the entry point.
+/// A `Utils.input()` helper is available to the classes, it returns the provided input read from stdin.
+/// Returns everything the program printed to stdout.
+///
+/// Only one project is run at a time, also across processes, so this function can be safely called from parallel tests.
+/// The project is kept in a temporary directory and reused by subsequent runs to avoid cold builds.
+///
+/// Example:
+/// ```no_run
+/// use rdc::targets::java::JavaClass;
+/// use rdc::targets::java::verify::run_java;
+///
+/// let main = JavaClass::new(
+///     "Main".to_string(),
+///     r#"
+///         public class Main {
+///             public static void main(String[] args) {
+///                 System.out.print(Utils.input().toUpperCase());
+///             }
+///         }
+///     "#.to_string(),
+/// );
+/// let output = run_java(&[main], "hello").unwrap();
+/// assert_eq!(output, "HELLO");
+/// ```
+pub fn run_java(classes: &[JavaClass], input: &str) -> Result<String, Error> {
+    run_java_with_config(classes, input, &JavaProjectConfig::default())
+}
+
+/// This function works like `run_java`, but allows choosing the build tool used to build and run the project.
+pub fn run_java_with_build_tool(
+    classes: &[JavaClass],
+    input: &str,
+    build_tool: JavaBuildTool,
+) -> Result<String, Error> {
+    let config = JavaProjectConfig::default().with_build_tool(build_tool);
+    run_java_with_config(classes, input, &config)
+}
+
+/// This function works like `run_java`, but builds the project described by the provided configuration.
+pub fn run_java_with_config(
+    classes: &[JavaClass],
+    input: &str,
+    config: &JavaProjectConfig,
+) -> Result<String, Error> {
+    let project_dir = config.build_tool().project_dir();
+    let lock = lock_project(&project_dir)?;
+    let result = run_project(&project_dir, classes, input, config);
+    drop(lock);
+    result
+}
+
+fn run_project(
+    project_dir: &Path,
+    classes: &[JavaClass],
+    input: &str,
+    config: &JavaProjectConfig,
+) -> Result<String, Error> {
+    prepare_project(project_dir, classes, config)?;
+
+    let (cmd, args) = config.build_tool().command();
+    run_command(
+        cmd,
+        args,
+        project_dir.to_str().unwrap(),
+        input,
+        config.timeout(),
+    )
+}
+
+/// Creates or updates a project in the given directory, replacing all previous sources.
+pub(super) fn prepare_project(
+    project_dir: &Path,
+    classes: &[JavaClass],
+    config: &JavaProjectConfig,
+) -> Result<(), Error> {
+    let src_dir = project_dir.join("src/main/java/com/rdc");
+    if src_dir.exists() {
+        std::fs::remove_dir_all(&src_dir)
+            .map_err(|_| Error::new("Failed to remove previous sources"))?;
+    }
+    std::fs::create_dir_all(&src_dir)
+        .map_err(|_| Error::new("Failed to create project directory"))?;
+
+    write_sources(&src_dir, classes)?;
+    write_build_files(project_dir, config)
+}
+
+/// Writes the classes and `Utils.java` to the `com.rdc` package directory.
+pub(super) fn write_sources(src_dir: &Path, classes: &[JavaClass]) -> Result<(), Error> {
+    for class in classes {
+        let class_path = src_dir.join(format!("{}.java", class.name()));
+        let contents = class.code();
+        let contents = format!("package com.rdc;\n\n{contents}");
+        std::fs::write(class_path, contents)
+            .map_err(|_| Error::new("Failed to write class file"))?;
+    }
+
+    let utils_java_path = src_dir.join("Utils.java");
+    std::fs::write(utils_java_path, UTILS_JAVA)
+        .map_err(|_| Error::new("Failed to write Utils.java"))
+}
+
+pub(super) fn write_build_files(
+    project_dir: &Path,
+    config: &JavaProjectConfig,
+) -> Result<(), Error> {
+    for (file_name, contents) in config.build_files() {
+        write_if_changed(&project_dir.join(file_name), &contents)
+            .map_err(|_| Error::new(&format!("Failed to write {file_name}")))?;
+    }
+    Ok(())
+}
+
+/// Build files are only rewritten when their contents change,
+/// so that the build tool can reuse its caches between runs.
+fn write_if_changed(path: &Path, contents: &str) -> std::io::Result<()> {
+    match std::fs::read_to_string(path) {
+        Ok(current) if current == contents => Ok(()),
+        _ => std::fs::write(path, contents),
+    }
+}
+
+/// Runs the command, writing `std_input` to its stdin while reading its output concurrently,
+/// so that large inputs and outputs can not block each other.
+/// The command fails if it exits with a non-zero status or does not finish before the timeout.
+/// Output written to stderr is only a part of the error, because build tools also use it for warnings.
+pub(super) fn run_command(
+    cmd: &str,
+    args: &[&str],
+    dir: &str,
+    std_input: &str,
+    timeout: Duration,
+) -> Result<String, Error> {
+    run_command_with_cleanup(cmd, args, dir, std_input, timeout, || {})
+}
+
+/// Works like `run_command`, but calls `on_timeout` after killing a command that timed out,
+/// e.g. to stop a container that is not a child process and would keep running.
+pub(super) fn run_command_with_cleanup(
+    cmd: &str,
+    args: &[&str],
+    dir: &str,
+    std_input: &str,
+    timeout: Duration,
+    on_timeout: impl FnOnce(),
+) -> Result<String, Error> {
+    let mut command = Command::new(cmd);
+    command.args(args);
+    command.stdin(Stdio::piped());
+    command.stdout(Stdio::piped());
+    command.stderr(Stdio::piped());
+    command.current_dir(dir);
+
+    let mut child = command
+        .spawn()
+        .map_err(|_| Error::new(&format!("Failed to run command: {cmd}")))?;
+
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or(Error::new("Failed to get stdin"))?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or(Error::new("Failed to get stdout"))?;
+    let stderr = child
+        .stderr
+        .take()
+        .ok_or(Error::new("Failed to get stderr"))?;
+
+    let input = std_input.as_bytes().to_vec();
+    let stdin_writer = thread::spawn(move || stdin.write_all(&input));
+    let stdout_reader = thread::spawn(move || read_to_string(stdout));
+    let stderr_reader = thread::spawn(move || read_to_string(stderr));
+
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if started.elapsed() >= timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                on_timeout();
+                // The pipes are closed once the process and whatever it started are gone.
+                let _ = stdin_writer.join();
+                let _ = stdout_reader.join();
+                let _ = stderr_reader.join();
+                return Err(Error::new(&format!(
+                    "Command {cmd} timed out after {} seconds",
+                    timeout.as_secs_f64()
+                )));
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(10)),
+            Err(_) => return Err(Error::new("Failed to wait for command")),
+        }
+    };
+
+    // The process may exit without reading the whole input, which is not an error by itself.
+    let _ = stdin_writer.join();
+    let stdout = stdout_reader
+        .join()
+        .map_err(|_| Error::new("Failed to read stdout"))?;
+    let stderr = stderr_reader
+        .join()
+        .map_err(|_| Error::new("Failed to read stderr"))?;
+    if status.success() {
+        Ok(stdout)
+    } else {
+        Err(Error::new(&format!(
+            "Command {cmd} failed with {status}: {stderr}"
+        )))
+    }
+}
+
+fn read_to_string<R: Read>(mut reader: R) -> String {
+    let mut bytes = Vec::new();
+    let _ = reader.read_to_end(&mut bytes);
+    String::from_utf8_lossy(&bytes).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::targets::java::JavaClass;
+
+    #[test]
+    fn run_java_test() {
+        let classes = vec![JavaClass::new(
+            "Main".to_string(),
+            r#"
+                            public class Main {
+                                public static void main(String[] args) throws Exception {
+                                    System.out.print("Hello, World!");
+                                }
+                            }"#
+            .to_string(),
+        )];
+        let result = super::run_java(&classes, "");
+        assert_eq!(result.unwrap(), "Hello, World!");
+    }
+
+    #[test]
+    fn uppercase_stdin_test() {
+        let classes = vec![JavaClass::new(
+            "Main".to_string(),
+            r#"
+                            public class Main {
+                                public static void main(String[] args) throws Exception {
+                                    System.out.print(Utils.input().toUpperCase());
+                                }
+                            }"#
+            .to_string(),
+        )];
+        let result = super::run_java(&classes, "hello, world!");
+        assert_eq!(result.unwrap(), "HELLO, WORLD!");
+    }
+
+    #[test]
+    fn jackson_test() {
+        let classes = vec![JavaClass::new(
+            "Main".to_string(),
+            r#"
+                            public class Main {
+
+                                static class Obj {
+                                   @com.fasterxml.jackson.annotation.JsonIgnore
+                                   public int height;
+                                }
+                                public static void main(String[] args) throws Exception {
+                                    System.out.print("success");
+                                }
+                            }"#
+            .to_string(),
+        )];
+        let result = super::run_java(&classes, "");
+        assert_eq!(result.unwrap(), "success");
+    }
+
+    #[test]
+    fn maven_uppercase_stdin_test() {
+        let classes = vec![JavaClass::new(
+            "Main".to_string(),
+            r#"
+                            public class Main {
+                                public static void main(String[] args) throws Exception {
+                                    System.out.print(Utils.input().toUpperCase());
+                                }
+                            }"#
+            .to_string(),
+        )];
+        let result =
+            super::run_java_with_build_tool(&classes, "hello, world!", super::JavaBuildTool::Maven);
+        assert_eq!(result.unwrap(), "HELLO, WORLD!");
+    }
+
+    #[test]
+    fn command_stderr_is_not_a_failure_test() {
+        let result = super::run_command(
+            "sh",
+            &["-c", "echo warning >&2; cat"],
+            ".",
+            "input",
+            std::time::Duration::from_secs(10),
+        );
+        assert_eq!(result.unwrap(), "input");
+    }
+
+    #[test]
+    fn command_exit_status_test() {
+        let result = super::run_command(
+            "sh",
+            &["-c", "echo broken >&2; exit 3"],
+            ".",
+            "",
+            std::time::Duration::from_secs(10),
+        );
+        let error = result.unwrap_err();
+        assert!(error.message().contains("broken"));
+    }
+
+    #[test]
+    fn command_large_input_test() {
+        let input = "x".repeat(4 * 1024 * 1024);
+        let result =
+            super::run_command("cat", &[], ".", &input, std::time::Duration::from_secs(30));
+        assert_eq!(result.unwrap().len(), input.len());
+    }
+
+    #[test]
+    fn command_timeout_test() {
+        let result = super::run_command(
+            "sleep",
+            &["10"],
+            ".",
+            "",
+            std::time::Duration::from_millis(200),
+        );
+        assert!(result.unwrap_err().message().contains("timed out"));
+    }
+
+    #[test]
+    fn command_timeout_cleanup_test() {
+        let mut cleaned_up = false;
+        let result = super::run_command_with_cleanup(
+            "sleep",
+            &["10"],
+            ".",
+            "",
+            std::time::Duration::from_millis(200),
+            || cleaned_up = true,
+        );
+        assert!(result.is_err());
+        assert!(cleaned_up);
+    }
+}
@@ -0,0 +1,308 @@
+use crate::targets::java::verify::JavaBuildTool;
+use std::time::Duration;
+
+/// This struct describes the Java project used to verify generated code.
+/// It allows checking compatibility against specific Jackson and Java versions.
+///
+/// Example:
+/// ```rust
+/// use rdc::targets::java::verify::{JavaBuildTool, JavaProjectConfig};
+///
+/// let config = JavaProjectConfig::new()
+///     .with_build_tool(JavaBuildTool::Maven)
+///     .with_jackson_version("2.15.2")
+///     .with_java_version(17);
+/// assert_eq!(config.jackson_version(), "2.15.2");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JavaProjectConfig {
+    build_tool: JavaBuildTool,
+    jackson_version: String,
+    java_version: Option<u32>,
+    dependencies: Vec<JavaDependency>,
+    timeout: Duration,
+}
+
+/// This struct represents an additional Maven dependency of the verification project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JavaDependency {
+    group: String,
+    artifact: String,
+    version: String,
+}
+
+impl Default for JavaProjectConfig {
+    fn default() -> Self {
+        Self {
+            build_tool: JavaBuildTool::Gradle,
+            jackson_version: "2.14.1".to_string(),
+            java_version: None,
+            dependencies: Vec::new(),
+            timeout: Duration::from_secs(600),
+        }
+    }
+}
+
+impl JavaProjectConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_build_tool(mut self, build_tool: JavaBuildTool) -> Self {
+        self.build_tool = build_tool;
+        self
+    }
+
+    /// Sets the version of `jackson-core` and `jackson-databind`.
+    pub fn with_jackson_version<S: Into<String>>(mut self, version: S) -> Self {
+        self.jackson_version = version.into();
+        self
+    }
+
+    /// Sets the Java version used to compile and run the project.
+    /// Gradle uses it as the toolchain version, Maven uses it as the compiler release.
+    /// By default Gradle uses the JDK it runs on and Maven compiles for Java 17.
+    pub fn with_java_version(mut self, version: u32) -> Self {
+        self.java_version = Some(version);
+        self
+    }
+
+    /// Adds a dependency to the project, e.g. a Jackson module required by the generated code.
+    pub fn with_dependency(mut self, dependency: JavaDependency) -> Self {
+        self.dependencies.push(dependency);
+        self
+    }
+
+    /// Sets the maximum time a single build tool or compiler invocation can take.
+    /// It is 10 minutes by default, which leaves room for downloading dependencies on the first run.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn build_tool(&self) -> JavaBuildTool {
+        self.build_tool
+    }
+
+    pub fn jackson_version(&self) -> &str {
+        &self.jackson_version
+    }
+
+    pub fn java_version(&self) -> Option<u32> {
+        self.java_version
+    }
+
+    pub fn dependencies(&self) -> &[JavaDependency] {
+        &self.dependencies
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Returns the configuration with the given Jackson version, also used by Jackson modules added as dependencies,
+    /// e.g. `jackson-datatype-jsr310`, because their versions have to match `jackson-databind`.
+    pub(super) fn for_jackson_version(&self, version: &str) -> Self {
+        let mut config = self.clone().with_jackson_version(version);
+        for dependency in &mut config.dependencies {
+            if dependency.group.starts_with("com.fasterxml.jackson") {
+                dependency.version = version.to_string();
+            }
+        }
+        config
+    }
+
+    /// Returns names and contents of the build files for the configured build tool.
+    pub(super) fn build_files(&self) -> Vec<(&'static str, String)> {
+        match self.build_tool {
+            JavaBuildTool::Gradle => vec![
+                ("build.gradle", self.build_gradle()),
+                ("settings.gradle", SETTINGS_GRADLE.to_string()),
+            ],
+            JavaBuildTool::Maven => vec![("pom.xml", self.pom_xml())],
+        }
+    }
+
+    fn all_dependencies(&self) -> Vec<JavaDependency> {
+        let jackson = ["jackson-core", "jackson-databind"].iter().map(|artifact| {
+            JavaDependency::new(
+                "com.fasterxml.jackson.core",
+                *artifact,
+                self.jackson_version.as_str(),
+            )
+        });
+        jackson.chain(self.dependencies.iter().cloned()).collect()
+    }
+
+    fn build_gradle(&self) -> String {
+        let dependencies = self
+            .all_dependencies()
+            .iter()
+            .map(|d| {
+                format!(
+                    "    implementation group: '{}', name: '{}', version: '{}'\n",
+                    d.group, d.artifact, d.version
+                )
+            })
+            .collect::<String>();
+        let toolchain = match self.java_version {
+            Some(version) => format!(
+                "\njava {{\n    toolchain {{\n        languageVersion = JavaLanguageVersion.of({version})\n    }}\n}}\n"
+            ),
+            None => String::new(),
+        };
+        BUILD_GRADLE
+            .replace("{dependencies}", &dependencies)
+            .replace("{toolchain}", &toolchain)
+    }
+
+    fn pom_xml(&self) -> String {
+        let dependencies = self
+            .all_dependencies()
+            .iter()
+            .map(|d| {
+                format!(
+                    "        <dependency>\n            <groupId>{}</groupId>\n            <artifactId>{}</artifactId>\n            <version>{}</version>\n        </dependency>\n",
+                    d.group, d.artifact, d.version
+                )
+            })
+            .collect::<String>();
+        POM_XML.replace("{dependencies}", &dependencies).replace(
+            "{java_version}",
+            &self.java_version.unwrap_or(17).to_string(),
+        )
+    }
+}
+
+impl JavaDependency {
+    pub fn new<S: Into<String>>(group: S, artifact: S, version: S) -> Self {
+        Self {
+            group: group.into(),
+            artifact: artifact.into(),
+            version: version.into(),
+        }
+    }
+
+    pub fn group(&self) -> &str {
+        &self.group
+    }
+
+    pub fn artifact(&self) -> &str {
+        &self.artifact
+    }
+
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+}
+
+const BUILD_GRADLE: &str = r#"
+plugins {
+    id 'java'
+    id 'application'
+}
+
+application {
+    mainClass = 'com.rdc.Main'
+}
+
+group 'com.rdc'
+version '1.0-SNAPSHOT'
+{toolchain}
+repositories {
+    mavenCentral()
+}
+
+dependencies {
+{dependencies}}
+
+run {
+    standardInput = System.in
+}
+
+tasks.withType(JavaCompile).configureEach {
+    options.encoding = 'UTF-8'
+}
+
+tasks.register('copyDependencies', Copy) {
+    from configurations.runtimeClasspath
+    into 'libs'
+}
+"#;
+
+const SETTINGS_GRADLE: &str = r#"
+rootProject.name = 'gradle-test'
+"#;
+
+const POM_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0"
+         xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+         xsi:schemaLocation="http://maven.apache.org/POM/4.0.0 http://maven.apache.org/xsd/maven-4.0.0.xsd">
+    <modelVersion>4.0.0</modelVersion>
+
+    <groupId>com.rdc</groupId>
+    <artifactId>maven-test</artifactId>
+    <version>1.0-SNAPSHOT</version>
+
+    <properties>
+        <maven.compiler.release>{java_version}</maven.compiler.release>
+        <project.build.sourceEncoding>UTF-8</project.build.sourceEncoding>
+    </properties>
+
+    <dependencies>
+{dependencies}    </dependencies>
+
+    <build>
+        <plugins>
+            <plugin>
+                <groupId>org.codehaus.mojo</groupId>
+                <artifactId>exec-maven-plugin</artifactId>
+                <version>3.1.0</version>
+                <configuration>
+                    <mainClass>com.rdc.Main</mainClass>
+                </configuration>
+            </plugin>
+        </plugins>
+    </build>
+</project>
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::{JavaDependency, JavaProjectConfig};
+    use crate::targets::java::verify::JavaBuildTool;
+
+    #[test]
+    fn gradle_build_files_test() {
+        let config = JavaProjectConfig::new()
+            .with_jackson_version("2.12.7")
+            .with_java_version(11)
+            .with_dependency(JavaDependency::new(
+                "com.fasterxml.jackson.datatype",
+                "jackson-datatype-jsr310",
+                "2.12.7",
+            ));
+        let files = config.build_files();
+        assert_eq!(files.len(), 2);
+        let (name, build_gradle) = &files[0];
+        assert_eq!(*name, "build.gradle");
+        assert!(build_gradle.contains("name: 'jackson-databind', version: '2.12.7'"));
+        assert!(build_gradle.contains("name: 'jackson-datatype-jsr310', version: '2.12.7'"));
+        assert!(build_gradle.contains("JavaLanguageVersion.of(11)"));
+    }
+
+    #[test]
+    fn maven_build_files_test() {
+        let config = JavaProjectConfig::new()
+            .with_build_tool(JavaBuildTool::Maven)
+            .with_jackson_version("2.15.2");
+        let files = config.build_files();
+        assert_eq!(files.len(), 1);
+        let (name, pom) = &files[0];
+        assert_eq!(*name, "pom.xml");
+        assert!(pom.contains(
+            "<artifactId>jackson-core</artifactId>\n            <version>2.15.2</version>"
+        ));
+        assert!(pom.contains("<maven.compiler.release>17</maven.compiler.release>"));
+    }
+}
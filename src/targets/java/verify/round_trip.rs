@@ -0,0 +1,204 @@
+use crate::errors::Error;
+use crate::ir::{IntermediateRepresentation, TypeTarget};
+use crate::targets::java::verify::JavaBuildTool;
+use crate::targets::java::{generate_java_code, JavaClass};
+use crate::targets::runner::TargetRunner;
+use crate::RDCType;
+use arbitrary::{Arbitrary, Unstructured};
+use genco::quote;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt::Debug;
+
+const DEFAULT_SEED: u64 = 0x5eed;
+const MAX_INPUT_SIZE: usize = 4096;
+
+/// This function generates random instances of `T`, passes them through the generated Java classes
+/// and asserts that they come back unchanged.
+///
+/// It uses Gradle to run the generated code and a fixed seed, so failures are reproducible.
+/// All the instances are processed by a single Java program, so it is fine to use many iterations.
+///
+/// Example:
+/// ```no_run
+/// use arbitrary::Arbitrary;
+/// use rdc::RDC;
+/// use rdc::targets::java::verify::assert_java_round_trip;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(RDC, Serialize, Deserialize, Arbitrary, PartialEq, Debug)]
+/// struct MyStruct {
+///     field1: String,
+///     field2: Option<Vec<i32>>,
+/// }
+///
+/// assert_java_round_trip::<MyStruct>(100);
+/// ```
+pub fn assert_java_round_trip<T>(iterations: usize)
+where
+    T: RDCType + Serialize + DeserializeOwned + for<'a> Arbitrary<'a> + PartialEq + Debug,
+{
+    assert_java_round_trip_with::<T, _>(&JavaBuildTool::Gradle, iterations, DEFAULT_SEED);
+}
+
+/// This function works like `assert_java_round_trip`, but allows choosing the runner and the seed.
+pub fn assert_java_round_trip_with<T, R>(runner: &R, iterations: usize, seed: u64)
+where
+    T: RDCType + Serialize + DeserializeOwned + for<'a> Arbitrary<'a> + PartialEq + Debug,
+    R: TargetRunner<File = JavaClass>,
+{
+    let values = random_values::<T>(iterations, seed);
+    assert_eq!(
+        values.len(),
+        iterations,
+        "Only {} of {iterations} random values of `{}` survive a JSON round trip in Rust, e.g. because of NaN",
+        values.len(),
+        std::any::type_name::<T>()
+    );
+    let results = match java_round_trip(runner, &values) {
+        Ok(results) => results,
+        Err(e) => panic!("Round trip failed: {}", e.message()),
+    };
+    assert_eq!(values.len(), results.len());
+    for (value, result) in values.iter().zip(results.iter()) {
+        assert_eq!(value, result);
+    }
+}
+
+/// This function serializes the values, deserializes and serializes them again with the generated Java classes
+/// and returns the values read back from the Java output.
+pub fn java_round_trip<T, R>(runner: &R, values: &[T]) -> Result<Vec<T>, Error>
+where
+    T: RDCType + Serialize + DeserializeOwned,
+    R: TargetRunner<File = JavaClass>,
+{
+    let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+    ir.add::<T>();
+    let mut classes = generate_java_code(&ir)?;
+    classes.push(generate_main_class(T::java_type().type_name())?);
+
+    let input = serde_json::to_string(values)
+        .map_err(|e| Error::new(&format!("Failed to serialize values: {e}")))?;
+    let output = runner.run(&classes, &input)?;
+    serde_json::from_str(&output)
+        .map_err(|e| Error::new(&format!("Failed to deserialize Java output: {e}")))
+}
+
+fn generate_main_class(type_name: &str) -> Result<JavaClass, Error> {
+    JavaClass::from_tokens(
+        "Main".to_string(),
+        quote!(
+            import com.fasterxml.jackson.databind.ObjectMapper;
+            import com.fasterxml.jackson.databind.JsonNode;
+            import com.fasterxml.jackson.core.type.TypeReference;
+
+            public class Main {
+                public static void main(String[] args) throws Exception {
+                    var objectMapper = new ObjectMapper();
+                    JsonNode values = objectMapper.readTree(Utils.input());
+                    var results = new java.util.ArrayList<Object>();
+                    for (JsonNode node : values) {
+                        $type_name value = objectMapper.readValue(objectMapper.treeAsTokens(node), new TypeReference<$type_name>() {});
+                        results.add(value);
+                    }
+                    System.out.print(objectMapper.writeValueAsString(results));
+                }
+            }
+        ),
+    )
+}
+
+/// Generates values from pseudo-random bytes.
+/// Values that do not survive a `serde_json` round trip on their own (e.g. `NaN`) are skipped,
+/// because they can not be compared after passing through JSON.
+fn random_values<T>(count: usize, seed: u64) -> Vec<T>
+where
+    T: Serialize + DeserializeOwned + for<'a> Arbitrary<'a> + PartialEq,
+{
+    let mut random = XorShift(seed.max(1));
+    let mut values = Vec::with_capacity(count);
+    let mut attempts = 0;
+    while values.len() < count && attempts < count * 10 {
+        attempts += 1;
+        let size = (random.next() as usize) % MAX_INPUT_SIZE;
+        let bytes = (0..size).map(|_| random.next() as u8).collect::<Vec<u8>>();
+        let Ok(value) = T::arbitrary(&mut Unstructured::new(&bytes)) else {
+            continue;
+        };
+        let survives_json = serde_json::to_string(&value)
+            .ok()
+            .and_then(|json| serde_json::from_str::<T>(&json).ok())
+            .map(|read| read == value)
+            .unwrap_or(false);
+        if survives_json {
+            values.push(value);
+        }
+    }
+    values
+}
+
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::RDC;
+    use arbitrary::Arbitrary;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(RDC, Serialize, Deserialize, Arbitrary, PartialEq, Debug)]
+    enum Status {
+        Active,
+        Suspended { reason: String },
+        Moved(i64, Option<String>),
+    }
+
+    #[derive(RDC, Serialize, Deserialize, Arbitrary, PartialEq, Debug)]
+    struct Account {
+        id: String,
+        tags: Vec<String>,
+        balance: f64,
+        status: Status,
+    }
+
+    #[test]
+    fn random_values_test() {
+        let values = super::random_values::<Account>(50, 42);
+        assert_eq!(values.len(), 50);
+        assert_eq!(values, super::random_values::<Account>(50, 42));
+        assert!(values.iter().any(|a| a.tags.is_empty()));
+        assert!(values.iter().any(|a| !a.tags.is_empty()));
+    }
+
+    /// Values that are never equal, like `NaN`, so none of them survive a JSON round trip.
+    #[derive(RDC, Serialize, Deserialize, Arbitrary, Debug)]
+    struct Unequal {
+        value: i32,
+    }
+
+    impl PartialEq for Unequal {
+        fn eq(&self, _: &Self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Only 0 of 10 random values of")]
+    fn values_not_surviving_json_test() {
+        super::assert_java_round_trip::<Unequal>(10);
+    }
+
+    #[test]
+    fn account_round_trip_test() {
+        super::assert_java_round_trip::<Account>(50);
+    }
+}
@@ -0,0 +1,561 @@
+use crate::errors::Error;
+use crate::ir::{Type, TypeKind};
+
+/// This is a minimal JSON value used to render schemas and documents, and to read IR snapshots.
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(u32),
+    /// Number rendered as it is, e.g. an example value of a field, which does not have to fit into `u32`.
+    Literal(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(&'static str, Json)>),
+    /// Object with keys known only at generation time, e.g. property names.
+    Map(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn string<S: Into<String>>(value: S) -> Self {
+        Json::String(value.into())
+    }
+
+    /// Returns the JSON value of an example of a field of the given type, e.g. `42` for an integer field.
+    /// Examples of numeric and boolean fields that are valid numbers or booleans are written as such, others as strings.
+    pub fn example(example: &str, t: &Type) -> Self {
+        match t.kind() {
+            TypeKind::Option(inner) => Json::example(example, inner),
+            TypeKind::UnsignedLong if example.parse::<u64>().is_ok() => {
+                Json::Literal(example.to_string())
+            }
+            TypeKind::Named => match (t.type_name().as_str(), example) {
+                ("Boolean", "true") => Json::Bool(true),
+                ("Boolean", "false") => Json::Bool(false),
+                ("Byte" | "Short" | "Integer" | "Long" | "java.math.BigInteger", _)
+                    if example.parse::<i128>().is_ok() =>
+                {
+                    Json::Literal(example.to_string())
+                }
+                ("Float" | "Double", _) if is_json_number(example) => {
+                    Json::Literal(example.to_string())
+                }
+                _ => Json::string(example),
+            },
+            _ => Json::string(example),
+        }
+    }
+
+    /// Parses a JSON document. Objects are parsed to `Json::Map`, numbers which fit into `u32` to `Json::Number`
+    /// and other numbers, e.g. `-1.5e3`, to `Json::Literal`.
+    pub fn parse(input: &str) -> Result<Json, Error> {
+        let mut parser = Parser {
+            chars: input.chars().peekable(),
+        };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        match parser.chars.next() {
+            None => Ok(value),
+            Some(c) => Err(Error::new(&format!("Unexpected `{c}` after JSON value"))),
+        }
+    }
+
+    /// Returns the value of the given key if this is an object.
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(entries) => entries.iter().find(|(k, _)| *k == key).map(|(_, v)| v),
+            Json::Map(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Json::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Renders the value with two-space indentation.
+    pub fn render(&self) -> String {
+        let mut output = String::new();
+        self.render_into(&mut output, 0);
+        output
+    }
+
+    /// Renders the value as a YAML block with two-space indentation.
+    /// Strings are double-quoted, so that they never turn into numbers, booleans or nulls.
+    pub fn render_yaml(&self) -> String {
+        let mut output = String::new();
+        render_yaml_value(self, &mut output, 0);
+        output.trim_start().to_string()
+    }
+
+    fn render_into(&self, output: &mut String, indent: usize) {
+        match self {
+            Json::Null => output.push_str("null"),
+            Json::Bool(b) => output.push_str(&b.to_string()),
+            Json::Number(n) => output.push_str(&n.to_string()),
+            Json::Literal(n) => output.push_str(n),
+            Json::String(s) => render_string(s, output),
+            Json::Array(items) if items.is_empty() => output.push_str("[]"),
+            Json::Array(items) => {
+                output.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    output.push_str(if i == 0 { "\n" } else { ",\n" });
+                    output.push_str(&"  ".repeat(indent + 1));
+                    item.render_into(output, indent + 1);
+                }
+                output.push('\n');
+                output.push_str(&"  ".repeat(indent));
+                output.push(']');
+            }
+            Json::Object(entries) if entries.is_empty() => output.push_str("{}"),
+            Json::Map(entries) if entries.is_empty() => output.push_str("{}"),
+            Json::Object(entries) => {
+                let entries = entries.iter().map(|(key, value)| (*key, value));
+                render_entries(entries, output, indent);
+            }
+            Json::Map(entries) => {
+                let entries = entries.iter().map(|(key, value)| (key.as_str(), value));
+                render_entries(entries, output, indent);
+            }
+        }
+    }
+}
+
+/// Returns true if the text is a number in the JSON syntax, e.g. `-1.5e3`, which Rust parsing is more lenient about.
+fn is_json_number(text: &str) -> bool {
+    let digits = text.strip_prefix('-').unwrap_or(text);
+    digits.starts_with(|c: char| c.is_ascii_digit())
+        && digits
+            .chars()
+            .all(|c| c.is_ascii_digit() || "+-.eE".contains(c))
+        && text.parse::<f64>().is_ok()
+}
+
+fn render_entries<'a, I>(entries: I, output: &mut String, indent: usize)
+where
+    I: Iterator<Item = (&'a str, &'a Json)>,
+{
+    output.push('{');
+    for (i, (key, value)) in entries.enumerate() {
+        output.push_str(if i == 0 { "\n" } else { ",\n" });
+        output.push_str(&"  ".repeat(indent + 1));
+        render_string(key, output);
+        output.push_str(": ");
+        value.render_into(output, indent + 1);
+    }
+    output.push('\n');
+    output.push_str(&"  ".repeat(indent));
+    output.push('}');
+}
+
+fn render_yaml_entries<'a, I>(entries: I, output: &mut String, indent: usize, indent_first: bool)
+where
+    I: Iterator<Item = (&'a str, &'a Json)>,
+{
+    for (i, (key, value)) in entries.enumerate() {
+        if i > 0 || indent_first {
+            output.push_str(&"  ".repeat(indent));
+        }
+        let plain = key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '$')
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "$_-.".contains(c));
+        if plain {
+            output.push_str(key);
+        } else {
+            render_string(key, output);
+        }
+        output.push(':');
+        render_yaml_value(value, output, indent + 1);
+    }
+}
+
+fn render_yaml_items(items: &[Json], output: &mut String, indent: usize) {
+    for item in items {
+        output.push_str(&"  ".repeat(indent));
+        output.push('-');
+        match item {
+            // The first entry of an object goes right after the dash and the others are aligned with it.
+            Json::Object(entries) if !entries.is_empty() => {
+                output.push(' ');
+                let entries = entries.iter().map(|(key, value)| (*key, value));
+                render_yaml_entries(entries, output, indent + 1, false);
+            }
+            Json::Map(entries) if !entries.is_empty() => {
+                output.push(' ');
+                let entries = entries.iter().map(|(key, value)| (key.as_str(), value));
+                render_yaml_entries(entries, output, indent + 1, false);
+            }
+            item => render_yaml_value(item, output, indent + 1),
+        }
+    }
+}
+
+/// Renders a value after a key or a dash, either inline or as a block on the following lines.
+fn render_yaml_value(value: &Json, output: &mut String, indent: usize) {
+    match value {
+        Json::Array(items) if !items.is_empty() => {
+            output.push('\n');
+            render_yaml_items(items, output, indent);
+        }
+        Json::Object(entries) if !entries.is_empty() => {
+            output.push('\n');
+            let entries = entries.iter().map(|(key, value)| (*key, value));
+            render_yaml_entries(entries, output, indent, true);
+        }
+        Json::Map(entries) if !entries.is_empty() => {
+            output.push('\n');
+            let entries = entries.iter().map(|(key, value)| (key.as_str(), value));
+            render_yaml_entries(entries, output, indent, true);
+        }
+        scalar => {
+            output.push(' ');
+            scalar.render_into(output, indent);
+            output.push('\n');
+        }
+    }
+}
+
+fn render_string(s: &str, output: &mut String) {
+    output.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if (c as u32) < 0x20 => output.push_str(&format!("\\u{:04x}", c as u32)),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+}
+
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl Parser<'_> {
+    fn value(&mut self) -> Result<Json, Error> {
+        self.skip_whitespace();
+        match self.chars.peek() {
+            Some('n') => self.keyword("null", Json::Null),
+            Some('t') => self.keyword("true", Json::Bool(true)),
+            Some('f') => self.keyword("false", Json::Bool(false)),
+            Some('"') => Ok(Json::String(self.string()?)),
+            Some('[') => {
+                self.chars.next();
+                let mut items = Vec::new();
+                self.skip_whitespace();
+                if self.chars.peek() == Some(&']') {
+                    self.chars.next();
+                    return Ok(Json::Array(items));
+                }
+                loop {
+                    items.push(self.value()?);
+                    self.skip_whitespace();
+                    match self.chars.next() {
+                        Some(',') => continue,
+                        Some(']') => return Ok(Json::Array(items)),
+                        _ => return Err(Error::new("Expected `,` or `]` in JSON array")),
+                    }
+                }
+            }
+            Some('{') => {
+                self.chars.next();
+                let mut entries = Vec::new();
+                self.skip_whitespace();
+                if self.chars.peek() == Some(&'}') {
+                    self.chars.next();
+                    return Ok(Json::Map(entries));
+                }
+                loop {
+                    self.skip_whitespace();
+                    let key = self.string()?;
+                    self.skip_whitespace();
+                    if self.chars.next() != Some(':') {
+                        return Err(Error::new("Expected `:` in JSON object"));
+                    }
+                    entries.push((key, self.value()?));
+                    self.skip_whitespace();
+                    match self.chars.next() {
+                        Some(',') => continue,
+                        Some('}') => return Ok(Json::Map(entries)),
+                        _ => return Err(Error::new("Expected `,` or `}` in JSON object")),
+                    }
+                }
+            }
+            Some(c) if *c == '-' || c.is_ascii_digit() => self.number(),
+            Some(c) => Err(Error::new(&format!("Unexpected `{c}` in JSON"))),
+            None => Err(Error::new("Unexpected end of JSON")),
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str, value: Json) -> Result<Json, Error> {
+        for expected in keyword.chars() {
+            if self.chars.next() != Some(expected) {
+                return Err(Error::new(&format!("Expected `{keyword}` in JSON")));
+            }
+        }
+        Ok(value)
+    }
+
+    fn string(&mut self) -> Result<String, Error> {
+        if self.chars.next() != Some('"') {
+            return Err(Error::new("Expected a JSON string"));
+        }
+        let mut s = String::new();
+        loop {
+            match self.chars.next() {
+                Some('"') => return Ok(s),
+                Some('\\') => match self.chars.next() {
+                    Some('n') => s.push('\n'),
+                    Some('r') => s.push('\r'),
+                    Some('t') => s.push('\t'),
+                    Some('b') => s.push('\u{8}'),
+                    Some('f') => s.push('\u{c}'),
+                    Some('u') => s.push(self.unicode_escape()?),
+                    Some(c @ ('"' | '\\' | '/')) => s.push(c),
+                    Some(c) => return Err(Error::new(&format!("Invalid JSON escape `\\{c}`"))),
+                    None => return Err(Error::new("Unexpected end of JSON string")),
+                },
+                Some(c) if (c as u32) < 0x20 => {
+                    return Err(Error::new("Unescaped control character in JSON string"))
+                }
+                Some(c) => s.push(c),
+                None => return Err(Error::new("Unexpected end of JSON string")),
+            }
+        }
+    }
+
+    /// Parses a number. Numbers which fit into `u32` are `Json::Number`, others are kept as `Json::Literal`.
+    fn number(&mut self) -> Result<Json, Error> {
+        let mut text = String::new();
+        if self.chars.next_if_eq(&'-').is_some() {
+            text.push('-');
+        }
+        match self.chars.next() {
+            Some('0') => text.push('0'),
+            Some(c @ '1'..='9') => {
+                text.push(c);
+                self.digits(&mut text);
+            }
+            _ => return Err(Error::new(&format!("Invalid JSON number `{text}`"))),
+        }
+        if self.chars.next_if_eq(&'.').is_some() {
+            text.push('.');
+            if self.digits(&mut text) == 0 {
+                return Err(Error::new(&format!("Invalid JSON number `{text}`")));
+            }
+        }
+        if let Some(e) = self.chars.next_if(|c| *c == 'e' || *c == 'E') {
+            text.push(e);
+            if let Some(sign) = self.chars.next_if(|c| *c == '+' || *c == '-') {
+                text.push(sign);
+            }
+            if self.digits(&mut text) == 0 {
+                return Err(Error::new(&format!("Invalid JSON number `{text}`")));
+            }
+        }
+        Ok(match text.parse() {
+            Ok(n) => Json::Number(n),
+            Err(_) => Json::Literal(text),
+        })
+    }
+
+    /// Appends the digits that follow to the text and returns their count.
+    fn digits(&mut self, text: &mut String) -> usize {
+        let mut count = 0;
+        while let Some(c) = self.chars.next_if(|c| c.is_ascii_digit()) {
+            text.push(c);
+            count += 1;
+        }
+        count
+    }
+
+    /// Parses the code point of a `\u` escape, combining UTF-16 surrogate pairs, e.g. `\ud83d\ude00`.
+    fn unicode_escape(&mut self) -> Result<char, Error> {
+        let high = self.hex()?;
+        let code_point = if (0xd800..0xdc00).contains(&high) {
+            let low = match (self.chars.next(), self.chars.next()) {
+                (Some('\\'), Some('u')) => self.hex()?,
+                _ => 0,
+            };
+            if !(0xdc00..0xe000).contains(&low) {
+                return Err(Error::new(&format!(
+                    "Unpaired surrogate `\\u{high:04x}` in JSON string"
+                )));
+            }
+            0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+        } else {
+            high
+        };
+        char::from_u32(code_point).ok_or_else(|| {
+            Error::new(&format!(
+                "Unpaired surrogate `\\u{code_point:04x}` in JSON string"
+            ))
+        })
+    }
+
+    fn hex(&mut self) -> Result<u32, Error> {
+        let hex = (0..4).filter_map(|_| self.chars.next()).collect::<String>();
+        match hex
+            .chars()
+            .map(|c| c.to_digit(16))
+            .collect::<Option<Vec<u32>>>()
+        {
+            Some(digits) if digits.len() == 4 => Ok(digits.iter().fold(0, |n, d| n * 16 + d)),
+            _ => Err(Error::new(&format!("Invalid JSON escape `\\u{hex}`"))),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self
+            .chars
+            .peek()
+            .is_some_and(|c| matches!(c, ' ' | '\t' | '\n' | '\r'))
+        {
+            self.chars.next();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rendered_json() {
+        let value = Json::Object(vec![
+            ("name", Json::string("a \"quoted\" \\ name\n")),
+            (
+                "items",
+                Json::Array(vec![Json::Null, Json::Bool(true), Json::Number(42)]),
+            ),
+            ("empty", Json::Map(Vec::new())),
+        ]);
+        let parsed = Json::parse(&value.render()).unwrap();
+        assert_eq!(parsed.render(), value.render());
+        assert_eq!(
+            parsed.get("name").and_then(|n| n.as_str()),
+            Some("a \"quoted\" \\ name\n")
+        );
+        assert_eq!(
+            parsed
+                .get("items")
+                .and_then(|i| i.as_array())
+                .map(|i| i.len()),
+            Some(3)
+        );
+        assert!(Json::parse("{\"a\": 1,}").is_err());
+        assert!(Json::parse("[1] 2").is_err());
+    }
+
+    #[test]
+    fn parse_numbers() {
+        let parsed = Json::parse("[0, 42, 4294967296, -1, -0, 1.5, -2.25e-3, 1E+10, 0.0]").unwrap();
+        assert_eq!(
+            parsed.render(),
+            "[\n  0,\n  42,\n  4294967296,\n  -1,\n  -0,\n  1.5,\n  -2.25e-3,\n  1E+10,\n  0.0\n]"
+        );
+        assert!(matches!(
+            parsed.as_array(),
+            Some([Json::Number(0), Json::Number(42), Json::Literal(_), ..])
+        ));
+        for invalid in ["01", "-", "1.", ".5", "1e", "1e+", "+1", "--1", "1.e3"] {
+            assert!(
+                Json::parse(invalid).is_err(),
+                "{invalid} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_escapes() {
+        let parsed = Json::parse(r#""\"\\\/\b\f\n\r\t\u00e9\ud83d\ude00""#).unwrap();
+        assert_eq!(parsed.as_str(), Some("\"\\/\u{8}\u{c}\n\r\té😀"));
+        for invalid in [
+            r#""\ud83d""#,
+            r#""\ud83d\u0041""#,
+            r#""\ude00""#,
+            r#""\u12""#,
+            r#""\u+123""#,
+            r#""\x""#,
+            "\"\u{1}\"",
+        ] {
+            assert!(
+                Json::parse(invalid).is_err(),
+                "{invalid} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn render_yaml() {
+        let value = Json::Object(vec![
+            ("type", Json::string("object")),
+            (
+                "required",
+                Json::Array(vec![Json::string("name"), Json::string("true")]),
+            ),
+            (
+                "properties",
+                Json::Map(vec![
+                    (
+                        "name".to_string(),
+                        Json::Object(vec![("type", Json::string("string"))]),
+                    ),
+                    (
+                        "user id".to_string(),
+                        Json::Object(vec![(
+                            "anyOf",
+                            Json::Array(vec![
+                                Json::Object(vec![
+                                    ("$ref", Json::string("#/components/schemas/Id")),
+                                    ("description", Json::string("Id")),
+                                ]),
+                                Json::Object(vec![("type", Json::string("null"))]),
+                            ]),
+                        )]),
+                    ),
+                ]),
+            ),
+            ("additionalProperties", Json::Object(vec![])),
+        ]);
+        assert_eq!(
+            value.render_yaml(),
+            r##"type: "object"
+required:
+  - "name"
+  - "true"
+properties:
+  name:
+    type: "string"
+  "user id":
+    anyOf:
+      - $ref: "#/components/schemas/Id"
+        description: "Id"
+      - type: "null"
+additionalProperties: {}
+"##
+        );
+        assert_eq!(Json::Number(1).render_yaml(), "1\n");
+    }
+}
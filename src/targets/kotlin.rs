@@ -0,0 +1,152 @@
+use crate::errors::Error;
+use crate::ir::IntermediateRepresentation;
+use std::io::Write;
+
+mod cg_jackson;
+mod cg_kotlinx;
+mod config;
+mod context;
+
+pub use config::{KotlinConfig, KotlinFlavor};
+pub(crate) use context::escape_kotlin_keyword;
+use context::KotlinContext;
+
+/// This is a struct that represents a generated Kotlin file with a single top level declaration.
+pub struct KotlinFile {
+    name: String,
+    code: String,
+}
+
+impl KotlinFile {
+    pub fn new(name: String, code: String) -> Self {
+        KotlinFile { name, code }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+}
+
+/// This function generates Kotlin code from an IntermediateRepresentation using the provided configuration.
+///
+/// Structs are translated to data classes, unit enums to enum classes,
+/// and data enums and polymorphic types to sealed classes and interfaces.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::targets::kotlin::{generate_kotlin_code, KotlinConfig, KotlinFlavor};
+/// use rdc::RDC;
+///
+/// #[derive(RDC)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add::<User>();
+/// let config = KotlinConfig::new().with_flavor(KotlinFlavor::Kotlinx);
+/// let files = generate_kotlin_code(&ir, &config).unwrap();
+/// assert!(files[0].code().contains("@Serializable"));
+/// ```
+pub fn generate_kotlin_code(
+    ir: &IntermediateRepresentation,
+    config: &KotlinConfig,
+) -> Result<Vec<KotlinFile>, Error> {
+    ir.validate()?;
+    ir.reject_transparent_structs("Kotlin")?;
+    let ctx = KotlinContext::new(ir);
+    let mut files = Vec::new();
+    match config.flavor() {
+        KotlinFlavor::Jackson => {
+            for s in ir.structs() {
+                files.push(cg_jackson::generate_data_class(s, &ctx));
+            }
+            for e in ir.enums() {
+                files.push(cg_jackson::generate_enum_class(e));
+            }
+            if let Some(de) = ir.data_enums().first() {
+                return Err(Error::new(&format!(
+                    "Data enum `{}` is only supported with the kotlinx.serialization flavor",
+                    de.self_type().type_name()
+                )));
+            }
+            for p in ir.polymorphics() {
+                files.push(cg_jackson::generate_sealed_interface(p));
+            }
+        }
+        KotlinFlavor::Kotlinx => {
+            for s in ir.structs() {
+                if let Some(f) = s.fields().iter().find(|f| f.format().is_some()) {
+                    return Err(Error::new(&format!(
+                        "`{}.{}` has a date format, which is only supported with the Jackson flavor",
+                        s.self_type().type_name(),
+                        f.name().as_snake_case()
+                    )));
+                }
+                files.push(cg_kotlinx::generate_data_class(s, &ctx));
+            }
+            for e in ir.enums() {
+                files.push(cg_kotlinx::generate_enum_class(e));
+            }
+            for de in ir.data_enums() {
+                files.push(cg_kotlinx::generate_sealed_class(de, &ctx));
+            }
+            for p in ir.polymorphics() {
+                files.push(cg_kotlinx::generate_sealed_interface(p));
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// This function writes generated Kotlin files to the provided directory, in subdirectories matching the package.
+pub fn write_kotlin(files: &[KotlinFile], package: &str, directory: &str) -> Result<(), Error> {
+    let mut path = std::path::PathBuf::from(directory);
+    path.push(package.replace('.', "/"));
+    std::fs::create_dir_all(&path).map_err(|_| Error::new("Failed to create directory"))?;
+    for kotlin_file in files {
+        let mut file_path = path.clone();
+        file_path.push(format!("{}.kt", kotlin_file.name()));
+        let mut file =
+            std::fs::File::create(file_path).map_err(|_| Error::new("Failed to create file"))?;
+        let code = format!("package {};\n\n{}", package, kotlin_file.code());
+        file.write_all(code.as_bytes())
+            .map_err(|_| Error::new("Failed to write to file"))?;
+    }
+    Ok(())
+}
+
+/// This function renders imports and declarations of a single file.
+fn render_file(name: &str, imports: &[&str], declaration: Vec<String>) -> KotlinFile {
+    let mut code = String::new();
+    let mut imports = imports.to_vec();
+    imports.sort_unstable();
+    imports.dedup();
+    for import in &imports {
+        code.push_str(&format!("import {import}\n"));
+    }
+    if !imports.is_empty() {
+        code.push('\n');
+    }
+    for line in declaration {
+        code.push_str(&line);
+        code.push('\n');
+    }
+    KotlinFile::new(name.to_string(), code)
+}
+
+/// This function indents lines of a nested declaration.
+fn indent(lines: Vec<String>) -> impl Iterator<Item = String> {
+    lines.into_iter().map(|line| {
+        if line.is_empty() {
+            line
+        } else {
+            format!("    {line}")
+        }
+    })
+}
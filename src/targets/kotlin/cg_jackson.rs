@@ -0,0 +1,176 @@
+use crate::ir::{Enum, Polymorphic, Struct};
+use crate::targets::kotlin::context::KotlinContext;
+use crate::targets::kotlin::{render_file, KotlinFile};
+
+pub fn generate_data_class(s: &Struct, ctx: &KotlinContext) -> KotlinFile {
+    let name = s.self_type().type_name();
+    let interfaces = ctx.interfaces(s.self_type());
+    let supertypes = if interfaces.is_empty() {
+        String::new()
+    } else {
+        format!(" : {}", interfaces.join(", "))
+    };
+    // Jackson refuses to serialize classes without properties, unless they are annotated.
+    if s.fields().is_empty() {
+        return render_file(
+            name,
+            &["com.fasterxml.jackson.databind.annotation.JsonSerialize"],
+            vec![
+                "@JsonSerialize".to_string(),
+                format!("class {name}{supertypes}"),
+            ],
+        );
+    }
+    let mut imports = vec!["com.fasterxml.jackson.annotation.JsonProperty"];
+    let mut lines = vec![format!("data class {name}(")];
+    for field in s.fields() {
+        let format = match field.format() {
+            Some(format) => {
+                imports.push("com.fasterxml.jackson.annotation.JsonFormat");
+                let timezone = format
+                    .timezone()
+                    .map(|timezone| format!(", timezone = \"{timezone}\""))
+                    .unwrap_or_default();
+                format!(
+                    "@JsonFormat(shape = JsonFormat.Shape.STRING, pattern = \"{}\"{timezone}) ",
+                    format.pattern()
+                )
+            }
+            None => String::new(),
+        };
+        lines.push(format!(
+            "    @JsonProperty(\"{}\") {format}{},",
+            field.json_name(),
+            ctx.property(field.name(), field.field_type())
+        ));
+    }
+    lines.push(format!("){supertypes}"));
+    render_file(name, &imports, lines)
+}
+
+pub fn generate_enum_class(e: &Enum) -> KotlinFile {
+    let name = e.self_type().type_name();
+    let mut lines = vec![format!("enum class {name} {{")];
+    for variant in e.variants() {
+        lines.push(format!(
+            "    @JsonProperty(\"{}\") {},",
+            variant.json_name(),
+            variant.name().as_upper_snake_case()
+        ));
+    }
+    lines.push("}".to_string());
+    render_file(
+        name,
+        &["com.fasterxml.jackson.annotation.JsonProperty"],
+        lines,
+    )
+}
+
+pub fn generate_sealed_interface(p: &Polymorphic) -> KotlinFile {
+    let name = p.self_type().type_name();
+    let type_info = match p.tag() {
+        Some(tag) => format!(
+            "@JsonTypeInfo(use = JsonTypeInfo.Id.NAME, include = JsonTypeInfo.As.PROPERTY, property = \"{tag}\")"
+        ),
+        None => "@JsonTypeInfo(use = JsonTypeInfo.Id.NAME, include = JsonTypeInfo.As.WRAPPER_OBJECT)"
+            .to_string(),
+    };
+    let mut lines = vec![type_info, "@JsonSubTypes(".to_string()];
+    for variant in p.variants() {
+        lines.push(format!(
+            "    JsonSubTypes.Type(value = {}::class, name = \"{}\"),",
+            variant.variant_type().type_name(),
+            variant.json_name()
+        ));
+    }
+    lines.push(")".to_string());
+    lines.push(format!("sealed interface {name}"));
+    render_file(
+        name,
+        &[
+            "com.fasterxml.jackson.annotation.JsonSubTypes",
+            "com.fasterxml.jackson.annotation.JsonTypeInfo",
+        ],
+        lines,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::ir::{DateTimeFormat, FieldType, IntermediateRepresentation, IrBuilder, TypeTarget};
+    use crate::targets::kotlin::{generate_kotlin_code, KotlinConfig, KotlinFlavor};
+    use crate::{rdc_polymorphic, RDC};
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct User {
+        #[serde(rename = "user_name")]
+        name: String,
+        nickname: Option<String>,
+        role: Role,
+        shape: Box<dyn Shape>,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Role {
+        Admin,
+        Member,
+    }
+
+    trait Shape {}
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Circle {
+        radius: f64,
+    }
+    impl Shape for Circle {}
+
+    rdc_polymorphic!(dyn Shape, tag = "kind" => Circle);
+
+    #[test]
+    fn generate_jackson_classes() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<User>();
+        let files = generate_kotlin_code(&ir, &KotlinConfig::new()).unwrap();
+        let file = |name: &str| files.iter().find(|f| f.name() == name).unwrap().code();
+        for f in &files {
+            println!("{}", f.code());
+        }
+        let user = file("User");
+        assert!(user.contains("data class User(\n"));
+        assert!(user.contains("    @JsonProperty(\"user_name\") val name: String,\n"));
+        assert!(user.contains("    @JsonProperty(\"nickname\") val nickname: String? = null,\n"));
+        assert!(file("Role").contains("    @JsonProperty(\"Admin\") ADMIN,\n"));
+        assert!(file("Circle").contains(") : Shape\n"));
+        assert!(file("Shape").contains("property = \"kind\""));
+        assert!(
+            file("Shape").contains("JsonSubTypes.Type(value = Circle::class, name = \"Circle\"),")
+        );
+    }
+
+    #[test]
+    fn date_time_format() {
+        let ir = IrBuilder::new(TypeTarget::Java)
+            .struct_("Event")
+            .field("created_at", FieldType::string())
+            .format(DateTimeFormat::new("yyyy-MM-dd").with_timezone("UTC"))
+            .build()
+            .unwrap();
+        let files = generate_kotlin_code(&ir, &KotlinConfig::new()).unwrap();
+        assert!(files[0]
+            .code()
+            .contains("import com.fasterxml.jackson.annotation.JsonFormat\n"));
+        assert!(files[0].code().contains(
+            "    @JsonProperty(\"created_at\") @JsonFormat(shape = JsonFormat.Shape.STRING, pattern = \"yyyy-MM-dd\", timezone = \"UTC\") val createdAt: String,\n"
+        ));
+        let config = KotlinConfig::new().with_flavor(KotlinFlavor::Kotlinx);
+        let error = generate_kotlin_code(&ir, &config).err().unwrap();
+        assert_eq!(
+            error.message(),
+            "`Event.created_at` has a date format, which is only supported with the Jackson flavor"
+        );
+    }
+}
@@ -0,0 +1,434 @@
+use crate::ir::{DataEnum, DataEnumVariant, Enum, Name, Polymorphic, Struct, Type};
+use crate::targets::kotlin::context::KotlinContext;
+use crate::targets::kotlin::{indent, render_file, KotlinFile};
+
+const SERIALIZER_IMPORTS: &[&str] = &[
+    "kotlinx.serialization.KSerializer",
+    "kotlinx.serialization.SerializationException",
+    "kotlinx.serialization.Serializable",
+    "kotlinx.serialization.descriptors.SerialDescriptor",
+    "kotlinx.serialization.descriptors.buildClassSerialDescriptor",
+    "kotlinx.serialization.encoding.Decoder",
+    "kotlinx.serialization.encoding.Encoder",
+    "kotlinx.serialization.json.*",
+];
+
+/// This enum describes how a variant of a sealed type is written in serde's externally tagged representation.
+enum VariantCodec {
+    /// A plain string, e.g. `"Cash"`.
+    Unit { json_name: String, class: String },
+    /// An object serialized with the serializer of the variant class, e.g. `{"Card": {"number": "1"}}`.
+    Object { json_name: String, class: String },
+    /// Values of the properties of the variant class, e.g. `{"Token": "a"}` or `{"Transfer": ["a", 1]}`.
+    Tuple {
+        json_name: String,
+        class: String,
+        properties: Vec<(String, String)>,
+    },
+}
+
+pub fn generate_data_class(s: &Struct, ctx: &KotlinContext) -> KotlinFile {
+    let name = s.self_type().type_name();
+    let interfaces = ctx.interfaces(s.self_type());
+    let supertypes = if interfaces.is_empty() {
+        String::new()
+    } else {
+        format!(" : {}", interfaces.join(", "))
+    };
+    let mut imports = vec!["kotlinx.serialization.Serializable"];
+    let mut lines = vec!["@Serializable".to_string()];
+    if let Some(serial_name) = ctx.serial_name(s.self_type()) {
+        imports.push("kotlinx.serialization.SerialName");
+        lines.push(format!("@SerialName(\"{serial_name}\")"));
+    }
+    let fields = s
+        .fields()
+        .iter()
+        .map(|f| (f.name(), f.json_name().as_str(), f.field_type()))
+        .collect::<Vec<_>>();
+    lines.extend(class_declaration(
+        &format!("{name}{supertypes}"),
+        &fields,
+        ctx,
+        &mut imports,
+    ));
+    render_file(name, &imports, lines)
+}
+
+pub fn generate_enum_class(e: &Enum) -> KotlinFile {
+    let name = e.self_type().type_name();
+    let mut lines = vec!["@Serializable".to_string(), format!("enum class {name} {{")];
+    for variant in e.variants() {
+        lines.push(format!(
+            "    @SerialName(\"{}\") {},",
+            variant.json_name(),
+            variant.name().as_upper_snake_case()
+        ));
+    }
+    lines.push("}".to_string());
+    render_file(
+        name,
+        &[
+            "kotlinx.serialization.SerialName",
+            "kotlinx.serialization.Serializable",
+        ],
+        lines,
+    )
+}
+
+/// Data enums are sealed classes with a custom serializer, because kotlinx.serialization
+/// only supports internally tagged polymorphism, while serde tags data enums externally.
+pub fn generate_sealed_class(de: &DataEnum, ctx: &KotlinContext) -> KotlinFile {
+    let name = de.self_type().type_name();
+    let mut imports = SERIALIZER_IMPORTS.to_vec();
+    let mut body = Vec::new();
+    let mut codecs = Vec::new();
+    for variant in de.variants() {
+        let class = variant.name().as_pascal_case();
+        let json_name = variant.json_name().clone();
+        let supertype = format!("{class} : {name}()");
+        match variant {
+            DataEnumVariant::Unit { .. } => {
+                body.push(format!("object {supertype}"));
+                codecs.push(VariantCodec::Unit { json_name, class });
+            }
+            DataEnumVariant::Object { fields, .. } => {
+                let fields = fields
+                    .iter()
+                    .map(|f| (f.name(), f.json_name(), f.field_type()))
+                    .collect::<Vec<_>>();
+                body.push("@Serializable".to_string());
+                body.extend(class_declaration(&supertype, &fields, ctx, &mut imports));
+                codecs.push(VariantCodec::Object { json_name, class });
+            }
+            DataEnumVariant::Tuple { fields, .. } => {
+                let names = fields
+                    .iter()
+                    .enumerate()
+                    .map(|(i, _)| match fields.len() {
+                        1 => Name::from_snake_case("value"),
+                        _ => Name::from_snake_case(format!("value_{}", i + 1)),
+                    })
+                    .collect::<Vec<Name>>();
+                let properties = names
+                    .iter()
+                    .zip(fields)
+                    .map(|(n, t)| (ctx.property_name(n), ctx.type_name(t)))
+                    .collect::<Vec<(String, String)>>();
+                let declarations = properties
+                    .iter()
+                    .map(|(property, property_type)| format!("val {property}: {property_type}"))
+                    .collect::<Vec<String>>();
+                body.push(format!(
+                    "data class {class}({}) : {name}()",
+                    declarations.join(", ")
+                ));
+                codecs.push(VariantCodec::Tuple {
+                    json_name,
+                    class,
+                    properties,
+                });
+            }
+        }
+    }
+    body.push(String::new());
+    body.extend(serializer(name, &codecs));
+
+    let mut lines = vec![
+        format!("@Serializable(with = {name}.Serializer::class)"),
+        format!("sealed class {name} {{"),
+    ];
+    lines.extend(indent(body));
+    lines.push("}".to_string());
+    render_file(name, &imports, lines)
+}
+
+/// Tagged polymorphic types use the class discriminator of kotlinx.serialization.
+/// Untagged ones are wrapped in an object with the variant name as its key, so they need a custom serializer.
+pub fn generate_sealed_interface(p: &Polymorphic) -> KotlinFile {
+    let name = p.self_type().type_name();
+    match p.tag() {
+        Some(tag) => render_file(
+            name,
+            &[
+                "kotlinx.serialization.ExperimentalSerializationApi",
+                "kotlinx.serialization.Serializable",
+                "kotlinx.serialization.json.JsonClassDiscriminator",
+            ],
+            vec![
+                "@OptIn(ExperimentalSerializationApi::class)".to_string(),
+                "@Serializable".to_string(),
+                format!("@JsonClassDiscriminator(\"{tag}\")"),
+                format!("sealed interface {name}"),
+            ],
+        ),
+        None => {
+            let codecs = p
+                .variants()
+                .iter()
+                .map(|v| VariantCodec::Object {
+                    json_name: v.json_name().clone(),
+                    class: v.variant_type().type_name().clone(),
+                })
+                .collect::<Vec<VariantCodec>>();
+            let mut lines = vec![
+                format!("@Serializable(with = {name}.Serializer::class)"),
+                format!("sealed interface {name} {{"),
+            ];
+            lines.extend(indent(serializer(name, &codecs)));
+            lines.push("}".to_string());
+            render_file(name, SERIALIZER_IMPORTS, lines)
+        }
+    }
+}
+
+/// Returns a class declaration with properties in its primary constructor.
+/// Properties are annotated with `@SerialName` only when the JSON name differs from the property name.
+fn class_declaration(
+    declaration: &str,
+    fields: &[(&Name, &str, &Type)],
+    ctx: &KotlinContext,
+    imports: &mut Vec<&'static str>,
+) -> Vec<String> {
+    if fields.is_empty() {
+        return vec![format!("class {declaration}")];
+    }
+    let (class, supertypes) = match declaration.split_once(" : ") {
+        Some((class, supertypes)) => (class, format!(" : {supertypes}")),
+        None => (declaration, String::new()),
+    };
+    let mut lines = vec![format!("data class {class}(")];
+    for (name, json_name, field_type) in fields {
+        let property = ctx.property(name, field_type);
+        if name.as_camel_case() == *json_name {
+            lines.push(format!("    {property},"));
+        } else {
+            imports.push("kotlinx.serialization.SerialName");
+            lines.push(format!("    @SerialName(\"{json_name}\") {property},"));
+        }
+    }
+    lines.push(format!("){supertypes}"));
+    lines
+}
+
+fn serializer(name: &str, codecs: &[VariantCodec]) -> Vec<String> {
+    let mut lines = vec![
+        format!("object Serializer : KSerializer<{name}> {{"),
+        format!(
+            "    override val descriptor: SerialDescriptor = buildClassSerialDescriptor(\"{name}\")"
+        ),
+        String::new(),
+        format!("    override fun serialize(encoder: Encoder, value: {name}) {{"),
+        "        val output = encoder as JsonEncoder".to_string(),
+        "        val json = output.json".to_string(),
+        "        val element = when (value) {".to_string(),
+    ];
+    for codec in codecs {
+        match codec {
+            VariantCodec::Unit { json_name, class } => lines.push(format!(
+                "            is {class} -> JsonPrimitive(\"{json_name}\")"
+            )),
+            VariantCodec::Object { json_name, class } => lines.push(format!(
+                "            is {class} -> buildJsonObject {{ put(\"{json_name}\", json.encodeToJsonElement({class}.serializer(), value)) }}"
+            )),
+            VariantCodec::Tuple {
+                json_name,
+                class,
+                properties,
+            } if properties.len() == 1 => {
+                let (property, property_type) = &properties[0];
+                lines.push(format!(
+                    "            is {class} -> buildJsonObject {{ put(\"{json_name}\", json.encodeToJsonElement<{property_type}>(value.{property})) }}"
+                ));
+            }
+            VariantCodec::Tuple {
+                json_name,
+                class,
+                properties,
+            } => {
+                lines.push(format!("            is {class} -> buildJsonObject {{"));
+                lines.push(format!("                putJsonArray(\"{json_name}\") {{"));
+                for (property, property_type) in properties {
+                    lines.push(format!(
+                        "                    add(json.encodeToJsonElement<{property_type}>(value.{property}))"
+                    ));
+                }
+                lines.push("                }".to_string());
+                lines.push("            }".to_string());
+            }
+        }
+    }
+    lines.extend([
+        "        }".to_string(),
+        "        output.encodeJsonElement(element)".to_string(),
+        "    }".to_string(),
+        String::new(),
+        format!("    override fun deserialize(decoder: Decoder): {name} {{"),
+        "        val input = decoder as JsonDecoder".to_string(),
+        "        val json = input.json".to_string(),
+        "        val element = input.decodeJsonElement()".to_string(),
+        "        if (element is JsonPrimitive) {".to_string(),
+        "            return when (element.content) {".to_string(),
+    ]);
+    for codec in codecs {
+        if let VariantCodec::Unit { json_name, class } = codec {
+            lines.push(format!("                \"{json_name}\" -> {class}"));
+        }
+    }
+    lines.extend([
+        format!(
+            "                else -> throw SerializationException(\"Unknown variant of {name}: ${{element.content}}\")"
+        ),
+        "            }".to_string(),
+        "        }".to_string(),
+        "        val (key, content) = element.jsonObject.entries.single()".to_string(),
+        "        return when (key) {".to_string(),
+    ]);
+    for codec in codecs {
+        match codec {
+            VariantCodec::Unit { .. } => {}
+            VariantCodec::Object { json_name, class } => lines.push(format!(
+                "            \"{json_name}\" -> json.decodeFromJsonElement({class}.serializer(), content)"
+            )),
+            VariantCodec::Tuple {
+                json_name,
+                class,
+                properties,
+            } if properties.len() == 1 => {
+                let (_, property_type) = &properties[0];
+                lines.push(format!(
+                    "            \"{json_name}\" -> {class}(json.decodeFromJsonElement<{property_type}>(content))"
+                ));
+            }
+            VariantCodec::Tuple {
+                json_name,
+                class,
+                properties,
+            } => {
+                let values = properties
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (_, property_type))| {
+                        format!("json.decodeFromJsonElement<{property_type}>(it[{i}])")
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                lines.push(format!(
+                    "            \"{json_name}\" -> content.jsonArray.let {{ {class}({values}) }}"
+                ));
+            }
+        }
+    }
+    lines.extend([
+        format!(
+            "            else -> throw SerializationException(\"Unknown variant of {name}: $key\")"
+        ),
+        "        }".to_string(),
+        "    }".to_string(),
+        "}".to_string(),
+    ]);
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::ir::{IntermediateRepresentation, TypeTarget};
+    use crate::targets::kotlin::{generate_kotlin_code, KotlinConfig, KotlinFlavor};
+    use crate::{rdc_polymorphic, RDC};
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Order {
+        order_id: String,
+        note: Option<String>,
+        status: Status,
+        payment: Payment,
+        shape: Box<dyn Shape>,
+        event: Box<dyn Event>,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Status {
+        New,
+        Paid,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Payment {
+        Cash,
+        Card { number: String },
+        Token(String),
+        Transfer(String, u64),
+    }
+
+    trait Shape {}
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Circle {
+        radius: f64,
+    }
+    impl Shape for Circle {}
+
+    rdc_polymorphic!(dyn Shape, tag = "kind" => Circle);
+
+    trait Event {}
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Created {
+        id: String,
+    }
+    impl Event for Created {}
+
+    rdc_polymorphic!(dyn Event => Created);
+
+    #[test]
+    fn generate_kotlinx_classes() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Order>();
+        let config = KotlinConfig::new().with_flavor(KotlinFlavor::Kotlinx);
+        let files = generate_kotlin_code(&ir, &config).unwrap();
+        let file = |name: &str| files.iter().find(|f| f.name() == name).unwrap().code();
+        for f in &files {
+            println!("{}", f.code());
+        }
+
+        let order = file("Order");
+        assert!(order.contains("@Serializable\ndata class Order(\n"));
+        assert!(order.contains("    @SerialName(\"order_id\") val orderId: String,\n"));
+        assert!(order.contains("    val note: String? = null,\n"));
+        assert!(file("Status").contains("    @SerialName(\"New\") NEW,\n"));
+
+        let payment = file("Payment");
+        assert!(payment
+            .contains("@Serializable(with = Payment.Serializer::class)\nsealed class Payment {"));
+        assert!(payment.contains("    object Cash : Payment()\n"));
+        assert!(payment.contains(
+            "    data class Transfer(val value1: String, val value2: ULong) : Payment()\n"
+        ));
+        assert!(payment.contains("is Cash -> JsonPrimitive(\"Cash\")"));
+        assert!(
+            payment.contains("put(\"Card\", json.encodeToJsonElement(Card.serializer(), value))")
+        );
+        assert!(payment.contains("put(\"Token\", json.encodeToJsonElement<String>(value.value))"));
+        assert!(payment.contains("putJsonArray(\"Transfer\") {"));
+        assert!(payment.contains("\"Token\" -> Token(json.decodeFromJsonElement<String>(content))"));
+        assert!(payment.contains("Transfer(json.decodeFromJsonElement<String>(it[0]), json.decodeFromJsonElement<ULong>(it[1]))"));
+        assert!(payment.contains("Unknown variant of Payment: ${element.content}"));
+
+        assert!(file("Shape").contains("@JsonClassDiscriminator(\"kind\")\nsealed interface Shape"));
+        assert!(
+            file("Circle").contains("@Serializable\n@SerialName(\"Circle\")\ndata class Circle(")
+        );
+        assert!(file("Circle").contains(") : Shape\n"));
+        let event = file("Event");
+        assert!(event.contains("sealed interface Event {"));
+        assert!(event
+            .contains("\"Created\" -> json.decodeFromJsonElement(Created.serializer(), content)"));
+        assert!(!file("Created").contains("SerialName"));
+    }
+}
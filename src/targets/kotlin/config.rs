@@ -0,0 +1,40 @@
+/// This struct contains options that control Kotlin code generation.
+///
+/// Example:
+/// ```rust
+/// use rdc::targets::kotlin::{KotlinConfig, KotlinFlavor};
+///
+/// let config = KotlinConfig::new().with_flavor(KotlinFlavor::Kotlinx);
+/// assert_eq!(config.flavor(), KotlinFlavor::Kotlinx);
+/// ```
+#[derive(Default)]
+pub struct KotlinConfig {
+    flavor: KotlinFlavor,
+}
+
+/// This enum describes which serialization library the generated classes are annotated for.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KotlinFlavor {
+    /// Jackson annotations, to be used with `jackson-module-kotlin`.
+    #[default]
+    Jackson,
+    /// kotlinx.serialization annotations, usable in Kotlin Multiplatform projects.
+    /// Data enums and untagged polymorphic types get custom serializers matching serde's external tagging.
+    Kotlinx,
+}
+
+impl KotlinConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the serialization library of the generated classes.
+    pub fn with_flavor(mut self, flavor: KotlinFlavor) -> Self {
+        self.flavor = flavor;
+        self
+    }
+
+    pub fn flavor(&self) -> KotlinFlavor {
+        self.flavor
+    }
+}
@@ -0,0 +1,124 @@
+use crate::ir::{CustomType, IntermediateRepresentation, Name, Type, TypeKind};
+use std::collections::HashMap;
+
+/// Hard keywords that have to be escaped with backticks when used as Kotlin identifiers.
+const KOTLIN_KEYWORDS: &[&str] = &[
+    "as",
+    "break",
+    "class",
+    "continue",
+    "do",
+    "else",
+    "false",
+    "for",
+    "fun",
+    "if",
+    "in",
+    "interface",
+    "is",
+    "null",
+    "object",
+    "package",
+    "return",
+    "super",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typealias",
+    "typeof",
+    "val",
+    "var",
+    "when",
+    "while",
+];
+
+/// Returns the identifier escaped with backticks if it is a reserved keyword.
+pub fn escape_kotlin_keyword(identifier: &str) -> String {
+    if KOTLIN_KEYWORDS.contains(&identifier) {
+        format!("`{identifier}`")
+    } else {
+        identifier.to_string()
+    }
+}
+
+/// This struct holds the state shared by Kotlin code generators during a single generation.
+pub struct KotlinContext {
+    interfaces: HashMap<String, Vec<String>>,
+    serial_names: HashMap<String, String>,
+}
+
+impl KotlinContext {
+    pub fn new(ir: &IntermediateRepresentation) -> Self {
+        let mut interfaces: HashMap<String, Vec<String>> = HashMap::new();
+        let mut serial_names = HashMap::new();
+        for p in ir.polymorphics() {
+            for variant in p.variants() {
+                let type_name = variant.variant_type().type_name();
+                interfaces
+                    .entry(type_name.clone())
+                    .or_default()
+                    .push(p.self_type().type_name().clone());
+                if p.tag().is_some() {
+                    serial_names
+                        .entry(type_name.clone())
+                        .or_insert_with(|| variant.json_name().clone());
+                }
+            }
+        }
+        Self {
+            interfaces,
+            serial_names,
+        }
+    }
+
+    /// Returns names of the sealed interfaces implemented by the class generated for the given type.
+    pub fn interfaces(&self, custom_type: &CustomType) -> Vec<String> {
+        self.interfaces
+            .get(custom_type.type_name())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Returns the tag value of the class generated for the given type, if it is a variant of a tagged polymorphic type.
+    pub fn serial_name(&self, custom_type: &CustomType) -> Option<&str> {
+        self.serial_names
+            .get(custom_type.type_name())
+            .map(|n| n.as_str())
+    }
+
+    /// Returns the name of a Kotlin property, with reserved keywords escaped with backticks.
+    pub fn property_name(&self, name: &Name) -> String {
+        escape_kotlin_keyword(&name.as_camel_case())
+    }
+
+    /// Returns the Kotlin type name. Optional types are nullable.
+    pub fn type_name(&self, t: &Type) -> String {
+        match t.kind() {
+            TypeKind::List(element) => format!("List<{}>", self.type_name(element)),
+            TypeKind::Map(key, value) => {
+                format!("Map<{}, {}>", self.type_name(key), self.type_name(value))
+            }
+            TypeKind::Option(inner) => format!("{}?", self.type_name(inner)),
+            TypeKind::UnsignedLong => "ULong".to_string(),
+            TypeKind::Named => match t.type_name().as_str() {
+                "Integer" => "Int".to_string(),
+                name => name.to_string(),
+            },
+        }
+    }
+
+    /// Returns a constructor parameter declaring a property, e.g. `val name: String`.
+    /// Optional properties default to `null`, so they can be missing in JSON.
+    pub fn property(&self, name: &Name, t: &Type) -> String {
+        let default = match t.kind() {
+            TypeKind::Option(_) => " = null",
+            _ => "",
+        };
+        format!(
+            "val {}: {}{default}",
+            self.property_name(name),
+            self.type_name(t)
+        )
+    }
+}
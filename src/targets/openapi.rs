@@ -0,0 +1,939 @@
+use crate::errors::Error;
+use crate::ir::{
+    DataEnum, DataEnumVariant, DateTimeFormat, Endpoint, FlagsFormat, IntermediateRepresentation,
+    ParamKind, Polymorphic, Struct, Type, TypeKind,
+};
+use crate::targets::json::Json;
+use std::collections::HashSet;
+use std::io::Write;
+
+/// This function generates an OpenAPI 3.0 document with the endpoints of all the services in the IR as paths
+/// and all the types in the IR as component schemas.
+/// It can be served to swagger-ui or used to generate clients in other languages.
+///
+/// Fields and parameters are required unless they are optional in Rust.
+/// Data enums are described with `oneOf` matching their externally tagged JSON representation
+/// and polymorphic types with `oneOf` of their variants, using a discriminator when they are tagged.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::targets::openapi::generate_openapi_document;
+/// use rdc::{rdc_service, RDC};
+///
+/// #[derive(RDC)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// rdc_service! {
+///     pub trait UserApi {
+///         #[rdc(get, "/users/{id}")]
+///         fn get_user(&self, id: String) -> User;
+///     }
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add_service_trait::<dyn UserApi>();
+/// let document = generate_openapi_document(&ir, "Users", "1.0.0").unwrap();
+/// assert!(document.contains("\"/users/{id}\""));
+/// ```
+pub fn generate_openapi_document(
+    ir: &IntermediateRepresentation,
+    title: &str,
+    version: &str,
+) -> Result<String, Error> {
+    ir.validate()?;
+    let generator = DocumentGenerator {
+        ir,
+        schemas_path: "#/components/schemas/",
+        nullable_options: false,
+    };
+    let schemas = generator.type_schemas(|_| true)?;
+
+    let mut paths: Vec<(String, Json)> = Vec::new();
+    for service in ir.services() {
+        let tag = service.name().as_pascal_case();
+        for endpoint in service.endpoints() {
+            let method = endpoint.method().as_str().to_lowercase();
+            let operation = generator.operation(endpoint, &tag)?;
+            match paths.iter_mut().find(|(path, _)| path == endpoint.path()) {
+                Some((_, Json::Map(operations))) => operations.push((method, operation)),
+                _ => paths.push((
+                    endpoint.path().to_string(),
+                    Json::Map(vec![(method, operation)]),
+                )),
+            }
+        }
+    }
+
+    let document = Json::Object(vec![
+        ("openapi", Json::string("3.0.3")),
+        (
+            "info",
+            Json::Object(vec![
+                ("title", Json::string(title)),
+                ("version", Json::string(version)),
+            ]),
+        ),
+        ("paths", Json::Map(paths)),
+        (
+            "components",
+            Json::Object(vec![("schemas", Json::Map(schemas))]),
+        ),
+    ]);
+    Ok(document.render())
+}
+
+/// This function generates a JSON Schema (draft 2020-12) of a single type, e.g. to validate payloads at runtime.
+/// The schemas are the same as the component schemas of the OpenAPI document,
+/// with the type and all the types it references under `$defs`.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::targets::openapi::generate_json_schema;
+/// use rdc::RDC;
+///
+/// #[derive(RDC)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add::<User>();
+/// let schema = generate_json_schema(&ir, "User").unwrap();
+/// assert!(schema.contains("\"$ref\": \"#/$defs/User\""));
+/// ```
+pub fn generate_json_schema(
+    ir: &IntermediateRepresentation,
+    type_name: &str,
+) -> Result<String, Error> {
+    let generator = DocumentGenerator {
+        ir,
+        schemas_path: "#/$defs/",
+        nullable_options: false,
+    };
+    let mut referenced = HashSet::new();
+    generator.collect_references(type_name, &mut referenced);
+    if referenced.is_empty() {
+        return Err(Error::new(&format!(
+            "`{type_name}` is not a type of the IR"
+        )));
+    }
+    let schemas = generator.type_schemas(|name| referenced.contains(name))?;
+    let schema = Json::Object(vec![
+        (
+            "$schema",
+            Json::string("https://json-schema.org/draft/2020-12/schema"),
+        ),
+        ("$ref", Json::string(generator.schema_path(type_name))),
+        ("$defs", Json::Map(schemas)),
+    ]);
+    Ok(schema.render())
+}
+
+/// This enum describes the syntax of generated OpenAPI components.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ComponentsFormat {
+    #[default]
+    Json,
+    /// YAML, which most hand-written API specs use. Strings are always quoted.
+    Yaml,
+}
+
+/// This function generates the `components/schemas` section of an OpenAPI 3.1 document with all the types in the IR,
+/// so that it can be merged into an existing API spec or referenced from it with `$ref`.
+///
+/// Unlike in OpenAPI 3.0 documents, `Option<T>` describes both sides of serde's behavior:
+/// the field is not required and its schema also accepts `null`,
+/// e.g. `type: ["string", "null"]`, or `anyOf` with `type: "null"` for references to other schemas.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::targets::openapi::{generate_openapi_components, ComponentsFormat};
+/// use rdc::RDC;
+///
+/// #[derive(RDC)]
+/// struct User {
+///     name: String,
+///     nickname: Option<String>,
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add::<User>();
+/// let components = generate_openapi_components(&ir, ComponentsFormat::Yaml).unwrap();
+/// assert!(components.starts_with("components:\n  schemas:\n    User:\n"));
+/// assert!(components.contains("type:\n            - \"string\"\n            - \"null\"\n"));
+/// ```
+pub fn generate_openapi_components(
+    ir: &IntermediateRepresentation,
+    format: ComponentsFormat,
+) -> Result<String, Error> {
+    ir.validate()?;
+    let generator = DocumentGenerator {
+        ir,
+        schemas_path: "#/components/schemas/",
+        nullable_options: true,
+    };
+    let schemas = generator.type_schemas(|_| true)?;
+    let components = Json::Object(vec![(
+        "components",
+        Json::Object(vec![("schemas", Json::Map(schemas))]),
+    )]);
+    Ok(match format {
+        ComponentsFormat::Json => components.render(),
+        ComponentsFormat::Yaml => components.render_yaml(),
+    })
+}
+
+/// This function generates OpenAPI 3.1 components and writes them to the given file.
+pub fn write_openapi_components(
+    ir: &IntermediateRepresentation,
+    format: ComponentsFormat,
+    file_path: &str,
+) -> Result<(), Error> {
+    let components = generate_openapi_components(ir, format)?;
+    if let Some(parent) = std::path::Path::new(file_path).parent() {
+        std::fs::create_dir_all(parent).map_err(|_| Error::new("Failed to create directory"))?;
+    }
+    let mut file =
+        std::fs::File::create(file_path).map_err(|_| Error::new("Failed to create file"))?;
+    file.write_all(components.as_bytes())
+        .map_err(|_| Error::new("Failed to write to file"))
+}
+
+/// This function generates an OpenAPI document and writes it to the given file.
+pub fn write_openapi_document(
+    ir: &IntermediateRepresentation,
+    title: &str,
+    version: &str,
+    file_path: &str,
+) -> Result<(), Error> {
+    let document = generate_openapi_document(ir, title, version)?;
+    if let Some(parent) = std::path::Path::new(file_path).parent() {
+        std::fs::create_dir_all(parent).map_err(|_| Error::new("Failed to create directory"))?;
+    }
+    let mut file =
+        std::fs::File::create(file_path).map_err(|_| Error::new("Failed to create file"))?;
+    file.write_all(document.as_bytes())
+        .map_err(|_| Error::new("Failed to write to file"))
+}
+
+struct DocumentGenerator<'a> {
+    ir: &'a IntermediateRepresentation,
+    schemas_path: &'static str,
+    /// Whether optional values accept `null`, which OpenAPI 3.1 can express with plain JSON Schema.
+    nullable_options: bool,
+}
+
+impl DocumentGenerator<'_> {
+    /// Returns schemas of the types of the IR accepted by the filter, keyed by their names.
+    fn type_schemas<F>(&self, filter: F) -> Result<Vec<(String, Json)>, Error>
+    where
+        F: Fn(&str) -> bool,
+    {
+        let ir = self.ir;
+        let mut schemas = Vec::new();
+        for s in ir.structs() {
+            if filter(s.self_type().type_name()) {
+                schemas.push((s.self_type().type_name().clone(), self.object(s)?));
+            }
+        }
+        for e in ir.enums() {
+            if !filter(e.self_type().type_name()) {
+                continue;
+            }
+            let values = e
+                .variants()
+                .iter()
+                .map(|v| Json::string(v.json_name().as_str()))
+                .collect();
+            let schema = match e.flags() {
+                Some(FlagsFormat::Bits) => Json::Object(vec![
+                    ("type", Json::string("integer")),
+                    ("format", Json::string("int64")),
+                ]),
+                Some(FlagsFormat::Names) => Json::Object(vec![("type", Json::string("string"))]),
+                None => Json::Object(vec![
+                    ("type", Json::string("string")),
+                    ("enum", Json::Array(values)),
+                ]),
+            };
+            schemas.push((e.self_type().type_name().clone(), schema));
+        }
+        for de in ir.data_enums() {
+            if filter(de.self_type().type_name()) {
+                schemas.push((de.self_type().type_name().clone(), self.data_enum(de)?));
+            }
+        }
+        for p in ir.polymorphics() {
+            if filter(p.self_type().type_name()) {
+                schemas.push((p.self_type().type_name().clone(), self.polymorphic(p)));
+            }
+        }
+        Ok(schemas)
+    }
+
+    /// Collects the names of the type and all the types of the IR it references.
+    fn collect_references(&self, name: &str, referenced: &mut HashSet<String>) {
+        let ir = self.ir;
+        let mut types = Vec::new();
+        if let Some(s) = ir
+            .structs()
+            .iter()
+            .find(|s| s.self_type().type_name() == name)
+        {
+            types.extend(s.fields().iter().map(|f| f.field_type()));
+        } else if let Some(de) = ir
+            .data_enums()
+            .iter()
+            .find(|de| de.self_type().type_name() == name)
+        {
+            for variant in de.variants() {
+                match variant {
+                    DataEnumVariant::Unit { .. } => {}
+                    DataEnumVariant::Tuple { fields, .. } => types.extend(fields.iter()),
+                    DataEnumVariant::Object { fields, .. } => {
+                        types.extend(fields.iter().map(|f| f.field_type()))
+                    }
+                }
+            }
+        } else if let Some(p) = ir
+            .polymorphics()
+            .iter()
+            .find(|p| p.self_type().type_name() == name)
+        {
+            types.extend(p.variants().iter().map(|v| v.variant_type()));
+        } else if !ir.enums().iter().any(|e| e.self_type().type_name() == name) {
+            return;
+        }
+        if !referenced.insert(name.to_string()) {
+            return;
+        }
+        for t in types {
+            self.collect_type_references(t, referenced);
+        }
+    }
+
+    fn collect_type_references(&self, t: &Type, referenced: &mut HashSet<String>) {
+        match t.kind() {
+            TypeKind::List(inner) | TypeKind::Option(inner) | TypeKind::Map(_, inner) => {
+                self.collect_type_references(inner, referenced)
+            }
+            TypeKind::Named => self.collect_references(t.type_name(), referenced),
+            TypeKind::UnsignedLong => {}
+        }
+    }
+
+    fn object(&self, s: &Struct) -> Result<Json, Error> {
+        if let (true, [field]) = (s.is_transparent(), s.fields().as_slice()) {
+            return self.schema(field.field_type());
+        }
+        let fields = s.fields().iter().map(|f| Property {
+            json_name: f.json_name(),
+            field_type: f.field_type(),
+            is_required: f.is_required(),
+            example: f.example(),
+            format: f.format(),
+        });
+        self.object_schema(fields)
+    }
+
+    /// Returns a schema of an object with the given properties, where only the optional ones may be missing.
+    /// Examples of properties are written as `example` in OpenAPI 3.0 and as `examples` in OpenAPI 3.1 and JSON schemas.
+    fn object_schema<'t, I>(&self, properties: I) -> Result<Json, Error>
+    where
+        I: Iterator<Item = Property<'t>>,
+    {
+        let mut schemas = Vec::new();
+        let mut required = Vec::new();
+        for property in properties {
+            let Property {
+                json_name,
+                field_type: t,
+                is_required,
+                example,
+                format,
+            } = property;
+            if is_required {
+                required.push(Json::string(json_name));
+            }
+            let mut schema = self.schema(t)?;
+            if let (Json::Object(entries), Some(format)) = (&mut schema, format) {
+                entries.extend(format_entries(format));
+            }
+            if let (Json::Object(entries), Some(example)) = (&mut schema, example) {
+                let example = Json::example(example, t);
+                entries.push(match (self.schemas_path, self.nullable_options) {
+                    ("#/$defs/", _) | (_, true) => ("examples", Json::Array(vec![example])),
+                    _ => ("example", example),
+                });
+            }
+            schemas.push((json_name.to_string(), schema));
+        }
+        let mut entries = vec![
+            ("type", Json::string("object")),
+            ("properties", Json::Map(schemas)),
+        ];
+        if !required.is_empty() {
+            entries.push(("required", Json::Array(required)));
+        }
+        Ok(Json::Object(entries))
+    }
+
+    /// Returns a schema of an object with a single required property, e.g. `{"Circle": {...}}`.
+    fn wrapper_schema(json_name: &str, schema: Json) -> Json {
+        Json::Object(vec![
+            ("type", Json::string("object")),
+            (
+                "properties",
+                Json::Map(vec![(json_name.to_string(), schema)]),
+            ),
+            ("required", Json::Array(vec![Json::string(json_name)])),
+        ])
+    }
+
+    fn data_enum(&self, de: &DataEnum) -> Result<Json, Error> {
+        let mut variants = Vec::new();
+        for variant in de.variants() {
+            let json_name = variant.json_name().as_str();
+            let schema = match variant {
+                DataEnumVariant::Unit { .. } => Json::Object(vec![
+                    ("type", Json::string("string")),
+                    ("enum", Json::Array(vec![Json::string(json_name)])),
+                ]),
+                DataEnumVariant::Object { fields, .. } => {
+                    let fields = fields.iter().map(|f| Property {
+                        json_name: f.json_name(),
+                        field_type: f.field_type(),
+                        is_required: f.is_required(),
+                        example: None,
+                        format: None,
+                    });
+                    Self::wrapper_schema(json_name, self.object_schema(fields)?)
+                }
+                DataEnumVariant::Tuple { fields, .. } if fields.len() == 1 => {
+                    Self::wrapper_schema(json_name, self.schema(&fields[0])?)
+                }
+                DataEnumVariant::Tuple { fields, .. } => {
+                    let items = fields
+                        .iter()
+                        .map(|f| self.schema(f))
+                        .collect::<Result<Vec<Json>, Error>>()?;
+                    let length = fields.len() as u32;
+                    let array = Json::Object(vec![
+                        ("type", Json::string("array")),
+                        ("items", Json::Object(vec![("oneOf", Json::Array(items))])),
+                        ("minItems", Json::Number(length)),
+                        ("maxItems", Json::Number(length)),
+                    ]);
+                    Self::wrapper_schema(json_name, array)
+                }
+            };
+            variants.push(schema);
+        }
+        Ok(Json::Object(vec![("oneOf", Json::Array(variants))]))
+    }
+
+    fn polymorphic(&self, p: &Polymorphic) -> Json {
+        match p.tag() {
+            Some(tag) => {
+                let variants = p
+                    .variants()
+                    .iter()
+                    .map(|v| self.reference(v.variant_type().type_name()))
+                    .collect();
+                let mapping = p
+                    .variants()
+                    .iter()
+                    .map(|v| {
+                        let target = self.schema_path(v.variant_type().type_name());
+                        (v.json_name().clone(), Json::string(target))
+                    })
+                    .collect();
+                Json::Object(vec![
+                    ("oneOf", Json::Array(variants)),
+                    (
+                        "discriminator",
+                        Json::Object(vec![
+                            ("propertyName", Json::string(tag)),
+                            ("mapping", Json::Map(mapping)),
+                        ]),
+                    ),
+                ])
+            }
+            None => {
+                let variants = p
+                    .variants()
+                    .iter()
+                    .map(|v| {
+                        let schema = self.reference(v.variant_type().type_name());
+                        Self::wrapper_schema(v.json_name(), schema)
+                    })
+                    .collect();
+                Json::Object(vec![("oneOf", Json::Array(variants))])
+            }
+        }
+    }
+
+    fn operation(&self, endpoint: &Endpoint, tag: &str) -> Result<Json, Error> {
+        let mut parameters = Vec::new();
+        let mut request_body = None;
+        for param in endpoint.params() {
+            let required = !matches!(param.param_type().kind(), TypeKind::Option(_));
+            let location = match param.kind() {
+                ParamKind::Path => "path",
+                ParamKind::Query => "query",
+                ParamKind::Body => {
+                    request_body = Some(Json::Object(vec![
+                        ("required", Json::Bool(required)),
+                        ("content", json_content(self.schema(param.param_type())?)),
+                    ]));
+                    continue;
+                }
+            };
+            parameters.push(Json::Object(vec![
+                ("name", Json::string(param.name().as_snake_case())),
+                ("in", Json::string(location)),
+                ("required", Json::Bool(required || location == "path")),
+                ("schema", self.schema(param.param_type())?),
+            ]));
+        }
+        let response = match endpoint.response() {
+            Some(response) => (
+                "200".to_string(),
+                Json::Object(vec![
+                    ("description", Json::string("OK")),
+                    ("content", json_content(self.schema(response)?)),
+                ]),
+            ),
+            None => (
+                "204".to_string(),
+                Json::Object(vec![("description", Json::string("No Content"))]),
+            ),
+        };
+        let mut entries = vec![
+            ("operationId", Json::string(endpoint.name().as_camel_case())),
+            ("tags", Json::Array(vec![Json::string(tag)])),
+        ];
+        if !parameters.is_empty() {
+            entries.push(("parameters", Json::Array(parameters)));
+        }
+        if let Some(request_body) = request_body {
+            entries.push(("requestBody", request_body));
+        }
+        entries.push(("responses", Json::Map(vec![response])));
+        Ok(Json::Object(entries))
+    }
+
+    fn schema(&self, t: &Type) -> Result<Json, Error> {
+        match t.kind() {
+            TypeKind::List(element) => Ok(Json::Object(vec![
+                ("type", Json::string("array")),
+                ("items", self.schema(element)?),
+            ])),
+            TypeKind::Map(_, value) => Ok(Json::Object(vec![
+                ("type", Json::string("object")),
+                ("additionalProperties", self.schema(value)?),
+            ])),
+            TypeKind::Option(inner) if self.nullable_options => Ok(nullable(self.schema(inner)?)),
+            TypeKind::Option(inner) => self.schema(inner),
+            TypeKind::UnsignedLong => Ok(Json::Object(vec![
+                ("type", Json::string("integer")),
+                ("minimum", Json::Number(0)),
+            ])),
+            TypeKind::Named => self.named_schema(t.type_name()),
+        }
+    }
+
+    fn schema_path(&self, name: &str) -> String {
+        format!("{}{name}", self.schemas_path)
+    }
+
+    fn reference(&self, name: &str) -> Json {
+        Json::Object(vec![("$ref", Json::string(self.schema_path(name)))])
+    }
+
+    fn named_schema(&self, name: &str) -> Result<Json, Error> {
+        let primitive = match name {
+            "Boolean" => Some(("boolean", None)),
+            "Byte" | "Short" | "Integer" => Some(("integer", Some("int32"))),
+            "Long" => Some(("integer", Some("int64"))),
+            "Float" => Some(("number", Some("float"))),
+            "Double" => Some(("number", Some("double"))),
+            "String" => Some(("string", None)),
+            _ => None,
+        };
+        if let Some((schema_type, format)) = primitive {
+            let mut entries = vec![("type", Json::string(schema_type))];
+            if let Some(format) = format {
+                entries.push(("format", Json::string(format)));
+            }
+            return Ok(Json::Object(entries));
+        }
+        if name == "com.fasterxml.jackson.databind.JsonNode" {
+            // An empty schema accepts any JSON value.
+            return Ok(Json::Object(vec![]));
+        }
+        let ir = self.ir;
+        let defined = ir
+            .structs()
+            .iter()
+            .any(|s| s.self_type().type_name() == name)
+            || ir.enums().iter().any(|e| e.self_type().type_name() == name)
+            || ir
+                .data_enums()
+                .iter()
+                .any(|de| de.self_type().type_name() == name)
+            || ir
+                .polymorphics()
+                .iter()
+                .any(|p| p.self_type().type_name() == name);
+        if defined {
+            Ok(self.reference(name))
+        } else {
+            Err(Error::new(&format!(
+                "`{name}` can not be represented in OpenAPI"
+            )))
+        }
+    }
+}
+
+/// A property of an object schema.
+struct Property<'t> {
+    json_name: &'t str,
+    field_type: &'t Type,
+    is_required: bool,
+    example: Option<&'t str>,
+    format: Option<&'t DateTimeFormat>,
+}
+
+/// Returns schema keywords describing the text format of a date or time field set with `set_field_format`.
+/// The `DateTimeFormatter` pattern is kept as `x-date-format`, and patterns producing RFC 3339 values
+/// also get the standard `format`, e.g. `date-time` for `yyyy-MM-dd'T'HH:mm:ssXXX`.
+fn format_entries(format: &DateTimeFormat) -> Vec<(&'static str, Json)> {
+    let utc = matches!(format.timezone(), Some("UTC" | "Z" | "GMT"));
+    let offset = |rest: &str| match rest {
+        "XXX" | "XXXXX" => true,
+        "X" => utc,
+        _ => false,
+    };
+    let time = |pattern: &str| {
+        let rest = pattern.strip_prefix("HH:mm:ss")?;
+        // Fractions of a second are optional in RFC 3339.
+        let rest = match rest.strip_prefix('.') {
+            Some(fraction) => fraction.trim_start_matches('S'),
+            None => rest,
+        };
+        Some(offset(rest))
+    };
+    let pattern = format.pattern();
+    let date = pattern
+        .strip_prefix("yyyy-MM-dd")
+        .or_else(|| pattern.strip_prefix("uuuu-MM-dd"));
+    let standard = match date {
+        Some("") => Some("date"),
+        Some(rest) => match rest.strip_prefix("'T'").and_then(time) {
+            Some(true) => Some("date-time"),
+            _ => None,
+        },
+        None => time(pattern).filter(|&t| t).map(|_| "time"),
+    };
+    let mut entries = Vec::new();
+    if let Some(standard) = standard {
+        entries.push(("format", Json::string(standard)));
+    }
+    entries.push(("x-date-format", Json::string(pattern)));
+    entries
+}
+
+/// Returns a schema which also accepts `null`, adding it to the type if there is a single one.
+fn nullable(schema: Json) -> Json {
+    let Json::Object(mut entries) = schema else {
+        return schema;
+    };
+    if entries.is_empty() {
+        // An empty schema already accepts null.
+        return Json::Object(entries);
+    }
+    match entries.iter_mut().find(|(key, _)| *key == "type") {
+        Some((_, schema_type @ Json::String(_))) => {
+            let single = std::mem::replace(schema_type, Json::Null);
+            *schema_type = Json::Array(vec![single, Json::string("null")]);
+            Json::Object(entries)
+        }
+        Some(_) => Json::Object(entries),
+        None => Json::Object(vec![(
+            "anyOf",
+            Json::Array(vec![
+                Json::Object(entries),
+                Json::Object(vec![("type", Json::string("null"))]),
+            ]),
+        )]),
+    }
+}
+
+fn json_content(schema: Json) -> Json {
+    Json::Object(vec![(
+        "application/json",
+        Json::Object(vec![("schema", schema)]),
+    )])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as rdc;
+    use crate::ir::TypeTarget;
+    use crate::{rdc_newtype, rdc_polymorphic, rdc_service, RDC};
+    use std::collections::HashMap;
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct User {
+        name: String,
+        nickname: Option<String>,
+        role: Role,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Role {
+        Admin,
+        Member,
+    }
+
+    rdc_service! {
+        #[allow(unused)]
+        trait UserApi {
+            #[rdc(get, "/users/{user_id}")]
+            fn get_user(&self, user_id: String) -> User;
+            #[rdc(put, "/users/{user_id}")]
+            fn update_user(&self, user_id: String, body: User) -> User;
+            #[rdc(get, "/users")]
+            fn list_users(&self, limit: Option<i32>) -> Vec<User>;
+            #[rdc(delete, "/users/{user_id}")]
+            fn delete_user(&self, user_id: String);
+        }
+    }
+
+    #[test]
+    fn generate_paths_and_components() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add_service_trait::<dyn UserApi>();
+        let document = generate_openapi_document(&ir, "Users", "1.0.0").unwrap();
+        println!("{document}");
+        let compact = document.split_whitespace().collect::<String>();
+        assert!(compact.contains("\"openapi\":\"3.0.3\""));
+        // Endpoints sharing a path are grouped under it.
+        assert_eq!(compact.matches("\"/users/{user_id}\":{").count(), 1);
+        assert!(compact.contains("\"get\":{\"operationId\":\"getUser\""));
+        assert!(compact.contains("\"put\":{\"operationId\":\"updateUser\""));
+        assert!(compact.contains("\"delete\":{\"operationId\":\"deleteUser\""));
+        assert!(compact.contains("\"in\":\"path\",\"required\":true"));
+        assert!(compact.contains("\"in\":\"query\",\"required\":false"));
+        assert!(compact.contains("\"requestBody\":{\"required\":true"));
+        assert!(compact.contains("\"204\":{\"description\":\"NoContent\""));
+        assert!(compact.contains("\"$ref\":\"#/components/schemas/User\""));
+        assert!(compact.contains("\"required\":[\"name\",\"role\"]"));
+        assert!(compact.contains("\"enum\":[\"Admin\",\"Member\"]"));
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Payment {
+        Cash,
+        Card { number: String },
+        Transfer(String, u64),
+    }
+
+    trait Shape {}
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Circle {
+        radius: f64,
+    }
+    impl Shape for Circle {}
+
+    rdc_polymorphic!(dyn Shape, tag = "kind" => Circle);
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Order {
+        payment: Payment,
+        shape: Box<dyn Shape>,
+    }
+
+    #[test]
+    fn generate_data_enum_and_polymorphic_schemas() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Order>();
+        let document = generate_openapi_document(&ir, "Orders", "1.0.0").unwrap();
+        println!("{document}");
+        let compact = document.split_whitespace().collect::<String>();
+        assert!(compact.contains("\"paths\":{}"));
+        assert!(compact.contains("\"Payment\":{\"oneOf\":["));
+        assert!(compact.contains("\"Transfer\":{\"type\":\"array\""));
+        assert!(compact.contains("\"minItems\":2"));
+        assert!(compact.contains("\"propertyName\":\"kind\""));
+        assert!(compact.contains("\"Circle\":\"#/components/schemas/Circle\""));
+    }
+
+    #[test]
+    fn generate_json_schema_of_type() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Order>();
+        ir.add::<User>();
+        let schema = generate_json_schema(&ir, "Order").unwrap();
+        println!("{schema}");
+        let compact = schema.split_whitespace().collect::<String>();
+        assert!(compact.starts_with(
+            "{\"$schema\":\"https://json-schema.org/draft/2020-12/schema\",\"$ref\":\"#/$defs/Order\""
+        ));
+        assert!(compact.contains("\"payment\":{\"$ref\":\"#/$defs/Payment\"}"));
+        assert!(compact.contains("\"Circle\":\"#/$defs/Circle\""));
+        assert!(compact.contains("\"Circle\":{\"type\":\"object\""));
+        assert!(!compact.contains("\"User\""));
+        let error = generate_json_schema(&ir, "Missing").err().unwrap();
+        assert_eq!(error.message(), "`Missing` is not a type of the IR");
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Booking {
+        day: String,
+        starts_at: String,
+        label: Option<String>,
+    }
+
+    #[test]
+    fn date_time_formats() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Booking>();
+        ir.set_field_format("Booking", "day", DateTimeFormat::new("yyyy-MM-dd"))
+            .unwrap();
+        let format = DateTimeFormat::new("yyyy-MM-dd'T'HH:mm:ss.SSSXXX");
+        ir.set_field_format("Booking", "starts_at", format).unwrap();
+        let format = DateTimeFormat::new("dd.MM.yyyy HH:mm");
+        ir.set_field_format("Booking", "label", format).unwrap();
+        let schema = generate_json_schema(&ir, "Booking").unwrap();
+        let compact = schema.split_whitespace().collect::<String>();
+        assert!(compact.contains(
+            "\"day\":{\"type\":\"string\",\"format\":\"date\",\"x-date-format\":\"yyyy-MM-dd\"}"
+        ));
+        assert!(compact.contains(
+            "\"format\":\"date-time\",\"x-date-format\":\"yyyy-MM-dd'T'HH:mm:ss.SSSXXX\""
+        ));
+        assert!(compact
+            .contains("\"label\":{\"type\":\"string\",\"x-date-format\":\"dd.MM.yyyyHH:mm\"}"));
+
+        let utc = DateTimeFormat::new("yyyy-MM-dd'T'HH:mm:ssX");
+        assert_eq!(format_entries(&utc).len(), 1);
+        let utc = utc.with_timezone("UTC");
+        assert!(
+            matches!(&format_entries(&utc)[0], ("format", Json::String(f)) if f == "date-time")
+        );
+    }
+
+    #[allow(unused)]
+    struct Tags(HashMap<String, String>);
+
+    rdc_newtype!(Tags => HashMap<String, String>);
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Article {
+        tags: Tags,
+    }
+
+    #[test]
+    fn transparent_struct_schema() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Article>();
+        let schema = generate_json_schema(&ir, "Article").unwrap();
+        let compact = schema.split_whitespace().collect::<String>();
+        assert!(compact.contains(
+            "\"Tags\":{\"type\":\"object\",\"additionalProperties\":{\"type\":\"string\"}}"
+        ));
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Invoice {
+        number: String,
+        total: Option<f64>,
+        lines: u32,
+    }
+
+    #[test]
+    fn field_examples() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Invoice>();
+        ir.set_field_example("Invoice", "number", "INV-001")
+            .unwrap();
+        ir.set_field_example("Invoice", "total", "12.50").unwrap();
+        ir.set_field_example("Invoice", "lines", "many").unwrap();
+        let document = generate_openapi_document(&ir, "Invoices", "1.0.0").unwrap();
+        println!("{document}");
+        let compact = document.split_whitespace().collect::<String>();
+        assert!(compact.contains("\"number\":{\"type\":\"string\",\"example\":\"INV-001\"}"));
+        assert!(compact.contains("\"format\":\"double\",\"example\":12.50}"));
+        assert!(compact.contains("\"format\":\"int64\",\"example\":\"many\"}"));
+        let schema = generate_json_schema(&ir, "Invoice").unwrap();
+        let compact = schema.split_whitespace().collect::<String>();
+        assert!(compact.contains("\"number\":{\"type\":\"string\",\"examples\":[\"INV-001\"]}"));
+        let error = ir
+            .set_field_example("Invoice", "due", "soon")
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.message(),
+            "`Invoice.due` is not a struct field in the IR"
+        );
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Profile {
+        user: User,
+        manager: Option<User>,
+        aliases: Option<Vec<String>>,
+        age: Option<u64>,
+    }
+
+    #[test]
+    fn openapi_31_components() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Profile>();
+        let components = generate_openapi_components(&ir, ComponentsFormat::Json).unwrap();
+        println!("{components}");
+        let compact = components.split_whitespace().collect::<String>();
+        assert!(compact.starts_with("{\"components\":{\"schemas\":{"));
+        assert!(!compact.contains("\"paths\""));
+        assert!(compact.contains("\"nickname\":{\"type\":[\"string\",\"null\"]}"));
+        assert!(compact.contains("\"required\":[\"name\",\"role\"]"));
+        assert!(compact.contains(
+            "\"manager\":{\"anyOf\":[{\"$ref\":\"#/components/schemas/User\"},{\"type\":\"null\"}]}"
+        ));
+        assert!(compact.contains(
+            "\"aliases\":{\"type\":[\"array\",\"null\"],\"items\":{\"type\":\"string\"}}"
+        ));
+        assert!(compact.contains("\"age\":{\"type\":[\"integer\",\"null\"],\"minimum\":0}"));
+        assert!(compact.contains("\"required\":[\"user\"]"));
+
+        let yaml = generate_openapi_components(&ir, ComponentsFormat::Yaml).unwrap();
+        println!("{yaml}");
+        assert!(yaml.starts_with("components:\n  schemas:\n"));
+        assert!(yaml.contains(
+            "        manager:\n          anyOf:\n            - $ref: \"#/components/schemas/User\"\n            - type: \"null\"\n"
+        ));
+        assert!(yaml.contains("      required:\n        - \"user\"\n"));
+
+        // OpenAPI 3.0 documents keep describing optional values as missing only.
+        let document = generate_openapi_document(&ir, "Profiles", "1.0.0").unwrap();
+        assert!(!document.contains("\"null\""));
+    }
+}
@@ -0,0 +1,393 @@
+use crate::errors::Error;
+use crate::ir::{EnumVariant, IntermediateRepresentation, Name};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::Write;
+
+/// This is a struct that represents a generated `.properties` file of a resource bundle.
+pub struct PropertiesFile {
+    file_name: String,
+    content: String,
+}
+
+impl PropertiesFile {
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+}
+
+/// This struct contains options that control generation of resource bundles.
+///
+/// Display names set here take precedence over the ones from `#[rdc(display = ...)]` and `#[rdc(display(de = ...))]`.
+/// Type and variant names are Rust names, e.g. `("OrderStatus", "PaymentPending")`.
+///
+/// Example:
+/// ```rust
+/// use rdc::targets::properties::PropertiesConfig;
+///
+/// let config = PropertiesConfig::new("labels")
+///     .with_display_name("OrderStatus", "PaymentPending", "Awaiting payment")
+///     .with_translation("de", "OrderStatus", "PaymentPending", "Zahlung ausstehend");
+/// assert_eq!(config.bundle_name(), "labels");
+/// ```
+pub struct PropertiesConfig {
+    bundle_name: String,
+    display_names: HashMap<(String, String), String>,
+    translations: BTreeMap<String, HashMap<(String, String), String>>,
+}
+
+impl Default for PropertiesConfig {
+    fn default() -> Self {
+        PropertiesConfig::new("messages")
+    }
+}
+
+impl PropertiesConfig {
+    /// Creates a configuration of a bundle with the given base name, e.g. `messages` for `messages.properties`.
+    pub fn new<S: Into<String>>(bundle_name: S) -> Self {
+        PropertiesConfig {
+            bundle_name: bundle_name.into(),
+            display_names: HashMap::new(),
+            translations: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the display name of a variant in the base bundle.
+    /// Variants without one are displayed as their names split into words, e.g. `Payment pending`.
+    pub fn with_display_name<S: Into<String>, V: Into<String>, D: Into<String>>(
+        mut self,
+        type_name: S,
+        variant_name: V,
+        display_name: D,
+    ) -> Self {
+        self.display_names
+            .insert((type_name.into(), variant_name.into()), display_name.into());
+        self
+    }
+
+    /// Sets the display name of a variant in the bundle of the given locale, e.g. `de` for `messages_de.properties`.
+    pub fn with_translation<L: Into<String>, S: Into<String>, V: Into<String>, D: Into<String>>(
+        mut self,
+        locale: L,
+        type_name: S,
+        variant_name: V,
+        display_name: D,
+    ) -> Self {
+        self.translations
+            .entry(locale.into())
+            .or_default()
+            .insert((type_name.into(), variant_name.into()), display_name.into());
+        self
+    }
+
+    pub fn bundle_name(&self) -> &str {
+        &self.bundle_name
+    }
+
+    pub fn display_name(&self, type_name: &str, variant_name: &str) -> Option<&str> {
+        self.display_names
+            .get(&(type_name.to_string(), variant_name.to_string()))
+            .map(|d| d.as_str())
+    }
+}
+
+/// This function generates `.properties` files of a resource bundle with display names of the variants of all enums,
+/// so that Java UIs can show them with `ResourceBundle.getBundle`.
+///
+/// Keys are the Rust type names followed by the names of the Java enum constants, e.g. `OrderStatus.PAYMENT_PENDING`.
+/// The base bundle holds every variant, and bundles of other locales hold their translations,
+/// falling back to the base bundle for the rest. Display names and translations set on the IR
+/// with `set_variant_display` and `set_variant_translation` are used unless the configuration overrides them.
+/// It fails if a display name is set for a variant that is not in the IR.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::targets::properties::{generate_properties, PropertiesConfig};
+/// use rdc::RDC;
+///
+/// #[derive(RDC)]
+/// enum OrderStatus {
+///     PaymentPending,
+///     Shipped,
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add::<OrderStatus>();
+/// let config = PropertiesConfig::new("labels").with_display_name("OrderStatus", "Shipped", "On its way");
+/// let files = generate_properties(&ir, &config).unwrap();
+/// assert_eq!(files[0].file_name(), "labels.properties");
+/// assert!(files[0].content().contains("OrderStatus.PAYMENT_PENDING=Payment pending\n"));
+/// assert!(files[0].content().contains("OrderStatus.SHIPPED=On its way\n"));
+/// ```
+pub fn generate_properties(
+    ir: &IntermediateRepresentation,
+    config: &PropertiesConfig,
+) -> Result<Vec<PropertiesFile>, Error> {
+    let enums = ir.enums().iter().map(|e| {
+        (
+            e.self_type(),
+            e.variants().iter().map(|v| (v.name(), Some(v))).collect(),
+        )
+    });
+    let data_enums = ir.data_enums().iter().map(|de| {
+        (
+            de.self_type(),
+            de.variants().iter().map(|v| (v.name(), None)).collect(),
+        )
+    });
+    let variants = enums
+        .chain(data_enums)
+        .flat_map(|(t, names): (_, Vec<(&Name, Option<&EnumVariant>)>)| {
+            names
+                .into_iter()
+                .map(move |(name, variant)| (t.type_name().clone(), name, variant))
+        })
+        .collect::<Vec<(String, &Name, Option<&EnumVariant>)>>();
+    let key =
+        |type_name: &str, variant: &Name| format!("{type_name}.{}", variant.as_upper_snake_case());
+    let configured = config
+        .display_names
+        .keys()
+        .chain(config.translations.values().flat_map(|t| t.keys()));
+    for (type_name, variant_name) in configured {
+        let known = variants
+            .iter()
+            .any(|(t, v, _)| t == type_name && v.as_pascal_case() == *variant_name);
+        if !known {
+            return Err(Error::new(&format!(
+                "`{type_name}::{variant_name}` is not a variant of an enum in the IR"
+            )));
+        }
+    }
+
+    let mut base = String::new();
+    for (type_name, variant, ir_variant) in &variants {
+        let display_name = config
+            .display_name(type_name, &variant.as_pascal_case())
+            .or_else(|| ir_variant.and_then(|v| v.display_name()))
+            .map(|d| d.to_string())
+            .unwrap_or_else(|| words(variant));
+        push_entry(&mut base, &key(type_name, variant), &display_name);
+    }
+    let mut files = vec![PropertiesFile {
+        file_name: format!("{}.properties", config.bundle_name),
+        content: base,
+    }];
+    let locales = config
+        .translations
+        .keys()
+        .chain(
+            variants
+                .iter()
+                .filter_map(|(_, _, v)| *v)
+                .flat_map(|v| v.translations().keys()),
+        )
+        .collect::<BTreeSet<&String>>();
+    for locale in locales {
+        let translations = config.translations.get(locale);
+        let mut content = String::new();
+        for (type_name, variant, ir_variant) in &variants {
+            let translation = translations
+                .and_then(|t| t.get(&(type_name.clone(), variant.as_pascal_case())))
+                .or_else(|| ir_variant.and_then(|v| v.translations().get(locale)));
+            if let Some(translation) = translation {
+                push_entry(&mut content, &key(type_name, variant), translation);
+            }
+        }
+        files.push(PropertiesFile {
+            file_name: format!("{}_{locale}.properties", config.bundle_name),
+            content,
+        });
+    }
+    Ok(files)
+}
+
+/// This function writes generated `.properties` files to the given directory.
+pub fn write_properties(files: &[PropertiesFile], directory: &str) -> Result<(), Error> {
+    std::fs::create_dir_all(directory).map_err(|_| Error::new("Failed to create directory"))?;
+    for properties in files {
+        let mut file_path = std::path::PathBuf::from(directory);
+        file_path.push(properties.file_name());
+        let mut file =
+            std::fs::File::create(file_path).map_err(|_| Error::new("Failed to create file"))?;
+        file.write_all(properties.content().as_bytes())
+            .map_err(|_| Error::new("Failed to write to file"))?;
+    }
+    Ok(())
+}
+
+/// Returns the name split into words, with only the first one capitalized, e.g. `Payment pending`.
+fn words(name: &Name) -> String {
+    let words = name.as_snake_case().replace('_', " ");
+    let mut chars = words.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => words,
+    }
+}
+
+fn push_entry(output: &mut String, key: &str, value: &str) {
+    output.push_str(&escape(key, true));
+    output.push('=');
+    output.push_str(&escape(value, false));
+    output.push('\n');
+}
+
+/// Escapes a key or a value, so that it can be read with `Properties.load`, which expects ISO 8859-1.
+fn escape(text: &str, key: bool) -> String {
+    let mut escaped = String::new();
+    for (i, c) in text.chars().enumerate() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            ' ' if key || i == 0 => escaped.push_str("\\ "),
+            '=' | ':' | '#' | '!' if key || i == 0 => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c if (c as u32) < 0x20 || (c as u32) > 0x7e => {
+                let mut units = [0u16; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    escaped.push_str(&format!("\\u{unit:04X}"));
+                }
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as rdc;
+    use crate::ir::TypeTarget;
+    use crate::RDC;
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Order {
+        status: OrderStatus,
+        payment: Payment,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum OrderStatus {
+        PaymentPending,
+        Shipped,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Payment {
+        Cash,
+        Card { number: String },
+    }
+
+    #[test]
+    fn resource_bundles() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Order>();
+        let config = PropertiesConfig::default()
+            .with_display_name("OrderStatus", "Shipped", "Shipped: on its way")
+            .with_translation("de", "OrderStatus", "Shipped", "Unterwegs")
+            .with_translation("fr", "Payment", "Card", "Carte bancaire")
+            .with_translation("pl", "Payment", "Cash", "Gotówka");
+        let files = generate_properties(&ir, &config).unwrap();
+        let names = files.iter().map(|f| f.file_name()).collect::<Vec<&str>>();
+        assert_eq!(
+            names,
+            [
+                "messages.properties",
+                "messages_de.properties",
+                "messages_fr.properties",
+                "messages_pl.properties"
+            ]
+        );
+        assert_eq!(
+            files[0].content(),
+            "OrderStatus.PAYMENT_PENDING=Payment pending\n\
+             OrderStatus.SHIPPED=Shipped: on its way\n\
+             Payment.CASH=Cash\n\
+             Payment.CARD=Card\n"
+        );
+        assert_eq!(files[1].content(), "OrderStatus.SHIPPED=Unterwegs\n");
+        assert_eq!(files[3].content(), "Payment.CASH=Got\\u00F3wka\n");
+    }
+
+    #[test]
+    fn unknown_variant() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Order>();
+        let config = PropertiesConfig::default().with_display_name("OrderStatus", "Lost", "Lost");
+        let error = generate_properties(&ir, &config).err().unwrap();
+        assert_eq!(
+            error.message(),
+            "`OrderStatus::Lost` is not a variant of an enum in the IR"
+        );
+    }
+
+    #[test]
+    fn translations_from_ir() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Order>();
+        ir.set_variant_display("OrderStatus", "Shipped", "Sent", None)
+            .unwrap();
+        ir.set_variant_translation("OrderStatus", "Shipped", "de", "Versandt")
+            .unwrap();
+        ir.set_variant_translation("OrderStatus", "PaymentPending", "de", "Zahlung ausstehend")
+            .unwrap();
+        ir.set_variant_translation("OrderStatus", "Shipped", "pl", "Wysłane")
+            .unwrap();
+        let config = PropertiesConfig::default().with_translation(
+            "de",
+            "OrderStatus",
+            "Shipped",
+            "Unterwegs",
+        );
+        let files = generate_properties(&ir, &config).unwrap();
+        assert!(files[0].content().contains("OrderStatus.SHIPPED=Sent\n"));
+        assert_eq!(files[1].file_name(), "messages_de.properties");
+        assert_eq!(
+            files[1].content(),
+            "OrderStatus.PAYMENT_PENDING=Zahlung ausstehend\n\
+             OrderStatus.SHIPPED=Unterwegs\n"
+        );
+        assert_eq!(files[2].content(), "OrderStatus.SHIPPED=Wys\\u0142ane\n");
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Delivery {
+        #[rdc(display = "Courier", display(de = "Kurier", pl = "Kurier"))]
+        Courier,
+        #[rdc(display(de = "Abholung"))]
+        Pickup,
+    }
+
+    #[test]
+    fn translations_from_attributes() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Delivery>();
+        let files = generate_properties(&ir, &PropertiesConfig::default()).unwrap();
+        assert_eq!(
+            files[0].content(),
+            "Delivery.COURIER=Courier\n\
+             Delivery.PICKUP=Pickup\n"
+        );
+        assert_eq!(files[1].file_name(), "messages_de.properties");
+        assert_eq!(
+            files[1].content(),
+            "Delivery.COURIER=Kurier\n\
+             Delivery.PICKUP=Abholung\n"
+        );
+        assert_eq!(files[2].content(), "Delivery.COURIER=Kurier\n");
+    }
+}
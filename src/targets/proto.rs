@@ -0,0 +1,658 @@
+use crate::errors::Error;
+use crate::ir::{
+    DataEnum, DataEnumVariant, Endpoint, Enum, IntermediateRepresentation, Name, Polymorphic,
+    Service, Struct, Type, TypeKind,
+};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+
+/// The largest field number allowed by Protobuf.
+const MAX_FIELD_NUMBER: u32 = 536_870_911;
+
+/// This struct contains options that control generation of proto files.
+///
+/// Fields and enum values are numbered in declaration order by default, so adding a field in the middle of a struct
+/// or removing one renumbers the following fields and breaks the binary compatibility of the messages.
+/// Numbers of such fields can be pinned here. Fields that are not pinned take the lowest numbers left, in declaration order.
+///
+/// Messages are named like in the generated file, e.g. `User`, `Credential.Password` for the message of a data enum variant,
+/// or `LoginRequest` for a request of an endpoint.
+/// Fields, `oneof` variants and enum values are named with Rust names in snake case, e.g. `("User", "user_name")`.
+///
+/// Example:
+/// ```rust
+/// use rdc::targets::proto::ProtoConfig;
+///
+/// let config = ProtoConfig::new()
+///     .with_field_number("User", "email", 5)
+///     .with_field_number("Credential", "token", 3);
+/// assert_eq!(config.field_number("User", "email"), Some(5));
+/// ```
+#[derive(Default)]
+pub struct ProtoConfig {
+    field_numbers: HashMap<(String, String), u32>,
+}
+
+impl ProtoConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of a field, a `oneof` variant or an enum value.
+    pub fn with_field_number<M: Into<String>, F: Into<String>>(
+        mut self,
+        message: M,
+        field_name: F,
+        number: u32,
+    ) -> Self {
+        self.field_numbers
+            .insert((message.into(), field_name.into()), number);
+        self
+    }
+
+    pub fn field_number(&self, message: &str, field_name: &str) -> Option<u32> {
+        self.field_numbers
+            .get(&(message.to_string(), field_name.to_string()))
+            .copied()
+    }
+}
+
+/// This function generates a proto3 file with messages for all the types in the IR
+/// and a gRPC service for every service in the IR.
+///
+/// Structs are translated to messages, unit enums to enums and data enums and polymorphic types
+/// to messages with a `oneof` of their variants.
+/// Every endpoint becomes an rpc taking a `<Endpoint>Request` message with all of its parameters.
+/// Responses which are not messages themselves are wrapped in a `<Endpoint>Response` message.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::targets::proto::generate_proto_file;
+/// use rdc::{rdc_service, RDC};
+///
+/// #[derive(RDC)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// rdc_service! {
+///     pub trait UserApi {
+///         #[rdc(get, "/users/{id}")]
+///         fn get_user(&self, id: String) -> User;
+///     }
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add_service_trait::<dyn UserApi>();
+/// let proto = generate_proto_file(&ir, "example.users").unwrap();
+/// assert!(proto.contains("rpc GetUser(GetUserRequest) returns (User);"));
+/// ```
+pub fn generate_proto_file(
+    ir: &IntermediateRepresentation,
+    package: &str,
+) -> Result<String, Error> {
+    generate_proto_file_with_config(ir, package, &ProtoConfig::default())
+}
+
+/// This function generates a proto3 file using the provided configuration.
+/// It fails if a pinned number is not a valid field number, is used twice in a message,
+/// or names a field that is not in the generated file.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::targets::proto::{generate_proto_file_with_config, ProtoConfig};
+/// use rdc::RDC;
+///
+/// #[derive(RDC)]
+/// struct User {
+///     name: String,
+///     nickname: String,
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add::<User>();
+/// let config = ProtoConfig::new().with_field_number("User", "name", 2);
+/// let proto = generate_proto_file_with_config(&ir, "example.users", &config).unwrap();
+/// assert!(proto.contains("  string name = 2;\n  string nickname = 1;\n"));
+/// ```
+pub fn generate_proto_file_with_config(
+    ir: &IntermediateRepresentation,
+    package: &str,
+    config: &ProtoConfig,
+) -> Result<String, Error> {
+    ir.validate()?;
+    ir.reject_transparent_structs("Protobuf")?;
+    let generator = ProtoGenerator {
+        ir,
+        config,
+        numbered: RefCell::new(HashSet::new()),
+    };
+    let mut blocks = Vec::new();
+    for s in ir.structs() {
+        blocks.push(generator.struct_message(s)?);
+    }
+    for e in ir.enums() {
+        blocks.push(generator.enum_definition(e)?);
+    }
+    for de in ir.data_enums() {
+        blocks.push(generator.data_enum_message(de)?);
+    }
+    for p in ir.polymorphics() {
+        blocks.push(generator.polymorphic_message(p)?);
+    }
+    for service in ir.services() {
+        blocks.extend(generator.service(service)?);
+    }
+    let mut pinned = config.field_numbers.keys().collect::<Vec<_>>();
+    pinned.sort();
+    let numbered = generator.numbered.borrow();
+    if let Some((message, field_name)) = pinned.into_iter().find(|key| !numbered.contains(*key)) {
+        return Err(Error::new(&format!(
+            "`{message}.{field_name}` is not a field of a generated message"
+        )));
+    }
+    let mut output = format!("syntax = \"proto3\";\n\npackage {package};\n");
+    for block in blocks {
+        output.push('\n');
+        for line in block {
+            output.push_str(&line);
+            output.push('\n');
+        }
+    }
+    Ok(output)
+}
+
+/// This function generates a proto3 file and writes it to the given path.
+pub fn write_proto_file(
+    ir: &IntermediateRepresentation,
+    package: &str,
+    file_path: &str,
+) -> Result<(), Error> {
+    write_proto_file_with_config(ir, package, &ProtoConfig::default(), file_path)
+}
+
+/// This function generates a proto3 file using the provided configuration and writes it to the given path.
+pub fn write_proto_file_with_config(
+    ir: &IntermediateRepresentation,
+    package: &str,
+    config: &ProtoConfig,
+    file_path: &str,
+) -> Result<(), Error> {
+    let proto = generate_proto_file_with_config(ir, package, config)?;
+    if let Some(parent) = std::path::Path::new(file_path).parent() {
+        std::fs::create_dir_all(parent).map_err(|_| Error::new("Failed to create directory"))?;
+    }
+    let mut file =
+        std::fs::File::create(file_path).map_err(|_| Error::new("Failed to create file"))?;
+    file.write_all(proto.as_bytes())
+        .map_err(|_| Error::new("Failed to write to file"))
+}
+
+/// This is a single field of a generated message.
+struct ProtoField {
+    name: String,
+    json_name: Option<String>,
+    field_type: String,
+}
+
+struct ProtoGenerator<'a> {
+    ir: &'a IntermediateRepresentation,
+    config: &'a ProtoConfig,
+    /// Fields that have been numbered so far, to find pinned numbers of fields that are not generated.
+    numbered: RefCell<HashSet<(String, String)>>,
+}
+
+impl ProtoGenerator<'_> {
+    fn struct_message(&self, s: &Struct) -> Result<Vec<String>, Error> {
+        let fields = s
+            .fields()
+            .iter()
+            .map(|f| self.field(f.name(), f.json_name(), f.field_type()))
+            .collect::<Result<Vec<ProtoField>, Error>>()?;
+        self.message(s.self_type().type_name(), &fields)
+    }
+
+    fn data_enum_message(&self, de: &DataEnum) -> Result<Vec<String>, Error> {
+        let mut nested = Vec::new();
+        let mut variants = Vec::new();
+        for variant in de.variants() {
+            let message_name = variant.name().as_pascal_case();
+            let fields = match variant {
+                DataEnumVariant::Unit { .. } => Vec::new(),
+                DataEnumVariant::Object { fields, .. } => fields
+                    .iter()
+                    .map(|f| self.field(f.name(), f.json_name(), f.field_type()))
+                    .collect::<Result<Vec<ProtoField>, Error>>()?,
+                DataEnumVariant::Tuple { fields, .. } => fields
+                    .iter()
+                    .enumerate()
+                    .map(|(i, t)| {
+                        let name = match fields.len() {
+                            1 => Name::from_snake_case("value"),
+                            _ => Name::from_snake_case(format!("value_{}", i + 1)),
+                        };
+                        self.field(&name, &name.as_camel_case(), t)
+                    })
+                    .collect::<Result<Vec<ProtoField>, Error>>()?,
+            };
+            let qualified_name = format!("{}.{message_name}", de.self_type().type_name());
+            let numbers = self.numbers(&qualified_name, fields.iter().map(|f| f.name.as_str()))?;
+            nested.extend(message(&message_name, &fields, &numbers));
+            variants.push(ProtoField {
+                name: variant.name().as_snake_case(),
+                json_name: json_name_option(&variant.name().as_camel_case(), variant.json_name()),
+                field_type: message_name,
+            });
+        }
+        self.oneof_message(de.self_type().type_name(), nested, &variants)
+    }
+
+    fn polymorphic_message(&self, p: &Polymorphic) -> Result<Vec<String>, Error> {
+        let variants = p
+            .variants()
+            .iter()
+            .map(|v| {
+                let name = Name::from_pascal_case(v.json_name());
+                ProtoField {
+                    name: name.as_snake_case(),
+                    json_name: json_name_option(&name.as_camel_case(), v.json_name()),
+                    field_type: v.variant_type().type_name().clone(),
+                }
+            })
+            .collect::<Vec<ProtoField>>();
+        self.oneof_message(p.self_type().type_name(), Vec::new(), &variants)
+    }
+
+    fn service(&self, service: &Service) -> Result<Vec<Vec<String>>, Error> {
+        let mut blocks = Vec::new();
+        let mut rpcs = Vec::new();
+        for endpoint in service.endpoints() {
+            let rpc_name = endpoint.name().as_pascal_case();
+            let request = format!("{rpc_name}Request");
+            let params = endpoint
+                .params()
+                .iter()
+                .map(|p| self.field(p.name(), &p.name().as_camel_case(), p.param_type()))
+                .collect::<Result<Vec<ProtoField>, Error>>()?;
+            blocks.push(self.message(&request, &params)?);
+            let response = match self.response_message(endpoint) {
+                Some(response) => response,
+                None => {
+                    let response = format!("{rpc_name}Response");
+                    let fields = match endpoint.response() {
+                        Some(t) => {
+                            let name = Name::from_snake_case("value");
+                            vec![self.field(&name, &name.as_camel_case(), t)?]
+                        }
+                        None => Vec::new(),
+                    };
+                    blocks.push(self.message(&response, &fields)?);
+                    response
+                }
+            };
+            rpcs.push(format!("  rpc {rpc_name}({request}) returns ({response});"));
+        }
+        let mut definition = vec![format!("service {} {{", service.name().as_pascal_case())];
+        definition.extend(rpcs);
+        definition.push("}".to_string());
+        blocks.push(definition);
+        Ok(blocks)
+    }
+
+    /// Returns the name of the message returned by the endpoint, if the response is a message.
+    fn response_message(&self, endpoint: &Endpoint) -> Option<String> {
+        let response = endpoint.response()?;
+        if !matches!(response.kind(), TypeKind::Named) {
+            return None;
+        }
+        let name = response.type_name();
+        let ir = self.ir;
+        let is_message = ir
+            .structs()
+            .iter()
+            .any(|s| s.self_type().type_name() == name)
+            || ir
+                .data_enums()
+                .iter()
+                .any(|de| de.self_type().type_name() == name)
+            || ir
+                .polymorphics()
+                .iter()
+                .any(|p| p.self_type().type_name() == name);
+        is_message.then(|| name.clone())
+    }
+
+    /// Proto3 enums must start with a zero value, so an `UNSPECIFIED` value is added before the variants.
+    /// Values are prefixed with the enum name, because they share a scope with other enums in the package.
+    fn enum_definition(&self, e: &Enum) -> Result<Vec<String>, Error> {
+        let name = e.self_type().type_name();
+        let prefix = Name::from_pascal_case(name).as_upper_snake_case();
+        let variants = e
+            .variants()
+            .iter()
+            .map(|v| v.name().as_snake_case())
+            .collect::<Vec<String>>();
+        let numbers = self.numbers(name, variants.iter().map(|v| v.as_str()))?;
+        let mut lines = vec![
+            format!("enum {name} {{"),
+            format!("  {prefix}_UNSPECIFIED = 0;"),
+        ];
+        for (variant, number) in e.variants().iter().zip(numbers) {
+            lines.push(format!(
+                "  {prefix}_{} = {number};",
+                variant.name().as_upper_snake_case()
+            ));
+        }
+        lines.push("}".to_string());
+        Ok(lines)
+    }
+
+    fn message(&self, name: &str, fields: &[ProtoField]) -> Result<Vec<String>, Error> {
+        let numbers = self.numbers(name, fields.iter().map(|f| f.name.as_str()))?;
+        Ok(message(name, fields, &numbers))
+    }
+
+    fn oneof_message(
+        &self,
+        name: &str,
+        nested: Vec<String>,
+        variants: &[ProtoField],
+    ) -> Result<Vec<String>, Error> {
+        let numbers = self.numbers(name, variants.iter().map(|f| f.name.as_str()))?;
+        let mut lines = vec![format!("message {name} {{")];
+        lines.extend(nested.into_iter().map(|line| format!("  {line}")));
+        lines.push("  oneof variant {".to_string());
+        lines.extend(
+            variants
+                .iter()
+                .zip(numbers)
+                .map(|(f, n)| format!("  {}", field_line(f, n))),
+        );
+        lines.push("  }".to_string());
+        lines.push("}".to_string());
+        Ok(lines)
+    }
+
+    /// Returns the numbers of the fields of a message: pinned numbers from the config,
+    /// and the lowest numbers not pinned in the message for the other fields, in declaration order.
+    fn numbers<'f, I>(&self, message: &str, field_names: I) -> Result<Vec<u32>, Error>
+    where
+        I: Iterator<Item = &'f str>,
+    {
+        let field_names = field_names.collect::<Vec<&str>>();
+        let mut pinned: HashMap<u32, &str> = HashMap::new();
+        for field_name in &field_names {
+            let Some(number) = self.config.field_number(message, field_name) else {
+                continue;
+            };
+            self.numbered
+                .borrow_mut()
+                .insert((message.to_string(), field_name.to_string()));
+            if number == 0 || number > MAX_FIELD_NUMBER || (19_000..20_000).contains(&number) {
+                return Err(Error::new(&format!(
+                    "{number} is not a valid Protobuf field number for `{message}.{field_name}`"
+                )));
+            }
+            if let Some(other) = pinned.insert(number, field_name) {
+                return Err(Error::new(&format!(
+                    "Field number {number} is used by both `{message}.{other}` and `{message}.{field_name}`"
+                )));
+            }
+        }
+        let mut next = 1;
+        let mut numbers = Vec::new();
+        for field_name in field_names {
+            match self.config.field_number(message, field_name) {
+                Some(number) => numbers.push(number),
+                None => {
+                    while pinned.contains_key(&next) {
+                        next += 1;
+                    }
+                    numbers.push(next);
+                    next += 1;
+                }
+            }
+        }
+        Ok(numbers)
+    }
+
+    fn field(&self, name: &Name, json_name: &str, t: &Type) -> Result<ProtoField, Error> {
+        Ok(ProtoField {
+            name: name.as_snake_case(),
+            json_name: json_name_option(&name.as_camel_case(), json_name),
+            field_type: self.field_type(t)?,
+        })
+    }
+
+    fn field_type(&self, t: &Type) -> Result<String, Error> {
+        match t.kind() {
+            TypeKind::List(element) => Ok(format!("repeated {}", self.singular_type(element)?)),
+            TypeKind::Map(key, value) => {
+                let key_type = self.singular_type(key)?;
+                if !is_valid_map_key(&key_type) {
+                    return Err(Error::new(&format!(
+                        "Protobuf map keys must be integers or strings, but `{}` was used",
+                        key.type_name()
+                    )));
+                }
+                Ok(format!("map<{key_type}, {}>", self.singular_type(value)?))
+            }
+            TypeKind::Option(inner) => Ok(format!("optional {}", self.singular_type(inner)?)),
+            TypeKind::UnsignedLong | TypeKind::Named => self.singular_type(t),
+        }
+    }
+
+    /// Returns the type of a field which is neither repeated nor optional, e.g. an element of a list.
+    fn singular_type(&self, t: &Type) -> Result<String, Error> {
+        match t.kind() {
+            TypeKind::UnsignedLong => Ok("uint64".to_string()),
+            TypeKind::Named => self.named_type(t.type_name()),
+            _ => Err(Error::new(&format!(
+                "`{}` can not be nested in Protobuf",
+                t.type_name()
+            ))),
+        }
+    }
+
+    fn named_type(&self, name: &str) -> Result<String, Error> {
+        let scalar = match name {
+            "Boolean" => Some("bool"),
+            "Byte" | "Short" | "Integer" => Some("int32"),
+            "Long" => Some("int64"),
+            "Float" => Some("float"),
+            "Double" => Some("double"),
+            "String" => Some("string"),
+            _ => None,
+        };
+        if let Some(scalar) = scalar {
+            return Ok(scalar.to_string());
+        }
+        let ir = self.ir;
+        let defined = ir
+            .structs()
+            .iter()
+            .any(|s| s.self_type().type_name() == name)
+            || ir.enums().iter().any(|e| e.self_type().type_name() == name)
+            || ir
+                .data_enums()
+                .iter()
+                .any(|de| de.self_type().type_name() == name)
+            || ir
+                .polymorphics()
+                .iter()
+                .any(|p| p.self_type().type_name() == name);
+        if defined {
+            Ok(name.to_string())
+        } else {
+            Err(Error::new(&format!(
+                "`{name}` can not be represented in Protobuf"
+            )))
+        }
+    }
+}
+
+fn message(name: &str, fields: &[ProtoField], numbers: &[u32]) -> Vec<String> {
+    if fields.is_empty() {
+        return vec![format!("message {name} {{}}")];
+    }
+    let mut lines = vec![format!("message {name} {{")];
+    lines.extend(fields.iter().zip(numbers).map(|(f, n)| field_line(f, *n)));
+    lines.push("}".to_string());
+    lines
+}
+
+fn field_line(field: &ProtoField, number: u32) -> String {
+    match &field.json_name {
+        Some(json_name) => format!(
+            "  {} {} = {number} [json_name = \"{json_name}\"];",
+            field.field_type, field.name
+        ),
+        None => format!("  {} {} = {number};", field.field_type, field.name),
+    }
+}
+
+/// Protobuf derives lowerCamelCase JSON names from field names, so only different names have to be set.
+fn json_name_option(default: &str, json_name: &str) -> Option<String> {
+    (default != json_name).then(|| json_name.to_string())
+}
+
+fn is_valid_map_key(proto_type: &str) -> bool {
+    matches!(proto_type, "int32" | "int64" | "uint64" | "string" | "bool")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as rdc;
+    use crate::ir::TypeTarget;
+    use crate::{rdc_service, RDC};
+    use std::collections::HashMap;
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct User {
+        user_name: String,
+        #[serde(rename = "mail")]
+        email: Option<String>,
+        tags: Vec<String>,
+        scores: HashMap<String, u64>,
+        role: Role,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Role {
+        Admin,
+        Member,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Credential {
+        Anonymous,
+        Password { secret: String },
+        Token(String),
+    }
+
+    rdc_service! {
+        #[allow(unused)]
+        trait UserApi {
+            #[rdc(get, "/users/{user_id}")]
+            fn get_user(&self, user_id: String) -> User;
+            #[rdc(post, "/users/{user_id}/login")]
+            fn login(&self, user_id: String, body: Credential) -> String;
+            #[rdc(delete, "/users/{user_id}")]
+            fn delete_user(&self, user_id: String);
+        }
+    }
+
+    #[test]
+    fn generate_messages_and_service() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add_service_trait::<dyn UserApi>();
+        let proto = generate_proto_file(&ir, "example.users").unwrap();
+        println!("{proto}");
+        assert!(proto.starts_with("syntax = \"proto3\";\n\npackage example.users;\n"));
+        // Serde keeps Rust field names, while Protobuf would use lowerCamelCase in JSON.
+        assert!(proto.contains("  string user_name = 1 [json_name = \"user_name\"];\n"));
+        assert!(proto.contains("  optional string email = 2 [json_name = \"mail\"];\n"));
+        assert!(proto.contains("  repeated string tags = 3;\n"));
+        assert!(proto.contains("  map<string, uint64> scores = 4;\n"));
+        assert!(proto.contains("  ROLE_UNSPECIFIED = 0;\n  ROLE_ADMIN = 1;\n"));
+        assert!(proto.contains("  message Anonymous {}\n"));
+        assert!(proto.contains("    Password password = 2 [json_name = \"Password\"];\n"));
+        assert!(proto
+            .contains("message LoginRequest {\n  string user_id = 1;\n  Credential body = 2;\n}"));
+        assert!(proto.contains("message LoginResponse {\n  string value = 1;\n}"));
+        assert!(proto.contains("message DeleteUserResponse {}"));
+        assert!(proto.contains("  rpc GetUser(GetUserRequest) returns (User);\n"));
+        assert!(proto.contains("  rpc Login(LoginRequest) returns (LoginResponse);\n"));
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Matrix {
+        rows: Vec<Vec<i32>>,
+    }
+
+    #[test]
+    fn nested_lists_are_not_supported() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Matrix>();
+        let error = generate_proto_file(&ir, "example").err().unwrap();
+        assert_eq!(
+            error.message(),
+            "`java.util.List<Integer>` can not be nested in Protobuf"
+        );
+    }
+
+    #[test]
+    fn pinned_field_numbers() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add_service_trait::<dyn UserApi>();
+        let config = ProtoConfig::new()
+            .with_field_number("User", "tags", 1)
+            .with_field_number("User", "role", 10)
+            .with_field_number("Role", "member", 5)
+            .with_field_number("Credential", "token", 7)
+            .with_field_number("Credential.Password", "secret", 2)
+            .with_field_number("LoginRequest", "body", 1);
+        let proto = generate_proto_file_with_config(&ir, "example.users", &config).unwrap();
+        println!("{proto}");
+        assert!(proto.contains(
+            "  string user_name = 2 [json_name = \"user_name\"];\n  optional string email = 3 [json_name = \"mail\"];\n  repeated string tags = 1;\n  map<string, uint64> scores = 4;\n  Role role = 10;\n"
+        ));
+        assert!(proto.contains("  ROLE_ADMIN = 1;\n  ROLE_MEMBER = 5;\n"));
+        assert!(proto.contains("  message Password {\n    string secret = 2;\n  }\n"));
+        assert!(proto.contains("    Password password = 2 [json_name = \"Password\"];\n    Token token = 7 [json_name = \"Token\"];\n"));
+        assert!(proto
+            .contains("message LoginRequest {\n  string user_id = 2;\n  Credential body = 1;\n}"));
+
+        let error = |config: ProtoConfig| {
+            generate_proto_file_with_config(&ir, "example.users", &config)
+                .err()
+                .unwrap()
+                .message()
+                .to_string()
+        };
+        assert_eq!(
+            error(ProtoConfig::new().with_field_number("User", "email", 19_500)),
+            "19500 is not a valid Protobuf field number for `User.email`"
+        );
+        assert_eq!(
+            error(
+                ProtoConfig::new()
+                    .with_field_number("User", "email", 4)
+                    .with_field_number("User", "tags", 4)
+            ),
+            "Field number 4 is used by both `User.email` and `User.tags`"
+        );
+        assert_eq!(
+            error(ProtoConfig::new().with_field_number("User", "password", 4)),
+            "`User.password` is not a field of a generated message"
+        );
+    }
+}
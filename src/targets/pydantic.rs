@@ -0,0 +1,449 @@
+use crate::errors::Error;
+use crate::ir::{
+    DataEnum, DataEnumVariant, Enum, FlagsFormat, IntermediateRepresentation, Name, Polymorphic,
+    Struct, Type, TypeKind,
+};
+use crate::targets::json::Json;
+use crate::targets::python::attribute_name;
+use std::collections::HashSet;
+use std::io::Write;
+
+/// Integer types constrained to the range of their Rust counterparts, so out of range values fail validation.
+const INTEGER_TYPES: &[&str] = &[
+    "Int8 = Annotated[int, Field(ge=-128, le=127)]",
+    "Int16 = Annotated[int, Field(ge=-32768, le=32767)]",
+    "Int32 = Annotated[int, Field(ge=-2147483648, le=2147483647)]",
+    "Int64 = Annotated[int, Field(ge=-9223372036854775808, le=9223372036854775807)]",
+    "UInt64 = Annotated[int, Field(ge=0, le=18446744073709551615)]",
+];
+
+/// This function generates a Python module with Pydantic v2 models validating the JSON of the types in the IR,
+/// e.g. for request and response bodies of a FastAPI service.
+///
+/// Structs are translated to `BaseModel` subclasses with snake case fields, aliased to their JSON names when they differ,
+/// and `Optional[...]` fields defaulting to `None`. Integers are constrained to the range of their type in the IR,
+/// which is the Java one, so e.g. `u8` accepts the values of an `i16`.
+/// Unit enums become `Literal` unions of their JSON names. Data enums become unions following serde's external tagging,
+/// e.g. `Union[Literal["Point"], ShapeCircle]`, where `ShapeCircle` has a single `Circle` field and forbids other keys.
+/// Internally tagged polymorphic types become discriminated unions of subclasses of their variants with the tag field.
+/// Models have to be dumped with `model_dump(by_alias=True)` to get the JSON names.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::targets::pydantic::generate_pydantic_models;
+/// use rdc::RDC;
+///
+/// #[derive(RDC)]
+/// struct User {
+///     name: String,
+///     #[serde(rename = "nick")]
+///     nickname: Option<String>,
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add::<User>();
+/// let module = generate_pydantic_models(&ir).unwrap();
+/// assert!(module.contains("    name: str\n    nickname: Optional[str] = Field(None, alias=\"nick\")\n"));
+/// ```
+pub fn generate_pydantic_models(ir: &IntermediateRepresentation) -> Result<String, Error> {
+    ir.validate()?;
+    ir.reject_transparent_structs("Pydantic")?;
+    let generator = PydanticGenerator::new(ir);
+    let mut definitions = Vec::new();
+    let mut models = Vec::new();
+    for e in ir.enums() {
+        definitions.push(generator.enum_alias(e));
+    }
+    for s in ir.structs() {
+        definitions.push(generator.model(s)?);
+        models.push(s.self_type().type_name().clone());
+    }
+    for de in ir.data_enums() {
+        definitions.extend(generator.data_enum_models(de, &mut models)?);
+    }
+    for p in ir.polymorphics() {
+        definitions.extend(generator.polymorphic_models(p, &mut models)?);
+    }
+    let mut module = [
+        "from __future__ import annotations",
+        "",
+        "from typing import Annotated, Any, Dict, List, Literal, Optional, Tuple, Union",
+        "",
+        "from pydantic import BaseModel, ConfigDict, Field",
+        "",
+        "",
+    ]
+    .join("\n");
+    module.push_str(&INTEGER_TYPES.join("\n"));
+    for definition in definitions {
+        module.push_str("\n\n\n");
+        module.push_str(&definition.join("\n"));
+    }
+    // Models are declared before the types they reference, so they are rebuilt once everything is defined.
+    if !models.is_empty() {
+        module.push_str("\n\n\n");
+        for model in models {
+            module.push_str(&format!("{model}.model_rebuild()\n"));
+        }
+    } else {
+        module.push('\n');
+    }
+    Ok(module)
+}
+
+/// This function generates a Python module with Pydantic models and writes it to the given path, e.g. `api/models.py`.
+pub fn write_pydantic_models(
+    ir: &IntermediateRepresentation,
+    file_path: &str,
+) -> Result<(), Error> {
+    let module = generate_pydantic_models(ir)?;
+    if let Some(parent) = std::path::Path::new(file_path).parent() {
+        std::fs::create_dir_all(parent).map_err(|_| Error::new("Failed to create directory"))?;
+    }
+    let mut file =
+        std::fs::File::create(file_path).map_err(|_| Error::new("Failed to create file"))?;
+    file.write_all(module.as_bytes())
+        .map_err(|_| Error::new("Failed to write to file"))
+}
+
+struct PydanticGenerator {
+    types: HashSet<String>,
+}
+
+/// This struct describes a field of a generated model, with its type hint already rendered.
+struct ModelField {
+    attribute: String,
+    json_name: String,
+    hint: String,
+    optional: bool,
+}
+
+impl PydanticGenerator {
+    fn new(ir: &IntermediateRepresentation) -> Self {
+        let types = ir
+            .structs()
+            .iter()
+            .map(|s| s.self_type())
+            .chain(ir.enums().iter().map(|e| e.self_type()))
+            .chain(ir.data_enums().iter().map(|de| de.self_type()))
+            .chain(ir.polymorphics().iter().map(|p| p.self_type()))
+            .map(|t| t.type_name().clone())
+            .collect();
+        Self { types }
+    }
+
+    fn enum_alias(&self, e: &Enum) -> Vec<String> {
+        let name = e.self_type().type_name();
+        let alias = match e.flags() {
+            Some(FlagsFormat::Bits) => "Annotated[int, Field(ge=0)]".to_string(),
+            Some(FlagsFormat::Names) => "str".to_string(),
+            None => {
+                let literals = e
+                    .variants()
+                    .iter()
+                    .map(|v| Json::string(v.json_name()).render())
+                    .collect::<Vec<String>>();
+                format!("Literal[{}]", literals.join(", "))
+            }
+        };
+        vec![format!("{name} = {alias}")]
+    }
+
+    fn model(&self, s: &Struct) -> Result<Vec<String>, Error> {
+        let fields = s
+            .fields()
+            .iter()
+            .map(|f| self.model_field(f.name(), f.json_name(), f.field_type()))
+            .collect::<Result<Vec<ModelField>, Error>>()?;
+        Ok(base_model(s.self_type().type_name(), &fields, false))
+    }
+
+    fn model_field(&self, name: &Name, json_name: &str, t: &Type) -> Result<ModelField, Error> {
+        Ok(ModelField {
+            attribute: attribute_name(name),
+            json_name: json_name.to_string(),
+            hint: self.type_hint(t)?,
+            optional: matches!(t.kind(), TypeKind::Option(_)),
+        })
+    }
+
+    fn data_enum_models(
+        &self,
+        de: &DataEnum,
+        models: &mut Vec<String>,
+    ) -> Result<Vec<Vec<String>>, Error> {
+        let name = de.self_type().type_name();
+        let mut definitions = Vec::new();
+        let mut members = Vec::new();
+        for v in de.variants() {
+            let model_name = format!("{name}{}", v.name().as_pascal_case());
+            let payload = match v {
+                DataEnumVariant::Unit { json_name, .. } => {
+                    members.push(format!("Literal[{}]", Json::string(json_name).render()));
+                    continue;
+                }
+                DataEnumVariant::Tuple { fields, .. } if fields.len() == 1 => {
+                    self.type_hint(&fields[0])?
+                }
+                DataEnumVariant::Tuple { fields, .. } if fields.is_empty() => {
+                    "Tuple[()]".to_string()
+                }
+                DataEnumVariant::Tuple { fields, .. } => {
+                    let hints = fields
+                        .iter()
+                        .map(|t| self.type_hint(t))
+                        .collect::<Result<Vec<String>, Error>>()?;
+                    format!("Tuple[{}]", hints.join(", "))
+                }
+                DataEnumVariant::Object { fields, .. } => {
+                    let value_name = format!("{model_name}Value");
+                    let fields = fields
+                        .iter()
+                        .map(|f| self.model_field(f.name(), f.json_name(), f.field_type()))
+                        .collect::<Result<Vec<ModelField>, Error>>()?;
+                    definitions.push(base_model(&value_name, &fields, false));
+                    models.push(value_name.clone());
+                    value_name
+                }
+            };
+            let field = ModelField {
+                attribute: attribute_name(v.name()),
+                json_name: v.json_name().to_string(),
+                hint: payload,
+                optional: false,
+            };
+            definitions.push(base_model(&model_name, &[field], true));
+            models.push(model_name.clone());
+            members.push(model_name);
+        }
+        definitions.push(vec![format!("{name} = Union[{}]", members.join(", "))]);
+        Ok(definitions)
+    }
+
+    fn polymorphic_models(
+        &self,
+        p: &Polymorphic,
+        models: &mut Vec<String>,
+    ) -> Result<Vec<Vec<String>>, Error> {
+        let name = p.self_type().type_name();
+        let mut definitions = Vec::new();
+        let mut members = Vec::new();
+        for v in p.variants() {
+            let variant_type = v.variant_type().type_name();
+            let model_name = format!("{name}{variant_type}");
+            let json_name = Json::string(v.json_name()).render();
+            match p.tag() {
+                Some(tag) => {
+                    let attribute = attribute_name(&Name::from_snake_case(tag));
+                    let field = match attribute == tag {
+                        true => format!("Field({json_name})"),
+                        false => {
+                            format!("Field({json_name}, alias={})", Json::string(tag).render())
+                        }
+                    };
+                    let mut lines = vec![format!("class {model_name}({variant_type}):")];
+                    if attribute != tag {
+                        lines.push(
+                            "    model_config = ConfigDict(populate_by_name=True)".to_string(),
+                        );
+                    }
+                    lines.push(format!("    {attribute}: Literal[{json_name}] = {field}"));
+                    definitions.push(lines);
+                }
+                None => {
+                    let field_name = Name::from_pascal_case(variant_type);
+                    let field = self.model_field(&field_name, v.json_name(), v.variant_type())?;
+                    definitions.push(base_model(&model_name, &[field], true));
+                }
+            }
+            models.push(model_name.clone());
+            members.push(model_name);
+        }
+        let alias = match (p.tag(), members.len()) {
+            (_, 1) => members.remove(0),
+            (Some(tag), _) => format!(
+                "Annotated[Union[{}], Field(discriminator={})]",
+                members.join(", "),
+                Json::string(attribute_name(&Name::from_snake_case(tag))).render()
+            ),
+            (None, _) => format!("Union[{}]", members.join(", ")),
+        };
+        definitions.push(vec![format!("{name} = {alias}")]);
+        Ok(definitions)
+    }
+
+    /// Returns the Python type hint of a value. Integers are constrained to the range of their Rust type.
+    fn type_hint(&self, t: &Type) -> Result<String, Error> {
+        match t.kind() {
+            TypeKind::List(element) => Ok(format!("List[{}]", self.type_hint(element)?)),
+            TypeKind::Map(key, value) => Ok(format!(
+                "Dict[{}, {}]",
+                self.type_hint(key)?,
+                self.type_hint(value)?
+            )),
+            TypeKind::Option(inner) => Ok(format!("Optional[{}]", self.type_hint(inner)?)),
+            TypeKind::UnsignedLong => Ok("UInt64".to_string()),
+            TypeKind::Named => {
+                let name = t.type_name().as_str();
+                match name {
+                    "Boolean" => Ok("bool".to_string()),
+                    "Byte" => Ok("Int8".to_string()),
+                    "Short" => Ok("Int16".to_string()),
+                    "Integer" => Ok("Int32".to_string()),
+                    "Long" => Ok("Int64".to_string()),
+                    "java.math.BigInteger" => Ok("int".to_string()),
+                    "Float" | "Double" => Ok("float".to_string()),
+                    "String" => Ok("str".to_string()),
+                    "com.fasterxml.jackson.databind.JsonNode" => Ok("Any".to_string()),
+                    _ if self.types.contains(name) => Ok(name.to_string()),
+                    _ => Err(Error::new(&format!("`{name}` has no Pydantic counterpart"))),
+                }
+            }
+        }
+    }
+}
+
+/// Returns a model with the given fields, aliased to their JSON names when they differ.
+/// Models wrapping a variant forbid other keys, so that an object with several variants is rejected
+/// instead of matching the first one.
+fn base_model(name: &str, fields: &[ModelField], forbid_extra: bool) -> Vec<String> {
+    let mut lines = vec![format!("class {name}(BaseModel):")];
+    let mut config = Vec::new();
+    if fields.iter().any(|f| f.attribute != f.json_name) {
+        config.push("populate_by_name=True");
+    }
+    if forbid_extra {
+        config.push("extra=\"forbid\"");
+    }
+    if !config.is_empty() {
+        lines.push(format!(
+            "    model_config = ConfigDict({})",
+            config.join(", ")
+        ));
+    }
+    for f in fields {
+        let alias = match f.attribute == f.json_name {
+            true => None,
+            false => Some(format!("alias={}", Json::string(&f.json_name).render())),
+        };
+        let default = match (f.optional, alias) {
+            (true, None) => " = None".to_string(),
+            (true, Some(alias)) => format!(" = Field(None, {alias})"),
+            (false, None) => String::new(),
+            (false, Some(alias)) => format!(" = Field({alias})"),
+        };
+        lines.push(format!("    {}: {}{default}", f.attribute, f.hint));
+    }
+    if lines.len() == 1 {
+        lines.push("    pass".to_string());
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as rdc;
+    use crate::ir::TypeTarget;
+    use crate::{rdc_polymorphic, RDC};
+    use std::collections::HashMap;
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct User {
+        #[serde(rename = "user-name")]
+        name: String,
+        age: u8,
+        nickname: Option<String>,
+        class: Role,
+        limits: HashMap<Role, Vec<Option<i32>>>,
+        shape: Option<Shape>,
+        pets: Vec<Box<dyn Pet>>,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Role {
+        Admin,
+        #[serde(rename = "member")]
+        Member,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Circle {
+        radius: f64,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Shape {
+        Point,
+        Circle(Circle),
+        Line(f64, Option<Role>),
+        Rect { width: f64, height: Option<f64> },
+    }
+
+    trait Pet {}
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Dog {
+        name: String,
+    }
+    impl Pet for Dog {}
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Cat {
+        lives: i64,
+    }
+    impl Pet for Cat {}
+
+    rdc_polymorphic!(dyn Pet, tag = "kind" => Dog, Cat);
+
+    fn module() -> String {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<User>();
+        generate_pydantic_models(&ir).unwrap()
+    }
+
+    #[test]
+    fn generate_models() {
+        let module = module();
+        assert!(module.contains(
+            "class User(BaseModel):\n    \
+             model_config = ConfigDict(populate_by_name=True)\n    \
+             name: str = Field(alias=\"user-name\")\n    \
+             age: Int16\n    \
+             nickname: Optional[str] = None\n    \
+             class_: Role = Field(alias=\"class\")\n    \
+             limits: Dict[Role, List[Optional[Int32]]]\n    \
+             shape: Optional[Shape] = None\n    \
+             pets: List[Pet]\n"
+        ));
+        assert!(module.contains("Role = Literal[\"Admin\", \"member\"]\n"));
+        assert!(module.ends_with("User.model_rebuild()\nShapeCircle.model_rebuild()\nShapeLine.model_rebuild()\nShapeRectValue.model_rebuild()\nShapeRect.model_rebuild()\nPetDog.model_rebuild()\nPetCat.model_rebuild()\n"));
+    }
+
+    #[test]
+    fn generate_unions() {
+        let module = module();
+        assert!(module.contains(
+            "class ShapeLine(BaseModel):\n    \
+             model_config = ConfigDict(populate_by_name=True, extra=\"forbid\")\n    \
+             line: Tuple[float, Optional[Role]] = Field(alias=\"Line\")\n"
+        ));
+        assert!(module.contains(
+            "class ShapeRectValue(BaseModel):\n    width: float\n    height: Optional[float] = None\n"
+        ));
+        assert!(module
+            .contains("Shape = Union[Literal[\"Point\"], ShapeCircle, ShapeLine, ShapeRect]\n"));
+        assert!(
+            module.contains("class PetCat(Cat):\n    kind: Literal[\"Cat\"] = Field(\"Cat\")\n")
+        );
+        assert!(module
+            .contains("Pet = Annotated[Union[PetDog, PetCat], Field(discriminator=\"kind\")]\n"));
+    }
+}
@@ -0,0 +1,760 @@
+use crate::errors::Error;
+use crate::ir::{
+    DataEnum, DataEnumVariant, Enum, FlagsFormat, IntermediateRepresentation, Name, Polymorphic,
+    Struct, Type, TypeKind,
+};
+use crate::targets::json::Json;
+use crate::targets::utils::{tuple_names, variant_class};
+use std::collections::HashSet;
+use std::io::Write;
+
+/// Keywords that have to be escaped with a trailing underscore when used as Python identifiers.
+const PYTHON_KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// This function generates a Python module with dataclasses for the types in the IR,
+/// each with `from_dict` and `to_dict` helpers converting to and from the JSON layout of serde.
+///
+/// Structs are translated to dataclasses with snake case attributes, where optional ones default to `None`,
+/// and unit enums to `Enum` classes holding the JSON names. Data enums get a base class whose `from_dict`
+/// reads serde's external tagging, and a dataclass per variant, e.g. `ShapeCircle`, whose `to_dict` writes it.
+/// Polymorphic types become `Union` aliases with module-level `<name>_from_dict` and `<name>_to_dict` functions,
+/// since their variants can belong to several of them.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::targets::python::generate_python_dataclasses;
+/// use rdc::RDC;
+///
+/// #[derive(RDC)]
+/// struct User {
+///     name: String,
+///     nickname: Option<String>,
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add::<User>();
+/// let module = generate_python_dataclasses(&ir).unwrap();
+/// assert!(module.contains("@dataclass\nclass User:\n    name: str\n    nickname: Optional[str] = None\n"));
+/// assert!(module.contains("            nickname=data.get(\"nickname\"),\n"));
+/// ```
+pub fn generate_python_dataclasses(ir: &IntermediateRepresentation) -> Result<String, Error> {
+    ir.validate()?;
+    ir.reject_transparent_structs("Python")?;
+    let generator = PythonGenerator::new(ir);
+    let mut definitions = Vec::new();
+    for e in ir.enums() {
+        definitions.push(generator.enum_class(e));
+    }
+    for s in ir.structs() {
+        definitions.push(generator.dataclass(s)?);
+    }
+    for de in ir.data_enums() {
+        definitions.push(generator.data_enum_classes(de)?);
+    }
+    for p in ir.polymorphics() {
+        definitions.push(generator.polymorphic_functions(p)?);
+    }
+    let mut module = [
+        "from __future__ import annotations",
+        "",
+        "from dataclasses import dataclass",
+        "from enum import Enum",
+        "from typing import Any, Dict, List, Optional, Union",
+    ]
+    .join("\n");
+    for definition in definitions {
+        module.push_str("\n\n\n");
+        module.push_str(&definition.join("\n"));
+    }
+    module.push('\n');
+    Ok(module)
+}
+
+/// This function generates a Python module with dataclasses and writes it to the given path, e.g. `client/models.py`.
+pub fn write_python_dataclasses(
+    ir: &IntermediateRepresentation,
+    file_path: &str,
+) -> Result<(), Error> {
+    let module = generate_python_dataclasses(ir)?;
+    if let Some(parent) = std::path::Path::new(file_path).parent() {
+        std::fs::create_dir_all(parent).map_err(|_| Error::new("Failed to create directory"))?;
+    }
+    let mut file =
+        std::fs::File::create(file_path).map_err(|_| Error::new("Failed to create file"))?;
+    file.write_all(module.as_bytes())
+        .map_err(|_| Error::new("Failed to write to file"))
+}
+
+/// This enum describes how values of a named type are converted to and from their JSON form.
+enum Conversion {
+    /// The JSON value is used as it is, e.g. for numbers and strings.
+    Plain,
+    /// Unit enums are constructed from their JSON names and written with `.value`.
+    Enum,
+    /// Structs and data enums have `from_dict` and `to_dict` methods.
+    Class,
+    /// Polymorphic types have module-level functions, e.g. `shape_from_dict` and `shape_to_dict`.
+    Functions,
+}
+
+struct PythonGenerator {
+    enums: HashSet<String>,
+    flags: HashSet<String>,
+    classes: HashSet<String>,
+    polymorphics: HashSet<String>,
+}
+
+impl PythonGenerator {
+    fn new(ir: &IntermediateRepresentation) -> Self {
+        let type_names = |types: Vec<&String>| types.into_iter().cloned().collect();
+        Self {
+            enums: type_names(
+                ir.enums()
+                    .iter()
+                    .filter(|e| e.flags().is_none())
+                    .map(|e| e.self_type().type_name())
+                    .collect(),
+            ),
+            flags: type_names(
+                ir.enums()
+                    .iter()
+                    .filter(|e| e.flags().is_some())
+                    .map(|e| e.self_type().type_name())
+                    .collect(),
+            ),
+            classes: type_names(
+                ir.structs()
+                    .iter()
+                    .map(|s| s.self_type().type_name())
+                    .chain(ir.data_enums().iter().map(|de| de.self_type().type_name()))
+                    .collect(),
+            ),
+            polymorphics: type_names(
+                ir.polymorphics()
+                    .iter()
+                    .map(|p| p.self_type().type_name())
+                    .collect(),
+            ),
+        }
+    }
+
+    fn enum_class(&self, e: &Enum) -> Vec<String> {
+        let name = e.self_type().type_name();
+        match e.flags() {
+            Some(FlagsFormat::Bits) => return vec![format!("{name} = int")],
+            Some(FlagsFormat::Names) => return vec![format!("{name} = str")],
+            None => {}
+        }
+        let mut lines = vec![format!("class {name}(Enum):")];
+        for v in e.variants() {
+            lines.push(format!(
+                "    {} = {}",
+                v.name().as_upper_snake_case(),
+                Json::string(v.json_name()).render()
+            ));
+        }
+        if e.variants().is_empty() {
+            lines.push("    pass".to_string());
+        }
+        lines
+    }
+
+    fn dataclass(&self, s: &Struct) -> Result<Vec<String>, Error> {
+        let name = s.self_type().type_name();
+        let fields = s
+            .fields()
+            .iter()
+            .map(|f| (f.name(), f.json_name().as_str(), f.field_type()))
+            .collect::<Vec<_>>();
+        let mut lines = vec!["@dataclass".to_string(), format!("class {name}:")];
+        lines.extend(self.attributes(&fields)?);
+        lines.push(String::new());
+        lines.push("    @classmethod".to_string());
+        lines.push(format!(
+            "    def from_dict(cls, data: Dict[str, Any]) -> {name}:"
+        ));
+        lines.extend(self.constructor_call("cls", "data", &fields)?);
+        lines.push(String::new());
+        lines.push("    def to_dict(self) -> Dict[str, Any]:".to_string());
+        lines.extend(self.dict_literal("return ", &fields)?);
+        Ok(lines)
+    }
+
+    fn data_enum_classes(&self, de: &DataEnum) -> Result<Vec<String>, Error> {
+        let name = de.self_type().type_name();
+        let mut lines = vec![
+            format!("class {name}:"),
+            "    @staticmethod".to_string(),
+            format!("    def from_dict(data: Any) -> {name}:"),
+        ];
+        let units = de
+            .variants()
+            .iter()
+            .filter(|v| matches!(v, DataEnumVariant::Unit { .. }))
+            .collect::<Vec<_>>();
+        for v in &units {
+            lines.push(format!(
+                "        if data == {}:",
+                Json::string(v.json_name()).render()
+            ));
+            lines.push(format!("            return {}()", variant_class(de, v)));
+        }
+        if units.len() < de.variants().len() {
+            lines.push("        if isinstance(data, dict) and len(data) == 1:".to_string());
+            lines.push("            key, value = next(iter(data.items()))".to_string());
+        }
+        for v in de.variants() {
+            let class_name = variant_class(de, v);
+            let condition = format!(
+                "            if key == {}:",
+                Json::string(v.json_name()).render()
+            );
+            match v {
+                DataEnumVariant::Unit { .. } => continue,
+                DataEnumVariant::Tuple { fields, .. } if fields.len() == 1 => {
+                    lines.push(condition);
+                    lines.push(format!(
+                        "                return {class_name}({})",
+                        self.decode("value", &fields[0], 0)?
+                    ));
+                }
+                DataEnumVariant::Tuple { fields, .. } => {
+                    let values = fields
+                        .iter()
+                        .enumerate()
+                        .map(|(i, t)| self.decode(&format!("value[{i}]"), t, 0))
+                        .collect::<Result<Vec<String>, Error>>()?;
+                    lines.push(condition);
+                    lines.push(format!(
+                        "                return {class_name}({})",
+                        values.join(", ")
+                    ));
+                }
+                DataEnumVariant::Object { fields, .. } => {
+                    let fields = fields
+                        .iter()
+                        .map(|f| (f.name(), f.json_name(), f.field_type()))
+                        .collect::<Vec<_>>();
+                    lines.push(condition);
+                    lines.extend(
+                        self.constructor_call(&class_name, "value", &fields)?
+                            .into_iter()
+                            .map(|line| format!("        {line}")),
+                    );
+                }
+            }
+        }
+        lines.push(format!(
+            "        raise ValueError(f\"Unknown variant of {name}: {{data!r}}\")"
+        ));
+        lines.push(String::new());
+        lines.push("    def to_dict(self) -> Any:".to_string());
+        lines.push("        raise NotImplementedError".to_string());
+        for v in de.variants() {
+            lines.extend(self.variant_class(de, v)?);
+        }
+        Ok(lines)
+    }
+
+    /// Returns the dataclass of a data enum variant, whose `to_dict` writes the variant with external tagging.
+    fn variant_class(&self, de: &DataEnum, v: &DataEnumVariant) -> Result<Vec<String>, Error> {
+        let key = Json::string(v.json_name()).render();
+        let mut lines = vec![
+            String::new(),
+            String::new(),
+            "@dataclass".to_string(),
+            format!(
+                "class {}({}):",
+                variant_class(de, v),
+                de.self_type().type_name()
+            ),
+        ];
+        match v {
+            DataEnumVariant::Unit { .. } => {
+                lines.push("    def to_dict(self) -> Any:".to_string());
+                lines.push(format!("        return {key}"));
+            }
+            DataEnumVariant::Tuple { fields, .. } => {
+                let names = tuple_names(fields.len());
+                let mut values = Vec::new();
+                for (name, t) in names.iter().zip(fields) {
+                    lines.push(format!("    {name}: {}", self.type_hint(t)?));
+                    values.push(self.encode(&format!("self.{name}"), t, 0)?);
+                }
+                if !fields.is_empty() {
+                    lines.push(String::new());
+                }
+                let value = match values.len() {
+                    1 => values.remove(0),
+                    _ => format!("[{}]", values.join(", ")),
+                };
+                lines.push("    def to_dict(self) -> Any:".to_string());
+                lines.push(format!("        return {{{key}: {value}}}"));
+            }
+            DataEnumVariant::Object { fields, .. } => {
+                let fields = fields
+                    .iter()
+                    .map(|f| (f.name(), f.json_name(), f.field_type()))
+                    .collect::<Vec<_>>();
+                lines.extend(self.attributes(&fields)?);
+                if !fields.is_empty() {
+                    lines.push(String::new());
+                }
+                lines.push("    def to_dict(self) -> Any:".to_string());
+                lines.extend(self.dict_literal(&format!("return {{{key}: "), &fields)?);
+                let last = lines.pop().unwrap_or_default();
+                lines.push(format!("{last}}}"));
+            }
+        }
+        Ok(lines)
+    }
+
+    fn polymorphic_functions(&self, p: &Polymorphic) -> Result<Vec<String>, Error> {
+        let name = p.self_type().type_name();
+        let function = Name::from_pascal_case(name).as_snake_case();
+        let variants = p
+            .variants()
+            .iter()
+            .map(|v| Json::string(v.variant_type().type_name()).render())
+            .collect::<Vec<String>>();
+        let alias = match variants.is_empty() {
+            true => "Any".to_string(),
+            false => format!("Union[{}]", variants.join(", ")),
+        };
+        let mut lines = vec![
+            format!("{name} = {alias}"),
+            String::new(),
+            String::new(),
+            format!("def {function}_from_dict(data: Dict[str, Any]) -> {name}:"),
+        ];
+        let tag = p.tag().map(|tag| Json::string(tag).render());
+        if tag.is_none() && !p.variants().is_empty() {
+            lines.push("    key, value = next(iter(data.items()))".to_string());
+        }
+        for v in p.variants() {
+            let json_name = Json::string(v.json_name()).render();
+            let variant_type = v.variant_type();
+            match &tag {
+                Some(tag) => {
+                    lines.push(format!("    if data.get({tag}) == {json_name}:"));
+                    lines.push(format!(
+                        "        return {}",
+                        self.decode("data", variant_type, 0)?
+                    ));
+                }
+                None => {
+                    lines.push(format!("    if key == {json_name}:"));
+                    lines.push(format!(
+                        "        return {}",
+                        self.decode("value", variant_type, 0)?
+                    ));
+                }
+            }
+        }
+        lines.push(format!(
+            "    raise ValueError(f\"Unknown variant of {name}: {{data!r}}\")"
+        ));
+        lines.push(String::new());
+        lines.push(String::new());
+        lines.push(format!(
+            "def {function}_to_dict(value: {name}) -> Dict[str, Any]:"
+        ));
+        for v in p.variants() {
+            let json_name = Json::string(v.json_name()).render();
+            let variant_type = v.variant_type();
+            let encoded = self.encode("value", variant_type, 0)?;
+            lines.push(format!(
+                "    if isinstance(value, {}):",
+                self.type_hint(variant_type)?
+            ));
+            lines.push(match &tag {
+                Some(tag) => format!("        return {{{tag}: {json_name}, **{encoded}}}"),
+                None => format!("        return {{{json_name}: {encoded}}}"),
+            });
+        }
+        lines.push(format!(
+            "    raise ValueError(f\"Unknown variant of {name}: {{value!r}}\")"
+        ));
+        Ok(lines)
+    }
+
+    /// Returns attributes of a dataclass. Optional attributes default to `None`,
+    /// so they come after the required ones, which dataclasses require.
+    fn attributes(&self, fields: &[(&Name, &str, &Type)]) -> Result<Vec<String>, Error> {
+        let mut required = Vec::new();
+        let mut optional = Vec::new();
+        for (name, _, t) in fields {
+            let hint = self.type_hint(t)?;
+            let attribute = attribute_name(name);
+            match t.kind() {
+                TypeKind::Option(_) => optional.push(format!("    {attribute}: {hint} = None")),
+                _ => required.push(format!("    {attribute}: {hint}")),
+            }
+        }
+        if fields.is_empty() {
+            required.push("    pass".to_string());
+        }
+        required.extend(optional);
+        Ok(required)
+    }
+
+    /// Returns a `return` statement creating an object from a dict, e.g. `return cls(name=data["name"])`.
+    /// Optional fields may be missing, because serde accepts missing options.
+    fn constructor_call(
+        &self,
+        constructor: &str,
+        data: &str,
+        fields: &[(&Name, &str, &Type)],
+    ) -> Result<Vec<String>, Error> {
+        if fields.is_empty() {
+            return Ok(vec![format!("        return {constructor}()")]);
+        }
+        let mut lines = vec![format!("        return {constructor}(")];
+        for (name, json_name, t) in fields {
+            let key = Json::string(*json_name).render();
+            let value = match t.kind() {
+                TypeKind::Option(inner) => {
+                    let value = format!("{data}.get({key})");
+                    let decoded = self.decode(&value, inner, 0)?;
+                    match decoded == value {
+                        true => value,
+                        false => format!("None if {value} is None else {decoded}"),
+                    }
+                }
+                _ => self.decode(&format!("{data}[{key}]"), t, 0)?,
+            };
+            lines.push(format!("            {}={value},", attribute_name(name)));
+        }
+        lines.push("        )".to_string());
+        Ok(lines)
+    }
+
+    /// Returns a statement with a dict literal holding the JSON form of the attributes, e.g. `return {"name": self.name}`.
+    fn dict_literal(
+        &self,
+        prefix: &str,
+        fields: &[(&Name, &str, &Type)],
+    ) -> Result<Vec<String>, Error> {
+        if fields.is_empty() {
+            return Ok(vec![format!("        {prefix}{{}}")]);
+        }
+        let mut lines = vec![format!("        {prefix}{{")];
+        for (name, json_name, t) in fields {
+            let value = self.encode(&format!("self.{}", attribute_name(name)), t, 0)?;
+            lines.push(format!(
+                "            {}: {value},",
+                Json::string(*json_name).render()
+            ));
+        }
+        lines.push("        }".to_string());
+        Ok(lines)
+    }
+
+    /// Returns the Python type hint of a value.
+    fn type_hint(&self, t: &Type) -> Result<String, Error> {
+        match t.kind() {
+            TypeKind::List(element) => Ok(format!("List[{}]", self.type_hint(element)?)),
+            TypeKind::Map(key, value) => Ok(format!(
+                "Dict[{}, {}]",
+                self.type_hint(key)?,
+                self.type_hint(value)?
+            )),
+            TypeKind::Option(inner) => Ok(format!("Optional[{}]", self.type_hint(inner)?)),
+            TypeKind::UnsignedLong => Ok("int".to_string()),
+            TypeKind::Named => {
+                let name = t.type_name().as_str();
+                match name {
+                    "Boolean" => Ok("bool".to_string()),
+                    "Byte" | "Short" | "Integer" | "Long" | "java.math.BigInteger" => {
+                        Ok("int".to_string())
+                    }
+                    "Float" | "Double" => Ok("float".to_string()),
+                    "String" => Ok("str".to_string()),
+                    "com.fasterxml.jackson.databind.JsonNode" => Ok("Any".to_string()),
+                    _ => {
+                        self.conversion(t)?;
+                        Ok(name.to_string())
+                    }
+                }
+            }
+        }
+    }
+
+    fn conversion(&self, t: &Type) -> Result<Conversion, Error> {
+        if !matches!(t.kind(), TypeKind::Named) {
+            return Ok(Conversion::Plain);
+        }
+        let name = t.type_name();
+        if self.enums.contains(name) {
+            Ok(Conversion::Enum)
+        } else if self.classes.contains(name) {
+            Ok(Conversion::Class)
+        } else if self.polymorphics.contains(name) {
+            Ok(Conversion::Functions)
+        } else if self.flags.contains(name) || is_builtin(name) {
+            Ok(Conversion::Plain)
+        } else {
+            Err(Error::new(&format!("`{name}` has no Python counterpart")))
+        }
+    }
+
+    /// Returns an expression converting a JSON value to a value of the given type.
+    /// It returns the expression itself when no conversion is needed.
+    fn decode(&self, expr: &str, t: &Type, depth: usize) -> Result<String, Error> {
+        match t.kind() {
+            TypeKind::Option(inner) => {
+                let decoded = self.decode(expr, inner, depth)?;
+                Ok(match decoded == expr {
+                    true => decoded,
+                    false => format!("None if {expr} is None else {decoded}"),
+                })
+            }
+            TypeKind::List(element) => {
+                let item = format!("item{depth}");
+                let decoded = self.decode(&item, element, depth + 1)?;
+                Ok(match decoded == item {
+                    true => expr.to_string(),
+                    false => format!("[{decoded} for {item} in {expr}]"),
+                })
+            }
+            TypeKind::Map(key, value) => {
+                let (k, v) = (format!("k{depth}"), format!("v{depth}"));
+                let decoded_key = match key.type_name().as_str() {
+                    "Byte" | "Short" | "Integer" | "Long" => format!("int({k})"),
+                    _ => self.decode(&k, key, depth + 1)?,
+                };
+                let decoded_value = self.decode(&v, value, depth + 1)?;
+                Ok(match decoded_key == k && decoded_value == v {
+                    true => expr.to_string(),
+                    false => {
+                        format!("{{{decoded_key}: {decoded_value} for {k}, {v} in {expr}.items()}}")
+                    }
+                })
+            }
+            TypeKind::UnsignedLong => Ok(expr.to_string()),
+            TypeKind::Named => {
+                let name = t.type_name();
+                Ok(match self.conversion(t)? {
+                    Conversion::Plain => expr.to_string(),
+                    Conversion::Enum => format!("{name}({expr})"),
+                    Conversion::Class => format!("{name}.from_dict({expr})"),
+                    Conversion::Functions => format!(
+                        "{}_from_dict({expr})",
+                        Name::from_pascal_case(name).as_snake_case()
+                    ),
+                })
+            }
+        }
+    }
+
+    /// Returns an expression converting a value of the given type to its JSON form.
+    /// It returns the expression itself when no conversion is needed.
+    fn encode(&self, expr: &str, t: &Type, depth: usize) -> Result<String, Error> {
+        match t.kind() {
+            TypeKind::Option(inner) => {
+                let encoded = self.encode(expr, inner, depth)?;
+                Ok(match encoded == expr {
+                    true => encoded,
+                    false => format!("None if {expr} is None else {encoded}"),
+                })
+            }
+            TypeKind::List(element) => {
+                let item = format!("item{depth}");
+                let encoded = self.encode(&item, element, depth + 1)?;
+                Ok(match encoded == item {
+                    true => expr.to_string(),
+                    false => format!("[{encoded} for {item} in {expr}]"),
+                })
+            }
+            TypeKind::Map(key, value) => {
+                let (k, v) = (format!("k{depth}"), format!("v{depth}"));
+                // JSON keys are strings, so serde writes numeric keys as strings.
+                let encoded_key = match key.type_name().as_str() {
+                    "Byte" | "Short" | "Integer" | "Long" => format!("str({k})"),
+                    _ => self.encode(&k, key, depth + 1)?,
+                };
+                let encoded_value = self.encode(&v, value, depth + 1)?;
+                Ok(match encoded_key == k && encoded_value == v {
+                    true => expr.to_string(),
+                    false => {
+                        format!("{{{encoded_key}: {encoded_value} for {k}, {v} in {expr}.items()}}")
+                    }
+                })
+            }
+            TypeKind::UnsignedLong => Ok(expr.to_string()),
+            TypeKind::Named => Ok(match self.conversion(t)? {
+                Conversion::Plain => expr.to_string(),
+                Conversion::Enum => format!("{expr}.value"),
+                Conversion::Class => format!("{expr}.to_dict()"),
+                Conversion::Functions => format!(
+                    "{}_to_dict({expr})",
+                    Name::from_pascal_case(t.type_name()).as_snake_case()
+                ),
+            }),
+        }
+    }
+}
+
+fn is_builtin(name: &str) -> bool {
+    matches!(
+        name,
+        "Boolean"
+            | "Byte"
+            | "Short"
+            | "Integer"
+            | "Long"
+            | "Float"
+            | "Double"
+            | "String"
+            | "java.math.BigInteger"
+            | "com.fasterxml.jackson.databind.JsonNode"
+    )
+}
+
+/// Returns the name of a dataclass attribute, with keywords escaped with a trailing underscore, e.g. `class_`.
+pub(crate) fn attribute_name(name: &Name) -> String {
+    let snake_case = name.as_snake_case();
+    match PYTHON_KEYWORDS.contains(&snake_case.as_str()) {
+        true => format!("{snake_case}_"),
+        false => snake_case,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as rdc;
+    use crate::ir::TypeTarget;
+    use crate::{rdc_polymorphic, RDC};
+    use std::collections::HashMap;
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct User {
+        #[serde(rename = "user-name")]
+        name: String,
+        nickname: Option<String>,
+        class: Role,
+        limits: HashMap<Role, Vec<Option<i32>>>,
+        shape: Option<Shape>,
+        pets: Vec<Box<dyn Pet>>,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Role {
+        Admin,
+        #[serde(rename = "member")]
+        Member,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Circle {
+        radius: f64,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Shape {
+        Point,
+        Circle(Circle),
+        Line(f64, Option<Role>),
+        Rect { width: f64, height: Option<f64> },
+    }
+
+    trait Pet {}
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Dog {
+        name: String,
+    }
+    impl Pet for Dog {}
+
+    rdc_polymorphic!(dyn Pet, tag = "kind" => Dog);
+
+    fn module() -> String {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<User>();
+        generate_python_dataclasses(&ir).unwrap()
+    }
+
+    #[test]
+    fn generate_dataclasses() {
+        let module = module();
+        assert!(module.contains(
+            "@dataclass\n\
+             class User:\n    \
+             name: str\n    \
+             class_: Role\n    \
+             limits: Dict[Role, List[Optional[int]]]\n    \
+             pets: List[Pet]\n    \
+             nickname: Optional[str] = None\n    \
+             shape: Optional[Shape] = None\n"
+        ));
+        assert!(module.contains(
+            "        return cls(\n            \
+             name=data[\"user-name\"],\n            \
+             nickname=data.get(\"nickname\"),\n            \
+             class_=Role(data[\"class\"]),\n            \
+             limits={Role(k0): v0 for k0, v0 in data[\"limits\"].items()},\n            \
+             shape=None if data.get(\"shape\") is None else Shape.from_dict(data.get(\"shape\")),\n            \
+             pets=[pet_from_dict(item0) for item0 in data[\"pets\"]],\n        \
+             )\n"
+        ));
+        assert!(module.contains(
+            "            \"class\": self.class_.value,\n            \
+             \"limits\": {k0.value: v0 for k0, v0 in self.limits.items()},\n            \
+             \"shape\": None if self.shape is None else self.shape.to_dict(),\n            \
+             \"pets\": [pet_to_dict(item0) for item0 in self.pets],\n"
+        ));
+        assert!(
+            module.contains("class Role(Enum):\n    ADMIN = \"Admin\"\n    MEMBER = \"member\"\n")
+        );
+    }
+
+    #[test]
+    fn external_tagging() {
+        let module = module();
+        assert!(module.contains(
+            "        if data == \"Point\":\n            \
+             return ShapePoint()\n        \
+             if isinstance(data, dict) and len(data) == 1:\n            \
+             key, value = next(iter(data.items()))\n            \
+             if key == \"Circle\":\n                \
+             return ShapeCircle(Circle.from_dict(value))\n            \
+             if key == \"Line\":\n                \
+             return ShapeLine(value[0], None if value[1] is None else Role(value[1]))\n            \
+             if key == \"Rect\":\n                \
+             return ShapeRect(\n"
+        ));
+        assert!(module.contains(
+            "class ShapePoint(Shape):\n    def to_dict(self) -> Any:\n        return \"Point\"\n"
+        ));
+        assert!(module.contains(
+            "class ShapeLine(Shape):\n    \
+             value0: float\n    \
+             value1: Optional[Role]\n\n    \
+             def to_dict(self) -> Any:\n        \
+             return {\"Line\": [self.value0, None if self.value1 is None else self.value1.value]}\n"
+        ));
+        assert!(module.contains(
+            "        return {\"Rect\": {\n            \
+             \"width\": self.width,\n            \
+             \"height\": self.height,\n        \
+             }}\n"
+        ));
+        assert!(module.contains("Pet = Union[\"Dog\"]\n"));
+        assert!(module.contains(
+            "    if data.get(\"kind\") == \"Dog\":\n        return Dog.from_dict(data)\n"
+        ));
+        assert!(module.contains("    if isinstance(value, Dog):\n        return {\"kind\": \"Dog\", **value.to_dict()}\n"));
+    }
+}
@@ -0,0 +1,24 @@
+use crate::errors::Error;
+
+/// This trait is implemented by runners that build and execute generated code of a target.
+/// It is used to write round-trip tests in the same way for every target:
+/// generated files are run together with an entry point that reads input from stdin and prints the result to stdout.
+///
+/// Example:
+/// ```no_run
+/// use rdc::targets::java::JavaClass;
+/// use rdc::targets::java::verify::JavaBuildTool;
+/// use rdc::targets::runner::TargetRunner;
+///
+/// fn echo<R: TargetRunner<File = JavaClass>>(runner: &R, main: JavaClass) -> String {
+///     runner.run(&[main], "hello").unwrap()
+/// }
+/// ```
+pub trait TargetRunner {
+    /// Type of a single generated file, e.g. `JavaClass`.
+    type File;
+
+    /// Builds and runs the files, passing `stdin` as standard input.
+    /// Returns everything the program printed to stdout.
+    fn run(&self, files: &[Self::File], stdin: &str) -> Result<String, Error>;
+}
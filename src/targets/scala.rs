@@ -0,0 +1,927 @@
+use crate::errors::Error;
+use crate::ir::{
+    DataEnum, DataEnumVariant, Enum, FlagsFormat, IntermediateRepresentation, Name, Polymorphic,
+    Struct, Type, TypeKind,
+};
+use crate::targets::json::Json;
+use crate::targets::utils::{indent, tuple_names, variant_class};
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::io::Write;
+
+/// Reserved words of Scala 2 and 3 that have to be escaped with backticks when used as identifiers.
+const SCALA_KEYWORDS: &[&str] = &[
+    "abstract",
+    "case",
+    "catch",
+    "class",
+    "def",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "final",
+    "finally",
+    "for",
+    "forSome",
+    "given",
+    "if",
+    "implicit",
+    "import",
+    "lazy",
+    "match",
+    "new",
+    "null",
+    "object",
+    "override",
+    "package",
+    "private",
+    "protected",
+    "return",
+    "sealed",
+    "super",
+    "then",
+    "this",
+    "throw",
+    "trait",
+    "true",
+    "try",
+    "type",
+    "val",
+    "var",
+    "while",
+    "with",
+    "yield",
+];
+
+/// Names of the cursors used by the generated circe decoders, which can not be names of decoded values.
+const CURSOR_NAMES: &[&str] = &["c", "value"];
+
+/// This enum describes how the generated Scala classes are converted to and from JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScalaFlavor {
+    /// Companion objects hold implicit circe `Decoder` and `Encoder` instances following serde's layout.
+    #[default]
+    Circe,
+    /// Classes are annotated for Jackson with `jackson-module-scala` registered on the `ObjectMapper`.
+    /// Data enums are not supported, because Jackson has no way to read serde's externally tagged variants,
+    /// so `generate_scala_code` returns an error for an IR with data enums.
+    Jackson,
+}
+
+/// This function generates a Scala source file of the given package with types for the JSON of the types in the IR,
+/// e.g. for Spark jobs parsing the same payloads. An empty package leaves out the `package` clause.
+///
+/// Structs are translated to case classes with fields in camel case, where optional fields are `Option`s.
+/// Unit enums become sealed abstract classes with a case object per variant holding its JSON name.
+/// Data enums become sealed traits with a case class per variant, e.g. `ShapeCircle`, and polymorphic types
+/// sealed traits extended by the case classes of their variants, which therefore have to be structs.
+/// Sealed traits have to be declared in the same file as their subclasses, so all the types share a single file.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::targets::scala::{generate_scala_code, ScalaFlavor};
+/// use rdc::RDC;
+///
+/// #[derive(RDC)]
+/// struct User {
+///     name: String,
+///     nickname: Option<String>,
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add::<User>();
+/// let code = generate_scala_code(&ir, ScalaFlavor::Circe, "com.example.api").unwrap();
+/// assert!(code.contains("package com.example.api\n"));
+/// assert!(code.contains("final case class User(\n  name: String,\n  nickname: Option[String]\n)\n"));
+/// ```
+pub fn generate_scala_code(
+    ir: &IntermediateRepresentation,
+    flavor: ScalaFlavor,
+    package: &str,
+) -> Result<String, Error> {
+    ir.validate()?;
+    ir.reject_transparent_structs("Scala")?;
+    if let (ScalaFlavor::Jackson, Some(de)) = (flavor, ir.data_enums().first()) {
+        return Err(Error::new(&format!(
+            "Data enum `{}` can not be read by Jackson, it is only supported with the circe flavor",
+            de.self_type().type_name()
+        )));
+    }
+    let generator = ScalaGenerator::new(ir, flavor);
+    let mut definitions = Vec::new();
+    for s in ir.structs() {
+        definitions.push(generator.generate_struct(s)?);
+    }
+    for e in ir.enums().iter().filter(|e| e.flags().is_none()) {
+        definitions.push(generator.generate_enum(e));
+    }
+    for de in ir.data_enums() {
+        definitions.push(generator.generate_data_enum(de)?);
+    }
+    for p in ir.polymorphics() {
+        definitions.push(generator.generate_polymorphic(p)?);
+    }
+
+    let mut code = "// Code generated by rdc. DO NOT EDIT.\n".to_string();
+    if !package.is_empty() {
+        code.push_str(&format!("\npackage {package}\n"));
+    }
+    let imports = generator.imports.borrow();
+    if !imports.is_empty() {
+        code.push('\n');
+        for import in imports.iter() {
+            code.push_str(&format!("import {import}\n"));
+        }
+    }
+    for definition in definitions {
+        code.push('\n');
+        for line in definition {
+            code.push_str(&line);
+            code.push('\n');
+        }
+    }
+    Ok(code)
+}
+
+/// This function generates a Scala source file and writes it to the given path, e.g. `src/main/scala/api/Models.scala`.
+pub fn write_scala_code(
+    ir: &IntermediateRepresentation,
+    flavor: ScalaFlavor,
+    package: &str,
+    file_path: &str,
+) -> Result<(), Error> {
+    let code = generate_scala_code(ir, flavor, package)?;
+    if let Some(parent) = std::path::Path::new(file_path).parent() {
+        std::fs::create_dir_all(parent).map_err(|_| Error::new("Failed to create directory"))?;
+    }
+    let mut file =
+        std::fs::File::create(file_path).map_err(|_| Error::new("Failed to create file"))?;
+    file.write_all(code.as_bytes())
+        .map_err(|_| Error::new("Failed to write to file"))
+}
+
+/// A field of a case class: its Scala name, its JSON name and its type.
+type ScalaField<'a> = (Name, &'a str, &'a Type);
+
+struct ScalaGenerator {
+    flavor: ScalaFlavor,
+    structs: HashSet<String>,
+    types: HashSet<String>,
+    enums: HashSet<String>,
+    flags: HashMap<String, &'static str>,
+    /// Sealed traits extended by the case class of each struct, i.e. the polymorphic types it is a variant of.
+    traits: HashMap<String, Vec<String>>,
+    imports: RefCell<BTreeSet<&'static str>>,
+}
+
+impl ScalaGenerator {
+    fn new(ir: &IntermediateRepresentation, flavor: ScalaFlavor) -> Self {
+        let mut traits: HashMap<String, Vec<String>> = HashMap::new();
+        for p in ir.polymorphics() {
+            for v in p.variants() {
+                traits
+                    .entry(v.variant_type().type_name().clone())
+                    .or_default()
+                    .push(p.self_type().type_name().clone());
+            }
+        }
+        Self {
+            flavor,
+            structs: ir
+                .structs()
+                .iter()
+                .map(|s| s.self_type().type_name().clone())
+                .collect(),
+            types: ir
+                .structs()
+                .iter()
+                .map(|s| s.self_type())
+                .chain(ir.data_enums().iter().map(|de| de.self_type()))
+                .chain(ir.polymorphics().iter().map(|p| p.self_type()))
+                .map(|t| t.type_name().clone())
+                .collect(),
+            enums: ir
+                .enums()
+                .iter()
+                .filter(|e| e.flags().is_none())
+                .map(|e| e.self_type().type_name().clone())
+                .collect(),
+            flags: ir
+                .enums()
+                .iter()
+                .filter_map(|e| {
+                    let type_name = match e.flags()? {
+                        FlagsFormat::Bits => "Long",
+                        FlagsFormat::Names => "String",
+                    };
+                    Some((e.self_type().type_name().clone(), type_name))
+                })
+                .collect(),
+            traits,
+            imports: RefCell::new(BTreeSet::new()),
+        }
+    }
+
+    fn import(&self, imports: &[&'static str]) {
+        self.imports.borrow_mut().extend(imports);
+    }
+
+    fn import_circe(&self) {
+        self.import(&[
+            "io.circe.{Decoder, DecodingFailure, Encoder, Json}",
+            "io.circe.syntax._",
+        ]);
+    }
+
+    fn generate_struct(&self, s: &Struct) -> Result<Vec<String>, Error> {
+        let name = s.self_type().type_name();
+        let fields = s
+            .fields()
+            .iter()
+            .map(|f| (f.name().clone(), f.json_name().as_str(), f.field_type()))
+            .collect::<Vec<ScalaField>>();
+        let supertypes = match self.traits.get(name) {
+            Some(traits) => format!(" extends {}", traits.join(" with ")),
+            None => String::new(),
+        };
+        let mut parameters = Vec::new();
+        for (field, json_name, t) in &fields {
+            let annotations = match self.flavor {
+                ScalaFlavor::Circe => String::new(),
+                ScalaFlavor::Jackson => self.jackson_annotations(json_name, t),
+            };
+            parameters.push(format!(
+                "{annotations}{}: {}",
+                member_name(field),
+                self.type_name(t)?
+            ));
+        }
+        let mut lines = Vec::new();
+        if self.flavor == ScalaFlavor::Jackson && fields.is_empty() {
+            // Jackson refuses to serialize classes without properties, unless they are annotated.
+            self.import(&["com.fasterxml.jackson.databind.annotation.JsonSerialize"]);
+            lines.push("@JsonSerialize".to_string());
+        }
+        lines.extend(case_class(name, parameters, &supertypes));
+        if self.flavor == ScalaFlavor::Circe {
+            self.import_circe();
+            let mut decoder = vec![format!(
+                "implicit val decoder: Decoder[{name}] = Decoder.instance {{ c =>"
+            )];
+            decoder.extend(indent(self.decode_fields("c", name, &fields)?, 1));
+            decoder.push("}".to_string());
+            let mut encoder = vec![format!(
+                "implicit val encoder: Encoder[{name}] = Encoder.instance {{ v =>"
+            )];
+            encoder.extend(indent(object_literal("v", &fields), 1));
+            encoder.push("}".to_string());
+            lines.push(String::new());
+            lines.extend(companion(name, vec![decoder, encoder]));
+        }
+        Ok(lines)
+    }
+
+    /// Returns annotations of a case class parameter for Jackson: `@JsonProperty` with the JSON name and,
+    /// for options and lists of numbers, `@JsonDeserialize` with the boxed class of the numbers,
+    /// which is erased from the generic type.
+    fn jackson_annotations(&self, json_name: &str, t: &Type) -> String {
+        self.import(&["com.fasterxml.jackson.annotation.JsonProperty"]);
+        let mut annotations = format!("@JsonProperty({}) ", Json::string(json_name).render());
+        if let TypeKind::Option(inner) | TypeKind::List(inner) = t.kind() {
+            let boxed = match inner.type_name().as_str() {
+                "Byte" => Some("java.lang.Byte"),
+                "Short" => Some("java.lang.Short"),
+                "Integer" => Some("java.lang.Integer"),
+                "Long" => Some("java.lang.Long"),
+                "Float" => Some("java.lang.Float"),
+                "Double" => Some("java.lang.Double"),
+                "Boolean" => Some("java.lang.Boolean"),
+                _ => None,
+            };
+            if let Some(boxed) = boxed {
+                self.import(&["com.fasterxml.jackson.databind.annotation.JsonDeserialize"]);
+                annotations.push_str(&format!("@JsonDeserialize(contentAs = classOf[{boxed}]) "));
+            }
+        }
+        annotations
+    }
+
+    fn generate_enum(&self, e: &Enum) -> Vec<String> {
+        let name = e.self_type().type_name();
+        let mut lines = Vec::new();
+        let mut members = Vec::new();
+        let objects = e
+            .variants()
+            .iter()
+            .map(|v| {
+                format!(
+                    "case object {} extends {name}({})",
+                    v.name().as_pascal_case(),
+                    Json::string(v.json_name()).render()
+                )
+            })
+            .collect::<Vec<String>>();
+        members.push(objects);
+        let values = e
+            .variants()
+            .iter()
+            .map(|v| v.name().as_pascal_case())
+            .collect::<Vec<String>>();
+        members.push(vec![
+            format!("val values: List[{name}] = List({})", values.join(", ")),
+            String::new(),
+            format!("def fromJson(json: String): Option[{name}] = values.find(_.jsonName == json)"),
+        ]);
+        match self.flavor {
+            ScalaFlavor::Circe => {
+                self.import_circe();
+                self.import(&["io.circe.{KeyDecoder, KeyEncoder}"]);
+                lines.push(format!(
+                    "sealed abstract class {name}(val jsonName: String)"
+                ));
+                members.push(vec![
+                    format!("implicit val decoder: Decoder[{name}] ="),
+                    format!(
+                        "  Decoder.decodeString.emap(json => fromJson(json).toRight(s\"Unknown variant of {name}: $json\"))"
+                    ),
+                    String::new(),
+                    format!(
+                        "implicit val encoder: Encoder[{name}] = Encoder.encodeString.contramap(_.jsonName)"
+                    ),
+                    String::new(),
+                    format!(
+                        "implicit val keyDecoder: KeyDecoder[{name}] = KeyDecoder.instance(fromJson)"
+                    ),
+                    String::new(),
+                    format!(
+                        "implicit val keyEncoder: KeyEncoder[{name}] = KeyEncoder.instance(_.jsonName)"
+                    ),
+                ]);
+            }
+            ScalaFlavor::Jackson => {
+                self.import(&[
+                    "com.fasterxml.jackson.annotation.JsonValue",
+                    "com.fasterxml.jackson.core.JsonParser",
+                    "com.fasterxml.jackson.databind.DeserializationContext",
+                    "com.fasterxml.jackson.databind.annotation.JsonDeserialize",
+                    "com.fasterxml.jackson.databind.deser.std.StdDeserializer",
+                ]);
+                lines.extend([
+                    format!("@JsonDeserialize(using = classOf[{name}.Deserializer])"),
+                    format!("sealed abstract class {name}(val jsonName: String) {{"),
+                    "  @JsonValue def toJson: String = jsonName".to_string(),
+                    "}".to_string(),
+                ]);
+                members.push(vec![
+                    format!("class Deserializer extends StdDeserializer[{name}](classOf[{name}]) {{"),
+                    format!(
+                        "  override def deserialize(p: JsonParser, ctxt: DeserializationContext): {name} = {{"
+                    ),
+                    "    val json = p.getValueAsString".to_string(),
+                    format!(
+                        "    fromJson(json).getOrElse(throw ctxt.weirdStringException(json, classOf[{name}], \"Unknown variant of {name}\"))"
+                    ),
+                    "  }".to_string(),
+                    "}".to_string(),
+                ]);
+            }
+        }
+        lines.push(String::new());
+        lines.extend(companion(name, members));
+        lines
+    }
+
+    fn generate_data_enum(&self, de: &DataEnum) -> Result<Vec<String>, Error> {
+        self.import_circe();
+        let name = de.self_type().type_name();
+        let mut lines = vec![format!("sealed trait {name}")];
+        let mut cases = Vec::new();
+        let mut unit_cases = Vec::new();
+        let mut encoded = Vec::new();
+        for v in de.variants() {
+            let class_name = variant_class(de, v);
+            let key = Json::string(v.json_name()).render();
+            lines.push(String::new());
+            match v {
+                DataEnumVariant::Unit { .. } => {
+                    lines.push(format!("case object {class_name} extends {name}"));
+                    unit_cases.push(format!("case {key} => Right({class_name})"));
+                    encoded.push(format!("case {class_name} => Json.fromString({key})"));
+                }
+                DataEnumVariant::Tuple { fields, .. } => {
+                    let names = tuple_names(fields.len());
+                    let mut parameters = Vec::new();
+                    let mut bindings = Vec::new();
+                    let mut values = Vec::new();
+                    for (i, (field, t)) in names.iter().zip(fields).enumerate() {
+                        let type_name = self.type_name(t)?;
+                        parameters.push(format!("{field}: {type_name}"));
+                        bindings.push((field.clone(), format!("value.downN({i}).as[{type_name}]")));
+                        values.push(format!("v.{field}.asJson"));
+                    }
+                    lines.extend(case_class(
+                        &class_name,
+                        parameters,
+                        &format!(" extends {name}"),
+                    ));
+                    let value = match fields.len() {
+                        1 => values.remove(0),
+                        _ => format!("Json.arr({})", values.join(", ")),
+                    };
+                    encoded.push(format!(
+                        "case v: {class_name} => Json.obj({key} -> {value})"
+                    ));
+                    // serde writes the value of a newtype variant as it is, and the values of other variants as an array.
+                    let statement = match fields.len() {
+                        1 => vec![format!(
+                            "c.downField({key}).as[{}].map({class_name}(_))",
+                            self.type_name(&fields[0])?
+                        )],
+                        _ => {
+                            let mut statement = vec![format!("val value = c.downField({key})")];
+                            statement.extend(for_yield(bindings, &class_name, &names));
+                            statement
+                        }
+                    };
+                    cases.push((key, statement));
+                }
+                DataEnumVariant::Object { fields, .. } => {
+                    let fields = fields
+                        .iter()
+                        .map(|f| (f.name().clone(), f.json_name(), f.field_type()))
+                        .collect::<Vec<ScalaField>>();
+                    let parameters = fields
+                        .iter()
+                        .map(|(field, _, t)| {
+                            Ok(format!("{}: {}", member_name(field), self.type_name(t)?))
+                        })
+                        .collect::<Result<Vec<String>, Error>>()?;
+                    lines.extend(case_class(
+                        &class_name,
+                        parameters,
+                        &format!(" extends {name}"),
+                    ));
+                    let entries = fields
+                        .iter()
+                        .map(|(field, json_name, _)| {
+                            format!(
+                                "{} -> v.{}.asJson",
+                                Json::string(*json_name).render(),
+                                member_name(field)
+                            )
+                        })
+                        .collect::<Vec<String>>();
+                    encoded.push(format!(
+                        "case v: {class_name} => Json.obj({key} -> Json.obj({}))",
+                        entries.join(", ")
+                    ));
+                    let mut statement = vec![format!("val value = c.downField({key})")];
+                    statement.extend(self.decode_fields("value", &class_name, &fields)?);
+                    cases.push((key, statement));
+                }
+            }
+        }
+        let mut fallback = vec![format!(
+            "Left(DecodingFailure(\"Unknown variant of {name}\", c.history))"
+        )];
+        if !unit_cases.is_empty() {
+            let mut unit = vec!["c.as[String].flatMap {".to_string()];
+            unit.extend(indent(unit_cases, 1));
+            unit.push(format!(
+                "  case other => Left(DecodingFailure(s\"Unknown variant of {name}: $other\", c.history))"
+            ));
+            unit.push("}".to_string());
+            fallback = unit;
+        }
+        let mut decoder = vec![format!(
+            "implicit val decoder: Decoder[{name}] = Decoder.instance {{ c =>"
+        )];
+        decoder.extend(indent(keyed_cases(cases, fallback), 1));
+        decoder.push("}".to_string());
+        let mut encoder = vec![format!(
+            "implicit val encoder: Encoder[{name}] = Encoder.instance {{"
+        )];
+        encoder.extend(indent(encoded, 1));
+        encoder.push("}".to_string());
+        lines.push(String::new());
+        lines.extend(companion(name, vec![decoder, encoder]));
+        Ok(lines)
+    }
+
+    fn generate_polymorphic(&self, p: &Polymorphic) -> Result<Vec<String>, Error> {
+        let name = p.self_type().type_name();
+        for v in p.variants() {
+            let variant_name = v.variant_type().type_name();
+            if !self.structs.contains(variant_name) {
+                return Err(Error::new(&format!(
+                    "Variant `{variant_name}` of `{name}` is not a struct, so it can not extend a sealed trait in Scala"
+                )));
+            }
+        }
+        let mut lines = Vec::new();
+        if self.flavor == ScalaFlavor::Jackson {
+            self.import(&[
+                "com.fasterxml.jackson.annotation.JsonSubTypes",
+                "com.fasterxml.jackson.annotation.JsonTypeInfo",
+            ]);
+            lines.push(match p.tag() {
+                Some(tag) => format!(
+                    "@JsonTypeInfo(use = JsonTypeInfo.Id.NAME, include = JsonTypeInfo.As.PROPERTY, property = {})",
+                    Json::string(tag).render()
+                ),
+                None => "@JsonTypeInfo(use = JsonTypeInfo.Id.NAME, include = JsonTypeInfo.As.WRAPPER_OBJECT)"
+                    .to_string(),
+            });
+            lines.push("@JsonSubTypes(Array(".to_string());
+            let last = p.variants().len() - 1;
+            for (i, v) in p.variants().iter().enumerate() {
+                lines.push(format!(
+                    "  new JsonSubTypes.Type(value = classOf[{}], name = {}){}",
+                    v.variant_type().type_name(),
+                    Json::string(v.json_name()).render(),
+                    if i == last { "" } else { "," }
+                ));
+            }
+            lines.push("))".to_string());
+            lines.push(format!("sealed trait {name}"));
+            return Ok(lines);
+        }
+
+        self.import_circe();
+        lines.push(format!("sealed trait {name}"));
+        let mut decoder = vec![format!(
+            "implicit val decoder: Decoder[{name}] = Decoder.instance {{ c =>"
+        )];
+        let mut encoder = vec![format!(
+            "implicit val encoder: Encoder[{name}] = Encoder.instance {{"
+        )];
+        match p.tag() {
+            Some(tag) => {
+                let tag = Json::string(tag).render();
+                decoder.push(format!("  c.downField({tag}).as[String].flatMap {{"));
+                for v in p.variants() {
+                    let key = Json::string(v.json_name()).render();
+                    let variant_name = v.variant_type().type_name();
+                    decoder.push(format!("    case {key} => c.as[{variant_name}]"));
+                    encoder.push(format!(
+                        "  case v: {variant_name} => v.asJson.mapObject(({tag} -> Json.fromString({key})) +: _)"
+                    ));
+                }
+                decoder.push(format!(
+                    "    case other => Left(DecodingFailure(s\"Unknown variant of {name}: $other\", c.history))"
+                ));
+                decoder.push("  }".to_string());
+            }
+            None => {
+                let mut cases = Vec::new();
+                for v in p.variants() {
+                    let key = Json::string(v.json_name()).render();
+                    let variant_name = v.variant_type().type_name();
+                    cases.push((
+                        key.clone(),
+                        vec![format!("c.downField({key}).as[{variant_name}]")],
+                    ));
+                    encoder.push(format!(
+                        "  case v: {variant_name} => Json.obj({key} -> v.asJson)"
+                    ));
+                }
+                let fallback = vec![format!(
+                    "Left(DecodingFailure(\"Unknown variant of {name}\", c.history))"
+                )];
+                decoder.extend(indent(keyed_cases(cases, fallback), 1));
+            }
+        }
+        decoder.push("}".to_string());
+        encoder.push("}".to_string());
+        lines.push(String::new());
+        lines.extend(companion(name, vec![decoder, encoder]));
+        Ok(lines)
+    }
+
+    /// Returns a for comprehension decoding the fields of a JSON object from the given cursor
+    /// into an instance of the case class.
+    fn decode_fields(
+        &self,
+        cursor: &str,
+        class_name: &str,
+        fields: &[ScalaField],
+    ) -> Result<Vec<String>, Error> {
+        let mut bindings = Vec::new();
+        let mut names = Vec::new();
+        for (field, json_name, t) in fields {
+            let local = local_name(field);
+            bindings.push((
+                local.clone(),
+                format!(
+                    "{cursor}.downField({}).as[{}]",
+                    Json::string(*json_name).render(),
+                    self.type_name(t)?
+                ),
+            ));
+            names.push(local);
+        }
+        Ok(for_yield(bindings, class_name, &names))
+    }
+
+    /// Returns the Scala type of a value.
+    fn type_name(&self, t: &Type) -> Result<String, Error> {
+        match t.kind() {
+            TypeKind::Option(inner) => Ok(format!("Option[{}]", self.type_name(inner)?)),
+            TypeKind::List(element) => Ok(format!("List[{}]", self.type_name(element)?)),
+            TypeKind::Map(key, value) => {
+                let key_name = self.type_name(key)?;
+                let supported = match key_name.as_str() {
+                    "String" | "Byte" | "Short" | "Int" | "Long" => true,
+                    _ => self.flavor == ScalaFlavor::Circe && self.enums.contains(key.type_name()),
+                };
+                if !supported {
+                    return Err(Error::new(&format!(
+                        "`{}` can not be a key of a JSON object in Scala",
+                        key.type_name()
+                    )));
+                }
+                Ok(format!("Map[{key_name}, {}]", self.type_name(value)?))
+            }
+            TypeKind::UnsignedLong => Ok("BigInt".to_string()),
+            TypeKind::Named => {
+                let name = t.type_name();
+                let type_name = match name.as_str() {
+                    "Byte" | "Short" | "Long" | "Float" | "Double" | "Boolean" | "String" => {
+                        name.to_string()
+                    }
+                    "Integer" => "Int".to_string(),
+                    "java.math.BigInteger" => "BigInt".to_string(),
+                    "com.fasterxml.jackson.databind.JsonNode" => match self.flavor {
+                        ScalaFlavor::Circe => "Json".to_string(),
+                        ScalaFlavor::Jackson => name.to_string(),
+                    },
+                    _ if self.types.contains(name) || self.enums.contains(name) => name.to_string(),
+                    _ => match self.flags.get(name) {
+                        Some(type_name) => type_name.to_string(),
+                        None => {
+                            return Err(Error::new(&format!("`{name}` has no Scala counterpart")))
+                        }
+                    },
+                };
+                Ok(type_name)
+            }
+        }
+    }
+}
+
+/// Returns a case class with the given parameters, each on its own line, and supertypes, e.g. ` extends Pet`.
+fn case_class(name: &str, parameters: Vec<String>, supertypes: &str) -> Vec<String> {
+    if parameters.is_empty() {
+        return vec![format!("final case class {name}(){supertypes}")];
+    }
+    let mut lines = vec![format!("final case class {name}(")];
+    let last = parameters.len() - 1;
+    for (i, parameter) in parameters.into_iter().enumerate() {
+        lines.push(match i == last {
+            true => format!("  {parameter}"),
+            false => format!("  {parameter},"),
+        });
+    }
+    lines.push(format!("){supertypes}"));
+    lines
+}
+
+/// Returns a companion object with the given groups of members, separated by empty lines.
+fn companion(name: &str, members: Vec<Vec<String>>) -> Vec<String> {
+    let mut lines = vec![format!("object {name} {{")];
+    for (i, member) in members.into_iter().enumerate() {
+        if i > 0 {
+            lines.push(String::new());
+        }
+        lines.extend(indent(member, 1));
+    }
+    lines.push("}".to_string());
+    lines
+}
+
+/// Returns a `Json.obj` literal holding the JSON form of the fields of the given value, with the entries on separate lines.
+fn object_literal(value: &str, fields: &[ScalaField]) -> Vec<String> {
+    if fields.is_empty() {
+        return vec!["Json.obj()".to_string()];
+    }
+    let mut lines = vec!["Json.obj(".to_string()];
+    let last = fields.len() - 1;
+    for (i, (field, json_name, _)) in fields.iter().enumerate() {
+        lines.push(format!(
+            "  {} -> {value}.{}.asJson{}",
+            Json::string(*json_name).render(),
+            member_name(field),
+            if i == last { "" } else { "," }
+        ));
+    }
+    lines.push(")".to_string());
+    lines
+}
+
+/// Returns a for comprehension binding the decoded values and yielding an instance of the case class,
+/// or a `Right` of the instance when there is nothing to decode.
+fn for_yield(bindings: Vec<(String, String)>, class_name: &str, names: &[String]) -> Vec<String> {
+    let instance = format!("{class_name}({})", names.join(", "));
+    if bindings.is_empty() {
+        return vec![format!("Right({instance})")];
+    }
+    let mut lines = vec!["for {".to_string()];
+    for (name, decoded) in bindings {
+        lines.push(format!("  {name} <- {decoded}"));
+    }
+    lines.push(format!("}} yield {instance}"));
+    lines
+}
+
+/// Returns a match over the single key of a JSON object naming the variant, with the given cases
+/// and the fallback for JSON values that are not such objects.
+fn keyed_cases(cases: Vec<(String, Vec<String>)>, fallback: Vec<String>) -> Vec<String> {
+    if cases.is_empty() {
+        return fallback;
+    }
+    let mut lines = vec!["c.keys.map(_.toList) match {".to_string()];
+    for (key, statement) in cases {
+        match statement.len() {
+            1 => lines.push(format!("  case Some(List({key})) => {}", statement[0])),
+            _ => {
+                lines.push(format!("  case Some(List({key})) =>"));
+                lines.extend(indent(statement, 2));
+            }
+        }
+    }
+    match fallback.len() {
+        1 => lines.push(format!("  case _ => {}", fallback[0])),
+        _ => {
+            lines.push("  case _ =>".to_string());
+            lines.extend(indent(fallback, 2));
+        }
+    }
+    lines.push("}".to_string());
+    lines
+}
+
+/// Returns the name of a case class parameter, with reserved words escaped with backticks, e.g. `` `type` ``.
+fn member_name(name: &Name) -> String {
+    let camel_case = name.as_camel_case();
+    match SCALA_KEYWORDS.contains(&camel_case.as_str()) {
+        true => format!("`{camel_case}`"),
+        false => camel_case,
+    }
+}
+
+/// Returns the name of a decoded value in a for comprehension. Backticks would make it a pattern matching
+/// an existing value, so reserved words and names of cursors are escaped with a trailing underscore instead.
+fn local_name(name: &Name) -> String {
+    let camel_case = name.as_camel_case();
+    match SCALA_KEYWORDS.contains(&camel_case.as_str())
+        || CURSOR_NAMES.contains(&camel_case.as_str())
+    {
+        true => format!("{camel_case}_"),
+        false => camel_case,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generate_scala_code, ScalaFlavor};
+    use crate as rdc;
+    use crate::ir::{IntermediateRepresentation, TypeTarget};
+    use crate::{rdc_polymorphic, RDC};
+    use std::collections::HashMap;
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct User {
+        #[serde(rename = "user-name")]
+        name: String,
+        nickname: Option<String>,
+        #[serde(rename = "type")]
+        kind: Role,
+        limits: HashMap<Role, Option<i64>>,
+        pet: Box<dyn Pet>,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Role {
+        Admin,
+        #[serde(rename = "member")]
+        Member,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Circle {
+        radius: f64,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Shape {
+        Point,
+        Circle(Circle),
+        Line(f64, Option<f64>),
+        Rect { width: f64, height: Option<f64> },
+    }
+
+    trait Pet {}
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Dog {
+        name: String,
+    }
+    impl Pet for Dog {}
+
+    rdc_polymorphic!(dyn Pet, tag = "kind" => Dog);
+
+    #[test]
+    fn generate_circe_classes_and_enums() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<User>();
+        let code = generate_scala_code(&ir, ScalaFlavor::Circe, "com.example.api").unwrap();
+        println!("{code}");
+        assert!(code.starts_with(
+            "// Code generated by rdc. DO NOT EDIT.\n\npackage com.example.api\n\nimport io.circe.syntax._\n"
+        ));
+        assert!(code.contains("  limits: Map[Role, Option[Long]],\n"));
+        assert!(code.contains(
+            "    for {\n      name <- c.downField(\"user-name\").as[String]\n      nickname <- c.downField(\"nickname\").as[Option[String]]\n      kind <- c.downField(\"type\").as[Role]\n"
+        ));
+        assert!(code.contains("    } yield User(name, nickname, kind, limits, pet)\n"));
+        assert!(code.contains("      \"user-name\" -> v.name.asJson,\n"));
+        assert!(code.contains("final case class Dog(\n  name: String\n) extends Pet\n"));
+        assert!(code.contains("sealed abstract class Role(val jsonName: String)\n"));
+        assert!(code.contains("  case object Member extends Role(\"member\")\n"));
+        assert!(code.contains(
+            "  implicit val keyDecoder: KeyDecoder[Role] = KeyDecoder.instance(fromJson)\n"
+        ));
+    }
+
+    #[test]
+    fn generate_circe_sealed_traits() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Shape>();
+        ir.add::<Box<dyn Pet>>();
+        let code = generate_scala_code(&ir, ScalaFlavor::Circe, "").unwrap();
+        println!("{code}");
+        assert!(code.starts_with("// Code generated by rdc. DO NOT EDIT.\n\nimport "));
+        assert!(code.contains("case object ShapePoint extends Shape\n"));
+        assert!(code.contains(
+            "final case class ShapeLine(\n  value0: Double,\n  value1: Option[Double]\n) extends Shape\n"
+        ));
+        assert!(code.contains(
+            "      case Some(List(\"Circle\")) => c.downField(\"Circle\").as[Circle].map(ShapeCircle(_))\n"
+        ));
+        assert!(code.contains(
+            "      case Some(List(\"Line\")) =>\n        val value = c.downField(\"Line\")\n        for {\n          value0 <- value.downN(0).as[Double]\n"
+        ));
+        assert!(code.contains("          case \"Point\" => Right(ShapePoint)\n"));
+        assert!(code.contains(
+            "    case v: ShapeRect => Json.obj(\"Rect\" -> Json.obj(\"width\" -> v.width.asJson, \"height\" -> v.height.asJson))\n"
+        ));
+        assert!(code.contains("      case \"Dog\" => c.as[Dog]\n"));
+        assert!(code.contains(
+            "    case v: Dog => v.asJson.mapObject((\"kind\" -> Json.fromString(\"Dog\")) +: _)\n"
+        ));
+    }
+
+    #[test]
+    fn generate_jackson_classes() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<User>();
+        let code = generate_scala_code(&ir, ScalaFlavor::Jackson, "com.example.api");
+        let error = code.err().unwrap();
+        assert_eq!(
+            error.message(),
+            "`Role` can not be a key of a JSON object in Scala"
+        );
+
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Dog>();
+        ir.add::<Role>();
+        ir.add::<Box<dyn Pet>>();
+        let code = generate_scala_code(&ir, ScalaFlavor::Jackson, "com.example.api").unwrap();
+        assert!(code.contains("  @JsonProperty(\"name\") name: String\n) extends Pet\n"));
+        assert!(code.contains(
+            "@JsonDeserialize(using = classOf[Role.Deserializer])\nsealed abstract class Role(val jsonName: String) {\n  @JsonValue def toJson: String = jsonName\n}\n"
+        ));
+        assert!(code.contains(
+            "@JsonSubTypes(Array(\n  new JsonSubTypes.Type(value = classOf[Dog], name = \"Dog\")\n))\nsealed trait Pet\n"
+        ));
+        assert!(!code.contains("io.circe"));
+
+        ir.add::<Shape>();
+        let error = generate_scala_code(&ir, ScalaFlavor::Jackson, "")
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.message(),
+            "Data enum `Shape` can not be read by Jackson, it is only supported with the circe flavor"
+        );
+    }
+}
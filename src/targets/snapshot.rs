@@ -0,0 +1,157 @@
+use crate::errors::Error;
+use crate::targets::java::JavaClass;
+use std::path::Path;
+
+/// Name of the environment variable that makes snapshot assertions rewrite snapshots instead of comparing them.
+pub const UPDATE_SNAPSHOTS_ENV: &str = "RDC_UPDATE_SNAPSHOTS";
+
+/// This trait is implemented by generated files that can be stored as snapshots.
+pub trait SnapshotFile {
+    fn file_name(&self) -> String;
+    fn contents(&self) -> &str;
+}
+
+impl SnapshotFile for JavaClass {
+    fn file_name(&self) -> String {
+        format!("{}.java", self.name())
+    }
+
+    fn contents(&self) -> &str {
+        self.code()
+    }
+}
+
+/// This function compares generated files with snapshots stored in the directory and panics if they differ.
+/// No compiler or runtime of the target language is needed, so it is a cheap way to catch codegen regressions.
+///
+/// When the `RDC_UPDATE_SNAPSHOTS` environment variable is set, snapshots are rewritten instead.
+///
+/// Example:
+/// ```no_run
+/// use rdc::{rdc_java, RDC};
+/// use rdc::targets::snapshot::assert_snapshot;
+///
+/// #[derive(RDC)]
+/// struct MyStruct {
+///     field1: String,
+/// }
+///
+/// let classes = rdc_java!(MyStruct).unwrap();
+/// assert_snapshot(&classes, "tests/snapshots/my_struct");
+/// ```
+pub fn assert_snapshot<F: SnapshotFile, P: AsRef<Path>>(files: &[F], directory: P) {
+    if let Err(e) = check_snapshot(files, directory) {
+        panic!("{}", e.message());
+    }
+}
+
+/// This function works like `assert_snapshot`, but returns an error instead of panicking.
+pub fn check_snapshot<F: SnapshotFile, P: AsRef<Path>>(
+    files: &[F],
+    directory: P,
+) -> Result<(), Error> {
+    let update = std::env::var(UPDATE_SNAPSHOTS_ENV)
+        .map(|v| !v.is_empty() && v != "0")
+        .unwrap_or(false);
+    if update {
+        write_snapshot(files, directory.as_ref())
+    } else {
+        compare_snapshot(files, directory.as_ref())
+    }
+}
+
+fn write_snapshot<F: SnapshotFile>(files: &[F], directory: &Path) -> Result<(), Error> {
+    if directory.exists() {
+        std::fs::remove_dir_all(directory)
+            .map_err(|_| Error::new("Failed to remove snapshot directory"))?;
+    }
+    std::fs::create_dir_all(directory)
+        .map_err(|_| Error::new("Failed to create snapshot directory"))?;
+    for file in files {
+        std::fs::write(directory.join(file.file_name()), file.contents())
+            .map_err(|_| Error::new("Failed to write snapshot file"))?;
+    }
+    Ok(())
+}
+
+fn compare_snapshot<F: SnapshotFile>(files: &[F], directory: &Path) -> Result<(), Error> {
+    let mut problems = Vec::new();
+    for file in files {
+        let file_name = file.file_name();
+        match std::fs::read_to_string(directory.join(&file_name)) {
+            Ok(expected) => {
+                if let Some(problem) = first_difference(&expected, file.contents()) {
+                    problems.push(format!("{file_name}: {problem}"));
+                }
+            }
+            Err(_) => problems.push(format!("{file_name}: missing snapshot")),
+        }
+    }
+
+    let generated = files.iter().map(|f| f.file_name()).collect::<Vec<String>>();
+    if let Ok(entries) = std::fs::read_dir(directory) {
+        for entry in entries.flatten() {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if !generated.contains(&file_name) {
+                problems.push(format!("{file_name}: file is no longer generated"));
+            }
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        problems.sort();
+        Err(Error::new(&format!(
+            "Snapshot {} does not match generated code (set {UPDATE_SNAPSHOTS_ENV}=1 to update it):\n{}",
+            directory.display(),
+            problems.join("\n")
+        )))
+    }
+}
+
+fn first_difference(expected: &str, actual: &str) -> Option<String> {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut line = 1;
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (None, None) => return None,
+            (e, a) if e == a => line += 1,
+            (e, a) => {
+                return Some(format!(
+                    "line {line} differs\n  expected: {}\n  actual:   {}",
+                    e.unwrap_or("<end of file>"),
+                    a.unwrap_or("<end of file>")
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::targets::java::JavaClass;
+
+    fn classes(code: &str) -> Vec<JavaClass> {
+        vec![JavaClass::new("Test".to_string(), code.to_string())]
+    }
+
+    #[test]
+    fn snapshot_test() {
+        let directory = std::path::Path::new("target/test-tmp/snapshots/snapshot_test");
+        super::write_snapshot(&classes("class Test {\n}\n"), directory).unwrap();
+        assert!(super::compare_snapshot(&classes("class Test {\n}\n"), directory).is_ok());
+
+        let error = super::compare_snapshot(&classes("class Test {\n  int a;\n}\n"), directory)
+            .unwrap_err();
+        assert!(error.message().contains("Test.java: line 2 differs"));
+
+        let other = vec![JavaClass::new("Other".to_string(), String::new())];
+        let error = super::compare_snapshot(&other, directory).unwrap_err();
+        assert!(error.message().contains("Other.java: missing snapshot"));
+        assert!(error
+            .message()
+            .contains("Test.java: file is no longer generated"));
+    }
+}
@@ -0,0 +1,394 @@
+use crate::errors::Error;
+use crate::ir::{FlagsFormat, IntermediateRepresentation, Name, Struct, Type, TypeKind};
+use std::collections::HashMap;
+use std::io::Write;
+
+/// This enum describes the SQL dialect of the generated DDL.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SqlDialect {
+    #[default]
+    Postgres,
+}
+
+/// This struct contains options that control generation of `CREATE TABLE` statements.
+///
+/// Column types and primary keys set here take precedence over the ones from `#[rdc(sql_type = ...)]` and `#[rdc(primary_key)]`,
+/// and are meant for types which can not be annotated.
+/// Type and field names are Rust names, e.g. `("Invoice", "invoice_number")`.
+///
+/// Example:
+/// ```rust
+/// use rdc::targets::sql::{SqlConfig, SqlDialect};
+///
+/// let config = SqlConfig::new()
+///     .with_table_name("Invoice", "staging_invoices")
+///     .with_column_type("Invoice", "total", "NUMERIC(12, 2)")
+///     .with_primary_key("Invoice", &["invoice_number"]);
+/// assert_eq!(config.dialect(), SqlDialect::Postgres);
+/// ```
+#[derive(Default)]
+pub struct SqlConfig {
+    dialect: SqlDialect,
+    table_names: HashMap<String, String>,
+    column_types: HashMap<(String, String), String>,
+    primary_keys: HashMap<String, Vec<String>>,
+}
+
+impl SqlConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_dialect(mut self, dialect: SqlDialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// Sets the name of the table of a struct. Tables are named after their structs in snake case by default.
+    pub fn with_table_name<S: Into<String>, T: Into<String>>(
+        mut self,
+        type_name: S,
+        table_name: T,
+    ) -> Self {
+        self.table_names.insert(type_name.into(), table_name.into());
+        self
+    }
+
+    /// Sets the SQL type of a column, e.g. `NUMERIC(12, 2)` for an amount or `JSONB` for a nested struct.
+    /// Nullability still follows the field, so the type should not contain `NOT NULL`.
+    pub fn with_column_type<S: Into<String>, F: Into<String>, C: Into<String>>(
+        mut self,
+        type_name: S,
+        field_name: F,
+        column_type: C,
+    ) -> Self {
+        self.column_types
+            .insert((type_name.into(), field_name.into()), column_type.into());
+        self
+    }
+
+    /// Sets the fields making up the primary key of the table of a struct, instead of the ones marked with `#[rdc(primary_key)]`.
+    pub fn with_primary_key<S: Into<String>>(mut self, type_name: S, field_names: &[&str]) -> Self {
+        self.primary_keys.insert(
+            type_name.into(),
+            field_names.iter().map(|f| f.to_string()).collect(),
+        );
+        self
+    }
+
+    pub fn dialect(&self) -> SqlDialect {
+        self.dialect
+    }
+
+    pub fn table_name(&self, type_name: &str) -> Option<&str> {
+        self.table_names.get(type_name).map(|t| t.as_str())
+    }
+
+    pub fn column_type(&self, type_name: &str, field_name: &str) -> Option<&str> {
+        self.column_types
+            .get(&(type_name.to_string(), field_name.to_string()))
+            .map(|c| c.as_str())
+    }
+
+    pub fn primary_key(&self, type_name: &str) -> Option<&[String]> {
+        self.primary_keys.get(type_name).map(|k| k.as_slice())
+    }
+}
+
+/// This function generates a `CREATE TABLE` statement for every struct in the IR.
+///
+/// Columns are named after the fields in snake case and are `NOT NULL` unless the field is an `Option`.
+/// Fields of scalar types and unit enums are mapped to column types of the dialect, and the remaining fields
+/// need a column type set with `#[rdc(sql_type = ...)]` or in the config, e.g. `JSONB` for a nested struct.
+/// It fails if such a field has no column type, or if a primary key names a field that is not in the struct or is optional.
+///
+/// Example:
+/// ```rust
+/// use rdc::ir::{IntermediateRepresentation, TypeTarget};
+/// use rdc::targets::sql::{generate_sql_ddl, SqlConfig};
+/// use rdc::RDC;
+///
+/// #[derive(RDC)]
+/// struct Invoice {
+///     invoice_number: String,
+///     paid_at: Option<i64>,
+/// }
+///
+/// let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+/// ir.add::<Invoice>();
+/// let config = SqlConfig::new().with_primary_key("Invoice", &["invoice_number"]);
+/// let ddl = generate_sql_ddl(&ir, &config).unwrap();
+/// assert_eq!(
+///     ddl,
+///     "CREATE TABLE \"invoice\" (\n    \"invoice_number\" TEXT NOT NULL,\n    \"paid_at\" BIGINT,\n    PRIMARY KEY (\"invoice_number\")\n);\n"
+/// );
+/// ```
+pub fn generate_sql_ddl(
+    ir: &IntermediateRepresentation,
+    config: &SqlConfig,
+) -> Result<String, Error> {
+    ir.validate()?;
+    ir.reject_transparent_structs("SQL")?;
+    for type_name in config.primary_keys.keys() {
+        if !ir
+            .structs()
+            .iter()
+            .any(|s| s.self_type().type_name() == type_name)
+        {
+            return Err(Error::new(&format!(
+                "A primary key is set for `{type_name}`, which is not a struct in the IR"
+            )));
+        }
+    }
+    let generator = SqlGenerator { ir, config };
+    let tables = ir
+        .structs()
+        .iter()
+        .map(|s| generator.create_table(s))
+        .collect::<Result<Vec<String>, Error>>()?;
+    Ok(tables.join("\n"))
+}
+
+/// This function generates `CREATE TABLE` statements and writes them to the given path.
+pub fn write_sql_ddl(
+    ir: &IntermediateRepresentation,
+    config: &SqlConfig,
+    file_path: &str,
+) -> Result<(), Error> {
+    let ddl = generate_sql_ddl(ir, config)?;
+    if let Some(parent) = std::path::Path::new(file_path).parent() {
+        std::fs::create_dir_all(parent).map_err(|_| Error::new("Failed to create directory"))?;
+    }
+    let mut file =
+        std::fs::File::create(file_path).map_err(|_| Error::new("Failed to create file"))?;
+    file.write_all(ddl.as_bytes())
+        .map_err(|_| Error::new("Failed to write to file"))
+}
+
+struct SqlGenerator<'a> {
+    ir: &'a IntermediateRepresentation,
+    config: &'a SqlConfig,
+}
+
+impl SqlGenerator<'_> {
+    fn create_table(&self, s: &Struct) -> Result<String, Error> {
+        let type_name = s.self_type().type_name();
+        let table_name = match self.config.table_name(type_name) {
+            Some(table_name) => table_name.to_string(),
+            None => Name::from_pascal_case(type_name).as_snake_case(),
+        };
+        let mut lines = Vec::new();
+        for f in s.fields() {
+            let field_name = f.name().as_snake_case();
+            let (column_type, nullable) = match f.field_type().kind() {
+                TypeKind::Option(inner) => (inner.as_ref(), true),
+                _ => (f.field_type(), false),
+            };
+            let column_type = match self
+                .config
+                .column_type(type_name, &field_name)
+                .or(f.column_type())
+            {
+                Some(column_type) => column_type.to_string(),
+                None => self.column_type(column_type).ok_or_else(|| {
+                    Error::new(&format!(
+                        "`{type_name}.{field_name}` of type `{}` has no column type, so it has to be set with `#[rdc(sql_type)]` or in the config",
+                        f.field_type().type_name()
+                    ))
+                })?,
+            };
+            let constraint = if nullable { "" } else { " NOT NULL" };
+            lines.push(format!(
+                "    {} {column_type}{constraint}",
+                quote_identifier(&field_name)
+            ));
+        }
+        let primary_key = match self.config.primary_key(type_name) {
+            Some(primary_key) => primary_key.to_vec(),
+            None => s
+                .fields()
+                .iter()
+                .filter(|f| f.is_primary_key())
+                .map(|f| f.name().as_snake_case())
+                .collect(),
+        };
+        if !primary_key.is_empty() {
+            for key in &primary_key {
+                let field = s
+                    .fields()
+                    .iter()
+                    .find(|f| &f.name().as_snake_case() == key)
+                    .ok_or_else(|| {
+                        Error::new(&format!(
+                            "The primary key of `{type_name}` contains `{key}`, which is not a field of the struct"
+                        ))
+                    })?;
+                if !field.is_required() {
+                    return Err(Error::new(&format!(
+                        "The primary key of `{type_name}` contains `{key}`, which is optional"
+                    )));
+                }
+            }
+            let columns = primary_key
+                .iter()
+                .map(|key| quote_identifier(key))
+                .collect::<Vec<String>>();
+            lines.push(format!("    PRIMARY KEY ({})", columns.join(", ")));
+        }
+        Ok(format!(
+            "CREATE TABLE {} (\n{}\n);\n",
+            quote_identifier(&table_name),
+            lines.join(",\n")
+        ))
+    }
+
+    /// Returns the column type of a field of a flat struct, or `None` if the type has no column representation.
+    fn column_type(&self, t: &Type) -> Option<String> {
+        match self.config.dialect() {
+            SqlDialect::Postgres => self.postgres_type(t),
+        }
+    }
+
+    fn postgres_type(&self, t: &Type) -> Option<String> {
+        let name = match t.kind() {
+            TypeKind::UnsignedLong => "NUMERIC(20)",
+            TypeKind::Named => match t.type_name().as_str() {
+                "Boolean" => "BOOLEAN",
+                "Byte" | "Short" => "SMALLINT",
+                "Integer" => "INTEGER",
+                "Long" => "BIGINT",
+                "Float" => "REAL",
+                "Double" => "DOUBLE PRECISION",
+                "String" => "TEXT",
+                "com.fasterxml.jackson.databind.JsonNode" => "JSONB",
+                name => {
+                    let e = self
+                        .ir
+                        .enums()
+                        .iter()
+                        .find(|e| e.self_type().type_name() == name)?;
+                    match e.flags() {
+                        Some(FlagsFormat::Bits) => "BIGINT",
+                        Some(FlagsFormat::Names) => "TEXT[]",
+                        None => "TEXT",
+                    }
+                }
+            },
+            _ => return None,
+        };
+        Some(name.to_string())
+    }
+}
+
+/// Identifiers are always quoted, so that columns named after fields like `order` or `user` do not clash with keywords.
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as rdc;
+    use crate::ir::TypeTarget;
+    use crate::RDC;
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct OrderLine {
+        order_id: i64,
+        line_number: i32,
+        sku: String,
+        quantity: u64,
+        discount: Option<f64>,
+        status: LineStatus,
+        address: Address,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum LineStatus {
+        Open,
+        Shipped,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Address {
+        user: String,
+    }
+
+    #[test]
+    fn create_tables() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<OrderLine>();
+        let config = SqlConfig::new()
+            .with_table_name("OrderLine", "staging_order_lines")
+            .with_column_type("OrderLine", "address", "JSONB")
+            .with_column_type("OrderLine", "discount", "NUMERIC(5, 2)")
+            .with_primary_key("OrderLine", &["order_id", "line_number"]);
+        let ddl = generate_sql_ddl(&ir, &config).unwrap();
+        println!("{ddl}");
+        assert!(ddl.contains(
+            "CREATE TABLE \"staging_order_lines\" (\n    \"order_id\" BIGINT NOT NULL,\n    \"line_number\" INTEGER NOT NULL,\n"
+        ));
+        assert!(ddl.contains("    \"quantity\" NUMERIC(20) NOT NULL,\n"));
+        assert!(ddl.contains("    \"discount\" NUMERIC(5, 2),\n"));
+        assert!(ddl.contains("    \"status\" TEXT NOT NULL,\n"));
+        assert!(ddl.contains("    \"address\" JSONB NOT NULL,\n"));
+        assert!(ddl.contains("    PRIMARY KEY (\"order_id\", \"line_number\")\n);\n"));
+        assert!(ddl.contains("CREATE TABLE \"address\" (\n    \"user\" TEXT NOT NULL\n);\n"));
+    }
+
+    #[test]
+    fn nested_structs_need_column_types() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<OrderLine>();
+        let error = generate_sql_ddl(&ir, &SqlConfig::new()).err().unwrap();
+        assert_eq!(
+            error.message(),
+            "`OrderLine.address` of type `Address` has no column type, so it has to be set with `#[rdc(sql_type)]` or in the config"
+        );
+    }
+
+    #[test]
+    fn optional_primary_keys_are_rejected() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<OrderLine>();
+        let config = SqlConfig::new()
+            .with_column_type("OrderLine", "address", "JSONB")
+            .with_primary_key("OrderLine", &["discount"]);
+        let error = generate_sql_ddl(&ir, &config).err().unwrap();
+        assert_eq!(
+            error.message(),
+            "The primary key of `OrderLine` contains `discount`, which is optional"
+        );
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Shipment {
+        #[rdc(primary_key)]
+        order_id: i64,
+        #[rdc(primary_key)]
+        shipment_number: i32,
+        #[rdc(sql_type = "JSONB")]
+        address: Address,
+        #[rdc(sql_type = "NUMERIC(8, 3)")]
+        weight: Option<f64>,
+    }
+
+    #[test]
+    fn column_types_and_primary_keys_from_attributes() {
+        let mut ir = IntermediateRepresentation::new(TypeTarget::Java);
+        ir.add::<Shipment>();
+        let ddl = generate_sql_ddl(&ir, &SqlConfig::new()).unwrap();
+        assert!(ddl.contains(
+            "CREATE TABLE \"shipment\" (\n    \"order_id\" BIGINT NOT NULL,\n    \"shipment_number\" INTEGER NOT NULL,\n    \"address\" JSONB NOT NULL,\n    \"weight\" NUMERIC(8, 3),\n    PRIMARY KEY (\"order_id\", \"shipment_number\")\n);\n"
+        ));
+        let config = SqlConfig::new()
+            .with_column_type("Shipment", "weight", "REAL")
+            .with_primary_key("Shipment", &["order_id"]);
+        let ddl = generate_sql_ddl(&ir, &config).unwrap();
+        assert!(ddl.contains("    \"weight\" REAL,\n    PRIMARY KEY (\"order_id\")\n"));
+    }
+}
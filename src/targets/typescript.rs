@@ -0,0 +1,500 @@
+use crate::errors::Error;
+use crate::ir::{
+    DataEnum, DataEnumVariant, Enum, FlagsFormat, IntermediateRepresentation, Polymorphic, Struct,
+    Type, TypeKind,
+};
+use crate::targets::json::Json;
+use std::collections::{BTreeSet, HashSet};
+use std::io::Write;
+
+/// This is a struct that represents a generated TypeScript module with a single exported type.
+pub struct TypeScriptFile {
+    name: String,
+    code: String,
+}
+
+impl TypeScriptFile {
+    pub fn new(name: String, code: String) -> Self {
+        TypeScriptFile { name, code }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+}
+
+/// This function generates TypeScript types describing the JSON of the types in the IR, one module per type.
+///
+/// Structs are translated to interfaces with properties named after their JSON names,
+/// unit enums to unions of string literals, and data enums to unions matching serde's external tagging,
+/// e.g. `"Point" | { Circle: Circle } | { Line: [number, number] }`, which can be narrowed with `in`.
+/// Polymorphic types become unions of their variants, either wrapped in an object or intersected with the tag.
+/// Optional fields may be missing or `null`. All numbers are `number`, so `i64` and `u64` values
+/// above `Number.MAX_SAFE_INTEGER` lose precision when parsed with `JSON.parse`.
+/// Properties holding such integers get a doc comment saying so.
+///
+/// Example:
+/// ```rust
+/// use rdc::{rdc_typescript, RDC};
+///
+/// #[derive(RDC)]
+/// struct User {
+///     name: String,
+///     nickname: Option<String>,
+/// }
+///
+/// let files = rdc_typescript!(User).unwrap();
+/// assert!(files[0].code().contains("export interface User {\n  name: string;\n  nickname?: string | null;\n}"));
+/// ```
+pub fn generate_typescript_code(
+    ir: &IntermediateRepresentation,
+) -> Result<Vec<TypeScriptFile>, Error> {
+    ir.validate()?;
+    let generator = TypeScriptGenerator::new(ir);
+    let mut files = Vec::new();
+    for s in ir.structs() {
+        files.push(generator.generate_interface(s)?);
+    }
+    for e in ir.enums() {
+        files.push(generator.generate_enum(e));
+    }
+    for de in ir.data_enums() {
+        files.push(generator.generate_data_enum(de)?);
+    }
+    for p in ir.polymorphics() {
+        files.push(generator.generate_polymorphic(p)?);
+    }
+    Ok(files)
+}
+
+/// This function writes generated TypeScript modules to the provided directory, e.g. `src/api`.
+/// Modules import each other with relative paths, so they have to stay in the same directory.
+pub fn write_typescript(files: &[TypeScriptFile], directory: &str) -> Result<(), Error> {
+    std::fs::create_dir_all(directory).map_err(|_| Error::new("Failed to create directory"))?;
+    for ts_file in files {
+        let mut file_path = std::path::PathBuf::from(directory);
+        file_path.push(format!("{}.ts", ts_file.name()));
+        let mut file =
+            std::fs::File::create(file_path).map_err(|_| Error::new("Failed to create file"))?;
+        file.write_all(ts_file.code().as_bytes())
+            .map_err(|_| Error::new("Failed to write to file"))?;
+    }
+    Ok(())
+}
+
+struct TypeScriptGenerator {
+    types: HashSet<String>,
+    string_enums: HashSet<String>,
+}
+
+impl TypeScriptGenerator {
+    fn new(ir: &IntermediateRepresentation) -> Self {
+        let types = ir
+            .structs()
+            .iter()
+            .map(|s| s.self_type())
+            .chain(ir.enums().iter().map(|e| e.self_type()))
+            .chain(ir.data_enums().iter().map(|de| de.self_type()))
+            .chain(ir.polymorphics().iter().map(|p| p.self_type()))
+            .map(|t| t.type_name().clone())
+            .collect();
+        let string_enums = ir
+            .enums()
+            .iter()
+            .filter(|e| e.flags().is_none())
+            .map(|e| e.self_type().type_name().clone())
+            .collect();
+        Self {
+            types,
+            string_enums,
+        }
+    }
+
+    fn generate_interface(&self, s: &Struct) -> Result<TypeScriptFile, Error> {
+        let name = s.self_type().type_name();
+        let mut imports = BTreeSet::new();
+        if let (true, [field]) = (s.is_transparent(), s.fields().as_slice()) {
+            let alias = self.type_name(field.field_type(), &mut imports)?;
+            return Ok(render_file(
+                name,
+                imports,
+                vec![format!("export type {name} = {alias};")],
+            ));
+        }
+        let mut lines = vec![format!("export interface {name} {{")];
+        for field in s.fields() {
+            if is_wide_integer(field.field_type()) {
+                lines.push(
+                    "  /** Integers above Number.MAX_SAFE_INTEGER lose precision when parsed with JSON.parse. */"
+                        .to_string(),
+                );
+            }
+            let readonly = if field.is_readonly() { "readonly " } else { "" };
+            let property = self.property(field.json_name(), field.field_type(), &mut imports)?;
+            lines.push(format!("  {readonly}{property};"));
+        }
+        lines.push("}".to_string());
+        Ok(render_file(name, imports, lines))
+    }
+
+    fn generate_enum(&self, e: &Enum) -> TypeScriptFile {
+        let name = e.self_type().type_name();
+        let declaration = match e.flags() {
+            Some(FlagsFormat::Bits) => format!("export type {name} = number;"),
+            Some(FlagsFormat::Names) => format!("export type {name} = string;"),
+            None => {
+                let literals = e
+                    .variants()
+                    .iter()
+                    .map(|v| Json::string(v.json_name()).render())
+                    .collect::<Vec<String>>();
+                format!("export type {name} = {};", union(literals))
+            }
+        };
+        render_file(name, BTreeSet::new(), vec![declaration])
+    }
+
+    fn generate_data_enum(&self, de: &DataEnum) -> Result<TypeScriptFile, Error> {
+        let name = de.self_type().type_name();
+        let mut imports = BTreeSet::new();
+        let mut variants = Vec::new();
+        for v in de.variants() {
+            let key = property_key(v.json_name());
+            variants.push(match v {
+                DataEnumVariant::Unit { json_name, .. } => Json::string(json_name).render(),
+                DataEnumVariant::Tuple { fields, .. } if fields.len() == 1 => {
+                    format!("{{ {key}: {} }}", self.type_name(&fields[0], &mut imports)?)
+                }
+                DataEnumVariant::Tuple { fields, .. } => {
+                    let elements = fields
+                        .iter()
+                        .map(|t| self.type_name(t, &mut imports))
+                        .collect::<Result<Vec<String>, Error>>()?;
+                    format!("{{ {key}: [{}] }}", elements.join(", "))
+                }
+                DataEnumVariant::Object { fields, .. } => {
+                    let properties = fields
+                        .iter()
+                        .map(|f| self.property(f.json_name(), f.field_type(), &mut imports))
+                        .collect::<Result<Vec<String>, Error>>()?;
+                    let body = match properties.is_empty() {
+                        true => "{}".to_string(),
+                        false => format!("{{ {} }}", properties.join("; ")),
+                    };
+                    format!("{{ {key}: {body} }}")
+                }
+            });
+        }
+        Ok(render_file(
+            name,
+            imports,
+            union_declaration(name, variants),
+        ))
+    }
+
+    fn generate_polymorphic(&self, p: &Polymorphic) -> Result<TypeScriptFile, Error> {
+        let name = p.self_type().type_name();
+        let mut imports = BTreeSet::new();
+        let mut variants = Vec::new();
+        for v in p.variants() {
+            let variant_type = self.type_name(v.variant_type(), &mut imports)?;
+            variants.push(match p.tag() {
+                Some(tag) => format!(
+                    "({{ {}: {} }} & {variant_type})",
+                    property_key(tag),
+                    Json::string(v.json_name()).render()
+                ),
+                None => format!("{{ {}: {variant_type} }}", property_key(v.json_name())),
+            });
+        }
+        Ok(render_file(
+            name,
+            imports,
+            union_declaration(name, variants),
+        ))
+    }
+
+    /// Returns a property of an object type. Optional properties may be missing, because serde accepts missing options.
+    fn property(
+        &self,
+        json_name: &str,
+        t: &Type,
+        imports: &mut BTreeSet<String>,
+    ) -> Result<String, Error> {
+        let optional = if matches!(t.kind(), TypeKind::Option(_)) {
+            "?"
+        } else {
+            ""
+        };
+        Ok(format!(
+            "{}{optional}: {}",
+            property_key(json_name),
+            self.type_name(t, imports)?
+        ))
+    }
+
+    /// Returns the TypeScript type of a value, adding generated types it refers to to the imports.
+    fn type_name(&self, t: &Type, imports: &mut BTreeSet<String>) -> Result<String, Error> {
+        match t.kind() {
+            TypeKind::List(element) => {
+                let element = self.type_name(element, imports)?;
+                Ok(match element.contains(' ') {
+                    true => format!("Array<{element}>"),
+                    false => format!("{element}[]"),
+                })
+            }
+            TypeKind::Map(key, value) => {
+                let value = self.type_name(value, imports)?;
+                // JSON keys are always strings, only string enums narrow them further.
+                if self.string_enums.contains(key.type_name()) {
+                    let key = self.type_name(key, imports)?;
+                    Ok(format!("Partial<Record<{key}, {value}>>"))
+                } else {
+                    Ok(format!("Record<string, {value}>"))
+                }
+            }
+            TypeKind::Option(inner) => Ok(format!("{} | null", self.type_name(inner, imports)?)),
+            TypeKind::UnsignedLong => Ok("number".to_string()),
+            TypeKind::Named => match t.type_name().as_str() {
+                "Byte"
+                | "Short"
+                | "Integer"
+                | "Long"
+                | "Float"
+                | "Double"
+                | "java.math.BigInteger" => Ok("number".to_string()),
+                "Boolean" => Ok("boolean".to_string()),
+                "String" => Ok("string".to_string()),
+                "com.fasterxml.jackson.databind.JsonNode" => Ok("unknown".to_string()),
+                name if self.types.contains(name) => {
+                    imports.insert(name.to_string());
+                    Ok(name.to_string())
+                }
+                name => Err(Error::new(&format!(
+                    "`{name}` has no TypeScript counterpart"
+                ))),
+            },
+        }
+    }
+}
+
+/// Returns whether a type holds integers which may not fit into a `number` exactly, e.g. `u64` or `Vec<i64>`.
+fn is_wide_integer(t: &Type) -> bool {
+    match t.kind() {
+        TypeKind::List(inner) | TypeKind::Option(inner) | TypeKind::Map(_, inner) => {
+            is_wide_integer(inner)
+        }
+        TypeKind::UnsignedLong => true,
+        TypeKind::Named => matches!(t.type_name().as_str(), "Long" | "java.math.BigInteger"),
+    }
+}
+
+/// Returns a property key, quoted unless it is a valid identifier.
+fn property_key(json_name: &str) -> String {
+    let mut chars = json_name.chars();
+    let valid_start = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$');
+    if valid_start && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$') {
+        json_name.to_string()
+    } else {
+        Json::string(json_name).render()
+    }
+}
+
+/// Returns a union of the given types, or `never` if there are none.
+fn union(types: Vec<String>) -> String {
+    match types.is_empty() {
+        true => "never".to_string(),
+        false => types.join(" | "),
+    }
+}
+
+/// Returns a type alias of a union with one variant per line.
+fn union_declaration(name: &str, variants: Vec<String>) -> Vec<String> {
+    if variants.is_empty() {
+        return vec![format!("export type {name} = never;")];
+    }
+    let mut lines = vec![format!("export type {name} =")];
+    let last = variants.len() - 1;
+    for (i, variant) in variants.into_iter().enumerate() {
+        let end = if i == last { ";" } else { "" };
+        lines.push(format!("  | {variant}{end}"));
+    }
+    lines
+}
+
+/// This function renders type-only imports of other generated modules and the declaration of a module.
+fn render_file(
+    name: &str,
+    mut imports: BTreeSet<String>,
+    declaration: Vec<String>,
+) -> TypeScriptFile {
+    imports.remove(name);
+    let mut code = String::new();
+    for import in &imports {
+        code.push_str(&format!(
+            "import type {{ {import} }} from \"./{import}\";\n"
+        ));
+    }
+    if !imports.is_empty() {
+        code.push('\n');
+    }
+    for line in declaration {
+        code.push_str(&line);
+        code.push('\n');
+    }
+    TypeScriptFile::new(name.to_string(), code)
+}
+
+/// This macro generates TypeScript modules for all the provided types and their dependencies.
+///
+/// Example:
+/// ```rust
+/// use rdc::{rdc_typescript, RDC};
+///
+/// #[derive(RDC)]
+/// enum Role {
+///     Admin,
+///     Member,
+/// }
+///
+/// let files = rdc_typescript!(Role).unwrap();
+/// assert_eq!(files[0].code(), "export type Role = \"Admin\" | \"Member\";\n");
+/// ```
+#[macro_export]
+macro_rules! rdc_typescript {
+    ($($type:ty),*) => {
+        {
+            let mut ir = $crate::ir::IntermediateRepresentation::new($crate::ir::TypeTarget::Java);
+            $(
+                ir.add::<$type>();
+            )*
+            $crate::targets::typescript::generate_typescript_code(&ir)
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as rdc;
+    use crate::{rdc_newtype, rdc_polymorphic, RDC};
+    use std::collections::HashMap;
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct User {
+        #[serde(rename = "user-name")]
+        name: String,
+        nickname: Option<String>,
+        roles: Vec<Role>,
+        limits: HashMap<Role, Option<i64>>,
+        shape: Shape,
+        pet: Box<dyn Pet>,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Role {
+        Admin,
+        #[serde(rename = "member")]
+        Member,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Circle {
+        radius: f64,
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    enum Shape {
+        Point,
+        Circle(Circle),
+        Line(f64, Option<f64>),
+        Rect { width: f64, height: Option<f64> },
+    }
+
+    trait Pet {}
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Dog {
+        name: String,
+    }
+    impl Pet for Dog {}
+
+    rdc_polymorphic!(dyn Pet, tag = "kind" => Dog);
+
+    #[test]
+    fn generate_typescript_modules() {
+        let files = rdc_typescript!(User).unwrap();
+        let file = |name: &str| files.iter().find(|f| f.name() == name).unwrap().code();
+        assert_eq!(
+            file("User"),
+            "import type { Pet } from \"./Pet\";\n\
+             import type { Role } from \"./Role\";\n\
+             import type { Shape } from \"./Shape\";\n\
+             \n\
+             export interface User {\n  \
+             \"user-name\": string;\n  \
+             nickname?: string | null;\n  \
+             roles: Role[];\n  \
+             /** Integers above Number.MAX_SAFE_INTEGER lose precision when parsed with JSON.parse. */\n  \
+             limits: Partial<Record<Role, number | null>>;\n  \
+             shape: Shape;\n  \
+             pet: Pet;\n\
+             }\n"
+        );
+        assert_eq!(file("Role"), "export type Role = \"Admin\" | \"member\";\n");
+        assert_eq!(
+            file("Shape"),
+            "import type { Circle } from \"./Circle\";\n\
+             \n\
+             export type Shape =\n  \
+             | \"Point\"\n  \
+             | { Circle: Circle }\n  \
+             | { Line: [number, number | null] }\n  \
+             | { Rect: { width: number; height?: number | null } };\n"
+        );
+        assert!(file("Pet").contains("export type Pet =\n  | ({ kind: \"Dog\" } & Dog);\n"));
+    }
+
+    #[derive(RDC)]
+    #[allow(unused)]
+    struct Batch {
+        ids: Vec<Option<String>>,
+        attributes: HashMap<String, Vec<u64>>,
+    }
+
+    #[test]
+    fn collection_types() {
+        let files = rdc_typescript!(Batch).unwrap();
+        let code = files[0].code();
+        assert!(code.contains("  ids: Array<string | null>;\n"));
+        assert!(code.contains(
+            "  /** Integers above Number.MAX_SAFE_INTEGER lose precision when parsed with JSON.parse. */\n  \
+             attributes: Record<string, number[]>;\n"
+        ));
+        assert!(!code.contains("import"));
+    }
+
+    #[allow(unused)]
+    struct Tags(HashMap<Role, String>);
+
+    rdc_newtype!(Tags => HashMap<Role, String>);
+
+    #[test]
+    fn transparent_structs() {
+        let files = rdc_typescript!(Tags).unwrap();
+        assert_eq!(
+            files[0].code(),
+            "import type { Role } from \"./Role\";\n\nexport type Tags = Partial<Record<Role, string>>;\n"
+        );
+    }
+}
@@ -0,0 +1,32 @@
+use crate::ir::{DataEnum, DataEnumVariant};
+
+/// Returns the name of the class of a data enum variant, e.g. `ShapeCircle`.
+/// It is prefixed with the name of the data enum, because a payload type is often named like the variant, e.g. `Circle`.
+/// A type of the IR named like the prefixed name still clashes with it.
+pub fn variant_class(de: &DataEnum, v: &DataEnumVariant) -> String {
+    format!(
+        "{}{}",
+        de.self_type().type_name(),
+        v.name().as_pascal_case()
+    )
+}
+
+/// Returns names of the values of a tuple variant: `value` for a single one, `value0..valueN` otherwise.
+pub fn tuple_names(count: usize) -> Vec<String> {
+    match count {
+        1 => vec!["value".to_string()],
+        _ => (0..count).map(|i| format!("value{i}")).collect(),
+    }
+}
+
+/// Indents non-empty lines by two spaces per level.
+pub fn indent(lines: Vec<String>, depth: usize) -> Vec<String> {
+    let prefix = "  ".repeat(depth);
+    lines
+        .into_iter()
+        .map(|l| match l.is_empty() {
+            true => l,
+            false => format!("{prefix}{l}"),
+        })
+        .collect()
+}